@@ -3,7 +3,9 @@
 use crate::{
     ingester_address::IngesterAddress,
     memory_size::MemorySize,
-    single_tenant::{CONFIG_AUTHZ_ENV_NAME, CONFIG_AUTHZ_FLAG},
+    single_tenant::{
+        CONFIG_AUTHZ_ENV_NAME, CONFIG_AUTHZ_FLAG, CONFIG_CST_ENV_NAME, CONFIG_CST_FLAG,
+    },
 };
 use std::{collections::HashMap, num::NonZeroUsize};
 
@@ -120,6 +122,41 @@ pub struct QuerierConfig {
         action
     )]
     pub datafusion_config: HashMap<String, String>,
+
+    /// HTTP address of the router, used to write the results of InfluxQL
+    /// `SELECT ... INTO` queries. For example:
+    ///
+    /// "http://127.0.0.1:8080"
+    ///
+    /// Single tenant routers are written to using the InfluxDB 1.x compatible
+    /// `/write` API, multi-tenant routers using the `/api/v2/write` API.
+    ///
+    /// If not specified, `SELECT ... INTO` queries are rejected.
+    #[clap(long = "router-address", env = "INFLUXDB_IOX_ROUTER_ADDRESS", action)]
+    pub router_address: Option<String>,
+
+    /// Whether the router at `--router-address` is deployed in single
+    /// tenancy mode.
+    #[clap(
+        long = CONFIG_CST_FLAG,
+        env = CONFIG_CST_ENV_NAME,
+        default_value = "false",
+    )]
+    pub single_tenant_deployment: bool,
+
+    /// gRPC address of the router, used to apply the deletes of InfluxQL
+    /// `DELETE`, `DROP MEASUREMENT` and `DROP SERIES` statements. For
+    /// example:
+    ///
+    /// "http://127.0.0.1:8081"
+    ///
+    /// If not specified, these statements are rejected.
+    #[clap(
+        long = "router-grpc-address",
+        env = "INFLUXDB_IOX_ROUTER_GRPC_ADDRESS",
+        action
+    )]
+    pub router_grpc_address: Option<String>,
 }
 
 fn parse_datafusion_config(
@@ -166,6 +203,9 @@ mod tests {
         assert_eq!(actual.num_query_threads, None);
         assert!(actual.ingester_addresses.is_empty());
         assert!(actual.datafusion_config.is_empty());
+        assert_eq!(actual.router_address, None);
+        assert!(!actual.single_tenant_deployment);
+        assert_eq!(actual.router_grpc_address, None);
     }
 
     #[test]
@@ -57,6 +57,25 @@ pub struct RouterConfig {
     )]
     pub http_request_limit: usize,
 
+    /// When a line protocol write contains lines that cannot be parsed or
+    /// written, write the remaining valid lines instead of rejecting the
+    /// whole request.
+    ///
+    /// The rejected lines and the reason for each are listed in the 400
+    /// response body, matching the InfluxDB 2.x "partial write" behaviour.
+    ///
+    /// Only lines that fail to parse, or that conflict with other lines of
+    /// the same request, are dropped. A line whose field or tag type
+    /// conflicts with the existing namespace schema still rejects the whole
+    /// request.
+    #[clap(
+        long = "partial-writes-enabled",
+        env = "INFLUXDB_IOX_PARTIAL_WRITES_ENABLED",
+        default_value = "false",
+        action
+    )]
+    pub partial_writes_enabled: bool,
+
     /// gRPC address for the router to talk with the ingesters. For
     /// example:
     ///
@@ -1,11 +1,12 @@
 //! QueryableParquetChunk for building query plan
 use std::{any::Any, sync::Arc};
 
-use data_types::{ChunkId, ChunkOrder, TransitionPartitionId};
+use data_types::{ChunkId, ChunkOrder, DeletePredicate, Tombstone, TransitionPartitionId};
 use datafusion::physical_plan::Statistics;
 use iox_query::{util::create_basic_summary, QueryChunk, QueryChunkData};
-use observability_deps::tracing::debug;
+use observability_deps::tracing::{debug, error};
 use parquet_file::{chunk::ParquetChunk, storage::ParquetStorage};
+use predicate::delete_predicate::parse_tombstone;
 use schema::{merge::SchemaMerger, sort::SortKey, Schema};
 use uuid::Uuid;
 
@@ -20,6 +21,7 @@ pub struct QueryableParquetChunk {
     sort_key: Option<SortKey>,
     order: ChunkOrder,
     stats: Arc<Statistics>,
+    delete_predicates: Vec<Arc<DeletePredicate>>,
 }
 
 impl QueryableParquetChunk {
@@ -41,9 +43,18 @@ impl QueryableParquetChunk {
             sort_key,
             order,
             stats,
+            delete_predicates: vec![],
         }
     }
 
+    /// Set the delete predicates that apply to the data of this chunk.
+    ///
+    /// Rows matching any of them are dropped from the compacted output.
+    pub fn with_delete_predicates(mut self, delete_predicates: Vec<Arc<DeletePredicate>>) -> Self {
+        self.delete_predicates = delete_predicates;
+        self
+    }
+
     /// Merge schema of the given chunks
     pub fn merge_schemas(chunks: &[Arc<dyn QueryChunk>]) -> Schema {
         let mut merger = SchemaMerger::new();
@@ -95,6 +106,10 @@ impl QueryChunk for QueryableParquetChunk {
         QueryChunkData::Parquet(self.data.parquet_exec_input())
     }
 
+    fn delete_predicates(&self) -> &[Arc<DeletePredicate>] {
+        &self.delete_predicates
+    }
+
     /// Returns chunk type
     fn chunk_type(&self) -> &str {
         "QueryableParquetChunk"
@@ -115,14 +130,41 @@ pub fn to_query_chunks(
     partition_info: &PartitionInfo,
     store: ParquetStorage,
 ) -> Vec<Arc<dyn QueryChunk>> {
+    let tombstones = parse_tombstones(partition_info);
+
     files
         .iter()
         .map(|file| {
-            Arc::new(to_queryable_parquet_chunk(
-                file,
-                partition_info,
-                store.clone(),
-            )) as _
+            let delete_predicates = tombstones
+                .iter()
+                .filter(|(tombstone, _)| tombstone.applies_to(file.file.max_l0_created_at))
+                .map(|(_, pred)| Arc::clone(pred))
+                .collect();
+
+            Arc::new(
+                to_queryable_parquet_chunk(file, partition_info, store.clone())
+                    .with_delete_predicates(delete_predicates),
+            ) as _
+        })
+        .collect()
+}
+
+/// Parse the tombstones of the partition's table, skipping (and logging) invalid ones.
+fn parse_tombstones(partition_info: &PartitionInfo) -> Vec<(&Tombstone, Arc<DeletePredicate>)> {
+    partition_info
+        .tombstones
+        .iter()
+        .filter_map(|t| match parse_tombstone(t) {
+            Ok(pred) => Some((t, Arc::new(pred))),
+            Err(e) => {
+                error!(
+                    %e,
+                    tombstone_id = t.id.get(),
+                    partition_id = partition_info.partition_id.get(),
+                    "invalid tombstone"
+                );
+                None
+            }
         })
         .collect()
 }
@@ -3,7 +3,7 @@
 use std::sync::Arc;
 
 use data_types::{
-    NamespaceId, PartitionHashId, PartitionId, PartitionKey, Table, TableSchema,
+    NamespaceId, PartitionHashId, PartitionId, PartitionKey, Table, TableSchema, Tombstone,
     TransitionPartitionId,
 };
 use schema::sort::SortKey;
@@ -34,6 +34,9 @@ pub struct PartitionInfo {
 
     /// partition_key
     pub partition_key: PartitionKey,
+
+    /// Tombstones (predicate deletes) of the table, ordered by ID
+    pub tombstones: Vec<Tombstone>,
 }
 
 impl PartitionInfo {
@@ -36,6 +36,7 @@ impl PartitionInfoBuilder {
                 table_schema,
                 sort_key: None,
                 partition_key,
+                tombstones: vec![],
             },
         }
     }
@@ -1,6 +1,9 @@
 use arrow_util::assert_batches_sorted_eq;
 use compactor_test_utils::{format_files, list_object_store, TestSetup};
-use data_types::{CompactionLevel, ParquetFile, PartitionId};
+use data_types::{
+    CompactionLevel, DeleteExpr, DeletePredicate, Op, ParquetFile, PartitionId, Scalar,
+    TimestampRange,
+};
 
 mod layouts;
 
@@ -181,6 +184,62 @@ async fn test_compact_target_level() {
     );
 }
 
+#[tokio::test]
+async fn test_compact_applies_tombstones() {
+    test_helpers::maybe_start_logging();
+
+    // Create a test setup with 6 files
+    let setup = TestSetup::builder()
+        .await
+        .with_files()
+        .await
+        // Ensure we have enough resource to compact the files
+        .with_max_num_files_per_plan(10)
+        .with_min_num_l1_files_to_compact(2)
+        .build()
+        .await;
+
+    // delete all VT rows. The tombstone is recorded after all files were persisted, so it
+    // applies to every one of them.
+    setup
+        .table
+        .create_tombstone(&DeletePredicate {
+            range: TimestampRange::new(i64::MIN, i64::MAX),
+            exprs: vec![DeleteExpr::new(
+                "tag1".to_string(),
+                Op::Eq,
+                Scalar::String("VT".to_string()),
+            )],
+        })
+        .await;
+
+    // compact
+    setup.run_compact().await;
+
+    // verify the VT rows are gone from the compacted output
+    let files = setup.list_by_table_not_to_delete().await;
+    let mut batches = vec![];
+    for file in files {
+        batches.extend(setup.read_parquet_file(file).await);
+    }
+    assert_batches_sorted_eq!(
+        [
+            "+-----------+------+------+------+-----------------------------+",
+            "| field_int | tag1 | tag2 | tag3 | time                        |",
+            "+-----------+------+------+------+-----------------------------+",
+            "| 1500      | WA   |      |      | 1970-01-01T00:00:00.000008Z |",
+            "| 1601      |      | PA   | 15   | 1970-01-01T00:00:00.000030Z |",
+            "| 210       |      | OH   | 21   | 1970-01-01T00:00:00.000136Z |",
+            "| 22        |      | OH   | 21   | 1970-01-01T00:00:00.000036Z |",
+            "| 270       | UT   |      |      | 1970-01-01T00:00:00.000025Z |",
+            "| 70        | UT   |      |      | 1970-01-01T00:00:00.000020Z |",
+            "| 99        | OR   |      |      | 1970-01-01T00:00:00.000012Z |",
+            "+-----------+------+------+------+-----------------------------+",
+        ],
+        &batches
+    );
+}
+
 #[tokio::test]
 async fn test_compact_large_overlapes() {
     test_helpers::maybe_start_logging();
@@ -639,6 +639,7 @@ impl<const WITH_FILES: bool> TestSetupBuilder<WITH_FILES> {
             table_schema: Arc::new(self.table.catalog_schema().await),
             sort_key: self.partition.partition.sort_key(),
             partition_key: self.partition.partition.partition_key.clone(),
+            tombstones: self.table.list_tombstones().await,
         });
 
         TestSetup {
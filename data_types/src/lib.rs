@@ -162,6 +162,27 @@ impl std::fmt::Display for TableId {
     }
 }
 
+/// Unique ID for a `Tombstone`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, sqlx::Type)]
+#[sqlx(transparent)]
+pub struct TombstoneId(i64);
+
+#[allow(missing_docs)]
+impl TombstoneId {
+    pub const fn new(v: i64) -> Self {
+        Self(v)
+    }
+    pub fn get(&self) -> i64 {
+        self.0
+    }
+}
+
+impl std::fmt::Display for TombstoneId {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 /// A sequence number from an ingester
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct SequenceNumber(u64);
@@ -409,6 +430,36 @@ impl From<Table> for generated_types::influxdata::iox::table::v1::Table {
     }
 }
 
+/// Data object for a predicate delete recorded against a table.
+///
+/// A tombstone applies to all data in the table that was persisted before
+/// it was created, which is determined by comparing `created_at` with the
+/// `max_l0_created_at` of each [`ParquetFile`].
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct Tombstone {
+    /// the id of the tombstone
+    pub id: TombstoneId,
+    /// the table the delete applies to
+    pub table_id: TableId,
+    /// the inclusive lower bound of the time range of deleted data
+    pub min_time: Timestamp,
+    /// the inclusive upper bound of the time range of deleted data
+    pub max_time: Timestamp,
+    /// the delete predicate expressions, formatted by
+    /// [`DeletePredicate::expr_sql_string()`]
+    pub serialized_predicate: String,
+    /// the time at which the tombstone was recorded in the catalog
+    pub created_at: Timestamp,
+}
+
+impl Tombstone {
+    /// Returns true if this tombstone must be applied to data in a parquet
+    /// file with the given `max_l0_created_at`.
+    pub fn applies_to(&self, max_l0_created_at: Timestamp) -> bool {
+        max_l0_created_at < self.created_at
+    }
+}
+
 /// Column definitions for a table
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct TableSchema {
@@ -1034,6 +1085,97 @@ impl std::fmt::Display for Scalar {
     }
 }
 
+/// Errors converting a protobuf [`Predicate`] into a [`DeletePredicate`].
+///
+/// [`Predicate`]: generated_types::influxdata::iox::predicate::v1::Predicate
+#[derive(Debug, Error, PartialEq, Eq)]
+#[allow(missing_copy_implementations)]
+pub enum DeletePredicateProtoError {
+    /// The predicate has no time range.
+    #[error("delete predicate must specify a time range")]
+    NoRange,
+
+    /// An expression has no scalar value.
+    #[error("delete expression for column {0} must specify a value")]
+    NoScalar(String),
+
+    /// An expression has an unspecified or unknown operator.
+    #[error("delete expression for column {0} has invalid operator {1}")]
+    InvalidOp(String, i32),
+}
+
+impl From<&DeletePredicate> for generated_types::influxdata::iox::predicate::v1::Predicate {
+    fn from(v: &DeletePredicate) -> Self {
+        use generated_types::influxdata::iox::predicate::v1 as proto;
+
+        Self {
+            range: Some(proto::TimestampRange {
+                start: v.range.start(),
+                end: v.range.end(),
+            }),
+            exprs: v
+                .exprs
+                .iter()
+                .map(|e| proto::Expr {
+                    column: e.column.clone(),
+                    op: match e.op {
+                        Op::Eq => proto::Op::Eq,
+                        Op::Ne => proto::Op::Ne,
+                    }
+                    .into(),
+                    scalar: Some(proto::Scalar {
+                        value: Some(match &e.scalar {
+                            Scalar::Bool(v) => proto::scalar::Value::ValueBool(*v),
+                            Scalar::I64(v) => proto::scalar::Value::ValueI64(*v),
+                            Scalar::F64(v) => proto::scalar::Value::ValueF64(v.into_inner()),
+                            Scalar::String(v) => proto::scalar::Value::ValueString(v.clone()),
+                        }),
+                    }),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<generated_types::influxdata::iox::predicate::v1::Predicate> for DeletePredicate {
+    type Error = DeletePredicateProtoError;
+
+    /// Both ends of the time range of a delete are inclusive.
+    fn try_from(
+        v: generated_types::influxdata::iox::predicate::v1::Predicate,
+    ) -> Result<Self, Self::Error> {
+        use generated_types::influxdata::iox::predicate::v1 as proto;
+
+        let range = v.range.ok_or(DeletePredicateProtoError::NoRange)?;
+        let exprs = v
+            .exprs
+            .into_iter()
+            .map(|e| {
+                let op = match proto::Op::from_i32(e.op) {
+                    Some(proto::Op::Eq) => Op::Eq,
+                    Some(proto::Op::Ne) => Op::Ne,
+                    Some(proto::Op::Unspecified) | None => {
+                        return Err(DeletePredicateProtoError::InvalidOp(e.column, e.op))
+                    }
+                };
+                let scalar = match e.scalar.and_then(|s| s.value) {
+                    Some(proto::scalar::Value::ValueBool(v)) => Scalar::Bool(v),
+                    Some(proto::scalar::Value::ValueI64(v)) => Scalar::I64(v),
+                    Some(proto::scalar::Value::ValueF64(v)) => Scalar::F64(v.into()),
+                    Some(proto::scalar::Value::ValueString(v)) => Scalar::String(v),
+                    None => return Err(DeletePredicateProtoError::NoScalar(e.column)),
+                };
+                Ok(DeleteExpr::new(e.column, op, scalar))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            range: TimestampRange::new(range.start, range.end),
+            exprs,
+        })
+    }
+}
+
 /// A string that cannot be empty
 ///
 /// This is particularly useful for types that map to/from protobuf, where string fields
@@ -1946,6 +2088,63 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_delete_predicate_proto_round_trip() {
+        let pred = DeletePredicate {
+            range: TimestampRange::new(1, 2),
+            exprs: vec![
+                DeleteExpr::new(String::from("col1"), Op::Eq, Scalar::Bool(true)),
+                DeleteExpr::new(String::from("col2"), Op::Ne, Scalar::I64(-1)),
+                DeleteExpr::new(String::from("col3"), Op::Eq, Scalar::F64(OrderedFloat(1.5))),
+                DeleteExpr::new(
+                    String::from("col4"),
+                    Op::Eq,
+                    Scalar::String(String::from("foo")),
+                ),
+            ],
+        };
+
+        let proto = generated_types::influxdata::iox::predicate::v1::Predicate::from(&pred);
+        assert_eq!(DeletePredicate::try_from(proto).unwrap(), pred);
+    }
+
+    #[test]
+    fn test_delete_predicate_proto_invalid() {
+        use generated_types::influxdata::iox::predicate::v1 as proto;
+
+        let err = DeletePredicate::try_from(proto::Predicate::default()).unwrap_err();
+        assert_eq!(err, DeletePredicateProtoError::NoRange);
+
+        let range = || Some(proto::TimestampRange { start: 1, end: 2 });
+        let err = DeletePredicate::try_from(proto::Predicate {
+            range: range(),
+            exprs: vec![proto::Expr {
+                column: String::from("col1"),
+                op: proto::Op::Unspecified.into(),
+                scalar: None,
+            }],
+        })
+        .unwrap_err();
+        assert_eq!(
+            err,
+            DeletePredicateProtoError::InvalidOp(String::from("col1"), 0)
+        );
+
+        let err = DeletePredicate::try_from(proto::Predicate {
+            range: range(),
+            exprs: vec![proto::Expr {
+                column: String::from("col1"),
+                op: proto::Op::Eq.into(),
+                scalar: None,
+            }],
+        })
+        .unwrap_err();
+        assert_eq!(
+            err,
+            DeletePredicateProtoError::NoScalar(String::from("col1"))
+        );
+    }
+
     #[test]
     fn statistics_new_non_null() {
         let actual = StatValues::new_non_null(Some(-1i64), Some(1i64), 3);
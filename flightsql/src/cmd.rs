@@ -2,6 +2,10 @@
 
 use std::fmt::Display;
 
+use arrow::{
+    ipc::{reader::StreamReader, writer::StreamWriter},
+    record_batch::RecordBatch,
+};
 use arrow_flight::sql::{
     ActionClosePreparedStatementRequest, ActionCreatePreparedStatementRequest, Any,
     CommandGetCatalogs, CommandGetCrossReference, CommandGetDbSchemas, CommandGetExportedKeys,
@@ -9,11 +13,26 @@ use arrow_flight::sql::{
     CommandGetTables, CommandGetXdbcTypeInfo, CommandPreparedStatementQuery, CommandStatementQuery,
 };
 use bytes::Bytes;
+use datafusion::scalar::ScalarValue;
 use prost::Message;
 use snafu::ResultExt;
 
 use crate::error::*;
 
+/// Marks an encoded [`PreparedStatementHandle`] that carries bound
+/// parameters. Handles without parameters are the raw UTF-8 query text.
+const BOUND_HANDLE_PREFIX: u8 = 0;
+
+/// The encoded form of a [`PreparedStatementHandle`] with bound parameters.
+#[derive(Clone, PartialEq, Message)]
+struct BoundHandle {
+    #[prost(string, tag = "1")]
+    query: String,
+    /// Arrow IPC stream containing a single row of parameter values
+    #[prost(bytes = "bytes", tag = "2")]
+    params: Bytes,
+}
+
 /// Represents a prepared statement "handle". IOx passes all state
 /// required to run the prepared statement back and forth to the
 /// client, so any querier instance can run it
@@ -21,11 +40,16 @@ use crate::error::*;
 pub struct PreparedStatementHandle {
     /// The raw SQL query text
     query: String,
+    /// Bound parameter values, IPC encoded, if any
+    params: Option<Bytes>,
 }
 
 impl PreparedStatementHandle {
     pub fn new(query: String) -> Self {
-        Self { query }
+        Self {
+            query,
+            params: None,
+        }
     }
 
     /// return the query
@@ -33,16 +57,80 @@ impl PreparedStatementHandle {
         self.query.as_ref()
     }
 
+    /// Bind the parameter values in `params`, a single row with one
+    /// column per placeholder (`$1`, `$2`, ...) in order.
+    pub fn with_params(self, params: RecordBatch) -> Result<Self> {
+        if params.num_rows() != 1 {
+            return InvalidParametersSnafu {
+                description: format!("expected 1 row of parameters, got {}", params.num_rows()),
+            }
+            .fail();
+        }
+
+        let mut writer = StreamWriter::try_new(vec![], &params.schema())?;
+        writer.write(&params)?;
+        writer.finish()?;
+        let params = Bytes::from(writer.into_inner()?);
+
+        Ok(Self {
+            query: self.query,
+            params: Some(params),
+        })
+    }
+
+    /// Return the bound parameter values, in placeholder order. Returns
+    /// an empty list if no parameters are bound.
+    pub fn param_values(&self) -> Result<Vec<ScalarValue>> {
+        let Some(params) = &self.params else {
+            return Ok(vec![]);
+        };
+
+        let batches =
+            StreamReader::try_new(params.as_ref(), None)?.collect::<Result<Vec<_>, _>>()?;
+        let [batch] = batches.as_slice() else {
+            return InvalidParametersSnafu {
+                description: format!("expected 1 parameter batch, got {}", batches.len()),
+            }
+            .fail();
+        };
+
+        Ok(batch
+            .columns()
+            .iter()
+            .map(|column| ScalarValue::try_from_array(column, 0))
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+
     fn try_decode(handle: Bytes) -> Result<Self> {
-        // Note: in IOx  handles are the entire decoded query
-        // It will likely need to get more sophisticated as part of
-        // https://github.com/influxdata/influxdb_iox/issues/6699
+        // Note: in IOx  handles are the entire decoded query, unless
+        // parameters have been bound via DoPut
+        if handle.first() == Some(&BOUND_HANDLE_PREFIX) {
+            let BoundHandle { query, params } = Message::decode(handle.slice(1..))?;
+            return Ok(Self {
+                query,
+                params: Some(params),
+            });
+        }
+
         let query = String::from_utf8(handle.to_vec()).context(InvalidHandleSnafu)?;
-        Ok(Self { query })
+        Ok(Self::new(query))
     }
 
     fn encode(self) -> Bytes {
-        Bytes::from(self.query.into_bytes())
+        match self.params {
+            Some(params) => {
+                let msg = BoundHandle {
+                    query: self.query,
+                    params,
+                };
+                let mut buf = Vec::with_capacity(msg.encoded_len() + 1);
+                buf.push(BOUND_HANDLE_PREFIX);
+                msg.encode(&mut buf)
+                    .expect("encoding to a Vec can not fail");
+                Bytes::from(buf)
+            }
+            None => Bytes::from(self.query.into_bytes()),
+        }
     }
 }
 
@@ -55,7 +143,7 @@ impl Display for PreparedStatementHandle {
 /// Encode a PreparedStatementHandle as Bytes
 impl From<PreparedStatementHandle> for Bytes {
     fn from(value: PreparedStatementHandle) -> Self {
-        Self::from(value.query.into_bytes())
+        value.encode()
     }
 }
 
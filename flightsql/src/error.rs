@@ -31,6 +31,9 @@ pub enum Error {
     #[snafu(context(false))]
     Arrow { source: ArrowError },
 
+    #[snafu(display("Invalid prepared statement parameters: {}", description))]
+    InvalidParameters { description: String },
+
     #[snafu(display("Unsupported FlightSQL message type: {}", description))]
     UnsupportedMessageType { description: String },
 
@@ -55,7 +55,8 @@ impl FlightSQLPlanner {
                 get_schema_for_query(&query, ctx).await
             }
             FlightSQLCommand::CommandPreparedStatementQuery(handle) => {
-                get_schema_for_query(handle.query(), ctx).await
+                let plan = plan_prepared_statement(&handle, ctx).await?;
+                Ok(get_schema_for_plan(plan))
             }
             FlightSQLCommand::CommandGetSqlInfo(CommandGetSqlInfo { .. }) => {
                 Ok(iox_sql_info_data().schema())
@@ -108,7 +109,8 @@ impl FlightSQLPlanner {
             FlightSQLCommand::CommandPreparedStatementQuery(handle) => {
                 let query = handle.query();
                 debug!(%query, "Planning FlightSQL prepared query");
-                Ok(ctx.sql_to_physical_plan(query).await?)
+                let plan = plan_prepared_statement(&handle, ctx).await?;
+                Ok(ctx.create_physical_plan(&plan).await?)
             }
             FlightSQLCommand::CommandGetSqlInfo(cmd) => {
                 debug!(?cmd, "Planning GetSqlInfo query");
@@ -249,18 +251,17 @@ impl FlightSQLPlanner {
             ) => {
                 debug!(%query, "Creating prepared statement");
 
-                // todo run the planner here and actually figure out parameter schemas
-                // see https://github.com/apache/arrow-datafusion/pull/4701
-                let parameter_schema = vec![];
+                let plan = ctx.sql_to_logical_plan(&query).await?;
+                let parameter_schema = get_parameter_schema(&plan)?;
 
-                let dataset_schema = get_schema_for_query(&query, ctx).await?;
+                let dataset_schema = get_schema_for_plan(plan);
                 let dataset_schema = encode_schema(dataset_schema.as_ref())?;
                 let handle = PreparedStatementHandle::new(query);
 
                 let result = ActionCreatePreparedStatementResult {
                     prepared_statement_handle: Bytes::from(handle),
                     dataset_schema,
-                    parameter_schema: Bytes::from(parameter_schema),
+                    parameter_schema,
                 };
 
                 let msg = Any::pack(&result)?;
@@ -280,6 +281,105 @@ impl FlightSQLPlanner {
             .fail(),
         }
     }
+
+    /// Binds the parameter values in `batches` to the prepared
+    /// statement in `cmd` and returns bytes for the
+    /// [`arrow_flight::PutResult`] app_metadata, containing the updated
+    /// handle the client must use to execute the statement.
+    pub fn do_put(
+        namespace_name: impl Into<String>,
+        cmd: FlightSQLCommand,
+        batches: Vec<RecordBatch>,
+    ) -> Result<Bytes> {
+        let namespace_name = namespace_name.into();
+        debug!(%namespace_name, %cmd, "Handling flightsql do_put");
+
+        let handle = match cmd {
+            FlightSQLCommand::CommandPreparedStatementQuery(handle) => handle,
+            cmd => {
+                return ProtocolSnafu {
+                    cmd: format!("{cmd:?}"),
+                    method: "DoPut",
+                }
+                .fail()
+            }
+        };
+
+        // clients may send an empty batch before the parameter values
+        let mut batches = batches.into_iter().filter(|b| b.num_rows() > 0);
+        let (Some(params), None) = (batches.next(), batches.next()) else {
+            return InvalidParametersSnafu {
+                description: "expected a single record batch of parameter values",
+            }
+            .fail();
+        };
+
+        let handle = handle.with_params(params)?;
+        let result = DoPutPreparedStatementResult {
+            prepared_statement_handle: Some(Bytes::from(handle)),
+        };
+
+        let msg = Any {
+            type_url: DO_PUT_PREPARED_STATEMENT_RESULT_TYPE_URL.to_string(),
+            value: result.encode_to_vec().into(),
+        };
+        Ok(msg.encode_to_vec().into())
+    }
+}
+
+/// The type URL of [`DoPutPreparedStatementResult`] when packed in an
+/// [`Any`] message.
+const DO_PUT_PREPARED_STATEMENT_RESULT_TYPE_URL: &str =
+    "type.googleapis.com/arrow.flight.protocol.sql.DoPutPreparedStatementResult";
+
+/// Result of binding parameters to a prepared statement via DoPut.
+///
+/// Not yet provided by the arrow-flight crate.
+#[derive(Clone, PartialEq, Message)]
+struct DoPutPreparedStatementResult {
+    /// The handle to use in subsequent `CommandPreparedStatementQuery`
+    /// requests, if it has changed.
+    #[prost(bytes = "bytes", optional, tag = "1")]
+    prepared_statement_handle: Option<Bytes>,
+}
+
+/// Plan the query of a prepared statement, substituting any bound
+/// parameter values for its placeholders
+async fn plan_prepared_statement(
+    handle: &PreparedStatementHandle,
+    ctx: &IOxSessionContext,
+) -> Result<LogicalPlan> {
+    let plan = ctx.sql_to_logical_plan(handle.query()).await?;
+    let params = handle.param_values()?;
+    if params.is_empty() {
+        return Ok(plan);
+    }
+    Ok(plan.with_param_values(params)?)
+}
+
+/// Return the IPC encoded schema of the placeholders (`$1`, `$2`,
+/// ...) in `plan`, in the order their values are bound, or empty
+/// bytes if the plan has no placeholders
+fn get_parameter_schema(plan: &LogicalPlan) -> Result<Bytes> {
+    let mut params = plan.get_parameter_types()?.into_iter().collect::<Vec<_>>();
+    if params.is_empty() {
+        return Ok(Bytes::new());
+    }
+    params.sort_unstable_by_key(|(id, _)| {
+        (
+            id.trim_start_matches('$')
+                .parse::<usize>()
+                .unwrap_or(usize::MAX),
+            id.clone(),
+        )
+    });
+
+    // the type of a placeholder can not always be inferred
+    let fields = params
+        .into_iter()
+        .map(|(id, data_type)| Field::new(id, data_type.unwrap_or(DataType::Null), true))
+        .collect::<Vec<_>>();
+    encode_schema(&Schema::new(fields))
 }
 
 /// Return the schema for the specified query
@@ -32,6 +32,11 @@ fn main() -> Result<()> {
 /// - `influxdata.iox.wal.v1.rs`
 /// - `influxdata.iox.write.v1.rs`
 /// - `influxdata.platform.storage.rs`
+/// - `opentelemetry.proto.collector.metrics.v1.rs`
+/// - `opentelemetry.proto.common.v1.rs`
+/// - `opentelemetry.proto.metrics.v1.rs`
+/// - `opentelemetry.proto.resource.v1.rs`
+/// - `prometheus.rs`
 fn generate_grpc_types(root: &Path) -> Result<()> {
     let authz_path = root.join("influxdata/iox/authz/v1");
     let catalog_path = root.join("influxdata/iox/catalog/v1");
@@ -41,6 +46,7 @@ fn generate_grpc_types(root: &Path) -> Result<()> {
     let ingester_path = root.join("influxdata/iox/ingester/v1");
     let namespace_path = root.join("influxdata/iox/namespace/v1");
     let object_store_path = root.join("influxdata/iox/object_store/v1");
+    let otel_path = root.join("opentelemetry/proto");
     let partition_template_path = root.join("influxdata/iox/partition_template/v1");
     let predicate_path = root.join("influxdata/iox/predicate/v1");
     let querier_path = root.join("influxdata/iox/querier/v1");
@@ -74,6 +80,12 @@ fn generate_grpc_types(root: &Path) -> Result<()> {
         root.join("google/rpc/status.proto"),
         root.join("grpc/health/v1/service.proto"),
         root.join("influxdata/pbdata/v1/influxdb_pb_data_protocol.proto"),
+        otel_path.join("collector/metrics/v1/metrics_service.proto"),
+        otel_path.join("common/v1/common.proto"),
+        otel_path.join("metrics/v1/metrics.proto"),
+        otel_path.join("resource/v1/resource.proto"),
+        root.join("prometheus/remote.proto"),
+        root.join("prometheus/types.proto"),
         schema_path.join("service.proto"),
         storage_errors_path.join("errors.proto"),
         storage_path.join("predicate.proto"),
@@ -1,12 +1,16 @@
-//! Types and parsers for the [`DROP MEASUREMENT`][sql] statement.
+//! Types and parsers for the [`DROP MEASUREMENT`][sql] and [`DROP SERIES`][sql_series] statements.
 //!
 //! [sql]: https://docs.influxdata.com/influxdb/v1.8/query_language/manage-database/#delete-measurements-with-drop-measurement
+//! [sql_series]: https://docs.influxdata.com/influxdb/v1.8/query_language/manage-database/#drop-series-from-the-index-with-drop-series
 
-use crate::common::ws1;
+use crate::common::{where_clause, ws0, ws1, WhereClause};
 use crate::identifier::{identifier, Identifier};
 use crate::internal::{expect, ParseResult};
 use crate::keywords::keyword;
-use nom::combinator::map;
+use crate::simple_from_clause::{delete_from_clause, DeleteFromClause};
+use crate::statement::Statement;
+use nom::branch::alt;
+use nom::combinator::{map, opt};
 use nom::sequence::{pair, preceded};
 use std::fmt::{Display, Formatter};
 
@@ -14,7 +18,7 @@ use std::fmt::{Display, Formatter};
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DropMeasurementStatement {
     /// The name of the measurement to delete.
-    name: Identifier,
+    pub name: Identifier,
 }
 
 impl Display for DropMeasurementStatement {
@@ -23,12 +27,46 @@ impl Display for DropMeasurementStatement {
     }
 }
 
-pub(crate) fn drop_statement(i: &str) -> ParseResult<&str, DropMeasurementStatement> {
+/// Represents a `DROP SERIES` statement.
+///
+/// At least one of the `FROM` or `WHERE` clauses is specified.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DropSeriesStatement {
+    /// The measurements from which to delete series. If `None`, series
+    /// are deleted from all measurements.
+    pub from: Option<DeleteFromClause>,
+
+    /// A conditional expression to restrict which series are deleted.
+    pub condition: Option<WhereClause>,
+}
+
+impl Display for DropSeriesStatement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DROP SERIES")?;
+
+        if let Some(ref from_clause) = self.from {
+            write!(f, " {from_clause}")?;
+        }
+
+        if let Some(ref where_clause) = self.condition {
+            write!(f, " {where_clause}")?;
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) fn drop_statement(i: &str) -> ParseResult<&str, Statement> {
     preceded(
         pair(keyword("DROP"), ws1),
         expect(
-            "invalid DROP statement, expected MEASUREMENT",
-            drop_measurement,
+            "invalid DROP statement, expected MEASUREMENT or SERIES",
+            alt((
+                map(drop_measurement, |s| {
+                    Statement::DropMeasurement(Box::new(s))
+                }),
+                map(drop_series, |s| Statement::DropSeries(Box::new(s))),
+            )),
         ),
     )(i)
 }
@@ -46,6 +84,32 @@ fn drop_measurement(i: &str) -> ParseResult<&str, DropMeasurementStatement> {
     )(i)
 }
 
+fn drop_series(i: &str) -> ParseResult<&str, DropSeriesStatement> {
+    // drop_series ::= "SERIES" ( from_clause where_clause? | where_clause )
+    preceded(
+        keyword("SERIES"),
+        expect(
+            "invalid DROP SERIES statement, expected FROM or WHERE",
+            preceded(
+                ws1,
+                alt((
+                    map(
+                        pair(delete_from_clause, opt(preceded(ws0, where_clause))),
+                        |(from, condition)| DropSeriesStatement {
+                            from: Some(from),
+                            condition,
+                        },
+                    ),
+                    map(where_clause, |condition| DropSeriesStatement {
+                        from: None,
+                        condition: Some(condition),
+                    }),
+                )),
+            ),
+        ),
+    )(i)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -54,11 +118,12 @@ mod test {
     #[test]
     fn test_drop_statement() {
         drop_statement("DROP MEASUREMENT foo").unwrap();
+        drop_statement("DROP SERIES FROM foo").unwrap();
 
         // Fallible cases
         assert_expect_error!(
             drop_statement("DROP foo"),
-            "invalid DROP statement, expected MEASUREMENT"
+            "invalid DROP statement, expected MEASUREMENT or SERIES"
         );
     }
 
@@ -75,4 +140,34 @@ mod test {
             "invalid DROP MEASUREMENT statement, expected identifier"
         );
     }
+
+    #[test]
+    fn test_drop_series() {
+        // Validate via the Display trait, as the contents of the FROM and WHERE
+        // clauses are tested in their own modules.
+
+        let (_, got) = drop_series("SERIES FROM foo").unwrap();
+        assert_eq!(got.to_string(), "DROP SERIES FROM foo");
+
+        let (_, got) = drop_series("SERIES FROM foo, /bar/ WHERE host = 'a'").unwrap();
+        assert_eq!(
+            got.to_string(),
+            "DROP SERIES FROM foo, /bar/ WHERE host = 'a'"
+        );
+
+        let (_, got) = drop_series("SERIES WHERE host = 'a'").unwrap();
+        assert_eq!(got.to_string(), "DROP SERIES WHERE host = 'a'");
+        assert!(got.from.is_none());
+
+        // Fallible cases
+        assert_expect_error!(
+            drop_series("SERIES"),
+            "invalid DROP SERIES statement, expected FROM or WHERE"
+        );
+
+        assert_expect_error!(
+            drop_series("SERIES foo"),
+            "invalid DROP SERIES statement, expected FROM or WHERE"
+        );
+    }
 }
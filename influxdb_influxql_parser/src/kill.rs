@@ -0,0 +1,100 @@
+//! Types and parsers for the [`KILL QUERY`][sql] statement.
+//!
+//! [sql]: https://docs.influxdata.com/influxdb/v1.8/troubleshooting/query_management/#kill-query
+
+use crate::common::ws1;
+use crate::identifier::{identifier, Identifier};
+use crate::internal::{expect, ParseResult};
+use crate::keywords::keyword;
+use crate::literal::unsigned_integer;
+use nom::combinator::{map, opt};
+use nom::sequence::{pair, preceded, tuple};
+use std::fmt::{Display, Formatter};
+
+/// Represents a `KILL QUERY` statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KillQueryStatement {
+    /// The ID of the query to kill.
+    pub id: u64,
+
+    /// The host running the query, if specified.
+    pub host: Option<Identifier>,
+}
+
+impl Display for KillQueryStatement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "KILL QUERY {}", self.id)?;
+
+        if let Some(host) = &self.host {
+            write!(f, " ON {host}")?;
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) fn kill_query_statement(i: &str) -> ParseResult<&str, KillQueryStatement> {
+    // kill_query ::= "KILL" "QUERY" unsigned_integer ( "ON" identifier )?
+    preceded(
+        pair(keyword("KILL"), ws1),
+        expect(
+            "invalid KILL statement, expected QUERY",
+            preceded(
+                pair(keyword("QUERY"), ws1),
+                map(
+                    pair(
+                        expect(
+                            "invalid KILL QUERY statement, expected query ID",
+                            unsigned_integer,
+                        ),
+                        opt(preceded(
+                            tuple((ws1, keyword("ON"), ws1)),
+                            expect("invalid ON clause, expected identifier", identifier),
+                        )),
+                    ),
+                    |(id, host)| KillQueryStatement { id, host },
+                ),
+            ),
+        ),
+    )(i)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::assert_expect_error;
+
+    #[test]
+    fn test_kill_query_statement() {
+        let (_, got) = kill_query_statement("KILL QUERY 36").unwrap();
+        assert_eq!(got, KillQueryStatement { id: 36, host: None });
+        // validate Display
+        assert_eq!(got.to_string(), "KILL QUERY 36");
+
+        let (_, got) = kill_query_statement("KILL QUERY 53 ON \"localhost:8088\"").unwrap();
+        assert_eq!(
+            got,
+            KillQueryStatement {
+                id: 53,
+                host: Some("localhost:8088".into())
+            }
+        );
+        assert_eq!(got.to_string(), "KILL QUERY 53 ON \"localhost:8088\"");
+
+        // Fallible cases
+        assert_expect_error!(
+            kill_query_statement("KILL foo"),
+            "invalid KILL statement, expected QUERY"
+        );
+
+        assert_expect_error!(
+            kill_query_statement("KILL QUERY foo"),
+            "invalid KILL QUERY statement, expected query ID"
+        );
+
+        assert_expect_error!(
+            kill_query_statement("KILL QUERY 36 ON 'foo'"),
+            "invalid ON clause, expected identifier"
+        );
+    }
+}
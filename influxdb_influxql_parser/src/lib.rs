@@ -39,13 +39,16 @@ pub mod functions;
 pub mod identifier;
 mod internal;
 mod keywords;
+pub mod kill;
 pub mod literal;
 pub mod parameter;
 pub mod select;
 pub mod show;
+pub mod show_cardinality;
 pub mod show_field_keys;
 pub mod show_measurements;
 pub mod show_retention_policies;
+pub mod show_series;
 pub mod show_tag_keys;
 pub mod show_tag_values;
 pub mod simple_from_clause;
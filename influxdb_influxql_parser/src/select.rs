@@ -25,7 +25,7 @@ use nom::branch::alt;
 use nom::bytes::complete::tag;
 use nom::character::complete::char;
 use nom::combinator::{map, opt, value};
-use nom::sequence::{delimited, pair, preceded, tuple};
+use nom::sequence::{delimited, pair, preceded, terminated, tuple};
 use nom::Offset;
 use std::fmt;
 use std::fmt::{Display, Formatter, Write};
@@ -37,6 +37,9 @@ pub struct SelectStatement {
     /// Expressions returned by the selection.
     pub fields: FieldList,
 
+    /// The target measurement to write the results of the selection.
+    pub into: Option<IntoClause>,
+
     /// A list of measurements or subqueries used as the source data for the selection.
     pub from: FromMeasurementClause,
 
@@ -84,7 +87,13 @@ impl SelectStatement {
 
 impl Display for SelectStatement {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "SELECT {} {}", self.fields, self.from)?;
+        write!(f, "SELECT {}", self.fields)?;
+
+        if let Some(into) = &self.into {
+            write!(f, " {into}")?;
+        }
+
+        write!(f, " {}", self.from)?;
 
         if let Some(where_clause) = &self.condition {
             write!(f, " {where_clause}")?;
@@ -133,6 +142,7 @@ pub(crate) fn select_statement(i: &str) -> ParseResult<&str, SelectStatement> {
             _, // SELECT
             _, // whitespace
             fields,
+            into,
             from,
             condition,
             group_by,
@@ -148,6 +158,7 @@ pub(crate) fn select_statement(i: &str) -> ParseResult<&str, SelectStatement> {
         keyword("SELECT"),
         ws0,
         field_list,
+        opt(preceded(ws0, into_clause)),
         preceded(ws0, from_clause),
         opt(preceded(ws0, where_clause)),
         opt(preceded(ws0, group_by_clause)),
@@ -164,6 +175,7 @@ pub(crate) fn select_statement(i: &str) -> ParseResult<&str, SelectStatement> {
         remaining,
         SelectStatement {
             fields,
+            into,
             from,
             condition,
             group_by,
@@ -178,6 +190,105 @@ pub(crate) fn select_statement(i: &str) -> ParseResult<&str, SelectStatement> {
     ))
 }
 
+/// Represents the measurement name of an `INTO` clause.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IntoMeasurementName {
+    /// The measurement name is an [`Identifier`].
+    Name(Identifier),
+
+    /// The measurement name is the `:MEASUREMENT` back-reference, which
+    /// writes each row to a measurement with the same name as the source
+    /// measurement.
+    BackReference,
+}
+
+impl Display for IntoMeasurementName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Name(ident) => fmt::Display::fmt(ident, f),
+            Self::BackReference => f.write_str(":MEASUREMENT"),
+        }
+    }
+}
+
+/// Represents an `INTO` clause, which identifies the target measurement of
+/// a `SELECT` statement.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IntoClause {
+    /// An optional database name.
+    pub database: Option<Identifier>,
+
+    /// An optional retention policy.
+    pub retention_policy: Option<Identifier>,
+
+    /// The measurement name.
+    pub name: IntoMeasurementName,
+}
+
+impl Display for IntoClause {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("INTO ")?;
+        match (&self.database, &self.retention_policy) {
+            (None, None) => {}
+            (Some(db), None) => write!(f, "{db}..")?,
+            (None, Some(rp)) => write!(f, "{rp}.")?,
+            (Some(db), Some(rp)) => write!(f, "{db}.{rp}.")?,
+        }
+        fmt::Display::fmt(&self.name, f)
+    }
+}
+
+/// Parse an `INTO` clause.
+///
+/// ```text
+/// into_clause      ::= "INTO" ws+ ( measurement_name |
+///                                   ( policy_name "." measurement_name ) |
+///                                   ( db_name "." policy_name? "." measurement_name ) )
+///
+/// db_name          ::= identifier
+/// policy_name      ::= identifier
+/// measurement_name ::= identifier | ":MEASUREMENT"
+/// ```
+fn into_clause(i: &str) -> ParseResult<&str, IntoClause> {
+    let (remaining, (opt_db_rp, name)) = preceded(
+        pair(keyword("INTO"), ws1),
+        expect(
+            "invalid INTO clause, expected identifier or :MEASUREMENT",
+            pair(
+                opt(alt((
+                    // database "." retention_policy "."
+                    map(
+                        pair(
+                            terminated(identifier, tag(".")),
+                            terminated(identifier, tag(".")),
+                        ),
+                        |(db, rp)| (Some(db), Some(rp)),
+                    ),
+                    // database ".."
+                    map(terminated(identifier, tag("..")), |db| (Some(db), None)),
+                    // retention_policy "."
+                    map(terminated(identifier, tag(".")), |rp| (None, Some(rp))),
+                ))),
+                alt((
+                    value(IntoMeasurementName::BackReference, tag(":MEASUREMENT")),
+                    map(identifier, IntoMeasurementName::Name),
+                )),
+            ),
+        ),
+    )(i)?;
+
+    let (database, retention_policy) = opt_db_rp.unwrap_or_default();
+
+    Ok((
+        remaining,
+        IntoClause {
+            database,
+            retention_policy,
+            name,
+        },
+    ))
+}
+
 /// Represents a single measurement selection for a `FROM` clause.
 #[derive(Clone, Debug, PartialEq)]
 pub enum MeasurementSelection {
@@ -890,6 +1001,16 @@ mod test {
             r#"SELECT value FROM foo TZ('Australia/Hobart')"#
         );
 
+        let (_, got) = select_statement(
+            "SELECT mean(*) INTO rollup.:MEASUREMENT FROM raw GROUP BY time(1h), *",
+        )
+        .unwrap();
+        assert_eq!(
+            got.to_string(),
+            r#"SELECT mean(*) INTO rollup.:MEASUREMENT FROM raw GROUP BY TIME(1h), *"#
+        );
+        assert_matches!(got.into, Some(_));
+
         // validate spacing between keywords
 
         let (rem, _) = select_statement("SELECT value FROM(SELECT val FROM cpu)").unwrap();
@@ -1146,6 +1267,48 @@ mod test {
         assert_expect_error!(field_list("."), "invalid SELECT statement, expected field");
     }
 
+    #[test]
+    fn test_into_clause() {
+        let (_, got) = into_clause("INTO foo").unwrap();
+        assert_eq!(got.to_string(), "INTO foo");
+        assert_matches!(
+            got,
+            IntoClause {
+                database: None,
+                retention_policy: None,
+                name: IntoMeasurementName::Name(_)
+            }
+        );
+
+        let (_, got) = into_clause("INTO rollup.:MEASUREMENT").unwrap();
+        assert_eq!(got.to_string(), "INTO rollup.:MEASUREMENT");
+        assert_matches!(
+            got,
+            IntoClause {
+                database: None,
+                retention_policy: Some(_),
+                name: IntoMeasurementName::BackReference
+            }
+        );
+
+        let (_, got) = into_clause("INTO db..:MEASUREMENT").unwrap();
+        assert_eq!(got.to_string(), "INTO db..:MEASUREMENT");
+
+        let (_, got) = into_clause(r#"INTO "db"."rp"."my measurement""#).unwrap();
+        assert_eq!(got.to_string(), r#"INTO db.rp."my measurement""#);
+
+        // Fallible cases
+
+        assert_expect_error!(
+            into_clause("INTO /foo/"),
+            "invalid INTO clause, expected identifier or :MEASUREMENT"
+        );
+        assert_expect_error!(
+            into_clause("INTO :foo"),
+            "invalid INTO clause, expected identifier or :MEASUREMENT"
+        );
+    }
+
     #[test]
     fn test_measurement_selection() {
         // measurement name expression
@@ -7,9 +7,14 @@ use crate::identifier::{identifier, Identifier};
 use crate::impl_tuple_clause;
 use crate::internal::{expect, ParseResult};
 use crate::keywords::keyword;
+use crate::show_cardinality::{
+    show_field_key_cardinality, show_measurement_cardinality, show_series_cardinality,
+    show_tag_key_cardinality, show_tag_values_cardinality,
+};
 use crate::show_field_keys::show_field_keys;
 use crate::show_measurements::show_measurements;
 use crate::show_retention_policies::show_retention_policies;
+use crate::show_series::show_series;
 use crate::show_tag_keys::show_tag_keys;
 use crate::show_tag_values::show_tag_values;
 use crate::statement::Statement;
@@ -23,12 +28,20 @@ pub(crate) fn show_statement(i: &str) -> ParseResult<&str, Statement> {
     preceded(
         pair(keyword("SHOW"), ws1),
         expect(
-            "invalid SHOW statement, expected DATABASES, FIELD, MEASUREMENTS, TAG, or RETENTION following SHOW",
+            "invalid SHOW statement, expected DATABASES, FIELD, MEASUREMENT, MEASUREMENTS, SERIES, TAG, or RETENTION following SHOW",
             alt((
                 // SHOW DATABASES
                 map(show_databases, |s| Statement::ShowDatabases(Box::new(s))),
+                // SHOW FIELD KEY CARDINALITY
+                map(show_field_key_cardinality, |s| {
+                    Statement::ShowCardinality(Box::new(s))
+                }),
                 // SHOW FIELD KEYS
                 map(show_field_keys, |s| Statement::ShowFieldKeys(Box::new(s))),
+                // SHOW MEASUREMENT CARDINALITY
+                map(show_measurement_cardinality, |s| {
+                    Statement::ShowCardinality(Box::new(s))
+                }),
                 // SHOW MEASUREMENTS
                 map(show_measurements, |s| {
                     Statement::ShowMeasurements(Box::new(s))
@@ -37,6 +50,12 @@ pub(crate) fn show_statement(i: &str) -> ParseResult<&str, Statement> {
                 map(show_retention_policies, |s| {
                     Statement::ShowRetentionPolicies(Box::new(s))
                 }),
+                // SHOW SERIES CARDINALITY
+                map(show_series_cardinality, |s| {
+                    Statement::ShowCardinality(Box::new(s))
+                }),
+                // SHOW SERIES
+                map(show_series, |s| Statement::ShowSeries(Box::new(s))),
                 // SHOW TAG
                 show_tag,
             )),
@@ -82,13 +101,19 @@ pub(crate) fn on_clause(i: &str) -> ParseResult<&str, OnClause> {
     )(i)
 }
 
-/// Parse a `SHOW TAG (KEYS|VALUES)` statement.
+/// Parse a `SHOW TAG (KEYS|VALUES)` or `SHOW TAG (KEY|VALUES) CARDINALITY` statement.
 fn show_tag(i: &str) -> ParseResult<&str, Statement> {
     preceded(
         pair(keyword("TAG"), ws1),
         expect(
-            "invalid SHOW TAG statement, expected KEYS or VALUES",
+            "invalid SHOW TAG statement, expected KEY, KEYS or VALUES",
             alt((
+                map(show_tag_key_cardinality, |s| {
+                    Statement::ShowCardinality(Box::new(s))
+                }),
+                map(show_tag_values_cardinality, |s| {
+                    Statement::ShowCardinality(Box::new(s))
+                }),
                 map(show_tag_keys, |s| Statement::ShowTagKeys(Box::new(s))),
                 map(show_tag_values, |s| Statement::ShowTagValues(Box::new(s))),
             )),
@@ -123,17 +148,38 @@ mod test {
         let (_, got) = show_statement("SHOW TAG VALUES WITH KEY = some_key").unwrap();
         assert_eq!(got.to_string(), "SHOW TAG VALUES WITH KEY = some_key");
 
+        let (_, got) = show_statement("SHOW SERIES").unwrap();
+        assert_eq!(got.to_string(), "SHOW SERIES");
+
+        let (_, got) = show_statement("SHOW SERIES EXACT CARDINALITY").unwrap();
+        assert_eq!(got.to_string(), "SHOW SERIES EXACT CARDINALITY");
+
+        let (_, got) = show_statement("SHOW MEASUREMENT CARDINALITY").unwrap();
+        assert_eq!(got.to_string(), "SHOW MEASUREMENT CARDINALITY");
+
+        let (_, got) = show_statement("SHOW FIELD KEY CARDINALITY").unwrap();
+        assert_eq!(got.to_string(), "SHOW FIELD KEY CARDINALITY");
+
+        let (_, got) = show_statement("SHOW TAG KEY EXACT CARDINALITY").unwrap();
+        assert_eq!(got.to_string(), "SHOW TAG KEY EXACT CARDINALITY");
+
+        let (_, got) = show_statement("SHOW TAG VALUES CARDINALITY WITH KEY = some_key").unwrap();
+        assert_eq!(
+            got.to_string(),
+            "SHOW TAG VALUES CARDINALITY WITH KEY = some_key"
+        );
+
         // Fallible cases
 
         assert_expect_error!(
             show_statement("SHOW TAG FOO WITH KEY = some_key"),
-            "invalid SHOW TAG statement, expected KEYS or VALUES"
+            "invalid SHOW TAG statement, expected KEY, KEYS or VALUES"
         );
 
         // Unsupported SHOW
         assert_expect_error!(
             show_statement("SHOW FOO"),
-            "invalid SHOW statement, expected DATABASES, FIELD, MEASUREMENTS, TAG, or RETENTION following SHOW"
+            "invalid SHOW statement, expected DATABASES, FIELD, MEASUREMENT, MEASUREMENTS, SERIES, TAG, or RETENTION following SHOW"
         );
     }
 }
@@ -0,0 +1,321 @@
+//! Types and parsers for the various [`SHOW ... CARDINALITY`][sql] statements.
+//!
+//! [sql]: https://docs.influxdata.com/influxdb/v1.8/query_language/spec/#show-series-cardinality
+
+use crate::common::{
+    limit_clause, offset_clause, where_clause, ws1, LimitClause, OffsetClause, WhereClause,
+};
+use crate::internal::{expect, ParseResult};
+use crate::keywords::keyword;
+use crate::show::{on_clause, OnClause};
+use crate::show_tag_values::{with_key_clause, WithKeyClause};
+use crate::simple_from_clause::{show_from_clause, ShowFromClause};
+use nom::combinator::{map, opt};
+use nom::sequence::{pair, preceded, terminated, tuple};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// The kind of values counted by a `SHOW ... CARDINALITY` statement.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CardinalityKind {
+    /// Count the series, as in `SHOW SERIES CARDINALITY`.
+    Series,
+    /// Count the measurements, as in `SHOW MEASUREMENT CARDINALITY`.
+    Measurement,
+    /// Count the tag keys, as in `SHOW TAG KEY CARDINALITY`.
+    TagKey,
+    /// Count the values of the tag keys matching the `WITH KEY` clause,
+    /// as in `SHOW TAG VALUES CARDINALITY`.
+    TagValues(WithKeyClause),
+    /// Count the field keys, as in `SHOW FIELD KEY CARDINALITY`.
+    FieldKey,
+}
+
+impl Display for CardinalityKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Series => "SERIES",
+            Self::Measurement => "MEASUREMENT",
+            Self::TagKey => "TAG KEY",
+            Self::TagValues(_) => "TAG VALUES",
+            Self::FieldKey => "FIELD KEY",
+        })
+    }
+}
+
+/// Represents a `SHOW ... CARDINALITY` InfluxQL statement.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShowCardinalityStatement {
+    /// The kind of values to count.
+    pub kind: CardinalityKind,
+
+    /// `true` when the `EXACT` keyword was specified, to request an
+    /// exact count rather than an estimate.
+    pub exact: bool,
+
+    /// The name of the database to query. If `None`, a default
+    /// database will be used.
+    pub database: Option<OnClause>,
+
+    /// The measurement or measurements to restrict which values
+    /// are counted.
+    pub from: Option<ShowFromClause>,
+
+    /// A conditional expression to filter the values.
+    pub condition: Option<WhereClause>,
+
+    /// A value to restrict the number of rows returned.
+    pub limit: Option<LimitClause>,
+
+    /// A value to specify an offset to start retrieving rows.
+    pub offset: Option<OffsetClause>,
+}
+
+impl Display for ShowCardinalityStatement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "SHOW {}", self.kind)?;
+
+        if self.exact {
+            write!(f, " EXACT")?;
+        }
+
+        write!(f, " CARDINALITY")?;
+
+        if let Some(ref on_clause) = self.database {
+            write!(f, " {on_clause}")?;
+        }
+
+        if let Some(ref from_clause) = self.from {
+            write!(f, " {from_clause}")?;
+        }
+
+        if let CardinalityKind::TagValues(ref with_key) = self.kind {
+            write!(f, " {with_key}")?;
+        }
+
+        if let Some(ref where_clause) = self.condition {
+            write!(f, " {where_clause}")?;
+        }
+
+        if let Some(ref limit) = self.limit {
+            write!(f, " {limit}")?;
+        }
+
+        if let Some(ref offset) = self.offset {
+            write!(f, " {offset}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse the optional `EXACT` keyword and the `CARDINALITY` keyword which
+/// follow the kind of a `SHOW ... CARDINALITY` statement, returning `true`
+/// if `EXACT` was specified.
+fn exact_cardinality(i: &str) -> ParseResult<&str, bool> {
+    map(
+        preceded(
+            ws1,
+            pair(
+                opt(terminated(keyword("EXACT"), ws1)),
+                keyword("CARDINALITY"),
+            ),
+        ),
+        |(exact, _)| exact.is_some(),
+    )(i)
+}
+
+/// Parse the clauses which follow the `CARDINALITY` keyword, for all kinds
+/// other than [`CardinalityKind::TagValues`].
+fn cardinality_clauses(
+    i: &str,
+    kind: CardinalityKind,
+    exact: bool,
+) -> ParseResult<&str, ShowCardinalityStatement> {
+    let (remaining_input, (database, from, condition, limit, offset)) = tuple((
+        opt(preceded(ws1, on_clause)),
+        opt(preceded(ws1, show_from_clause)),
+        opt(preceded(ws1, where_clause)),
+        opt(preceded(ws1, limit_clause)),
+        opt(preceded(ws1, offset_clause)),
+    ))(i)?;
+
+    Ok((
+        remaining_input,
+        ShowCardinalityStatement {
+            kind,
+            exact,
+            database,
+            from,
+            condition,
+            limit,
+            offset,
+        },
+    ))
+}
+
+/// Parse a `SHOW SERIES [EXACT] CARDINALITY` statement, starting from the
+/// `SERIES` token.
+pub(crate) fn show_series_cardinality(i: &str) -> ParseResult<&str, ShowCardinalityStatement> {
+    let (i, exact) = preceded(keyword("SERIES"), exact_cardinality)(i)?;
+    cardinality_clauses(i, CardinalityKind::Series, exact)
+}
+
+/// Parse a `SHOW MEASUREMENT [EXACT] CARDINALITY` statement, starting from
+/// the `MEASUREMENT` token.
+pub(crate) fn show_measurement_cardinality(i: &str) -> ParseResult<&str, ShowCardinalityStatement> {
+    let (i, exact) = preceded(keyword("MEASUREMENT"), exact_cardinality)(i)?;
+    cardinality_clauses(i, CardinalityKind::Measurement, exact)
+}
+
+/// Parse a `SHOW FIELD KEY [EXACT] CARDINALITY` statement, starting from
+/// the `FIELD` token.
+pub(crate) fn show_field_key_cardinality(i: &str) -> ParseResult<&str, ShowCardinalityStatement> {
+    let (i, exact) = preceded(
+        tuple((keyword("FIELD"), ws1, keyword("KEY"))),
+        exact_cardinality,
+    )(i)?;
+    cardinality_clauses(i, CardinalityKind::FieldKey, exact)
+}
+
+/// Parse a `SHOW TAG KEY [EXACT] CARDINALITY` statement, starting from the
+/// `KEY` token.
+pub(crate) fn show_tag_key_cardinality(i: &str) -> ParseResult<&str, ShowCardinalityStatement> {
+    let (i, exact) = preceded(keyword("KEY"), exact_cardinality)(i)?;
+    cardinality_clauses(i, CardinalityKind::TagKey, exact)
+}
+
+/// Parse a `SHOW TAG VALUES [EXACT] CARDINALITY` statement, starting from
+/// the `VALUES` token.
+pub(crate) fn show_tag_values_cardinality(i: &str) -> ParseResult<&str, ShowCardinalityStatement> {
+    let (remaining_input, (exact, database, from, with_key, condition, limit, offset)) = tuple((
+        preceded(keyword("VALUES"), exact_cardinality),
+        opt(preceded(ws1, on_clause)),
+        opt(preceded(ws1, show_from_clause)),
+        expect(
+            "invalid SHOW TAG VALUES CARDINALITY statement, expected WITH KEY clause",
+            preceded(ws1, with_key_clause),
+        ),
+        opt(preceded(ws1, where_clause)),
+        opt(preceded(ws1, limit_clause)),
+        opt(preceded(ws1, offset_clause)),
+    ))(i)?;
+
+    Ok((
+        remaining_input,
+        ShowCardinalityStatement {
+            kind: CardinalityKind::TagValues(with_key),
+            exact,
+            database,
+            from,
+            condition,
+            limit,
+            offset,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::assert_expect_error;
+
+    #[test]
+    fn test_show_series_cardinality() {
+        let (_, got) = show_series_cardinality("SERIES CARDINALITY").unwrap();
+        assert_eq!(got.to_string(), "SHOW SERIES CARDINALITY");
+
+        let (_, got) = show_series_cardinality("SERIES EXACT CARDINALITY").unwrap();
+        assert_eq!(got.to_string(), "SHOW SERIES EXACT CARDINALITY");
+        assert!(got.exact);
+
+        // all optional clauses
+        let (_, got) = show_series_cardinality(
+            "SERIES EXACT CARDINALITY ON db FROM /foo/ WHERE foo = 'bar' LIMIT 1 OFFSET 2",
+        )
+        .unwrap();
+        assert_eq!(
+            got.to_string(),
+            "SHOW SERIES EXACT CARDINALITY ON db FROM /foo/ WHERE foo = 'bar' LIMIT 1 OFFSET 2"
+        );
+
+        // Fails without an error, so the parser can fall back to SHOW SERIES
+        show_series_cardinality("SERIES").unwrap_err();
+        show_series_cardinality("SERIES FROM foo").unwrap_err();
+    }
+
+    #[test]
+    fn test_show_measurement_cardinality() {
+        let (_, got) = show_measurement_cardinality("MEASUREMENT CARDINALITY").unwrap();
+        assert_eq!(got.to_string(), "SHOW MEASUREMENT CARDINALITY");
+
+        let (_, got) =
+            show_measurement_cardinality("MEASUREMENT EXACT CARDINALITY FROM foo WHERE bar = 'x'")
+                .unwrap();
+        assert_eq!(
+            got.to_string(),
+            "SHOW MEASUREMENT EXACT CARDINALITY FROM foo WHERE bar = 'x'"
+        );
+
+        show_measurement_cardinality("MEASUREMENTS CARDINALITY").unwrap_err();
+    }
+
+    #[test]
+    fn test_show_field_key_cardinality() {
+        let (_, got) = show_field_key_cardinality("FIELD KEY CARDINALITY").unwrap();
+        assert_eq!(got.to_string(), "SHOW FIELD KEY CARDINALITY");
+
+        let (_, got) =
+            show_field_key_cardinality("FIELD KEY EXACT CARDINALITY ON db FROM foo").unwrap();
+        assert_eq!(
+            got.to_string(),
+            "SHOW FIELD KEY EXACT CARDINALITY ON db FROM foo"
+        );
+
+        show_field_key_cardinality("FIELD KEYS").unwrap_err();
+    }
+
+    #[test]
+    fn test_show_tag_key_cardinality() {
+        let (_, got) = show_tag_key_cardinality("KEY CARDINALITY").unwrap();
+        assert_eq!(got.to_string(), "SHOW TAG KEY CARDINALITY");
+
+        let (_, got) =
+            show_tag_key_cardinality("KEY EXACT CARDINALITY FROM foo WHERE bar = 'x' LIMIT 1")
+                .unwrap();
+        assert_eq!(
+            got.to_string(),
+            "SHOW TAG KEY EXACT CARDINALITY FROM foo WHERE bar = 'x' LIMIT 1"
+        );
+
+        show_tag_key_cardinality("KEYS").unwrap_err();
+    }
+
+    #[test]
+    fn test_show_tag_values_cardinality() {
+        let (_, got) = show_tag_values_cardinality("VALUES CARDINALITY WITH KEY = host").unwrap();
+        assert_eq!(
+            got.to_string(),
+            "SHOW TAG VALUES CARDINALITY WITH KEY = host"
+        );
+
+        let (_, got) = show_tag_values_cardinality(
+            "VALUES EXACT CARDINALITY ON db FROM foo WITH KEY IN (host, region) WHERE bar = 'x' LIMIT 1 OFFSET 2",
+        )
+        .unwrap();
+        assert_eq!(
+            got.to_string(),
+            "SHOW TAG VALUES EXACT CARDINALITY ON db FROM foo WITH KEY IN (host, region) WHERE bar = 'x' LIMIT 1 OFFSET 2"
+        );
+
+        // Fails without an error, so the parser can fall back to SHOW TAG VALUES
+        show_tag_values_cardinality("VALUES WITH KEY = host").unwrap_err();
+
+        // Fallible cases
+
+        assert_expect_error!(
+            show_tag_values_cardinality("VALUES CARDINALITY"),
+            "invalid SHOW TAG VALUES CARDINALITY statement, expected WITH KEY clause"
+        );
+    }
+}
@@ -169,7 +169,7 @@ fn identifier_list(i: &str) -> ParseResult<&str, InList> {
     )(i)
 }
 
-fn with_key_clause(i: &str) -> ParseResult<&str, WithKeyClause> {
+pub(crate) fn with_key_clause(i: &str) -> ParseResult<&str, WithKeyClause> {
     preceded(
         tuple((
             keyword("WITH"),
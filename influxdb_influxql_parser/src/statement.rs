@@ -2,14 +2,17 @@
 
 use crate::create::{create_statement, CreateDatabaseStatement};
 use crate::delete::{delete_statement, DeleteStatement};
-use crate::drop::{drop_statement, DropMeasurementStatement};
+use crate::drop::{drop_statement, DropMeasurementStatement, DropSeriesStatement};
 use crate::explain::{explain_statement, ExplainStatement};
 use crate::internal::ParseResult;
+use crate::kill::{kill_query_statement, KillQueryStatement};
 use crate::select::{select_statement, SelectStatement};
 use crate::show::{show_statement, ShowDatabasesStatement};
+use crate::show_cardinality::ShowCardinalityStatement;
 use crate::show_field_keys::ShowFieldKeysStatement;
 use crate::show_measurements::ShowMeasurementsStatement;
 use crate::show_retention_policies::ShowRetentionPoliciesStatement;
+use crate::show_series::ShowSeriesStatement;
 use crate::show_tag_keys::ShowTagKeysStatement;
 use crate::show_tag_values::ShowTagValuesStatement;
 use nom::branch::alt;
@@ -25,8 +28,12 @@ pub enum Statement {
     Delete(Box<DeleteStatement>),
     /// Represents a `DROP MEASUREMENT` statement.
     DropMeasurement(Box<DropMeasurementStatement>),
+    /// Represents a `DROP SERIES` statement.
+    DropSeries(Box<DropSeriesStatement>),
     /// Represents an `EXPLAIN` statement.
     Explain(Box<ExplainStatement>),
+    /// Represents a `KILL QUERY` statement.
+    KillQuery(Box<KillQueryStatement>),
     /// Represents a `SELECT` statement.
     Select(Box<SelectStatement>),
     /// Represents a `SHOW DATABASES` statement.
@@ -41,6 +48,10 @@ pub enum Statement {
     ShowTagValues(Box<ShowTagValuesStatement>),
     /// Represents a `SHOW FIELD KEYS` statement.
     ShowFieldKeys(Box<ShowFieldKeysStatement>),
+    /// Represents a `SHOW SERIES` statement.
+    ShowSeries(Box<ShowSeriesStatement>),
+    /// Represents one of the `SHOW ... CARDINALITY` statements.
+    ShowCardinality(Box<ShowCardinalityStatement>),
 }
 
 impl Display for Statement {
@@ -49,7 +60,9 @@ impl Display for Statement {
             Self::CreateDatabase(s) => Display::fmt(s, f),
             Self::Delete(s) => Display::fmt(s, f),
             Self::DropMeasurement(s) => Display::fmt(s, f),
+            Self::DropSeries(s) => Display::fmt(s, f),
             Self::Explain(s) => Display::fmt(s, f),
+            Self::KillQuery(s) => Display::fmt(s, f),
             Self::Select(s) => Display::fmt(s, f),
             Self::ShowDatabases(s) => Display::fmt(s, f),
             Self::ShowMeasurements(s) => Display::fmt(s, f),
@@ -57,6 +70,8 @@ impl Display for Statement {
             Self::ShowTagKeys(s) => Display::fmt(s, f),
             Self::ShowTagValues(s) => Display::fmt(s, f),
             Self::ShowFieldKeys(s) => Display::fmt(s, f),
+            Self::ShowSeries(s) => Display::fmt(s, f),
+            Self::ShowCardinality(s) => Display::fmt(s, f),
         }
     }
 }
@@ -65,8 +80,9 @@ impl Display for Statement {
 pub fn statement(i: &str) -> ParseResult<&str, Statement> {
     alt((
         map(delete_statement, |s| Statement::Delete(Box::new(s))),
-        map(drop_statement, |s| Statement::DropMeasurement(Box::new(s))),
+        drop_statement,
         map(explain_statement, |s| Statement::Explain(Box::new(s))),
+        map(kill_query_statement, |s| Statement::KillQuery(Box::new(s))),
         map(select_statement, |s| Statement::Select(Box::new(s))),
         create_statement,
         show_statement,
@@ -93,6 +109,9 @@ mod test {
         let (got, _) = statement("DROP MEASUREMENT foo").unwrap();
         assert_eq!(got, "");
 
+        let (got, _) = statement("DROP SERIES FROM foo WHERE host = 'a'").unwrap();
+        assert_eq!(got, "");
+
         // explain_statement combinator
         let (got, _) = statement("EXPLAIN SELECT * FROM cpu").unwrap();
         assert_eq!(got, "");
@@ -100,8 +119,18 @@ mod test {
         let (got, _) = statement("SELECT * FROM foo WHERE time > now() - 5m AND host = 'bar' GROUP BY TIME(5m) FILL(previous) ORDER BY time DESC").unwrap();
         assert_eq!(got, "");
 
+        // kill_query_statement combinator
+        let (got, _) = statement("KILL QUERY 36").unwrap();
+        assert_eq!(got, "");
+
         // show_statement combinator
         let (got, _) = statement("SHOW TAG KEYS").unwrap();
         assert_eq!(got, "");
+
+        let (got, _) = statement("SHOW SERIES FROM cpu WHERE host = 'a'").unwrap();
+        assert_eq!(got, "");
+
+        let (got, _) = statement("SHOW SERIES EXACT CARDINALITY FROM cpu").unwrap();
+        assert_eq!(got, "");
     }
 }
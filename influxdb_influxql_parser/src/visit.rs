@@ -30,23 +30,26 @@ use crate::common::{
 };
 use crate::create::CreateDatabaseStatement;
 use crate::delete::DeleteStatement;
-use crate::drop::DropMeasurementStatement;
+use crate::drop::{DropMeasurementStatement, DropSeriesStatement};
 use crate::explain::ExplainStatement;
 use crate::expression::arithmetic::Expr;
 use crate::expression::conditional::ConditionalExpression;
 use crate::expression::{Binary, Call, ConditionalBinary, VarRef};
+use crate::kill::KillQueryStatement;
 use crate::literal::Literal;
 use crate::select::{
-    Dimension, Field, FieldList, FillClause, FromMeasurementClause, GroupByClause,
+    Dimension, Field, FieldList, FillClause, FromMeasurementClause, GroupByClause, IntoClause,
     MeasurementSelection, SLimitClause, SOffsetClause, SelectStatement, TimeDimension,
     TimeZoneClause,
 };
 use crate::show::{OnClause, ShowDatabasesStatement};
+use crate::show_cardinality::{CardinalityKind, ShowCardinalityStatement};
 use crate::show_field_keys::ShowFieldKeysStatement;
 use crate::show_measurements::{
     ExtendedOnClause, ShowMeasurementsStatement, WithMeasurementClause,
 };
 use crate::show_retention_policies::ShowRetentionPoliciesStatement;
+use crate::show_series::ShowSeriesStatement;
 use crate::show_tag_keys::ShowTagKeysStatement;
 use crate::show_tag_values::{ShowTagValuesStatement, WithKeyClause};
 use crate::simple_from_clause::{DeleteFromClause, ShowFromClause};
@@ -150,6 +153,22 @@ pub trait Visitor: Sized {
         Ok(self)
     }
 
+    /// Invoked before any children of the `DROP SERIES` statement are visited.
+    fn pre_visit_drop_series_statement(
+        self,
+        _n: &DropSeriesStatement,
+    ) -> Result<Recursion<Self>, Self::Error> {
+        Ok(Continue(self))
+    }
+
+    /// Invoked after all children of the `DROP SERIES` statement are visited.
+    fn post_visit_drop_series_statement(
+        self,
+        _n: &DropSeriesStatement,
+    ) -> Result<Self, Self::Error> {
+        Ok(self)
+    }
+
     /// Invoked before any children of the `EXPLAIN` statement are visited.
     fn pre_visit_explain_statement(
         self,
@@ -163,6 +182,19 @@ pub trait Visitor: Sized {
         Ok(self)
     }
 
+    /// Invoked before any children of the `KILL QUERY` statement are visited.
+    fn pre_visit_kill_query_statement(
+        self,
+        _n: &KillQueryStatement,
+    ) -> Result<Recursion<Self>, Self::Error> {
+        Ok(Continue(self))
+    }
+
+    /// Invoked after all children of the `KILL QUERY` statement are visited.
+    fn post_visit_kill_query_statement(self, _n: &KillQueryStatement) -> Result<Self, Self::Error> {
+        Ok(self)
+    }
+
     /// Invoked before any children of the `SELECT` statement are visited.
     fn pre_visit_select_statement(
         self,
@@ -272,6 +304,38 @@ pub trait Visitor: Sized {
         Ok(self)
     }
 
+    /// Invoked before any children of the `SHOW SERIES` statement are visited.
+    fn pre_visit_show_series_statement(
+        self,
+        _n: &ShowSeriesStatement,
+    ) -> Result<Recursion<Self>, Self::Error> {
+        Ok(Continue(self))
+    }
+
+    /// Invoked after all children of the `SHOW SERIES` statement are visited.
+    fn post_visit_show_series_statement(
+        self,
+        _n: &ShowSeriesStatement,
+    ) -> Result<Self, Self::Error> {
+        Ok(self)
+    }
+
+    /// Invoked before any children of the `SHOW ... CARDINALITY` statement are visited.
+    fn pre_visit_show_cardinality_statement(
+        self,
+        _n: &ShowCardinalityStatement,
+    ) -> Result<Recursion<Self>, Self::Error> {
+        Ok(Continue(self))
+    }
+
+    /// Invoked after all children of the `SHOW ... CARDINALITY` statement are visited.
+    fn post_visit_show_cardinality_statement(
+        self,
+        _n: &ShowCardinalityStatement,
+    ) -> Result<Self, Self::Error> {
+        Ok(self)
+    }
+
     /// Invoked before any children of the conditional expression are visited.
     fn pre_visit_conditional_expression(
         self,
@@ -422,6 +486,16 @@ pub trait Visitor: Sized {
         Ok(self)
     }
 
+    /// Invoked before any children of the `INTO` clause are visited.
+    fn pre_visit_into_clause(self, _n: &IntoClause) -> Result<Recursion<Self>, Self::Error> {
+        Ok(Continue(self))
+    }
+
+    /// Invoked after all children of the `INTO` clause are visited.
+    fn post_visit_into_clause(self, _n: &IntoClause) -> Result<Self, Self::Error> {
+        Ok(self)
+    }
+
     /// Invoked before any children of the `FILL` clause are visited.
     fn pre_visit_fill_clause(self, _n: &FillClause) -> Result<Recursion<Self>, Self::Error> {
         Ok(Continue(self))
@@ -615,7 +689,9 @@ impl Visitable for Statement {
             Self::CreateDatabase(s) => s.accept(visitor),
             Self::Delete(s) => s.accept(visitor),
             Self::DropMeasurement(s) => s.accept(visitor),
+            Self::DropSeries(s) => s.accept(visitor),
             Self::Explain(s) => s.accept(visitor),
+            Self::KillQuery(s) => s.accept(visitor),
             Self::Select(s) => s.accept(visitor),
             Self::ShowDatabases(s) => s.accept(visitor),
             Self::ShowMeasurements(s) => s.accept(visitor),
@@ -623,6 +699,8 @@ impl Visitable for Statement {
             Self::ShowTagKeys(s) => s.accept(visitor),
             Self::ShowTagValues(s) => s.accept(visitor),
             Self::ShowFieldKeys(s) => s.accept(visitor),
+            Self::ShowSeries(s) => s.accept(visitor),
+            Self::ShowCardinality(s) => s.accept(visitor),
         }?;
 
         visitor.post_visit_statement(self)
@@ -712,6 +790,29 @@ impl Visitable for DropMeasurementStatement {
     }
 }
 
+impl Visitable for DropSeriesStatement {
+    fn accept<V: Visitor>(&self, visitor: V) -> Result<V, V::Error> {
+        let visitor = match visitor.pre_visit_drop_series_statement(self)? {
+            Continue(visitor) => visitor,
+            Stop(visitor) => return Ok(visitor),
+        };
+
+        let visitor = if let Some(from) = &self.from {
+            from.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        let visitor = if let Some(condition) = &self.condition {
+            condition.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        visitor.post_visit_drop_series_statement(self)
+    }
+}
+
 impl Visitable for ExplainStatement {
     fn accept<V: Visitor>(&self, visitor: V) -> Result<V, V::Error> {
         let visitor = match visitor.pre_visit_explain_statement(self)? {
@@ -725,6 +826,17 @@ impl Visitable for ExplainStatement {
     }
 }
 
+impl Visitable for KillQueryStatement {
+    fn accept<V: Visitor>(&self, visitor: V) -> Result<V, V::Error> {
+        let visitor = match visitor.pre_visit_kill_query_statement(self)? {
+            Continue(visitor) => visitor,
+            Stop(visitor) => return Ok(visitor),
+        };
+
+        visitor.post_visit_kill_query_statement(self)
+    }
+}
+
 impl Visitable for SelectStatement {
     fn accept<V: Visitor>(&self, visitor: V) -> Result<V, V::Error> {
         let visitor = match visitor.pre_visit_select_statement(self)? {
@@ -734,6 +846,12 @@ impl Visitable for SelectStatement {
 
         let visitor = self.fields.accept(visitor)?;
 
+        let visitor = if let Some(into) = &self.into {
+            into.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
         let visitor = self.from.accept(visitor)?;
 
         let visitor = if let Some(condition) = &self.condition {
@@ -849,6 +967,17 @@ impl Visitable for SOffsetClause {
     }
 }
 
+impl Visitable for IntoClause {
+    fn accept<V: Visitor>(&self, visitor: V) -> Result<V, V::Error> {
+        let visitor = match visitor.pre_visit_into_clause(self)? {
+            Continue(visitor) => visitor,
+            Stop(visitor) => return Ok(visitor),
+        };
+
+        visitor.post_visit_into_clause(self)
+    }
+}
+
 impl Visitable for FillClause {
     fn accept<V: Visitor>(&self, visitor: V) -> Result<V, V::Error> {
         let visitor = match visitor.pre_visit_fill_clause(self)? {
@@ -1114,6 +1243,94 @@ impl Visitable for ShowFieldKeysStatement {
     }
 }
 
+impl Visitable for ShowSeriesStatement {
+    fn accept<V: Visitor>(&self, visitor: V) -> Result<V, V::Error> {
+        let visitor = match visitor.pre_visit_show_series_statement(self)? {
+            Continue(visitor) => visitor,
+            Stop(visitor) => return Ok(visitor),
+        };
+
+        let visitor = if let Some(on_clause) = &self.database {
+            on_clause.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        let visitor = if let Some(from) = &self.from {
+            from.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        let visitor = if let Some(condition) = &self.condition {
+            condition.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        let visitor = if let Some(limit) = &self.limit {
+            limit.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        let visitor = if let Some(offset) = &self.offset {
+            offset.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        visitor.post_visit_show_series_statement(self)
+    }
+}
+
+impl Visitable for ShowCardinalityStatement {
+    fn accept<V: Visitor>(&self, visitor: V) -> Result<V, V::Error> {
+        let visitor = match visitor.pre_visit_show_cardinality_statement(self)? {
+            Continue(visitor) => visitor,
+            Stop(visitor) => return Ok(visitor),
+        };
+
+        let visitor = if let Some(on_clause) = &self.database {
+            on_clause.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        let visitor = if let Some(from) = &self.from {
+            from.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        let visitor = if let CardinalityKind::TagValues(with_key) = &self.kind {
+            with_key.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        let visitor = if let Some(condition) = &self.condition {
+            condition.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        let visitor = if let Some(limit) = &self.limit {
+            limit.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        let visitor = if let Some(offset) = &self.offset {
+            offset.accept(visitor)
+        } else {
+            Ok(visitor)
+        }?;
+
+        visitor.post_visit_show_cardinality_statement(self)
+    }
+}
+
 impl Visitable for FieldList {
     fn accept<V: Visitor>(&self, visitor: V) -> Result<V, V::Error> {
         let visitor = match visitor.pre_visit_select_field_list(self)? {
@@ -1347,23 +1564,26 @@ mod test {
         WhereClause,
     };
     use crate::delete::DeleteStatement;
-    use crate::drop::DropMeasurementStatement;
+    use crate::drop::{DropMeasurementStatement, DropSeriesStatement};
     use crate::explain::ExplainStatement;
     use crate::expression::arithmetic::Expr;
     use crate::expression::conditional::ConditionalExpression;
     use crate::expression::{Binary, Call, ConditionalBinary, VarRef};
+    use crate::kill::KillQueryStatement;
     use crate::literal::Literal;
     use crate::select::{
-        Dimension, Field, FieldList, FillClause, FromMeasurementClause, GroupByClause,
+        Dimension, Field, FieldList, FillClause, FromMeasurementClause, GroupByClause, IntoClause,
         MeasurementSelection, SLimitClause, SOffsetClause, SelectStatement, TimeDimension,
         TimeZoneClause,
     };
     use crate::show::{OnClause, ShowDatabasesStatement};
+    use crate::show_cardinality::ShowCardinalityStatement;
     use crate::show_field_keys::ShowFieldKeysStatement;
     use crate::show_measurements::{
         ExtendedOnClause, ShowMeasurementsStatement, WithMeasurementClause,
     };
     use crate::show_retention_policies::ShowRetentionPoliciesStatement;
+    use crate::show_series::ShowSeriesStatement;
     use crate::show_tag_keys::ShowTagKeysStatement;
     use crate::show_tag_values::{ShowTagValuesStatement, WithKeyClause};
     use crate::simple_from_clause::{DeleteFromClause, ShowFromClause};
@@ -1411,7 +1631,9 @@ mod test {
         trace_visit!(delete_from_clause, DeleteFromClause);
         trace_visit!(measurement_name, MeasurementName);
         trace_visit!(drop_measurement_statement, DropMeasurementStatement);
+        trace_visit!(drop_series_statement, DropSeriesStatement);
         trace_visit!(explain_statement, ExplainStatement);
+        trace_visit!(kill_query_statement, KillQueryStatement);
         trace_visit!(select_statement, SelectStatement);
         trace_visit!(show_databases_statement, ShowDatabasesStatement);
         trace_visit!(show_measurements_statement, ShowMeasurementsStatement);
@@ -1422,6 +1644,8 @@ mod test {
         trace_visit!(show_tag_keys_statement, ShowTagKeysStatement);
         trace_visit!(show_tag_values_statement, ShowTagValuesStatement);
         trace_visit!(show_field_keys_statement, ShowFieldKeysStatement);
+        trace_visit!(show_series_statement, ShowSeriesStatement);
+        trace_visit!(show_cardinality_statement, ShowCardinalityStatement);
         trace_visit!(conditional_expression, ConditionalExpression);
         trace_visit!(expr, Expr);
         trace_visit!(select_field_list, FieldList);
@@ -1434,6 +1658,7 @@ mod test {
         trace_visit!(where_clause, WhereClause);
         trace_visit!(show_from_clause, ShowFromClause);
         trace_visit!(qualified_measurement_name, QualifiedMeasurementName);
+        trace_visit!(into_clause, IntoClause);
         trace_visit!(fill_clause, FillClause);
         trace_visit!(order_by_clause, OrderByClause);
         trace_visit!(limit_clause, LimitClause);
@@ -1472,11 +1697,22 @@ mod test {
         insta::assert_yaml_snapshot!(visit_statement!("DROP MEASUREMENT cpu"))
     }
 
+    #[test]
+    fn test_drop_series_statement() {
+        insta::assert_yaml_snapshot!(visit_statement!("DROP SERIES FROM a WHERE b = \"c\""));
+        insta::assert_yaml_snapshot!(visit_statement!("DROP SERIES WHERE b = \"c\""));
+    }
+
     #[test]
     fn test_explain_statement() {
         insta::assert_yaml_snapshot!(visit_statement!("EXPLAIN SELECT * FROM cpu"));
     }
 
+    #[test]
+    fn test_kill_query_statement() {
+        insta::assert_yaml_snapshot!(visit_statement!("KILL QUERY 36"));
+    }
+
     #[test]
     fn test_select_statement() {
         insta::assert_yaml_snapshot!(visit_statement!(r#"SELECT value FROM temp"#));
@@ -1553,4 +1789,23 @@ mod test {
         insta::assert_yaml_snapshot!(visit_statement!("SHOW FIELD KEYS FROM cpu"));
         insta::assert_yaml_snapshot!(visit_statement!("SHOW FIELD KEYS ON telegraf FROM /cpu/"));
     }
+
+    #[test]
+    fn test_show_series_statement() {
+        insta::assert_yaml_snapshot!(visit_statement!("SHOW SERIES"));
+        insta::assert_yaml_snapshot!(visit_statement!(
+            "SHOW SERIES ON telegraf FROM cpu LIMIT 5 OFFSET 10"
+        ));
+    }
+
+    #[test]
+    fn test_show_cardinality_statement() {
+        insta::assert_yaml_snapshot!(visit_statement!("SHOW SERIES EXACT CARDINALITY"));
+        insta::assert_yaml_snapshot!(visit_statement!(
+            "SHOW MEASUREMENT CARDINALITY ON telegraf FROM cpu"
+        ));
+        insta::assert_yaml_snapshot!(visit_statement!(
+            "SHOW TAG VALUES CARDINALITY FROM cpu WITH KEY = host LIMIT 5"
+        ));
+    }
 }
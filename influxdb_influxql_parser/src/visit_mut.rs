@@ -30,23 +30,26 @@ use crate::common::{
 };
 use crate::create::CreateDatabaseStatement;
 use crate::delete::DeleteStatement;
-use crate::drop::DropMeasurementStatement;
+use crate::drop::{DropMeasurementStatement, DropSeriesStatement};
 use crate::explain::ExplainStatement;
 use crate::expression::arithmetic::Expr;
 use crate::expression::conditional::ConditionalExpression;
 use crate::expression::{Binary, Call, ConditionalBinary, VarRef};
+use crate::kill::KillQueryStatement;
 use crate::literal::Literal;
 use crate::select::{
-    Dimension, Field, FieldList, FillClause, FromMeasurementClause, GroupByClause,
+    Dimension, Field, FieldList, FillClause, FromMeasurementClause, GroupByClause, IntoClause,
     MeasurementSelection, SLimitClause, SOffsetClause, SelectStatement, TimeDimension,
     TimeZoneClause,
 };
 use crate::show::{OnClause, ShowDatabasesStatement};
+use crate::show_cardinality::{CardinalityKind, ShowCardinalityStatement};
 use crate::show_field_keys::ShowFieldKeysStatement;
 use crate::show_measurements::{
     ExtendedOnClause, ShowMeasurementsStatement, WithMeasurementClause,
 };
 use crate::show_retention_policies::ShowRetentionPoliciesStatement;
+use crate::show_series::ShowSeriesStatement;
 use crate::show_tag_keys::ShowTagKeysStatement;
 use crate::show_tag_values::{ShowTagValuesStatement, WithKeyClause};
 use crate::simple_from_clause::{DeleteFromClause, ShowFromClause};
@@ -154,6 +157,22 @@ pub trait VisitorMut: Sized {
         Ok(())
     }
 
+    /// Invoked before any children of the `DROP SERIES` statement are visited.
+    fn pre_visit_drop_series_statement(
+        &mut self,
+        _n: &mut DropSeriesStatement,
+    ) -> Result<Recursion, Self::Error> {
+        Ok(Continue)
+    }
+
+    /// Invoked after all children of the `DROP SERIES` statement are visited.
+    fn post_visit_drop_series_statement(
+        &mut self,
+        _n: &mut DropSeriesStatement,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
     /// Invoked before any children of the `EXPLAIN` statement are visited.
     fn pre_visit_explain_statement(
         &mut self,
@@ -170,6 +189,22 @@ pub trait VisitorMut: Sized {
         Ok(())
     }
 
+    /// Invoked before any children of the `KILL QUERY` statement are visited.
+    fn pre_visit_kill_query_statement(
+        &mut self,
+        _n: &mut KillQueryStatement,
+    ) -> Result<Recursion, Self::Error> {
+        Ok(Continue)
+    }
+
+    /// Invoked after all children of the `KILL QUERY` statement are visited.
+    fn post_visit_kill_query_statement(
+        &mut self,
+        _n: &mut KillQueryStatement,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
     /// Invoked before any children of the `SELECT` statement are visited.
     fn pre_visit_select_statement(
         &mut self,
@@ -279,6 +314,38 @@ pub trait VisitorMut: Sized {
         Ok(())
     }
 
+    /// Invoked before any children of the `SHOW SERIES` statement are visited.
+    fn pre_visit_show_series_statement(
+        &mut self,
+        _n: &mut ShowSeriesStatement,
+    ) -> Result<Recursion, Self::Error> {
+        Ok(Continue)
+    }
+
+    /// Invoked after all children of the `SHOW SERIES` statement are visited.
+    fn post_visit_show_series_statement(
+        &mut self,
+        _n: &mut ShowSeriesStatement,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Invoked before any children of the `SHOW ... CARDINALITY` statement are visited.
+    fn pre_visit_show_cardinality_statement(
+        &mut self,
+        _n: &mut ShowCardinalityStatement,
+    ) -> Result<Recursion, Self::Error> {
+        Ok(Continue)
+    }
+
+    /// Invoked after all children of the `SHOW ... CARDINALITY` statement are visited.
+    fn post_visit_show_cardinality_statement(
+        &mut self,
+        _n: &mut ShowCardinalityStatement,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
     /// Invoked before any children of the conditional expression are visited.
     fn pre_visit_conditional_expression(
         &mut self,
@@ -438,6 +505,16 @@ pub trait VisitorMut: Sized {
         Ok(())
     }
 
+    /// Invoked before any children of the `INTO` clause are visited.
+    fn pre_visit_into_clause(&mut self, _n: &mut IntoClause) -> Result<Recursion, Self::Error> {
+        Ok(Continue)
+    }
+
+    /// Invoked after all children of the `INTO` clause are visited.
+    fn post_visit_into_clause(&mut self, _n: &mut IntoClause) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
     /// Invoked before any children of the `FILL` clause are visited.
     fn pre_visit_fill_clause(&mut self, _n: &mut FillClause) -> Result<Recursion, Self::Error> {
         Ok(Continue)
@@ -645,7 +722,9 @@ impl VisitableMut for Statement {
             Self::CreateDatabase(s) => s.accept(visitor),
             Self::Delete(s) => s.accept(visitor),
             Self::DropMeasurement(s) => s.accept(visitor),
+            Self::DropSeries(s) => s.accept(visitor),
             Self::Explain(s) => s.accept(visitor),
+            Self::KillQuery(s) => s.accept(visitor),
             Self::Select(s) => s.accept(visitor),
             Self::ShowDatabases(s) => s.accept(visitor),
             Self::ShowMeasurements(s) => s.accept(visitor),
@@ -653,6 +732,8 @@ impl VisitableMut for Statement {
             Self::ShowTagKeys(s) => s.accept(visitor),
             Self::ShowTagValues(s) => s.accept(visitor),
             Self::ShowFieldKeys(s) => s.accept(visitor),
+            Self::ShowSeries(s) => s.accept(visitor),
+            Self::ShowCardinality(s) => s.accept(visitor),
         }?;
 
         visitor.post_visit_statement(self)
@@ -736,6 +817,24 @@ impl VisitableMut for DropMeasurementStatement {
     }
 }
 
+impl VisitableMut for DropSeriesStatement {
+    fn accept<V: VisitorMut>(&mut self, visitor: &mut V) -> Result<(), V::Error> {
+        if let Stop = visitor.pre_visit_drop_series_statement(self)? {
+            return Ok(());
+        };
+
+        if let Some(from) = &mut self.from {
+            from.accept(visitor)?;
+        }
+
+        if let Some(condition) = &mut self.condition {
+            condition.accept(visitor)?;
+        }
+
+        visitor.post_visit_drop_series_statement(self)
+    }
+}
+
 impl VisitableMut for ExplainStatement {
     fn accept<V: VisitorMut>(&mut self, visitor: &mut V) -> Result<(), V::Error> {
         if let Stop = visitor.pre_visit_explain_statement(self)? {
@@ -748,6 +847,16 @@ impl VisitableMut for ExplainStatement {
     }
 }
 
+impl VisitableMut for KillQueryStatement {
+    fn accept<V: VisitorMut>(&mut self, visitor: &mut V) -> Result<(), V::Error> {
+        if let Stop = visitor.pre_visit_kill_query_statement(self)? {
+            return Ok(());
+        };
+
+        visitor.post_visit_kill_query_statement(self)
+    }
+}
+
 impl VisitableMut for SelectStatement {
     fn accept<V: VisitorMut>(&mut self, visitor: &mut V) -> Result<(), V::Error> {
         if let Stop = visitor.pre_visit_select_statement(self)? {
@@ -756,6 +865,10 @@ impl VisitableMut for SelectStatement {
 
         self.fields.accept(visitor)?;
 
+        if let Some(into) = &mut self.into {
+            into.accept(visitor)?;
+        }
+
         self.from.accept(visitor)?;
 
         if let Some(condition) = &mut self.condition {
@@ -848,6 +961,16 @@ impl VisitableMut for SOffsetClause {
     }
 }
 
+impl VisitableMut for IntoClause {
+    fn accept<V: VisitorMut>(&mut self, visitor: &mut V) -> Result<(), V::Error> {
+        if let Stop = visitor.pre_visit_into_clause(self)? {
+            return Ok(());
+        };
+
+        visitor.post_visit_into_clause(self)
+    }
+}
+
 impl VisitableMut for FillClause {
     fn accept<V: VisitorMut>(&mut self, visitor: &mut V) -> Result<(), V::Error> {
         if let Stop = visitor.pre_visit_fill_clause(self)? {
@@ -1065,6 +1188,70 @@ impl VisitableMut for ShowFieldKeysStatement {
     }
 }
 
+impl VisitableMut for ShowSeriesStatement {
+    fn accept<V: VisitorMut>(&mut self, visitor: &mut V) -> Result<(), V::Error> {
+        if let Stop = visitor.pre_visit_show_series_statement(self)? {
+            return Ok(());
+        };
+
+        if let Some(on_clause) = &mut self.database {
+            on_clause.accept(visitor)?;
+        }
+
+        if let Some(from) = &mut self.from {
+            from.accept(visitor)?;
+        }
+
+        if let Some(condition) = &mut self.condition {
+            condition.accept(visitor)?;
+        }
+
+        if let Some(limit) = &mut self.limit {
+            limit.accept(visitor)?;
+        }
+
+        if let Some(offset) = &mut self.offset {
+            offset.accept(visitor)?;
+        }
+
+        visitor.post_visit_show_series_statement(self)
+    }
+}
+
+impl VisitableMut for ShowCardinalityStatement {
+    fn accept<V: VisitorMut>(&mut self, visitor: &mut V) -> Result<(), V::Error> {
+        if let Stop = visitor.pre_visit_show_cardinality_statement(self)? {
+            return Ok(());
+        };
+
+        if let Some(on_clause) = &mut self.database {
+            on_clause.accept(visitor)?;
+        }
+
+        if let Some(from) = &mut self.from {
+            from.accept(visitor)?;
+        }
+
+        if let CardinalityKind::TagValues(with_key) = &mut self.kind {
+            with_key.accept(visitor)?;
+        }
+
+        if let Some(condition) = &mut self.condition {
+            condition.accept(visitor)?;
+        }
+
+        if let Some(limit) = &mut self.limit {
+            limit.accept(visitor)?;
+        }
+
+        if let Some(offset) = &mut self.offset {
+            offset.accept(visitor)?;
+        }
+
+        visitor.post_visit_show_cardinality_statement(self)
+    }
+}
+
 impl VisitableMut for FieldList {
     fn accept<V: VisitorMut>(&mut self, visitor: &mut V) -> Result<(), V::Error> {
         if let Stop = visitor.pre_visit_select_field_list(self)? {
@@ -1284,24 +1471,27 @@ mod test {
         WhereClause,
     };
     use crate::delete::DeleteStatement;
-    use crate::drop::DropMeasurementStatement;
+    use crate::drop::{DropMeasurementStatement, DropSeriesStatement};
     use crate::explain::ExplainStatement;
     use crate::expression::arithmetic::Expr;
     use crate::expression::conditional::ConditionalExpression;
     use crate::expression::{Binary, Call, ConditionalBinary, VarRef};
+    use crate::kill::KillQueryStatement;
     use crate::literal::Literal;
     use crate::parse_statements;
     use crate::select::{
-        Dimension, Field, FieldList, FillClause, FromMeasurementClause, GroupByClause,
+        Dimension, Field, FieldList, FillClause, FromMeasurementClause, GroupByClause, IntoClause,
         MeasurementSelection, SLimitClause, SOffsetClause, SelectStatement, TimeDimension,
         TimeZoneClause,
     };
     use crate::show::{OnClause, ShowDatabasesStatement};
+    use crate::show_cardinality::ShowCardinalityStatement;
     use crate::show_field_keys::ShowFieldKeysStatement;
     use crate::show_measurements::{
         ExtendedOnClause, ShowMeasurementsStatement, WithMeasurementClause,
     };
     use crate::show_retention_policies::ShowRetentionPoliciesStatement;
+    use crate::show_series::ShowSeriesStatement;
     use crate::show_tag_keys::ShowTagKeysStatement;
     use crate::show_tag_values::{ShowTagValuesStatement, WithKeyClause};
     use crate::simple_from_clause::{DeleteFromClause, ShowFromClause};
@@ -1347,7 +1537,9 @@ mod test {
         trace_visit!(delete_from_clause, DeleteFromClause);
         trace_visit!(measurement_name, MeasurementName);
         trace_visit!(drop_measurement_statement, DropMeasurementStatement);
+        trace_visit!(drop_series_statement, DropSeriesStatement);
         trace_visit!(explain_statement, ExplainStatement);
+        trace_visit!(kill_query_statement, KillQueryStatement);
         trace_visit!(select_statement, SelectStatement);
         trace_visit!(show_databases_statement, ShowDatabasesStatement);
         trace_visit!(show_measurements_statement, ShowMeasurementsStatement);
@@ -1358,6 +1550,8 @@ mod test {
         trace_visit!(show_tag_keys_statement, ShowTagKeysStatement);
         trace_visit!(show_tag_values_statement, ShowTagValuesStatement);
         trace_visit!(show_field_keys_statement, ShowFieldKeysStatement);
+        trace_visit!(show_series_statement, ShowSeriesStatement);
+        trace_visit!(show_cardinality_statement, ShowCardinalityStatement);
         trace_visit!(conditional_expression, ConditionalExpression);
         trace_visit!(expr, Expr);
         trace_visit!(select_field_list, FieldList);
@@ -1370,6 +1564,7 @@ mod test {
         trace_visit!(where_clause, WhereClause);
         trace_visit!(show_from_clause, ShowFromClause);
         trace_visit!(qualified_measurement_name, QualifiedMeasurementName);
+        trace_visit!(into_clause, IntoClause);
         trace_visit!(fill_clause, FillClause);
         trace_visit!(order_by_clause, OrderByClause);
         trace_visit!(limit_clause, LimitClause);
@@ -1410,11 +1605,22 @@ mod test {
         insta::assert_yaml_snapshot!(visit_statement!("DROP MEASUREMENT cpu"))
     }
 
+    #[test]
+    fn test_drop_series_statement() {
+        insta::assert_yaml_snapshot!(visit_statement!("DROP SERIES FROM a WHERE b = \"c\""));
+        insta::assert_yaml_snapshot!(visit_statement!("DROP SERIES WHERE b = \"c\""));
+    }
+
     #[test]
     fn test_explain_statement() {
         insta::assert_yaml_snapshot!(visit_statement!("EXPLAIN SELECT * FROM cpu"));
     }
 
+    #[test]
+    fn test_kill_query_statement() {
+        insta::assert_yaml_snapshot!(visit_statement!("KILL QUERY 36"));
+    }
+
     #[test]
     fn test_select_statement() {
         insta::assert_yaml_snapshot!(visit_statement!(r#"SELECT value FROM temp"#));
@@ -1492,6 +1698,25 @@ mod test {
         insta::assert_yaml_snapshot!(visit_statement!("SHOW FIELD KEYS ON telegraf FROM /cpu/"));
     }
 
+    #[test]
+    fn test_show_series_statement() {
+        insta::assert_yaml_snapshot!(visit_statement!("SHOW SERIES"));
+        insta::assert_yaml_snapshot!(visit_statement!(
+            "SHOW SERIES ON telegraf FROM cpu LIMIT 5 OFFSET 10"
+        ));
+    }
+
+    #[test]
+    fn test_show_cardinality_statement() {
+        insta::assert_yaml_snapshot!(visit_statement!("SHOW SERIES EXACT CARDINALITY"));
+        insta::assert_yaml_snapshot!(visit_statement!(
+            "SHOW MEASUREMENT CARDINALITY ON telegraf FROM cpu"
+        ));
+        insta::assert_yaml_snapshot!(visit_statement!(
+            "SHOW TAG VALUES CARDINALITY FROM cpu WITH KEY = host LIMIT 5"
+        ));
+    }
+
     #[test]
     fn test_mutability() {
         struct AddLimit;
@@ -0,0 +1,42 @@
+use influxdb_iox_client::{connection::Connection, flight};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Error cancelling query: {0}")]
+    Cancel(#[from] influxdb_iox_client::flight::Error),
+
+    #[error("Query {0} is not running")]
+    NotRunning(u64),
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Cancel a running query
+#[derive(Debug, clap::Parser)]
+pub struct Config {
+    /// The IOx namespace of the query
+    #[clap(action)]
+    namespace: String,
+
+    /// The ID of the query, as shown in the `system.queries` table
+    #[clap(action)]
+    query_id: u64,
+}
+
+pub async fn command(connection: Connection, config: Config) -> Result<()> {
+    let mut client = flight::Client::new(connection);
+
+    let Config {
+        namespace,
+        query_id,
+    } = config;
+
+    if !client.cancel_query(namespace, query_id).await? {
+        return Err(Error::NotRunning(query_id));
+    }
+
+    println!("Cancelled query {query_id}");
+
+    Ok(())
+}
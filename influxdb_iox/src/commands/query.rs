@@ -15,6 +15,9 @@ pub enum Error {
 
     #[error("Error formatting InfluxQL: {0}")]
     InfluxQlFormatting(#[from] influxdb_iox_client::format::influxql::Error),
+
+    #[error("{0} InfluxQL statement(s) failed")]
+    InfluxQlStatements(usize),
 }
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
@@ -84,10 +87,34 @@ pub async fn command(connection: Connection, config: Config) -> Result<()> {
         query_lang,
     } = config;
 
-    let mut query_results = match query_lang {
-        QueryLanguage::Sql => client.sql(namespace, query).await,
-        QueryLanguage::InfluxQL => client.influxql(namespace, query).await,
-    }?;
+    if let QueryLanguage::InfluxQL = query_lang {
+        // The query may contain multiple statements, whose results are
+        // printed in order.
+        let mut n_failed = 0;
+        for flight::InfluxQLStatementResult {
+            statement_id,
+            result,
+        } in client.influxql_statements(namespace, query).await?
+        {
+            match result {
+                Ok((schema, mut batches)) => {
+                    // preserve schema so we print table headers even for empty results
+                    batches.push(RecordBatch::new_empty(schema));
+                    print_batches(&query_lang, &format, &batches)?;
+                }
+                Err(e) => {
+                    n_failed += 1;
+                    eprintln!("Error in statement {statement_id}: {e}");
+                }
+            }
+        }
+        if n_failed > 0 {
+            return Err(Error::InfluxQlStatements(n_failed));
+        }
+        return Ok(());
+    }
+
+    let mut query_results = client.sql(namespace, query).await?;
 
     // It might be nice to do some sort of streaming write
     // rather than buffering the whole thing.
@@ -103,13 +130,21 @@ pub async fn command(connection: Connection, config: Config) -> Result<()> {
     // preserve schema so we print table headers even for empty results
     batches.push(RecordBatch::new_empty(schema));
 
-    match (query_lang, &format) {
+    print_batches(&query_lang, &format, &batches)
+}
+
+fn print_batches(
+    query_lang: &QueryLanguage,
+    format: &OutputFormat,
+    batches: &[RecordBatch],
+) -> Result<()> {
+    match (query_lang, format) {
         (QueryLanguage::InfluxQL, OutputFormat::Pretty) => {
-            write_columnar(std::io::stdout(), &batches, Options::default())?
+            write_columnar(std::io::stdout(), batches, Options::default())?
         }
         _ => {
-            let format: QueryOutputFormat = format.into();
-            let formatted_result = format.format(&batches)?;
+            let format: QueryOutputFormat = format.clone().into();
+            let formatted_result = format.format(batches)?;
             println!("{formatted_result}");
         }
     }
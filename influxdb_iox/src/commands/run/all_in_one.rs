@@ -492,6 +492,7 @@ impl Config {
             authz_address: authz_address.clone(),
             single_tenant_deployment,
             http_request_limit: 1_000,
+            partial_writes_enabled: false,
             ingester_addresses: ingester_addresses.clone(),
             new_namespace_retention_hours: None, // infinite retention
             namespace_autocreation_enabled: true,
@@ -536,6 +537,9 @@ impl Config {
             exec_mem_pool_bytes,
             ingester_circuit_breaker_threshold: u64::MAX, // never for all-in-one-mode
             datafusion_config: Default::default(),
+            router_address: Some(format!("http://{router_http_bind_address}")),
+            single_tenant_deployment,
+            router_grpc_address: Some(format!("http://{router_grpc_bind_address}")),
         };
 
         SpecializedConfig {
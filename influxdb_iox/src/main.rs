@@ -37,6 +37,7 @@ use trace_exporters::{
 };
 
 mod commands {
+    pub mod cancel_query;
     pub mod catalog;
     pub mod debug;
     pub mod namespace;
@@ -222,6 +223,9 @@ enum Command {
     /// Query the ingester only
     QueryIngester(commands::query_ingester::Config),
 
+    /// Cancel a running query
+    CancelQuery(commands::cancel_query::Config),
+
     /// Various commands for namespace manipulation
     Namespace(commands::namespace::Config),
 
@@ -375,6 +379,14 @@ fn main() -> Result<(), std::io::Error> {
                     std::process::exit(ReturnCode::Failure as _)
                 }
             }
+            Some(Command::CancelQuery(config)) => {
+                let _tracing_guard = handle_init_logs(init_simple_logs(log_verbose_count));
+                let connection = connection(grpc_host).await;
+                if let Err(e) = commands::cancel_query::command(connection, config).await {
+                    eprintln!("{e}");
+                    std::process::exit(ReturnCode::Failure as _)
+                }
+            }
             Some(Command::Namespace(config)) => {
                 let _tracing_guard = handle_init_logs(init_simple_logs(log_verbose_count));
                 let connection = connection(grpc_host).await;
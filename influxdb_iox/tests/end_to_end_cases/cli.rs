@@ -399,6 +399,54 @@ async fn influxql_error_handling() {
         .await
 }
 
+/// Test the query CLI command with multiple InfluxQL statements
+#[tokio::test]
+async fn influxql_multiple_statements() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let mut cluster = MiniCluster::create_shared(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocol("the_table,tag=A val=42i 1".into()),
+            Step::Custom(Box::new(|state: &mut StepTestState| {
+                async {
+                    let querier_addr = state.cluster().querier().querier_grpc_base().to_string();
+                    let namespace = state.cluster().namespace();
+
+                    // The results of each successful statement are printed,
+                    // and the failed statements reported.
+                    Command::cargo_bin("influxdb_iox")
+                        .unwrap()
+                        .arg("-h")
+                        .arg(&querier_addr)
+                        .arg("query")
+                        .arg("--lang")
+                        .arg("influxql")
+                        .arg("--format")
+                        .arg("table")
+                        .arg(namespace)
+                        .arg("SHOW TAG KEYS ON foo; SELECT val FROM the_table")
+                        .assert()
+                        .failure()
+                        .stdout(predicate::str::contains(
+                            "| the_table        | 1970-01-01T00:00:00.000000001Z | 42  |",
+                        ))
+                        .stderr(
+                            predicate::str::contains("Error in statement 0:")
+                                .and(predicate::str::contains("1 InfluxQL statement(s) failed")),
+                        );
+                }
+                .boxed()
+            })),
+        ],
+    )
+    .run()
+    .await
+}
+
 /// Test the query_ingester CLI command
 #[tokio::test]
 async fn query_ingester() {
@@ -1,7 +1,7 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, sync::Arc};
 
 use arrow::{
-    array::as_generic_binary_array,
+    array::{as_generic_binary_array, ArrayRef, Int64Array, StringArray},
     datatypes::{DataType, Schema, TimeUnit},
     record_batch::RecordBatch,
 };
@@ -158,6 +158,70 @@ async fn flightsql_prepared_query() {
     .await
 }
 
+#[tokio::test]
+async fn flightsql_prepared_query_with_params() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let table_name = "the_table";
+
+    // Set up the cluster  ====================================
+    let mut cluster = MiniCluster::create_shared(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocol(format!(
+                "{table_name},tag1=A,tag2=B val=42i 123456\n\
+                 {table_name},tag1=A,tag2=C val=43i 123457"
+            )),
+            Step::Custom(Box::new(move |state: &mut StepTestState| {
+                async move {
+                    let sql = format!("select * from {table_name} where tag2 = $1 and val > $2");
+                    let mut client = flightsql_client(state.cluster());
+
+                    let handle = client.prepare(sql).await.unwrap();
+
+                    // parameter types are inferred from the query
+                    let parameter_schema = handle.get_parameter_schema();
+                    let fields = parameter_schema.fields();
+                    assert_eq!(fields.len(), 2);
+                    assert_eq!(fields[0].name(), "$1");
+                    assert_eq!(fields[0].data_type(), &DataType::Utf8);
+                    assert_eq!(fields[1].name(), "$2");
+                    assert_eq!(fields[1].data_type(), &DataType::Int64);
+
+                    let params = RecordBatch::try_from_iter([
+                        ("$1", Arc::new(StringArray::from(vec!["C"])) as ArrayRef),
+                        ("$2", Arc::new(Int64Array::from(vec![1])) as ArrayRef),
+                    ])
+                    .unwrap();
+                    let stream = client
+                        .execute(handle.with_parameters(params))
+                        .await
+                        .unwrap();
+
+                    let batches = collect_stream(stream).await;
+                    insta::assert_yaml_snapshot!(
+                        batches_to_sorted_lines(&batches),
+                        @r###"
+                    ---
+                    - +------+------+--------------------------------+-----+
+                    - "| tag1 | tag2 | time                           | val |"
+                    - +------+------+--------------------------------+-----+
+                    - "| A    | C    | 1970-01-01T00:00:00.000123457Z | 43  |"
+                    - +------+------+--------------------------------+-----+
+                    "###
+                    );
+                }
+                .boxed()
+            })),
+        ],
+    )
+    .run()
+    .await
+}
+
 #[tokio::test]
 async fn flightsql_get_sql_infos() {
     test_helpers::maybe_start_logging();
@@ -507,28 +571,36 @@ async fn flightsql_get_tables() {
                     - "table_types:[]"
                     - "include_schema:false"
                     - "*********************"
-                    - +--------------+--------------------+-------------+------------+
-                    - "| catalog_name | db_schema_name     | table_name  | table_type |"
-                    - +--------------+--------------------+-------------+------------+
-                    - "| public       | information_schema | columns     | VIEW       |"
-                    - "| public       | information_schema | df_settings | VIEW       |"
-                    - "| public       | information_schema | tables      | VIEW       |"
-                    - "| public       | information_schema | views       | VIEW       |"
-                    - "| public       | iox                | the_table   | BASE TABLE |"
-                    - "| public       | system             | queries     | BASE TABLE |"
-                    - +--------------+--------------------+-------------+------------+
+                    - +--------------+--------------------+---------------+------------+
+                    - "| catalog_name | db_schema_name     | table_name    | table_type |"
+                    - +--------------+--------------------+---------------+------------+
+                    - "| public       | information_schema | columns       | VIEW       |"
+                    - "| public       | information_schema | df_settings   | VIEW       |"
+                    - "| public       | information_schema | tables        | VIEW       |"
+                    - "| public       | information_schema | views         | VIEW       |"
+                    - "| public       | iox                | the_table     | BASE TABLE |"
+                    - "| public       | system             | columns       | BASE TABLE |"
+                    - "| public       | system             | parquet_files | BASE TABLE |"
+                    - "| public       | system             | partitions    | BASE TABLE |"
+                    - "| public       | system             | queries       | BASE TABLE |"
+                    - "| public       | system             | tables        | BASE TABLE |"
+                    - +--------------+--------------------+---------------+------------+
                     - "catalog:None"
                     - "db_schema_filter_pattern:None"
                     - "table_name_filter_pattern:None"
                     - "table_types:[\"BASE TABLE\"]"
                     - "include_schema:false"
                     - "*********************"
-                    - +--------------+----------------+------------+------------+
-                    - "| catalog_name | db_schema_name | table_name | table_type |"
-                    - +--------------+----------------+------------+------------+
-                    - "| public       | iox            | the_table  | BASE TABLE |"
-                    - "| public       | system         | queries    | BASE TABLE |"
-                    - +--------------+----------------+------------+------------+
+                    - +--------------+----------------+---------------+------------+
+                    - "| catalog_name | db_schema_name | table_name    | table_type |"
+                    - +--------------+----------------+---------------+------------+
+                    - "| public       | iox            | the_table     | BASE TABLE |"
+                    - "| public       | system         | columns       | BASE TABLE |"
+                    - "| public       | system         | parquet_files | BASE TABLE |"
+                    - "| public       | system         | partitions    | BASE TABLE |"
+                    - "| public       | system         | queries       | BASE TABLE |"
+                    - "| public       | system         | tables        | BASE TABLE |"
+                    - +--------------+----------------+---------------+------------+
                     - "catalog:None"
                     - "db_schema_filter_pattern:None"
                     - "table_name_filter_pattern:None"
@@ -685,16 +757,20 @@ async fn flightsql_get_tables_matches_information_schema() {
                         get_tables_output,
                         @r###"
                     ---
-                    - +--------------+--------------------+-------------+------------+
-                    - "| catalog_name | db_schema_name     | table_name  | table_type |"
-                    - +--------------+--------------------+-------------+------------+
-                    - "| public       | information_schema | columns     | VIEW       |"
-                    - "| public       | information_schema | df_settings | VIEW       |"
-                    - "| public       | information_schema | tables      | VIEW       |"
-                    - "| public       | information_schema | views       | VIEW       |"
-                    - "| public       | iox                | the_table   | BASE TABLE |"
-                    - "| public       | system             | queries     | BASE TABLE |"
-                    - +--------------+--------------------+-------------+------------+
+                    - +--------------+--------------------+---------------+------------+
+                    - "| catalog_name | db_schema_name     | table_name    | table_type |"
+                    - +--------------+--------------------+---------------+------------+
+                    - "| public       | information_schema | columns       | VIEW       |"
+                    - "| public       | information_schema | df_settings   | VIEW       |"
+                    - "| public       | information_schema | tables        | VIEW       |"
+                    - "| public       | information_schema | views         | VIEW       |"
+                    - "| public       | iox                | the_table     | BASE TABLE |"
+                    - "| public       | system             | columns       | BASE TABLE |"
+                    - "| public       | system             | parquet_files | BASE TABLE |"
+                    - "| public       | system             | partitions    | BASE TABLE |"
+                    - "| public       | system             | queries       | BASE TABLE |"
+                    - "| public       | system             | tables        | BASE TABLE |"
+                    - +--------------+--------------------+---------------+------------+
                     "###
                     );
 
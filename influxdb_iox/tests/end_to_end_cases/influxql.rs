@@ -1,7 +1,11 @@
+use arrow::{array::Int64Array, util::pretty::pretty_format_batches};
 use futures::FutureExt;
+use observability_deps::tracing::info;
+use std::time::Duration;
+use test_helpers::{assert_contains, timeout::FutureTimeout};
 use test_helpers_end_to_end::{
     check_flight_error, maybe_skip_integration, try_run_influxql, Authorizer, MiniCluster, Step,
-    StepTest, StepTestState,
+    StepTest, StepTestState, TestConfig,
 };
 
 #[tokio::test]
@@ -32,7 +36,7 @@ async fn influxql_returns_error() {
                 query: "SHOW TAG KEYYYYYES".into(),
                 expected_error_code: tonic::Code::InvalidArgument,
                 expected_message:
-                    "Error while planning query: Error during planning: invalid SHOW TAG statement, expected KEYS or VALUES at pos 9"
+                    "Error while planning query: Error during planning: invalid SHOW TAG statement, expected KEY, KEYS or VALUES at pos 9"
                         .into(),
             },
         ],
@@ -41,6 +45,95 @@ async fn influxql_returns_error() {
     .await
 }
 
+#[tokio::test]
+async fn influxql_multiple_statements() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let table_name = "the_table";
+
+    // Set up the cluster  ====================================
+    let mut cluster = MiniCluster::create_shared(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocol(format!(
+                "{table_name},tag1=A,tag2=B val=42i 123456\n\
+                 {table_name},tag1=A,tag2=C val=43i 123457"
+            )),
+            Step::Custom(Box::new(move |state: &mut StepTestState| {
+                async move {
+                    let cluster = state.cluster();
+                    let mut client = influxdb_iox_client::flight::Client::new(
+                        cluster.querier().querier_grpc_connection(),
+                    );
+
+                    let results = client
+                        .influxql_statements(
+                            cluster.namespace(),
+                            format!(
+                                "SELECT tag1, val FROM {table_name}; \
+                                 SHOW TAG KEYS ON foo; \
+                                 SELECT val FROM {table_name} WHERE tag2 = 'C'"
+                            ),
+                        )
+                        .await
+                        .unwrap();
+                    assert_eq!(results.len(), 3);
+
+                    let actual = results
+                        .into_iter()
+                        .enumerate()
+                        .map(|(statement_id, result)| {
+                            assert_eq!(result.statement_id, statement_id);
+                            result
+                                .result
+                                .map(|(_, batches)| {
+                                    pretty_format_batches(&batches).unwrap().to_string()
+                                })
+                                .map_err(|e| e.to_string())
+                        })
+                        .collect::<Vec<_>>();
+
+                    assert_eq!(
+                        actual[0].as_deref().unwrap(),
+                        [
+                            "+------------------+--------------------------------+------+-----+",
+                            "| iox::measurement | time                           | tag1 | val |",
+                            "+------------------+--------------------------------+------+-----+",
+                            "| the_table        | 1970-01-01T00:00:00.000123456Z | A    | 42  |",
+                            "| the_table        | 1970-01-01T00:00:00.000123457Z | A    | 43  |",
+                            "+------------------+--------------------------------+------+-----+",
+                        ]
+                        .join("\n")
+                    );
+                    // The failed statement does not prevent the next one from
+                    // being executed.
+                    assert_contains!(
+                        actual[1].as_ref().unwrap_err(),
+                        "This feature is not implemented: SHOW TAG KEYS ON <database>"
+                    );
+                    assert_eq!(
+                        actual[2].as_deref().unwrap(),
+                        [
+                            "+------------------+--------------------------------+-----+",
+                            "| iox::measurement | time                           | val |",
+                            "+------------------+--------------------------------+-----+",
+                            "| the_table        | 1970-01-01T00:00:00.000123457Z | 43  |",
+                            "+------------------+--------------------------------+-----+",
+                        ]
+                        .join("\n")
+                    );
+                }
+                .boxed()
+            })),
+        ],
+    )
+    .run()
+    .await
+}
+
 #[tokio::test]
 async fn influxql_select_returns_results() {
     test_helpers::maybe_start_logging();
@@ -145,3 +238,215 @@ async fn authz() {
 
     authz.close().await;
 }
+
+#[tokio::test]
+async fn select_into() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let table_name = "the_table";
+
+    // Set up the authorizer  =================================
+    let mut authz = Authorizer::create().await;
+
+    // Set up the cluster  ====================================
+    let mut cluster = MiniCluster::create_non_shared_with_authz(database_url, authz.addr()).await;
+
+    let write_token = authz.create_token_for(cluster.namespace(), &["ACTION_READ", "ACTION_WRITE"]);
+    let read_token = authz.create_token_for(cluster.namespace(), &["ACTION_READ"]);
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocolWithAuthorization {
+                line_protocol: format!(
+                    "{table_name},tag1=A val=42i 123456\n\
+                 {table_name},tag1=A val=43i 123457"
+                ),
+                authorization: format!("Token {}", write_token.clone()),
+            },
+            Step::Custom(Box::new(move |state: &mut StepTestState| {
+                async move {
+                    let cluster = state.cluster();
+                    let err = try_run_influxql(
+                        format!("SELECT val INTO rollup FROM {table_name}"),
+                        cluster.namespace(),
+                        cluster.querier().querier_grpc_connection(),
+                        Some(format!("Bearer {read_token}").as_str()),
+                    )
+                    .await
+                    .unwrap_err();
+                    check_flight_error(
+                        err,
+                        tonic::Code::PermissionDenied,
+                        Some("Permission denied"),
+                    );
+                }
+                .boxed()
+            })),
+            Step::Custom(Box::new(move |state: &mut StepTestState| {
+                async move {
+                    let cluster = state.cluster();
+                    let authorization = format!("Bearer {write_token}");
+
+                    let (batches, _) = try_run_influxql(
+                        format!("SELECT val INTO rollup FROM {table_name}"),
+                        cluster.namespace(),
+                        cluster.querier().querier_grpc_connection(),
+                        Some(authorization.as_str()),
+                    )
+                    .await
+                    .unwrap();
+                    let written = batches
+                        .iter()
+                        .flat_map(|batch| {
+                            batch
+                                .column_by_name("written")
+                                .expect("written column")
+                                .as_any()
+                                .downcast_ref::<Int64Array>()
+                                .expect("written is an Int64 column")
+                                .values()
+                                .to_vec()
+                        })
+                        .collect::<Vec<_>>();
+                    assert_eq!(written, vec![2]);
+
+                    // The querier learns about the new table once its
+                    // namespace cache is refreshed, so poll until the points
+                    // written by the query are returned.
+                    let expected = [
+                        "+------------------+--------------------------------+-----+",
+                        "| iox::measurement | time                           | val |",
+                        "+------------------+--------------------------------+-----+",
+                        "| rollup           | 1970-01-01T00:00:00.000123456Z | 42  |",
+                        "| rollup           | 1970-01-01T00:00:00.000123457Z | 43  |",
+                        "+------------------+--------------------------------+-----+",
+                    ]
+                    .join("\n");
+                    async {
+                        loop {
+                            if let Ok((batches, _)) = try_run_influxql(
+                                "SELECT val FROM rollup",
+                                cluster.namespace(),
+                                cluster.querier().querier_grpc_connection(),
+                                Some(authorization.as_str()),
+                            )
+                            .await
+                            {
+                                let actual = pretty_format_batches(&batches).unwrap().to_string();
+                                if actual == expected {
+                                    break;
+                                }
+                                info!(%actual, "SELECT INTO results not yet visible");
+                            }
+                            tokio::time::sleep(Duration::from_millis(500)).await;
+                        }
+                    }
+                    .with_timeout_panic(Duration::from_secs(30))
+                    .await;
+                }
+                .boxed()
+            })),
+        ],
+    )
+    .run()
+    .await;
+
+    authz.close().await;
+}
+
+#[tokio::test]
+async fn select_into_multi_tenant() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let table_name = "the_table";
+
+    // Set up the cluster  ====================================
+    let ingester_config = TestConfig::new_ingester(&database_url);
+    let router_config = TestConfig::new_router(&ingester_config);
+    let querier_config =
+        TestConfig::new_querier(&ingester_config).with_router_address(&router_config);
+    let mut cluster = MiniCluster::new()
+        .with_ingester(ingester_config)
+        .await
+        .with_router(router_config)
+        .await
+        .with_querier(querier_config)
+        .await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocol(format!(
+                "{table_name},tag1=A val=42i 123456\n\
+                 {table_name},tag1=A val=43i 123457"
+            )),
+            Step::Custom(Box::new(move |state: &mut StepTestState| {
+                async move {
+                    let cluster = state.cluster();
+
+                    // The results are written to the "<org>_<bucket>"
+                    // namespace of the query using the v2 write API.
+                    let (batches, _) = try_run_influxql(
+                        format!("SELECT val INTO rollup FROM {table_name}"),
+                        cluster.namespace(),
+                        cluster.querier().querier_grpc_connection(),
+                        None,
+                    )
+                    .await
+                    .unwrap();
+                    let written = batches
+                        .iter()
+                        .flat_map(|batch| {
+                            batch
+                                .column_by_name("written")
+                                .expect("written column")
+                                .as_any()
+                                .downcast_ref::<Int64Array>()
+                                .expect("written is an Int64 column")
+                                .values()
+                                .to_vec()
+                        })
+                        .collect::<Vec<_>>();
+                    assert_eq!(written, vec![2]);
+
+                    let expected = [
+                        "+------------------+--------------------------------+-----+",
+                        "| iox::measurement | time                           | val |",
+                        "+------------------+--------------------------------+-----+",
+                        "| rollup           | 1970-01-01T00:00:00.000123456Z | 42  |",
+                        "| rollup           | 1970-01-01T00:00:00.000123457Z | 43  |",
+                        "+------------------+--------------------------------+-----+",
+                    ]
+                    .join("\n");
+                    async {
+                        loop {
+                            if let Ok((batches, _)) = try_run_influxql(
+                                "SELECT val FROM rollup",
+                                cluster.namespace(),
+                                cluster.querier().querier_grpc_connection(),
+                                None,
+                            )
+                            .await
+                            {
+                                let actual = pretty_format_batches(&batches).unwrap().to_string();
+                                if actual == expected {
+                                    break;
+                                }
+                                info!(%actual, "SELECT INTO results not yet visible");
+                            }
+                            tokio::time::sleep(Duration::from_millis(500)).await;
+                        }
+                    }
+                    .with_timeout_panic(Duration::from_secs(30))
+                    .await;
+                }
+                .boxed()
+            })),
+        ],
+    )
+    .run()
+    .await;
+}
@@ -453,6 +453,32 @@ mod influxql {
         .await;
     }
 
+    /// Test the INTEGRAL function.
+    #[tokio::test]
+    async fn integral() {
+        test_helpers::maybe_start_logging();
+
+        TestCase {
+            input: "cases/in/integral.influxql",
+            chunk_stage: ChunkStage::Ingester,
+        }
+        .run()
+        .await;
+    }
+
+    /// Test the MODE and SPREAD functions.
+    #[tokio::test]
+    async fn mode_spread() {
+        test_helpers::maybe_start_logging();
+
+        TestCase {
+            input: "cases/in/mode_spread.influxql",
+            chunk_stage: ChunkStage::Ingester,
+        }
+        .run()
+        .await;
+    }
+
     #[tokio::test]
     async fn influxql_metadata() {
         test_helpers::maybe_start_logging();
@@ -1406,6 +1406,34 @@ pub static SETUPS: Lazy<HashMap<SetupName, SetupSteps>> = Lazy::new(|| {
                 },
             ],
         ),
+        (
+            // Used for integral function tests for InfluxQL
+            "integral",
+            vec![
+                Step::RecordNumParquetFiles,
+                Step::WriteLineProtocol(
+                    include_str!("data/integral.lp").to_string()
+                ),
+                Step::Persist,
+                Step::WaitForPersisted {
+                    expected_increase: 1,
+                },
+            ],
+        ),
+        (
+            // Used for mode and spread function tests for InfluxQL
+            "mode_spread",
+            vec![
+                Step::RecordNumParquetFiles,
+                Step::WriteLineProtocol(
+                    include_str!("data/mode_spread.lp").to_string()
+                ),
+                Step::Persist,
+                Step::WaitForPersisted {
+                    expected_increase: 1,
+                },
+            ],
+        ),
         (
             "DuplicateDifferentDomains",
             (0..2)
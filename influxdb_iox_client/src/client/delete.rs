@@ -88,6 +88,7 @@ impl Client {
                     database_id,
                     table_name,
                     predicate: Some(predicate),
+                    table_id: 0,
                 }),
             })
             .await?;
@@ -1,21 +1,31 @@
 //! Client for InfluxDB IOx Flight API
 
-use std::{pin::Pin, task::Poll};
+use std::{collections::HashMap, pin::Pin, sync::Arc, task::Poll};
 
-use ::generated_types::influxdata::iox::querier::v1::{read_info::QueryType, ReadInfo};
+use ::generated_types::influxdata::iox::querier::v1::{
+    read_info::QueryType, CancelQueryRequest, CancelQueryResponse, QueryParamValue, ReadInfo,
+};
 use futures_util::{Stream, StreamExt};
 use prost::Message;
 use thiserror::Error;
 use tonic::metadata::{MetadataKey, MetadataMap, MetadataValue};
 
 use arrow::{
+    datatypes::SchemaRef,
     ipc::{self},
     record_batch::RecordBatch,
 };
 
 use rand::Rng;
 
-use arrow_flight::{decode::FlightRecordBatchStream, error::FlightError, FlightClient, Ticket};
+use arrow_flight::{
+    decode::{DecodedPayload, FlightDataDecoder, FlightRecordBatchStream},
+    error::FlightError,
+    Action, FlightClient, Ticket,
+};
+use schema::{
+    INFLUXQL_STATEMENT_ERRORS_STATUS_KEY, INFLUXQL_STATEMENT_ERROR_KEY, INFLUXQL_STATEMENT_ID_KEY,
+};
 
 use crate::connection::Connection;
 
@@ -69,6 +79,14 @@ pub enum Error {
     /// Unexpected schema change.
     #[error("Unexpected schema change")]
     UnexpectedSchemaChange,
+
+    /// The server returned no result for an action.
+    #[error("No result returned for action: {0}")]
+    NoActionResult(String),
+
+    /// The server returned an invalid InfluxQL statement ID.
+    #[error("Invalid InfluxQL statement ID: {0}")]
+    InvalidStatementId(String),
 }
 
 impl Error {
@@ -99,6 +117,11 @@ impl From<tonic::Status> for Error {
 /// For SQL queries, this client yields a stream of [`RecordBatch`]es
 /// with the same schema.
 ///
+/// The results of InfluxQL queries with multiple `;` separated statements
+/// are returned as a sequence of streams, one per statement, each starting
+/// with its own schema. [`Client::influxql_statements`] decodes them into
+/// an [`InfluxQLStatementResult`] per statement.
+///
 /// # Example
 ///
 /// ```rust,no_run
@@ -198,6 +221,8 @@ impl Client {
             query_type: QueryType::Sql.into(),
             flightsql_command: vec![],
             is_debug: false,
+            params: HashMap::new(),
+            query_handle: 0,
         };
 
         self.do_get_with_read_info(request).await
@@ -209,6 +234,19 @@ impl Client {
         &mut self,
         database: impl Into<String> + Send,
         influxql_query: impl Into<String> + Send,
+    ) -> Result<IOxRecordBatchStream, Error> {
+        self.influxql_with_params(database, influxql_query, HashMap::new())
+            .await
+    }
+
+    /// Query the given database with the given InfluxQL query, replacing
+    /// its bind parameters (such as `$host`) with the values in `params`,
+    /// keyed by name without the leading `$`.
+    pub async fn influxql_with_params(
+        &mut self,
+        database: impl Into<String> + Send,
+        influxql_query: impl Into<String> + Send,
+        params: HashMap<String, QueryParamValue>,
     ) -> Result<IOxRecordBatchStream, Error> {
         let request = ReadInfo {
             database: database.into(),
@@ -216,16 +254,49 @@ impl Client {
             query_type: QueryType::InfluxQl.into(),
             flightsql_command: vec![],
             is_debug: false,
+            params,
+            query_handle: 0,
         };
 
         self.do_get_with_read_info(request).await
     }
 
+    /// Query the given database with the given InfluxQL query, which may
+    /// contain multiple `;` separated statements, returning the results of
+    /// each statement.
+    ///
+    /// As in InfluxDB 1.x, a statement that fails does not prevent the
+    /// remaining statements from being executed, and its error message is
+    /// returned as its result.
+    pub async fn influxql_statements(
+        &mut self,
+        database: impl Into<String> + Send,
+        influxql_query: impl Into<String> + Send,
+    ) -> Result<Vec<InfluxQLStatementResult>, Error> {
+        let request = ReadInfo {
+            database: database.into(),
+            sql_query: influxql_query.into(),
+            query_type: QueryType::InfluxQl.into(),
+            flightsql_command: vec![],
+            is_debug: false,
+            params: HashMap::new(),
+            query_handle: 0,
+        };
+
+        let stream = self.do_get(request).await?;
+        collect_statement_results(stream.into_inner()).await
+    }
+
     /// Perform a lower level client read with the `ReadInfo`
     async fn do_get_with_read_info(
         &mut self,
         read_info: ReadInfo,
     ) -> Result<IOxRecordBatchStream, Error> {
+        self.do_get(read_info).await.map(IOxRecordBatchStream::new)
+    }
+
+    /// Call `DoGet` with the `ReadInfo` encoded as the ticket
+    async fn do_get(&mut self, read_info: ReadInfo) -> Result<FlightRecordBatchStream, Error> {
         // encode readinfo as bytes and send it
         let ticket = Ticket {
             ticket: read_info.encode_to_vec().into(),
@@ -233,10 +304,41 @@ impl Client {
         self.inner
             .do_get(ticket)
             .await
-            .map(IOxRecordBatchStream::new)
             .map_err(Error::ArrowFlightError)
     }
 
+    /// Cancel the running query with the ID `query_id` in the given
+    /// database, as shown in the `system.queries` table.
+    ///
+    /// Returns `false` if there is no such query or if it already
+    /// completed.
+    pub async fn cancel_query(
+        &mut self,
+        database: impl Into<String> + Send,
+        query_id: u64,
+    ) -> Result<bool, Error> {
+        const ACTION_TYPE: &str = "CancelQuery";
+
+        let request = CancelQueryRequest {
+            database: database.into(),
+            query_id,
+        };
+        let action = Action {
+            r#type: ACTION_TYPE.to_string(),
+            body: request.encode_to_vec().into(),
+        };
+
+        let body = self
+            .inner
+            .do_action(action)
+            .await?
+            .next()
+            .await
+            .ok_or_else(|| Error::NoActionResult(ACTION_TYPE.to_string()))??;
+
+        Ok(CancelQueryResponse::decode(body)?.cancelled)
+    }
+
     /// Perform a handshake with the server, returning Ok on success
     /// and Err if the server fails the handshake.
     ///
@@ -264,6 +366,77 @@ impl Client {
     }
 }
 
+/// The results of one statement of an InfluxQL query.
+#[derive(Debug)]
+pub struct InfluxQLStatementResult {
+    /// The index of the statement in the query.
+    pub statement_id: usize,
+
+    /// The schema and record batches returned by the statement, or the
+    /// error message it failed with.
+    pub result: Result<(SchemaRef, Vec<RecordBatch>), String>,
+}
+
+/// Collect the results of each statement of an InfluxQL query from the
+/// decoded `DoGet` response.
+async fn collect_statement_results(
+    mut stream: FlightDataDecoder,
+) -> Result<Vec<InfluxQLStatementResult>, Error> {
+    let mut results: Vec<InfluxQLStatementResult> = vec![];
+    while let Some(data) = stream.next().await {
+        let data = match data {
+            Ok(data) => data,
+            // The error ending the results of a query with failed statements,
+            // which were already reported by their own schema messages.
+            Err(FlightError::Tonic(status))
+                if status
+                    .metadata()
+                    .contains_key(INFLUXQL_STATEMENT_ERRORS_STATUS_KEY) =>
+            {
+                break
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        match data.payload {
+            DecodedPayload::None => {}
+            DecodedPayload::Schema(schema) => {
+                let metadata = schema.metadata();
+                // The results of a query with a single statement are not
+                // labelled with a statement ID.
+                let statement_id = match metadata.get(INFLUXQL_STATEMENT_ID_KEY) {
+                    Some(id) => id
+                        .parse()
+                        .map_err(|_| Error::InvalidStatementId(id.clone()))?,
+                    None => results.len(),
+                };
+                let result = match metadata.get(INFLUXQL_STATEMENT_ERROR_KEY) {
+                    Some(error) => Err(error.clone()),
+                    None => Ok((Arc::clone(&schema), vec![])),
+                };
+
+                // A statement that fails after returning some results is
+                // reported by a second schema message with the same ID.
+                match results.last_mut() {
+                    Some(last) if last.statement_id == statement_id => last.result = result,
+                    _ => results.push(InfluxQLStatementResult {
+                        statement_id,
+                        result,
+                    }),
+                }
+            }
+            DecodedPayload::RecordBatch(batch) => match results.last_mut() {
+                Some(InfluxQLStatementResult {
+                    result: Ok((_, batches)),
+                    ..
+                }) => batches.push(batch),
+                _ => return Err(Error::NoSchema),
+            },
+        }
+    }
+    Ok(results)
+}
+
 #[derive(Debug)]
 /// Translates errors from FlightErrors to IOx client errors,
 /// providing access to the underyling [`FlightRecordBatchStream`]
@@ -305,3 +478,127 @@ impl Stream for IOxRecordBatchStream {
             .map_err(Error::ArrowFlightError)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::{
+        array::Int64Array,
+        datatypes::{DataType, Field, Fields, Schema},
+        ipc::writer::IpcWriteOptions,
+    };
+    use arrow_flight::{encode::FlightDataEncoderBuilder, FlightData, SchemaAsIpc};
+    use futures_util::TryStreamExt;
+
+    /// Encode the results of the statement `statement_id`, returning
+    /// `values`.
+    async fn statement_data(statement_id: usize, values: Vec<i64>) -> Vec<FlightData> {
+        let schema = Arc::new(Schema::new_with_metadata(
+            vec![Field::new("val", DataType::Int64, false)],
+            HashMap::from([(
+                INFLUXQL_STATEMENT_ID_KEY.to_string(),
+                statement_id.to_string(),
+            )]),
+        ));
+        let batch = RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![Arc::new(Int64Array::from(values))],
+        )
+        .unwrap();
+        FlightDataEncoderBuilder::new()
+            .with_schema(schema)
+            .build(futures_util::stream::iter([Ok(batch)]))
+            .try_collect()
+            .await
+            .unwrap()
+    }
+
+    /// Encode the error message of the statement `statement_id`.
+    fn statement_error(statement_id: usize, error: &str) -> FlightData {
+        let schema = Schema::new_with_metadata(
+            Fields::empty(),
+            HashMap::from([
+                (
+                    INFLUXQL_STATEMENT_ID_KEY.to_string(),
+                    statement_id.to_string(),
+                ),
+                (INFLUXQL_STATEMENT_ERROR_KEY.to_string(), error.to_string()),
+            ]),
+        );
+        SchemaAsIpc::new(&schema, &IpcWriteOptions::default()).into()
+    }
+
+    async fn collect(
+        data: Vec<Result<FlightData, FlightError>>,
+    ) -> Result<Vec<InfluxQLStatementResult>, Error> {
+        let stream =
+            FlightRecordBatchStream::new_from_flight_data(futures_util::stream::iter(data));
+        collect_statement_results(stream.into_inner()).await
+    }
+
+    fn values(result: &InfluxQLStatementResult) -> Vec<i64> {
+        let (_, batches) = result.result.as_ref().unwrap();
+        batches
+            .iter()
+            .flat_map(|batch| {
+                batch
+                    .column(0)
+                    .as_any()
+                    .downcast_ref::<Int64Array>()
+                    .unwrap()
+                    .values()
+                    .to_vec()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_collect_statement_results() {
+        let mut status = tonic::Status::invalid_argument("2 of 4 InfluxQL statements failed");
+        status
+            .metadata_mut()
+            .insert(INFLUXQL_STATEMENT_ERRORS_STATUS_KEY, 2_usize.into());
+
+        let data = [
+            statement_data(0, vec![1, 2]).await,
+            vec![statement_error(1, "bad statement")],
+            statement_data(2, vec![3]).await,
+            vec![statement_error(2, "failed during execution")],
+            statement_data(3, vec![]).await,
+        ]
+        .into_iter()
+        .flatten()
+        .map(Ok)
+        .chain([Err(FlightError::Tonic(status))])
+        .collect();
+
+        let results = collect(data).await.unwrap();
+        assert_eq!(results.len(), 4);
+        for (statement_id, result) in results.iter().enumerate() {
+            assert_eq!(result.statement_id, statement_id);
+        }
+        assert_eq!(values(&results[0]), vec![1, 2]);
+        assert_eq!(results[1].result.as_ref().unwrap_err(), "bad statement");
+        assert_eq!(
+            results[2].result.as_ref().unwrap_err(),
+            "failed during execution"
+        );
+        assert_eq!(values(&results[3]), Vec::<i64>::new());
+    }
+
+    #[tokio::test]
+    async fn test_collect_statement_results_error() {
+        // Errors not reporting failed statements fail the query.
+        let data = statement_data(0, vec![1])
+            .await
+            .into_iter()
+            .map(Ok)
+            .chain([Err(FlightError::Tonic(tonic::Status::cancelled(
+                "Query cancelled",
+            )))])
+            .collect();
+
+        let err = collect(data).await.unwrap_err();
+        assert_eq!(err.tonic_status().unwrap().code(), tonic::Code::Cancelled);
+    }
+}
@@ -23,9 +23,13 @@
 
 use std::sync::Arc;
 
-use arrow::datatypes::{Schema, SchemaRef};
+use arrow::{
+    datatypes::{Schema, SchemaRef},
+    record_batch::RecordBatch,
+};
 use arrow_flight::{
     decode::FlightRecordBatchStream,
+    encode::FlightDataEncoderBuilder,
     error::{FlightError, Result},
     sql::{
         ActionCreatePreparedStatementRequest, ActionCreatePreparedStatementResult, Any,
@@ -34,7 +38,7 @@ use arrow_flight::{
         CommandGetTables, CommandGetXdbcTypeInfo, CommandPreparedStatementQuery,
         CommandStatementQuery, ProstMessageExt,
     },
-    Action, FlightClient, FlightDescriptor, FlightInfo, IpcMessage, Ticket,
+    Action, FlightClient, FlightDescriptor, FlightInfo, IpcMessage, PutResult, Ticket,
 };
 use bytes::Bytes;
 use futures_util::TryStreamExt;
@@ -490,15 +494,11 @@ impl FlightSqlClient {
         ))
     }
 
-    /// Execute a SQL query on the server using [`CommandStatementQuery`]
-    ///
-    /// This involves two round trips
+    /// Execute a prepared statement on the server using [`CommandPreparedStatementQuery`]
     ///
-    /// Step 1: send a [`CommandStatementQuery`] message to the
-    /// `GetFlightInfo` endpoint of the FlightSQL server to receive a
-    /// FlightInfo descriptor.
-    ///
-    /// Step 2: Fetch the results described in the [`FlightInfo`]
+    /// If parameters have been set with
+    /// [`PreparedStatement::with_parameters`], they are first bound
+    /// by sending them to the `DoPut` endpoint of the FlightSQL server.
     ///
     /// This implementation does not support alternate endpoints
     pub async fn execute(
@@ -509,8 +509,16 @@ impl FlightSqlClient {
             prepared_statement_handle,
             dataset_schema: _,
             parameter_schema: _,
+            parameter_binding,
         } = statement;
-        // TODO handle parameters (via DoPut)
+
+        let prepared_statement_handle = match parameter_binding {
+            Some(params) => {
+                self.bind_parameters(prepared_statement_handle, params)
+                    .await?
+            }
+            None => prepared_statement_handle,
+        };
 
         let cmd = CommandPreparedStatementQuery {
             prepared_statement_handle,
@@ -518,6 +526,62 @@ impl FlightSqlClient {
 
         self.do_get_with_cmd(cmd.as_any()).await
     }
+
+    /// Send `params` for the prepared statement with
+    /// `prepared_statement_handle` to the `DoPut` endpoint, and return
+    /// the handle to use to execute the statement.
+    async fn bind_parameters(
+        &mut self,
+        prepared_statement_handle: Bytes,
+        params: RecordBatch,
+    ) -> Result<Bytes> {
+        let cmd = CommandPreparedStatementQuery {
+            prepared_statement_handle: prepared_statement_handle.clone(),
+        };
+        let descriptor = FlightDescriptor::new_cmd(cmd.as_any().encode_to_vec());
+        let flight_data = FlightDataEncoderBuilder::new()
+            .with_flight_descriptor(Some(descriptor))
+            .build(futures_util::stream::iter([Ok(params)]));
+
+        let results: Vec<PutResult> = self.inner.do_put(flight_data).await?.try_collect().await?;
+
+        // The server may return an updated handle that carries the parameters
+        for PutResult { app_metadata } in results {
+            if app_metadata.is_empty() {
+                continue;
+            }
+            let response: arrow_flight::sql::Any = Message::decode(app_metadata.as_ref())
+                .map_err(|e| FlightError::ExternalError(Box::new(e)))?;
+            if response.type_url != DO_PUT_PREPARED_STATEMENT_RESULT_TYPE_URL {
+                return Err(FlightError::ProtocolError(format!(
+                    "Expected DoPutPreparedStatementResult message but got {} instead",
+                    response.type_url
+                )));
+            }
+            let result = DoPutPreparedStatementResult::decode(response.value)
+                .map_err(|e| FlightError::ExternalError(Box::new(e)))?;
+            if let Some(handle) = result.prepared_statement_handle {
+                return Ok(handle);
+            }
+        }
+
+        Ok(prepared_statement_handle)
+    }
+}
+
+/// The type URL of [`DoPutPreparedStatementResult`] when packed in an
+/// [`Any`] message.
+const DO_PUT_PREPARED_STATEMENT_RESULT_TYPE_URL: &str =
+    "type.googleapis.com/arrow.flight.protocol.sql.DoPutPreparedStatementResult";
+
+/// Result of binding parameters to a prepared statement via `DoPut`.
+///
+/// Not yet provided by the arrow-flight crate.
+#[derive(Clone, PartialEq, Message)]
+struct DoPutPreparedStatementResult {
+    /// The handle to use to execute the prepared statement, if changed
+    #[prost(bytes = "bytes", optional, tag = "1")]
+    prepared_statement_handle: Option<Bytes>,
 }
 
 fn schema_bytes_to_schema(schema: Bytes) -> Result<SchemaRef> {
@@ -541,6 +605,9 @@ pub struct PreparedStatement {
 
     /// Schema of parameters, if any
     parameter_schema: SchemaRef,
+
+    /// Parameter values to bind before execution, if any
+    parameter_binding: Option<RecordBatch>,
 }
 
 impl PreparedStatement {
@@ -556,6 +623,7 @@ impl PreparedStatement {
             prepared_statement_handle,
             dataset_schema,
             parameter_schema,
+            parameter_binding: None,
         }
     }
 
@@ -568,4 +636,12 @@ impl PreparedStatement {
     pub fn get_parameter_schema(&self) -> SchemaRef {
         Arc::clone(&self.parameter_schema)
     }
+
+    /// Set the values for the parameters of the query: a single row
+    /// with one column per placeholder, matching the
+    /// [parameter schema](Self::get_parameter_schema)
+    pub fn with_parameters(mut self, params: RecordBatch) -> Self {
+        self.parameter_binding = Some(params);
+        self
+    }
 }
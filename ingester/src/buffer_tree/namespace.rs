@@ -186,6 +186,14 @@ where
                         .await?;
                 }
             }
+            IngestOp::Delete(delete) => {
+                // There is nothing to delete if no data has been buffered for
+                // the table.
+                if let Some(table_data) = self.table(delete.table()) {
+                    let released = table_data.apply_delete(delete.predicate(), delete.reference());
+                    delete.release(&released);
+                }
+            }
         }
 
         Ok(())
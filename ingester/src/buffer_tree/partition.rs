@@ -3,8 +3,8 @@
 use std::sync::Arc;
 
 use data_types::{
-    sequence_number_set::SequenceNumberSet, NamespaceId, PartitionKey, SequenceNumber,
-    SortedColumnSet, TableId, TimestampMinMax, TransitionPartitionId,
+    sequence_number_set::SequenceNumberSet, DeletePredicate, NamespaceId, PartitionKey,
+    SequenceNumber, SortedColumnSet, TableId, TimestampMinMax, TransitionPartitionId,
 };
 use mutable_batch::MutableBatch;
 use observability_deps::tracing::*;
@@ -18,11 +18,13 @@ use self::{
 };
 use super::{namespace::NamespaceName, table::metadata::TableMetadata, BufferWriteError};
 use crate::{
-    deferred_load::DeferredLoad, query::projection::OwnedProjection, query_adaptor::QueryAdaptor,
+    deferred_load::DeferredLoad, dml_payload::DeleteReference, query::projection::OwnedProjection,
+    query_adaptor::QueryAdaptor,
 };
 
 mod buffer;
 pub(crate) mod counter;
+mod delete;
 pub(crate) mod persisting;
 mod persisting_list;
 pub(crate) mod resolver;
@@ -104,6 +106,10 @@ pub struct PartitionData {
     /// A [`DataBuffer`] for incoming writes.
     buffer: DataBuffer,
 
+    /// The references of the deletes applied to the rows in `buffer`, held
+    /// until the buffer is persisted.
+    buffer_deletes: Vec<DeleteReference>,
+
     /// The currently persisting [`DataBuffer`] instances, if any.
     ///
     /// This queue is ordered from newest at the head, to oldest at the tail -
@@ -153,6 +159,7 @@ impl PartitionData {
             table_id,
             table,
             buffer: DataBuffer::default(),
+            buffer_deletes: Vec::new(),
             persisting: PersistingList::default(),
             started_persistence_count: BatchIdent::default(),
             completed_persistence_count: 0,
@@ -200,6 +207,67 @@ impl PartitionData {
         Ok(())
     }
 
+    /// Remove all rows matching `pred` from the data in this partition.
+    ///
+    /// Rows in the "hot" buffer are removed immediately, while the delete is
+    /// applied to currently persisting data at query time - the persisted
+    /// parquet files are covered by the catalog tombstone instead.
+    ///
+    /// A clone of the delete `reference` is held by each buffer the delete is
+    /// applied to, and released by [`Self::mark_persisted()`] once that buffer
+    /// is persisted.
+    ///
+    /// If all the rows in the "hot" buffer are deleted, the buffer is
+    /// discarded and the returned [`SequenceNumberSet`] contains the IDs of
+    /// the writes that produced them, and those of the deletes applied to it
+    /// that are no longer referenced by any other buffer - these no longer
+    /// need to be retained in the WAL, as they will never be persisted.
+    pub(crate) fn apply_delete(
+        &mut self,
+        pred: &Arc<DeletePredicate>,
+        reference: &DeleteReference,
+    ) -> SequenceNumberSet {
+        let mut released = SequenceNumberSet::default();
+        if self.is_empty() {
+            return released;
+        }
+
+        if self.buffer.rows() > 0 {
+            self.buffer.apply_delete(pred);
+            if self.buffer.rows() > 0 {
+                self.buffer_deletes.push(reference.clone());
+            } else {
+                released = std::mem::take(&mut self.buffer).into_sequence_number_set();
+                for id in std::mem::take(&mut self.buffer_deletes)
+                    .into_iter()
+                    .filter_map(DeleteReference::release)
+                {
+                    released.add(id);
+                }
+            }
+        }
+        self.persisting.apply_delete(pred, reference);
+
+        // The delete may have removed all the buffered rows, in which case the
+        // permit must be returned to the per-namespace pool.
+        if self.persisting.is_empty() && self.buffer.rows() == 0 {
+            self.partition_counter.dec();
+            self.is_empty = true;
+        }
+
+        trace!(
+            namespace_id = %self.namespace_id,
+            table_id = %self.table_id,
+            table = %self.table,
+            partition_id = %self.partition_id,
+            partition_key = %self.partition_key,
+            n_released = released.len(),
+            "applied delete"
+        );
+
+        released
+    }
+
     /// Return an estimated cost of persisting the data buffered in this
     /// [`PartitionData`].
     pub(crate) fn persist_cost_estimate(&self) -> usize {
@@ -210,7 +278,8 @@ impl PartitionData {
     ///
     /// The returned value will always match the row count of the data returned
     /// by a subsequent call to [`PartitionData::get_query_data()`], without any
-    /// column projection applied.
+    /// column projection applied, unless a delete has been applied to the
+    /// persisting data, in which case it is an upper bound.
     ///
     /// This value is inclusive of "hot" buffered data, and all currently
     /// persisting data.
@@ -313,8 +382,12 @@ impl PartitionData {
             "read partition data"
         );
 
+        // Deletes applied to persisting data are evaluated at query time,
+        // making the cached row count and timestamp statistics upper bounds.
+        let has_deletes = self.persisting.has_deletes();
+
         if data.is_empty() {
-            debug_assert_eq!(self.rows(), 0);
+            debug_assert!(has_deletes || self.rows() == 0);
             return None;
         }
 
@@ -337,13 +410,18 @@ impl PartitionData {
         // the row count reported by the rows() method.
         //
         // The row count is never affected by projection.
-        debug_assert_eq!(q.num_rows(), self.rows() as u64);
+        debug_assert!(
+            q.num_rows() == self.rows() as u64
+                || (has_deletes && q.num_rows() < self.rows() as u64)
+        );
 
         // Invariant: the timestamp min/max MUST match the values reported by
         // timestamp_stats(), iff the projection contains the "time" column.
         #[cfg(debug_assertions)]
         {
-            if projection
+            if has_deletes {
+                // The statistics are upper bounds.
+            } else if projection
                 .columns()
                 .map(|v| v.iter().any(|v| v == schema::TIME_COLUMN_NAME))
                 .unwrap_or(true)
@@ -407,7 +485,8 @@ impl PartitionData {
 
         // Push the buffer into the persisting list (which maintains batch
         // order).
-        self.persisting.push(batch_ident, fsm);
+        self.persisting
+            .push(batch_ident, fsm, std::mem::take(&mut self.buffer_deletes));
 
         // Invariant: the partition must not be marked as empty when there's an
         // entry in the persisting list.
@@ -433,7 +512,7 @@ impl PartitionData {
         // persisting data as complete.
         debug_assert!(!self.is_empty());
 
-        let fsm = self.persisting.remove(batch.batch_ident());
+        let (fsm, deletes) = self.persisting.remove(batch.batch_ident());
 
         self.completed_persistence_count += 1;
 
@@ -464,8 +543,13 @@ impl PartitionData {
             self.is_empty = true;
         }
 
-        // Return the set of IDs this buffer contained.
-        fsm.into_sequence_number_set()
+        // Return the set of IDs this buffer contained, and those of the deletes
+        // applied to it that are no longer referenced by any other buffer.
+        let mut set = fsm.into_sequence_number_set();
+        for id in deletes.into_iter().filter_map(DeleteReference::release) {
+            set.add(id);
+        }
+        set
     }
 
     pub(crate) fn partition_id(&self) -> &TransitionPartitionId {
@@ -547,7 +631,11 @@ mod tests {
     use super::*;
     use crate::{
         buffer_tree::partition::resolver::SortKeyResolver,
-        test_util::{populate_catalog, PartitionDataBuilder, ARBITRARY_TRANSITION_PARTITION_ID},
+        dml_payload::DeleteOperation,
+        test_util::{
+            populate_catalog, PartitionDataBuilder, ARBITRARY_NAMESPACE_ID, ARBITRARY_TABLE_ID,
+            ARBITRARY_TRANSITION_PARTITION_ID,
+        },
     };
 
     // Write some data and read it back from the buffer.
@@ -715,6 +803,85 @@ mod tests {
         assert!(p.is_empty());
     }
 
+    // Ensure deletes are applied to both the buffered and persisting data, and
+    // that the sequence numbers of buffered writes whose rows were all deleted
+    // are released immediately.
+    #[tokio::test]
+    async fn test_delete() {
+        let mut p = PartitionDataBuilder::new().build();
+
+        let mb =
+            lp_to_mutable_batch("bananas,city=London people=2 10\nbananas,city=Madrid people=4 20")
+                .1;
+        p.buffer_write(mb, SequenceNumber::new(1))
+            .expect("write should succeed");
+
+        let persisting_data = p.mark_persisting().expect("must contain existing data");
+
+        let mb = lp_to_mutable_batch("bananas,city=London people=6 30").1;
+        p.buffer_write(mb, SequenceNumber::new(2))
+            .expect("write should succeed");
+
+        // Delete all the London rows, spanning both the persisting and buffered
+        // data.
+        let pred = Arc::new(
+            predicate::delete_predicate::parse_delete_predicate("0", "100", r#"city="London""#)
+                .unwrap(),
+        );
+        let op = DeleteOperation::new(
+            ARBITRARY_NAMESPACE_ID,
+            ARBITRARY_TABLE_ID,
+            Arc::clone(&pred),
+            SequenceNumber::new(10),
+            None,
+        );
+        let released = p.apply_delete(&pred, op.reference());
+
+        // The buffered write was entirely deleted, so it is released
+        // immediately, while the persisting data holds a reference to the
+        // delete.
+        assert_eq!(released.len(), 1);
+        assert!(released.contains(SequenceNumber::new(2)));
+        assert_eq!(op.reference().clone().release(), None);
+
+        let data = p
+            .get_query_data(&OwnedProjection::default())
+            .expect("should contain data");
+        let expected = [
+            "+--------+--------+--------------------------------+",
+            "| city   | people | time                           |",
+            "+--------+--------+--------------------------------+",
+            "| Madrid | 4.0    | 1970-01-01T00:00:00.000000020Z |",
+            "+--------+--------+--------------------------------+",
+        ];
+        assert_batches_eq!(expected, data.record_batches());
+
+        // The data being persisted is unchanged, as the catalog tombstone
+        // covers the resulting parquet file.
+        assert_eq!(persisting_data.record_batches()[0].num_rows(), 2);
+
+        // The buffer is now empty, so there is nothing further to persist.
+        assert!(p.mark_persisting().is_none());
+
+        // The delete is released once the persisting data it was applied to
+        // is persisted.
+        drop(op);
+        let set = p.mark_persisted(persisting_data);
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(SequenceNumber::new(1)));
+        assert!(set.contains(SequenceNumber::new(10)));
+        assert!(p.is_empty());
+
+        // Subsequent persists are unaffected by the delete.
+        let mb = lp_to_mutable_batch("bananas,city=Paris people=8 40").1;
+        p.buffer_write(mb, SequenceNumber::new(3))
+            .expect("write should succeed");
+        let data = p.mark_persisting().expect("must contain existing data");
+        let set = p.mark_persisted(data);
+        assert_eq!(set.len(), 1);
+        assert!(set.contains(SequenceNumber::new(3)));
+    }
+
     // Ensure the ordering of snapshots & persisting data is preserved such that
     // updates resolve correctly, and batch identifiers are correctly allocated
     // and validated in mark_persisted() calls which return the correct
@@ -1,5 +1,9 @@
+use std::sync::Arc;
+
 use arrow::record_batch::RecordBatch;
-use data_types::{SequenceNumber, TimestampMinMax};
+use data_types::{
+    sequence_number_set::SequenceNumberSet, DeletePredicate, SequenceNumber, TimestampMinMax,
+};
 use mutable_batch::MutableBatch;
 
 mod always_some;
@@ -56,6 +60,16 @@ impl DataBuffer {
         })
     }
 
+    /// Remove the buffered rows matching `pred`.
+    pub(crate) fn apply_delete(&mut self, pred: &Arc<DeletePredicate>) {
+        self.0.mutate(|fsm| match fsm {
+            FsmState::Buffering(mut b) => {
+                b.apply_delete(pred);
+                (FsmState::Buffering(b), ())
+            }
+        })
+    }
+
     pub(crate) fn persist_cost_estimate(&self) -> usize {
         match self.0.get() {
             FsmState::Buffering(b) => b.persist_cost_estimate(),
@@ -96,6 +110,14 @@ impl DataBuffer {
         }
     }
 
+    /// Consume this buffer, returning the [`SequenceNumberSet`] of the writes
+    /// applied to it.
+    pub(crate) fn into_sequence_number_set(self) -> SequenceNumberSet {
+        match self.0.into_inner() {
+            FsmState::Buffering(b) => b.sequence_number_set().clone(),
+        }
+    }
+
     // Deconstruct the [`DataBuffer`] into the underlying FSM in a
     // [`Persisting`] state, if the buffer contains any data.
     pub(crate) fn into_persisting(self) -> Option<BufferState<Persisting>> {
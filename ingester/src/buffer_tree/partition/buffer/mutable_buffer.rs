@@ -29,6 +29,16 @@ impl Buffer {
         Ok(())
     }
 
+    /// Replace the buffered data with `batch`, which MUST contain a subset of
+    /// the rows currently buffered.
+    ///
+    /// If `batch` contains no rows, this [`Buffer`] becomes empty.
+    pub(super) fn replace(&mut self, batch: MutableBatch) {
+        debug_assert!(batch.rows() <= self.buffer().map(|v| v.rows()).unwrap_or_default());
+
+        self.buffer = (batch.rows() > 0).then_some(batch);
+    }
+
     /// Generates a [`RecordBatch`] from the data in this [`Buffer`].
     ///
     /// If this [`Buffer`] is empty when this method is called, the call is a
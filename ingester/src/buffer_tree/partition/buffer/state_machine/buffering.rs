@@ -1,15 +1,20 @@
 //! A write buffer.
 
+use std::sync::Arc;
+
 use arrow::record_batch::RecordBatch;
-use data_types::{StatValues, TimestampMinMax};
+use data_types::{DeletePredicate, StatValues, TimestampMinMax};
 use mutable_batch::{column::ColumnData, MutableBatch};
 use schema::{Projection, TIME_COLUMN_NAME};
 
 use super::{snapshot::Snapshot, BufferState, Transition};
 use crate::{
-    buffer_tree::partition::buffer::{
-        mutable_buffer::Buffer,
-        traits::{Queryable, Writeable},
+    buffer_tree::partition::{
+        buffer::{
+            mutable_buffer::Buffer,
+            traits::{Queryable, Writeable},
+        },
+        delete::filter_mutable_batch_delete,
     },
     query::projection::OwnedProjection,
 };
@@ -98,6 +103,23 @@ impl BufferState<Buffering> {
     pub(crate) fn persist_cost_estimate(&self) -> usize {
         self.state.buffer.persist_cost_estimate()
     }
+
+    /// Remove the buffered rows matching `pred`.
+    ///
+    /// If all buffered rows are deleted, the buffer becomes empty but retains
+    /// the [`SequenceNumber`] of the writes applied to it.
+    ///
+    /// [`SequenceNumber`]: data_types::SequenceNumber
+    pub(crate) fn apply_delete(&mut self, pred: &Arc<DeletePredicate>) {
+        if let Some(filtered) = self
+            .state
+            .buffer
+            .buffer()
+            .and_then(|b| filter_mutable_batch_delete(b, pred))
+        {
+            self.state.buffer.replace(filtered);
+        }
+    }
 }
 
 /// Perform an O(1) extraction of the timestamp column statistics.
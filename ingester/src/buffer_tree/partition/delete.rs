@@ -0,0 +1,163 @@
+//! Application of predicate deletes to buffered partition data.
+
+use std::{ops::Range, sync::Arc};
+
+use arrow::{
+    array::{as_boolean_array, Array, BooleanArray},
+    compute::filter_record_batch,
+    record_batch::RecordBatch,
+};
+use data_types::DeletePredicate;
+use iox_query::util::df_keep_rows_physical_expr;
+use mutable_batch::MutableBatch;
+use observability_deps::tracing::*;
+use schema::Projection;
+
+/// Evaluate `preds` against `batch`, returning a mask selecting the rows that
+/// are not deleted, or [`None`] if no row can match any of `preds`.
+fn keep_mask(batch: &RecordBatch, preds: &[Arc<DeletePredicate>]) -> Option<BooleanArray> {
+    let expr = df_keep_rows_physical_expr(batch.schema(), preds)?;
+
+    let mask = match expr.evaluate(batch) {
+        Ok(v) => v.into_array(batch.num_rows()),
+        Err(e) => {
+            error!(%e, "cannot evaluate delete predicates");
+            return None;
+        }
+    };
+
+    Some(as_boolean_array(&mask).clone())
+}
+
+/// Remove the rows matching any of `preds` from `batch`.
+pub(super) fn filter_record_batch_deletes(
+    batch: RecordBatch,
+    preds: &[Arc<DeletePredicate>],
+) -> RecordBatch {
+    match keep_mask(&batch, preds) {
+        Some(mask) => filter_record_batch(&batch, &mask).expect("mask length matches batch"),
+        None => batch,
+    }
+}
+
+/// Remove the rows matching `pred` from `batch`.
+///
+/// Returns [`None`] if no rows in `batch` match `pred`, and otherwise the
+/// remaining rows - which may be none at all.
+pub(super) fn filter_mutable_batch_delete(
+    batch: &MutableBatch,
+    pred: &Arc<DeletePredicate>,
+) -> Option<MutableBatch> {
+    let rb = batch
+        .to_arrow(Projection::All)
+        .expect("failed to convert buffer data");
+
+    let mask = keep_mask(&rb, std::slice::from_ref(pred))?;
+    if mask.true_count() == mask.len() {
+        return None;
+    }
+
+    let ranges = keep_ranges(&mask);
+    let mut out = MutableBatch::new();
+    if !ranges.is_empty() {
+        out.extend_from_ranges(batch, &ranges)
+            .expect("copying rows within a batch cannot fail");
+    }
+
+    Some(out)
+}
+
+/// Convert `mask` into the contiguous row ranges it selects.
+fn keep_ranges(mask: &BooleanArray) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = vec![];
+    for idx in (0..mask.len()).filter(|idx| mask.value(*idx)) {
+        match ranges.last_mut() {
+            Some(r) if r.end == idx => r.end += 1,
+            _ => ranges.push(idx..idx + 1),
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_util::assert_batches_eq;
+    use mutable_batch_lp::test_helpers::lp_to_mutable_batch;
+    use predicate::delete_predicate::parse_delete_predicate;
+
+    use super::*;
+
+    #[test]
+    fn test_keep_ranges() {
+        let mask = BooleanArray::from(vec![true, true, false, true, false, false, true]);
+        assert_eq!(keep_ranges(&mask), vec![0..2, 3..4, 6..7]);
+
+        let mask = BooleanArray::from(vec![false, false]);
+        assert!(keep_ranges(&mask).is_empty());
+    }
+
+    #[test]
+    fn test_filter_mutable_batch() {
+        let (_, mb) = lp_to_mutable_batch(
+            "bananas,city=Boston v=1 10\n\
+            bananas,city=London v=2 10\n\
+            bananas v=3 10\n\
+            bananas,city=Boston v=4 20",
+        );
+
+        let pred = Arc::new(parse_delete_predicate("0", "15", r#"city="Boston""#).unwrap());
+        let got = filter_mutable_batch_delete(&mb, &pred).expect("rows must be deleted");
+        assert_batches_eq!(
+            [
+                "+--------+--------------------------------+-----+",
+                "| city   | time                           | v   |",
+                "+--------+--------------------------------+-----+",
+                "| London | 1970-01-01T00:00:00.000000010Z | 2.0 |",
+                "|        | 1970-01-01T00:00:00.000000010Z | 3.0 |",
+                "| Boston | 1970-01-01T00:00:00.000000020Z | 4.0 |",
+                "+--------+--------------------------------+-----+",
+            ],
+            &[got.to_arrow(Projection::All).unwrap()]
+        );
+
+        // Nothing matches
+        let pred = Arc::new(parse_delete_predicate("0", "15", r#"city="Paris""#).unwrap());
+        assert!(filter_mutable_batch_delete(&mb, &pred).is_none());
+
+        // Unknown column
+        let pred = Arc::new(parse_delete_predicate("0", "15", r#"state="MA""#).unwrap());
+        assert!(filter_mutable_batch_delete(&mb, &pred).is_none());
+
+        // Everything matches
+        let pred = Arc::new(parse_delete_predicate("0", "100", "").unwrap());
+        let got = filter_mutable_batch_delete(&mb, &pred).expect("rows must be deleted");
+        assert_eq!(got.rows(), 0);
+    }
+
+    #[test]
+    fn test_filter_record_batch_uncoercible_predicate() {
+        let (_, mb) = lp_to_mutable_batch(
+            "bananas,city=Boston v=1,ripe=true 10\n\
+            bananas,city=London v=2,ripe=false 10",
+        );
+        let rb = mb.to_arrow(Projection::All).unwrap();
+
+        // The second predicate compares a boolean column with an integer and
+        // cannot be applied, but must not prevent the first being applied.
+        let preds = [
+            Arc::new(parse_delete_predicate("0", "15", r#"city="Boston""#).unwrap()),
+            Arc::new(parse_delete_predicate("0", "15", "ripe=1").unwrap()),
+        ];
+        let got = filter_record_batch_deletes(rb, &preds);
+        assert_batches_eq!(
+            [
+                "+--------+-------+--------------------------------+-----+",
+                "| city   | ripe  | time                           | v   |",
+                "+--------+-------+--------------------------------+-----+",
+                "| London | false | 1970-01-01T00:00:00.000000010Z | 2.0 |",
+                "+--------+-------+--------------------------------+-----+",
+            ],
+            &[got]
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use std::fmt::Display;
 
+use iox_time::{SystemProvider, Time, TimeProvider};
+
 use crate::query_adaptor::QueryAdaptor;
 
 /// An opaque, monotonic generational identifier of a buffer in a
@@ -45,17 +47,32 @@ impl Display for BatchIdent {
 pub struct PersistingData {
     data: QueryAdaptor,
     batch_ident: BatchIdent,
+
+    /// The time at which this data was marked as persisting.
+    marked_at: Time,
 }
 
 impl PersistingData {
     pub(super) fn new(data: QueryAdaptor, batch_ident: BatchIdent) -> Self {
-        Self { data, batch_ident }
+        Self {
+            data,
+            batch_ident,
+            marked_at: SystemProvider::new().now(),
+        }
     }
 
     pub(super) fn batch_ident(&self) -> BatchIdent {
         self.batch_ident
     }
 
+    /// Returns the time at which this data was marked as persisting.
+    ///
+    /// Deletes accepted before this time have been applied to this data,
+    /// while those accepted after it are not.
+    pub(crate) fn marked_at(&self) -> Time {
+        self.marked_at
+    }
+
     pub(crate) fn query_adaptor(&self) -> QueryAdaptor {
         self.data.clone()
     }
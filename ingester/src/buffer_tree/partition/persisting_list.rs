@@ -1,13 +1,14 @@
-use std::collections::VecDeque;
+use std::{collections::VecDeque, sync::Arc};
 
 use arrow::record_batch::RecordBatch;
-use data_types::TimestampMinMax;
+use data_types::{DeletePredicate, TimestampMinMax};
 use schema::{merge::SchemaMerger, Schema};
 
-use crate::query::projection::OwnedProjection;
+use crate::{dml_payload::DeleteReference, query::projection::OwnedProjection};
 
 use super::{
     buffer::{traits::Queryable, BufferState, Persisting},
+    delete::filter_record_batch_deletes,
     persisting::BatchIdent,
 };
 
@@ -27,8 +28,10 @@ pub(crate) struct PersistingList {
     /// The [`BatchIdent`] is a generational counter that is used to tag each
     /// persisting with a unique, opaque, monotonic identifier.
     ///
+    /// Each batch is accompanied by the [`BatchDeletes`] applied to it.
+    ///
     /// [`DataBuffer`]: super::buffer::DataBuffer
-    persisting: VecDeque<(BatchIdent, BufferState<Persisting>)>,
+    persisting: VecDeque<(BatchIdent, BufferState<Persisting>, BatchDeletes)>,
 
     cached: Option<CachedStats>,
 }
@@ -56,7 +59,15 @@ impl PersistingList {
     ///
     /// The provided buffer MUST be non-empty (containing a timestamp column,
     /// and a schema)
-    pub(crate) fn push(&mut self, ident: BatchIdent, buffer: BufferState<Persisting>) {
+    ///
+    /// The `references` of the deletes applied to the buffer before it was
+    /// marked as persisting are held until the buffer is removed.
+    pub(crate) fn push(
+        &mut self,
+        ident: BatchIdent,
+        buffer: BufferState<Persisting>,
+        references: Vec<DeleteReference>,
+    ) {
         // Recompute the statistics.
         match &mut self.cached {
             Some(v) => v.push(&buffer),
@@ -81,13 +92,21 @@ impl PersistingList {
         assert!(self
             .persisting
             .back()
-            .map(|(last, _)| ident > *last)
+            .map(|(last, _, _)| ident > *last)
             .unwrap_or(true));
 
-        self.persisting.push_back((ident, buffer));
+        self.persisting.push_back((
+            ident,
+            buffer,
+            BatchDeletes {
+                predicates: vec![],
+                references,
+            },
+        ));
     }
 
-    /// Remove the buffer identified by `ident` from the list.
+    /// Remove the buffer identified by `ident` from the list, returning it
+    /// alongside the references of the deletes applied to it.
     ///
     /// There is no ordering requirement for this call, but is more efficient
     /// when removals match the order of calls to [`PersistingList::push()`].
@@ -96,26 +115,56 @@ impl PersistingList {
     ///
     /// This method panics if there is currently no batch identified by `ident`
     /// in the list.
-    pub(crate) fn remove(&mut self, ident: BatchIdent) -> BufferState<Persisting> {
+    pub(crate) fn remove(
+        &mut self,
+        ident: BatchIdent,
+    ) -> (BufferState<Persisting>, Vec<DeleteReference>) {
         let idx = self
             .persisting
             .iter()
-            .position(|(old, _)| *old == ident)
+            .position(|(old, _, _)| *old == ident)
             .expect("no currently persisting batch");
 
-        let (old_ident, fsm) = self.persisting.remove(idx).unwrap();
+        let (old_ident, fsm, deletes) = self.persisting.remove(idx).unwrap();
         assert_eq!(old_ident, ident);
 
         // Recompute the cache of all remaining persisting batch stats (if any)
-        self.cached = CachedStats::new(self.persisting.iter().map(|(_, v)| v));
+        self.cached = CachedStats::new(self.persisting.iter().map(|(_, v, _)| v));
 
-        fsm
+        (fsm, deletes.references)
     }
 
     pub(crate) fn is_empty(&self) -> bool {
         self.persisting.is_empty()
     }
 
+    /// Record a delete of the rows matching `pred` in all batches in this
+    /// list.
+    ///
+    /// The persisting data is immutable, so the delete is applied when the
+    /// data is queried. Each batch holds a clone of `reference` until it is
+    /// removed from the list.
+    pub(crate) fn apply_delete(
+        &mut self,
+        pred: &Arc<DeletePredicate>,
+        reference: &DeleteReference,
+    ) {
+        for (_, _, deletes) in &mut self.persisting {
+            deletes.predicates.push(Arc::clone(pred));
+            deletes.references.push(reference.clone());
+        }
+    }
+
+    /// Returns true if any batch in this list has a delete applied to it.
+    ///
+    /// When true, the statistics returned by this list are an upper bound of
+    /// the data returned by a query.
+    pub(crate) fn has_deletes(&self) -> bool {
+        self.persisting
+            .iter()
+            .any(|(_, _, d)| !d.predicates.is_empty())
+    }
+
     /// Returns the row count sum across all batches in this list.
     ///
     /// This is an `O(1)` operation.
@@ -145,12 +194,37 @@ impl PersistingList {
         &'a self,
         projection: &'b OwnedProjection,
     ) -> impl Iterator<Item = RecordBatch> + 'a {
-        self.persisting
-            .iter()
-            .flat_map(move |(_, b)| b.get_query_data(projection))
+        self.persisting.iter().flat_map(move |(_, b, deletes)| {
+            let deletes = &deletes.predicates;
+            if deletes.is_empty() {
+                return b.get_query_data(projection);
+            }
+
+            // The delete predicates may refer to columns outside of the
+            // projection, so they are applied before projecting.
+            let filtered = b
+                .get_query_data(&OwnedProjection::default())
+                .into_iter()
+                .map(|batch| filter_record_batch_deletes(batch, deletes))
+                .filter(|batch| batch.num_rows() > 0)
+                .collect::<Vec<_>>();
+            projection.project_record_batch(&filtered)
+        })
     }
 }
 
+/// The deletes applied to a persisting batch.
+#[derive(Debug)]
+struct BatchDeletes {
+    /// The predicates of the deletes applied after the batch was marked as
+    /// persisting, which are applied at query time.
+    predicates: Vec<Arc<DeletePredicate>>,
+
+    /// The references of all deletes applied to the batch, released once it
+    /// has been persisted.
+    references: Vec<DeleteReference>,
+}
+
 /// The set of cached statistics describing the batches of data within the
 /// [`PersistingList`].
 #[derive(Debug)]
@@ -262,7 +336,7 @@ mod tests {
         let buffer = buffer_with_lp(r#"bananas,tag=platanos great="yes" 42"#);
 
         // Add it to the list.
-        list.push(ident_oracle.next(), buffer);
+        list.push(ident_oracle.next(), buffer, vec![]);
 
         // The statistics must now match the expected values.
         assert!(!list.is_empty());
@@ -289,7 +363,7 @@ mod tests {
 
         // Push a new buffer updating the last row to check yielded row ordering.
         let buffer = buffer_with_lp(r#"bananas,tag=platanos great="definitely" 42"#);
-        list.push(ident_oracle.next(), buffer);
+        list.push(ident_oracle.next(), buffer, vec![]);
 
         // The statistics must now match the expected values.
         assert!(!list.is_empty());
@@ -334,6 +408,7 @@ mod tests {
                 bananas,tag=platanos v=2,bananas=100 4242\n\
             ",
             ),
+            vec![],
         );
 
         list.push(
@@ -344,6 +419,7 @@ mod tests {
                 bananas v=4,bananas=200 42424242\n\
             ",
             ),
+            vec![],
         );
 
         // Assert the row content
@@ -376,6 +452,7 @@ mod tests {
         list.push(
             first_batch,
             buffer_with_lp(r#"bananas,tag=platanos great="yes" 42"#),
+            vec![],
         );
 
         // The statistics must now match the expected values.
@@ -392,6 +469,7 @@ mod tests {
         list.push(
             second_batch,
             buffer_with_lp(r#"bananas,another=yes great="definitely",incremental=true 4242"#),
+            vec![],
         );
 
         // The statistics must now match the expected values.
@@ -190,6 +190,13 @@ where
 
     async fn apply(&self, op: IngestOp) -> Result<(), Self::Error> {
         let namespace_id = op.namespace();
+
+        // A delete has nothing to remove if no data is buffered for the
+        // namespace.
+        if matches!(op, IngestOp::Delete(_)) && self.namespace(namespace_id).is_none() {
+            return Ok(());
+        }
+
         let namespace_data = self.namespaces.get_or_insert_with(&namespace_id, || {
             // Increase the metric that records the number of namespaces
             // buffered in this ingester instance.
@@ -8,7 +8,8 @@ use std::{collections::HashMap, fmt::Debug, sync::Arc};
 use async_trait::async_trait;
 use data_types::{
     partition_template::{build_column_values, ColumnValue, TablePartitionTemplateOverride},
-    NamespaceId, PartitionKey, SequenceNumber, TableId,
+    sequence_number_set::SequenceNumberSet,
+    DeletePredicate, NamespaceId, PartitionKey, SequenceNumber, TableId,
 };
 use datafusion::{prelude::Expr, scalar::ScalarValue};
 use iox_query::{
@@ -32,6 +33,7 @@ use super::{
 use crate::{
     arcmap::ArcMap,
     deferred_load::DeferredLoad,
+    dml_payload::DeleteReference,
     query::{
         partition_response::PartitionResponse, projection::OwnedProjection,
         response::PartitionStream, QueryError, QueryExec,
@@ -124,6 +126,24 @@ impl<O> TableData<O> {
     pub(crate) fn namespace_id(&self) -> NamespaceId {
         self.namespace_id
     }
+
+    /// Remove the rows matching `pred` from all partitions buffered for this
+    /// table, each holding a clone of the delete `reference` until persisted.
+    ///
+    /// Returns the [`SequenceNumberSet`] of the buffered operations that no
+    /// longer need to be retained in the WAL, as all the rows they buffered
+    /// were deleted.
+    pub(super) fn apply_delete(
+        &self,
+        pred: &Arc<DeletePredicate>,
+        reference: &DeleteReference,
+    ) -> SequenceNumberSet {
+        let mut released = SequenceNumberSet::default();
+        for p in self.partition_data.values() {
+            released.add_set(&p.lock().apply_delete(pred, reference));
+        }
+        released
+    }
 }
 
 impl<O> TableData<O>
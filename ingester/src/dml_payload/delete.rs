@@ -0,0 +1,112 @@
+use std::sync::Arc;
+
+use data_types::{
+    sequence_number_set::SequenceNumberSet, DeletePredicate, NamespaceId, SequenceNumber, TableId,
+};
+use parking_lot::Mutex;
+use trace::ctx::SpanContext;
+
+/// A predicate delete of the rows in a table, represented by an
+/// [`crate::dml_payload::IngestOp::Delete`]
+#[derive(Debug, Clone)]
+pub struct DeleteOperation {
+    namespace: NamespaceId,
+    table: TableId,
+    predicate: Arc<DeletePredicate>,
+    sequence_number: SequenceNumber,
+    reference: DeleteReference,
+
+    /// The [`SequenceNumberSet`] of the buffered writes (and deletes) that
+    /// no longer need to be retained in the WAL because this delete removed
+    /// all the rows buffered for them.
+    released: Arc<Mutex<SequenceNumberSet>>,
+
+    span_context: Option<SpanContext>,
+}
+
+impl DeleteOperation {
+    /// Construct a new [`DeleteOperation`] from the provided details.
+    pub fn new(
+        namespace: NamespaceId,
+        table: TableId,
+        predicate: Arc<DeletePredicate>,
+        sequence_number: SequenceNumber,
+        span_context: Option<SpanContext>,
+    ) -> Self {
+        Self {
+            namespace,
+            table,
+            predicate,
+            sequence_number,
+            reference: DeleteReference(Arc::new(sequence_number)),
+            released: Default::default(),
+            span_context,
+        }
+    }
+
+    /// The namespace the delete applies to
+    pub fn namespace(&self) -> NamespaceId {
+        self.namespace
+    }
+
+    /// The table the delete applies to
+    pub fn table(&self) -> TableId {
+        self.table
+    }
+
+    /// The predicate selecting the rows to delete
+    pub fn predicate(&self) -> &Arc<DeletePredicate> {
+        &self.predicate
+    }
+
+    /// The [`SequenceNumber`] assigned to this delete
+    pub fn sequence_number(&self) -> SequenceNumber {
+        self.sequence_number
+    }
+
+    /// The [`DeleteReference`] of this delete, cloned by each buffer the
+    /// delete is applied to.
+    pub(crate) fn reference(&self) -> &DeleteReference {
+        &self.reference
+    }
+
+    /// Record the [`SequenceNumberSet`] of buffered operations released by
+    /// applying this delete.
+    pub(crate) fn release(&self, set: &SequenceNumberSet) {
+        self.released.lock().add_set(set);
+    }
+
+    /// Returns a handle to the [`SequenceNumberSet`] of the buffered
+    /// operations released by applying this delete, which remains readable
+    /// after the [`DeleteOperation`] is consumed.
+    pub(crate) fn released(&self) -> Arc<Mutex<SequenceNumberSet>> {
+        Arc::clone(&self.released)
+    }
+
+    /// An optional tracing context associated with the [`DeleteOperation`]
+    pub fn span_context(&self) -> Option<&SpanContext> {
+        self.span_context.as_ref()
+    }
+}
+
+/// A reference to the WAL entry of a [`DeleteOperation`].
+///
+/// A delete is never persisted itself - it is applied to the buffered data,
+/// and the WAL entry MUST be retained until all the buffered data the delete
+/// was applied to has been persisted, or the deleted rows would be restored by
+/// a WAL replay that does not include the delete.
+///
+/// Each buffer a delete is applied to holds a clone of this reference,
+/// releasing it once persisted. The last reference to be released returns the
+/// [`SequenceNumber`] of the delete, which can then be released from the WAL.
+#[derive(Debug, Clone)]
+pub(crate) struct DeleteReference(Arc<SequenceNumber>);
+
+impl DeleteReference {
+    /// Release this reference, returning the [`SequenceNumber`] of the delete
+    /// if no other references to it remain.
+    #[must_use]
+    pub(crate) fn release(self) -> Option<SequenceNumber> {
+        Arc::into_inner(self.0)
+    }
+}
@@ -1,9 +1,9 @@
 //! This module houses encode helpers for the ingester internal DML types
 use data_types::NamespaceId;
-use generated_types::influxdata::pbdata::v1::DatabaseBatch;
+use generated_types::influxdata::{iox::delete::v1::DeletePayload, pbdata::v1::DatabaseBatch};
 use mutable_batch_pb::encode::encode_batch;
 
-use super::write::WriteOperation;
+use super::{write::WriteOperation, DeleteOperation};
 
 /// Encodes a [`WriteOperation`] for `namespace` into the [`DatabaseBatch`]
 /// wire format.
@@ -17,3 +17,14 @@ pub fn encode_write_op(namespace: NamespaceId, op: &WriteOperation) -> DatabaseB
             .collect(),
     }
 }
+
+/// Encodes a [`DeleteOperation`] for `namespace` into the [`DeletePayload`]
+/// wire format.
+pub fn encode_delete_op(namespace: NamespaceId, op: &DeleteOperation) -> DeletePayload {
+    DeletePayload {
+        database_id: namespace.get(),
+        table_name: String::new(),
+        table_id: op.table().get(),
+        predicate: Some(op.predicate().as_ref().into()),
+    }
+}
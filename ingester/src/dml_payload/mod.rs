@@ -4,5 +4,8 @@
 mod ingest_op;
 pub use ingest_op::*;
 
+mod delete;
+pub use delete::*;
+
 pub mod encode;
 pub mod write;
@@ -3,7 +3,7 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
-use data_types::{NamespaceId, PartitionKey, SequenceNumber, TableId};
+use data_types::{DeletePredicate, NamespaceId, PartitionKey, SequenceNumber, TableId};
 use generated_types::influxdata::iox::wal::v1::sequenced_wal_op::Op;
 use metric::U64Counter;
 use mutable_batch_pb::decode::decode_database_batch;
@@ -13,7 +13,7 @@ use wal::{SegmentId, SequencedWalOp};
 
 use crate::{
     dml_payload::write::{PartitionedData, TableData, WriteOperation},
-    dml_payload::IngestOp,
+    dml_payload::{DeleteOperation, IngestOp},
     dml_sink::{DmlError, DmlSink},
     ingest_state::{IngestState, IngestStateError},
     partition_iter::PartitionIter,
@@ -40,6 +40,10 @@ pub enum WalReplayError {
     #[error("failed converting wal entry to ingest operation: {0}")]
     MapToDml(#[from] mutable_batch_pb::decode::Error),
 
+    /// An error converting a WAL delete entry into a [`IngestOp`].
+    #[error("failed converting wal delete entry to ingest operation: {0}")]
+    MapDelete(String),
+
     /// A failure to apply a [`IngestOp`] from the WAL to the in-memory
     /// [`BufferTree`].
     ///
@@ -317,7 +321,40 @@ where
 
             let op = match op {
                 Op::Write(w) => w,
-                Op::Delete(_) => unreachable!(),
+                Op::Delete(d) => {
+                    let table_id = TableId::new(d.table_id);
+                    let sequence_number = SequenceNumber::new(
+                        *table_write_sequence_numbers
+                            .get(&table_id)
+                            .expect("attempt to apply unsequenced wal op"),
+                    );
+                    max_sequence = max_sequence.max(Some(sequence_number));
+
+                    let predicate = d
+                        .predicate
+                        .ok_or_else(|| WalReplayError::MapDelete("missing predicate".to_string()))
+                        .and_then(|p| {
+                            DeletePredicate::try_from(p)
+                                .map_err(|e| WalReplayError::MapDelete(e.to_string()))
+                        })?;
+
+                    let op = DeleteOperation::new(
+                        NamespaceId::new(d.database_id),
+                        table_id,
+                        Arc::new(predicate),
+                        sequence_number,
+                        None,
+                    );
+
+                    debug!(?op, "apply wal delete op");
+
+                    sink.apply(IngestOp::Delete(op))
+                        .await
+                        .map_err(Into::<DmlError>::into)?;
+
+                    ok_op_count_metric.inc(1);
+                    continue;
+                }
                 Op::Persist(_) => unreachable!(),
             };
 
@@ -287,7 +287,7 @@ mod tests {
                 ..
             }] =>
             {
-                assert_eq!(created_at.get(), max_l0_created_at.get());
+                assert!(max_l0_created_at.get() <= created_at.get());
 
                 assert_eq!(got_namespace_id, &namespace_id);
                 assert_eq!(got_table_id, &table_id);
@@ -472,7 +472,7 @@ mod tests {
                 ..
             }] =>
             {
-                assert_eq!(created_at.get(), max_l0_created_at.get());
+                assert!(max_l0_created_at.get() <= created_at.get());
 
                 assert_eq!(got_namespace_id, &namespace_id);
                 assert_eq!(got_table_id, &table_id);
@@ -273,6 +273,11 @@ where
     );
 
     // Construct the metadata for this parquet file.
+    //
+    // The max_l0_created_at is the time the data was marked as persisting
+    // rather than the creation time of the file, ensuring the file is covered
+    // by the tombstones of all deletes that were not applied to the data
+    // before it was snapshotted.
     let time_now = SystemProvider::new().now();
     let iox_metadata = IoxMetadata {
         object_store_id,
@@ -284,7 +289,7 @@ where
         partition_key: ctx.partition_key().clone(),
         compaction_level: CompactionLevel::Initial,
         sort_key: Some(data_sort_key),
-        max_l0_created_at: time_now,
+        max_l0_created_at: ctx.data().marked_at(),
     };
 
     // Save the compacted data to a parquet file in object storage.
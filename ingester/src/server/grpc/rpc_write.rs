@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use data_types::{NamespaceId, PartitionKey, TableId};
+use data_types::{DeletePredicate, DeletePredicateProtoError, NamespaceId, PartitionKey, TableId};
 use generated_types::influxdata::iox::ingester::v1::{
     self as proto, write_service_server::WriteService,
 };
@@ -17,7 +17,7 @@ use trace::{
 use crate::{
     buffer_tree::BufferWriteError,
     dml_payload::write::{PartitionedData, TableData, WriteOperation},
-    dml_payload::IngestOp,
+    dml_payload::{DeleteOperation, IngestOp},
     dml_sink::{DmlError, DmlSink},
     ingest_state::{IngestState, IngestStateError},
     timestamp_oracle::TimestampOracle,
@@ -39,6 +39,14 @@ enum RpcError {
     #[error("rpc write request does not contain any table data")]
     NoTables,
 
+    /// The RPC delete request did not contain a predicate.
+    #[error("rpc delete request does not contain a predicate")]
+    NoPredicate,
+
+    /// The delete predicate could not be read.
+    #[error(transparent)]
+    DecodePredicate(DeletePredicateProtoError),
+
     /// The serialised write payload could not be read.
     #[error(transparent)]
     Decode(mutable_batch_pb::decode::Error),
@@ -52,7 +60,11 @@ enum RpcError {
 impl From<RpcError> for tonic::Status {
     fn from(e: RpcError) -> Self {
         let code = match e {
-            RpcError::Decode(_) | RpcError::NoPayload | RpcError::NoTables => Code::InvalidArgument,
+            RpcError::Decode(_)
+            | RpcError::NoPayload
+            | RpcError::NoTables
+            | RpcError::NoPredicate
+            | RpcError::DecodePredicate(_) => Code::InvalidArgument,
             RpcError::SystemState(IngestStateError::PersistSaturated) => Code::ResourceExhausted,
             RpcError::SystemState(IngestStateError::DiskFull) => Code::ResourceExhausted,
             RpcError::SystemState(IngestStateError::GracefulStop) => Code::FailedPrecondition,
@@ -227,6 +239,51 @@ where
             }
         }
     }
+
+    /// Handle an RPC delete request.
+    async fn delete(
+        &self,
+        request: Request<proto::DeleteRequest>,
+    ) -> Result<Response<proto::DeleteResponse>, tonic::Status> {
+        // Extract the span context
+        let span_ctx: Option<SpanContext> = request.extensions().get().cloned();
+        let span = span_ctx.child_span("ingester delete");
+        let mut span_recorder = SpanRecorder::new(span);
+
+        // Deletes only reduce the buffered data, so they are accepted when
+        // persistence is saturated (but still require WAL space).
+        self.ingest_state
+            .read_with_exceptions([IngestStateError::PersistSaturated])
+            .map_err(RpcError::SystemState)?;
+
+        let payload = request.into_inner().payload.ok_or(RpcError::NoPayload)?;
+        let predicate = payload.predicate.ok_or(RpcError::NoPredicate)?;
+        let predicate = DeletePredicate::try_from(predicate).map_err(RpcError::DecodePredicate)?;
+        let namespace_id = NamespaceId::new(payload.database_id);
+        let table_id = TableId::new(payload.table_id);
+
+        trace!(%namespace_id, %table_id, ?predicate, "received rpc delete");
+
+        let op = DeleteOperation::new(
+            namespace_id,
+            table_id,
+            Arc::new(predicate),
+            self.timestamp.next(),
+            span_recorder.span().map(|span| span.ctx.clone()),
+        );
+
+        match self.sink.apply(IngestOp::Delete(op)).await {
+            Ok(()) => {
+                span_recorder.ok("applied delete");
+                Ok(Response::new(proto::DeleteResponse {}))
+            }
+            Err(e) => {
+                error!(error=%e, "failed to apply ingest operation");
+                span_recorder.error(e.to_string());
+                Err(e.into())?
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -747,4 +804,60 @@ mod tests {
             assert_eq!(handler_span.ctx.parent_span_id, Some(external_span.ctx.span_id));
         })
     }
+
+    /// Assert deletes are sequenced and passed to the DML sink, including
+    /// when persistence is saturated.
+    #[tokio::test]
+    async fn test_rpc_delete() {
+        use generated_types::influxdata::iox::{
+            delete::v1::DeletePayload,
+            predicate::v1::{Predicate, TimestampRange},
+        };
+
+        let mock = Arc::new(MockDmlSink::default().with_apply_return(vec![Ok(())]));
+        let timestamp = Arc::new(TimestampOracle::new(0));
+
+        let ingest_state = Arc::new(IngestState::default());
+        ingest_state.set(IngestStateError::PersistSaturated);
+
+        let handler = RpcWrite::new(Arc::clone(&mock), timestamp, Arc::clone(&ingest_state));
+
+        let payload = DeletePayload {
+            database_id: ARBITRARY_NAMESPACE_ID.get(),
+            table_name: String::new(),
+            table_id: ARBITRARY_TABLE_ID.get(),
+            predicate: Some(Predicate {
+                range: Some(TimestampRange { start: 1, end: 42 }),
+                exprs: vec![],
+            }),
+        };
+
+        handler
+            .delete(Request::new(proto::DeleteRequest {
+                payload: Some(payload.clone()),
+            }))
+            .await
+            .expect("delete should succeed");
+
+        assert_matches!(&mock.get_calls()[..], [IngestOp::Delete(d)] => {
+            assert_eq!(d.namespace(), ARBITRARY_NAMESPACE_ID);
+            assert_eq!(d.table(), ARBITRARY_TABLE_ID);
+            assert_eq!(d.predicate().range.start(), 1);
+            assert_eq!(d.predicate().range.end(), 42);
+            assert_eq!(d.sequence_number(), SequenceNumber::new(1));
+        });
+
+        // A delete without a predicate is rejected.
+        let err = handler
+            .delete(Request::new(proto::DeleteRequest {
+                payload: Some(DeletePayload {
+                    predicate: None,
+                    ..payload
+                }),
+            }))
+            .await
+            .expect_err("delete should fail");
+        assert_eq!(err.code(), Code::InvalidArgument);
+        assert_eq!(mock.get_calls().len(), 1);
+    }
 }
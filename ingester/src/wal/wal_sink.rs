@@ -7,7 +7,10 @@ use wal::{SequencedWalOp, WriteResult};
 
 use crate::{
     cancellation_safe::CancellationSafe,
-    dml_payload::{encode::encode_write_op, IngestOp},
+    dml_payload::{
+        encode::{encode_delete_op, encode_write_op},
+        IngestOp,
+    },
     dml_sink::{DmlError, DmlSink},
 };
 
@@ -71,6 +74,19 @@ where
 
         let set = op.sequence_number_set();
 
+        // Deletes are applied to the buffered data in place and are never
+        // persisted themselves - instead each buffer the delete is applied to
+        // holds a reference to it until persisted, retaining the WAL entry
+        // until then.
+        //
+        // A delete may also remove all the rows buffered for earlier writes,
+        // which are then released from the WAL as they will never be
+        // persisted.
+        let delete_reference = match &op {
+            IngestOp::Delete(d) => Some((d.reference().clone(), d.released())),
+            IngestOp::Write(_) => None,
+        };
+
         // Pass it to the inner handler while we wait for the write to be made
         // durable.
         //
@@ -96,8 +112,26 @@ where
             res.map_err(Into::into)
         })
         .await;
-        if inner_result.is_err() {
-            self.notifier_handle.notify_failed_write_buffer(set).await;
+        match delete_reference {
+            // Release the reference held for the duration of the apply call -
+            // if no buffer holds a reference to the delete, the WAL is notified
+            // that it will not be persisted, as for a write that failed to
+            // buffer, alongside the operations the delete released.
+            Some((r, released)) => {
+                let mut released = std::mem::take(&mut *released.lock());
+                if r.release().is_some() {
+                    released.add_set(&set);
+                }
+                if !released.is_empty() {
+                    self.notifier_handle
+                        .notify_failed_write_buffer(released)
+                        .await;
+                }
+            }
+            None if inner_result.is_err() => {
+                self.notifier_handle.notify_failed_write_buffer(set).await;
+            }
+            None => {}
         }
         inner_result?;
 
@@ -132,6 +166,12 @@ impl WalAppender for Arc<wal::Wal> {
                     partition_sequence_numbers,
                 )
             }
+            IngestOp::Delete(d) => (
+                Op::Delete(encode_delete_op(namespace_id, d)),
+                [(d.table(), d.sequence_number().get())]
+                    .into_iter()
+                    .collect::<HashMap<TableId, u64>>(),
+            ),
         };
 
         self.write_op(SequencedWalOp {
@@ -179,12 +219,13 @@ mod tests {
     use std::{future, sync::Arc};
 
     use assert_matches::assert_matches;
-    use data_types::{SequenceNumber, TableId};
+    use data_types::{DeletePredicate, SequenceNumber, TableId, TimestampRange};
     use lazy_static::lazy_static;
     use wal::Wal;
 
     use super::*;
     use crate::{
+        dml_payload::DeleteOperation,
         dml_sink::mock_sink::MockDmlSink,
         test_util::{
             make_multi_table_write_op, ARBITRARY_NAMESPACE_ID, ARBITRARY_PARTITION_KEY,
@@ -370,4 +411,82 @@ mod tests {
             .collect::<Vec<_>>()
         );
     }
+
+    /// A [`DmlSink`] implementation that discards all ops, as the buffer tree
+    /// does for a delete of a table with no buffered data.
+    #[derive(Debug, Default, Clone)]
+    struct DiscardingDmlSink;
+
+    #[async_trait]
+    impl DmlSink for DiscardingDmlSink {
+        type Error = DmlError;
+
+        async fn apply(&self, _op: IngestOp) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    fn make_delete_op() -> DeleteOperation {
+        DeleteOperation::new(
+            ARBITRARY_NAMESPACE_ID,
+            ARBITRARY_TABLE_ID,
+            Arc::new(DeletePredicate {
+                range: TimestampRange::new(0, 100),
+                exprs: vec![],
+            }),
+            SequenceNumber::new(42),
+            None,
+        )
+    }
+
+    /// A delete that is not referenced by any buffered data once applied is
+    /// released from the WAL immediately.
+    #[tokio::test]
+    async fn test_delete_unreferenced() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let wal = Wal::new(dir.path())
+            .await
+            .expect("failed to initialise WAL");
+        let notifier_handle = Arc::new(mock::MockUnbufferedWriteNotifier::default());
+
+        let wal_sink = WalSink::new(DiscardingDmlSink, wal, Arc::clone(&notifier_handle));
+
+        wal_sink
+            .apply(IngestOp::Delete(make_delete_op()))
+            .await
+            .expect("wal should not error");
+
+        assert_eq!(
+            notifier_handle.calls(),
+            [SequenceNumberSet::from_iter([SequenceNumber::new(42)])]
+                .into_iter()
+                .collect::<Vec<_>>()
+        );
+    }
+
+    /// A delete that is still referenced once applied (as it is by each
+    /// buffer it was applied to, until persisted) is retained in the WAL.
+    #[tokio::test]
+    async fn test_delete_referenced() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let wal = Wal::new(dir.path())
+            .await
+            .expect("failed to initialise WAL");
+        let notifier_handle = Arc::new(mock::MockUnbufferedWriteNotifier::default());
+
+        // The mock sink retains the op it is given, and therefore holds a
+        // reference to the delete.
+        let inner = Arc::new(MockDmlSink::default().with_apply_return(vec![Ok(())]));
+        let wal_sink = WalSink::new(Arc::clone(&inner), wal, Arc::clone(&notifier_handle));
+
+        wal_sink
+            .apply(IngestOp::Delete(make_delete_op()))
+            .await
+            .expect("wal should not error");
+
+        assert_eq!(inner.get_calls().len(), 1);
+        assert_eq!(notifier_handle.calls().len(), 0);
+    }
 }
@@ -81,7 +81,7 @@ async fn write_persist() {
         assert_eq!(f.to_delete, None);
         assert_eq!(f.row_count, 1);
         assert_eq!(f.column_set.len(), 3);
-        assert_eq!(f.max_l0_created_at, f.created_at);
+        assert!(f.max_l0_created_at <= f.created_at);
 
         (ParquetFilePath::from(f), f.file_size_bytes)
     });
@@ -269,6 +269,74 @@ async fn wal_replay() {
     assert_eq!(actual_table_ids, expected_table_ids);
 }
 
+// Ensure a delete applied to buffered data is not lost when the ingester
+// crashes before persisting the data, and is applied again during WAL replay.
+#[tokio::test]
+async fn wal_replay_delete() {
+    let wal_dir = Arc::new(test_helpers::tmp_dir().unwrap());
+    let metrics: Arc<metric::Registry> = Default::default();
+    let catalog: Arc<dyn Catalog> =
+        Arc::new(iox_catalog::mem::MemCatalog::new(Arc::clone(&metrics)));
+    let namespace_name = "wal_replay_delete_test_namespace";
+
+    {
+        let mut ctx = TestContextBuilder::default()
+            .with_wal_dir(Arc::clone(&wal_dir))
+            .with_catalog(Arc::clone(&catalog))
+            .build()
+            .await;
+
+        let ns = ctx.ensure_namespace(namespace_name, None, None).await;
+
+        let partition_key = PartitionKey::from("1970-01-01");
+        ctx.write_lp(
+            namespace_name,
+            "bananas,city=London count=1 10\nbananas,city=Madrid count=2 20",
+            partition_key.clone(),
+            0,
+            None,
+        )
+        .await;
+
+        // Delete the London rows from the buffered data.
+        let pred =
+            predicate::delete_predicate::parse_delete_predicate("0", "100", r#"city="London""#)
+                .unwrap();
+        ctx.delete(namespace_name, "bananas", &pred).await;
+
+        let data: Vec<_> = ctx
+            .query(IngesterQueryRequest {
+                namespace_id: ns.id.get(),
+                table_id: ctx.table_id(namespace_name, "bananas").await.get(),
+                columns: vec![],
+                predicate: None,
+            })
+            .await
+            .expect("query request failed");
+
+        let expected = vec![
+            "+--------+-------+--------------------------------+",
+            "| city   | count | time                           |",
+            "+--------+-------+--------------------------------+",
+            "| Madrid | 2.0   | 1970-01-01T00:00:00.000000020Z |",
+            "+--------+-------+--------------------------------+",
+        ];
+        assert_batches_sorted_eq!(&expected, &data);
+    } // Drop the first ingester instance without persisting the data
+
+    // Restart the ingester, replaying (and persisting) the WAL.
+    let ctx = TestContextBuilder::default()
+        .with_wal_dir(wal_dir)
+        .with_catalog(catalog)
+        .build()
+        .await;
+
+    // The replayed write must not restore the deleted rows.
+    let parquet_files = ctx.catalog_parquet_file_records(namespace_name).await;
+    let file = assert_matches!(&*parquet_files, [f] => f, "expected 1 parquet file");
+    assert_eq!(file.row_count, 1);
+}
+
 // Ensure that data applied to an ingester is persisted at shutdown, and the WAL
 // files are cleared.
 #[tokio::test]
@@ -480,6 +548,88 @@ async fn wal_reference_dropping() {
     assert_eq!(final_segment_names.len(), 1); // Ensure a single (open) segment is present after the old one has been dropped
 }
 
+// Ensure a WAL segment containing writes whose buffered rows were all removed
+// by a delete is dropped after rotation, without waiting for the partition to
+// be written to and persisted again.
+#[tokio::test]
+async fn wal_reference_dropping_delete() {
+    let wal_dir = Arc::new(test_helpers::tmp_dir().unwrap());
+    let metrics = Arc::new(metric::Registry::default());
+    let catalog: Arc<dyn Catalog> =
+        Arc::new(iox_catalog::mem::MemCatalog::new(Arc::clone(&metrics)));
+
+    // Test-local namespace name
+    const TEST_NAMESPACE_NAME: &str = "wal_reference_dropping_delete_test_namespace";
+    // Create an ingester using a fairly low write-ahead log rotation interval
+    const WAL_ROTATION_PERIOD: Duration = Duration::from_secs(15);
+
+    let mut ctx = TestContextBuilder::default()
+        .with_wal_dir(Arc::clone(&wal_dir))
+        .with_catalog(Arc::clone(&catalog))
+        .with_wal_rotation_period(WAL_ROTATION_PERIOD)
+        .build()
+        .await;
+
+    let ns = ctx.ensure_namespace(TEST_NAMESPACE_NAME, None, None).await;
+
+    ctx.write_lp(
+        TEST_NAMESPACE_NAME,
+        "bananas,city=London count=1 10\nbananas,city=Madrid count=2 20",
+        PartitionKey::from("1970-01-01"),
+        0,
+        None,
+    )
+    .await;
+
+    // Delete all the buffered rows.
+    let pred = predicate::delete_predicate::parse_delete_predicate("0", "100", "").unwrap();
+    ctx.delete(TEST_NAMESPACE_NAME, "bananas", &pred).await;
+
+    let data: Vec<_> = ctx
+        .query(IngesterQueryRequest {
+            namespace_id: ns.id.get(),
+            table_id: ctx.table_id(TEST_NAMESPACE_NAME, "bananas").await.get(),
+            columns: vec![],
+            predicate: None,
+        })
+        .await
+        .expect("query request failed");
+    assert!(data.is_empty());
+
+    let initial_segment_names =
+        get_file_names_in_dir(wal_dir.path()).expect("should be able to get file names");
+    assert_eq!(initial_segment_names.len(), 1); // Ensure a single (open) segment is present
+
+    tokio::time::pause();
+    tokio::time::advance(WAL_ROTATION_PERIOD).await;
+    tokio::time::resume();
+
+    // Wait for the rotation to result in the initial segment no longer being
+    // present in the write-ahead log directory, as neither the write nor the
+    // delete are referenced by any buffered data.
+    async {
+        loop {
+            let segments =
+                get_file_names_in_dir(wal_dir.path()).expect("should be able to get file names");
+            if !segments
+                .iter()
+                .any(|name| initial_segment_names.contains(name))
+            {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+    }
+    .with_timeout_panic(Duration::from_secs(5))
+    .await;
+
+    // Nothing was persisted.
+    assert!(ctx
+        .catalog_parquet_file_records(TEST_NAMESPACE_NAME)
+        .await
+        .is_empty());
+}
+
 fn get_file_names_in_dir(dir: &Path) -> Result<Vec<OsString>, std::io::Error> {
     read_dir(dir)?
         .filter_map_ok(|f| {
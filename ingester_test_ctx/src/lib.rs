@@ -23,12 +23,14 @@ use arrow::record_batch::RecordBatch;
 use arrow_flight::{decode::FlightRecordBatchStream, flight_service_server::FlightService, Ticket};
 use data_types::{
     partition_template::{NamespacePartitionTemplateOverride, TablePartitionTemplateOverride},
-    Namespace, NamespaceId, NamespaceSchema, ParquetFile, PartitionKey, SequenceNumber, TableId,
+    DeletePredicate, Namespace, NamespaceId, NamespaceSchema, ParquetFile, PartitionKey,
+    SequenceNumber, TableId,
 };
 use dml::{DmlMeta, DmlWrite};
 use futures::{stream::FuturesUnordered, FutureExt, StreamExt, TryStreamExt};
-use generated_types::influxdata::iox::ingester::v1::{
-    write_service_server::WriteService, WriteRequest,
+use generated_types::influxdata::iox::{
+    delete::v1::DeletePayload,
+    ingester::v1::{write_service_server::WriteService, DeleteRequest, WriteRequest},
 };
 use ingester::{GossipConfig, IngesterGuard, IngesterRpcInterface};
 use ingester_query_grpc::influxdata::iox::ingester::v1::IngesterQueryRequest;
@@ -348,6 +350,29 @@ where
         debug!("pushed dml op");
     }
 
+    /// Construct and submit a RPC request to delete the rows matching
+    /// `predicate` from the specified namespace & table.
+    pub async fn delete(&mut self, namespace: &str, table: &str, predicate: &DeletePredicate) {
+        let namespace_id = self.namespace_id(namespace).await;
+        let table_id = self.table_id(namespace, table).await;
+
+        self.ingester
+            .rpc()
+            .write_service()
+            .delete(Request::new(DeleteRequest {
+                payload: Some(DeletePayload {
+                    database_id: namespace_id.get(),
+                    table_name: table.to_string(),
+                    table_id: table_id.get(),
+                    predicate: Some(predicate.into()),
+                }),
+            }))
+            .await
+            .unwrap();
+
+        debug!("pushed delete op");
+    }
+
     /// Return the [`NamespaceId`] in the catalog for the specified namespace
     /// name, or panic.
     pub async fn namespace_id(&self, namespace: &str) -> NamespaceId {
@@ -0,0 +1,14 @@
+-- Predicate deletes recorded against a table.
+--
+-- Replaces the shard-based "tombstone" table, which is no longer written to.
+CREATE TABLE IF NOT EXISTS table_tombstone (
+    id BIGINT GENERATED ALWAYS AS IDENTITY,
+    table_id BIGINT NOT NULL REFERENCES table_name (id) ON DELETE CASCADE,
+    min_time BIGINT NOT NULL,
+    max_time BIGINT NOT NULL,
+    serialized_predicate TEXT NOT NULL,
+    created_at BIGINT NOT NULL,
+    PRIMARY KEY (id)
+);
+
+CREATE INDEX IF NOT EXISTS table_tombstone_table_id_idx ON table_tombstone (table_id);
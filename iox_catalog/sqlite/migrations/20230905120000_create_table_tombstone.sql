@@ -0,0 +1,17 @@
+-- Predicate deletes recorded against a table.
+create table if not exists table_tombstone
+(
+    id                   INTEGER
+        constraint table_tombstone_pkey
+            primary key autoincrement,
+    table_id             numeric not null
+        references table_name
+            on delete cascade,
+    min_time             numeric not null,
+    max_time             numeric not null,
+    serialized_predicate text    not null,
+    created_at           numeric not null
+);
+
+create index if not exists table_tombstone_table_id_idx
+    on table_tombstone (table_id);
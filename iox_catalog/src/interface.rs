@@ -3,11 +3,11 @@
 use async_trait::async_trait;
 use data_types::{
     partition_template::{NamespacePartitionTemplateOverride, TablePartitionTemplateOverride},
-    Column, ColumnType, ColumnsByName, CompactionLevel, MaxColumnsPerTable, MaxTables, Namespace,
-    NamespaceId, NamespaceName, NamespaceSchema, NamespaceServiceProtectionLimitsOverride,
-    ParquetFile, ParquetFileId, ParquetFileParams, Partition, PartitionHashId, PartitionId,
-    PartitionKey, SkippedCompaction, SortedColumnSet, Table, TableId, TableSchema, Timestamp,
-    TransitionPartitionId,
+    Column, ColumnType, ColumnsByName, CompactionLevel, DeletePredicate, MaxColumnsPerTable,
+    MaxTables, Namespace, NamespaceId, NamespaceName, NamespaceSchema,
+    NamespaceServiceProtectionLimitsOverride, ParquetFile, ParquetFileId, ParquetFileParams,
+    Partition, PartitionHashId, PartitionId, PartitionKey, SkippedCompaction, SortedColumnSet,
+    Table, TableId, TableSchema, Timestamp, Tombstone, TransitionPartitionId,
 };
 use iox_time::TimeProvider;
 use snafu::{OptionExt, Snafu};
@@ -249,6 +249,9 @@ pub trait RepoCollection: Send + Sync + Debug {
 
     /// Repository for [Parquet files](data_types::ParquetFile).
     fn parquet_files(&mut self) -> &mut dyn ParquetFileRepo;
+
+    /// Repository for [tombstones](data_types::Tombstone).
+    fn tombstones(&mut self) -> &mut dyn TombstoneRepo;
 }
 
 /// Functions for working with namespaces in the catalog
@@ -544,6 +547,19 @@ pub trait ParquetFileRepo: Send + Sync {
     ) -> Result<Vec<ParquetFileId>>;
 }
 
+/// Functions for working with tombstones (predicate deletes) in the catalog
+#[async_trait]
+pub trait TombstoneRepo: Send + Sync {
+    /// Record a delete of the data matching `predicate` in the given table.
+    ///
+    /// The tombstone's `created_at` is assigned by the catalog.
+    async fn create(&mut self, table_id: TableId, predicate: &DeletePredicate)
+        -> Result<Tombstone>;
+
+    /// List all tombstones for the given table, ordered by ID.
+    async fn list_by_table_id(&mut self, table_id: TableId) -> Result<Vec<Tombstone>>;
+}
+
 /// Gets the namespace schema including all tables and columns.
 pub async fn get_schema_by_id<R>(
     id: NamespaceId,
@@ -798,6 +814,7 @@ pub(crate) mod test_helpers {
         test_list_schemas(clean_state().await).await;
         test_list_schemas_soft_deleted_rows(clean_state().await).await;
         test_delete_namespace(clean_state().await).await;
+        test_tombstone(clean_state().await).await;
 
         let catalog = clean_state().await;
         test_namespace(Arc::clone(&catalog)).await;
@@ -3097,6 +3114,72 @@ pub(crate) mod test_helpers {
             .expect("delete namespace should succeed");
     }
 
+    async fn test_tombstone(catalog: Arc<dyn Catalog>) {
+        let mut repos = catalog.repositories().await;
+        let namespace = arbitrary_namespace(&mut *repos, "namespace_tombstone_test").await;
+        let table = arbitrary_table(&mut *repos, "tombstone_table", &namespace).await;
+        let other_table = arbitrary_table(&mut *repos, "other_table", &namespace).await;
+
+        let predicate = DeletePredicate {
+            range: data_types::TimestampRange::new(10, 20),
+            exprs: vec![data_types::DeleteExpr::new(
+                "city".to_string(),
+                data_types::Op::Eq,
+                data_types::Scalar::String("Boston".to_string()),
+            )],
+        };
+
+        let before = Timestamp::from(catalog.time_provider().now());
+        let t1 = repos
+            .tombstones()
+            .create(table.id, &predicate)
+            .await
+            .unwrap();
+        assert_eq!(t1.table_id, table.id);
+        assert_eq!(t1.min_time, Timestamp::new(10));
+        assert_eq!(t1.max_time, Timestamp::new(20));
+        assert_eq!(t1.serialized_predicate, r#""city"='Boston'"#);
+        assert!(t1.created_at >= before);
+
+        let t2 = repos
+            .tombstones()
+            .create(
+                table.id,
+                &DeletePredicate {
+                    range: data_types::TimestampRange::new(1, 2),
+                    exprs: vec![],
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(t2.serialized_predicate, "");
+
+        let listed = repos.tombstones().list_by_table_id(table.id).await.unwrap();
+        assert_eq!(listed, vec![t1, t2]);
+
+        let listed = repos
+            .tombstones()
+            .list_by_table_id(other_table.id)
+            .await
+            .unwrap();
+        assert!(listed.is_empty());
+
+        // A tombstone cannot be created for a table that does not exist.
+        let err = repos
+            .tombstones()
+            .create(TableId::new(i64::MAX), &predicate)
+            .await
+            .unwrap_err();
+        assert_matches!(err, Error::TableNotFound { .. });
+
+        // remove namespace to avoid it from affecting later tests
+        repos
+            .namespaces()
+            .soft_delete("namespace_tombstone_test")
+            .await
+            .expect("delete namespace should succeed");
+    }
+
     /// Assert that a namespace deletion does NOT cascade to the tables/schema
     /// items/parquet files/etc.
     ///
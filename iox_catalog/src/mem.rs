@@ -6,7 +6,7 @@ use crate::{
     interface::{
         CasFailure, Catalog, ColumnRepo, ColumnTypeMismatchSnafu, Error, NamespaceRepo,
         ParquetFileRepo, PartitionRepo, RepoCollection, Result, SoftDeletedRows, TableRepo,
-        MAX_PARQUET_FILES_SELECTED_ONCE_FOR_RETENTION,
+        TombstoneRepo, MAX_PARQUET_FILES_SELECTED_ONCE_FOR_RETENTION,
     },
     metrics::MetricDecorator,
 };
@@ -16,10 +16,10 @@ use data_types::{
     partition_template::{
         NamespacePartitionTemplateOverride, TablePartitionTemplateOverride, TemplatePart,
     },
-    Column, ColumnId, ColumnType, CompactionLevel, MaxColumnsPerTable, MaxTables, Namespace,
-    NamespaceId, NamespaceName, NamespaceServiceProtectionLimitsOverride, ParquetFile,
+    Column, ColumnId, ColumnType, CompactionLevel, DeletePredicate, MaxColumnsPerTable, MaxTables,
+    Namespace, NamespaceId, NamespaceName, NamespaceServiceProtectionLimitsOverride, ParquetFile,
     ParquetFileId, ParquetFileParams, Partition, PartitionHashId, PartitionId, PartitionKey,
-    SkippedCompaction, Table, TableId, Timestamp, TransitionPartitionId,
+    SkippedCompaction, Table, TableId, Timestamp, Tombstone, TombstoneId, TransitionPartitionId,
 };
 use iox_time::{SystemProvider, TimeProvider};
 use snafu::ensure;
@@ -71,6 +71,7 @@ struct MemCollections {
     partitions: Vec<Partition>,
     skipped_compactions: Vec<SkippedCompaction>,
     parquet_files: Vec<ParquetFile>,
+    tombstones: Vec<Tombstone>,
 }
 
 /// transaction bound to an in-memory catalog.
@@ -140,6 +141,10 @@ impl RepoCollection for MemTxn {
     fn parquet_files(&mut self) -> &mut dyn ParquetFileRepo {
         self
     }
+
+    fn tombstones(&mut self) -> &mut dyn TombstoneRepo {
+        self
+    }
 }
 
 #[async_trait]
@@ -1021,6 +1026,45 @@ impl ParquetFileRepo for MemTxn {
     }
 }
 
+#[async_trait]
+impl TombstoneRepo for MemTxn {
+    async fn create(
+        &mut self,
+        table_id: TableId,
+        predicate: &DeletePredicate,
+    ) -> Result<Tombstone> {
+        let created_at = Timestamp::from(self.time_provider.now());
+        let stage = self.stage();
+
+        if !stage.tables.iter().any(|t| t.id == table_id) {
+            return Err(Error::TableNotFound { id: table_id });
+        }
+
+        let tombstone = Tombstone {
+            id: TombstoneId::new(stage.tombstones.len() as i64 + 1),
+            table_id,
+            min_time: Timestamp::new(predicate.range.start()),
+            max_time: Timestamp::new(predicate.range.end()),
+            serialized_predicate: predicate.expr_sql_string(),
+            created_at,
+        };
+        stage.tombstones.push(tombstone.clone());
+
+        Ok(tombstone)
+    }
+
+    async fn list_by_table_id(&mut self, table_id: TableId) -> Result<Vec<Tombstone>> {
+        let stage = self.stage();
+
+        Ok(stage
+            .tombstones
+            .iter()
+            .filter(|t| t.table_id == table_id)
+            .cloned()
+            .collect())
+    }
+}
+
 fn filter_namespace_soft_delete<'a>(
     v: impl IntoIterator<Item = &'a Namespace>,
     deleted: SoftDeletedRows,
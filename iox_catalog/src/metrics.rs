@@ -2,15 +2,16 @@
 
 use crate::interface::{
     CasFailure, ColumnRepo, NamespaceRepo, ParquetFileRepo, PartitionRepo, RepoCollection, Result,
-    SoftDeletedRows, TableRepo,
+    SoftDeletedRows, TableRepo, TombstoneRepo,
 };
 use async_trait::async_trait;
 use data_types::{
     partition_template::{NamespacePartitionTemplateOverride, TablePartitionTemplateOverride},
-    Column, ColumnType, CompactionLevel, MaxColumnsPerTable, MaxTables, Namespace, NamespaceId,
-    NamespaceName, NamespaceServiceProtectionLimitsOverride, ParquetFile, ParquetFileId,
-    ParquetFileParams, Partition, PartitionHashId, PartitionId, PartitionKey, SkippedCompaction,
-    SortedColumnSet, Table, TableId, Timestamp, TransitionPartitionId,
+    Column, ColumnType, CompactionLevel, DeletePredicate, MaxColumnsPerTable, MaxTables, Namespace,
+    NamespaceId, NamespaceName, NamespaceServiceProtectionLimitsOverride, ParquetFile,
+    ParquetFileId, ParquetFileParams, Partition, PartitionHashId, PartitionId, PartitionKey,
+    SkippedCompaction, SortedColumnSet, Table, TableId, Timestamp, Tombstone,
+    TransitionPartitionId,
 };
 use iox_time::{SystemProvider, TimeProvider};
 use metric::{DurationHistogram, Metric};
@@ -43,7 +44,13 @@ impl<T> MetricDecorator<T> {
 
 impl<T, P> RepoCollection for MetricDecorator<T, P>
 where
-    T: NamespaceRepo + TableRepo + ColumnRepo + PartitionRepo + ParquetFileRepo + Debug,
+    T: NamespaceRepo
+        + TableRepo
+        + ColumnRepo
+        + PartitionRepo
+        + ParquetFileRepo
+        + TombstoneRepo
+        + Debug,
     P: TimeProvider,
 {
     fn namespaces(&mut self) -> &mut dyn NamespaceRepo {
@@ -65,6 +72,10 @@ where
     fn parquet_files(&mut self) -> &mut dyn ParquetFileRepo {
         self
     }
+
+    fn tombstones(&mut self) -> &mut dyn TombstoneRepo {
+        self
+    }
 }
 
 /// Emit a trait impl for `impl_trait` that delegates calls to the inner
@@ -202,3 +213,11 @@ decorate!(
         "parquet_create_upgrade_delete" = create_upgrade_delete(&mut self, delete: &[ParquetFileId], upgrade: &[ParquetFileId], create: &[ParquetFileParams], target_level: CompactionLevel) -> Result<Vec<ParquetFileId>>;
     ]
 );
+
+decorate!(
+    impl_trait = TombstoneRepo,
+    methods = [
+        "tombstone_create" = create(&mut self, table_id: TableId, predicate: &DeletePredicate) -> Result<Tombstone>;
+        "tombstone_list_by_table_id" = list_by_table_id(&mut self, table_id: TableId) -> Result<Vec<Tombstone>>;
+    ]
+);
@@ -5,7 +5,7 @@ use crate::{
     interface::{
         self, CasFailure, Catalog, ColumnRepo, ColumnTypeMismatchSnafu, Error, NamespaceRepo,
         ParquetFileRepo, PartitionRepo, RepoCollection, Result, SoftDeletedRows, TableRepo,
-        MAX_PARQUET_FILES_SELECTED_ONCE_FOR_RETENTION,
+        TombstoneRepo, MAX_PARQUET_FILES_SELECTED_ONCE_FOR_RETENTION,
     },
     kafkaless_transition::{
         SHARED_QUERY_POOL, SHARED_QUERY_POOL_ID, SHARED_TOPIC_ID, SHARED_TOPIC_NAME,
@@ -20,10 +20,10 @@ use data_types::{
     partition_template::{
         NamespacePartitionTemplateOverride, TablePartitionTemplateOverride, TemplatePart,
     },
-    Column, ColumnType, CompactionLevel, MaxColumnsPerTable, MaxTables, Namespace, NamespaceId,
-    NamespaceName, NamespaceServiceProtectionLimitsOverride, ParquetFile, ParquetFileId,
-    ParquetFileParams, Partition, PartitionHashId, PartitionId, PartitionKey, SkippedCompaction,
-    Table, TableId, Timestamp, TransitionPartitionId,
+    Column, ColumnType, CompactionLevel, DeletePredicate, MaxColumnsPerTable, MaxTables, Namespace,
+    NamespaceId, NamespaceName, NamespaceServiceProtectionLimitsOverride, ParquetFile,
+    ParquetFileId, ParquetFileParams, Partition, PartitionHashId, PartitionId, PartitionKey,
+    SkippedCompaction, Table, TableId, Timestamp, Tombstone, TransitionPartitionId,
 };
 use iox_time::{SystemProvider, TimeProvider};
 use metric::{Attributes, Instrument, MetricKind};
@@ -631,6 +631,10 @@ impl RepoCollection for PostgresTxn {
     fn parquet_files(&mut self) -> &mut dyn ParquetFileRepo {
         self
     }
+
+    fn tombstones(&mut self) -> &mut dyn TombstoneRepo {
+        self
+    }
 }
 
 async fn insert_column_with_connection<'q, E>(
@@ -1805,6 +1809,54 @@ WHERE object_store_id = ANY($1);
     }
 }
 
+#[async_trait]
+impl TombstoneRepo for PostgresTxn {
+    async fn create(
+        &mut self,
+        table_id: TableId,
+        predicate: &DeletePredicate,
+    ) -> Result<Tombstone> {
+        let created_at = Timestamp::from(self.time_provider.now());
+
+        sqlx::query_as::<_, Tombstone>(
+            r#"
+INSERT INTO table_tombstone ( table_id, min_time, max_time, serialized_predicate, created_at )
+VALUES ( $1, $2, $3, $4, $5 )
+RETURNING *;
+            "#,
+        )
+        .bind(table_id) // $1
+        .bind(predicate.range.start()) // $2
+        .bind(predicate.range.end()) // $3
+        .bind(predicate.expr_sql_string()) // $4
+        .bind(created_at) // $5
+        .fetch_one(&mut self.inner)
+        .await
+        .map_err(|e| {
+            if is_fk_violation(&e) {
+                Error::TableNotFound { id: table_id }
+            } else {
+                Error::SqlxError { source: e }
+            }
+        })
+    }
+
+    async fn list_by_table_id(&mut self, table_id: TableId) -> Result<Vec<Tombstone>> {
+        sqlx::query_as::<_, Tombstone>(
+            r#"
+SELECT *
+FROM table_tombstone
+WHERE table_id = $1
+ORDER BY id;
+            "#,
+        )
+        .bind(table_id) // $1
+        .fetch_all(&mut self.inner)
+        .await
+        .map_err(|e| Error::SqlxError { source: e })
+    }
+}
+
 // The following three functions are helpers to the create_upgrade_delete method.
 // They are also used by the respective create/flag_for_delete/update_compaction_level methods.
 async fn create_parquet_file<'q, E>(
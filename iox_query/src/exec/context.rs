@@ -29,6 +29,7 @@ use crate::{
         seriesset::{SeriesSetPlan, SeriesSetPlans},
         stringset::StringSetPlan,
     },
+    QueryCanceller, QueryDeleter, QueryNamespaceResolver, QueryPointsWriter,
 };
 use arrow::record_batch::RecordBatch;
 use async_trait::async_trait;
@@ -51,7 +52,10 @@ use datafusion_util::config::{iox_session_config, DEFAULT_CATALOG};
 use executor::DedicatedExecutor;
 use futures::{Stream, StreamExt, TryStreamExt};
 use observability_deps::tracing::{debug, warn};
-use query_functions::{register_scalar_functions, selectors::register_selector_aggregates};
+use query_functions::{
+    register_aggregate_functions, register_scalar_functions,
+    selectors::register_selector_aggregates,
+};
 use std::{fmt, num::NonZeroUsize, sync::Arc};
 use trace::{
     ctx::SpanContext,
@@ -176,6 +180,18 @@ pub struct IOxSessionConfig {
 
     /// Span context from which to create spans for this query
     span_ctx: Option<SpanContext>,
+
+    /// Writer used by queries that write points, such as `SELECT ... INTO`
+    points_writer: Option<Arc<dyn QueryPointsWriter>>,
+
+    /// Deleter used by queries that delete data, such as `DELETE`
+    deleter: Option<Arc<dyn QueryDeleter>>,
+
+    /// Resolver for the other namespaces referenced by queries
+    namespace_resolver: Option<Arc<dyn QueryNamespaceResolver>>,
+
+    /// Canceller used by queries that cancel other queries, such as `KILL QUERY`
+    query_canceller: Option<Arc<dyn QueryCanceller>>,
 }
 
 impl fmt::Debug for IOxSessionConfig {
@@ -198,6 +214,10 @@ impl IOxSessionConfig {
             runtime,
             default_catalog: None,
             span_ctx: None,
+            points_writer: None,
+            deleter: None,
+            namespace_resolver: None,
+            query_canceller: None,
         }
     }
 
@@ -222,6 +242,43 @@ impl IOxSessionConfig {
         Self { span_ctx, ..self }
     }
 
+    /// Set the writer used by queries that write points, such as `SELECT ... INTO`
+    pub fn with_points_writer(self, points_writer: Arc<dyn QueryPointsWriter>) -> Self {
+        Self {
+            points_writer: Some(points_writer),
+            ..self
+        }
+    }
+
+    /// Set the deleter used by queries that delete data, such as `DELETE`
+    pub fn with_deleter(self, deleter: Arc<dyn QueryDeleter>) -> Self {
+        Self {
+            deleter: Some(deleter),
+            ..self
+        }
+    }
+
+    /// Set the resolver for the other namespaces referenced by queries,
+    /// such as `FROM db.rp.measurement` in InfluxQL
+    pub fn with_namespace_resolver(
+        self,
+        namespace_resolver: Arc<dyn QueryNamespaceResolver>,
+    ) -> Self {
+        Self {
+            namespace_resolver: Some(namespace_resolver),
+            ..self
+        }
+    }
+
+    /// Set the canceller used by queries that cancel other queries, such as
+    /// `KILL QUERY` in InfluxQL
+    pub fn with_query_canceller(self, query_canceller: Arc<dyn QueryCanceller>) -> Self {
+        Self {
+            query_canceller: Some(query_canceller),
+            ..self
+        }
+    }
+
     /// Set DataFusion [config option].
     ///
     /// May be used to set [IOx-specific] option as well.
@@ -260,11 +317,21 @@ impl IOxSessionConfig {
         let inner = SessionContext::with_state(state);
         register_selector_aggregates(&inner);
         register_scalar_functions(&inner);
+        register_aggregate_functions(&inner);
         if let Some(default_catalog) = self.default_catalog {
             inner.register_catalog(DEFAULT_CATALOG, default_catalog);
         }
 
-        IOxSessionContext::new(inner, self.exec, recorder)
+        IOxSessionContext::new(
+            inner,
+            self.exec,
+            recorder,
+            self.points_writer,
+            self.deleter,
+            self.namespace_resolver,
+            self.query_canceller,
+            None,
+        )
     }
 }
 
@@ -293,6 +360,21 @@ pub struct IOxSessionContext {
 
     /// Span context from which to create spans for this query
     recorder: SpanRecorder,
+
+    /// Writer used by queries that write points, such as `SELECT ... INTO`
+    points_writer: Option<Arc<dyn QueryPointsWriter>>,
+
+    /// Deleter used by queries that delete data, such as `DELETE`
+    deleter: Option<Arc<dyn QueryDeleter>>,
+
+    /// Resolver for the other namespaces referenced by queries
+    namespace_resolver: Option<Arc<dyn QueryNamespaceResolver>>,
+
+    /// Canceller used by queries that cancel other queries, such as `KILL QUERY`
+    query_canceller: Option<Arc<dyn QueryCanceller>>,
+
+    /// Authorization token of the request running the query, if any
+    authz_token: Option<Vec<u8>>,
 }
 
 impl fmt::Debug for IOxSessionContext {
@@ -301,6 +383,14 @@ impl fmt::Debug for IOxSessionContext {
             .field("inner", &"<DataFusion ExecutionContext>")
             .field("exec", &self.exec)
             .field("recorder", &self.recorder)
+            .field("points_writer", &self.points_writer)
+            .field("deleter", &self.deleter)
+            .field("namespace_resolver", &self.namespace_resolver)
+            .field("query_canceller", &self.query_canceller)
+            .field(
+                "authz_token",
+                &self.authz_token.as_ref().map(|_| "<redacted>"),
+            )
             .finish()
     }
 }
@@ -315,19 +405,35 @@ impl IOxSessionContext {
             inner: SessionContext::default(),
             exec: DedicatedExecutor::new_testing(),
             recorder: SpanRecorder::default(),
+            points_writer: None,
+            deleter: None,
+            namespace_resolver: None,
+            query_canceller: None,
+            authz_token: None,
         }
     }
 
     /// Private constructor
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         inner: SessionContext,
         exec: DedicatedExecutor,
         recorder: SpanRecorder,
+        points_writer: Option<Arc<dyn QueryPointsWriter>>,
+        deleter: Option<Arc<dyn QueryDeleter>>,
+        namespace_resolver: Option<Arc<dyn QueryNamespaceResolver>>,
+        query_canceller: Option<Arc<dyn QueryCanceller>>,
+        authz_token: Option<Vec<u8>>,
     ) -> Self {
         Self {
             inner,
             exec,
             recorder,
+            points_writer,
+            deleter,
+            namespace_resolver,
+            query_canceller,
+            authz_token,
         }
     }
 
@@ -666,6 +772,11 @@ impl IOxSessionContext {
             self.inner.clone(),
             self.exec.clone(),
             self.recorder.child(name),
+            self.points_writer.clone(),
+            self.deleter.clone(),
+            self.namespace_resolver.clone(),
+            self.query_canceller.clone(),
+            self.authz_token.clone(),
         )
     }
 
@@ -689,6 +800,40 @@ impl IOxSessionContext {
         self.recorder.child_span(name)
     }
 
+    /// Set the authorization token of the request running the query, which
+    /// queries that write points, such as `SELECT ... INTO`, write on behalf of
+    pub fn with_authz_token(self, authz_token: Option<Vec<u8>>) -> Self {
+        Self {
+            authz_token,
+            ..self
+        }
+    }
+
+    /// Returns the authorization token of the request running the query, if any
+    pub fn authz_token(&self) -> Option<&[u8]> {
+        self.authz_token.as_deref()
+    }
+
+    /// Returns the writer used by queries that write points, if any
+    pub fn points_writer(&self) -> Option<&Arc<dyn QueryPointsWriter>> {
+        self.points_writer.as_ref()
+    }
+
+    /// Returns the deleter used by queries that delete data, if any
+    pub fn deleter(&self) -> Option<&Arc<dyn QueryDeleter>> {
+        self.deleter.as_ref()
+    }
+
+    /// Returns the resolver for the other namespaces referenced by queries, if any
+    pub fn namespace_resolver(&self) -> Option<&Arc<dyn QueryNamespaceResolver>> {
+        self.namespace_resolver.as_ref()
+    }
+
+    /// Returns the canceller used by queries that cancel other queries, if any
+    pub fn query_canceller(&self) -> Option<&Arc<dyn QueryCanceller>> {
+        self.query_canceller.as_ref()
+    }
+
     /// Number of currently active tasks.
     pub fn tasks(&self) -> usize {
         self.exec.tasks()
@@ -14,7 +14,7 @@
 
 use datafusion_util::MemoryStream;
 use futures::TryStreamExt;
-use trace::ctx::SpanContext;
+use trace::{ctx::SpanContext, span::Span};
 
 // Workaround for "unused crate" lint false positives.
 use workspace_hack as _;
@@ -24,8 +24,9 @@ use arrow::{
     record_batch::RecordBatch,
 };
 use async_trait::async_trait;
-use data_types::{ChunkId, ChunkOrder, TransitionPartitionId};
+use data_types::{ChunkId, ChunkOrder, DeletePredicate, TransitionPartitionId};
 use datafusion::{
+    catalog::schema::SchemaProvider,
     error::DataFusionError,
     physical_plan::{SendableRecordBatchStream, Statistics},
     prelude::{Expr, SessionContext},
@@ -35,6 +36,7 @@ use once_cell::sync::Lazy;
 use parquet_file::storage::ParquetExecInput;
 use schema::{sort::SortKey, Projection, Schema};
 use std::{any::Any, fmt::Debug, sync::Arc};
+use tokio_util::sync::CancellationToken;
 
 pub mod chunk_statistics;
 pub mod config;
@@ -94,6 +96,14 @@ pub trait QueryChunk: Debug + Send + Sync + 'static {
     /// Order of this chunk relative to other overlapping chunks.
     fn order(&self) -> ChunkOrder;
 
+    /// Delete predicates that apply to this chunk.
+    ///
+    /// Rows matching any of these predicates are removed by the query engine
+    /// when the chunk is scanned.
+    fn delete_predicates(&self) -> &[Arc<DeletePredicate>] {
+        &[]
+    }
+
     /// Return backend as [`Any`] which can be used to downcast to a specific implementation.
     fn as_any(&self) -> &dyn Any;
 }
@@ -109,6 +119,10 @@ pub struct QueryCompletedToken {
     /// Function invoked when the token is dropped. It is passed the
     /// vaue of `self.success`
     f: Option<Box<dyn FnOnce(bool) + Send>>,
+
+    /// Token that is cancelled when the query is cancelled, see
+    /// [`QueryCanceller`].
+    cancellation: CancellationToken,
 }
 
 impl Debug for QueryCompletedToken {
@@ -124,6 +138,15 @@ impl QueryCompletedToken {
         Self {
             success: false,
             f: Some(Box::new(f)),
+            cancellation: CancellationToken::new(),
+        }
+    }
+
+    /// Cancel the query when `cancellation` is cancelled.
+    pub fn with_cancellation(self, cancellation: CancellationToken) -> Self {
+        Self {
+            cancellation,
+            ..self
         }
     }
 
@@ -131,6 +154,14 @@ impl QueryCompletedToken {
     pub fn set_success(&mut self) {
         self.success = true;
     }
+
+    /// Token that is cancelled when the query is cancelled.
+    ///
+    /// Whoever executes the query should stop doing so once this token is
+    /// cancelled.
+    pub fn cancellation_token(&self) -> &CancellationToken {
+        &self.cancellation
+    }
 }
 
 impl Drop for QueryCompletedToken {
@@ -188,10 +219,106 @@ pub trait QueryNamespace: Debug + Send + Sync {
         query_text: QueryText,
     ) -> QueryCompletedToken;
 
+    /// Record that particular type of query was run / planned, using the
+    /// query ID identified by the `query_handle` created with
+    /// [`QueryCanceller::reserve_query_handle`].
+    ///
+    /// Unknown handles, and namespaces that do not support query
+    /// cancellation, record the query as [`record_query`](Self::record_query)
+    /// does.
+    fn record_query_with_handle(
+        &self,
+        span_ctx: Option<&SpanContext>,
+        query_type: &'static str,
+        query_text: QueryText,
+        query_handle: u64,
+    ) -> QueryCompletedToken {
+        let _ = query_handle;
+        self.record_query(span_ctx, query_type, query_text)
+    }
+
     /// Returns a new execution context suitable for running queries
     fn new_query_context(&self, span_ctx: Option<SpanContext>) -> IOxSessionContext;
 }
 
+/// `QueryPointsWriter` writes points produced by a query, such as the
+/// results of an InfluxQL `SELECT ... INTO` statement, back into storage.
+#[async_trait]
+pub trait QueryPointsWriter: Debug + Send + Sync {
+    /// Write the line protocol `lp` to the specified database and
+    /// retention policy.
+    ///
+    /// If `database` is `None` the points are written to the namespace
+    /// the query is running against. If `retention_policy` is `None`
+    /// the default retention policy of the database is used.
+    ///
+    /// The points are written on behalf of the caller identified by
+    /// `authz_token`, if any.
+    async fn write_lp(
+        &self,
+        database: Option<&str>,
+        retention_policy: Option<&str>,
+        lp: String,
+        authz_token: Option<&[u8]>,
+    ) -> Result<(), DataFusionError>;
+}
+
+/// `QueryDeleter` deletes data on behalf of a query, such as an InfluxQL
+/// `DELETE`, `DROP MEASUREMENT` or `DROP SERIES` statement.
+#[async_trait]
+pub trait QueryDeleter: Debug + Send + Sync {
+    /// Delete the rows matching `predicate` from the table `table_name` of
+    /// the namespace the query is running against.
+    ///
+    /// The rows are deleted on behalf of the caller identified by
+    /// `authz_token`, if any.
+    async fn delete(
+        &self,
+        table_name: &str,
+        predicate: &DeletePredicate,
+        authz_token: Option<&[u8]>,
+    ) -> Result<(), DataFusionError>;
+}
+
+/// `QueryNamespaceResolver` resolves the namespaces a query refers to
+/// other than the namespace it is running against, such as the database
+/// of an InfluxQL `SELECT ... FROM db.rp.measurement` statement.
+#[async_trait]
+pub trait QueryNamespaceResolver: Debug + Send + Sync {
+    /// Return the tables of the namespace named `namespace_name`, or `None`
+    /// if the namespace does not exist.
+    async fn schema_provider(
+        &self,
+        namespace_name: &str,
+        span: Option<Span>,
+    ) -> Result<Option<Arc<dyn SchemaProvider>>, DataFusionError>;
+}
+
+/// `QueryCanceller` cancels the running queries of a namespace, such as for
+/// an InfluxQL `KILL QUERY` statement.
+pub trait QueryCanceller: Debug + Send + Sync {
+    /// Cancel the running query with the ID `query_id`.
+    ///
+    /// Returns `false` if there is no such query or if it already
+    /// completed.
+    fn cancel_query(&self, query_id: u64) -> bool;
+
+    /// Reserve the ID of a query that is run later, such as the query of a
+    /// Flight SQL `FlightInfo`, returning an opaque handle that identifies
+    /// it.
+    ///
+    /// The ID is allocated by the namespace, and the handle cannot be used
+    /// to guess the IDs of other queries. See
+    /// [`QueryNamespace::record_query_with_handle`].
+    fn reserve_query_handle(&self) -> u64;
+
+    /// Cancel the running query with the ID identified by `query_handle`.
+    ///
+    /// Returns `false` if there is no such query or if it already
+    /// completed.
+    fn cancel_query_handle(&self, query_handle: u64) -> bool;
+}
+
 /// Raw data of a [`QueryChunk`].
 pub enum QueryChunkData {
     /// Record batches.
@@ -276,6 +403,10 @@ where
         self.as_ref().order()
     }
 
+    fn delete_predicates(&self) -> &[Arc<DeletePredicate>] {
+        self.as_ref().delete_predicates()
+    }
+
     fn as_any(&self) -> &dyn Any {
         // present the underlying implementation, not the wrapper
         self.as_ref().as_any()
@@ -319,6 +450,10 @@ impl QueryChunk for Arc<dyn QueryChunk> {
         self.as_ref().order()
     }
 
+    fn delete_predicates(&self) -> &[Arc<DeletePredicate>] {
+        self.as_ref().delete_predicates()
+    }
+
     fn as_any(&self) -> &dyn Any {
         // present the underlying implementation, not the wrapper
         self.as_ref().as_any()
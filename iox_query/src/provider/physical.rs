@@ -1,10 +1,12 @@
 //! Implementation of a DataFusion PhysicalPlan node across partition chunks
 
 use crate::{
-    provider::record_batch_exec::RecordBatchesExec, util::arrow_sort_key_exprs, QueryChunk,
-    QueryChunkData, CHUNK_ORDER_COLUMN_NAME,
+    provider::record_batch_exec::RecordBatchesExec,
+    util::{arrow_sort_key_exprs, df_keep_rows_physical_expr},
+    QueryChunk, QueryChunkData, CHUNK_ORDER_COLUMN_NAME,
 };
 use arrow::datatypes::{DataType, Fields, Schema as ArrowSchema, SchemaRef};
+use data_types::DeletePredicate;
 use datafusion::{
     datasource::{
         listing::PartitionedFile,
@@ -13,8 +15,8 @@ use datafusion::{
     },
     physical_expr::PhysicalSortExpr,
     physical_plan::{
-        empty::EmptyExec, expressions::Column, union::UnionExec, ColumnStatistics, ExecutionPlan,
-        Statistics,
+        empty::EmptyExec, expressions::Column, filter::FilterExec, union::UnionExec,
+        ColumnStatistics, ExecutionPlan, Statistics,
     },
     scalar::ScalarValue,
 };
@@ -134,10 +136,11 @@ fn combine_sort_key(
 /// # Empty Inputs
 /// For empty inputs (i.e. no chunks), this will create a single [`EmptyExec`] node with appropriate schema.
 ///
-/// # Predicates
-/// The give `predicate` will only be applied to [`ParquetExec`] nodes since they are the only node type benifiting from
-/// pushdown ([`RecordBatchesExec`] has NO builtin filter function). Delete predicates are NOT applied at all. The
-/// caller is responsible for wrapping the output node into appropriate filter nodes.
+/// # Delete Predicates
+/// Chunks with [delete predicates](QueryChunk::delete_predicates) are grouped by their set of predicates, and each
+/// group is scanned separately and wrapped into a [`FilterExec`] that removes the deleted rows. All scans are then
+/// combined using an [`UnionExec`]. The physical optimizer passes that extract chunks from the output of this function
+/// do not see through these filters and hence leave such plans untouched.
 pub fn chunks_to_physical_nodes(
     schema: &SchemaRef,
     output_sort_key: Option<&SortKey>,
@@ -148,6 +151,54 @@ pub fn chunks_to_physical_nodes(
         return Arc::new(EmptyExec::new(false, Arc::clone(schema)));
     }
 
+    if chunks.iter().all(|c| c.delete_predicates().is_empty()) {
+        return scan_chunks(schema, output_sort_key, chunks, target_partitions);
+    }
+
+    // Group the chunks by their delete predicates, retaining the order in
+    // which each set was first seen.
+    let mut groups: Vec<(&[Arc<DeletePredicate>], Vec<Arc<dyn QueryChunk>>)> = vec![];
+    for chunk in &chunks {
+        let preds = chunk.delete_predicates();
+        match groups.iter_mut().find(|(p, _)| *p == preds) {
+            Some((_, group)) => group.push(Arc::clone(chunk)),
+            None => groups.push((preds, vec![Arc::clone(chunk)])),
+        }
+    }
+
+    let output_nodes = groups
+        .into_iter()
+        .map(|(preds, group)| {
+            let plan = scan_chunks(schema, output_sort_key, group, target_partitions);
+            filter_deleted_rows(plan, preds)
+        })
+        .collect();
+
+    Arc::new(UnionExec::new(output_nodes))
+}
+
+/// Wrap `plan` into a [`FilterExec`] that removes all rows matching any of the given delete predicates.
+fn filter_deleted_rows(
+    plan: Arc<dyn ExecutionPlan>,
+    preds: &[Arc<DeletePredicate>],
+) -> Arc<dyn ExecutionPlan> {
+    let Some(keep) = df_keep_rows_physical_expr(plan.schema(), preds) else {
+        return plan;
+    };
+
+    Arc::new(
+        FilterExec::try_new(keep, Arc::clone(&plan))
+            .expect("delete predicates always evaluate to a boolean"),
+    )
+}
+
+/// Scan the given chunks, ignoring any delete predicates.
+fn scan_chunks(
+    schema: &SchemaRef,
+    output_sort_key: Option<&SortKey>,
+    chunks: Vec<Arc<dyn QueryChunk>>,
+    target_partitions: usize,
+) -> Arc<dyn ExecutionPlan> {
     let mut record_batch_chunks: Vec<Arc<dyn QueryChunk>> = vec![];
     let mut parquet_chunks: HashMap<String, ParquetChunkList> = HashMap::new();
 
@@ -352,8 +403,12 @@ where
 mod tests {
     use schema::{sort::SortKeyBuilder, SchemaBuilder, TIME_COLUMN_NAME};
 
+    use arrow_util::assert_batches_sorted_eq;
+    use data_types::{DeleteExpr, Op, Scalar, TimestampRange};
+
     use crate::{
         chunk_order_field,
+        exec::IOxSessionContext,
         test::{format_execution_plan, TestChunk},
     };
 
@@ -580,4 +635,63 @@ mod tests {
         "###
         );
     }
+
+    #[tokio::test]
+    async fn test_chunks_to_physical_nodes_delete_predicates() {
+        let pred = DeletePredicate {
+            range: TimestampRange::new(0, 15_000),
+            exprs: vec![DeleteExpr::new(
+                "tag1".to_string(),
+                Op::Eq,
+                Scalar::String("VT".to_string()),
+            )],
+        };
+        // A predicate on a column that does not exist cannot match any row.
+        let pred_missing_col = DeletePredicate {
+            range: TimestampRange::new(0, 15_000),
+            exprs: vec![DeleteExpr::new(
+                "bananas".to_string(),
+                Op::Ne,
+                Scalar::String("VT".to_string()),
+            )],
+        };
+
+        let chunk1 = TestChunk::new("table")
+            .with_id(1)
+            .with_tag_column("tag1")
+            .with_i64_field_column("field_int")
+            .with_time_column()
+            .with_three_rows_of_data()
+            .with_delete_predicate(pred)
+            .with_delete_predicate(pred_missing_col);
+        let chunk2 = TestChunk::new("table")
+            .with_id(2)
+            .with_tag_column("tag1")
+            .with_i64_field_column("field_int")
+            .with_time_column()
+            .with_three_rows_of_data();
+        let schema = chunk1.schema().as_arrow();
+
+        let plan =
+            chunks_to_physical_nodes(&schema, None, vec![Arc::new(chunk1), Arc::new(chunk2)], 2);
+        let batches = IOxSessionContext::with_testing()
+            .collect(plan)
+            .await
+            .unwrap();
+
+        assert_batches_sorted_eq!(
+            &[
+                "+-----------+------+-----------------------------+",
+                "| field_int | tag1 | time                        |",
+                "+-----------+------+-----------------------------+",
+                "| 10        | VT   | 1970-01-01T00:00:00.000010Z |",
+                "| 1000      | WA   | 1970-01-01T00:00:00.000008Z |",
+                "| 1000      | WA   | 1970-01-01T00:00:00.000008Z |",
+                "| 70        | UT   | 1970-01-01T00:00:00.000020Z |",
+                "| 70        | UT   | 1970-01-01T00:00:00.000020Z |",
+                "+-----------+------+-----------------------------+",
+            ],
+            &batches
+        );
+    }
 }
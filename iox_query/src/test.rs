@@ -20,7 +20,9 @@ use arrow::{
     record_batch::RecordBatch,
 };
 use async_trait::async_trait;
-use data_types::{ChunkId, ChunkOrder, PartitionKey, TableId, TransitionPartitionId};
+use data_types::{
+    ChunkId, ChunkOrder, DeletePredicate, PartitionKey, TableId, TransitionPartitionId,
+};
 use datafusion::error::DataFusionError;
 use datafusion::execution::context::SessionState;
 use datafusion::logical_expr::Expr;
@@ -47,6 +49,7 @@ use std::{
     num::NonZeroU64,
     sync::Arc,
 };
+use tokio_util::sync::CancellationToken;
 use trace::ctx::SpanContext;
 
 #[derive(Debug)]
@@ -65,6 +68,9 @@ pub struct TestDatabase {
 
     /// Retention time ns.
     retention_time_ns: Option<i64>,
+
+    /// Token that cancels all queries recorded with `record_query()`.
+    query_cancellation: CancellationToken,
 }
 
 impl TestDatabase {
@@ -75,6 +81,7 @@ impl TestDatabase {
             column_names: Default::default(),
             chunks_predicate: Default::default(),
             retention_time_ns: None,
+            query_cancellation: CancellationToken::new(),
         }
     }
 
@@ -116,6 +123,12 @@ impl TestDatabase {
         *Arc::clone(&self.column_names).lock() = Some(column_names)
     }
 
+    /// Cancel all queries recorded with `record_query()`, including those
+    /// recorded after this call.
+    pub fn cancel_queries(&self) {
+        self.query_cancellation.cancel();
+    }
+
     /// Set retention time.
     pub fn with_retention_time_ns(mut self, retention_time_ns: Option<i64>) -> Self {
         self.retention_time_ns = retention_time_ns;
@@ -166,7 +179,7 @@ impl QueryNamespace for TestDatabase {
         _query_type: &'static str,
         _query_text: QueryText,
     ) -> QueryCompletedToken {
-        QueryCompletedToken::new(|_| {})
+        QueryCompletedToken::new(|_| {}).with_cancellation(self.query_cancellation.clone())
     }
 
     fn new_query_context(&self, span_ctx: Option<SpanContext>) -> IOxSessionContext {
@@ -319,6 +332,9 @@ pub struct TestChunk {
     /// The sort key of this chunk
     sort_key: Option<SortKey>,
 
+    /// Delete predicates applied to this chunk
+    delete_predicates: Vec<Arc<DeletePredicate>>,
+
     /// Suppress output
     quiet: bool,
 }
@@ -382,6 +398,7 @@ impl TestChunk {
             saved_error: Default::default(),
             order: ChunkOrder::MIN,
             sort_key: None,
+            delete_predicates: vec![],
             partition_id: TransitionPartitionId::arbitrary_for_testing(),
             quiet: false,
         }
@@ -482,6 +499,11 @@ impl TestChunk {
         self
     }
 
+    pub fn with_delete_predicate(mut self, pred: DeletePredicate) -> Self {
+        self.delete_predicates.push(Arc::new(pred));
+        self
+    }
+
     /// Register a tag column with the test chunk with default stats
     pub fn with_tag_column(self, column_name: impl Into<String>) -> Self {
         let column_name = column_name.into();
@@ -1100,6 +1122,10 @@ impl QueryChunk for TestChunk {
         self.order
     }
 
+    fn delete_predicates(&self) -> &[Arc<DeletePredicate>] {
+        &self.delete_predicates
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
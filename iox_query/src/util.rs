@@ -6,22 +6,24 @@ use std::{
 };
 
 use arrow::{
-    array::TimestampNanosecondArray, compute::SortOptions, datatypes::Schema as ArrowSchema,
+    array::TimestampNanosecondArray,
+    compute::SortOptions,
+    datatypes::{Schema as ArrowSchema, SchemaRef},
     record_batch::RecordBatch,
 };
 
-use data_types::TimestampMinMax;
+use data_types::{DeletePredicate, TimestampMinMax};
 use datafusion::{
     self,
     common::ToDFSchema,
     datasource::{provider_as_source, MemTable},
     error::DataFusionError,
     execution::context::ExecutionProps,
-    logical_expr::{LogicalPlan, LogicalPlanBuilder},
+    logical_expr::{LogicalPlan, LogicalPlanBuilder, Operator},
     optimizer::simplify_expressions::{ExprSimplifier, SimplifyContext},
     physical_expr::create_physical_expr,
     physical_plan::{
-        expressions::{col as physical_col, PhysicalSortExpr},
+        expressions::{col as physical_col, BinaryExpr, PhysicalSortExpr},
         ColumnStatistics, ExecutionPlan, PhysicalExpr, Statistics,
     },
     prelude::{Column, Expr},
@@ -29,7 +31,8 @@ use datafusion::{
 };
 
 use itertools::Itertools;
-use observability_deps::tracing::trace;
+use observability_deps::tracing::{error, trace};
+use predicate::delete_predicate::keep_rows_exprs;
 use schema::{sort::SortKey, InfluxColumnType, Schema, TIME_COLUMN_NAME};
 use snafu::{ensure, OptionExt, ResultExt, Snafu};
 
@@ -123,8 +126,15 @@ pub fn df_physical_expr(
     input: &dyn ExecutionPlan,
     expr: Expr,
 ) -> std::result::Result<Arc<dyn PhysicalExpr>, DataFusionError> {
-    let schema = input.schema();
+    df_physical_expr_from_schema(input.schema(), expr)
+}
 
+/// Build a datafusion physical expression from a logical one, evaluated
+/// against data with the given `schema`
+pub fn df_physical_expr_from_schema(
+    schema: SchemaRef,
+    expr: Expr,
+) -> std::result::Result<Arc<dyn PhysicalExpr>, DataFusionError> {
     let df_schema = Arc::clone(&schema).to_dfschema_ref()?;
 
     let props = ExecutionProps::new();
@@ -139,6 +149,31 @@ pub fn df_physical_expr(
     create_physical_expr(&expr, df_schema.as_ref(), schema.as_ref(), &props)
 }
 
+/// Build a datafusion physical expression selecting the rows of data with the
+/// given `schema` that are not deleted by any of `preds`, or [`None`] if no
+/// predicate can match a row.
+///
+/// Each predicate is built separately, so a predicate that cannot be applied
+/// to `schema` (such as one with a value that cannot be coerced to the column
+/// type) is skipped without affecting the others.
+pub fn df_keep_rows_physical_expr(
+    schema: SchemaRef,
+    preds: &[Arc<DeletePredicate>],
+) -> Option<Arc<dyn PhysicalExpr>> {
+    keep_rows_exprs(preds, schema.as_ref())
+        .into_iter()
+        .filter_map(
+            |expr| match df_physical_expr_from_schema(Arc::clone(&schema), expr) {
+                Ok(v) => Some(v),
+                Err(e) => {
+                    error!(%e, "cannot apply delete predicate");
+                    None
+                }
+            },
+        )
+        .reduce(|acc, expr| Arc::new(BinaryExpr::new(acc, Operator::And, expr)))
+}
+
 /// Return min and max for column `time` of the given set of record batches
 pub fn compute_timenanosecond_min_max<'a, I>(batches: I) -> Result<TimestampMinMax>
 where
@@ -0,0 +1,272 @@
+use arrow::array::{as_list_array, Array, ArrayRef, Float64Array, TimestampNanosecondArray};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Field, IntervalUnit, TimeUnit};
+use datafusion::common::{downcast_value, DataFusionError, Result, ScalarValue};
+use datafusion::logical_expr::{Accumulator, Signature, TypeSignature, Volatility};
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+/// The name of the integral aggregate function.
+pub(super) const NAME: &str = "integral";
+
+/// Valid signatures for the integral aggregate function.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| {
+    Signature::one_of(
+        crate::NUMERICS
+            .iter()
+            .map(|dt| {
+                TypeSignature::Exact(vec![
+                    dt.clone(),
+                    DataType::Timestamp(TimeUnit::Nanosecond, None),
+                    DataType::Interval(IntervalUnit::MonthDayNano),
+                ])
+            })
+            .collect(),
+        Volatility::Immutable,
+    )
+});
+
+/// Calculate the return type given the function signature. Integral
+/// always returns a Float64.
+pub(super) fn return_type(_: &[DataType]) -> Result<Arc<DataType>> {
+    Ok(Arc::new(DataType::Float64))
+}
+
+/// Create a new accumulator for the data type.
+pub(super) fn accumulator(_: &DataType) -> Result<Box<dyn Accumulator>> {
+    Ok(Box::<IntegralAccumulator>::default())
+}
+
+/// Calculate the intermediate merge state for the aggregator.
+pub(super) fn state_type(_: &DataType) -> Result<Arc<Vec<DataType>>> {
+    Ok(Arc::new(vec![
+        DataType::List(Arc::new(Field::new(
+            "item",
+            DataType::Timestamp(TimeUnit::Nanosecond, None),
+            true,
+        ))),
+        DataType::List(Arc::new(Field::new("item", DataType::Float64, true))),
+        DataType::Interval(IntervalUnit::MonthDayNano),
+    ]))
+}
+
+/// Accumulator which calculates the area under the curve of the input
+/// values using the trapezoidal rule, in the provided units.
+///
+/// When grouping by time, the planner instead sums the area calculated for
+/// each row by the `integral_window` window function, which splits the area
+/// between adjacent windows.
+#[derive(Debug, Default)]
+struct IntegralAccumulator {
+    points: Vec<(i64, f64)>,
+    /// The unit of the result, in nanoseconds.
+    unit: Option<i64>,
+}
+
+impl IntegralAccumulator {
+    fn update(&mut self, values: &ArrayRef, times: &ArrayRef) -> Result<()> {
+        let values = cast(values, &DataType::Float64)?;
+        let values = downcast_value!(values, Float64Array);
+        let times = downcast_value!(times, TimestampNanosecondArray);
+
+        self.points.reserve(values.len() - values.null_count());
+        for idx in 0..values.len() {
+            if values.is_valid(idx) && times.is_valid(idx) {
+                self.points.push((times.value(idx), values.value(idx)));
+            }
+        }
+        Ok(())
+    }
+
+    fn set_unit(&mut self, array: &ArrayRef) -> Result<()> {
+        if self.unit.is_none() && array.is_valid(0) {
+            let unit = ScalarValue::try_from_array(array, 0)?;
+            self.unit = Some(crate::unit_nanos(NAME, &unit)?);
+        }
+        Ok(())
+    }
+}
+
+impl Accumulator for IntegralAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        assert_eq!(values.len(), 3);
+
+        self.set_unit(&values[2])?;
+        self.update(&values[0], &values[1])
+    }
+
+    fn evaluate(&self) -> Result<ScalarValue> {
+        let Some(unit) = self.unit else {
+            return Ok(ScalarValue::Float64(None));
+        };
+        if self.points.is_empty() {
+            return Ok(ScalarValue::Float64(None));
+        }
+
+        let mut points = self.points.clone();
+        points.sort_by_key(|(t, _)| *t);
+
+        let mut sum = 0.0;
+        let mut prev = points[0];
+        for &(t, v) in &points[1..] {
+            // Points with the same timestamp as the previous point replace
+            // it, as the elapsed time between the points is zero.
+            if t != prev.0 {
+                let elapsed = (t - prev.0) as f64 / unit as f64;
+                sum += 0.5 * (v + prev.1) * elapsed;
+            }
+            prev = (t, v);
+        }
+
+        Ok(ScalarValue::Float64(Some(sum)))
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self) + self.points.capacity() * std::mem::size_of::<(i64, f64)>()
+    }
+
+    fn state(&self) -> Result<Vec<ScalarValue>> {
+        let (times, values): (Vec<_>, Vec<_>) = self
+            .points
+            .iter()
+            .map(|(t, v)| {
+                (
+                    ScalarValue::TimestampNanosecond(Some(*t), None),
+                    ScalarValue::Float64(Some(*v)),
+                )
+            })
+            .unzip();
+
+        Ok(vec![
+            ScalarValue::new_list(Some(times), DataType::Timestamp(TimeUnit::Nanosecond, None)),
+            ScalarValue::new_list(Some(values), DataType::Float64),
+            ScalarValue::IntervalMonthDayNano(self.unit.map(i128::from)),
+        ])
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        assert_eq!(states.len(), 3);
+
+        self.set_unit(&states[2])?;
+
+        let times = as_list_array(&states[0]);
+        let values = as_list_array(&states[1]);
+        for idx in 0..times.len() {
+            self.update(&values.value(idx), &times.value(idx))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SECOND: i64 = 1_000_000_000;
+
+    fn update(acc: &mut IntegralAccumulator, points: &[(i64, f64)], unit: ScalarValue) {
+        let times: ArrayRef = Arc::new(TimestampNanosecondArray::from_iter_values(
+            points.iter().map(|(t, _)| *t),
+        ));
+        let values: ArrayRef = Arc::new(Float64Array::from_iter_values(
+            points.iter().map(|(_, v)| *v),
+        ));
+        let unit = unit.to_array_of_size(points.len());
+        acc.update_batch(&[values, times, unit]).unwrap();
+    }
+
+    fn integral(points: &[(i64, f64)], unit: ScalarValue) -> ScalarValue {
+        let mut acc = IntegralAccumulator::default();
+        update(&mut acc, points, unit);
+        acc.evaluate().unwrap()
+    }
+
+    #[test]
+    fn test_integral() {
+        let points = [(0, 20.0), (10 * SECOND, 10.0), (20 * SECOND, 30.0)];
+
+        // Matches InfluxQL OG, SELECT integral(v) and SELECT integral(v, 10s)
+        assert_eq!(
+            integral(&points, ScalarValue::new_interval_mdn(0, 0, SECOND)),
+            ScalarValue::Float64(Some(350.0))
+        );
+        assert_eq!(
+            integral(&points, ScalarValue::new_interval_mdn(0, 0, 10 * SECOND)),
+            ScalarValue::Float64(Some(35.0))
+        );
+
+        // The order of the input does not matter, and a point with the same
+        // timestamp as the previous point replaces it.
+        let points = [
+            (20 * SECOND, 30.0),
+            (0, 20.0),
+            (10 * SECOND, 10.0),
+            (20 * SECOND, 30.0),
+        ];
+        assert_eq!(
+            integral(&points, ScalarValue::new_interval_mdn(0, 0, SECOND)),
+            ScalarValue::Float64(Some(350.0))
+        );
+
+        // A single point has no area
+        assert_eq!(
+            integral(&[(0, 20.0)], ScalarValue::new_interval_mdn(0, 0, SECOND)),
+            ScalarValue::Float64(Some(0.0))
+        );
+
+        // and no points has no result
+        assert_eq!(
+            integral(&[], ScalarValue::new_interval_mdn(0, 0, SECOND)),
+            ScalarValue::Float64(None)
+        );
+    }
+
+    #[test]
+    fn test_integral_unit() {
+        let points = [(0, 1.0), (24 * 60 * 60 * SECOND, 1.0)];
+
+        // Days are converted as 24 hours
+        assert_eq!(
+            integral(&points, ScalarValue::new_interval_mdn(0, 1, 0)),
+            ScalarValue::Float64(Some(1.0))
+        );
+        assert_eq!(
+            integral(
+                &points,
+                ScalarValue::new_interval_mdn(0, 0, 60 * 60 * SECOND)
+            ),
+            ScalarValue::Float64(Some(24.0))
+        );
+
+        // Months do not have a fixed duration
+        let mut acc = IntegralAccumulator::default();
+        let err = acc
+            .set_unit(&ScalarValue::new_interval_mdn(1, 0, 0).to_array_of_size(1))
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("integral attempted with invalid unit"));
+
+        // and the unit must be positive
+        let mut acc = IntegralAccumulator::default();
+        acc.set_unit(&ScalarValue::new_interval_mdn(0, 0, 0).to_array_of_size(1))
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_integral_merge_batch() {
+        let unit = ScalarValue::new_interval_mdn(0, 0, SECOND);
+
+        let mut acc1 = IntegralAccumulator::default();
+        update(&mut acc1, &[(10 * SECOND, 10.0)], unit.clone());
+        let mut acc2 = IntegralAccumulator::default();
+        update(&mut acc2, &[(20 * SECOND, 30.0), (0, 20.0)], unit);
+
+        let mut acc = IntegralAccumulator::default();
+        for state in [acc1.state().unwrap(), acc2.state().unwrap()] {
+            let state = state.iter().map(|v| v.to_array()).collect::<Vec<_>>();
+            acc.merge_batch(&state).unwrap();
+        }
+        assert_eq!(acc.evaluate().unwrap(), ScalarValue::Float64(Some(350.0)));
+    }
+}
@@ -0,0 +1,285 @@
+use arrow::array::{as_list_array, Array, ArrayRef, TimestampNanosecondArray, UInt64Array};
+use arrow::datatypes::{DataType, Field, TimeUnit};
+use datafusion::common::{downcast_value, DataFusionError, Result, ScalarValue};
+use datafusion::logical_expr::{Accumulator, Signature, TypeSignature, Volatility};
+use once_cell::sync::Lazy;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// The name of the mode aggregate function.
+pub(super) const NAME: &str = "mode";
+
+/// Valid signatures for the mode aggregate function.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| {
+    Signature::one_of(
+        crate::NUMERICS
+            .iter()
+            .chain(&[DataType::Utf8, DataType::Boolean])
+            .map(|dt| {
+                TypeSignature::Exact(vec![
+                    dt.clone(),
+                    DataType::Timestamp(TimeUnit::Nanosecond, None),
+                ])
+            })
+            .collect(),
+        Volatility::Immutable,
+    )
+});
+
+/// Calculate the return type given the function signature. Mode
+/// always returns the same type as the input column.
+pub(super) fn return_type(signature: &[DataType]) -> Result<Arc<DataType>> {
+    Ok(Arc::new(signature[0].clone()))
+}
+
+/// Create a new accumulator for the data type.
+pub(super) fn accumulator(dt: &DataType) -> Result<Box<dyn Accumulator>> {
+    Ok(Box::new(ModeAccumulator::new(dt.clone())))
+}
+
+/// Calculate the intermediate merge state for the aggregator.
+pub(super) fn state_type(dt: &DataType) -> Result<Arc<Vec<DataType>>> {
+    Ok(Arc::new(vec![
+        DataType::List(Arc::new(Field::new("item", dt.clone(), true))),
+        DataType::List(Arc::new(Field::new("item", DataType::UInt64, true))),
+        DataType::List(Arc::new(Field::new(
+            "item",
+            DataType::Timestamp(TimeUnit::Nanosecond, None),
+            true,
+        ))),
+    ]))
+}
+
+/// Accumulator which returns the most frequent value of the input. When
+/// more than one value is the most frequent, the value that occurs first
+/// is returned, and if those values first occur at the same time, the
+/// smallest of them.
+#[derive(Debug)]
+struct ModeAccumulator {
+    data_type: DataType,
+    /// The number of times each value occurs, and the earliest time it occurs.
+    counts: HashMap<ScalarValue, (u64, i64)>,
+}
+
+impl ModeAccumulator {
+    fn new(data_type: DataType) -> Self {
+        Self {
+            data_type,
+            counts: HashMap::new(),
+        }
+    }
+
+    fn update(
+        &mut self,
+        array: &ArrayRef,
+        times: &ArrayRef,
+        counts: Option<&UInt64Array>,
+    ) -> Result<()> {
+        assert_eq!(array.data_type(), &self.data_type);
+        let times = downcast_value!(times, TimestampNanosecondArray);
+
+        for idx in 0..array.len() {
+            if array.is_valid(idx) && times.is_valid(idx) {
+                let count = counts.map_or(1, |c| c.value(idx));
+                let time = times.value(idx);
+                self.counts
+                    .entry(ScalarValue::try_from_array(array, idx)?)
+                    .and_modify(|(c, t)| {
+                        *c += count;
+                        *t = time.min(*t);
+                    })
+                    .or_insert((count, time));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Accumulator for ModeAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        assert_eq!(values.len(), 2);
+
+        self.update(&values[0], &values[1], None)
+    }
+
+    fn evaluate(&self) -> Result<ScalarValue> {
+        let mode = self
+            .counts
+            .iter()
+            .max_by(|(a, (a_count, a_time)), (b, (b_count, b_time))| {
+                // Prefer the earliest value when the counts are equal, and
+                // then the smallest value.
+                a_count
+                    .cmp(b_count)
+                    .then_with(|| b_time.cmp(a_time))
+                    .then_with(|| b.partial_cmp(a).unwrap_or(Ordering::Equal))
+            });
+
+        match mode {
+            Some((value, _)) => Ok(value.clone()),
+            None => ScalarValue::try_from(&self.data_type),
+        }
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+            + self
+                .counts
+                .keys()
+                .map(|v| v.size() + std::mem::size_of::<(u64, i64)>())
+                .sum::<usize>()
+    }
+
+    fn state(&self) -> Result<Vec<ScalarValue>> {
+        let mut values = Vec::with_capacity(self.counts.len());
+        let mut counts = Vec::with_capacity(self.counts.len());
+        let mut times = Vec::with_capacity(self.counts.len());
+        for (v, (c, t)) in &self.counts {
+            values.push(v.clone());
+            counts.push(ScalarValue::UInt64(Some(*c)));
+            times.push(ScalarValue::TimestampNanosecond(Some(*t), None));
+        }
+
+        Ok(vec![
+            ScalarValue::new_list(Some(values), self.data_type.clone()),
+            ScalarValue::new_list(Some(counts), DataType::UInt64),
+            ScalarValue::new_list(Some(times), DataType::Timestamp(TimeUnit::Nanosecond, None)),
+        ])
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        assert_eq!(states.len(), 3);
+
+        let values = as_list_array(&states[0]);
+        let counts = as_list_array(&states[1]);
+        let times = as_list_array(&states[2]);
+        for idx in 0..values.len() {
+            let counts = counts.value(idx);
+            let counts = downcast_value!(counts, UInt64Array);
+            self.update(&values.value(idx), &times.value(idx), Some(counts))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use arrow::array::{Float64Array, StringArray};
+
+    fn update(acc: &mut ModeAccumulator, values: ArrayRef, times: &[i64]) {
+        let times: ArrayRef = Arc::new(TimestampNanosecondArray::from(times.to_vec()));
+        acc.update_batch(&[values, times]).unwrap();
+    }
+
+    fn mode(values: &[(i64, Option<f64>)]) -> ScalarValue {
+        let mut acc = ModeAccumulator::new(DataType::Float64);
+        update(
+            &mut acc,
+            Arc::new(Float64Array::from_iter(values.iter().map(|(_, v)| *v))),
+            &values.iter().map(|(t, _)| *t).collect::<Vec<_>>(),
+        );
+        acc.evaluate().unwrap()
+    }
+
+    #[test]
+    fn test_mode() {
+        assert_eq!(
+            mode(&[(0, Some(1.0)), (1, Some(2.0)), (2, Some(2.0))]),
+            ScalarValue::Float64(Some(2.0))
+        );
+
+        // The value that occurs first is preferred when the counts are equal
+        assert_eq!(
+            mode(&[
+                (0, Some(3.0)),
+                (1, Some(1.0)),
+                (2, Some(3.0)),
+                (3, Some(1.0))
+            ]),
+            ScalarValue::Float64(Some(3.0))
+        );
+        // regardless of the order of the input
+        assert_eq!(
+            mode(&[
+                (3, Some(1.0)),
+                (2, Some(3.0)),
+                (1, Some(1.0)),
+                (0, Some(3.0))
+            ]),
+            ScalarValue::Float64(Some(3.0))
+        );
+        // and then the smallest value
+        assert_eq!(
+            mode(&[(0, Some(3.0)), (0, Some(1.0))]),
+            ScalarValue::Float64(Some(1.0))
+        );
+
+        // NULL values are ignored
+        assert_eq!(
+            mode(&[
+                (0, None),
+                (1, None),
+                (2, Some(5.0)),
+                (3, Some(6.0)),
+                (4, Some(6.0))
+            ]),
+            ScalarValue::Float64(Some(6.0))
+        );
+        assert_eq!(mode(&[(0, None)]), ScalarValue::Float64(None));
+        assert_eq!(mode(&[]), ScalarValue::Float64(None));
+    }
+
+    #[test]
+    fn test_mode_string() {
+        let mut acc = ModeAccumulator::new(DataType::Utf8);
+        update(
+            &mut acc,
+            Arc::new(StringArray::from(vec![
+                Some("b"),
+                None,
+                Some("a"),
+                Some("b"),
+            ])),
+            &[0, 1, 2, 3],
+        );
+        assert_eq!(acc.evaluate().unwrap(), ScalarValue::Utf8(Some("b".into())));
+    }
+
+    #[test]
+    fn test_mode_merge_batch() {
+        let mut acc1 = ModeAccumulator::new(DataType::Float64);
+        update(
+            &mut acc1,
+            Arc::new(Float64Array::from(vec![1.0, 1.0])),
+            &[5, 6],
+        );
+        let mut acc2 = ModeAccumulator::new(DataType::Float64);
+        update(
+            &mut acc2,
+            Arc::new(Float64Array::from(vec![2.0, 2.0])),
+            &[7, 0],
+        );
+
+        // The counts are equal, so the value that occurs first is returned
+        let mut acc = ModeAccumulator::new(DataType::Float64);
+        for state in [acc1.state().unwrap(), acc2.state().unwrap()] {
+            let state = state.iter().map(|v| v.to_array()).collect::<Vec<_>>();
+            acc.merge_batch(&state).unwrap();
+        }
+        assert_eq!(acc.evaluate().unwrap(), ScalarValue::Float64(Some(2.0)));
+
+        // and the counts from each state are summed
+        let mut acc3 = ModeAccumulator::new(DataType::Float64);
+        update(&mut acc3, Arc::new(Float64Array::from(vec![1.0])), &[8]);
+        let state = acc3
+            .state()
+            .unwrap()
+            .iter()
+            .map(|v| v.to_array())
+            .collect::<Vec<_>>();
+        acc.merge_batch(&state).unwrap();
+        assert_eq!(acc.evaluate().unwrap(), ScalarValue::Float64(Some(1.0)));
+    }
+}
@@ -0,0 +1,161 @@
+use arrow::array::ArrayRef;
+use arrow::datatypes::DataType;
+use datafusion::common::{Result, ScalarValue};
+use datafusion::logical_expr::{Accumulator, Signature, TypeSignature, Volatility};
+use datafusion::physical_plan::expressions::{MaxAccumulator, MinAccumulator};
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+/// The name of the spread aggregate function.
+pub(super) const NAME: &str = "spread";
+
+/// Valid signatures for the spread aggregate function.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| {
+    Signature::one_of(
+        crate::NUMERICS
+            .iter()
+            .map(|dt| TypeSignature::Exact(vec![dt.clone()]))
+            .collect(),
+        Volatility::Immutable,
+    )
+});
+
+/// Calculate the return type given the function signature. Spread
+/// always returns the same type as the input column.
+pub(super) fn return_type(signature: &[DataType]) -> Result<Arc<DataType>> {
+    Ok(Arc::new(signature[0].clone()))
+}
+
+/// Create a new accumulator for the data type.
+pub(super) fn accumulator(dt: &DataType) -> Result<Box<dyn Accumulator>> {
+    Ok(Box::new(SpreadAccumulator::try_new(dt)?))
+}
+
+/// Calculate the intermediate merge state for the aggregator.
+pub(super) fn state_type(dt: &DataType) -> Result<Arc<Vec<DataType>>> {
+    Ok(Arc::new(vec![dt.clone(), dt.clone()]))
+}
+
+/// Accumulator which calculates the difference between the maximum
+/// and minimum values of the input.
+#[derive(Debug)]
+struct SpreadAccumulator {
+    min: MinAccumulator,
+    max: MaxAccumulator,
+}
+
+impl SpreadAccumulator {
+    fn try_new(data_type: &DataType) -> Result<Self> {
+        Ok(Self {
+            min: MinAccumulator::try_new(data_type)?,
+            max: MaxAccumulator::try_new(data_type)?,
+        })
+    }
+}
+
+impl Accumulator for SpreadAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        assert_eq!(values.len(), 1);
+
+        self.min.update_batch(values)?;
+        self.max.update_batch(values)
+    }
+
+    fn evaluate(&self) -> Result<ScalarValue> {
+        let min = self.min.evaluate()?;
+        let max = self.max.evaluate()?;
+        if min.is_null() || max.is_null() {
+            return Ok(min);
+        }
+
+        // The values of an unsigned column are never less than the
+        // minimum, so the subtraction does not overflow.
+        max.sub(&min)
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+            - std::mem::size_of_val(&self.min)
+            - std::mem::size_of_val(&self.max)
+            + self.min.size()
+            + self.max.size()
+    }
+
+    fn state(&self) -> Result<Vec<ScalarValue>> {
+        Ok(vec![self.min.evaluate()?, self.max.evaluate()?])
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        assert_eq!(states.len(), 2);
+
+        self.min.merge_batch(&states[0..1])?;
+        self.max.merge_batch(&states[1..2])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use arrow::array::{Float64Array, Int64Array, UInt64Array};
+
+    fn spread(values: ArrayRef) -> ScalarValue {
+        let mut acc = SpreadAccumulator::try_new(values.data_type()).unwrap();
+        acc.update_batch(&[values]).unwrap();
+        acc.evaluate().unwrap()
+    }
+
+    #[test]
+    fn test_spread() {
+        assert_eq!(
+            spread(Arc::new(Float64Array::from(vec![
+                Some(1.0),
+                None,
+                Some(5.5),
+                Some(-2.0)
+            ]))),
+            ScalarValue::Float64(Some(7.5))
+        );
+        assert_eq!(
+            spread(Arc::new(Int64Array::from(vec![-3, 10, 4]))),
+            ScalarValue::Int64(Some(13))
+        );
+        assert_eq!(
+            spread(Arc::new(UInt64Array::from(vec![3, 10, 4]))),
+            ScalarValue::UInt64(Some(7))
+        );
+
+        // A single value has no spread
+        assert_eq!(
+            spread(Arc::new(Int64Array::from(vec![4]))),
+            ScalarValue::Int64(Some(0))
+        );
+
+        // and NULL values are ignored
+        assert_eq!(
+            spread(Arc::new(Float64Array::from(vec![None, None]))),
+            ScalarValue::Float64(None)
+        );
+    }
+
+    #[test]
+    fn test_spread_merge_batch() {
+        let mut acc1 = SpreadAccumulator::try_new(&DataType::Int64).unwrap();
+        let values: ArrayRef = Arc::new(Int64Array::from(vec![3, 4]));
+        acc1.update_batch(&[values]).unwrap();
+        let mut acc2 = SpreadAccumulator::try_new(&DataType::Int64).unwrap();
+        let values: ArrayRef = Arc::new(Int64Array::from(vec![10, 1]));
+        acc2.update_batch(&[values]).unwrap();
+        let acc3 = SpreadAccumulator::try_new(&DataType::Int64).unwrap();
+
+        let mut acc = SpreadAccumulator::try_new(&DataType::Int64).unwrap();
+        for state in [
+            acc1.state().unwrap(),
+            acc2.state().unwrap(),
+            acc3.state().unwrap(),
+        ] {
+            let state = state.iter().map(|v| v.to_array()).collect::<Vec<_>>();
+            acc.merge_batch(&state).unwrap();
+        }
+        assert_eq!(acc.evaluate().unwrap(), ScalarValue::Int64(Some(9)));
+    }
+}
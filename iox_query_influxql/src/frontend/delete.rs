@@ -0,0 +1,139 @@
+//! Execution of the InfluxQL `DELETE`, `DROP MEASUREMENT` and `DROP SERIES`
+//! statements, which delete data from storage.
+
+use crate::error;
+use arrow::datatypes::{Schema as ArrowSchema, SchemaRef};
+use data_types::DeletePredicate;
+use datafusion::common::{DataFusionError, Result, Statistics};
+use datafusion::execution::context::TaskContext;
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{
+    DisplayAs, DisplayFormatType, ExecutionPlan, Partitioning, SendableRecordBatchStream,
+};
+use futures::{stream, StreamExt};
+use iox_query::QueryDeleter;
+use std::any::Any;
+use std::fmt;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// A physical operator that applies a list of predicate deletes when it is
+/// executed.
+///
+/// Like InfluxDB 1.x, the result of a delete contains no rows.
+pub(super) struct DeleteExec {
+    /// The name of each table and the predicate of the rows to delete
+    /// from it.
+    deletes: Vec<(String, DeletePredicate)>,
+    deleter: Arc<dyn QueryDeleter>,
+    /// The authorization token of the caller the rows are deleted for.
+    authz_token: Option<Vec<u8>>,
+    schema: SchemaRef,
+}
+
+impl DeleteExec {
+    pub(super) fn new(
+        deletes: Vec<(String, DeletePredicate)>,
+        deleter: Arc<dyn QueryDeleter>,
+        authz_token: Option<Vec<u8>>,
+    ) -> Self {
+        Self {
+            deletes,
+            deleter,
+            authz_token,
+            schema: Arc::new(ArrowSchema::empty()),
+        }
+    }
+}
+
+impl Debug for DeleteExec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_as(DisplayFormatType::Default, f)
+    }
+}
+
+impl ExecutionPlan for DeleteExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        assert!(children.is_empty());
+        Ok(self)
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        _context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        if partition != 0 {
+            return error::internal(format!(
+                "DeleteExec invalid partition {partition}, expected 0"
+            ));
+        }
+
+        let deletes = self.deletes.clone();
+        let deleter = Arc::clone(&self.deleter);
+        let authz_token = self.authz_token.clone();
+
+        let fut = async move {
+            for (table_name, predicate) in &deletes {
+                deleter
+                    .delete(table_name, predicate, authz_token.as_deref())
+                    .await?;
+            }
+            Ok::<_, DataFusionError>(())
+        };
+
+        // The stream completes once all the deletes are applied, without
+        // producing any record batches.
+        let stream = stream::once(fut).filter_map(|res| async move { res.err().map(Err) });
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            Arc::clone(&self.schema),
+            stream,
+        )))
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
+
+impl DisplayAs for DeleteExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(f, "DeleteExec: tables=[")?;
+                for (i, (table_name, _)) in self.deletes.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{table_name}")?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
@@ -1 +1,3 @@
+mod delete;
 pub mod planner;
+mod select_into;
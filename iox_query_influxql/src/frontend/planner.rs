@@ -1,30 +1,41 @@
 use arrow::datatypes::SchemaRef;
 use datafusion::physical_expr::execution_props::ExecutionProps;
+use influxdb_influxql_parser::delete::DeleteStatement;
+use influxdb_influxql_parser::drop::{DropMeasurementStatement, DropSeriesStatement};
+use influxdb_influxql_parser::kill::KillQueryStatement;
+use influxdb_influxql_parser::show::OnClause;
+use influxdb_influxql_parser::show_cardinality::ShowCardinalityStatement;
 use influxdb_influxql_parser::show_field_keys::ShowFieldKeysStatement;
-use influxdb_influxql_parser::show_measurements::ShowMeasurementsStatement;
+use influxdb_influxql_parser::show_measurements::{ExtendedOnClause, ShowMeasurementsStatement};
+use influxdb_influxql_parser::show_series::ShowSeriesStatement;
 use influxdb_influxql_parser::show_tag_keys::ShowTagKeysStatement;
 use influxdb_influxql_parser::show_tag_values::ShowTagValuesStatement;
 use std::any::Any;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fmt;
 use std::fmt::Debug;
 use std::ops::Deref;
 use std::sync::Arc;
 
-use crate::plan::{parse_regex, InfluxQLToLogicalPlan, SchemaProvider};
+use super::delete::DeleteExec;
+use super::select_into::SelectIntoExec;
+use crate::params::{replace_bind_params, StatementParams};
+use crate::plan::{
+    namespace_name, parse_regex, qualified_table_name, InfluxQLToLogicalPlan, SchemaProvider,
+};
 use datafusion::common::Statistics;
 use datafusion::datasource::provider_as_source;
 use datafusion::execution::context::{SessionState, TaskContext};
 use datafusion::logical_expr::{AggregateUDF, LogicalPlan, ScalarUDF, TableSource};
 use datafusion::physical_expr::PhysicalSortExpr;
 use datafusion::physical_plan::{
-    DisplayAs, DisplayFormatType, Partitioning, SendableRecordBatchStream,
+    empty::EmptyExec, DisplayAs, DisplayFormatType, Partitioning, SendableRecordBatchStream,
 };
 use datafusion::{
     error::{DataFusionError, Result},
     physical_plan::ExecutionPlan,
 };
-use influxdb_influxql_parser::common::MeasurementName;
+use influxdb_influxql_parser::common::{MeasurementName, QualifiedMeasurementName};
 use influxdb_influxql_parser::parse_statements;
 use influxdb_influxql_parser::statement::Statement;
 use influxdb_influxql_parser::visit::{Visitable, Visitor};
@@ -35,12 +46,34 @@ use schema::Schema;
 struct ContextSchemaProvider<'a> {
     state: &'a SessionState,
     tables: HashMap<String, (Arc<dyn TableSource>, Schema)>,
+    /// The other namespaces referenced by the query, by namespace name.
+    namespaces: HashMap<String, ContextSchemaProvider<'a>>,
+    /// The namespace name and table name of each qualified measurement name
+    /// of the tables of `namespaces`.
+    qualified_tables: HashMap<String, (String, String)>,
+}
+
+impl<'a> ContextSchemaProvider<'a> {
+    fn new(state: &'a SessionState) -> Self {
+        Self {
+            state,
+            tables: HashMap::new(),
+            namespaces: HashMap::new(),
+            qualified_tables: HashMap::new(),
+        }
+    }
+
+    fn table(&self, name: &str) -> Option<&(Arc<dyn TableSource>, Schema)> {
+        self.tables.get(name).or_else(|| {
+            let (namespace_name, table_name) = self.qualified_tables.get(name)?;
+            self.namespaces.get(namespace_name)?.tables.get(table_name)
+        })
+    }
 }
 
 impl<'a> SchemaProvider for ContextSchemaProvider<'a> {
     fn get_table_provider(&self, name: &str) -> Result<Arc<dyn TableSource>> {
-        self.tables
-            .get(name)
+        self.table(name)
             .map(|(t, _)| Arc::clone(t))
             .ok_or_else(|| DataFusionError::Plan(format!("measurement does not exist: {name}")))
     }
@@ -58,16 +91,33 @@ impl<'a> SchemaProvider for ContextSchemaProvider<'a> {
     }
 
     fn table_exists(&self, name: &str) -> bool {
-        self.tables.contains_key(name)
+        self.table(name).is_some()
     }
 
     fn table_schema(&self, name: &str) -> Option<Schema> {
-        self.tables.get(name).map(|(_, s)| s.clone())
+        self.table(name).map(|(_, s)| s.clone())
     }
 
     fn execution_props(&self) -> &ExecutionProps {
         self.state.execution_props()
     }
+
+    fn namespace(
+        &self,
+        database: &str,
+        retention_policy: Option<&str>,
+    ) -> Option<&dyn SchemaProvider> {
+        self.namespaces
+            .get(&namespace_name(database, retention_policy))
+            .map(|ns| ns as _)
+    }
+
+    fn measurement_name<'b>(&'b self, name: &'b str) -> &'b str {
+        self.qualified_tables
+            .get(name)
+            .map(|(_, table_name)| table_name.as_str())
+            .unwrap_or(name)
+    }
 }
 
 /// A physical operator that overrides the `schema` API,
@@ -146,14 +196,48 @@ impl InfluxQLQueryPlanner {
 
     /// Plan an InfluxQL query against the catalogs registered with `ctx`, and return a
     /// DataFusion physical execution plan that runs on the query executor.
+    ///
+    /// Any bind parameters in the query are replaced by the values in `params`.
     pub async fn query(
         &self,
         query: &str,
+        params: &StatementParams,
         ctx: &IOxSessionContext,
     ) -> Result<Arc<dyn ExecutionPlan>> {
         debug!(text=%query, "planning InfluxQL query");
 
-        let statement = self.query_to_statement(query)?;
+        let mut statement = self.query_to_statement(query)?;
+        replace_bind_params(&mut statement, params)?;
+
+        if let Statement::KillQuery(kill_query) = &statement {
+            return kill_query_plan(kill_query, ctx);
+        }
+
+        if is_delete_statement(&statement) {
+            let deleter = ctx.deleter().ok_or_else(|| {
+                DataFusionError::NotImplemented(format!(
+                    "{} is not supported: no deleter configured",
+                    statement_name(&statement)
+                ))
+            })?;
+            let deleter = Arc::clone(deleter);
+            let deletes = self
+                .with_planner(statement, ctx, |planner, statement| {
+                    planner.statement_to_deletes(statement)
+                })
+                .await?;
+            return Ok(Arc::new(DeleteExec::new(
+                deletes,
+                deleter,
+                ctx.authz_token().map(<[u8]>::to_vec),
+            )));
+        }
+
+        let into = match &statement {
+            Statement::Select(select) => select.into.clone(),
+            _ => None,
+        };
+
         let logical_plan = self.statement_to_plan(statement, ctx).await?;
 
         let input = ctx.create_physical_plan(&logical_plan).await?;
@@ -169,7 +253,24 @@ impl InfluxQLQueryPlanner {
             md,
         ));
 
-        Ok(Arc::new(SchemaExec { input, schema }))
+        let plan = Arc::new(SchemaExec { input, schema });
+
+        match into {
+            Some(into) => {
+                let writer = ctx.points_writer().ok_or_else(|| {
+                    DataFusionError::NotImplemented(
+                        "SELECT INTO is not supported: no points writer configured".to_string(),
+                    )
+                })?;
+                Ok(Arc::new(SelectIntoExec::try_new(
+                    plan,
+                    into,
+                    Arc::clone(writer),
+                    ctx.authz_token().map(<[u8]>::to_vec),
+                )?))
+            }
+            None => Ok(plan),
+        }
     }
 
     async fn statement_to_plan(
@@ -177,6 +278,23 @@ impl InfluxQLQueryPlanner {
         statement: Statement,
         ctx: &IOxSessionContext,
     ) -> Result<LogicalPlan> {
+        let logical_plan = self
+            .with_planner(statement, ctx, |planner, statement| {
+                planner.statement_to_plan(statement)
+            })
+            .await?;
+        debug!(plan=%logical_plan.display_graphviz(), "logical plan");
+        Ok(logical_plan)
+    }
+
+    /// Resolve the measurements referenced by `statement` and call `f` with
+    /// an [`InfluxQLToLogicalPlan`] that has access to their schemas.
+    async fn with_planner<T>(
+        &self,
+        statement: Statement,
+        ctx: &IOxSessionContext,
+        f: impl FnOnce(InfluxQLToLogicalPlan<'_>, Statement) -> Result<T>,
+    ) -> Result<T> {
         use std::collections::hash_map::Entry;
 
         let session_cfg = ctx.inner().copied_config();
@@ -200,27 +318,132 @@ impl InfluxQLQueryPlanner {
         let names = schema.table_names();
         let query_tables = find_all_measurements(&statement, &names)?;
 
-        let mut sp = ContextSchemaProvider {
-            state: &ctx.inner().state(),
-            tables: HashMap::with_capacity(query_tables.len()),
-        };
+        let state = ctx.inner().state();
+        let mut sp = ContextSchemaProvider::new(&state);
 
         for table_name in &query_tables {
             if let Entry::Vacant(v) = sp.tables.entry(table_name.to_string()) {
                 if let Some(table) = schema.table(table_name).await {
-                    let schema = Schema::try_from(table.schema())
-                        .map_err(|err| {
-                            DataFusionError::Internal(format!("unable to convert DataFusion schema for measurement {table_name} to IOx schema: {err}"))
-                        })?;
+                    let schema = table_schema(table_name, table.schema())?;
                     v.insert((provider_as_source(table), schema));
                 }
             }
         }
 
-        let planner = InfluxQLToLogicalPlan::new(&sp, ctx);
-        let logical_plan = planner.statement_to_plan(statement)?;
-        debug!(plan=%logical_plan.display_graphviz(), "logical plan");
-        Ok(logical_plan)
+        for (database, retention_policy) in find_all_namespaces(&statement)? {
+            let namespace_name = namespace_name(&database, retention_policy.as_deref());
+            if sp.namespaces.contains_key(&namespace_name) {
+                continue;
+            }
+
+            let resolver = ctx.namespace_resolver().ok_or_else(|| {
+                DataFusionError::NotImplemented(format!(
+                    "querying database {database} is not supported: no namespace resolver configured"
+                ))
+            })?;
+            let Some(provider) = resolver
+                .schema_provider(&namespace_name, ctx.child_span("resolve namespace"))
+                .await?
+            else {
+                continue;
+            };
+
+            let mut namespace = ContextSchemaProvider::new(&state);
+            for table_name in provider.table_names() {
+                if let Some(table) = provider.table(&table_name).await {
+                    let schema = table_schema(&table_name, table.schema())?;
+                    sp.qualified_tables.insert(
+                        qualified_table_name(&database, retention_policy.as_deref(), &table_name),
+                        (namespace_name.clone(), table_name.clone()),
+                    );
+                    namespace
+                        .tables
+                        .insert(table_name, (provider_as_source(table), schema));
+                }
+            }
+            sp.namespaces.insert(namespace_name, namespace);
+        }
+
+        f(InfluxQLToLogicalPlan::new(&sp, ctx), statement)
+    }
+
+    /// Returns `true` if `query` contains an InfluxQL statement that writes
+    /// or deletes data, or cancels other queries, and therefore requires write
+    /// access to the namespace.
+    ///
+    /// Returns `false` if `query` cannot be parsed, so the parse error is
+    /// reported when the query is planned.
+    pub fn is_write_query(query: &str) -> bool {
+        match parse_statements(query) {
+            Ok(statements) => statements.iter().any(|s| {
+                is_delete_statement(s)
+                    || matches!(s, Statement::KillQuery(_))
+                    || matches!(s, Statement::Select(select) if select.into.is_some())
+            }),
+            Err(_) => false,
+        }
+    }
+
+    /// Returns the names of the namespaces written to by the `SELECT ... INTO`
+    /// statements of `query`, which run against the namespace `namespace`,
+    /// and therefore require write access.
+    ///
+    /// Returns an empty list if `query` cannot be parsed, so the parse error
+    /// is reported when the query is planned.
+    pub fn into_namespaces(namespace: &str, query: &str) -> Vec<String> {
+        let Ok(statements) = parse_statements(query) else {
+            return vec![];
+        };
+
+        statements
+            .iter()
+            .filter_map(|statement| match statement {
+                Statement::Select(select) => select.into.as_ref(),
+                _ => None,
+            })
+            .map(|into| {
+                namespace_name(
+                    into.database.as_ref().map_or(namespace, |v| v.as_str()),
+                    into.retention_policy.as_ref().map(|v| v.as_str()),
+                )
+            })
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Returns the names of the namespaces other than the namespace of the
+    /// query that are read by `query`, such as the namespace of the
+    /// `db.rp.cpu` measurement, and therefore require read access.
+    ///
+    /// Returns an empty list if `query` cannot be parsed, so the parse error
+    /// is reported when the query is planned.
+    pub fn referenced_namespaces(query: &str) -> Vec<String> {
+        let Ok(statements) = parse_statements(query) else {
+            return vec![];
+        };
+
+        statements
+            .iter()
+            .filter_map(|statement| find_all_namespaces(statement).ok())
+            .flatten()
+            .map(|(database, retention_policy)| {
+                namespace_name(&database, retention_policy.as_deref())
+            })
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Parse `query` and return the text of each of its statements, in
+    /// order, so that a query containing multiple statements can be planned
+    /// and executed one statement at a time.
+    pub fn split_statements(query: &str) -> Result<Vec<String>> {
+        Ok(parse_statements(query)
+            .map_err(|e| DataFusionError::Plan(e.to_string()))?
+            .iter()
+            .map(ToString::to_string)
+            .collect())
     }
 
     fn query_to_statement(&self, query: &str) -> Result<Statement> {
@@ -237,6 +460,63 @@ impl InfluxQLQueryPlanner {
     }
 }
 
+/// Convert the DataFusion schema of the measurement `table_name` to an IOx schema.
+fn table_schema(table_name: &str, schema: SchemaRef) -> Result<Schema> {
+    Schema::try_from(schema).map_err(|err| {
+        DataFusionError::Internal(format!(
+            "unable to convert DataFusion schema for measurement {table_name} to IOx schema: {err}"
+        ))
+    })
+}
+
+/// Returns `true` if `statement` deletes data.
+fn is_delete_statement(statement: &Statement) -> bool {
+    matches!(
+        statement,
+        Statement::Delete(_) | Statement::DropMeasurement(_) | Statement::DropSeries(_)
+    )
+}
+
+/// Cancel the query of the `KILL QUERY` statement `kill_query`, returning a
+/// plan that produces no rows.
+fn kill_query_plan(
+    kill_query: &KillQueryStatement,
+    ctx: &IOxSessionContext,
+) -> Result<Arc<dyn ExecutionPlan>> {
+    if kill_query.host.is_some() {
+        return Err(DataFusionError::NotImplemented(
+            "KILL QUERY ... ON is not supported".to_string(),
+        ));
+    }
+
+    let canceller = ctx.query_canceller().ok_or_else(|| {
+        DataFusionError::NotImplemented(
+            "KILL QUERY is not supported: no query canceller configured".to_string(),
+        )
+    })?;
+    if !canceller.cancel_query(kill_query.id) {
+        return Err(DataFusionError::Plan(format!(
+            "no such query id: {}",
+            kill_query.id
+        )));
+    }
+
+    Ok(Arc::new(EmptyExec::new(
+        false,
+        Arc::new(arrow::datatypes::Schema::empty()),
+    )))
+}
+
+/// Returns the name of the kind of `statement`, for use in error messages.
+fn statement_name(statement: &Statement) -> &'static str {
+    match statement {
+        Statement::Delete(_) => "DELETE",
+        Statement::DropMeasurement(_) => "DROP MEASUREMENT",
+        Statement::DropSeries(_) => "DROP SERIES",
+        _ => "statement",
+    }
+}
+
 fn find_all_measurements(stmt: &Statement, tables: &[String]) -> Result<HashSet<String>> {
     struct Matcher<'a>(&'a mut HashSet<String>, &'a [String]);
     impl<'a> Visitor for Matcher<'a> {
@@ -311,6 +591,59 @@ fn find_all_measurements(stmt: &Statement, tables: &[String]) -> Result<HashSet<
 
             Ok(self)
         }
+
+        fn post_visit_show_series_statement(
+            self,
+            ss: &ShowSeriesStatement,
+        ) -> Result<Self, Self::Error> {
+            if ss.from.is_none() {
+                self.0.extend(self.1.iter().cloned());
+            }
+
+            Ok(self)
+        }
+
+        fn post_visit_show_cardinality_statement(
+            self,
+            sc: &ShowCardinalityStatement,
+        ) -> Result<Self, Self::Error> {
+            if sc.from.is_none() {
+                self.0.extend(self.1.iter().cloned());
+            }
+
+            Ok(self)
+        }
+
+        fn post_visit_delete_statement(self, d: &DeleteStatement) -> Result<Self, Self::Error> {
+            if let DeleteStatement::Where(_) = d {
+                self.0.extend(self.1.iter().cloned());
+            }
+
+            Ok(self)
+        }
+
+        fn post_visit_drop_measurement_statement(
+            self,
+            dm: &DropMeasurementStatement,
+        ) -> Result<Self, Self::Error> {
+            let name = dm.name.as_str();
+            if self.1.iter().any(|table| table == name) {
+                self.0.insert(name.to_string());
+            }
+
+            Ok(self)
+        }
+
+        fn post_visit_drop_series_statement(
+            self,
+            ds: &DropSeriesStatement,
+        ) -> Result<Self, Self::Error> {
+            if ds.from.is_none() {
+                self.0.extend(self.1.iter().cloned());
+            }
+
+            Ok(self)
+        }
     }
 
     let mut m = HashSet::new();
@@ -320,6 +653,86 @@ fn find_all_measurements(stmt: &Statement, tables: &[String]) -> Result<HashSet<
     Ok(m)
 }
 
+/// Returns the database and retention policy of each namespace referenced by
+/// `stmt`, other than the namespace of the query.
+///
+/// A namespace is referenced by a qualified measurement name, such as
+/// `db.rp.cpu`, or by the `ON` clause of a `SHOW` statement.
+fn find_all_namespaces(stmt: &Statement) -> Result<BTreeSet<(String, Option<String>)>> {
+    #[derive(Default)]
+    struct Matcher {
+        namespaces: BTreeSet<(String, Option<String>)>,
+        /// The database of the `ON` clause, if any.
+        on: Option<String>,
+        /// The retention policies of measurement names that are qualified
+        /// without a database, such as `rp.cpu`.
+        retention_policies: Vec<String>,
+    }
+
+    impl Visitor for Matcher {
+        type Error = DataFusionError;
+
+        fn post_visit_qualified_measurement_name(
+            mut self,
+            n: &QualifiedMeasurementName,
+        ) -> Result<Self, Self::Error> {
+            let retention_policy = n.retention_policy.as_ref().map(|rp| rp.as_str().to_owned());
+            match &n.database {
+                Some(database) => {
+                    self.namespaces
+                        .insert((database.as_str().to_owned(), retention_policy));
+                }
+                None => self.retention_policies.extend(retention_policy),
+            }
+
+            Ok(self)
+        }
+
+        fn post_visit_on_clause(mut self, n: &OnClause) -> Result<Self, Self::Error> {
+            self.on = Some(n.as_str().to_owned());
+            Ok(self)
+        }
+
+        fn post_visit_extended_on_clause(
+            mut self,
+            n: &ExtendedOnClause,
+        ) -> Result<Self, Self::Error> {
+            match n {
+                ExtendedOnClause::Database(database) => {
+                    self.namespaces.insert((database.as_str().to_owned(), None));
+                }
+                ExtendedOnClause::DatabaseRetentionPolicy(database, retention_policy) => {
+                    self.namespaces.insert((
+                        database.as_str().to_owned(),
+                        Some(retention_policy.as_str().to_owned()),
+                    ));
+                }
+                ExtendedOnClause::AllDatabases
+                | ExtendedOnClause::AllDatabasesAndRetentionPolicies => {}
+            }
+
+            Ok(self)
+        }
+    }
+
+    let Matcher {
+        mut namespaces,
+        on,
+        retention_policies,
+    } = stmt.accept(Matcher::default())?;
+
+    if let Some(database) = on {
+        namespaces.extend(
+            retention_policies
+                .into_iter()
+                .map(|rp| (database.clone(), Some(rp)))
+                .chain([(database.clone(), None)]),
+        );
+    }
+
+    Ok(namespaces)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -341,6 +754,44 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_split_statements() {
+        assert_eq!(
+            InfluxQLQueryPlanner::split_statements("SELECT foo FROM bar").unwrap(),
+            vec!["SELECT foo FROM bar"]
+        );
+        assert_eq!(
+            InfluxQLQueryPlanner::split_statements(
+                "SELECT foo FROM bar; SHOW MEASUREMENTS;\nSHOW TAG KEYS FROM foo"
+            )
+            .unwrap(),
+            vec![
+                "SELECT foo FROM bar",
+                "SHOW MEASUREMENTS",
+                "SHOW TAG KEYS FROM foo"
+            ]
+        );
+
+        // Fallible
+
+        assert_error!(
+            InfluxQLQueryPlanner::split_statements("SELECT foo FROM bar; SHOW"),
+            DataFusionError::Plan(_)
+        );
+    }
+
+    #[test]
+    fn test_is_write_query() {
+        assert!(InfluxQLQueryPlanner::is_write_query("DELETE FROM foo"));
+        assert!(InfluxQLQueryPlanner::is_write_query("DROP MEASUREMENT foo"));
+        assert!(InfluxQLQueryPlanner::is_write_query(
+            "SHOW MEASUREMENTS; DROP SERIES WHERE host = 'a'"
+        ));
+        assert!(InfluxQLQueryPlanner::is_write_query("KILL QUERY 36"));
+        assert!(!InfluxQLQueryPlanner::is_write_query("SELECT foo FROM bar"));
+        assert!(!InfluxQLQueryPlanner::is_write_query("DELETE"));
+    }
+
     #[test]
     fn test_find_all_measurements() {
         fn find(q: &str) -> Vec<String> {
@@ -398,10 +849,113 @@ mod test {
         assert_eq!(find("SHOW TAG KEYS"), vec!["bar", "foo", "foobar"]);
         assert_eq!(find("SHOW TAG KEYS FROM /^foo/"), vec!["foo", "foobar"]);
 
+        // Find all measurements in `SHOW SERIES`
+        assert_eq!(find("SHOW SERIES"), vec!["bar", "foo", "foobar"]);
+        assert_eq!(find("SHOW SERIES FROM /^foo/"), vec!["foo", "foobar"]);
+
+        // Find all measurements in `SHOW ... CARDINALITY`
+        assert_eq!(
+            find("SHOW SERIES CARDINALITY"),
+            vec!["bar", "foo", "foobar"]
+        );
+        assert_eq!(
+            find("SHOW MEASUREMENT EXACT CARDINALITY"),
+            vec!["bar", "foo", "foobar"]
+        );
+        assert_eq!(
+            find("SHOW TAG VALUES CARDINALITY FROM foo WITH KEY = \"k\""),
+            vec!["foo"]
+        );
+
+        // Find all measurements in `DELETE`, `DROP MEASUREMENT` and `DROP SERIES`
+        assert_eq!(
+            find("DELETE WHERE host = 'a'"),
+            vec!["bar", "foo", "foobar"]
+        );
+        assert_eq!(find("DELETE FROM /^foo/"), vec!["foo", "foobar"]);
+        assert_eq!(find("DROP MEASUREMENT foo"), vec!["foo"]);
+        assert!(find("DROP MEASUREMENT none").is_empty());
+        assert_eq!(
+            find("DROP SERIES WHERE host = 'a'"),
+            vec!["bar", "foo", "foobar"]
+        );
+        assert_eq!(find("DROP SERIES FROM bar"), vec!["bar"]);
+
         // Finds no measurements
         assert!(find("SELECT * FROM none").is_empty());
         assert!(find("SELECT * FROM (SELECT * FROM none)").is_empty());
         assert!(find("SELECT * FROM /^l/").is_empty());
         assert!(find("SELECT * FROM (SELECT * FROM /^l/)").is_empty());
     }
+
+    #[test]
+    fn test_find_all_namespaces() {
+        fn find(q: &str) -> Vec<(String, Option<String>)> {
+            let p = InfluxQLQueryPlanner::new();
+            let s = p.query_to_statement(q).unwrap();
+            find_all_namespaces(&s).unwrap().into_iter().collect()
+        }
+
+        fn ns(database: &str, retention_policy: Option<&str>) -> (String, Option<String>) {
+            (database.into(), retention_policy.map(Into::into))
+        }
+
+        assert!(find("SELECT * FROM foo").is_empty());
+        assert!(find("SELECT * FROM autogen.foo").is_empty());
+        assert_eq!(find("SELECT * FROM db0..foo"), vec![ns("db0", None)]);
+        assert_eq!(
+            find("SELECT * FROM db0.rp0./^foo/, (SELECT * FROM db1..bar)"),
+            vec![ns("db0", Some("rp0")), ns("db1", None)]
+        );
+        assert_eq!(find("SHOW TAG KEYS ON db0"), vec![ns("db0", None)]);
+        assert_eq!(
+            find("SHOW SERIES ON db0 FROM rp0.foo"),
+            vec![ns("db0", None), ns("db0", Some("rp0"))]
+        );
+        assert_eq!(
+            find("SHOW MEASUREMENTS ON db0.rp0"),
+            vec![ns("db0", Some("rp0"))]
+        );
+        assert!(find("SHOW MEASUREMENTS ON *").is_empty());
+    }
+
+    #[test]
+    fn test_referenced_namespaces() {
+        assert!(InfluxQLQueryPlanner::referenced_namespaces("SELECT * FROM foo").is_empty());
+        assert_eq!(
+            InfluxQLQueryPlanner::referenced_namespaces(
+                "SELECT * FROM db0.autogen.foo; SHOW FIELD KEYS ON db0; SELECT * FROM db1.RP1.foo"
+            ),
+            vec!["db0", "db1/rp1"]
+        );
+        assert!(InfluxQLQueryPlanner::referenced_namespaces("SELECT * FROM db0..").is_empty());
+    }
+
+    #[test]
+    fn test_is_write_query() {
+        assert!(!InfluxQLQueryPlanner::is_write_query("SELECT * FROM foo"));
+        assert!(InfluxQLQueryPlanner::is_write_query(
+            "SELECT * INTO bar FROM foo"
+        ));
+        assert!(InfluxQLQueryPlanner::is_write_query(
+            "SELECT * FROM foo; DELETE FROM foo"
+        ));
+        assert!(InfluxQLQueryPlanner::is_write_query("KILL QUERY 1"));
+    }
+
+    #[test]
+    fn test_into_namespaces() {
+        assert!(InfluxQLQueryPlanner::into_namespaces("ns", "SELECT * FROM foo").is_empty());
+        assert_eq!(
+            InfluxQLQueryPlanner::into_namespaces("ns", "SELECT * INTO bar FROM foo"),
+            vec!["ns"]
+        );
+        assert_eq!(
+            InfluxQLQueryPlanner::into_namespaces(
+                "ns",
+                "SELECT * INTO db0.autogen.bar FROM foo; SELECT * INTO db1.RP1.:MEASUREMENT FROM foo; SELECT * INTO rp2.bar FROM foo"
+            ),
+            vec!["db0", "db1/rp1", "ns/rp2"]
+        );
+    }
 }
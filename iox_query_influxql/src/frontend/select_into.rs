@@ -0,0 +1,464 @@
+//! Execution of the InfluxQL `SELECT ... INTO` statement, which writes
+//! the results of a query back into storage.
+
+use crate::error;
+use arrow::array::{
+    Array, ArrayRef, BooleanArray, DictionaryArray, Float64Array, Int64Array, StringArray,
+    TimestampNanosecondArray, UInt64Array,
+};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Field, Int32Type, Schema as ArrowSchema, SchemaRef, TimeUnit};
+use arrow::record_batch::RecordBatch;
+use datafusion::common::{downcast_value, DataFusionError, Result, Statistics};
+use datafusion::execution::context::TaskContext;
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::coalesce_partitions::CoalescePartitionsExec;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{
+    DisplayAs, DisplayFormatType, ExecutionPlan, Partitioning, SendableRecordBatchStream,
+};
+use futures::{stream, TryStreamExt};
+use generated_types::influxdata::iox::querier::v1::InfluxQlMetadata;
+use influxdb_influxql_parser::select::{IntoClause, IntoMeasurementName};
+use influxdb_line_protocol::builder::FieldValue;
+use influxdb_line_protocol::LineProtocolBuilder;
+use iox_query::QueryPointsWriter;
+use schema::{INFLUXQL_MEASUREMENT_COLUMN_NAME, INFLUXQL_METADATA_KEY};
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// The measurement name of the result returned by a `SELECT ... INTO`
+/// statement.
+const RESULT_MEASUREMENT: &str = "result";
+
+/// The column containing the number of points written by a
+/// `SELECT ... INTO` statement.
+const WRITTEN_COLUMN_NAME: &str = "written";
+
+/// A physical operator that writes the rows produced by its input to the
+/// target of an `INTO` clause, and returns a single row with the number
+/// of points written.
+pub(super) struct SelectIntoExec {
+    input: Arc<dyn ExecutionPlan>,
+    into: IntoClause,
+    writer: Arc<dyn QueryPointsWriter>,
+    /// The authorization token of the caller the points are written for.
+    authz_token: Option<Vec<u8>>,
+    /// The InfluxQL metadata of the `input` schema.
+    input_md: Arc<InfluxQlMetadata>,
+    schema: SchemaRef,
+}
+
+impl SelectIntoExec {
+    pub(super) fn try_new(
+        input: Arc<dyn ExecutionPlan>,
+        into: IntoClause,
+        writer: Arc<dyn QueryPointsWriter>,
+        authz_token: Option<Vec<u8>>,
+    ) -> Result<Self> {
+        // The points are written by a single stream, so that the
+        // number of points written is returned as a single row.
+        let input: Arc<dyn ExecutionPlan> = if input.output_partitioning().partition_count() > 1 {
+            Arc::new(CoalescePartitionsExec::new(input))
+        } else {
+            input
+        };
+
+        let input_md = Arc::new(input_metadata(&input.schema())?);
+
+        let md = serde_json::to_string(&InfluxQlMetadata {
+            measurement_column_index: 0,
+            tag_key_columns: vec![],
+        })
+        .map_err(|err| {
+            error::map::internal(format!("error serializing InfluxQL metadata: {err}"))
+        })?;
+
+        let schema = Arc::new(ArrowSchema::new_with_metadata(
+            vec![
+                Field::new(
+                    INFLUXQL_MEASUREMENT_COLUMN_NAME,
+                    DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+                    false,
+                ),
+                Field::new(
+                    "time",
+                    DataType::Timestamp(TimeUnit::Nanosecond, None),
+                    false,
+                ),
+                Field::new(WRITTEN_COLUMN_NAME, DataType::Int64, false),
+            ],
+            HashMap::from([(INFLUXQL_METADATA_KEY.to_owned(), md)]),
+        ));
+
+        Ok(Self {
+            input,
+            into,
+            writer,
+            authz_token,
+            input_md,
+            schema,
+        })
+    }
+}
+
+impl Debug for SelectIntoExec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_as(DisplayFormatType::Default, f)
+    }
+}
+
+impl ExecutionPlan for SelectIntoExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![Arc::clone(&self.input)]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        assert_eq!(children.len(), 1);
+        Ok(Arc::new(Self::try_new(
+            Arc::clone(&children[0]),
+            self.into.clone(),
+            Arc::clone(&self.writer),
+            self.authz_token.clone(),
+        )?))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        if partition != 0 {
+            return error::internal(format!(
+                "SelectIntoExec invalid partition {partition}, expected 0"
+            ));
+        }
+
+        let mut input = self.input.execute(0, context)?;
+        let into = self.into.clone();
+        let writer = Arc::clone(&self.writer);
+        let authz_token = self.authz_token.clone();
+        let md = Arc::clone(&self.input_md);
+        let schema = Arc::clone(&self.schema);
+
+        let fut = async move {
+            let database = into.database.as_ref().map(|v| v.as_str());
+            let retention_policy = into.retention_policy.as_ref().map(|v| v.as_str());
+
+            let mut written = 0_i64;
+            while let Some(batch) = input.try_next().await? {
+                let (lp, points) = batch_to_line_protocol(&batch, &md, &into.name)?;
+                if points == 0 {
+                    continue;
+                }
+                let lp = String::from_utf8(lp)
+                    .map_err(|err| error::map::internal(format!("invalid line protocol: {err}")))?;
+                writer
+                    .write_lp(database, retention_policy, lp, authz_token.as_deref())
+                    .await?;
+                written += points as i64;
+            }
+
+            let measurement: DictionaryArray<Int32Type> =
+                vec![RESULT_MEASUREMENT].into_iter().collect();
+            let batch = RecordBatch::try_new(
+                schema,
+                vec![
+                    Arc::new(measurement),
+                    Arc::new(TimestampNanosecondArray::from(vec![0])),
+                    Arc::new(Int64Array::from(vec![written])),
+                ],
+            )?;
+            Ok::<_, DataFusionError>(batch)
+        };
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            Arc::clone(&self.schema),
+            stream::once(fut),
+        )))
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
+
+impl DisplayAs for SelectIntoExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(f, "SelectIntoExec: {}", self.into)
+            }
+        }
+    }
+}
+
+/// Read the InfluxQL metadata of the query results.
+fn input_metadata(schema: &ArrowSchema) -> Result<InfluxQlMetadata> {
+    let md = schema
+        .metadata()
+        .get(INFLUXQL_METADATA_KEY)
+        .ok_or_else(|| error::map::internal("missing InfluxQL metadata for SELECT INTO"))?;
+    serde_json::from_str(md)
+        .map_err(|err| error::map::internal(format!("invalid InfluxQL metadata: {err}")))
+}
+
+/// A field value of a point written by `SELECT ... INTO`.
+#[derive(Debug, Clone, Copy)]
+enum IntoFieldValue<'a> {
+    Float(f64),
+    Integer(i64),
+    Unsigned(u64),
+    Boolean(bool),
+    String(&'a str),
+}
+
+impl<'a> FieldValue for IntoFieldValue<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Float(v) => FieldValue::fmt(v, f),
+            Self::Integer(v) => FieldValue::fmt(v, f),
+            Self::Unsigned(v) => FieldValue::fmt(v, f),
+            Self::Boolean(v) => FieldValue::fmt(v, f),
+            Self::String(v) => FieldValue::fmt(v, f),
+        }
+    }
+}
+
+/// The values of a field column of the query results.
+enum FieldColumn {
+    Float(Float64Array),
+    Integer(Int64Array),
+    Unsigned(UInt64Array),
+    Boolean(BooleanArray),
+    String(StringArray),
+}
+
+impl FieldColumn {
+    fn try_new(name: &str, array: &ArrayRef) -> Result<Self> {
+        Ok(match array.data_type() {
+            DataType::Float64 => Self::Float(downcast_value!(array, Float64Array).clone()),
+            DataType::Int64 => Self::Integer(downcast_value!(array, Int64Array).clone()),
+            DataType::UInt64 => Self::Unsigned(downcast_value!(array, UInt64Array).clone()),
+            DataType::Boolean => Self::Boolean(downcast_value!(array, BooleanArray).clone()),
+            DataType::Utf8 | DataType::Dictionary(_, _) => {
+                let array = cast(array, &DataType::Utf8)?;
+                Self::String(downcast_value!(array, StringArray).clone())
+            }
+            dt => {
+                return error::query(format!(
+                    "unsupported data type for SELECT INTO field {name}: {dt}"
+                ))
+            }
+        })
+    }
+
+    fn value(&self, row: usize) -> Option<IntoFieldValue<'_>> {
+        match self {
+            Self::Float(a) => a.is_valid(row).then(|| IntoFieldValue::Float(a.value(row))),
+            Self::Integer(a) => a
+                .is_valid(row)
+                .then(|| IntoFieldValue::Integer(a.value(row))),
+            Self::Unsigned(a) => a
+                .is_valid(row)
+                .then(|| IntoFieldValue::Unsigned(a.value(row))),
+            Self::Boolean(a) => a
+                .is_valid(row)
+                .then(|| IntoFieldValue::Boolean(a.value(row))),
+            Self::String(a) => a
+                .is_valid(row)
+                .then(|| IntoFieldValue::String(a.value(row))),
+        }
+    }
+}
+
+/// Convert the rows of `batch` to line protocol, returning the line protocol
+/// and the number of points it contains.
+///
+/// The tags of each point are the `GROUP BY` tag columns described by `md`,
+/// and the remaining columns, other than the measurement and time, are the
+/// fields. Rows where all fields are `NULL` are not written, as a point
+/// must contain at least one field.
+fn batch_to_line_protocol(
+    batch: &RecordBatch,
+    md: &InfluxQlMetadata,
+    name: &IntoMeasurementName,
+) -> Result<(Vec<u8>, usize)> {
+    let schema = batch.schema();
+    let measurement_idx = md.measurement_column_index as usize;
+
+    let measurements = cast(batch.column(measurement_idx), &DataType::Utf8)?;
+    let measurements = downcast_value!(measurements, StringArray);
+
+    let Some(time_idx) = schema
+        .fields()
+        .iter()
+        .position(|f| matches!(f.data_type(), DataType::Timestamp(TimeUnit::Nanosecond, _)))
+    else {
+        return error::internal("missing time column for SELECT INTO");
+    };
+    let times = downcast_value!(batch.column(time_idx), TimestampNanosecondArray);
+
+    let tags = md
+        .tag_key_columns
+        .iter()
+        .map(|tk| {
+            let values = cast(batch.column(tk.column_index as usize), &DataType::Utf8)?;
+            Ok((
+                tk.tag_key.as_str(),
+                downcast_value!(values, StringArray).clone(),
+            ))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let fields = (0..schema.fields().len())
+        .filter(|idx| {
+            *idx != measurement_idx
+                && *idx != time_idx
+                && !md
+                    .tag_key_columns
+                    .iter()
+                    .any(|tk| tk.column_index as usize == *idx)
+        })
+        .map(|idx| {
+            let name = schema.field(idx).name().as_str();
+            Ok((name, FieldColumn::try_new(name, batch.column(idx))?))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut builder = LineProtocolBuilder::new();
+    let mut points = 0;
+    for row in 0..batch.num_rows() {
+        let values = fields
+            .iter()
+            .filter_map(|(name, col)| col.value(row).map(|v| (*name, v)))
+            .collect::<Vec<_>>();
+        let Some(((first_name, first_value), rest)) = values.split_first() else {
+            continue;
+        };
+
+        let measurement = match name {
+            IntoMeasurementName::Name(ident) => ident.as_str(),
+            IntoMeasurementName::BackReference => measurements.value(row),
+        };
+
+        let mut line = builder.measurement(measurement);
+        for (key, values) in &tags {
+            if values.is_valid(row) && !values.value(row).is_empty() {
+                line = line.tag(key, values.value(row));
+            }
+        }
+
+        let mut line = line.field(first_name, *first_value);
+        for (name, value) in rest {
+            line = line.field(name, *value);
+        }
+
+        builder = line.timestamp(times.value(row)).close_line();
+        points += 1;
+    }
+
+    Ok((builder.build(), points))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use generated_types::influxdata::iox::querier::v1::influx_ql_metadata::TagKeyColumn;
+
+    fn batch() -> RecordBatch {
+        RecordBatch::try_from_iter(vec![
+            (
+                INFLUXQL_MEASUREMENT_COLUMN_NAME,
+                Arc::new(StringArray::from(vec!["cpu", "cpu", "disk"])) as ArrayRef,
+            ),
+            (
+                "time",
+                Arc::new(TimestampNanosecondArray::from(vec![10, 20, 30])) as ArrayRef,
+            ),
+            (
+                "host",
+                Arc::new(StringArray::from(vec![Some("a"), None, Some("b")])) as ArrayRef,
+            ),
+            (
+                "mean",
+                Arc::new(Float64Array::from(vec![Some(1.5), None, Some(2.0)])) as ArrayRef,
+            ),
+            (
+                "count",
+                Arc::new(Int64Array::from(vec![Some(3), None, Some(4)])) as ArrayRef,
+            ),
+            (
+                "state",
+                Arc::new(StringArray::from(vec![Some("ok"), None, None])) as ArrayRef,
+            ),
+        ])
+        .unwrap()
+    }
+
+    fn to_lp(md: &InfluxQlMetadata, name: &IntoMeasurementName) -> (String, usize) {
+        let (lp, points) = batch_to_line_protocol(&batch(), md, name).unwrap();
+        (String::from_utf8(lp).unwrap(), points)
+    }
+
+    #[test]
+    fn test_batch_to_line_protocol() {
+        let md = InfluxQlMetadata {
+            measurement_column_index: 0,
+            tag_key_columns: vec![TagKeyColumn {
+                tag_key: "host".into(),
+                column_index: 2,
+                is_projected: false,
+            }],
+        };
+
+        // Rows with no field values are not written
+        let (lp, points) = to_lp(&md, &IntoMeasurementName::Name("rollup".into()));
+        assert_eq!(points, 2);
+        assert_eq!(
+            lp,
+            "rollup,host=a mean=1.5,count=3i,state=\"ok\" 10\nrollup,host=b mean=2,count=4i 30\n"
+        );
+
+        // The back-reference writes to the source measurement
+        let (lp, points) = to_lp(&md, &IntoMeasurementName::BackReference);
+        assert_eq!(points, 2);
+        assert_eq!(
+            lp,
+            "cpu,host=a mean=1.5,count=3i,state=\"ok\" 10\ndisk,host=b mean=2,count=4i 30\n"
+        );
+
+        // Tags that are not grouped are written as fields
+        let md = InfluxQlMetadata {
+            measurement_column_index: 0,
+            tag_key_columns: vec![],
+        };
+        let (lp, _) = to_lp(&md, &IntoMeasurementName::Name("rollup".into()));
+        assert_eq!(
+            lp,
+            "rollup host=\"a\",mean=1.5,count=3i,state=\"ok\" 10\nrollup host=\"b\",mean=2,count=4i 30\n"
+        );
+    }
+}
@@ -12,7 +12,8 @@
     unused_crate_dependencies
 )]
 
-use arrow::datatypes::DataType;
+use arrow::datatypes::{DataType, IntervalMonthDayNanoType};
+use datafusion::common::{Result, ScalarValue};
 
 // Workaround for "unused crate" lint false positives.
 use workspace_hack as _;
@@ -20,9 +21,33 @@ use workspace_hack as _;
 mod aggregate;
 mod error;
 pub mod frontend;
+pub mod params;
 pub mod plan;
 mod window;
 
 /// A list of the numeric types supported by InfluxQL that can be be used
 /// as input to user-defined functions.
 static NUMERICS: &[DataType] = &[DataType::Int64, DataType::UInt64, DataType::Float64];
+
+/// The number of nanoseconds in a day.
+const NANOS_PER_DAY: i64 = 24 * 60 * 60 * 1_000_000_000;
+
+/// Convert the `unit` argument of the user-defined function `name` to a
+/// number of nanoseconds.
+///
+/// The unit is an `IntervalMonthDayNano`, where days are converted as
+/// 24 hours. Months do not have a fixed duration, so a unit that includes
+/// months is rejected, as is a unit that is not positive.
+fn unit_nanos(name: &str, unit: &ScalarValue) -> Result<i64> {
+    if let ScalarValue::IntervalMonthDayNano(Some(v)) = unit {
+        let (months, days, nanos) = IntervalMonthDayNanoType::to_parts(*v);
+        let nanos = i64::from(days)
+            .checked_mul(NANOS_PER_DAY)
+            .and_then(|days| days.checked_add(nanos));
+        match nanos {
+            Some(nanos) if months == 0 && nanos > 0 => return Ok(nanos),
+            _ => {}
+        }
+    }
+    error::internal(format!("{name} attempted with invalid unit {unit}"))
+}
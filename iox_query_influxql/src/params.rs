@@ -0,0 +1,172 @@
+//! Values for InfluxQL [bind parameters].
+//!
+//! Bind parameters, such as `$host`, may appear in a query wherever a
+//! literal value is accepted, and are replaced by the literal values
+//! supplied with the query before it is planned.
+//!
+//! [bind parameters]: https://docs.influxdata.com/influxdb/v1.8/tools/api/#bind-parameters
+
+use crate::error;
+use datafusion::common::{DataFusionError, Result};
+use influxdb_influxql_parser::expression::Expr;
+use influxdb_influxql_parser::literal::Literal;
+use influxdb_influxql_parser::statement::Statement;
+use influxdb_influxql_parser::visit_mut::{VisitableMut, VisitorMut};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// The value of a single bind parameter.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum StatementParam {
+    /// A boolean value.
+    Boolean(bool),
+    /// A signed integer value.
+    Integer(i64),
+    /// An unsigned integer value, which is out of range of a signed integer.
+    Unsigned(u64),
+    /// A float value.
+    Float(f64),
+    /// A string value.
+    String(String),
+}
+
+impl From<StatementParam> for Literal {
+    fn from(value: StatementParam) -> Self {
+        match value {
+            StatementParam::Boolean(v) => Self::Boolean(v),
+            StatementParam::Integer(v) => Self::Integer(v),
+            StatementParam::Unsigned(v) => Self::Unsigned(v),
+            StatementParam::Float(v) => Self::Float(v),
+            StatementParam::String(v) => Self::String(v),
+        }
+    }
+}
+
+/// The values of the bind parameters of a query, keyed by name
+/// without the leading `$`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(transparent)]
+pub struct StatementParams(HashMap<String, StatementParam>);
+
+impl StatementParams {
+    /// Decode the parameters from a JSON object, such as
+    /// `{"host": "server01", "value": 1.5}`, as accepted by the
+    /// `params` parameter of the InfluxDB 1.x query API.
+    pub fn from_json(s: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(s)
+    }
+
+    /// Returns true if there are no parameters.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the value of the parameter `name`, if any.
+    pub fn get(&self, name: &str) -> Option<&StatementParam> {
+        self.0.get(name)
+    }
+
+    /// Returns an iterator of the parameter names and values.
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &StatementParam)> {
+        self.0.iter()
+    }
+}
+
+impl From<HashMap<String, StatementParam>> for StatementParams {
+    fn from(value: HashMap<String, StatementParam>) -> Self {
+        Self(value)
+    }
+}
+
+impl FromIterator<(String, StatementParam)> for StatementParams {
+    fn from_iter<T: IntoIterator<Item = (String, StatementParam)>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+/// Replace the bind parameters of `statement` with the literal values
+/// in `params`, returning an error if a parameter has no value.
+pub(crate) fn replace_bind_params(
+    statement: &mut Statement,
+    params: &StatementParams,
+) -> Result<()> {
+    struct Binder<'a>(&'a StatementParams);
+
+    impl<'a> VisitorMut for Binder<'a> {
+        type Error = DataFusionError;
+
+        fn post_visit_expr(&mut self, n: &mut Expr) -> Result<()> {
+            if let Expr::BindParameter(p) = n {
+                match self.0.get(p.as_str()) {
+                    Some(v) => *n = Expr::Literal(v.clone().into()),
+                    None => return error::query(format!("missing value for parameter: {p}")),
+                }
+            }
+            Ok(())
+        }
+    }
+
+    statement.accept(&mut Binder(params))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use influxdb_influxql_parser::parse_statements;
+
+    fn bind(q: &str, params: &str) -> Result<String> {
+        let mut statement = parse_statements(q).unwrap().pop().unwrap();
+        let params = StatementParams::from_json(params).unwrap();
+        replace_bind_params(&mut statement, &params)?;
+        Ok(statement.to_string())
+    }
+
+    #[test]
+    fn test_from_json() {
+        let params = StatementParams::from_json(
+            r#"{"b": true, "i": -1, "u": 18446744073709551615, "f": 1.5, "s": "foo"}"#,
+        )
+        .unwrap();
+        assert_eq!(params.get("b"), Some(&StatementParam::Boolean(true)));
+        assert_eq!(params.get("i"), Some(&StatementParam::Integer(-1)));
+        assert_eq!(params.get("u"), Some(&StatementParam::Unsigned(u64::MAX)));
+        assert_eq!(params.get("f"), Some(&StatementParam::Float(1.5)));
+        assert_eq!(
+            params.get("s"),
+            Some(&StatementParam::String("foo".to_string()))
+        );
+
+        // Only scalar values are supported
+        StatementParams::from_json(r#"{"a": [1]}"#).unwrap_err();
+        StatementParams::from_json(r#"{"a": null}"#).unwrap_err();
+    }
+
+    #[test]
+    fn test_replace_bind_params() {
+        assert_eq!(
+            bind(
+                "SELECT usage_idle + $n FROM cpu WHERE host = $host AND usage_user > $\"min value\"",
+                r#"{"n": 1, "host": "server01", "min value": 0.5}"#,
+            )
+            .unwrap(),
+            "SELECT usage_idle + 1 FROM cpu WHERE host = 'server01' AND usage_user > 0.5"
+        );
+
+        // Strings are always literals, and never interpreted as InfluxQL
+        assert_eq!(
+            bind(
+                "SELECT * FROM cpu WHERE host = $host",
+                r#"{"host": "a' OR 1 = 1 --"}"#,
+            )
+            .unwrap(),
+            r#"SELECT * FROM cpu WHERE host = 'a\' OR 1 = 1 --'"#
+        );
+
+        let err = bind("SELECT * FROM cpu WHERE host = $host", "{}").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: missing value for parameter: $host"
+        );
+    }
+}
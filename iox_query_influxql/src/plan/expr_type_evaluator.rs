@@ -187,11 +187,15 @@ impl<'a> TypeEvaluator<'a> {
             // See: https://github.com/influxdata/influxdb/blob/e484c4d87193a475466c0285c018d16f168139e6/query/functions.go#L54-L60
             "mean" => Some(VarRefDataType::Float),
             "count" => Some(VarRefDataType::Integer),
+            "sum_hll" => Some(VarRefDataType::String),
+            "count_hll" => Some(VarRefDataType::Unsigned),
             // These functions return the same type as their first argument
-            "min" | "max" | "sum" | "first" | "last" | "distinct" => match arg_types.first() {
-                Some(v) => *v,
-                None => None,
-            },
+            "min" | "max" | "sum" | "first" | "last" | "distinct" | "mode" | "spread" => {
+                match arg_types.first() {
+                    Some(v) => *v,
+                    None => None,
+                }
+            }
 
             // See: https://github.com/influxdata/influxdb/blob/e484c4d87193a475466c0285c018d16f168139e6/query/functions.go#L80
             "median"
@@ -613,6 +617,16 @@ mod test {
             .unwrap();
         assert_matches!(res, VarRefDataType::String);
 
+        let res = evaluate_type(&namespace, "MODE(field_str)", &["temp_01"])
+            .unwrap()
+            .unwrap();
+        assert_matches!(res, VarRefDataType::String);
+
+        let res = evaluate_type(&namespace, "SPREAD(field_i64)", &["temp_01"])
+            .unwrap()
+            .unwrap();
+        assert_matches!(res, VarRefDataType::Integer);
+
         let res = evaluate_type(&namespace, "MEAN(field_i64)", &["temp_01"])
             .unwrap()
             .unwrap();
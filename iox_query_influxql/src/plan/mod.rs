@@ -13,4 +13,5 @@ mod var_ref;
 
 pub use planner::InfluxQLToLogicalPlan;
 pub use planner::SchemaProvider;
-pub(crate) use util::parse_regex;
+pub use util::namespace_name;
+pub(crate) use util::{parse_regex, qualified_table_name};
@@ -1,6 +1,7 @@
+mod delete;
 mod select;
 
-use crate::aggregate::PERCENTILE;
+use crate::aggregate::{INTEGRAL, MODE, PERCENTILE, SPREAD};
 use crate::error;
 use crate::plan::ir::{DataSource, Field, Interval, Select, SelectQuery};
 use crate::plan::planner::select::{
@@ -10,16 +11,19 @@ use crate::plan::planner::select::{
 use crate::plan::planner_time_range_expression::time_range_to_df_expr;
 use crate::plan::rewriter::{find_table_names, rewrite_statement, ProjectionType};
 use crate::plan::udf::{
-    cumulative_sum, derivative, difference, find_window_udfs, moving_average,
-    non_negative_derivative, non_negative_difference,
+    chande_momentum_oscillator, cumulative_sum, derivative, difference,
+    double_exponential_moving_average, elapsed, exponential_moving_average, find_window_udfs,
+    holt_winters, holt_winters_with_fit, kaufmans_adaptive_moving_average,
+    kaufmans_efficiency_ratio, moving_average, non_negative_derivative, non_negative_difference,
+    relative_strength_index, triple_exponential_derivative, triple_exponential_moving_average,
+};
+use crate::plan::util::{
+    binary_operator_to_df_operator, check_retention_policy_without_database, namespace_name,
+    rebase_expr, IQLSchema,
 };
-use crate::plan::util::{binary_operator_to_df_operator, rebase_expr, IQLSchema};
 use crate::plan::var_ref::var_ref_data_type_to_data_type;
 use crate::plan::{planner_rewrite_expression, udf};
-use crate::window::{
-    CUMULATIVE_SUM, DERIVATIVE, DIFFERENCE, MOVING_AVERAGE, NON_NEGATIVE_DERIVATIVE,
-    NON_NEGATIVE_DIFFERENCE, PERCENT_ROW_NUMBER,
-};
+use crate::window::{INTEGRAL_WINDOW, PERCENT_ROW_NUMBER, SAMPLE_ROW};
 use arrow::array::{
     BooleanArray, DictionaryArray, Int32Array, Int64Array, StringArray, StringBuilder,
     StringDictionaryBuilder,
@@ -27,6 +31,7 @@ use arrow::array::{
 use arrow::datatypes::{DataType, Field as ArrowField, Int32Type, Schema as ArrowSchema};
 use arrow::record_batch::RecordBatch;
 use chrono_tz::Tz;
+use data_types::DeletePredicate;
 use datafusion::catalog::TableReference;
 use datafusion::common::tree_node::{Transformed, TreeNode, VisitRecursion};
 use datafusion::common::{DFSchema, DFSchemaRef, DataFusionError, Result, ScalarValue, ToDFSchema};
@@ -43,12 +48,13 @@ use datafusion::logical_expr::{
     LogicalPlanBuilder, Operator, PlanType, Projection, ScalarUDF, TableSource, ToStringifiedPlan,
     WindowFrame, WindowFrameBound, WindowFrameUnits,
 };
-use datafusion::optimizer::utils::conjunction;
+use datafusion::optimizer::utils::{conjunction, disjunction};
 use datafusion::physical_expr::execution_props::ExecutionProps;
-use datafusion::prelude::{cast, sum, when, Column};
+use datafusion::prelude::{cast, concat, count, sum, when, Column};
 use datafusion_util::{lit_dict, AsExpr};
 use generated_types::influxdata::iox::querier::v1::InfluxQlMetadata;
 use influxdb_influxql_parser::common::{LimitClause, OffsetClause, OrderByClause};
+use influxdb_influxql_parser::delete::DeleteStatement;
 use influxdb_influxql_parser::explain::{ExplainOption, ExplainStatement};
 use influxdb_influxql_parser::expression::walk::{walk_expr, walk_expression, Expression};
 use influxdb_influxql_parser::expression::{
@@ -58,19 +64,21 @@ use influxdb_influxql_parser::expression::{
 use influxdb_influxql_parser::functions::{
     is_aggregate_function, is_now_function, is_scalar_math_function,
 };
-use influxdb_influxql_parser::select::{FillClause, GroupByClause};
+use influxdb_influxql_parser::select::{FillClause, GroupByClause, SLimitClause, SOffsetClause};
+use influxdb_influxql_parser::show_cardinality::{CardinalityKind, ShowCardinalityStatement};
 use influxdb_influxql_parser::show_field_keys::ShowFieldKeysStatement;
 use influxdb_influxql_parser::show_measurements::{
-    ShowMeasurementsStatement, WithMeasurementClause,
+    ExtendedOnClause, ShowMeasurementsStatement, WithMeasurementClause,
 };
 use influxdb_influxql_parser::show_retention_policies::ShowRetentionPoliciesStatement;
+use influxdb_influxql_parser::show_series::ShowSeriesStatement;
 use influxdb_influxql_parser::show_tag_keys::ShowTagKeysStatement;
 use influxdb_influxql_parser::show_tag_values::{ShowTagValuesStatement, WithKeyClause};
 use influxdb_influxql_parser::simple_from_clause::ShowFromClause;
 use influxdb_influxql_parser::time_range::{split_cond, ReduceContext, TimeRange};
 use influxdb_influxql_parser::timestamp::Timestamp;
 use influxdb_influxql_parser::{
-    common::{MeasurementName, WhereClause},
+    common::{MeasurementName, QualifiedMeasurementName, WhereClause},
     expression::Expr as IQLExpr,
     literal::Literal,
     select::SelectStatement,
@@ -84,13 +92,15 @@ use itertools::Itertools;
 use observability_deps::tracing::debug;
 use query_functions::{
     clean_non_meta_escapes,
+    hll::{APPROX_DISTINCT_HLL_UDAF_NAME, COUNT_HLL_UDAF_NAME, SUM_HLL_UDAF_NAME},
+    registry,
     selectors::{selector_first, selector_last, selector_max, selector_min},
 };
 use schema::{
     InfluxColumnType, InfluxFieldType, Schema, INFLUXQL_MEASUREMENT_COLUMN_NAME,
     INFLUXQL_METADATA_KEY,
 };
-use std::collections::{hash_map::Entry, BTreeSet, HashMap, HashSet};
+use std::collections::{hash_map::Entry, BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt::Debug;
 use std::iter;
 use std::ops::{Bound, ControlFlow, Deref, Not, Range};
@@ -127,6 +137,27 @@ pub trait SchemaProvider {
     fn table_schema(&self, name: &str) -> Option<Schema>;
 
     fn execution_props(&self) -> &ExecutionProps;
+
+    /// Get the [`SchemaProvider`] for the namespace of the specified
+    /// `database` and `retention_policy`, when it is not the namespace
+    /// of the query.
+    ///
+    /// The tables of the namespace are also available from this provider,
+    /// using their qualified names, such as `db..cpu`.
+    fn namespace(
+        &self,
+        _database: &str,
+        _retention_policy: Option<&str>,
+    ) -> Option<&dyn SchemaProvider> {
+        None
+    }
+
+    /// Get the name of the measurement of the table `name`, which differs
+    /// from `name` when it is the qualified name of a table of another
+    /// namespace.
+    fn measurement_name<'b>(&'b self, name: &'b str) -> &'b str {
+        name
+    }
 }
 
 /// Informs the planner which rules should be applied when transforming
@@ -247,11 +278,33 @@ impl<'a> Context<'a> {
     /// Returns true if the current context has an extended
     /// time range to provide leading data for window functions
     /// to produce the result for the first window.
-    #[allow(dead_code)]
     fn has_extended_time_range(&self) -> bool {
         self.extra_intervals > 0 && self.interval.is_some()
     }
 
+    /// Returns an expression to exclude the additional window read
+    /// by the extended time range, when the context has one.
+    ///
+    /// The bound of the time range is aligned to the start of its window,
+    /// so that the expression matches the values of the `time_expr`
+    /// column produced by `GROUP BY TIME`.
+    fn window_time_range_expr(&self, time_expr: Expr) -> Option<Expr> {
+        if !self.has_extended_time_range() {
+            return None;
+        }
+        let interval = self.interval?;
+        let offset = interval.offset.unwrap_or_default();
+        let align = |v: i64| v - (v - offset).rem_euclid(interval.duration);
+
+        if self.order_by.is_ascending() {
+            let lower = self.time_range.lower?;
+            Some(time_expr.gt_eq(lit_timestamp_nano(align(lower))))
+        } else {
+            let upper = self.time_range.upper?;
+            Some(time_expr.lt_eq(lit_timestamp_nano(align(upper))))
+        }
+    }
+
     /// Return the time range of the context, including any
     /// additional intervals required for window functions like
     /// `difference` or `moving_average`, when the query contains a
@@ -444,9 +497,15 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
     pub fn statement_to_plan(&self, statement: Statement) -> Result<LogicalPlan> {
         match statement {
             Statement::CreateDatabase(_) => error::not_implemented("CREATE DATABASE"),
-            Statement::Delete(_) => error::not_implemented("DELETE"),
-            Statement::DropMeasurement(_) => error::not_implemented("DROP MEASUREMENT"),
+            Statement::Delete(_) | Statement::DropMeasurement(_) | Statement::DropSeries(_) => {
+                error::internal(format!(
+                    "statement must be planned using statement_to_deletes: {statement}"
+                ))
+            }
             Statement::Explain(explain) => self.explain_statement_to_plan(*explain),
+            Statement::KillQuery(_) => error::internal(format!(
+                "statement must be executed using the query canceller: {statement}"
+            )),
             Statement::Select(select) => {
                 self.select_query_to_plan(&self.rewrite_select_statement(*select)?)
             }
@@ -464,9 +523,67 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             Statement::ShowFieldKeys(show_field_keys) => {
                 self.show_field_keys_to_plan(*show_field_keys)
             }
+            Statement::ShowSeries(show_series) => self.show_series_to_plan(*show_series),
+            Statement::ShowCardinality(show_cardinality) => {
+                self.show_cardinality_to_plan(*show_cardinality)
+            }
         }
     }
 
+    /// Translate a `DELETE`, `DROP MEASUREMENT` or `DROP SERIES` statement into
+    /// the predicate deletes that implement it, as a list of the name of each
+    /// affected table and the predicate matching the rows to delete.
+    ///
+    /// Measurements that do not exist are ignored, consistent with InfluxQL.
+    pub fn statement_to_deletes(
+        &self,
+        statement: Statement,
+    ) -> Result<Vec<(String, DeletePredicate)>> {
+        let start_time = Timestamp::from(self.s.execution_props().query_execution_start_time);
+        let rc = ReduceContext {
+            now: Some(start_time),
+            tz: None,
+        };
+        let table_names = self.s.table_names();
+
+        let (tables, predicate) = match statement {
+            Statement::Delete(delete) => match *delete {
+                DeleteStatement::FromWhere { from, condition } => (
+                    delete::expand_delete_from_clause(&table_names, Some(&from))?,
+                    delete::condition_to_delete_predicate(&rc, condition.as_deref())?,
+                ),
+                DeleteStatement::Where(condition) => (
+                    delete::expand_delete_from_clause(&table_names, None)?,
+                    delete::condition_to_delete_predicate(&rc, Some(&condition))?,
+                ),
+            },
+            Statement::DropMeasurement(drop_measurement) => {
+                let name = drop_measurement.name.as_str();
+                let tables = if self.s.table_exists(name) {
+                    vec![name.to_owned()]
+                } else {
+                    vec![]
+                };
+                (tables, delete::delete_all())
+            }
+            Statement::DropSeries(drop_series) => (
+                delete::expand_delete_from_clause(&table_names, drop_series.from.as_ref())?,
+                delete::condition_to_delete_predicate(&rc, drop_series.condition.as_deref())?,
+            ),
+            _ => return error::internal(format!("statement does not delete data: {statement}")),
+        };
+
+        tables
+            .into_iter()
+            .map(|table| {
+                if let Some(schema) = self.s.table_schema(&table) {
+                    delete::validate_delete_predicate(&predicate, &schema)?;
+                }
+                Ok((table, predicate.clone()))
+            })
+            .collect()
+    }
+
     fn explain_statement_to_plan(&self, explain: ExplainStatement) -> Result<LogicalPlan> {
         let plan = self.select_query_to_plan(&self.rewrite_select_statement(*explain.select)?)?;
         let plan = Arc::new(plan);
@@ -557,7 +674,7 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             //
             // See: https://github.com/influxdata/influxdb_iox/issues/8042
 
-            plans.push((table_name, plan));
+            plans.push((self.s.measurement_name(table_name), plan));
         }
 
         let plan = {
@@ -638,6 +755,14 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             false,
         );
 
+        let plan = self.series_limit(
+            plan,
+            select.series_offset,
+            select.series_limit,
+            sort_by_measurement,
+            &group_by_tag_set,
+        )?;
+
         let plan = plan_with_sort(
             plan,
             vec![time_sort_expr.clone()],
@@ -686,6 +811,14 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             false,
         );
 
+        let plan = self.series_limit(
+            plan,
+            select.series_offset,
+            select.series_limit,
+            false,
+            &group_by_tag_set,
+        )?;
+
         let plan = plan_with_sort(
             plan,
             vec![time_sort_expr.clone()],
@@ -784,12 +917,24 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         match ctx.projection_type {
             ProjectionType::Raw => self.project_select_raw(input, fields),
             ProjectionType::RawDistinct => self.project_select_raw_distinct(input, fields),
-            ProjectionType::Aggregate   => self.project_select_aggregate(ctx, input, fields, group_by_tag_set),
-            ProjectionType::Window => self.project_select_window(ctx, input, fields, group_by_tag_set),
-            ProjectionType::WindowAggregate => self.project_select_window_aggregate(ctx, input, fields, group_by_tag_set),
-            ProjectionType::WindowAggregateMixed => error::not_implemented("mixed window-aggregate and aggregate columns, such as DIFFERENCE(MEAN(col)), MEAN(col)"),
-            ProjectionType::Selector{..} => self.project_select_selector(ctx, input, fields, group_by_tag_set),
-            ProjectionType::TopBottomSelector => self.project_select_top_bottom_selector(ctx, input, fields, group_by_tag_set),
+            ProjectionType::Aggregate => {
+                self.project_select_aggregate(ctx, input, fields, group_by_tag_set)
+            }
+            ProjectionType::Window => {
+                self.project_select_window(ctx, input, fields, group_by_tag_set)
+            }
+            ProjectionType::WindowAggregate => {
+                self.project_select_window_aggregate(ctx, input, fields, group_by_tag_set)
+            }
+            ProjectionType::WindowAggregateMixed => {
+                self.project_select_window_aggregate_mixed(ctx, input, fields, group_by_tag_set)
+            }
+            ProjectionType::Selector { .. } => {
+                self.project_select_selector(ctx, input, fields, group_by_tag_set)
+            }
+            ProjectionType::TopBottomSelector => {
+                self.project_select_top_bottom_selector(ctx, input, fields, group_by_tag_set)
+            }
         }
     }
 
@@ -917,8 +1062,10 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
 
         let (plan, select_exprs) =
             self.select_aggregate(ctx, input, fields, select_exprs, group_by_tag_set)?;
+        let aggr_columns = aggregate_columns(&plan, group_by_tag_set);
 
         let (plan, select_exprs) = self.select_window(ctx, plan, select_exprs, group_by_tag_set)?;
+        let plan = filter_forecast_rows(ctx, fields, plan, &aggr_columns, &select_exprs)?;
 
         // Wrap the plan in a `LogicalPlan::Projection` from the select expressions
         let plan = project(plan, select_exprs)?;
@@ -941,12 +1088,58 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         }
     }
 
+    /// Plan "WindowAggregateMixed" SELECT queries. These are queries that use
+    /// a combination of window and nested aggregate functions, as well as
+    /// aggregate functions that are not nested in a window function, such as:
+    ///
+    /// ```sql
+    /// SELECT DIFFERENCE(MEAN(col)), MEAN(col) FROM cpu GROUP BY TIME(10s)
+    /// ```
+    ///
+    /// The aggregates are computed once and shared by the window functions
+    /// and the remaining projections.
+    fn project_select_window_aggregate_mixed(
+        &self,
+        ctx: &Context<'_>,
+        input: LogicalPlan,
+        fields: &[Field],
+        group_by_tag_set: &[&str],
+    ) -> Result<LogicalPlan> {
+        let schema = IQLSchema::new_from_fields(input.schema(), fields)?;
+
+        // Transform InfluxQL AST field expressions to a list of DataFusion expressions.
+        let select_exprs = self.field_list_to_exprs(&input, fields, &schema)?;
+
+        let (plan, select_exprs) =
+            self.select_aggregate(ctx, input, fields, select_exprs, group_by_tag_set)?;
+        let aggr_columns = aggregate_columns(&plan, group_by_tag_set);
+
+        let (plan, select_exprs) = self.select_window(ctx, plan, select_exprs, group_by_tag_set)?;
+        let plan = filter_forecast_rows(ctx, fields, plan, &aggr_columns, &select_exprs)?;
+
+        // Wrap the plan in a `LogicalPlan::Projection` from the select expressions
+        let plan = project(plan, select_exprs)?;
+
+        // Unlike the "WindowAggregate" projection, rows are not removed when the
+        // window functions produce `NULL` results, as the aggregate columns are
+        // produced for every window, to match InfluxQL OG.
+        //
+        // However, the time range may have been extended to provide the leading
+        // data for the window functions, and that additional window must be
+        // removed from the results.
+        let Some(time_column_index) = find_time_column_index(fields) else {
+            return error::internal("unable to find time column");
+        };
+        match ctx.window_time_range_expr(fields[time_column_index].name.as_expr()) {
+            Some(expr) => LogicalPlanBuilder::from(plan).filter(expr)?.build(),
+            None => Ok(plan),
+        }
+    }
+
     /// Plan the execution of SELECT queries that have the Selector projection
     /// type. These a queries that include a single FIRST, LAST, MAX, MIN,
     /// PERCENTILE, or SAMPLE function call, possibly requesting additional
     /// tags or fields.
-    ///
-    /// N.B SAMPLE is not yet implemented.
     fn project_select_selector(
         &self,
         ctx: &Context<'_>,
@@ -1010,8 +1203,27 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
 
                 (idx, field_key, plan)
             }
-            (_, Selector::Sample { field_key: _, n: _ }) => {
-                return error::not_implemented("sample selector function")
+            (idx, Selector::Sample { field_key, n }) => {
+                let window_sample_row = Expr::WindowFunction(WindowFunction::new(
+                    SAMPLE_ROW.clone(),
+                    vec![lit(n)],
+                    window_partition_by(ctx, input.schema(), group_by_tag_set),
+                    vec![ctx.time_sort_expr()],
+                    WindowFrame {
+                        units: WindowFrameUnits::Rows,
+                        start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
+                        end_bound: WindowFrameBound::Following(ScalarValue::Null),
+                    },
+                ));
+                let sample_row_column_name = window_sample_row.display_name()?;
+
+                let plan = LogicalPlanBuilder::from(input)
+                    .filter(field_key.as_expr().is_not_null())?
+                    .window(vec![window_sample_row.alias(sample_row_column_name.clone())])?
+                    .filter(col(sample_row_column_name))?
+                    .build()?;
+
+                (idx, field_key, plan)
             }
 
             (_, s) => {
@@ -1208,6 +1420,20 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             }
         }
 
+        // When grouping by time, the area calculated by INTEGRAL is split between
+        // adjacent windows, so it is calculated per row by a window function over
+        // each series, and the aggregate sums the area of the rows in each window.
+        let input = match ctx.interval {
+            Some(interval) => Self::select_integral_window(
+                interval,
+                input,
+                &mut aggr_exprs,
+                &mut select_exprs,
+                group_by_tag_set,
+            )?,
+            None => input,
+        };
+
         // This block identifies the time column index and updates the time expression
         // based on the semantics of the projection.
         let time_column = {
@@ -1290,6 +1516,14 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
 
         let fill_option = ctx.fill();
 
+        // The HOLT_WINTERS functions return their forecast values in the
+        // intervals that follow the time range of the query, so these
+        // must be produced by the GapFill operator.
+        let forecast_duration = match (holt_winters_forecast_count(fields), ctx.interval) {
+            (count @ 1.., Some(i)) => count * i.duration,
+            _ => 0,
+        };
+
         // Wrap the plan in a GapFill operator if the statement specifies a `GROUP BY TIME` clause and
         // the FILL option is one of
         //
@@ -1298,17 +1532,22 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         // * `literal` value
         // * `linear`
         //
+        // or the projection forecasts values using HOLT_WINTERS.
         let plan = if ctx.group_by.and_then(|gb| gb.time_dimension()).is_some()
-            && fill_option != FillClause::None
+            && (fill_option != FillClause::None || forecast_duration > 0)
         {
+            // With `FILL(none)`, the gaps are only filled for the model of
+            // the HOLT_WINTERS functions, and are removed once the values
+            // are forecast.
+            //
+            // See: filter_forecast_rows
             let fill_strategy = match fill_option {
-                FillClause::Null | FillClause::Value(_) => FillStrategy::Null,
+                FillClause::Null | FillClause::Value(_) | FillClause::None => FillStrategy::Null,
                 FillClause::Previous => FillStrategy::PrevNullAsMissing,
                 FillClause::Linear => FillStrategy::LinearInterpolate,
-                FillClause::None => unreachable!(),
             };
 
-            build_gap_fill_node(plan, time_column, fill_strategy)?
+            build_gap_fill_node(plan, time_column, fill_strategy, forecast_duration)?
         } else {
             plan
         };
@@ -1352,6 +1591,67 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         Ok((plan, select_exprs_post_aggr))
     }
 
+    /// Rewrite any `INTEGRAL` aggregates in `aggr_exprs` and `select_exprs` to sum
+    /// the area each row contributes to its `GROUP BY time()` window, which is
+    /// projected by a window function over each series in `input`.
+    fn select_integral_window(
+        interval: Interval,
+        input: LogicalPlan,
+        aggr_exprs: &mut [Expr],
+        select_exprs: &mut [Expr],
+        group_by_tag_set: &[&str],
+    ) -> Result<LogicalPlan> {
+        let stride = lit(ScalarValue::new_interval_mdn(0, 0, interval.duration));
+        let origin = lit(ScalarValue::TimestampNanosecond(
+            Some(interval.offset.unwrap_or_default()),
+            None,
+        ));
+
+        let mut window_exprs = vec![];
+        for aggr_expr in aggr_exprs.iter_mut() {
+            let Expr::AggregateUDF(expr::AggregateUDF { fun, args, .. }) = &*aggr_expr else {
+                continue;
+            };
+            if fun.name != INTEGRAL.name {
+                continue;
+            }
+
+            let name = aggr_expr.display_name()?;
+            let window_expr = Expr::WindowFunction(WindowFunction::new(
+                INTEGRAL_WINDOW.clone(),
+                [args.as_slice(), &[stride.clone(), origin.clone()]].concat(),
+                fields_to_exprs_no_nulls(input.schema(), group_by_tag_set).collect(),
+                vec!["time".as_expr().sort(true, false)],
+                WindowFrame {
+                    units: WindowFrameUnits::Rows,
+                    start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
+                    end_bound: WindowFrameBound::Following(ScalarValue::Null),
+                },
+            ));
+
+            let sum_expr = sum(Expr::Column(Column::from_name(name.clone())));
+            let integral = std::mem::replace(aggr_expr, sum_expr.clone());
+            for expr in select_exprs.iter_mut() {
+                *expr = expr.clone().transform_up(&|expr| {
+                    Ok(if expr == integral {
+                        Transformed::Yes(sum_expr.clone())
+                    } else {
+                        Transformed::No(expr)
+                    })
+                })?;
+            }
+            window_exprs.push(window_expr.alias(name));
+        }
+
+        if window_exprs.is_empty() {
+            return Ok(input);
+        }
+
+        LogicalPlanBuilder::from(input)
+            .window(window_exprs)?
+            .build()
+    }
+
     /// Generate a plan for any window functions, such as `moving_average` or `difference`.
     fn select_window(
         &self,
@@ -1469,96 +1769,192 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             }
         }
 
-        match udf::WindowFunction::try_from_scalar_udf(Arc::clone(&fun)) {
-            Some(udf::WindowFunction::MovingAverage) => Ok(Expr::WindowFunction(WindowFunction {
-                fun: MOVING_AVERAGE.clone(),
-                args,
-                partition_by,
-                order_by,
-                window_frame: WindowFrame {
-                    units: WindowFrameUnits::Rows,
-                    start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
-                    end_bound: WindowFrameBound::Following(ScalarValue::Null),
-                },
-            })
-            .alias(alias)),
-            Some(udf::WindowFunction::Difference) => Ok(Expr::WindowFunction(WindowFunction {
-                fun: DIFFERENCE.clone(),
-                args,
-                partition_by,
-                order_by,
-                window_frame: WindowFrame {
-                    units: WindowFrameUnits::Rows,
-                    start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
-                    end_bound: WindowFrameBound::Following(ScalarValue::Null),
-                },
-            })
-            .alias(alias)),
-            Some(udf::WindowFunction::NonNegativeDifference) => {
-                Ok(Expr::WindowFunction(WindowFunction {
-                    fun: NON_NEGATIVE_DIFFERENCE.clone(),
-                    args,
-                    partition_by,
-                    order_by,
-                    window_frame: WindowFrame {
-                        units: WindowFrameUnits::Rows,
-                        start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
-                        end_bound: WindowFrameBound::Following(ScalarValue::Null),
-                    },
-                })
-                .alias(alias))
+        /// Unlike `DERIVATIVE`, the default unit of `ELAPSED` is always 1ns,
+        /// regardless of the `GROUP BY time()` interval.
+        fn elapsed_unit(args: &Vec<Expr>) -> Result<ScalarValue> {
+            if args.len() > 1 {
+                if let Expr::Literal(v) = &args[1] {
+                    Ok(v.clone())
+                } else {
+                    error::internal(format!("udf_to_expr: unexpected expression: {}", args[1]))
+                }
+            } else {
+                Ok(ScalarValue::new_interval_mdn(0, 0, 1)) // 1ns
             }
-            Some(udf::WindowFunction::Derivative) => Ok(Expr::WindowFunction(WindowFunction {
-                fun: DERIVATIVE.clone(),
-                args: vec![
+        }
+
+        let Some(wf) = udf::WindowFunction::try_from_scalar_udf(Arc::clone(&fun)) else {
+            return error::internal(format!(
+                "unexpected user-defined window function: {}",
+                fun.name
+            ));
+        };
+
+        let (args, order_by) = match wf {
+            udf::WindowFunction::Derivative | udf::WindowFunction::NonNegativeDerivative => (
+                vec![
                     args[0].clone(),
                     lit(derivative_unit(ctx, &args)?),
                     "time".as_expr(),
                 ],
-                partition_by,
                 order_by,
-                window_frame: WindowFrame {
-                    units: WindowFrameUnits::Rows,
-                    start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
-                    end_bound: WindowFrameBound::Following(ScalarValue::Null),
-                },
-            })
-            .alias(alias)),
-            Some(udf::WindowFunction::NonNegativeDerivative) => {
-                Ok(Expr::WindowFunction(WindowFunction {
-                    fun: NON_NEGATIVE_DERIVATIVE.clone(),
-                    args: vec![
+            ),
+            udf::WindowFunction::Elapsed => (
+                vec![args[0].clone(), lit(elapsed_unit(&args)?), "time".as_expr()],
+                order_by,
+            ),
+            udf::WindowFunction::HoltWinters | udf::WindowFunction::HoltWintersWithFit => {
+                // The model is only fitted to the values within the time
+                // range of the query, as the gap-filled intervals that follow
+                // are where the forecast values are returned.
+                //
+                // See: InfluxQLToLogicalPlan::select_aggregate
+                let upper = ctx.time_range.upper.unwrap_or(i64::MAX);
+                (
+                    vec![
                         args[0].clone(),
-                        lit(derivative_unit(ctx, &args)?),
+                        args[1].clone(),
+                        args[2].clone(),
+                        lit(ScalarValue::TimestampNanosecond(Some(upper), None)),
                         "time".as_expr(),
                     ],
-                    partition_by,
-                    order_by,
-                    window_frame: WindowFrame {
-                        units: WindowFrameUnits::Rows,
-                        start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
-                        end_bound: WindowFrameBound::Following(ScalarValue::Null),
-                    },
-                })
-                .alias(alias))
+                    // The values are always fitted in ascending time order,
+                    // regardless of the ORDER BY clause.
+                    vec![ctx.time_alias.as_expr().sort(true, false)],
+                )
             }
-            Some(udf::WindowFunction::CumulativeSum) => Ok(Expr::WindowFunction(WindowFunction {
-                fun: CUMULATIVE_SUM.clone(),
-                args,
-                partition_by,
-                order_by,
-                window_frame: WindowFrame {
-                    units: WindowFrameUnits::Rows,
-                    start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
-                    end_bound: WindowFrameBound::Following(ScalarValue::Null),
-                },
-            })
-            .alias(alias)),
-            None => error::internal(format!(
-                "unexpected user-defined window function: {}",
-                fun.name
-            )),
+            _ => (args, order_by),
+        };
+
+        Ok(Expr::WindowFunction(WindowFunction {
+            fun: wf.window_udf().clone(),
+            args,
+            partition_by,
+            order_by,
+            window_frame: WindowFrame {
+                units: WindowFrameUnits::Rows,
+                start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
+                end_bound: WindowFrameBound::Following(ScalarValue::Null),
+            },
+        })
+        .alias(alias))
+    }
+
+    /// Generate a plan that restricts the input data to a range of series, first omitting a
+    /// specified number of series, followed by restricting the quantity of series per
+    /// measurement. A series is a unique combination of the tag values of the `GROUP BY` clause.
+    ///
+    /// The output plan is not sorted, and is expected to be sorted by the caller.
+    ///
+    /// ## Arguments
+    ///
+    /// - `input`: The plan to apply the series limit to.
+    /// - `series_offset`: The number of series to skip.
+    /// - `series_limit`: The maximum number of series to return in the output plan.
+    /// - `sort_by_measurement`: `true` if the `input` contains more than one measurement.
+    /// - `group_by_tag_set`: Tag columns from the `input` plan that identify a series.
+    fn series_limit(
+        &self,
+        input: LogicalPlan,
+        series_offset: Option<SOffsetClause>,
+        series_limit: Option<SLimitClause>,
+        sort_by_measurement: bool,
+        group_by_tag_set: &[&str],
+    ) -> Result<LogicalPlan> {
+        if series_offset.is_none() && series_limit.is_none() {
+            return Ok(input);
+        }
+
+        let series_limit = series_limit
+            .map(|v| <u64 as TryInto<i64>>::try_into(*v))
+            .transpose()
+            .map_err(|_| error::map::query("series limit out of range"))?;
+        let series_offset = series_offset
+            .map(|v| <u64 as TryInto<i64>>::try_into(*v))
+            .transpose()
+            .map_err(|_| error::map::query("series offset out of range"))?;
+
+        let order_by = fields_to_exprs_no_nulls(input.schema(), group_by_tag_set)
+            .map(|expr| expr.sort(true, false))
+            .collect::<Vec<_>>();
+
+        if order_by.is_empty() {
+            // Without a GROUP BY tag set, each measurement is a single series, so the
+            // result is either the entire input or empty.
+            return if series_offset.unwrap_or(0) > 0 || series_limit == Some(0) {
+                LogicalPlanBuilder::from(input).limit(0, Some(0))?.build()
+            } else {
+                Ok(input)
+            };
         }
+
+        // The name of the DENSE_RANK window expression
+        const IOX_SERIES_ALIAS: &str = "iox::series";
+
+        // Construct a DENSE_RANK window expression, which assigns the same number
+        // to all the rows of a series:
+        //
+        // DENSE_RANK() OVER (
+        //   PARTITION BY [iox::measurement]
+        //   ORDER BY group_by_tag_set
+        //   RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW
+        // ) AS iox::series
+        let partition_by = if sort_by_measurement {
+            vec![INFLUXQL_MEASUREMENT_COLUMN_NAME.as_expr()]
+        } else {
+            vec![]
+        };
+
+        let window_func_exprs = vec![Expr::WindowFunction(WindowFunction {
+            fun: window_function::WindowFunction::BuiltInWindowFunction(
+                BuiltInWindowFunction::DenseRank,
+            ),
+            args: vec![],
+            partition_by,
+            order_by,
+            window_frame: WindowFrame {
+                units: WindowFrameUnits::Range,
+                start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
+                end_bound: WindowFrameBound::CurrentRow,
+            },
+        })
+        .alias(IOX_SERIES_ALIAS)];
+
+        // Prepare new projection.
+        let proj_exprs = input
+            .schema()
+            .fields()
+            .iter()
+            .map(|expr| Expr::Column(expr.unqualified_column()))
+            .collect::<Vec<_>>();
+
+        // a reference to the DENSE_RANK column.
+        let series_alias = IOX_SERIES_ALIAS.as_expr();
+
+        let series_filter_expr = match (series_limit, series_offset) {
+            // WHERE "iox::series" BETWEEN SOFFSET + 1 AND SOFFSET + SLIMIT
+            (Some(limit), Some(offset)) => Expr::Between(Between {
+                expr: Box::new(series_alias),
+                negated: false,
+                low: Box::new(lit(offset + 1)),
+                high: Box::new(lit(offset + limit)),
+            }),
+
+            // WHERE "iox::series" <= SLIMIT
+            (Some(limit), None) => series_alias.lt_eq(lit(limit)),
+
+            // WHERE "iox::series" > SOFFSET
+            (None, Some(offset)) => series_alias.gt(lit(offset)),
+            (None, None) => unreachable!("series limit and offset cannot be None"),
+        };
+
+        LogicalPlanBuilder::from(input)
+            .window(window_func_exprs)?
+            // Filter by the SLIMIT and SOFFSET clause
+            .filter(series_filter_expr)?
+            // Project the output without the IOX_SERIES_ALIAS column
+            .project(proj_exprs)?
+            .build()
     }
 
     /// Generate a plan that partitions the input data into groups, first omitting a specified
@@ -1836,7 +2232,8 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                     },
                 })
             }
-            IQLExpr::BindParameter(_) => error::not_implemented("parameter"),
+            // Bind parameters should be replaced prior to planning.
+            IQLExpr::BindParameter(_) => error::internal("unexpected bind parameter"),
             IQLExpr::Literal(val) => match val {
                 Literal::Integer(v) => Ok(lit(*v)),
                 Literal::Unsigned(v) => Ok(lit(*v)),
@@ -1981,17 +2378,84 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                     None,
                 )))
             }
-            "percentile" => {
+            name @ ("mode" | "spread") => {
                 let expr = self.expr_to_df_expr(scope, &args[0], schema)?;
                 if let Expr::Literal(ScalarValue::Null) = expr {
                     return Ok(expr);
                 }
 
-                check_arg_count(name, args, 2)?;
-                let nexpr = self.expr_to_df_expr(scope, &args[1], schema)?;
+                check_arg_count(name, args, 1)?;
+                let (udf, args) = match name {
+                    // MODE prefers the value that occurs first when the counts are equal
+                    "mode" => (MODE.clone(), vec![expr, "time".as_expr()]),
+                    _ => (SPREAD.clone(), vec![expr]),
+                };
                 Ok(Expr::AggregateUDF(expr::AggregateUDF::new(
-                    PERCENTILE.clone(),
-                    vec![expr, nexpr],
+                    udf, args, None, None,
+                )))
+            }
+            "sum_hll" | "count_hll" => {
+                // A call to count_hll(sum_hll(field)) is planned as a single
+                // aggregate, which estimates the distinct values of field.
+                let (udaf_name, expr) = match &args[0] {
+                    IQLExpr::Call(c) if name == "count_hll" && c.name == "sum_hll" => {
+                        check_arg_count(&c.name, &c.args, 1)?;
+                        (
+                            APPROX_DISTINCT_HLL_UDAF_NAME,
+                            self.expr_to_df_expr(scope, &c.args[0], schema)?,
+                        )
+                    }
+                    expr => (
+                        if name == "sum_hll" {
+                            SUM_HLL_UDAF_NAME
+                        } else {
+                            COUNT_HLL_UDAF_NAME
+                        },
+                        self.expr_to_df_expr(scope, expr, schema)?,
+                    ),
+                };
+                if let Expr::Literal(ScalarValue::Null) = expr {
+                    return Ok(expr);
+                }
+
+                check_arg_count(name, args, 1)?;
+                Ok(Expr::AggregateUDF(expr::AggregateUDF::new(
+                    registry().udaf(udaf_name)?,
+                    vec![expr],
+                    None,
+                    None,
+                )))
+            }
+            "integral" => {
+                let expr = self.expr_to_df_expr(scope, &args[0], schema)?;
+                if let Expr::Literal(ScalarValue::Null) = expr {
+                    return Ok(expr);
+                }
+
+                check_arg_count_range(name, args, 1, 2)?;
+                let unit = if args.len() > 1 {
+                    self.expr_to_df_expr(scope, &args[1], schema)?
+                } else {
+                    lit(ScalarValue::new_interval_mdn(0, 0, 1_000_000_000)) // 1s
+                };
+                Ok(Expr::AggregateUDF(expr::AggregateUDF::new(
+                    INTEGRAL.clone(),
+                    vec![expr, "time".as_expr(), unit],
+                    None,
+                    None,
+                )))
+            }
+            "percentile" => {
+                let expr = self.expr_to_df_expr(scope, &args[0], schema)?;
+                if let Expr::Literal(ScalarValue::Null) = expr {
+                    return Ok(expr);
+                }
+
+                check_arg_count(name, args, 2)?;
+                let nexpr = self.expr_to_df_expr(scope, &args[1], schema)?;
+                Ok(Expr::AggregateUDF(expr::AggregateUDF::new(
+                    PERCENTILE.clone(),
+                    vec![expr, nexpr],
                     None,
                     None,
                 )))
@@ -2059,6 +2523,88 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
 
                 Ok(moving_average(vec![arg0, lit(arg1)]))
             }
+            "exponential_moving_average"
+            | "double_exponential_moving_average"
+            | "triple_exponential_moving_average"
+            | "relative_strength_index"
+            | "triple_exponential_derivative"
+            | "kaufmans_efficiency_ratio"
+            | "kaufmans_adaptive_moving_average"
+            | "chande_momentum_oscillator" => {
+                let is_kaufmans = name.starts_with("kaufmans");
+                check_arg_count_range(name, args, 2, if is_kaufmans { 3 } else { 4 })?;
+
+                // arg0 should be a column or function
+                let arg0 = self.expr_to_df_expr(scope, &args[0], schema)?;
+                if let Expr::Literal(ScalarValue::Null) = arg0 {
+                    return Ok(arg0);
+                }
+
+                // arg1 is the period and arg2 is the optional hold period,
+                // both of which should be integers. A hold period of -1
+                // selects the default hold period of the function.
+                let integer_arg = |idx: usize, default: i64| -> Result<i64> {
+                    let Some(arg) = args.get(idx) else {
+                        return Ok(default);
+                    };
+                    match self.expr_to_df_expr(scope, arg, schema)? {
+                        Expr::Literal(ScalarValue::Int64(Some(v))) => Ok(v),
+                        Expr::Literal(ScalarValue::UInt64(Some(v))) => Ok(v as i64),
+                        _ => error::query(format!("expected integer argument in {name}()")),
+                    }
+                };
+                let mut eargs = vec![arg0, lit(integer_arg(1, 0)?), lit(integer_arg(2, -1)?)];
+
+                // arg3 is the optional warmup type, which should be a string.
+                if !is_kaufmans {
+                    let warmup = match args.get(3) {
+                        Some(arg) => self.expr_to_df_expr(scope, arg, schema)?,
+                        None if name == "chande_momentum_oscillator" => lit("none"),
+                        None => lit("exponential"),
+                    };
+                    if !matches!(warmup, Expr::Literal(ScalarValue::Utf8(Some(_)))) {
+                        return error::query(format!("expected string argument in {name}()"));
+                    }
+                    eargs.push(warmup);
+                }
+
+                Ok(match name.as_str() {
+                    "exponential_moving_average" => exponential_moving_average(eargs),
+                    "double_exponential_moving_average" => double_exponential_moving_average(eargs),
+                    "triple_exponential_moving_average" => triple_exponential_moving_average(eargs),
+                    "relative_strength_index" => relative_strength_index(eargs),
+                    "triple_exponential_derivative" => triple_exponential_derivative(eargs),
+                    "kaufmans_efficiency_ratio" => kaufmans_efficiency_ratio(eargs),
+                    "kaufmans_adaptive_moving_average" => kaufmans_adaptive_moving_average(eargs),
+                    _ => chande_momentum_oscillator(eargs),
+                })
+            }
+            "holt_winters" | "holt_winters_with_fit" => {
+                check_arg_count(name, args, 3)?;
+
+                // arg0 should be an aggregate function
+                let arg0 = self.expr_to_df_expr(scope, &args[0], schema)?;
+                if let Expr::Literal(ScalarValue::Null) = arg0 {
+                    return Ok(arg0);
+                }
+
+                // arg1 is the number of values to forecast and arg2 is the
+                // length of the seasonal pattern, both of which should be integers.
+                let integer_arg = |idx: usize| -> Result<i64> {
+                    match self.expr_to_df_expr(scope, &args[idx], schema)? {
+                        Expr::Literal(ScalarValue::Int64(Some(v))) => Ok(v),
+                        Expr::Literal(ScalarValue::UInt64(Some(v))) => Ok(v as i64),
+                        _ => error::query(format!("expected integer argument in {name}()")),
+                    }
+                };
+                let eargs = vec![arg0, lit(integer_arg(1)?), lit(integer_arg(2)?)];
+
+                Ok(if name == "holt_winters" {
+                    holt_winters(eargs)
+                } else {
+                    holt_winters_with_fit(eargs)
+                })
+            }
             "derivative" => {
                 check_arg_count_range(name, args, 1, 2)?;
 
@@ -2091,6 +2637,22 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
 
                 Ok(non_negative_derivative(eargs))
             }
+            "elapsed" => {
+                check_arg_count_range(name, args, 1, 2)?;
+
+                // arg0 should be a column or function
+                let arg0 = self.expr_to_df_expr(scope, &args[0], schema)?;
+                if let Expr::Literal(ScalarValue::Null) = arg0 {
+                    return Ok(arg0);
+                }
+                let mut eargs = vec![arg0];
+                if args.len() > 1 {
+                    let arg1 = self.expr_to_df_expr(scope, &args[1], schema)?;
+                    eargs.push(arg1);
+                }
+
+                Ok(elapsed(eargs))
+            }
             "cumulative_sum" => {
                 check_arg_count(name, args, 1)?;
 
@@ -2106,6 +2668,9 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             // query, so the planner only needs to project the single column
             // argument.
             "top" | "bottom" => self.expr_to_df_expr(scope, &args[0], schema),
+            // The SAMPLE function is handled as a `ProjectionType::Selector` query,
+            // and returns many rows, so it cannot be combined with other functions.
+            "sample" => error::not_implemented("sample combined with other functions"),
 
             _ => error::query(format!("Invalid function '{name}'")),
         }
@@ -2258,13 +2823,83 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             let table_ref = TableReference::bare(table_name.to_owned());
             Some((
                 LogicalPlanBuilder::scan(table_ref, source, None)?.build()?,
-                vec![lit_dict(table_name).alias(INFLUXQL_MEASUREMENT_COLUMN_NAME)],
+                vec![lit_dict(self.s.measurement_name(table_name))
+                    .alias(INFLUXQL_MEASUREMENT_COLUMN_NAME)],
             ))
         } else {
             None
         })
     }
 
+    /// Return the planner for the database of a `SHOW` statement, when it is
+    /// not the database of the query.
+    ///
+    /// The database is specified by the `ON` clause, as `on`, or by the
+    /// qualified measurement names of the `FROM` clause, such as `db.rp.cpu`.
+    /// The qualifiers are removed from the names of `from`, which are then
+    /// resolved by the returned planner.
+    ///
+    /// A statement referring to more than one database is not supported: the
+    /// results of `SHOW` statements are grouped by measurement name alone, so
+    /// measurements with the same name in different databases could not be
+    /// told apart.
+    fn show_database_planner(
+        &self,
+        on: Option<(&str, Option<&str>)>,
+        from: &mut [QualifiedMeasurementName],
+    ) -> Result<Option<Self>> {
+        // The namespaces referred to by the statement, and the database and
+        // retention policy of each.
+        let mut namespaces = BTreeMap::new();
+        let mut query_namespace = false;
+
+        let mut insert = |database: &str, retention_policy: Option<&str>| {
+            namespaces
+                .entry(namespace_name(database, retention_policy))
+                .or_insert_with(|| (database.to_owned(), retention_policy.map(ToOwned::to_owned)));
+        };
+
+        if from.is_empty() {
+            if let Some((database, retention_policy)) = on {
+                insert(database, retention_policy);
+            }
+        }
+
+        for name in from.iter_mut() {
+            let database = name.database.take();
+            let retention_policy = name.retention_policy.take();
+            let retention_policy = retention_policy.as_deref().map(String::as_str);
+            match (database, on) {
+                (Some(database), _) => insert(&database, retention_policy),
+                (None, Some((database, on_retention_policy))) => {
+                    insert(database, retention_policy.or(on_retention_policy))
+                }
+                (None, None) => {
+                    if let Some(retention_policy) = retention_policy {
+                        check_retention_policy_without_database(retention_policy)?;
+                    }
+                    query_namespace = true;
+                }
+            }
+        }
+
+        if namespaces.len() + usize::from(query_namespace) > 1 {
+            return error::not_implemented("SHOW statement for measurements of multiple databases");
+        }
+
+        let Some((database, retention_policy)) = namespaces.into_values().next() else {
+            return Ok(None);
+        };
+
+        match self.s.namespace(&database, retention_policy.as_deref()) {
+            Some(s) => Ok(Some(Self {
+                s,
+                iox_ctx: self.iox_ctx,
+            })),
+            None => error::query(format!("database not found: {database}")),
+        }
+    }
+
     /// Expand tables from `FROM` clause in metadata queries.
     fn expand_show_from_clause(&self, from: Option<ShowFromClause>) -> Result<Vec<String>> {
         match from {
@@ -2282,12 +2917,6 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                 let all_tables = self.s.table_names().into_iter().collect::<HashSet<_>>();
                 let mut out = HashSet::new();
                 for qualified_name in &*from {
-                    if qualified_name.database.is_some() {
-                        return error::not_implemented("database name in from clause");
-                    }
-                    if qualified_name.retention_policy.is_some() {
-                        return error::not_implemented("retention policy in from clause");
-                    }
                     match &qualified_name.name {
                         MeasurementName::Name(name) => {
                             let name = name.as_str();
@@ -2318,18 +2947,6 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         with_measurement: Option<WithMeasurementClause>,
     ) -> Result<Vec<String>> {
         match with_measurement {
-            Some(
-                WithMeasurementClause::Equals(qualified_name)
-                | WithMeasurementClause::Regex(qualified_name),
-            ) if qualified_name.database.is_some() => {
-                error::not_implemented("database name in from clause")
-            }
-            Some(
-                WithMeasurementClause::Equals(qualified_name)
-                | WithMeasurementClause::Regex(qualified_name),
-            ) if qualified_name.retention_policy.is_some() => {
-                error::not_implemented("retention policy in from clause")
-            }
             Some(WithMeasurementClause::Equals(qualified_name)) => match qualified_name.name {
                 MeasurementName::Name(n) => {
                     let names = self.s.table_names();
@@ -2370,10 +2987,18 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         }
     }
 
-    fn show_tag_keys_to_plan(&self, show_tag_keys: ShowTagKeysStatement) -> Result<LogicalPlan> {
-        if show_tag_keys.database.is_some() {
-            // How do we handle this? Do we need to perform cross-namespace queries here?
-            return error::not_implemented("SHOW TAG KEYS ON <database>");
+    fn show_tag_keys_to_plan(
+        &self,
+        mut show_tag_keys: ShowTagKeysStatement,
+    ) -> Result<LogicalPlan> {
+        let database = show_tag_keys.database.take();
+        if let Some(planner) = self.show_database_planner(
+            database
+                .as_deref()
+                .map(|database| (database.as_str(), None)),
+            show_tag_keys.from.as_deref_mut().unwrap_or_default(),
+        )? {
+            return planner.show_tag_keys_to_plan(show_tag_keys);
         }
 
         let tag_key_col = "tagKey";
@@ -2392,94 +3017,13 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             Some(condition) => {
                 debug!("`SHOW TAG KEYS` w/ WHERE-clause, use data scan plan",);
 
-                let condition = Some(condition);
-                let metadata_cutoff = self.metadata_cutoff();
-
-                let mut union_plan = None;
-                for table in tables {
-                    let Some(table_schema) = self.s.table_schema(&table) else {
-                        continue;
-                    };
-                    let Some((plan, measurement_expr)) = self.create_table_ref(&table)? else {
-                        continue;
-                    };
-
-                    let ds = DataSource::Table(table.clone());
-                    let schema = IQLSchema::new_from_ds_schema(plan.schema(), ds.schema(self.s)?)?;
-                    let plan =
-                        self.plan_where_clause(plan, &condition, metadata_cutoff, &schema)?;
-
-                    let tags = table_schema
-                        .iter()
-                        .filter(|(t, _f)| matches!(t, InfluxColumnType::Tag))
-                        .map(|(_t, f)| f.name().as_str())
-                        .collect::<Vec<_>>();
-
-                    // We want to find all tag columns that had non-null values and create a row for each of them. SQL
-                    // (and DataFusion) don't have a real pivot/transpose operation, but we can work around this by
-                    // using some `make_array`+`unnest` trickery.
-                    let tag_key_df_col = Column::from_name(tag_key_col);
-                    let tag_key_col_expr = Expr::Column(tag_key_df_col.clone());
-                    let plan = LogicalPlanBuilder::from(plan)
-                        // aggregate `SUM(tag IS NOT NULL)` for all tags in one go
-                        //
-                        // we have a single row afterwards because the group expression is empty.
-                        .aggregate(
-                            [] as [Expr; 0],
-                            tags.iter().map(|tag| {
-                                let tag_col = Expr::Column(Column::from_name(*tag));
-
-                                sum(cast(tag_col.is_not_null(), DataType::UInt64)).alias(*tag)
-                            }),
-                        )?
-                        // create array of tag names, where every name is:
-                        // - null if it had no non-null values
-                        // - not null if it had any non-null values
-                        //
-                        // note that since we only have a single row, this is efficient
-                        .project([Expr::ScalarFunction(ScalarFunction {
-                            fun: BuiltinScalarFunction::MakeArray,
-                            args: tags
-                                .iter()
-                                .map(|tag| {
-                                    let tag_col = Expr::Column(Column::from_name(*tag));
-
-                                    when(tag_col.gt(lit(0)), lit(*tag)).end()
-                                })
-                                .collect::<Result<Vec<_>, _>>()?,
-                        })
-                        .alias(tag_key_col)])?
-                        // roll our single array row into one row per tag key
-                        .unnest_column(tag_key_df_col)?
-                        // filter out tags that had no none-null values
-                        .filter(tag_key_col_expr.clone().is_not_null())?
-                        // build proper output
-                        .project(measurement_expr.into_iter().chain([tag_key_col_expr]))?
-                        .build()?;
-
-                    union_plan = match union_plan {
-                        Some(union_plan) => {
-                            Some(LogicalPlanBuilder::from(union_plan).union(plan)?.build()?)
-                        }
-                        None => Some(plan),
-                    };
-                }
-
-                let plan = match union_plan {
-                    Some(plan) => plan,
-                    None => LogicalPlan::EmptyRelation(EmptyRelation {
-                        produce_one_row: false,
-                        schema: output_schema.to_dfschema_ref()?,
-                    }),
-                };
-
-                LogicalPlanBuilder::from(plan)
-                    .sort([
-                        Expr::Column(Column::new_unqualified(INFLUXQL_MEASUREMENT_COLUMN_NAME))
-                            .sort(true, false),
-                        Expr::Column(Column::new_unqualified(tag_key_col)).sort(true, false),
-                    ])?
-                    .build()?
+                self.non_null_keys_plan(
+                    tables,
+                    condition,
+                    |t| matches!(t, InfluxColumnType::Tag),
+                    tag_key_col,
+                    &output_schema,
+                )?
             }
             None => {
                 debug!("`SHOW TAG KEYS` w/o WHERE-clause, use cheap metadata scan",);
@@ -2540,13 +3084,118 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         Ok(plan)
     }
 
+    /// Plan a scan of the data in `tables` that matches `condition`, producing
+    /// a row with the measurement name and the column name, in `key_col`, for
+    /// every column selected by `is_key` that has any non-null values.
+    fn non_null_keys_plan(
+        &self,
+        tables: Vec<String>,
+        condition: WhereClause,
+        is_key: impl Fn(InfluxColumnType) -> bool,
+        key_col: &str,
+        output_schema: &Arc<ArrowSchema>,
+    ) -> Result<LogicalPlan> {
+        let condition = Some(condition);
+        let metadata_cutoff = self.metadata_cutoff();
+
+        let mut union_plan = None;
+        for table in tables {
+            let Some(table_schema) = self.s.table_schema(&table) else {
+                continue;
+            };
+            let Some((plan, measurement_expr)) = self.create_table_ref(&table)? else {
+                continue;
+            };
+
+            let ds = DataSource::Table(table.clone());
+            let schema = IQLSchema::new_from_ds_schema(plan.schema(), ds.schema(self.s)?)?;
+            let plan = self.plan_where_clause(plan, &condition, metadata_cutoff, &schema)?;
+
+            let keys = table_schema
+                .iter()
+                .filter(|(t, _f)| is_key(*t))
+                .map(|(_t, f)| f.name().as_str())
+                .collect::<Vec<_>>();
+
+            // We want to find all key columns that had non-null values and create a row for each of them. SQL
+            // (and DataFusion) don't have a real pivot/transpose operation, but we can work around this by
+            // using some `make_array`+`unnest` trickery.
+            let key_df_col = Column::from_name(key_col);
+            let key_col_expr = Expr::Column(key_df_col.clone());
+            let plan = LogicalPlanBuilder::from(plan)
+                // aggregate `SUM(key IS NOT NULL)` for all keys in one go
+                //
+                // we have a single row afterwards because the group expression is empty.
+                .aggregate(
+                    [] as [Expr; 0],
+                    keys.iter().map(|key| {
+                        let key_col = Expr::Column(Column::from_name(*key));
+
+                        sum(cast(key_col.is_not_null(), DataType::UInt64)).alias(*key)
+                    }),
+                )?
+                // create array of key names, where every name is:
+                // - null if it had no non-null values
+                // - not null if it had any non-null values
+                //
+                // note that since we only have a single row, this is efficient
+                .project([Expr::ScalarFunction(ScalarFunction {
+                    fun: BuiltinScalarFunction::MakeArray,
+                    args: keys
+                        .iter()
+                        .map(|key| {
+                            let key_col = Expr::Column(Column::from_name(*key));
+
+                            when(key_col.gt(lit(0)), lit(*key)).end()
+                        })
+                        .collect::<Result<Vec<_>, _>>()?,
+                })
+                .alias(key_col)])?
+                // roll our single array row into one row per key
+                .unnest_column(key_df_col)?
+                // filter out keys that had no none-null values
+                .filter(key_col_expr.clone().is_not_null())?
+                // build proper output
+                .project(measurement_expr.into_iter().chain([key_col_expr]))?
+                .build()?;
+
+            union_plan = match union_plan {
+                Some(union_plan) => {
+                    Some(LogicalPlanBuilder::from(union_plan).union(plan)?.build()?)
+                }
+                None => Some(plan),
+            };
+        }
+
+        let plan = match union_plan {
+            Some(plan) => plan,
+            None => LogicalPlan::EmptyRelation(EmptyRelation {
+                produce_one_row: false,
+                schema: output_schema.to_dfschema_ref()?,
+            }),
+        };
+
+        LogicalPlanBuilder::from(plan)
+            .sort([
+                Expr::Column(Column::new_unqualified(INFLUXQL_MEASUREMENT_COLUMN_NAME))
+                    .sort(true, false),
+                Expr::Column(Column::new_unqualified(key_col)).sort(true, false),
+            ])?
+            .build()
+    }
+
     fn show_field_keys_to_plan(
         &self,
-        show_field_keys: ShowFieldKeysStatement,
+        mut show_field_keys: ShowFieldKeysStatement,
     ) -> Result<LogicalPlan> {
-        if show_field_keys.database.is_some() {
-            // How do we handle this? Do we need to perform cross-namespace queries here?
-            return error::not_implemented("SHOW FIELD KEYS ON <database>");
+        let database = show_field_keys.database.take();
+        if let Some(planner) = self.show_database_planner(
+            database
+                .as_deref()
+                .map(|database| (database.as_str(), None)),
+            show_field_keys.from.as_deref_mut().unwrap_or_default(),
+        )? {
+            return planner.show_field_keys_to_plan(show_field_keys);
         }
 
         let field_key_col = "fieldKey";
@@ -2622,11 +3271,16 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
 
     fn show_tag_values_to_plan(
         &self,
-        show_tag_values: ShowTagValuesStatement,
+        mut show_tag_values: ShowTagValuesStatement,
     ) -> Result<LogicalPlan> {
-        if show_tag_values.database.is_some() {
-            // How do we handle this? Do we need to perform cross-namespace queries here?
-            return error::not_implemented("SHOW TAG VALUES ON <database>");
+        let database = show_tag_values.database.take();
+        if let Some(planner) = self.show_database_planner(
+            database
+                .as_deref()
+                .map(|database| (database.as_str(), None)),
+            show_tag_values.from.as_deref_mut().unwrap_or_default(),
+        )? {
+            return planner.show_tag_values_to_plan(show_tag_values);
         }
 
         let key_col = "key";
@@ -2728,11 +3382,39 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
 
     fn show_measurements_to_plan(
         &self,
-        show_measurements: ShowMeasurementsStatement,
+        mut show_measurements: ShowMeasurementsStatement,
     ) -> Result<LogicalPlan> {
-        if show_measurements.on.is_some() {
-            // How do we handle this? Do we need to perform cross-namespace queries here?
-            return error::not_implemented("SHOW MEASUREMENTS ON <database>");
+        let on = match show_measurements.on.take() {
+            None => None,
+            Some(ExtendedOnClause::Database(database)) => Some((database, None)),
+            Some(ExtendedOnClause::DatabaseRetentionPolicy(database, retention_policy)) => {
+                Some((database, Some(retention_policy)))
+            }
+            // Not supported, as other namespaces are resolved, and the
+            // caller authorized to read them, by name only, so the
+            // namespaces the caller may read cannot be listed.
+            Some(ExtendedOnClause::AllDatabases)
+            | Some(ExtendedOnClause::AllDatabasesAndRetentionPolicies) => {
+                return error::not_implemented("SHOW MEASUREMENTS ON *");
+            }
+        };
+        let from = match &mut show_measurements.with_measurement {
+            Some(
+                WithMeasurementClause::Equals(qualified_name)
+                | WithMeasurementClause::Regex(qualified_name),
+            ) => std::slice::from_mut(qualified_name),
+            None => &mut [],
+        };
+        if let Some(planner) = self.show_database_planner(
+            on.as_ref().map(|(database, retention_policy)| {
+                (
+                    database.as_str(),
+                    retention_policy.as_deref().map(String::as_str),
+                )
+            }),
+            from,
+        )? {
+            return planner.show_measurements_to_plan(show_measurements);
         }
 
         let tables = self.expand_with_measurement_clause(show_measurements.with_measurement)?;
@@ -2801,12 +3483,241 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             None => {
                 debug!("`SHOW MEASUREMENTS` w/o WHERE-clause, use cheap metadata scan",);
 
-                let mut dummy_measurement_names_builder =
-                    StringDictionaryBuilder::<Int32Type>::new();
-                let mut name_builder = StringDictionaryBuilder::<Int32Type>::new();
+                let mut dummy_measurement_names_builder =
+                    StringDictionaryBuilder::<Int32Type>::new();
+                let mut name_builder = StringDictionaryBuilder::<Int32Type>::new();
+                for table in tables {
+                    dummy_measurement_names_builder.append_value(dummy_measurement_name);
+                    name_builder.append_value(table);
+                }
+                LogicalPlanBuilder::scan(
+                    "measurements",
+                    provider_as_source(Arc::new(MemTable::try_new(
+                        Arc::clone(&output_schema),
+                        vec![vec![RecordBatch::try_new(
+                            Arc::clone(&output_schema),
+                            vec![
+                                Arc::new(dummy_measurement_names_builder.finish()),
+                                Arc::new(name_builder.finish()),
+                            ],
+                        )?]],
+                    )?)),
+                    None,
+                )?
+                .build()?
+            }
+        };
+
+        let plan = plan_with_metadata(
+            plan,
+            &InfluxQlMetadata {
+                measurement_column_index: MEASUREMENT_COLUMN_INDEX,
+                tag_key_columns: vec![],
+            },
+        )?;
+        let plan = self.limit(
+            plan,
+            show_measurements.offset,
+            show_measurements.limit,
+            vec![Expr::Column(Column::new_unqualified(name_col)).sort(true, false)],
+            true,
+            &[],
+            &[],
+        )?;
+
+        Ok(plan)
+    }
+
+    /// Generate a logical plan that produces the series keys of the
+    /// measurements in the `from` clause, which have data matching the
+    /// `condition`.
+    ///
+    /// The plan produces the measurement column and a `key` column, where
+    /// each key is the measurement name followed by the sorted tag key and
+    /// value pairs of the series, such as `cpu,host=a,region=b`.
+    fn series_key_plan(
+        &self,
+        from: Option<ShowFromClause>,
+        condition: &Option<WhereClause>,
+    ) -> Result<LogicalPlan> {
+        let key_col = "key";
+        let output_schema = Arc::new(ArrowSchema::new(vec![
+            ArrowField::new(
+                INFLUXQL_MEASUREMENT_COLUMN_NAME,
+                (&InfluxColumnType::Tag).into(),
+                false,
+            ),
+            ArrowField::new(key_col, DataType::Utf8, false),
+        ]));
+
+        let tables = self.expand_show_from_clause(from)?;
+        let metadata_cutoff = self.metadata_cutoff();
+
+        let mut union_plan = None;
+        for table in tables {
+            let Some(table_schema) = self.s.table_schema(&table) else {
+                continue;
+            };
+            let Some((plan, measurement_expr)) = self.create_table_ref(&table)? else {
+                continue;
+            };
+
+            let ds = DataSource::Table(table.clone());
+            let schema = IQLSchema::new_from_ds_schema(plan.schema(), ds.schema(self.s)?)?;
+            let plan = self.plan_where_clause(plan, condition, metadata_cutoff, &schema)?;
+
+            // `concat` ignores NULL arguments, so tags without a value for a
+            // row are omitted from the key.
+            let tag_exprs = table_schema
+                .tags_iter()
+                .map(|f| f.name().as_str())
+                .sorted()
+                .map(|tag| {
+                    let tag_col = Expr::Column(Column::from_name(tag));
+                    when(
+                        tag_col.clone().is_not_null(),
+                        concat(&[lit(format!(",{tag}=")), cast(tag_col, DataType::Utf8)]),
+                    )
+                    .end()
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let key_expr = concat(
+                &iter::once(lit(table.as_str()))
+                    .chain(tag_exprs)
+                    .collect_vec(),
+            );
+
+            let plan = LogicalPlanBuilder::from(plan)
+                .project([key_expr.alias(key_col)])?
+                .distinct()?
+                .project(
+                    measurement_expr
+                        .into_iter()
+                        .chain([Expr::Column(Column::from_name(key_col))]),
+                )?
+                .build()?;
+
+            union_plan = match union_plan {
+                Some(union_plan) => {
+                    Some(LogicalPlanBuilder::from(union_plan).union(plan)?.build()?)
+                }
+                None => Some(plan),
+            };
+        }
+
+        Ok(match union_plan {
+            Some(plan) => plan,
+            None => LogicalPlan::EmptyRelation(EmptyRelation {
+                produce_one_row: false,
+                schema: output_schema.to_dfschema_ref()?,
+            }),
+        })
+    }
+
+    fn show_series_to_plan(&self, mut show_series: ShowSeriesStatement) -> Result<LogicalPlan> {
+        let database = show_series.database.take();
+        if let Some(planner) = self.show_database_planner(
+            database
+                .as_deref()
+                .map(|database| (database.as_str(), None)),
+            show_series.from.as_deref_mut().unwrap_or_default(),
+        )? {
+            return planner.show_series_to_plan(show_series);
+        }
+
+        let key_col = "key";
+        let plan = self.series_key_plan(show_series.from, &show_series.condition)?;
+
+        // The series of all measurements are returned as a single
+        // result, without a measurement name.
+        let plan = LogicalPlanBuilder::from(plan)
+            .project([
+                lit_dict("").alias(INFLUXQL_MEASUREMENT_COLUMN_NAME),
+                Expr::Column(Column::new_unqualified(key_col)),
+            ])?
+            .sort([Expr::Column(Column::new_unqualified(key_col)).sort(true, false)])?
+            .build()?;
+        let plan = plan_with_metadata(
+            plan,
+            &InfluxQlMetadata {
+                measurement_column_index: MEASUREMENT_COLUMN_INDEX,
+                tag_key_columns: vec![],
+            },
+        )?;
+        let plan = self.limit(
+            plan,
+            show_series.offset,
+            show_series.limit,
+            vec![Expr::Column(Column::new_unqualified(key_col)).sort(true, false)],
+            true,
+            &[],
+            &[],
+        )?;
+
+        Ok(plan)
+    }
+
+    /// Generate a logical plan that produces the measurement column for
+    /// each measurement in the `from` clause, which has data matching the
+    /// `condition`.
+    fn measurement_names_plan(
+        &self,
+        from: Option<ShowFromClause>,
+        condition: Option<WhereClause>,
+    ) -> Result<LogicalPlan> {
+        let output_schema = Arc::new(ArrowSchema::new(vec![ArrowField::new(
+            INFLUXQL_MEASUREMENT_COLUMN_NAME,
+            (&InfluxColumnType::Tag).into(),
+            false,
+        )]));
+
+        let tables = self.expand_show_from_clause(from)?;
+
+        Ok(match condition {
+            Some(condition) => {
+                debug!("`SHOW MEASUREMENT CARDINALITY` w/ WHERE-clause, use data scan plan",);
+
+                let condition = Some(condition);
+                let metadata_cutoff = self.metadata_cutoff();
+
+                let mut union_plan = None;
+                for table in tables {
+                    let Some((plan, measurement_expr)) = self.create_table_ref(&table)? else {
+                        continue;
+                    };
+
+                    let ds = DataSource::Table(table.clone());
+                    let schema = IQLSchema::new_from_ds_schema(plan.schema(), ds.schema(self.s)?)?;
+                    let plan =
+                        self.plan_where_clause(plan, &condition, metadata_cutoff, &schema)?;
+
+                    let plan = LogicalPlanBuilder::from(plan)
+                        .limit(0, Some(1))?
+                        .project(measurement_expr)?
+                        .build()?;
+
+                    union_plan = match union_plan {
+                        Some(union_plan) => {
+                            Some(LogicalPlanBuilder::from(union_plan).union(plan)?.build()?)
+                        }
+                        None => Some(plan),
+                    };
+                }
+
+                match union_plan {
+                    Some(plan) => plan,
+                    None => LogicalPlan::EmptyRelation(EmptyRelation {
+                        produce_one_row: false,
+                        schema: output_schema.to_dfschema_ref()?,
+                    }),
+                }
+            }
+            None => {
+                debug!("`SHOW MEASUREMENT CARDINALITY` w/o WHERE-clause, use cheap metadata scan",);
+
+                let mut measurement_names_builder = StringDictionaryBuilder::<Int32Type>::new();
                 for table in tables {
-                    dummy_measurement_names_builder.append_value(dummy_measurement_name);
-                    name_builder.append_value(table);
+                    measurement_names_builder.append_value(table);
                 }
                 LogicalPlanBuilder::scan(
                     "measurements",
@@ -2814,16 +3725,137 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                         Arc::clone(&output_schema),
                         vec![vec![RecordBatch::try_new(
                             Arc::clone(&output_schema),
-                            vec![
-                                Arc::new(dummy_measurement_names_builder.finish()),
-                                Arc::new(name_builder.finish()),
-                            ],
+                            vec![Arc::new(measurement_names_builder.finish())],
                         )?]],
                     )?)),
                     None,
                 )?
                 .build()?
             }
+        })
+    }
+
+    /// Plan the `SHOW ... CARDINALITY` statements, by counting the rows
+    /// produced by the plan of the equivalent `SHOW` statement.
+    fn show_cardinality_to_plan(
+        &self,
+        mut show_cardinality: ShowCardinalityStatement,
+    ) -> Result<LogicalPlan> {
+        let database = show_cardinality.database.take();
+        if let Some(planner) = self.show_database_planner(
+            database
+                .as_deref()
+                .map(|database| (database.as_str(), None)),
+            show_cardinality.from.as_deref_mut().unwrap_or_default(),
+        )? {
+            return planner.show_cardinality_to_plan(show_cardinality);
+        }
+
+        let ShowCardinalityStatement {
+            kind,
+            exact,
+            database: _,
+            from,
+            condition,
+            limit,
+            offset,
+        } = show_cardinality;
+
+        // The number of measurements, and the estimated number of series, are
+        // returned as a single value for all measurements, whereas all other
+        // kinds are counted for each measurement.
+        let per_measurement = match kind {
+            CardinalityKind::Measurement => false,
+            CardinalityKind::Series => exact,
+            _ => true,
+        };
+
+        let (plan, value_col) = match kind {
+            CardinalityKind::Series => (self.series_key_plan(from, &condition)?, "key"),
+            CardinalityKind::Measurement => (
+                self.measurement_names_plan(from, condition)?,
+                INFLUXQL_MEASUREMENT_COLUMN_NAME,
+            ),
+            CardinalityKind::TagKey => (
+                self.show_tag_keys_to_plan(ShowTagKeysStatement {
+                    database: None,
+                    from,
+                    condition,
+                    limit: None,
+                    offset: None,
+                })?,
+                "tagKey",
+            ),
+            CardinalityKind::TagValues(with_key) => (
+                self.show_tag_values_to_plan(ShowTagValuesStatement {
+                    database: None,
+                    from,
+                    with_key,
+                    condition,
+                    limit: None,
+                    offset: None,
+                })?,
+                "value",
+            ),
+            CardinalityKind::FieldKey => match condition {
+                // Only the fields with values in the rows that match the
+                // condition are counted, so the data must be scanned.
+                Some(condition) => {
+                    let output_schema = Arc::new(ArrowSchema::new(vec![
+                        ArrowField::new(
+                            INFLUXQL_MEASUREMENT_COLUMN_NAME,
+                            (&InfluxColumnType::Tag).into(),
+                            false,
+                        ),
+                        ArrowField::new("fieldKey", DataType::Utf8, false),
+                    ]));
+                    (
+                        self.non_null_keys_plan(
+                            self.expand_show_from_clause(from)?,
+                            condition,
+                            |t| matches!(t, InfluxColumnType::Field(_)),
+                            "fieldKey",
+                            &output_schema,
+                        )?,
+                        "fieldKey",
+                    )
+                }
+                None => (
+                    self.show_field_keys_to_plan(ShowFieldKeysStatement {
+                        database: None,
+                        from,
+                        limit: None,
+                        offset: None,
+                    })?,
+                    "fieldKey",
+                ),
+            },
+        };
+
+        let value_expr = Expr::Column(Column::new_unqualified(value_col));
+        let measurement_expr =
+            Expr::Column(Column::new_unqualified(INFLUXQL_MEASUREMENT_COLUMN_NAME));
+        let plan = if per_measurement {
+            LogicalPlanBuilder::from(plan)
+                .aggregate(
+                    [measurement_expr.clone()],
+                    [count(value_expr).alias("count")],
+                )?
+                .sort([measurement_expr.sort(true, false)])?
+                .build()?
+        } else {
+            let count_col = if exact {
+                "count"
+            } else {
+                "cardinality estimation"
+            };
+            LogicalPlanBuilder::from(plan)
+                .aggregate([] as [Expr; 0], [count(value_expr).alias(count_col)])?
+                .project([
+                    lit_dict("").alias(INFLUXQL_MEASUREMENT_COLUMN_NAME),
+                    Expr::Column(Column::new_unqualified(count_col)),
+                ])?
+                .build()?
         };
 
         let plan = plan_with_metadata(
@@ -2833,15 +3865,7 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                 tag_key_columns: vec![],
             },
         )?;
-        let plan = self.limit(
-            plan,
-            show_measurements.offset,
-            show_measurements.limit,
-            vec![Expr::Column(Column::new_unqualified(name_col)).sort(true, false)],
-            true,
-            &[],
-            &[],
-        )?;
+        let plan = self.limit(plan, offset, limit, vec![], false, &[], &[])?;
 
         Ok(plan)
     }
@@ -2852,9 +3876,10 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         &self,
         show_retention_policies: ShowRetentionPoliciesStatement,
     ) -> Result<LogicalPlan> {
-        if show_retention_policies.database.is_some() {
-            // This syntax is not yet handled.
-            return error::not_implemented("SHOW RETENTION POLICIES ON <database>");
+        if let Some(database) = &show_retention_policies.database {
+            if self.s.namespace(database, None).is_none() {
+                return error::query(format!("database not found: {database}"));
+            }
         }
 
         let output_schema = Arc::new(ArrowSchema::new(vec![
@@ -2937,10 +3962,13 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
 /// * `input` - An aggregate plan which requires gap-filling.
 /// * `time_column` - The `date_bin` expression.
 /// * `fill_strategy` - The strategy used to fill gaps in the data.
+/// * `upper_extension` - The duration, in nanoseconds, to extend the upper
+///   bound of the time range, to produce intervals for forecast values.
 fn build_gap_fill_node(
     input: LogicalPlan,
     time_column: &Expr,
     fill_strategy: FillStrategy,
+    upper_extension: i64,
 ) -> Result<LogicalPlan> {
     let (expr, alias) = match time_column {
         Expr::Alias(Alias { expr, name: alias }) => (expr.as_ref(), alias),
@@ -3000,6 +4028,32 @@ fn build_gap_fill_node(
                     .ok_or_else(|| error::map::internal("expected to find a Filter or TableScan"))
             }?;
 
+            let time_range = if upper_extension > 0 {
+                let extend = |expr: Expr| match expr {
+                    Expr::Literal(ScalarValue::TimestampNanosecond(Some(v), tz)) => {
+                        lit(ScalarValue::TimestampNanosecond(
+                            Some(v.saturating_add(upper_extension)),
+                            tz,
+                        ))
+                    }
+                    expr => binary_expr(
+                        expr,
+                        Operator::Plus,
+                        lit(ScalarValue::new_interval_mdn(0, 0, upper_extension)),
+                    ),
+                };
+                Range {
+                    start: time_range.start,
+                    end: match time_range.end {
+                        Bound::Included(expr) => Bound::Included(extend(expr)),
+                        Bound::Excluded(expr) => Bound::Excluded(extend(expr)),
+                        Bound::Unbounded => Bound::Unbounded,
+                    },
+                }
+            } else {
+                time_range
+            };
+
             let origin = (nargs == 3).then_some(date_bin_args[2].clone());
 
             (date_bin_args[0].clone(), time_range, origin)
@@ -3180,6 +4234,83 @@ fn is_aggregate_field(f: &Field) -> bool {
     .is_break()
 }
 
+/// Returns the maximum number of values forecast by the `HOLT_WINTERS`
+/// and `HOLT_WINTERS_WITH_FIT` functions in `fields`, or `0` if the
+/// functions are not used.
+fn holt_winters_forecast_count(fields: &[Field]) -> i64 {
+    let mut count = 0;
+    for f in fields {
+        let _ = walk_expr(&f.expr, &mut |e| {
+            if let IQLExpr::Call(Call { name, args }) = e {
+                if let (
+                    "holt_winters" | "holt_winters_with_fit",
+                    Some(IQLExpr::Literal(Literal::Integer(n))),
+                ) = (name.as_str(), args.get(1))
+                {
+                    count = count.max(*n);
+                }
+            }
+            ControlFlow::<()>::Continue(())
+        });
+    }
+    count
+}
+
+/// Returns the columns of the aggregate `plan` that are not `GROUP BY`
+/// columns, which are all `NULL` for the rows produced by the GapFill
+/// operator.
+fn aggregate_columns(plan: &LogicalPlan, group_by_tag_set: &[&str]) -> Vec<Expr> {
+    plan.schema()
+        .fields()
+        .iter()
+        .map(|f| f.name())
+        .filter(|name| *name != "time" && !group_by_tag_set.contains(&name.as_str()))
+        .map(|name| Expr::Column(Column::new_unqualified(name)))
+        .collect()
+}
+
+/// Remove the rows of `plan` that the GapFill operator produced for the
+/// `HOLT_WINTERS` functions of `fields`, but which hold no forecast value.
+///
+/// When values are forecast, the GapFill operator extends the time range
+/// of the query by the intervals of the forecast values, and fills the gaps
+/// within the time range, so the model is fitted to evenly spaced values,
+/// regardless of the `FILL` option. The rows after the time range of the
+/// query, and for `FILL(none)` the rows where `aggr_columns` are all `NULL`,
+/// are only kept if they hold a forecast value.
+fn filter_forecast_rows(
+    ctx: &Context<'_>,
+    fields: &[Field],
+    plan: LogicalPlan,
+    aggr_columns: &[Expr],
+    select_exprs: &[Expr],
+) -> Result<LogicalPlan> {
+    if ctx.interval.is_none() || holt_winters_forecast_count(fields) == 0 {
+        return Ok(plan);
+    }
+
+    let forecast = fields
+        .iter()
+        .zip(select_exprs)
+        .filter(|(f, _)| holt_winters_forecast_count(std::slice::from_ref(f)) > 0)
+        .map(|(_, expr)| match expr {
+            Expr::Alias(Alias { expr, .. }) => expr.as_ref().clone().is_not_null(),
+            expr => expr.clone().is_not_null(),
+        });
+    let keep = match ctx.fill() {
+        FillClause::None => disjunction(aggr_columns.iter().map(|c| c.clone().is_not_null())),
+        _ => Some(match ctx.time_range.upper {
+            Some(upper) => "time".as_expr().lt_eq(lit_timestamp_nano(upper)),
+            None => "time".as_expr().lt(now()),
+        }),
+    };
+
+    match disjunction(keep.into_iter().chain(forecast)) {
+        Some(expr) => LogicalPlanBuilder::from(plan).filter(expr)?.build(),
+        None => Ok(plan),
+    }
+}
+
 /// A utility function that checks whether `f` is an aggregate field
 /// that should be filled with a 0 rather than an NULL.
 fn is_zero_filled_aggregate_field(f: &Field) -> bool {
@@ -3364,6 +4495,17 @@ mod test {
                 .build()
                 .unwrap(),
         ]);
+        sp.add_namespace(
+            "telegraf",
+            None,
+            vec![SchemaBuilder::new()
+                .measurement("mem")
+                .timestamp()
+                .tag("host")
+                .influx_field("used", InfluxFieldType::Float)
+                .build()
+                .unwrap()],
+        );
 
         let iox_ctx = IOxSessionContext::with_testing();
         let planner = InfluxQLToLogicalPlan::new(&sp, &iox_ctx);
@@ -3461,11 +4603,54 @@ mod test {
     #[test]
     fn test_unsupported_statements() {
         assert_snapshot!(plan("CREATE DATABASE foo"), @"This feature is not implemented: CREATE DATABASE");
-        assert_snapshot!(plan("DELETE FROM foo"), @"This feature is not implemented: DELETE");
-        assert_snapshot!(plan("DROP MEASUREMENT foo"), @"This feature is not implemented: DROP MEASUREMENT");
         assert_snapshot!(plan("SHOW DATABASES"), @"This feature is not implemented: SHOW DATABASES");
     }
 
+    fn deletes(sql: &str) -> String {
+        let mut statements = parse_statements(sql).unwrap();
+        let sp = MockSchemaProvider::default();
+        let iox_ctx = IOxSessionContext::with_testing();
+        let planner = InfluxQLToLogicalPlan::new(&sp, &iox_ctx);
+
+        match planner.statement_to_deletes(statements.pop().unwrap()) {
+            Ok(deletes) => deletes
+                .iter()
+                .map(|(table, predicate)| {
+                    let range = format!(
+                        "{table}: [{}, {})",
+                        predicate.range.start(),
+                        predicate.range.end()
+                    );
+                    match predicate.expr_sql_string() {
+                        exprs if exprs.is_empty() => range,
+                        exprs => format!("{range} {exprs}"),
+                    }
+                })
+                .join("\n"),
+            Err(err) => err.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_statement_to_deletes() {
+        assert_snapshot!(deletes("DROP MEASUREMENT cpu"), @"cpu: [-9223372036854775806, 9223372036854775807)");
+        assert_snapshot!(deletes("DROP MEASUREMENT non_existent"), @"");
+        assert_snapshot!(deletes("DELETE FROM cpu WHERE host = 'a' AND time < 1000"), @r###"cpu: [-9223372036854775806, 1000) "host"='a'"###);
+        assert_snapshot!(deletes("DELETE FROM /^disk/ WHERE time >= 10 AND time <= 20"), @r###"
+        disk: [10, 21)
+        diskio: [10, 21)
+        "###);
+        assert_snapshot!(deletes("DROP SERIES FROM cpu, disk WHERE region != 'west'"), @r###"
+        cpu: [-9223372036854775806, 9223372036854775807) "region"!='west'
+        disk: [-9223372036854775806, 9223372036854775807) "region"!='west'
+        "###);
+
+        // Fallible cases
+        assert_snapshot!(deletes("DELETE FROM cpu WHERE usage_idle = 'a'"), @"Error during planning: fields not supported in WHERE clause during deletion");
+        assert_snapshot!(deletes("DROP SERIES WHERE host = 'a' OR host = 'b'"), @"This feature is not implemented: unsupported expression in WHERE clause during deletion: host = 'a' OR host = 'b'");
+        assert_snapshot!(plan("DROP SERIES FROM foo"), @"External error: InfluxQL internal error: statement must be planned using statement_to_deletes: DROP SERIES FROM foo");
+    }
+
     mod metadata_queries {
         use super::*;
 
@@ -3484,6 +4669,10 @@ mod test {
         #[test]
         fn test_snow_measurements() {
             assert_snapshot!(plan("SHOW MEASUREMENTS"), @"TableScan: measurements [iox::measurement:Dictionary(Int32, Utf8), name:Dictionary(Int32, Utf8)]");
+            assert_snapshot!(plan("SHOW MEASUREMENTS ON telegraf"), @"TableScan: measurements [iox::measurement:Dictionary(Int32, Utf8), name:Dictionary(Int32, Utf8)]");
+            assert_snapshot!(plan("SHOW MEASUREMENTS ON my_db"), @"Error during planning: database not found: my_db");
+            assert_snapshot!(plan("SHOW MEASUREMENTS ON *"), @"This feature is not implemented: SHOW MEASUREMENTS ON *");
+            assert_snapshot!(plan("SHOW MEASUREMENTS ON *.*"), @"This feature is not implemented: SHOW MEASUREMENTS ON *");
             assert_snapshot!(plan("SHOW MEASUREMENTS LIMIT 1 OFFSET 2"), @r###"
             Sort: measurements.iox::measurement ASC NULLS LAST, measurements.name ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), name:Dictionary(Int32, Utf8)]
               Projection: measurements.iox::measurement, measurements.name [iox::measurement:Dictionary(Int32, Utf8), name:Dictionary(Int32, Utf8)]
@@ -3601,6 +4790,16 @@ mod test {
             "###);
         }
 
+        #[test]
+        fn test_show_tag_keys_database() {
+            assert_snapshot!(plan("SHOW TAG KEYS ON telegraf"), @"TableScan: tag_keys [iox::measurement:Dictionary(Int32, Utf8), tagKey:Dictionary(Int32, Utf8)]");
+            assert_snapshot!(plan("SHOW TAG KEYS FROM telegraf..mem"), @"TableScan: tag_keys [iox::measurement:Dictionary(Int32, Utf8), tagKey:Dictionary(Int32, Utf8)]");
+
+            // Fallible cases
+            assert_snapshot!(plan("SHOW TAG KEYS FROM telegraf..mem, data"), @"This feature is not implemented: SHOW statement for measurements of multiple databases");
+            assert_snapshot!(plan("SHOW TAG KEYS ON my_db FROM cpu"), @"Error during planning: database not found: my_db");
+        }
+
         #[test]
         fn test_show_tag_keys_2() {
             assert_snapshot!(plan("SHOW TAG KEYS WHERE foo = 'some_foo'"), @r###"
@@ -3828,9 +5027,93 @@ mod test {
             assert_snapshot!(plan("SHOW RETENTION POLICIES"), @r###"
             TableScan: retention policies [iox::measurement:Dictionary(Int32, Utf8), name:Utf8, duration:Utf8, shardGroupDuration:Utf8, replicaN:Int64, default:Boolean]
             "###);
-            assert_snapshot!(plan("SHOW RETENTION POLICIES ON my_db"), @r###"
-            This feature is not implemented: SHOW RETENTION POLICIES ON <database>
+            assert_snapshot!(plan("SHOW RETENTION POLICIES ON telegraf"), @r###"
+            TableScan: retention policies [iox::measurement:Dictionary(Int32, Utf8), name:Utf8, duration:Utf8, shardGroupDuration:Utf8, replicaN:Int64, default:Boolean]
+            "###);
+            assert_snapshot!(plan("SHOW RETENTION POLICIES ON my_db"), @"Error during planning: database not found: my_db");
+        }
+
+        /// Returns the names of the output columns of the plan for `sql`.
+        fn column_names(sql: &str) -> Vec<String> {
+            logical_plan(sql)
+                .unwrap()
+                .schema()
+                .fields()
+                .iter()
+                .map(|f| f.name().clone())
+                .collect()
+        }
+
+        #[test]
+        fn test_show_series() {
+            assert_eq!(column_names("SHOW SERIES"), ["iox::measurement", "key"]);
+            assert_eq!(
+                column_names("SHOW SERIES FROM data WHERE foo = 'some_foo' LIMIT 1 OFFSET 2"),
+                ["iox::measurement", "key"]
+            );
+            assert_eq!(
+                column_names("SHOW SERIES FROM non_existent"),
+                ["iox::measurement", "key"]
+            );
+            assert_eq!(
+                column_names("SHOW SERIES ON telegraf FROM mem"),
+                ["iox::measurement", "key"]
+            );
+            assert_snapshot!(plan("SHOW SERIES ON my_db"), @"Error during planning: database not found: my_db");
+        }
+
+        #[test]
+        fn test_show_cardinality() {
+            assert_eq!(
+                column_names("SHOW SERIES CARDINALITY"),
+                ["iox::measurement", "cardinality estimation"]
+            );
+            assert_eq!(
+                column_names("SHOW SERIES EXACT CARDINALITY WHERE time > 1337"),
+                ["iox::measurement", "count"]
+            );
+            assert_eq!(
+                column_names("SHOW MEASUREMENT CARDINALITY"),
+                ["iox::measurement", "cardinality estimation"]
+            );
+            assert_eq!(
+                column_names("SHOW MEASUREMENT EXACT CARDINALITY WHERE foo = 'some_foo'"),
+                ["iox::measurement", "count"]
+            );
+            assert_eq!(
+                column_names("SHOW TAG KEY CARDINALITY FROM data"),
+                ["iox::measurement", "count"]
+            );
+            assert_eq!(
+                column_names("SHOW TAG VALUES EXACT CARDINALITY WITH KEY = bar LIMIT 1"),
+                ["iox::measurement", "count"]
+            );
+            assert_eq!(
+                column_names("SHOW FIELD KEY CARDINALITY"),
+                ["iox::measurement", "count"]
+            );
+
+            // Only the fields with values in the rows matching the condition are counted
+            assert_snapshot!(plan("SHOW FIELD KEY CARDINALITY FROM cpu WHERE cpu = 'cpu0'"), @r###"
+            Sort: iox::measurement ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), count:Int64;N]
+              Aggregate: groupBy=[[iox::measurement]], aggr=[[COUNT(fieldKey) AS count]] [iox::measurement:Dictionary(Int32, Utf8), count:Int64;N]
+                Sort: iox::measurement ASC NULLS LAST, fieldKey ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), fieldKey:Utf8;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, fieldKey [iox::measurement:Dictionary(Int32, Utf8), fieldKey:Utf8;N]
+                    Filter: fieldKey IS NOT NULL [fieldKey:Utf8;N]
+                      Unnest: fieldKey [fieldKey:Utf8;N]
+                        Projection: make_array(CASE WHEN usage_idle > Int32(0) THEN Utf8("usage_idle") END, CASE WHEN usage_system > Int32(0) THEN Utf8("usage_system") END, CASE WHEN usage_user > Int32(0) THEN Utf8("usage_user") END) AS fieldKey [fieldKey:List(Field { name: "item", data_type: Utf8, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} });N]
+                          Aggregate: groupBy=[[]], aggr=[[SUM(CAST(cpu.usage_idle IS NOT NULL AS UInt64)) AS usage_idle, SUM(CAST(cpu.usage_system IS NOT NULL AS UInt64)) AS usage_system, SUM(CAST(cpu.usage_user IS NOT NULL AS UInt64)) AS usage_user]] [usage_idle:UInt64;N, usage_system:UInt64;N, usage_user:UInt64;N]
+                            Filter: cpu.time >= TimestampNanosecond(1672444800000000000, None) AND cpu.cpu = Dictionary(Int32, Utf8("cpu0")) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                              TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
             "###);
+            assert_eq!(
+                column_names("SHOW FIELD KEY EXACT CARDINALITY WHERE foo = 'some_foo'"),
+                ["iox::measurement", "count"]
+            );
+
+            // Fallible cases
+            assert_snapshot!(plan("SHOW SERIES CARDINALITY ON my_db"), @"Error during planning: database not found: my_db");
+            assert_snapshot!(plan("SHOW TAG VALUES CARDINALITY ON my_db WITH KEY = bar"), @"Error during planning: database not found: my_db");
         }
     }
 
@@ -4086,6 +5369,133 @@ mod test {
                 assert_snapshot!(plan("SELECT MOVING_AVERAGE(MEAN(usage_idle), usage_system) FROM cpu GROUP BY TIME(10s)"), @"Error during planning: expected integer argument in moving_average()");
             }
 
+            #[test]
+            fn test_exponential_moving_average() {
+                // no aggregates, where the hold period and warmup type are the defaults
+                assert_snapshot!(plan("SELECT EXPONENTIAL_MOVING_AVERAGE(usage_idle, 3) FROM cpu"), @r###"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), exponential_moving_average:Float64;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, exponential_moving_average [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), exponential_moving_average:Float64;N]
+                    Filter: NOT exponential_moving_average IS NULL [time:Timestamp(Nanosecond, None), exponential_moving_average:Float64;N]
+                      Projection: cpu.time AS time, exponential_moving_average(cpu.usage_idle,Int64(3),Int64(-1),Utf8("exponential")) AS exponential_moving_average [time:Timestamp(Nanosecond, None), exponential_moving_average:Float64;N]
+                        WindowAggr: windowExpr=[[exponential_moving_average(cpu.usage_idle, Int64(3), Int64(-1), Utf8("exponential")) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS exponential_moving_average(cpu.usage_idle,Int64(3),Int64(-1),Utf8("exponential"))]] [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, exponential_moving_average(cpu.usage_idle,Int64(3),Int64(-1),Utf8("exponential")):Float64;N]
+                          TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "###);
+
+                // aggregate
+                assert_snapshot!(plan("SELECT EXPONENTIAL_MOVING_AVERAGE(MEAN(usage_idle), 3, 2, 'simple') FROM cpu GROUP BY TIME(10s)"), @r###"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, exponential_moving_average:Float64;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, exponential_moving_average [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, exponential_moving_average:Float64;N]
+                    Filter: NOT exponential_moving_average IS NULL [time:Timestamp(Nanosecond, None);N, exponential_moving_average:Float64;N]
+                      Projection: time, exponential_moving_average(AVG(cpu.usage_idle),Int64(3),Int64(2),Utf8("simple")) AS exponential_moving_average [time:Timestamp(Nanosecond, None);N, exponential_moving_average:Float64;N]
+                        WindowAggr: windowExpr=[[exponential_moving_average(AVG(cpu.usage_idle), Int64(3), Int64(2), Utf8("simple")) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS exponential_moving_average(AVG(cpu.usage_idle),Int64(3),Int64(2),Utf8("simple"))]] [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N, exponential_moving_average(AVG(cpu.usage_idle),Int64(3),Int64(2),Utf8("simple")):Float64;N]
+                          GapFill: groupBy=[time], aggr=[[AVG(cpu.usage_idle)]], time_column=time, stride=IntervalMonthDayNano("10000000000"), range=Unbounded..Included(Literal(TimestampNanosecond(1672531200000000000, None))) [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N]
+                            Aggregate: groupBy=[[date_bin(IntervalMonthDayNano("10000000000"), cpu.time, TimestampNanosecond(0, None)) AS time]], aggr=[[AVG(cpu.usage_idle)]] [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N]
+                              Filter: cpu.time <= TimestampNanosecond(1672531200000000000, None) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                                TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "###);
+
+                // Invariant: second argument is always a constant
+                assert_snapshot!(plan("SELECT EXPONENTIAL_MOVING_AVERAGE(MEAN(usage_idle), usage_system) FROM cpu GROUP BY TIME(10s)"), @"Error during planning: expected integer argument in exponential_moving_average()");
+            }
+
+            #[test]
+            fn test_kaufmans_adaptive_moving_average() {
+                // no aggregates, where there is no warmup type argument
+                assert_snapshot!(plan("SELECT KAUFMANS_ADAPTIVE_MOVING_AVERAGE(usage_idle, 10) FROM cpu"), @r###"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), kaufmans_adaptive_moving_average:Float64;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, kaufmans_adaptive_moving_average [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), kaufmans_adaptive_moving_average:Float64;N]
+                    Filter: NOT kaufmans_adaptive_moving_average IS NULL [time:Timestamp(Nanosecond, None), kaufmans_adaptive_moving_average:Float64;N]
+                      Projection: cpu.time AS time, kaufmans_adaptive_moving_average(cpu.usage_idle,Int64(10),Int64(-1)) AS kaufmans_adaptive_moving_average [time:Timestamp(Nanosecond, None), kaufmans_adaptive_moving_average:Float64;N]
+                        WindowAggr: windowExpr=[[kaufmans_adaptive_moving_average(cpu.usage_idle, Int64(10), Int64(-1)) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS kaufmans_adaptive_moving_average(cpu.usage_idle,Int64(10),Int64(-1))]] [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, kaufmans_adaptive_moving_average(cpu.usage_idle,Int64(10),Int64(-1)):Float64;N]
+                          TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "###);
+            }
+
+            #[test]
+            fn test_chande_momentum_oscillator() {
+                // no aggregates, where the default warmup type is none
+                assert_snapshot!(plan("SELECT CHANDE_MOMENTUM_OSCILLATOR(usage_idle, 10) FROM cpu"), @r###"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), chande_momentum_oscillator:Float64;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, chande_momentum_oscillator [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), chande_momentum_oscillator:Float64;N]
+                    Filter: NOT chande_momentum_oscillator IS NULL [time:Timestamp(Nanosecond, None), chande_momentum_oscillator:Float64;N]
+                      Projection: cpu.time AS time, chande_momentum_oscillator(cpu.usage_idle,Int64(10),Int64(-1),Utf8("none")) AS chande_momentum_oscillator [time:Timestamp(Nanosecond, None), chande_momentum_oscillator:Float64;N]
+                        WindowAggr: windowExpr=[[chande_momentum_oscillator(cpu.usage_idle, Int64(10), Int64(-1), Utf8("none")) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS chande_momentum_oscillator(cpu.usage_idle,Int64(10),Int64(-1),Utf8("none"))]] [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, chande_momentum_oscillator(cpu.usage_idle,Int64(10),Int64(-1),Utf8("none")):Float64;N]
+                          TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "###);
+
+                // aggregate
+                assert_snapshot!(plan("SELECT CHANDE_MOMENTUM_OSCILLATOR(MEAN(usage_idle), 10, 0, 'exponential') FROM cpu GROUP BY TIME(10s)"), @r###"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, chande_momentum_oscillator:Float64;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, chande_momentum_oscillator [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, chande_momentum_oscillator:Float64;N]
+                    Filter: NOT chande_momentum_oscillator IS NULL [time:Timestamp(Nanosecond, None);N, chande_momentum_oscillator:Float64;N]
+                      Projection: time, chande_momentum_oscillator(AVG(cpu.usage_idle),Int64(10),Int64(0),Utf8("exponential")) AS chande_momentum_oscillator [time:Timestamp(Nanosecond, None);N, chande_momentum_oscillator:Float64;N]
+                        WindowAggr: windowExpr=[[chande_momentum_oscillator(AVG(cpu.usage_idle), Int64(10), Int64(0), Utf8("exponential")) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS chande_momentum_oscillator(AVG(cpu.usage_idle),Int64(10),Int64(0),Utf8("exponential"))]] [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N, chande_momentum_oscillator(AVG(cpu.usage_idle),Int64(10),Int64(0),Utf8("exponential")):Float64;N]
+                          GapFill: groupBy=[time], aggr=[[AVG(cpu.usage_idle)]], time_column=time, stride=IntervalMonthDayNano("10000000000"), range=Unbounded..Included(Literal(TimestampNanosecond(1672531200000000000, None))) [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N]
+                            Aggregate: groupBy=[[date_bin(IntervalMonthDayNano("10000000000"), cpu.time, TimestampNanosecond(0, None)) AS time]], aggr=[[AVG(cpu.usage_idle)]] [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N]
+                              Filter: cpu.time <= TimestampNanosecond(1672531200000000000, None) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                                TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "###);
+            }
+
+            #[test]
+            fn test_holt_winters() {
+                // the GapFill range is extended by N intervals for the forecast values
+                assert_snapshot!(plan("SELECT HOLT_WINTERS(MEAN(usage_idle), 3, 2) FROM cpu GROUP BY TIME(10s)"), @r###"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, holt_winters:Float64;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, holt_winters [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, holt_winters:Float64;N]
+                    Filter: NOT holt_winters IS NULL [time:Timestamp(Nanosecond, None);N, holt_winters:Float64;N]
+                      Projection: time, holt_winters(AVG(cpu.usage_idle),Int64(3),Int64(2)) AS holt_winters [time:Timestamp(Nanosecond, None);N, holt_winters:Float64;N]
+                        Filter: time <= TimestampNanosecond(1672531200000000000, None) OR holt_winters(AVG(cpu.usage_idle),Int64(3),Int64(2)) IS NOT NULL [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N, holt_winters(AVG(cpu.usage_idle),Int64(3),Int64(2)):Float64;N]
+                          WindowAggr: windowExpr=[[holt_winters(AVG(cpu.usage_idle), Int64(3), Int64(2), TimestampNanosecond(1672531200000000000, None), time) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS holt_winters(AVG(cpu.usage_idle),Int64(3),Int64(2))]] [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N, holt_winters(AVG(cpu.usage_idle),Int64(3),Int64(2)):Float64;N]
+                            GapFill: groupBy=[time], aggr=[[AVG(cpu.usage_idle)]], time_column=time, stride=IntervalMonthDayNano("10000000000"), range=Unbounded..Included(Literal(TimestampNanosecond(1672531230000000000, None))) [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N]
+                              Aggregate: groupBy=[[date_bin(IntervalMonthDayNano("10000000000"), cpu.time, TimestampNanosecond(0, None)) AS time]], aggr=[[AVG(cpu.usage_idle)]] [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N]
+                                Filter: cpu.time <= TimestampNanosecond(1672531200000000000, None) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                                  TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "###);
+
+                // FILL(none) only fills the gaps for the model, and the filled rows are removed
+                assert_snapshot!(plan("SELECT HOLT_WINTERS(MEAN(usage_idle), 3, 2), MEAN(usage_idle) FROM cpu GROUP BY TIME(10s) FILL(none)"), @r###"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, holt_winters:Float64;N, mean:Float64;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, holt_winters, mean [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, holt_winters:Float64;N, mean:Float64;N]
+                    Projection: time, holt_winters(AVG(cpu.usage_idle),Int64(3),Int64(2)) AS holt_winters, AVG(cpu.usage_idle) AS mean [time:Timestamp(Nanosecond, None);N, holt_winters:Float64;N, mean:Float64;N]
+                      Filter: AVG(cpu.usage_idle) IS NOT NULL OR holt_winters(AVG(cpu.usage_idle),Int64(3),Int64(2)) IS NOT NULL [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N, holt_winters(AVG(cpu.usage_idle),Int64(3),Int64(2)):Float64;N]
+                        WindowAggr: windowExpr=[[holt_winters(AVG(cpu.usage_idle), Int64(3), Int64(2), TimestampNanosecond(1672531200000000000, None), time) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS holt_winters(AVG(cpu.usage_idle),Int64(3),Int64(2))]] [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N, holt_winters(AVG(cpu.usage_idle),Int64(3),Int64(2)):Float64;N]
+                          GapFill: groupBy=[time], aggr=[[AVG(cpu.usage_idle)]], time_column=time, stride=IntervalMonthDayNano("10000000000"), range=Unbounded..Included(Literal(TimestampNanosecond(1672531230000000000, None))) [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N]
+                            Aggregate: groupBy=[[date_bin(IntervalMonthDayNano("10000000000"), cpu.time, TimestampNanosecond(0, None)) AS time]], aggr=[[AVG(cpu.usage_idle)]] [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N]
+                              Filter: cpu.time <= TimestampNanosecond(1672531200000000000, None) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                                TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "###);
+
+                // Invariant: second argument is always a constant
+                assert_snapshot!(plan("SELECT HOLT_WINTERS(MEAN(usage_idle), usage_system, 2) FROM cpu GROUP BY TIME(10s)"), @"Error during planning: expected integer argument in holt_winters()");
+            }
+
+            #[test]
+            fn test_elapsed() {
+                // no aggregates
+                assert_snapshot!(plan("SELECT ELAPSED(usage_idle) FROM cpu"), @r###"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), elapsed:Int64;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, elapsed [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), elapsed:Int64;N]
+                    Filter: NOT elapsed IS NULL [time:Timestamp(Nanosecond, None), elapsed:Int64;N]
+                      Projection: cpu.time AS time, elapsed(cpu.usage_idle) AS elapsed [time:Timestamp(Nanosecond, None), elapsed:Int64;N]
+                        WindowAggr: windowExpr=[[elapsed(cpu.usage_idle, IntervalMonthDayNano("1"), cpu.time) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS elapsed(cpu.usage_idle)]] [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, elapsed(cpu.usage_idle):Int64;N]
+                          TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "###);
+
+                // aggregate, where the default unit is not the interval
+                assert_snapshot!(plan("SELECT ELAPSED(MEAN(usage_idle)) FROM cpu GROUP BY TIME(10s)"), @r###"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, elapsed:Int64;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, elapsed [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, elapsed:Int64;N]
+                    Filter: NOT elapsed IS NULL [time:Timestamp(Nanosecond, None);N, elapsed:Int64;N]
+                      Projection: time, elapsed(AVG(cpu.usage_idle)) AS elapsed [time:Timestamp(Nanosecond, None);N, elapsed:Int64;N]
+                        WindowAggr: windowExpr=[[elapsed(AVG(cpu.usage_idle), IntervalMonthDayNano("1"), time) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS elapsed(AVG(cpu.usage_idle))]] [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N, elapsed(AVG(cpu.usage_idle)):Int64;N]
+                          GapFill: groupBy=[time], aggr=[[AVG(cpu.usage_idle)]], time_column=time, stride=IntervalMonthDayNano("10000000000"), range=Unbounded..Included(Literal(TimestampNanosecond(1672531200000000000, None))) [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N]
+                            Aggregate: groupBy=[[date_bin(IntervalMonthDayNano("10000000000"), cpu.time, TimestampNanosecond(0, None)) AS time]], aggr=[[AVG(cpu.usage_idle)]] [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N]
+                              Filter: cpu.time <= TimestampNanosecond(1672531200000000000, None) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                                TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "###);
+            }
+
             #[test]
             fn test_derivative() {
                 // no aggregates
@@ -4178,8 +5588,30 @@ mod test {
             }
 
             #[test]
-            fn test_not_implemented() {
-                assert_snapshot!(plan("SELECT DIFFERENCE(MEAN(usage_idle)), MEAN(usage_idle) FROM cpu GROUP BY TIME(10s)"), @"This feature is not implemented: mixed window-aggregate and aggregate columns, such as DIFFERENCE(MEAN(col)), MEAN(col)");
+            fn test_mixed_aggregate() {
+                assert_snapshot!(plan("SELECT DIFFERENCE(MEAN(usage_idle)), MEAN(usage_idle) FROM cpu GROUP BY TIME(10s)"), @r###"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, difference:Float64;N, mean:Float64;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, difference, mean [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, difference:Float64;N, mean:Float64;N]
+                    Projection: time, difference(AVG(cpu.usage_idle)) AS difference, AVG(cpu.usage_idle) AS mean [time:Timestamp(Nanosecond, None);N, difference:Float64;N, mean:Float64;N]
+                      WindowAggr: windowExpr=[[difference(AVG(cpu.usage_idle)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS difference(AVG(cpu.usage_idle))]] [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N, difference(AVG(cpu.usage_idle)):Float64;N]
+                        GapFill: groupBy=[time], aggr=[[AVG(cpu.usage_idle)]], time_column=time, stride=IntervalMonthDayNano("10000000000"), range=Unbounded..Included(Literal(TimestampNanosecond(1672531200000000000, None))) [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N]
+                          Aggregate: groupBy=[[date_bin(IntervalMonthDayNano("10000000000"), cpu.time, TimestampNanosecond(0, None)) AS time]], aggr=[[AVG(cpu.usage_idle)]] [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N]
+                            Filter: cpu.time <= TimestampNanosecond(1672531200000000000, None) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                              TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "###);
+
+                // The additional window read for the DIFFERENCE function is excluded from the results
+                assert_snapshot!(plan("SELECT DIFFERENCE(MEAN(usage_idle)), MEAN(usage_idle) FROM cpu WHERE time >= '2022-10-31T02:00:00Z' GROUP BY TIME(10s)"), @r###"
+                Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, difference:Float64;N, mean:Float64;N]
+                  Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, difference, mean [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, difference:Float64;N, mean:Float64;N]
+                    Filter: time >= TimestampNanosecond(1667181600000000000, None) [time:Timestamp(Nanosecond, None);N, difference:Float64;N, mean:Float64;N]
+                      Projection: time, difference(AVG(cpu.usage_idle)) AS difference, AVG(cpu.usage_idle) AS mean [time:Timestamp(Nanosecond, None);N, difference:Float64;N, mean:Float64;N]
+                        WindowAggr: windowExpr=[[difference(AVG(cpu.usage_idle)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS difference(AVG(cpu.usage_idle))]] [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N, difference(AVG(cpu.usage_idle)):Float64;N]
+                          GapFill: groupBy=[time], aggr=[[AVG(cpu.usage_idle)]], time_column=time, stride=IntervalMonthDayNano("10000000000"), range=Included(Literal(TimestampNanosecond(1667181590000000000, None)))..Included(Literal(TimestampNanosecond(1672531200000000000, None))) [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N]
+                            Aggregate: groupBy=[[date_bin(IntervalMonthDayNano("10000000000"), cpu.time, TimestampNanosecond(0, None)) AS time]], aggr=[[AVG(cpu.usage_idle)]] [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N]
+                              Filter: cpu.time >= TimestampNanosecond(1667181590000000000, None) AND cpu.time <= TimestampNanosecond(1672531200000000000, None) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                                TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                "###);
             }
         }
 
@@ -4362,6 +5794,101 @@ mod test {
             }
         }
 
+        #[test]
+        fn test_sample() {
+            assert_snapshot!(plan("SELECT sample(usage_idle, 2), usage_system FROM cpu"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), sample:Float64;N, usage_system:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.usage_idle AS sample, cpu.usage_system AS usage_system [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), sample:Float64;N, usage_system:Float64;N]
+                Filter: sample_row(Int64(2)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, sample_row(Int64(2)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING:Boolean;N]
+                  WindowAggr: windowExpr=[[sample_row(Int64(2)) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS sample_row(Int64(2)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING]] [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, sample_row(Int64(2)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING:Boolean;N]
+                    Filter: cpu.usage_idle IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                      TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            assert_snapshot!(plan("SELECT sample(usage_idle, 2) FROM cpu GROUP BY cpu"), @r###"
+            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, sample:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.cpu AS cpu, cpu.usage_idle AS sample [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, sample:Float64;N]
+                Filter: sample_row(Int64(2)) PARTITION BY [cpu] ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, sample_row(Int64(2)) PARTITION BY [cpu] ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING:Boolean;N]
+                  WindowAggr: windowExpr=[[sample_row(Int64(2)) PARTITION BY [cpu.cpu] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS sample_row(Int64(2)) PARTITION BY [cpu] ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING]] [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, sample_row(Int64(2)) PARTITION BY [cpu] ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING:Boolean;N]
+                    Filter: cpu.usage_idle IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                      TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // SAMPLE cannot be combined with other functions
+            assert_snapshot!(plan("SELECT sample(usage_idle, 2), mean(usage_system) FROM cpu"), @"This feature is not implemented: sample combined with other functions");
+        }
+
+        #[test]
+        fn test_integral() {
+            // defaults to a unit of 1s
+            assert_snapshot!(plan("SELECT integral(usage_idle) FROM cpu"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), integral:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, TimestampNanosecond(0, None) AS time, integral(cpu.usage_idle,cpu.time,IntervalMonthDayNano("1000000000")) AS integral [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), integral:Float64;N]
+                Aggregate: groupBy=[[]], aggr=[[integral(cpu.usage_idle, cpu.time, IntervalMonthDayNano("1000000000"))]] [integral(cpu.usage_idle,cpu.time,IntervalMonthDayNano("1000000000")):Float64;N]
+                  TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // when grouping by time, the area of each row is summed
+            assert_snapshot!(plan("SELECT integral(usage_idle, 1m) FROM cpu GROUP BY TIME(10s) FILL(none)"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, integral:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, SUM(integral(usage_idle,time,IntervalMonthDayNano("60000000000"))) AS integral [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, integral:Float64;N]
+                Aggregate: groupBy=[[date_bin(IntervalMonthDayNano("10000000000"), cpu.time, TimestampNanosecond(0, None)) AS time]], aggr=[[SUM(integral(usage_idle,time,IntervalMonthDayNano("60000000000")))]] [time:Timestamp(Nanosecond, None);N, SUM(integral(usage_idle,time,IntervalMonthDayNano("60000000000"))):Float64;N]
+                  WindowAggr: windowExpr=[[integral_window(cpu.usage_idle, cpu.time, IntervalMonthDayNano("60000000000"), IntervalMonthDayNano("10000000000"), TimestampNanosecond(0, None)) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS integral(usage_idle,time,IntervalMonthDayNano("60000000000"))]] [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, integral(usage_idle,time,IntervalMonthDayNano("60000000000")):Float64;N]
+                    Filter: cpu.time <= TimestampNanosecond(1672531200000000000, None) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                      TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // and each series is a separate partition of the window function
+            assert_snapshot!(plan("SELECT integral(usage_idle) FROM cpu GROUP BY TIME(10s), cpu FILL(none)"), @r###"
+            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, cpu:Dictionary(Int32, Utf8);N, integral:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, cpu.cpu AS cpu, SUM(integral(usage_idle,time,IntervalMonthDayNano("1000000000"))) AS integral [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, cpu:Dictionary(Int32, Utf8);N, integral:Float64;N]
+                Aggregate: groupBy=[[date_bin(IntervalMonthDayNano("10000000000"), cpu.time, TimestampNanosecond(0, None)) AS time, cpu.cpu]], aggr=[[SUM(integral(usage_idle,time,IntervalMonthDayNano("1000000000")))]] [time:Timestamp(Nanosecond, None);N, cpu:Dictionary(Int32, Utf8);N, SUM(integral(usage_idle,time,IntervalMonthDayNano("1000000000"))):Float64;N]
+                  WindowAggr: windowExpr=[[integral_window(cpu.usage_idle, cpu.time, IntervalMonthDayNano("1000000000"), IntervalMonthDayNano("10000000000"), TimestampNanosecond(0, None)) PARTITION BY [cpu.cpu] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS integral(usage_idle,time,IntervalMonthDayNano("1000000000"))]] [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, integral(usage_idle,time,IntervalMonthDayNano("1000000000")):Float64;N]
+                    Filter: cpu.time <= TimestampNanosecond(1672531200000000000, None) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                      TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+        }
+
+        #[test]
+        fn test_spread_mode() {
+            assert_snapshot!(plan("SELECT spread(usage_idle) FROM cpu GROUP BY TIME(10s) FILL(none)"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, spread:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, spread(cpu.usage_idle) AS spread [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, spread:Float64;N]
+                Aggregate: groupBy=[[date_bin(IntervalMonthDayNano("10000000000"), cpu.time, TimestampNanosecond(0, None)) AS time]], aggr=[[spread(cpu.usage_idle)]] [time:Timestamp(Nanosecond, None);N, spread(cpu.usage_idle):Float64;N]
+                  Filter: cpu.time <= TimestampNanosecond(1672531200000000000, None) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                    TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            assert_snapshot!(plan("SELECT mode(usage_idle) FROM cpu GROUP BY TIME(10s) FILL(previous)"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, mode:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, mode(cpu.usage_idle,cpu.time) AS mode [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, mode:Float64;N]
+                GapFill: groupBy=[time], aggr=[[LOCF(mode(cpu.usage_idle,cpu.time))]], time_column=time, stride=IntervalMonthDayNano("10000000000"), range=Unbounded..Included(Literal(TimestampNanosecond(1672531200000000000, None))) [time:Timestamp(Nanosecond, None);N, mode(cpu.usage_idle,cpu.time):Float64;N]
+                  Aggregate: groupBy=[[date_bin(IntervalMonthDayNano("10000000000"), cpu.time, TimestampNanosecond(0, None)) AS time]], aggr=[[mode(cpu.usage_idle, cpu.time)]] [time:Timestamp(Nanosecond, None);N, mode(cpu.usage_idle,cpu.time):Float64;N]
+                    Filter: cpu.time <= TimestampNanosecond(1672531200000000000, None) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                      TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+        }
+
+        #[test]
+        fn test_hll() {
+            assert_snapshot!(plan("SELECT sum_hll(usage_idle) FROM cpu GROUP BY TIME(10s) FILL(none)"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, sum_hll:Utf8;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, sum_hll(cpu.usage_idle) AS sum_hll [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, sum_hll:Utf8;N]
+                Aggregate: groupBy=[[date_bin(IntervalMonthDayNano("10000000000"), cpu.time, TimestampNanosecond(0, None)) AS time]], aggr=[[sum_hll(cpu.usage_idle)]] [time:Timestamp(Nanosecond, None);N, sum_hll(cpu.usage_idle):Utf8;N]
+                  Filter: cpu.time <= TimestampNanosecond(1672531200000000000, None) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                    TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // The nested call is planned as a single aggregate
+            assert_snapshot!(plan("SELECT count_hll(sum_hll(usage_idle)) FROM cpu GROUP BY TIME(10s) FILL(none)"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, count_hll:UInt64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, approx_distinct_hll(cpu.usage_idle) AS count_hll [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, count_hll:UInt64;N]
+                Aggregate: groupBy=[[date_bin(IntervalMonthDayNano("10000000000"), cpu.time, TimestampNanosecond(0, None)) AS time]], aggr=[[approx_distinct_hll(cpu.usage_idle)]] [time:Timestamp(Nanosecond, None);N, approx_distinct_hll(cpu.usage_idle):UInt64;N]
+                  Filter: cpu.time <= TimestampNanosecond(1672531200000000000, None) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                    TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+        }
+
         #[test]
         fn test_percentile() {
             assert_snapshot!(plan("SELECT percentile(usage_idle,50),usage_system FROM cpu"), @r###"
@@ -5311,6 +6838,24 @@ mod test {
             "###); // TIME is a field
         }
 
+        /// Select data from a measurement of another database
+        #[test]
+        fn test_qualified_measurement() {
+            assert_snapshot!(plan("SELECT used FROM telegraf..mem"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), used:Float64;N]
+              Projection: Dictionary(Int32, Utf8("mem")) AS iox::measurement, telegraf..mem.time AS time, telegraf..mem.used AS used [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), used:Float64;N]
+                TableScan: telegraf..mem [host:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), used:Float64;N]
+            "###);
+            assert_snapshot!(plan("SELECT used FROM telegraf.autogen./^m/"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), used:Float64;N]
+              Projection: Dictionary(Int32, Utf8("mem")) AS iox::measurement, telegraf..mem.time AS time, telegraf..mem.used AS used [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), used:Float64;N]
+                TableScan: telegraf..mem [host:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), used:Float64;N]
+            "###);
+
+            // Fallible cases
+            assert_snapshot!(plan("SELECT f64_field FROM rp0.data"), @"This feature is not implemented: retention policy in from clause without database name");
+        }
+
         /// Arithmetic expressions in the projection list
         #[test]
         fn test_simple_arithmetic_in_projection() {
@@ -5499,6 +7044,52 @@ mod test {
             "###);
         }
 
+        #[test]
+        fn test_select_group_by_slimit_soffset() {
+            assert_snapshot!(plan("SELECT usage_idle FROM cpu GROUP BY cpu SLIMIT 1"), @r###"
+            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+              Projection: iox::measurement, time, cpu, usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                Filter: iox::series <= Int64(1) [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                  WindowAggr: windowExpr=[[DENSE_RANK() ORDER BY [cpu ASC NULLS LAST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS iox::series]] [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                    Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.cpu AS cpu, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                      TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+            assert_snapshot!(plan("SELECT usage_idle FROM cpu GROUP BY cpu SOFFSET 1"), @r###"
+            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+              Projection: iox::measurement, time, cpu, usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                Filter: iox::series > Int64(1) [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                  WindowAggr: windowExpr=[[DENSE_RANK() ORDER BY [cpu ASC NULLS LAST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS iox::series]] [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                    Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.cpu AS cpu, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                      TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+            assert_snapshot!(plan("SELECT usage_idle FROM cpu GROUP BY cpu SLIMIT 1 SOFFSET 1"), @r###"
+            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+              Projection: iox::measurement, time, cpu, usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                Filter: iox::series BETWEEN Int64(2) AND Int64(2) [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                  WindowAggr: windowExpr=[[DENSE_RANK() ORDER BY [cpu ASC NULLS LAST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS iox::series]] [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                    Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.cpu AS cpu, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                      TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // Without a GROUP BY tag set, each measurement is a single series
+            assert_snapshot!(plan("SELECT usage_idle FROM cpu SOFFSET 1"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+              Limit: skip=0, fetch=0 [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+                Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+                  TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+            assert_snapshot!(plan("SELECT usage_idle FROM cpu SLIMIT 1"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+                TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // returns an error if SLIMIT or SOFFSET values exceed i64::MAX
+            let max = (i64::MAX as u64) + 1;
+            assert_snapshot!(plan(format!("SELECT usage_idle FROM cpu GROUP BY cpu SLIMIT {max}")), @"Error during planning: series limit out of range");
+            assert_snapshot!(plan(format!("SELECT usage_idle FROM cpu GROUP BY cpu SOFFSET {max}")), @"Error during planning: series offset out of range");
+        }
+
         #[test]
         fn test_select_function_tag_column() {
             assert_snapshot!(plan("SELECT last(foo) as foo, first(usage_idle) from cpu group by foo"), @r###"
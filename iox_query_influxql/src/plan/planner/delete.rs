@@ -0,0 +1,243 @@
+//! Translation of the InfluxQL `DELETE`, `DROP MEASUREMENT` and `DROP SERIES`
+//! statements into [`DeletePredicate`]s.
+
+use crate::error;
+use crate::plan::parse_regex;
+use data_types::{DeleteExpr, DeletePredicate, Op, Scalar, TimestampRange, MIN_NANO_TIME};
+use datafusion::common::Result;
+use influxdb_influxql_parser::common::MeasurementName;
+use influxdb_influxql_parser::expression::{
+    ConditionalBinary, ConditionalExpression, ConditionalOperator, Expr as IQLExpr, VarRef,
+};
+use influxdb_influxql_parser::literal::Literal;
+use influxdb_influxql_parser::simple_from_clause::DeleteFromClause;
+use influxdb_influxql_parser::time_range::{split_cond, ReduceContext, TimeRange};
+use schema::{InfluxColumnType, Schema};
+use std::collections::BTreeSet;
+
+/// Return the names of the tables in `tables` matched by the `FROM` clause
+/// of a `DELETE` or `DROP SERIES` statement, in sorted order.
+///
+/// All tables are matched when `from` is `None`.
+pub(super) fn expand_delete_from_clause(
+    tables: &[&str],
+    from: Option<&DeleteFromClause>,
+) -> Result<Vec<String>> {
+    let Some(from) = from else {
+        return Ok(tables
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect());
+    };
+
+    let mut out = BTreeSet::new();
+    for name in from.iter() {
+        match name {
+            MeasurementName::Name(name) => {
+                if tables.contains(&name.as_str()) {
+                    out.insert(name.to_string());
+                }
+            }
+            MeasurementName::Regex(regex) => {
+                let regex = parse_regex(regex)?;
+                out.extend(
+                    tables
+                        .iter()
+                        .filter(|table| regex.is_match(table))
+                        .map(|s| s.to_string()),
+                );
+            }
+        }
+    }
+
+    Ok(out.into_iter().collect())
+}
+
+/// Returns a [`DeletePredicate`] that matches all the rows of a table.
+pub(super) fn delete_all() -> DeletePredicate {
+    DeletePredicate {
+        range: TimestampRange::new(MIN_NANO_TIME, i64::MAX),
+        exprs: vec![],
+    }
+}
+
+/// Translate the optional `condition` of a `DELETE` or `DROP SERIES`
+/// statement into a [`DeletePredicate`].
+///
+/// The time range of `condition` becomes the range of the predicate. The
+/// remainder must be a conjunction of tag comparisons using the `=` or
+/// `!=` operators, which is the subset of InfluxQL supported by delete
+/// predicates.
+pub(super) fn condition_to_delete_predicate(
+    ctx: &ReduceContext,
+    condition: Option<&ConditionalExpression>,
+) -> Result<DeletePredicate> {
+    let Some(condition) = condition else {
+        return Ok(delete_all());
+    };
+
+    let (cond, time_range) = split_cond(ctx, condition).map_err(error::map::expr_error)?;
+
+    let mut exprs = vec![];
+    if let Some(cond) = cond {
+        conjunction_to_delete_exprs(&cond, &mut exprs)?;
+    }
+
+    Ok(DeletePredicate {
+        range: time_range_to_timestamp_range(time_range),
+        exprs,
+    })
+}
+
+/// Validate that the columns referenced by `predicate` are not fields of
+/// the table with the specified `schema`, as InfluxQL only permits
+/// deleting series by their tags.
+pub(super) fn validate_delete_predicate(
+    predicate: &DeletePredicate,
+    schema: &Schema,
+) -> Result<()> {
+    for expr in &predicate.exprs {
+        if let Some((InfluxColumnType::Field(_), _)) = schema.field_by_name(expr.column()) {
+            return error::query("fields not supported in WHERE clause during deletion");
+        }
+    }
+    Ok(())
+}
+
+/// Map the inclusive bounds of `time_range` to a [`TimestampRange`], which
+/// has an exclusive upper bound.
+fn time_range_to_timestamp_range(time_range: TimeRange) -> TimestampRange {
+    TimestampRange::new(
+        time_range.lower.unwrap_or(MIN_NANO_TIME),
+        time_range
+            .upper
+            .map_or(i64::MAX, |upper| upper.saturating_add(1)),
+    )
+}
+
+fn conjunction_to_delete_exprs(
+    cond: &ConditionalExpression,
+    exprs: &mut Vec<DeleteExpr>,
+) -> Result<()> {
+    match cond {
+        ConditionalExpression::Grouped(cond) => conjunction_to_delete_exprs(cond, exprs),
+        ConditionalExpression::Binary(ConditionalBinary {
+            lhs,
+            op: ConditionalOperator::And,
+            rhs,
+        }) => {
+            conjunction_to_delete_exprs(lhs, exprs)?;
+            conjunction_to_delete_exprs(rhs, exprs)
+        }
+        ConditionalExpression::Binary(ConditionalBinary {
+            lhs,
+            op: op @ (ConditionalOperator::Eq | ConditionalOperator::NotEq),
+            rhs,
+        }) => {
+            let op = match op {
+                ConditionalOperator::Eq => Op::Eq,
+                _ => Op::Ne,
+            };
+
+            match (lhs.expr(), rhs.expr()) {
+                (
+                    Some(IQLExpr::VarRef(VarRef { name, .. })),
+                    Some(IQLExpr::Literal(Literal::String(value))),
+                )
+                | (
+                    Some(IQLExpr::Literal(Literal::String(value))),
+                    Some(IQLExpr::VarRef(VarRef { name, .. })),
+                ) => {
+                    exprs.push(DeleteExpr::new(
+                        name.to_string(),
+                        op,
+                        Scalar::String(value.clone()),
+                    ));
+                    Ok(())
+                }
+                _ => error::query(format!(
+                    "expected tag key and string literal in WHERE clause during deletion, got: {cond}"
+                )),
+            }
+        }
+        _ => error::not_implemented(format!(
+            "unsupported expression in WHERE clause during deletion: {cond}"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    fn predicate(cond: &str) -> Result<DeletePredicate> {
+        let ctx = ReduceContext::default();
+        let cond = ConditionalExpression::from_str(cond).unwrap();
+        condition_to_delete_predicate(&ctx, Some(&cond))
+    }
+
+    #[test]
+    fn test_expand_delete_from_clause() {
+        let tables = ["cpu", "disk", "mem"];
+
+        assert_eq!(
+            expand_delete_from_clause(&tables, None).unwrap(),
+            vec!["cpu", "disk", "mem"]
+        );
+
+        let from = DeleteFromClause::new(vec![
+            MeasurementName::Name("mem".into()),
+            MeasurementName::Regex("^c".into()),
+            MeasurementName::Name("missing".into()),
+        ]);
+        assert_eq!(
+            expand_delete_from_clause(&tables, Some(&from)).unwrap(),
+            vec!["cpu", "mem"]
+        );
+    }
+
+    #[test]
+    fn test_condition_to_delete_predicate() {
+        let got = predicate("host = 'a' AND (region != 'west')").unwrap();
+        assert_eq!(got.range, delete_all().range);
+        assert_eq!(
+            got.exprs,
+            vec![
+                DeleteExpr::new("host".into(), Op::Eq, Scalar::String("a".into())),
+                DeleteExpr::new("region".into(), Op::Ne, Scalar::String("west".into())),
+            ]
+        );
+
+        // The upper bound of the InfluxQL time range is inclusive
+        let got = predicate("time >= 10 AND time <= 20 AND host = 'a'").unwrap();
+        assert_eq!(got.range, TimestampRange::new(10, 21));
+        assert_eq!(got.exprs.len(), 1);
+
+        let got = predicate("time < 20").unwrap();
+        assert_eq!(got.range, TimestampRange::new(MIN_NANO_TIME, 20));
+        assert!(got.exprs.is_empty());
+
+        // Fallible cases
+
+        let err = predicate("host = 'a' OR host = 'b'").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "This feature is not implemented: unsupported expression in WHERE clause during deletion: host = 'a' OR host = 'b'"
+        );
+
+        let err = predicate("host =~ /a/").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "This feature is not implemented: unsupported expression in WHERE clause during deletion: host =~ /a/"
+        );
+
+        let err = predicate("usage = 1").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: expected tag key and string literal in WHERE clause during deletion, got: usage = 1"
+        );
+    }
+}
@@ -68,24 +68,6 @@ fn rewrite_select(s: &dyn SchemaProvider, stmt: &SelectStatement) -> Result<Sele
     rw.rewrite(s, stmt)
 }
 
-/// Asserts that the `SELECT` statement does not use any unimplemented features.
-///
-/// The list of unimplemented or unsupported features are listed below.
-///
-/// # `SLIMIT` and `SOFFSET`
-///
-/// * `SLIMIT` and `SOFFSET` don't work as expected per issue [#7571]
-/// * This issue [is noted](https://docs.influxdata.com/influxdb/v1.8/query_language/explore-data/#the-slimit-clause) in our official documentation
-///
-/// [#7571]: https://github.com/influxdata/influxdb/issues/7571
-fn check_features(stmt: &SelectStatement) -> Result<()> {
-    if stmt.series_limit.is_some() || stmt.series_offset.is_some() {
-        return error::not_implemented("SLIMIT or SOFFSET");
-    }
-
-    Ok(())
-}
-
 #[derive(Default)]
 struct RewriteSelect {
     /// The depth of the `SELECT` statement currently processed by the rewriter.
@@ -96,8 +78,6 @@ impl RewriteSelect {
     /// Transform a `SelectStatement` to a `Select`, which is an intermediate representation used by
     /// the InfluxQL planner. Transformations include expanding wildcards.
     fn rewrite(&self, s: &dyn SchemaProvider, stmt: &SelectStatement) -> Result<Select> {
-        check_features(stmt)?;
-
         let from = self.expand_from(s, stmt)?;
         let tag_set = from_tag_set(s, &from);
         let (fields, group_by) = self.expand_projection(s, stmt, &from, &tag_set)?;
@@ -160,6 +140,8 @@ impl RewriteSelect {
             order_by: stmt.order_by,
             limit: stmt.limit,
             offset: stmt.offset,
+            series_limit: stmt.series_limit,
+            series_offset: stmt.series_offset,
             timezone: stmt.timezone.map(|v| *v),
         })
     }
@@ -172,6 +154,10 @@ impl RewriteSelect {
 
     /// Rewrite the `SELECT` statement by applying specific rules for subqueries.
     fn rewrite_subquery(&self, s: &dyn SchemaProvider, stmt: &SelectStatement) -> Result<Select> {
+        if stmt.into.is_some() {
+            return error::query("subqueries cannot have an INTO clause");
+        }
+
         let rw = Self {
             depth: self.depth + 1,
         };
@@ -348,26 +334,63 @@ impl RewriteSelect {
         let mut new_from = Vec::new();
         for ms in &*stmt.from {
             match ms {
-                MeasurementSelection::Name(qmn) => match qmn {
-                    QualifiedMeasurementName {
-                        name: MeasurementName::Name(name),
-                        ..
-                    } => {
-                        if s.table_exists(name) {
-                            new_from.push(DataSource::Table(name.deref().to_owned()))
+                MeasurementSelection::Name(QualifiedMeasurementName {
+                    database: Some(database),
+                    retention_policy,
+                    name,
+                }) => {
+                    // The measurement belongs to another namespace, and is referred to
+                    // by its qualified name.
+                    let retention_policy = retention_policy.as_deref().map(String::as_str);
+                    match name {
+                        MeasurementName::Name(name) => {
+                            let table =
+                                util::qualified_table_name(database, retention_policy, name);
+                            if s.table_exists(&table) {
+                                new_from.push(DataSource::Table(table))
+                            }
+                        }
+                        MeasurementName::Regex(re) => {
+                            let re = util::parse_regex(re)?;
+                            if let Some(ns) = s.namespace(database, retention_policy) {
+                                ns.table_names()
+                                    .into_iter()
+                                    .filter(|table| re.is_match(table))
+                                    .for_each(|table| {
+                                        new_from.push(DataSource::Table(
+                                            util::qualified_table_name(
+                                                database,
+                                                retention_policy,
+                                                table,
+                                            ),
+                                        ))
+                                    });
+                            }
                         }
                     }
-                    QualifiedMeasurementName {
-                        name: MeasurementName::Regex(re),
-                        ..
-                    } => {
-                        let re = util::parse_regex(re)?;
-                        s.table_names()
-                            .into_iter()
-                            .filter(|table| re.is_match(table))
-                            .for_each(|table| new_from.push(DataSource::Table(table.to_owned())));
+                }
+                MeasurementSelection::Name(qmn) => {
+                    if let Some(retention_policy) = &qmn.retention_policy {
+                        util::check_retention_policy_without_database(retention_policy)?;
                     }
-                },
+
+                    match &qmn.name {
+                        MeasurementName::Name(name) => {
+                            if s.table_exists(name) {
+                                new_from.push(DataSource::Table(name.deref().to_owned()))
+                            }
+                        }
+                        MeasurementName::Regex(re) => {
+                            let re = util::parse_regex(re)?;
+                            s.table_names()
+                                .into_iter()
+                                .filter(|table| re.is_match(table))
+                                .for_each(|table| {
+                                    new_from.push(DataSource::Table(table.to_owned()))
+                                });
+                        }
+                    }
+                }
                 MeasurementSelection::Subquery(q) => {
                     new_from.push(DataSource::Subquery(Box::new(self.rewrite_subquery(s, q)?)))
                 }
@@ -724,7 +747,8 @@ fn fields_expand_wildcards(
 
                 // Modify the supported types for certain functions.
                 match name.as_str() {
-                    "count" | "first" | "last" | "distinct" | "elapsed" | "mode" | "sample" => {
+                    "count" | "first" | "last" | "distinct" | "elapsed" | "mode" | "sample"
+                    | "sum_hll" => {
                         supported_types
                             .extend([Some(VarRefDataType::String), Some(VarRefDataType::Boolean)]);
                     }
@@ -947,6 +971,9 @@ struct FieldChecker {
     /// `true` if the projection contains an invocation of the `TOP` or `BOTTOM` function.
     has_top_bottom: bool,
 
+    /// `true` if the projection contains an invocation of the `SAMPLE` function.
+    has_sample: bool,
+
     /// `true` when one or more projections do not contain an aggregate expression.
     has_non_aggregate_fields: bool,
 
@@ -1035,6 +1062,11 @@ impl FieldChecker {
 
         let projection_type = if self.has_top_bottom {
             ProjectionType::TopBottomSelector
+        } else if self.has_sample && self.function_count() == 1 {
+            // SAMPLE returns multiple rows per group, including when grouping by time.
+            ProjectionType::Selector {
+                has_fields: self.has_non_aggregate_fields,
+            }
         } else if self.has_group_by_time {
             if self.window_count > 0 {
                 if self.window_count == self.aggregate_count + self.selector_count {
@@ -1165,7 +1197,7 @@ impl FieldChecker {
             "chande_momentum_oscillator" => self.check_chande_momentum_oscillator(name, &c.args),
             "elapsed" => self.check_elapsed(name, &c.args),
             "integral" => self.check_integral(name, &c.args),
-            "count_hll" => self.check_count_hll(&c.args),
+            "count_hll" => self.check_count_hll(name, &c.args),
             "holt_winters" | "holt_winters_with_fit" => self.check_holt_winters(name, &c.args),
             "max" | "min" | "first" | "last" => {
                 self.inc_selector_count();
@@ -1212,6 +1244,7 @@ impl FieldChecker {
 
     fn check_sample(&mut self, args: &[Expr]) -> Result<()> {
         self.inc_selector_count();
+        self.has_sample = true;
 
         check_exp_args!("sample", 2, args);
         let v = lit_integer!("sample", args, 1);
@@ -1480,19 +1513,23 @@ impl FieldChecker {
         self.check_symbol(name, &args[0])
     }
 
-    fn check_count_hll(&mut self, _args: &[Expr]) -> Result<()> {
+    fn check_count_hll(&mut self, name: &str, args: &[Expr]) -> Result<()> {
         self.inc_aggregate_count();
-        // The count hyperloglog function is not documented for versions 1.8 or the latest 2.7.
-        // If anyone is using it, we'd like to know, so we'll explicitly return a not implemented
-        // message.
-        //
-        // See: https://docs.influxdata.com/influxdb/v2.7/query-data/influxql/functions/
-        // See: https://docs.influxdata.com/influxdb/v1.8/query_language/functions
-        error::not_implemented("count_hll")
+        check_exp_args!(name, 1, args);
+
+        // The argument is either a field of sketches, or a call to sum_hll()
+        // to produce the sketches.
+        match &args[0] {
+            Expr::Call(c) if c.name == "sum_hll" => {
+                check_exp_args!("sum_hll", 1, c.args);
+                self.check_symbol("sum_hll", &c.args[0])
+            }
+            expr => self.check_symbol(name, expr),
+        }
     }
 
     fn check_holt_winters(&mut self, name: &str, args: &[Expr]) -> Result<()> {
-        self.inc_aggregate_count();
+        self.inc_window_count();
         check_exp_args!(name, 3, args);
 
         let v = lit_integer!(name, args, 1);
@@ -1747,6 +1784,15 @@ mod test {
         .unwrap();
         assert_matches!(info.projection_type, ProjectionType::Aggregate);
 
+        let info = select_statement_info(&parse_select(
+            "SELECT sample(foo, 2) FROM cpu GROUP BY TIME(10s)",
+        ))
+        .unwrap();
+        assert_matches!(
+            info.projection_type,
+            ProjectionType::Selector { has_fields: false }
+        );
+
         let info =
             select_statement_info(&parse_select("SELECT last(foo), first(foo) FROM cpu")).unwrap();
         assert_matches!(info.projection_type, ProjectionType::Aggregate);
@@ -1766,6 +1812,12 @@ mod test {
         .unwrap();
         assert_matches!(info.projection_type, ProjectionType::WindowAggregateMixed);
 
+        let info = select_statement_info(&parse_select(
+            "SELECT holt_winters(mean(foo), 2, 3) FROM cpu GROUP BY TIME(10s)",
+        ))
+        .unwrap();
+        assert_matches!(info.projection_type, ProjectionType::WindowAggregate);
+
         let info = select_statement_info(&parse_select("SELECT top(foo, 3) FROM cpu")).unwrap();
         assert_matches!(info.projection_type, ProjectionType::TopBottomSelector);
     }
@@ -1960,10 +2012,13 @@ mod test {
 
         // count_hll
         let sel = parse_select("SELECT count_hll(foo) FROM cpu");
-        assert_error!(
-            select_statement_info(&sel),
-            DataFusionError::NotImplemented(_)
-        );
+        select_statement_info(&sel).unwrap();
+        let sel = parse_select("SELECT count_hll(sum_hll(foo)) FROM cpu");
+        select_statement_info(&sel).unwrap();
+        let sel = parse_select("SELECT count_hll(mean(foo)) FROM cpu");
+        assert_error!(select_statement_info(&sel), DataFusionError::Plan(ref s) if s.starts_with("expected field argument in count_hll()"));
+        let sel = parse_select("SELECT count_hll(sum_hll(foo), 2) FROM cpu");
+        assert_error!(select_statement_info(&sel), DataFusionError::Plan(ref s) if s == "invalid number of arguments for count_hll, expected 1, got 2");
 
         // holt_winters, holt_winters_with_fit
         let sel = parse_select("SELECT holt_winters(mean(foo), 2, 3) FROM cpu GROUP BY time(30s)");
@@ -2451,18 +2506,11 @@ mod test {
                 "Error during planning: unable to use tag as wildcard in count()"
             );
 
-            let stmt = parse_select("SELECT usage_idle FROM cpu SLIMIT 1");
-            let err = rewrite_select_statement(&namespace, &stmt).unwrap_err();
-            assert_eq!(
-                err.to_string(),
-                "This feature is not implemented: SLIMIT or SOFFSET"
-            );
-
-            let stmt = parse_select("SELECT usage_idle FROM cpu SOFFSET 1");
+            let stmt = parse_select("SELECT usage_idle FROM (SELECT usage_idle INTO foo FROM cpu)");
             let err = rewrite_select_statement(&namespace, &stmt).unwrap_err();
             assert_eq!(
                 err.to_string(),
-                "This feature is not implemented: SLIMIT or SOFFSET"
+                "Error during planning: subqueries cannot have an INTO clause"
             );
         }
 
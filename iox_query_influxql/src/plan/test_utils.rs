@@ -2,7 +2,7 @@
 #![cfg(test)]
 
 use crate::error;
-use crate::plan::SchemaProvider;
+use crate::plan::{namespace_name, qualified_table_name, SchemaProvider};
 use chrono::{DateTime, NaiveDate, Utc};
 use datafusion::common::Result as DataFusionResult;
 use datafusion::datasource::empty::EmptyTable;
@@ -135,6 +135,11 @@ pub(crate) mod database {
 pub(crate) struct MockSchemaProvider {
     execution_props: ExecutionProps,
     tables: HashMap<String, (Arc<dyn TableSource>, Schema)>,
+    /// Other namespaces, by namespace name.
+    namespaces: HashMap<String, MockSchemaProvider>,
+    /// The namespace name and measurement name of the tables of other
+    /// namespaces, by qualified table name.
+    qualified_tables: HashMap<String, (String, String)>,
 }
 
 impl Default for MockSchemaProvider {
@@ -150,6 +155,8 @@ impl Default for MockSchemaProvider {
         let mut res = Self {
             execution_props,
             tables: HashMap::new(),
+            namespaces: HashMap::new(),
+            qualified_tables: HashMap::new(),
         };
         res.add_schemas(database::schemas());
         res
@@ -169,12 +176,44 @@ impl MockSchemaProvider {
     pub(crate) fn add_schemas(&mut self, schemas: impl IntoIterator<Item = Schema>) {
         schemas.into_iter().for_each(|s| self.add_schema(s));
     }
+
+    /// Add the namespace of `database` and `retention_policy`, with tables
+    /// for the specified `schemas`.
+    pub(crate) fn add_namespace(
+        &mut self,
+        database: &str,
+        retention_policy: Option<&str>,
+        schemas: impl IntoIterator<Item = Schema>,
+    ) {
+        let namespace_name = namespace_name(database, retention_policy);
+        let mut namespace = Self {
+            execution_props: self.execution_props.clone(),
+            tables: HashMap::new(),
+            namespaces: HashMap::new(),
+            qualified_tables: HashMap::new(),
+        };
+        namespace.add_schemas(schemas);
+
+        for table_name in namespace.tables.keys() {
+            self.qualified_tables.insert(
+                qualified_table_name(database, retention_policy, table_name),
+                (namespace_name.clone(), table_name.clone()),
+            );
+        }
+        self.namespaces.insert(namespace_name, namespace);
+    }
+
+    fn table(&self, name: &str) -> Option<&(Arc<dyn TableSource>, Schema)> {
+        self.tables.get(name).or_else(|| {
+            let (namespace_name, table_name) = self.qualified_tables.get(name)?;
+            self.namespaces.get(namespace_name)?.tables.get(table_name)
+        })
+    }
 }
 
 impl SchemaProvider for MockSchemaProvider {
     fn get_table_provider(&self, name: &str) -> DataFusionResult<Arc<dyn TableSource>> {
-        self.tables
-            .get(name)
+        self.table(name)
             .map(|(t, _)| Arc::clone(t))
             .ok_or_else(|| error::map::query(format!("measurement does not exist: {name}")))
     }
@@ -195,11 +234,31 @@ impl SchemaProvider for MockSchemaProvider {
             .collect::<Vec<_>>()
     }
 
+    fn table_exists(&self, name: &str) -> bool {
+        self.table(name).is_some()
+    }
+
     fn table_schema(&self, name: &str) -> Option<Schema> {
-        self.tables.get(name).map(|(_, s)| s.clone())
+        self.table(name).map(|(_, s)| s.clone())
     }
 
     fn execution_props(&self) -> &ExecutionProps {
         &self.execution_props
     }
+
+    fn namespace(
+        &self,
+        database: &str,
+        retention_policy: Option<&str>,
+    ) -> Option<&dyn SchemaProvider> {
+        self.namespaces
+            .get(&namespace_name(database, retention_policy))
+            .map(|ns| ns as _)
+    }
+
+    fn measurement_name<'b>(&'b self, name: &'b str) -> &'b str {
+        self.qualified_tables
+            .get(name)
+            .map_or(name, |(_, table_name)| table_name.as_str())
+    }
 }
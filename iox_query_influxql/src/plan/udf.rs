@@ -6,11 +6,18 @@
 //! rewritten at a later stage of planning, with more context available.
 
 use crate::plan::util::find_exprs_in_exprs;
+use crate::window::{
+    CHANDE_MOMENTUM_OSCILLATOR, CUMULATIVE_SUM, DERIVATIVE, DIFFERENCE,
+    DOUBLE_EXPONENTIAL_MOVING_AVERAGE, ELAPSED, EXPONENTIAL_MOVING_AVERAGE, HOLT_WINTERS,
+    HOLT_WINTERS_WITH_FIT, KAUFMANS_ADAPTIVE_MOVING_AVERAGE, KAUFMANS_EFFICIENCY_RATIO,
+    MOVING_AVERAGE, NON_NEGATIVE_DERIVATIVE, NON_NEGATIVE_DIFFERENCE, RELATIVE_STRENGTH_INDEX,
+    TRIPLE_EXPONENTIAL_DERIVATIVE, TRIPLE_EXPONENTIAL_MOVING_AVERAGE,
+};
 use crate::{error, NUMERICS};
-use arrow::datatypes::{DataType, TimeUnit};
+use arrow::datatypes::{DataType, IntervalUnit, TimeUnit};
 use datafusion::logical_expr::{
-    Expr, ReturnTypeFunction, ScalarFunctionImplementation, ScalarUDF, Signature, TypeSignature,
-    Volatility,
+    window_function, Expr, ReturnTypeFunction, ScalarFunctionImplementation, ScalarUDF, Signature,
+    TypeSignature, Volatility,
 };
 use once_cell::sync::Lazy;
 use std::sync::Arc;
@@ -22,6 +29,17 @@ pub(super) enum WindowFunction {
     Derivative,
     NonNegativeDerivative,
     CumulativeSum,
+    Elapsed,
+    ExponentialMovingAverage,
+    DoubleExponentialMovingAverage,
+    TripleExponentialMovingAverage,
+    RelativeStrengthIndex,
+    TripleExponentialDerivative,
+    KaufmansEfficiencyRatio,
+    KaufmansAdaptiveMovingAverage,
+    ChandeMomentumOscillator,
+    HoltWinters,
+    HoltWintersWithFit,
 }
 
 impl WindowFunction {
@@ -34,9 +52,47 @@ impl WindowFunction {
             DERIVATIVE_UDF_NAME => Some(Self::Derivative),
             NON_NEGATIVE_DERIVATIVE_UDF_NAME => Some(Self::NonNegativeDerivative),
             CUMULATIVE_SUM_UDF_NAME => Some(Self::CumulativeSum),
+            ELAPSED_UDF_NAME => Some(Self::Elapsed),
+            EXPONENTIAL_MOVING_AVERAGE_UDF_NAME => Some(Self::ExponentialMovingAverage),
+            DOUBLE_EXPONENTIAL_MOVING_AVERAGE_UDF_NAME => {
+                Some(Self::DoubleExponentialMovingAverage)
+            }
+            TRIPLE_EXPONENTIAL_MOVING_AVERAGE_UDF_NAME => {
+                Some(Self::TripleExponentialMovingAverage)
+            }
+            RELATIVE_STRENGTH_INDEX_UDF_NAME => Some(Self::RelativeStrengthIndex),
+            TRIPLE_EXPONENTIAL_DERIVATIVE_UDF_NAME => Some(Self::TripleExponentialDerivative),
+            KAUFMANS_EFFICIENCY_RATIO_UDF_NAME => Some(Self::KaufmansEfficiencyRatio),
+            KAUFMANS_ADAPTIVE_MOVING_AVERAGE_UDF_NAME => Some(Self::KaufmansAdaptiveMovingAverage),
+            CHANDE_MOMENTUM_OSCILLATOR_UDF_NAME => Some(Self::ChandeMomentumOscillator),
+            HOLT_WINTERS_UDF_NAME => Some(Self::HoltWinters),
+            HOLT_WINTERS_WITH_FIT_UDF_NAME => Some(Self::HoltWintersWithFit),
             _ => None,
         }
     }
+
+    /// Return the user-defined window function that implements `self`.
+    pub(super) fn window_udf(&self) -> &'static window_function::WindowFunction {
+        match self {
+            Self::MovingAverage => &MOVING_AVERAGE,
+            Self::Difference => &DIFFERENCE,
+            Self::NonNegativeDifference => &NON_NEGATIVE_DIFFERENCE,
+            Self::Derivative => &DERIVATIVE,
+            Self::NonNegativeDerivative => &NON_NEGATIVE_DERIVATIVE,
+            Self::CumulativeSum => &CUMULATIVE_SUM,
+            Self::Elapsed => &ELAPSED,
+            Self::ExponentialMovingAverage => &EXPONENTIAL_MOVING_AVERAGE,
+            Self::DoubleExponentialMovingAverage => &DOUBLE_EXPONENTIAL_MOVING_AVERAGE,
+            Self::TripleExponentialMovingAverage => &TRIPLE_EXPONENTIAL_MOVING_AVERAGE,
+            Self::RelativeStrengthIndex => &RELATIVE_STRENGTH_INDEX,
+            Self::TripleExponentialDerivative => &TRIPLE_EXPONENTIAL_DERIVATIVE,
+            Self::KaufmansEfficiencyRatio => &KAUFMANS_EFFICIENCY_RATIO,
+            Self::KaufmansAdaptiveMovingAverage => &KAUFMANS_ADAPTIVE_MOVING_AVERAGE,
+            Self::ChandeMomentumOscillator => &CHANDE_MOMENTUM_OSCILLATOR,
+            Self::HoltWinters => &HOLT_WINTERS,
+            Self::HoltWintersWithFit => &HOLT_WINTERS_WITH_FIT,
+        }
+    }
 }
 
 /// Find all [`Expr::ScalarUDF`] expressions that match one of the supported
@@ -209,6 +265,192 @@ static CUMULATIVE_SUM: Lazy<Arc<ScalarUDF>> = Lazy::new(|| {
     ))
 });
 
+const ELAPSED_UDF_NAME: &str = "elapsed";
+
+/// Create an expression to represent the `ELAPSED` function.
+pub(crate) fn elapsed(args: Vec<Expr>) -> Expr {
+    ELAPSED.call(args)
+}
+
+/// Definition of the `ELAPSED` function.
+static ELAPSED: Lazy<Arc<ScalarUDF>> = Lazy::new(|| {
+    let return_type_fn: ReturnTypeFunction = Arc::new(|_| Ok(Arc::new(DataType::Int64)));
+    Arc::new(ScalarUDF::new(
+        ELAPSED_UDF_NAME,
+        &Signature::one_of(
+            NUMERICS
+                .iter()
+                .chain(&[DataType::Utf8, DataType::Boolean])
+                .flat_map(|dt| {
+                    vec![
+                        TypeSignature::Exact(vec![dt.clone()]),
+                        TypeSignature::Exact(vec![
+                            dt.clone(),
+                            DataType::Interval(IntervalUnit::MonthDayNano),
+                        ]),
+                    ]
+                })
+                .collect(),
+            Volatility::Immutable,
+        ),
+        &return_type_fn,
+        &stand_in_impl(ELAPSED_UDF_NAME),
+    ))
+});
+
+const EXPONENTIAL_MOVING_AVERAGE_UDF_NAME: &str = "exponential_moving_average";
+
+/// Create an expression to represent the `EXPONENTIAL_MOVING_AVERAGE` function.
+pub(crate) fn exponential_moving_average(args: Vec<Expr>) -> Expr {
+    EXPONENTIAL_MOVING_AVERAGE.call(args)
+}
+
+/// Definition of the `EXPONENTIAL_MOVING_AVERAGE` function.
+static EXPONENTIAL_MOVING_AVERAGE: Lazy<Arc<ScalarUDF>> =
+    Lazy::new(|| technical_analysis_udf(EXPONENTIAL_MOVING_AVERAGE_UDF_NAME, true));
+
+const DOUBLE_EXPONENTIAL_MOVING_AVERAGE_UDF_NAME: &str = "double_exponential_moving_average";
+
+/// Create an expression to represent the `DOUBLE_EXPONENTIAL_MOVING_AVERAGE` function.
+pub(crate) fn double_exponential_moving_average(args: Vec<Expr>) -> Expr {
+    DOUBLE_EXPONENTIAL_MOVING_AVERAGE.call(args)
+}
+
+/// Definition of the `DOUBLE_EXPONENTIAL_MOVING_AVERAGE` function.
+static DOUBLE_EXPONENTIAL_MOVING_AVERAGE: Lazy<Arc<ScalarUDF>> =
+    Lazy::new(|| technical_analysis_udf(DOUBLE_EXPONENTIAL_MOVING_AVERAGE_UDF_NAME, true));
+
+const TRIPLE_EXPONENTIAL_MOVING_AVERAGE_UDF_NAME: &str = "triple_exponential_moving_average";
+
+/// Create an expression to represent the `TRIPLE_EXPONENTIAL_MOVING_AVERAGE` function.
+pub(crate) fn triple_exponential_moving_average(args: Vec<Expr>) -> Expr {
+    TRIPLE_EXPONENTIAL_MOVING_AVERAGE.call(args)
+}
+
+/// Definition of the `TRIPLE_EXPONENTIAL_MOVING_AVERAGE` function.
+static TRIPLE_EXPONENTIAL_MOVING_AVERAGE: Lazy<Arc<ScalarUDF>> =
+    Lazy::new(|| technical_analysis_udf(TRIPLE_EXPONENTIAL_MOVING_AVERAGE_UDF_NAME, true));
+
+const RELATIVE_STRENGTH_INDEX_UDF_NAME: &str = "relative_strength_index";
+
+/// Create an expression to represent the `RELATIVE_STRENGTH_INDEX` function.
+pub(crate) fn relative_strength_index(args: Vec<Expr>) -> Expr {
+    RELATIVE_STRENGTH_INDEX.call(args)
+}
+
+/// Definition of the `RELATIVE_STRENGTH_INDEX` function.
+static RELATIVE_STRENGTH_INDEX: Lazy<Arc<ScalarUDF>> =
+    Lazy::new(|| technical_analysis_udf(RELATIVE_STRENGTH_INDEX_UDF_NAME, true));
+
+const TRIPLE_EXPONENTIAL_DERIVATIVE_UDF_NAME: &str = "triple_exponential_derivative";
+
+/// Create an expression to represent the `TRIPLE_EXPONENTIAL_DERIVATIVE` function.
+pub(crate) fn triple_exponential_derivative(args: Vec<Expr>) -> Expr {
+    TRIPLE_EXPONENTIAL_DERIVATIVE.call(args)
+}
+
+/// Definition of the `TRIPLE_EXPONENTIAL_DERIVATIVE` function.
+static TRIPLE_EXPONENTIAL_DERIVATIVE: Lazy<Arc<ScalarUDF>> =
+    Lazy::new(|| technical_analysis_udf(TRIPLE_EXPONENTIAL_DERIVATIVE_UDF_NAME, true));
+
+const KAUFMANS_EFFICIENCY_RATIO_UDF_NAME: &str = "kaufmans_efficiency_ratio";
+
+/// Create an expression to represent the `KAUFMANS_EFFICIENCY_RATIO` function.
+pub(crate) fn kaufmans_efficiency_ratio(args: Vec<Expr>) -> Expr {
+    KAUFMANS_EFFICIENCY_RATIO.call(args)
+}
+
+/// Definition of the `KAUFMANS_EFFICIENCY_RATIO` function.
+static KAUFMANS_EFFICIENCY_RATIO: Lazy<Arc<ScalarUDF>> =
+    Lazy::new(|| technical_analysis_udf(KAUFMANS_EFFICIENCY_RATIO_UDF_NAME, false));
+
+const KAUFMANS_ADAPTIVE_MOVING_AVERAGE_UDF_NAME: &str = "kaufmans_adaptive_moving_average";
+
+/// Create an expression to represent the `KAUFMANS_ADAPTIVE_MOVING_AVERAGE` function.
+pub(crate) fn kaufmans_adaptive_moving_average(args: Vec<Expr>) -> Expr {
+    KAUFMANS_ADAPTIVE_MOVING_AVERAGE.call(args)
+}
+
+/// Definition of the `KAUFMANS_ADAPTIVE_MOVING_AVERAGE` function.
+static KAUFMANS_ADAPTIVE_MOVING_AVERAGE: Lazy<Arc<ScalarUDF>> =
+    Lazy::new(|| technical_analysis_udf(KAUFMANS_ADAPTIVE_MOVING_AVERAGE_UDF_NAME, false));
+
+const CHANDE_MOMENTUM_OSCILLATOR_UDF_NAME: &str = "chande_momentum_oscillator";
+
+/// Create an expression to represent the `CHANDE_MOMENTUM_OSCILLATOR` function.
+pub(crate) fn chande_momentum_oscillator(args: Vec<Expr>) -> Expr {
+    CHANDE_MOMENTUM_OSCILLATOR.call(args)
+}
+
+/// Definition of the `CHANDE_MOMENTUM_OSCILLATOR` function.
+static CHANDE_MOMENTUM_OSCILLATOR: Lazy<Arc<ScalarUDF>> =
+    Lazy::new(|| technical_analysis_udf(CHANDE_MOMENTUM_OSCILLATOR_UDF_NAME, true));
+
+const HOLT_WINTERS_UDF_NAME: &str = "holt_winters";
+
+/// Create an expression to represent the `HOLT_WINTERS` function.
+pub(crate) fn holt_winters(args: Vec<Expr>) -> Expr {
+    HOLT_WINTERS.call(args)
+}
+
+/// Definition of the `HOLT_WINTERS` function.
+static HOLT_WINTERS: Lazy<Arc<ScalarUDF>> = Lazy::new(|| holt_winters_udf(HOLT_WINTERS_UDF_NAME));
+
+const HOLT_WINTERS_WITH_FIT_UDF_NAME: &str = "holt_winters_with_fit";
+
+/// Create an expression to represent the `HOLT_WINTERS_WITH_FIT` function.
+pub(crate) fn holt_winters_with_fit(args: Vec<Expr>) -> Expr {
+    HOLT_WINTERS_WITH_FIT.call(args)
+}
+
+/// Definition of the `HOLT_WINTERS_WITH_FIT` function.
+static HOLT_WINTERS_WITH_FIT: Lazy<Arc<ScalarUDF>> =
+    Lazy::new(|| holt_winters_udf(HOLT_WINTERS_WITH_FIT_UDF_NAME));
+
+/// Create the definition of a Holt-Winters function, which accepts a
+/// numeric value, the number of values to forecast and the length of the
+/// seasonal pattern.
+fn holt_winters_udf(name: &'static str) -> Arc<ScalarUDF> {
+    let return_type_fn: ReturnTypeFunction = Arc::new(|_| Ok(Arc::new(DataType::Float64)));
+    Arc::new(ScalarUDF::new(
+        name,
+        &Signature::one_of(
+            NUMERICS
+                .iter()
+                .map(|dt| TypeSignature::Exact(vec![dt.clone(), DataType::Int64, DataType::Int64]))
+                .collect(),
+            Volatility::Immutable,
+        ),
+        &return_type_fn,
+        &stand_in_impl(name),
+    ))
+}
+
+/// Create the definition of a technical analysis function, which accepts a
+/// numeric value, the period, the hold period and, when `with_warmup`
+/// is `true`, the warmup type.
+fn technical_analysis_udf(name: &'static str, with_warmup: bool) -> Arc<ScalarUDF> {
+    let return_type_fn: ReturnTypeFunction = Arc::new(|_| Ok(Arc::new(DataType::Float64)));
+    Arc::new(ScalarUDF::new(
+        name,
+        &Signature::one_of(
+            NUMERICS
+                .iter()
+                .map(|dt| {
+                    let mut args = vec![dt.clone(), DataType::Int64, DataType::Int64];
+                    if with_warmup {
+                        args.push(DataType::Utf8);
+                    }
+                    TypeSignature::Exact(args)
+                })
+                .collect(),
+            Volatility::Immutable,
+        ),
+        &return_type_fn,
+        &stand_in_impl(name),
+    ))
+}
+
 /// Returns an implementation that always returns an error.
 fn stand_in_impl(name: &'static str) -> ScalarFunctionImplementation {
     Arc::new(move |_| error::internal(format!("{name} should not exist in the final logical plan")))
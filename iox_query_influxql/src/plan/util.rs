@@ -5,6 +5,7 @@ use datafusion::common::{DFSchemaRef, Result};
 use datafusion::logical_expr::utils::expr_as_column_expr;
 use datafusion::logical_expr::{lit, Expr, ExprSchemable, LogicalPlan, Operator};
 use datafusion::scalar::ScalarValue;
+use influxdb_influxql_parser::common::{MeasurementName, QualifiedMeasurementName};
 use influxdb_influxql_parser::expression::BinaryOperator;
 use influxdb_influxql_parser::literal::Number;
 use influxdb_influxql_parser::string::Regex;
@@ -93,6 +94,63 @@ pub(crate) fn parse_regex(re: &Regex) -> Result<regex::Regex> {
         .map_err(|e| error::map::query(format!("invalid regular expression '{re}': {e}")))
 }
 
+/// The separator of the database and retention policy in the name of a
+/// namespace, as used by the InfluxDB 1.x compatible APIs.
+const NAMESPACE_RP_SEPARATOR: char = '/';
+
+/// Returns `true` if `retention_policy` refers to the default retention
+/// policy of a database, which is stored in the namespace named after the
+/// database.
+fn is_default_retention_policy(retention_policy: &str) -> bool {
+    matches!(
+        retention_policy.to_lowercase().as_str(),
+        "" | "''" | "autogen" | "default"
+    )
+}
+
+/// Returns the name of the namespace that stores the data of the specified
+/// `database` and `retention_policy`.
+pub fn namespace_name(database: &str, retention_policy: Option<&str>) -> String {
+    match retention_policy {
+        Some(rp) if !is_default_retention_policy(rp) => {
+            format!("{database}{NAMESPACE_RP_SEPARATOR}{}", rp.to_lowercase())
+        }
+        _ => database.to_owned(),
+    }
+}
+
+/// Returns the name used to refer to the measurement `name` of another
+/// namespace, identified by `database` and `retention_policy`, such as
+/// `db..cpu`.
+pub(crate) fn qualified_table_name(
+    database: &str,
+    retention_policy: Option<&str>,
+    name: &str,
+) -> String {
+    QualifiedMeasurementName {
+        database: Some(database.into()),
+        retention_policy: retention_policy
+            .filter(|rp| !is_default_retention_policy(rp))
+            .map(|rp| rp.to_lowercase().into()),
+        name: MeasurementName::Name(name.into()),
+    }
+    .to_string()
+}
+
+/// Validate that a measurement name qualified only by its
+/// `retention_policy`, such as `autogen.cpu`, refers to the namespace of
+/// the query.
+///
+/// Only the default retention policy is supported, as the database of the
+/// query is not known to the planner.
+pub(crate) fn check_retention_policy_without_database(retention_policy: &str) -> Result<()> {
+    if is_default_retention_policy(retention_policy) {
+        Ok(())
+    } else {
+        error::not_implemented("retention policy in from clause without database name")
+    }
+}
+
 /// Returns `n` as a scalar value of the specified `data_type`.
 fn number_to_scalar(n: &Number, data_type: &DataType) -> Result<ScalarValue> {
     Ok(match (n, data_type) {
@@ -6,12 +6,40 @@ use datafusion::logical_expr::{
 use once_cell::sync::Lazy;
 use std::sync::Arc;
 
+mod chande_momentum_oscillator;
 mod cumulative_sum;
 mod derivative;
 mod difference;
+mod double_exponential_moving_average;
+mod elapsed;
+mod exponential_moving_average;
+mod holt_winters;
+mod integral;
+mod kaufmans_adaptive_moving_average;
+mod kaufmans_efficiency_ratio;
 mod moving_average;
+mod nelder_mead;
 mod non_negative;
 mod percent_row_number;
+mod relative_strength_index;
+mod sample_row;
+mod technical_analysis;
+mod triple_exponential_derivative;
+mod triple_exponential_moving_average;
+
+/// Definition of the `CHANDE_MOMENTUM_OSCILLATOR` user-defined window function.
+pub(crate) static CHANDE_MOMENTUM_OSCILLATOR: Lazy<WindowFunction> = Lazy::new(|| {
+    let return_type: ReturnTypeFunction = Arc::new(technical_analysis::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory =
+        Arc::new(chande_momentum_oscillator::partition_evaluator_factory);
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        chande_momentum_oscillator::NAME,
+        &chande_momentum_oscillator::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
 
 /// Definition of the `CUMULATIVE_SUM` user-defined window function.
 pub(crate) static CUMULATIVE_SUM: Lazy<WindowFunction> = Lazy::new(|| {
@@ -55,6 +83,119 @@ pub(crate) static DIFFERENCE: Lazy<WindowFunction> = Lazy::new(|| {
     )))
 });
 
+/// Definition of the `DOUBLE_EXPONENTIAL_MOVING_AVERAGE` user-defined window function.
+pub(crate) static DOUBLE_EXPONENTIAL_MOVING_AVERAGE: Lazy<WindowFunction> = Lazy::new(|| {
+    let return_type: ReturnTypeFunction = Arc::new(technical_analysis::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory =
+        Arc::new(double_exponential_moving_average::partition_evaluator_factory);
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        double_exponential_moving_average::NAME,
+        &double_exponential_moving_average::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
+
+/// Definition of the `ELAPSED` user-defined window function.
+pub(crate) static ELAPSED: Lazy<WindowFunction> = Lazy::new(|| {
+    let return_type: ReturnTypeFunction = Arc::new(elapsed::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory =
+        Arc::new(elapsed::partition_evaluator_factory);
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        elapsed::NAME,
+        &elapsed::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
+
+/// Definition of the `EXPONENTIAL_MOVING_AVERAGE` user-defined window function.
+pub(crate) static EXPONENTIAL_MOVING_AVERAGE: Lazy<WindowFunction> = Lazy::new(|| {
+    let return_type: ReturnTypeFunction = Arc::new(technical_analysis::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory =
+        Arc::new(exponential_moving_average::partition_evaluator_factory);
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        exponential_moving_average::NAME,
+        &exponential_moving_average::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
+
+/// Definition of the `HOLT_WINTERS` user-defined window function.
+pub(crate) static HOLT_WINTERS: Lazy<WindowFunction> = Lazy::new(|| {
+    let return_type: ReturnTypeFunction = Arc::new(holt_winters::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory =
+        Arc::new(holt_winters::partition_evaluator_factory);
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        holt_winters::NAME,
+        &holt_winters::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
+
+/// Definition of the `HOLT_WINTERS_WITH_FIT` user-defined window function.
+pub(crate) static HOLT_WINTERS_WITH_FIT: Lazy<WindowFunction> = Lazy::new(|| {
+    let return_type: ReturnTypeFunction = Arc::new(holt_winters::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory =
+        Arc::new(holt_winters::with_fit_partition_evaluator_factory);
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        holt_winters::WITH_FIT_NAME,
+        &holt_winters::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
+
+/// Definition of the `INTEGRAL_WINDOW` user-defined window function, which
+/// calculates the `INTEGRAL` aggregate when grouping by time.
+pub(crate) static INTEGRAL_WINDOW: Lazy<WindowFunction> = Lazy::new(|| {
+    let return_type: ReturnTypeFunction = Arc::new(integral::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory =
+        Arc::new(integral::partition_evaluator_factory);
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        integral::NAME,
+        &integral::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
+
+/// Definition of the `KAUFMANS_ADAPTIVE_MOVING_AVERAGE` user-defined window function.
+pub(crate) static KAUFMANS_ADAPTIVE_MOVING_AVERAGE: Lazy<WindowFunction> = Lazy::new(|| {
+    let return_type: ReturnTypeFunction = Arc::new(technical_analysis::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory =
+        Arc::new(kaufmans_adaptive_moving_average::partition_evaluator_factory);
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        kaufmans_adaptive_moving_average::NAME,
+        &kaufmans_adaptive_moving_average::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
+
+/// Definition of the `KAUFMANS_EFFICIENCY_RATIO` user-defined window function.
+pub(crate) static KAUFMANS_EFFICIENCY_RATIO: Lazy<WindowFunction> = Lazy::new(|| {
+    let return_type: ReturnTypeFunction = Arc::new(technical_analysis::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory =
+        Arc::new(kaufmans_efficiency_ratio::partition_evaluator_factory);
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        kaufmans_efficiency_ratio::NAME,
+        &kaufmans_efficiency_ratio::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
+
 /// Definition of the `MOVING_AVERAGE` user-defined window function.
 pub(crate) static MOVING_AVERAGE: Lazy<WindowFunction> = Lazy::new(|| {
     let return_type: ReturnTypeFunction = Arc::new(moving_average::return_type);
@@ -120,3 +261,59 @@ pub(crate) static PERCENT_ROW_NUMBER: Lazy<WindowFunction> = Lazy::new(|| {
         &partition_evaluator_factory,
     )))
 });
+
+/// Definition of the `RELATIVE_STRENGTH_INDEX` user-defined window function.
+pub(crate) static RELATIVE_STRENGTH_INDEX: Lazy<WindowFunction> = Lazy::new(|| {
+    let return_type: ReturnTypeFunction = Arc::new(technical_analysis::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory =
+        Arc::new(relative_strength_index::partition_evaluator_factory);
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        relative_strength_index::NAME,
+        &relative_strength_index::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
+
+/// Definition of the `SAMPLE_ROW` user-defined window function.
+pub(crate) static SAMPLE_ROW: Lazy<WindowFunction> = Lazy::new(|| {
+    let return_type: ReturnTypeFunction = Arc::new(sample_row::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory =
+        Arc::new(sample_row::partition_evaluator_factory);
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        sample_row::NAME,
+        &sample_row::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
+
+/// Definition of the `TRIPLE_EXPONENTIAL_DERIVATIVE` user-defined window function.
+pub(crate) static TRIPLE_EXPONENTIAL_DERIVATIVE: Lazy<WindowFunction> = Lazy::new(|| {
+    let return_type: ReturnTypeFunction = Arc::new(technical_analysis::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory =
+        Arc::new(triple_exponential_derivative::partition_evaluator_factory);
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        triple_exponential_derivative::NAME,
+        &triple_exponential_derivative::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
+
+/// Definition of the `TRIPLE_EXPONENTIAL_MOVING_AVERAGE` user-defined window function.
+pub(crate) static TRIPLE_EXPONENTIAL_MOVING_AVERAGE: Lazy<WindowFunction> = Lazy::new(|| {
+    let return_type: ReturnTypeFunction = Arc::new(technical_analysis::return_type);
+    let partition_evaluator_factory: PartitionEvaluatorFactory =
+        Arc::new(triple_exponential_moving_average::partition_evaluator_factory);
+
+    WindowFunction::WindowUDF(Arc::new(WindowUDF::new(
+        triple_exponential_moving_average::NAME,
+        &triple_exponential_moving_average::SIGNATURE,
+        &return_type,
+        &partition_evaluator_factory,
+    )))
+});
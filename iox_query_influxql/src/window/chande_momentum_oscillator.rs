@@ -0,0 +1,190 @@
+use super::technical_analysis::{
+    signature, Ema, Indicator, IndicatorPartitionEvaluator, WarmupType,
+};
+use datafusion::common::Result;
+use datafusion::logical_expr::{PartitionEvaluator, Signature};
+use once_cell::sync::Lazy;
+use std::collections::VecDeque;
+
+/// The name of the chande momentum oscillator window function.
+pub(super) const NAME: &str = "chande_momentum_oscillator";
+
+/// Valid signatures for the chande momentum oscillator window function.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| signature(true));
+
+/// Create a new partition_evaluator_factory.
+pub(super) fn partition_evaluator_factory() -> Result<Box<dyn PartitionEvaluator>> {
+    Ok(Box::new(IndicatorPartitionEvaluator::new(
+        NAME,
+        WarmupType::None,
+        |period, warmup| Box::new(ChandeMomentumOscillator::new(period, warmup)),
+    )))
+}
+
+/// The method used to total the gains and losses of the input.
+#[derive(Debug)]
+enum Totals {
+    /// The sum of the gains and losses of the last `period` changes, when
+    /// the warmup type is `none`.
+    Sum {
+        /// The changes of the last `period - 1` values, oldest first.
+        changes: VecDeque<f64>,
+        gains: f64,
+        losses: f64,
+    },
+    /// The exponential moving averages of the gains and losses.
+    Ema { gain: Ema, loss: Ema },
+}
+
+/// Indicator which returns the Chande momentum oscillator of the input,
+/// which is `100 * (gains - losses) / (gains + losses)`.
+///
+/// When the warmup type is `none`, the gains and losses of the last `period`
+/// changes are summed, and the first value is not a change. Otherwise they
+/// are smoothed using Wilder's smoothing, as for the relative strength
+/// index, and the first value is treated as a change from zero, as in
+/// InfluxQL OG.
+#[derive(Debug)]
+struct ChandeMomentumOscillator {
+    totals: Totals,
+    last: Option<f64>,
+}
+
+impl ChandeMomentumOscillator {
+    fn new(period: usize, warmup: WarmupType) -> Self {
+        let totals = match warmup {
+            WarmupType::None => Totals::Sum {
+                changes: VecDeque::from(vec![0.0; period - 1]),
+                gains: 0.0,
+                losses: 0.0,
+            },
+            _ => {
+                let ema = Ema::with_alpha(period + 1, 1.0 / period as f64, warmup);
+                Totals::Ema {
+                    gain: ema.clone(),
+                    loss: ema,
+                }
+            }
+        };
+        Self { totals, last: None }
+    }
+}
+
+impl Indicator for ChandeMomentumOscillator {
+    fn add(&mut self, v: f64) -> Option<f64> {
+        let last = self.last.replace(v);
+        match &mut self.totals {
+            Totals::Sum {
+                changes,
+                gains,
+                losses,
+            } => {
+                let change = last.map_or(0.0, |last| v - last);
+                if change > 0.0 {
+                    *gains += change;
+                } else if change < 0.0 {
+                    *losses -= change;
+                }
+                let cmo = if *gains != 0.0 || *losses != 0.0 {
+                    100.0 * ((*gains - *losses) / (*gains + *losses))
+                } else {
+                    0.0
+                };
+
+                changes.push_back(change);
+                if let Some(oldest) = changes.pop_front() {
+                    if oldest > 0.0 {
+                        *gains -= oldest;
+                    } else if oldest < 0.0 {
+                        *losses += oldest;
+                    }
+                }
+                Some(cmo)
+            }
+            Totals::Ema { gain, loss } => {
+                let last = last.unwrap_or_default();
+                let gain = gain.add((v - last).max(0.0));
+                let loss = loss.add((last - v).max(0.0));
+                Some(100.0 * ((gain - loss) / (gain + loss)))
+            }
+        }
+    }
+
+    fn warm_count(&self) -> usize {
+        match &self.totals {
+            Totals::Sum { changes, .. } => changes.len(),
+            Totals::Ema { gain, .. } => gain.warm_count(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::window::technical_analysis::assert_indicator;
+
+    #[test]
+    fn test_chande_momentum_oscillator() {
+        // The expected results follow InfluxQL OG.
+        assert_indicator(
+            partition_evaluator_factory,
+            3,
+            -1,
+            Some("none"),
+            &[
+                None,
+                None,
+                Some(33.33333333333333),
+                Some(66.66666666666666),
+                Some(20.0),
+                None,
+                Some(66.66666666666666),
+                Some(66.66666666666666),
+                Some(66.66666666666666),
+                Some(20.0),
+                Some(20.0),
+            ],
+        );
+
+        // The first value is not a change.
+        assert_indicator(
+            partition_evaluator_factory,
+            3,
+            0,
+            Some("none"),
+            &[
+                Some(0.0),
+                Some(100.0),
+                Some(33.33333333333333),
+                Some(66.66666666666666),
+                Some(20.0),
+                None,
+                Some(66.66666666666666),
+                Some(66.66666666666666),
+                Some(66.66666666666666),
+                Some(20.0),
+                Some(20.0),
+            ],
+        );
+
+        assert_indicator(
+            partition_evaluator_factory,
+            3,
+            -1,
+            Some("exponential"),
+            &[
+                None,
+                None,
+                None,
+                Some(90.08264462809917),
+                Some(71.00371747211898),
+                None,
+                Some(77.71428571428571),
+                Some(85.3452325035228),
+                Some(58.251453779827564),
+                Some(29.791957898199183),
+                Some(61.19531871378252),
+            ],
+        );
+    }
+}
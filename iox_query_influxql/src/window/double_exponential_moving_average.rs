@@ -0,0 +1,114 @@
+use super::technical_analysis::{
+    signature, Ema, Indicator, IndicatorPartitionEvaluator, WarmupType,
+};
+use datafusion::common::Result;
+use datafusion::logical_expr::{PartitionEvaluator, Signature};
+use once_cell::sync::Lazy;
+
+/// The name of the double exponential moving average window function.
+pub(super) const NAME: &str = "double_exponential_moving_average";
+
+/// Valid signatures for the double exponential moving average window function.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| signature(true));
+
+/// Create a new partition_evaluator_factory.
+pub(super) fn partition_evaluator_factory() -> Result<Box<dyn PartitionEvaluator>> {
+    Ok(Box::new(IndicatorPartitionEvaluator::new(
+        NAME,
+        WarmupType::Exponential,
+        |period, warmup| Box::new(DoubleExponentialMovingAverage::new(period, warmup)),
+    )))
+}
+
+/// Indicator which returns the double exponential moving average of the
+/// input, which is `2 * EMA - EMA(EMA)`.
+///
+/// When warming up with a simple moving average, `EMA(EMA)` tracks `EMA`
+/// until `EMA` has received `period` values.
+#[derive(Debug)]
+struct DoubleExponentialMovingAverage {
+    ema1: Ema,
+    ema2: Ema,
+    warmup: WarmupType,
+}
+
+impl DoubleExponentialMovingAverage {
+    fn new(period: usize, warmup: WarmupType) -> Self {
+        Self {
+            ema1: Ema::new(period, warmup),
+            ema2: Ema::new(period, warmup),
+            warmup,
+        }
+    }
+}
+
+impl Indicator for DoubleExponentialMovingAverage {
+    fn add(&mut self, v: f64) -> Option<f64> {
+        let e1 = self.ema1.add(v);
+        let e2 = if self.ema1.feeds_nested() {
+            self.ema2.add(e1)
+        } else {
+            e1
+        };
+        Some(2.0 * e1 - e2)
+    }
+
+    fn warm_count(&self) -> usize {
+        // When warming up with a simple moving average, the second average
+        // is not stable until it has received the stable values of the first.
+        match self.warmup {
+            WarmupType::Simple => self.ema1.warm_count() + self.ema2.warm_count(),
+            _ => self.ema1.warm_count(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::window::technical_analysis::assert_indicator;
+
+    #[test]
+    fn test_double_exponential_moving_average() {
+        // The expected results follow InfluxQL OG.
+        assert_indicator(
+            partition_evaluator_factory,
+            3,
+            -1,
+            Some("exponential"),
+            &[
+                None,
+                None,
+                Some(11.25),
+                Some(13.375),
+                Some(13.3125),
+                None,
+                Some(14.71875),
+                Some(17.390625),
+                Some(17.4609375),
+                Some(16.61328125),
+                Some(18.498046875),
+            ],
+        );
+
+        assert_indicator(
+            partition_evaluator_factory,
+            3,
+            -1,
+            Some("simple"),
+            &[
+                None,
+                None,
+                None,
+                None,
+                Some(13.416666666666666),
+                None,
+                Some(14.770833333333332),
+                Some(17.416666666666664),
+                Some(17.473958333333332),
+                Some(16.619791666666664),
+                Some(18.501302083333332),
+            ],
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use crate::NUMERICS;
+use arrow::array::{Array, ArrayRef, Int64Array, TimestampNanosecondArray};
+use arrow::datatypes::{DataType, IntervalUnit, TimeUnit};
+use datafusion::common::{downcast_value, DataFusionError, Result, ScalarValue};
+use datafusion::logical_expr::{PartitionEvaluator, Signature, TypeSignature, Volatility};
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+/// The name of the elapsed window function.
+pub(super) const NAME: &str = "elapsed";
+
+/// Valid signatures for the elapsed window function.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| {
+    Signature::one_of(
+        NUMERICS
+            .iter()
+            .chain(&[DataType::Utf8, DataType::Boolean])
+            .map(|dt| {
+                TypeSignature::Exact(vec![
+                    dt.clone(),
+                    DataType::Interval(IntervalUnit::MonthDayNano),
+                    DataType::Timestamp(TimeUnit::Nanosecond, None),
+                ])
+            })
+            .collect(),
+        Volatility::Immutable,
+    )
+});
+
+/// Calculate the return type given the function signature. Elapsed
+/// always returns an Int64.
+pub(super) fn return_type(_: &[DataType]) -> Result<Arc<DataType>> {
+    Ok(Arc::new(DataType::Int64))
+}
+
+/// Create a new partition_evaluator_factory.
+pub(super) fn partition_evaluator_factory() -> Result<Box<dyn PartitionEvaluator>> {
+    Ok(Box::new(ElapsedPartitionEvaluator {}))
+}
+
+/// PartitionEvaluator which returns the time elapsed between rows with
+/// non-null input values, in the provided units.
+#[derive(Debug)]
+struct ElapsedPartitionEvaluator {}
+
+impl PartitionEvaluator for ElapsedPartitionEvaluator {
+    fn evaluate_all(&mut self, values: &[ArrayRef], _num_rows: usize) -> Result<Arc<dyn Array>> {
+        assert_eq!(values.len(), 3);
+
+        let array = Arc::clone(&values[0]);
+        let times = Arc::clone(&values[2]);
+        let times = downcast_value!(times, TimestampNanosecondArray);
+
+        // The second element of the values array is the second argument to
+        // the 'elapsed' function. This specifies the unit duration of the
+        // elapsed time.
+        //
+        // INVARIANT:
+        // The planner guarantees that the second argument is always a duration
+        // literal.
+        let unit = crate::unit_nanos(NAME, &ScalarValue::try_from_array(&values[1], 0)?)?;
+
+        let mut last_time: Option<i64> = None;
+        let elapsed: Int64Array = (0..array.len())
+            .map(|idx| {
+                if array.is_null(idx) || times.is_null(idx) {
+                    return None;
+                }
+                let t = times.value(idx);
+                last_time.replace(t).map(|last| (t - last) / unit)
+            })
+            .collect();
+
+        Ok(Arc::new(elapsed))
+    }
+
+    fn uses_window_frame(&self) -> bool {
+        false
+    }
+
+    fn include_rank(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use arrow::array::Float64Array;
+
+    const SECOND: i64 = 1_000_000_000;
+
+    fn elapsed(points: &[(i64, Option<f64>)], unit: ScalarValue) -> Result<Vec<Option<i64>>> {
+        let values: ArrayRef = Arc::new(Float64Array::from_iter(points.iter().map(|(_, v)| *v)));
+        let times: ArrayRef = Arc::new(TimestampNanosecondArray::from_iter_values(
+            points.iter().map(|(t, _)| *t),
+        ));
+        let unit = unit.to_array_of_size(points.len());
+
+        let elapsed =
+            partition_evaluator_factory()?.evaluate_all(&[values, unit, times], points.len())?;
+        let elapsed = elapsed.as_any().downcast_ref::<Int64Array>().unwrap();
+        Ok(elapsed.iter().collect())
+    }
+
+    #[test]
+    fn test_elapsed() {
+        let points = [
+            (0, Some(1.0)),
+            (10 * SECOND, Some(2.0)),
+            (15 * SECOND, None),
+            (25 * SECOND, Some(3.0)),
+            (90 * SECOND, Some(4.0)),
+        ];
+
+        // The expected results are from InfluxQL OG, for
+        // SELECT elapsed(v) and SELECT elapsed(v, 10s)
+        assert_eq!(
+            elapsed(&points, ScalarValue::new_interval_mdn(0, 0, 1)).unwrap(),
+            vec![
+                None,
+                Some(10_000_000_000),
+                None,
+                Some(15_000_000_000),
+                Some(65_000_000_000)
+            ]
+        );
+        // The elapsed time is truncated to a whole number of units
+        assert_eq!(
+            elapsed(&points, ScalarValue::new_interval_mdn(0, 0, 10 * SECOND)).unwrap(),
+            vec![None, Some(1), None, Some(1), Some(6)]
+        );
+    }
+
+    #[test]
+    fn test_elapsed_unit() {
+        let points = [(0, Some(1.0)), (2 * 24 * 60 * 60 * SECOND, Some(2.0))];
+
+        // Days are converted as 24 hours
+        assert_eq!(
+            elapsed(&points, ScalarValue::new_interval_mdn(0, 1, 0)).unwrap(),
+            vec![None, Some(2)]
+        );
+
+        // Months do not have a fixed duration
+        let err = elapsed(&points, ScalarValue::new_interval_mdn(1, 0, 0)).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("elapsed attempted with invalid unit"));
+    }
+}
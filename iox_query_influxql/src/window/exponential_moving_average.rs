@@ -0,0 +1,106 @@
+use super::technical_analysis::{
+    signature, Ema, Indicator, IndicatorPartitionEvaluator, WarmupType,
+};
+use datafusion::common::Result;
+use datafusion::logical_expr::{PartitionEvaluator, Signature};
+use once_cell::sync::Lazy;
+
+/// The name of the exponential moving average window function.
+pub(super) const NAME: &str = "exponential_moving_average";
+
+/// Valid signatures for the exponential moving average window function.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| signature(true));
+
+/// Create a new partition_evaluator_factory.
+pub(super) fn partition_evaluator_factory() -> Result<Box<dyn PartitionEvaluator>> {
+    Ok(Box::new(IndicatorPartitionEvaluator::new(
+        NAME,
+        WarmupType::Exponential,
+        |period, warmup| Box::new(ExponentialMovingAverage(Ema::new(period, warmup))),
+    )))
+}
+
+/// Indicator which returns the exponential moving average of the input.
+#[derive(Debug)]
+struct ExponentialMovingAverage(Ema);
+
+impl Indicator for ExponentialMovingAverage {
+    fn add(&mut self, v: f64) -> Option<f64> {
+        Some(self.0.add(v))
+    }
+
+    fn warm_count(&self) -> usize {
+        self.0.warm_count()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::window::technical_analysis::assert_indicator;
+
+    #[test]
+    fn test_exponential_moving_average() {
+        // The expected results follow InfluxQL OG.
+        assert_indicator(
+            partition_evaluator_factory,
+            3,
+            -1,
+            Some("exponential"),
+            &[
+                None,
+                None,
+                Some(11.0),
+                Some(12.5),
+                Some(12.75),
+                None,
+                Some(13.875),
+                Some(15.9375),
+                Some(16.46875),
+                Some(16.234375),
+                Some(17.6171875),
+            ],
+        );
+
+        assert_indicator(
+            partition_evaluator_factory,
+            4,
+            -1,
+            Some("simple"),
+            &[
+                None,
+                None,
+                None,
+                Some(11.75),
+                Some(12.25),
+                None,
+                Some(13.35),
+                Some(15.21),
+                Some(15.926),
+                Some(15.9556),
+                Some(17.17336),
+            ],
+        );
+
+        // A hold period of 0 returns every value.
+        assert_indicator(
+            partition_evaluator_factory,
+            3,
+            0,
+            Some("exponential"),
+            &[
+                Some(10.0),
+                Some(11.0),
+                Some(11.0),
+                Some(12.5),
+                Some(12.75),
+                None,
+                Some(13.875),
+                Some(15.9375),
+                Some(16.46875),
+                Some(16.234375),
+                Some(17.6171875),
+            ],
+        );
+    }
+}
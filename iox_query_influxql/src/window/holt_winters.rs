@@ -0,0 +1,345 @@
+use super::nelder_mead;
+use crate::{error, NUMERICS};
+use arrow::array::{Array, ArrayRef, Float64Array, TimestampNanosecondArray};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, TimeUnit};
+use datafusion::common::{downcast_value, DataFusionError, Result, ScalarValue};
+use datafusion::logical_expr::{PartitionEvaluator, Signature, TypeSignature, Volatility};
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+/// The name of the holt_winters window function.
+pub(super) const NAME: &str = "holt_winters";
+
+/// The name of the holt_winters_with_fit window function.
+pub(super) const WITH_FIT_NAME: &str = "holt_winters_with_fit";
+
+/// Valid signatures for the holt_winters window functions. The arguments
+/// are the value, the number of values to forecast, the seasonal pattern
+/// length, the upper bound of the time range used to fit the model and
+/// the time column.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| {
+    Signature::one_of(
+        NUMERICS
+            .iter()
+            .map(|dt| {
+                TypeSignature::Exact(vec![
+                    dt.clone(),
+                    DataType::Int64,
+                    DataType::Int64,
+                    DataType::Timestamp(TimeUnit::Nanosecond, None),
+                    DataType::Timestamp(TimeUnit::Nanosecond, None),
+                ])
+            })
+            .collect(),
+        Volatility::Immutable,
+    )
+});
+
+/// Calculate the return type given the function signature. The
+/// holt_winters functions always return a Float64.
+pub(super) fn return_type(_: &[DataType]) -> Result<Arc<DataType>> {
+    Ok(Arc::new(DataType::Float64))
+}
+
+/// Create a new partition_evaluator_factory for `holt_winters`.
+pub(super) fn partition_evaluator_factory() -> Result<Box<dyn PartitionEvaluator>> {
+    Ok(Box::new(HoltWintersPartitionEvaluator {
+        include_fit: false,
+    }))
+}
+
+/// Create a new partition_evaluator_factory for `holt_winters_with_fit`.
+pub(super) fn with_fit_partition_evaluator_factory() -> Result<Box<dyn PartitionEvaluator>> {
+    Ok(Box::new(HoltWintersPartitionEvaluator {
+        include_fit: true,
+    }))
+}
+
+/// The lower bound of the grid of initial guesses for the smoothing
+/// parameters.
+const GUESS_LOWER: f64 = 0.3;
+
+/// The upper bound of the grid of initial guesses for the smoothing
+/// parameters. As the search is performed for every combination of the
+/// four parameters, the grid is kept small.
+const GUESS_UPPER: f64 = 1.0;
+
+/// The step between initial guesses for the smoothing parameters.
+const GUESS_STEP: f64 = 0.4;
+
+/// The tolerance used to stop the minimisation of the error.
+const EPSILON: f64 = 1.0e-4;
+
+/// PartitionEvaluator which fits a Holt-Winters seasonal model to the
+/// values of the partition and forecasts the values that follow.
+///
+/// The partition is expected to contain a row for every `GROUP BY time()`
+/// interval, including the intervals after the fitted time range where
+/// the forecast values are returned. Rows with a `NULL` value are
+/// treated as missing values when fitting the model.
+#[derive(Debug)]
+struct HoltWintersPartitionEvaluator {
+    /// Return the fitted values of the model, in addition to the
+    /// forecast values.
+    include_fit: bool,
+}
+
+impl PartitionEvaluator for HoltWintersPartitionEvaluator {
+    fn evaluate_all(&mut self, values: &[ArrayRef], num_rows: usize) -> Result<Arc<dyn Array>> {
+        assert_eq!(values.len(), 5);
+
+        // INVARIANT:
+        // The planner guarantees that the N, S and upper bound arguments
+        // are always literals, and the rewriter has validated N and S.
+        //
+        // See: FieldChecker::check_holt_winters
+        let h = match ScalarValue::try_from_array(&values[1], 0)? {
+            ScalarValue::Int64(Some(v)) if v > 0 => v as usize,
+            v => return error::internal(format!("holt_winters attempted with invalid N {v}")),
+        };
+        let m = match ScalarValue::try_from_array(&values[2], 0)? {
+            ScalarValue::Int64(Some(v)) if v >= 0 => v as usize,
+            v => return error::internal(format!("holt_winters attempted with invalid S {v}")),
+        };
+        let upper = match ScalarValue::try_from_array(&values[3], 0)? {
+            ScalarValue::TimestampNanosecond(Some(v), _) => v,
+            v => {
+                return error::internal(format!(
+                    "holt_winters attempted with invalid upper bound {v}"
+                ))
+            }
+        };
+
+        let array = cast(&values[0], &DataType::Float64)?;
+        let array = downcast_value!(array, Float64Array);
+        let times = Arc::clone(&values[4]);
+        let times = downcast_value!(times, TimestampNanosecondArray);
+
+        // The model is fitted to the rows between the first and last
+        // non-null values within the time range of the query.
+        let mut fit_rows = (0..array.len())
+            .filter(|&idx| array.is_valid(idx) && times.is_valid(idx) && times.value(idx) <= upper);
+        let first = fit_rows.next();
+        let last = fit_rows.last().or(first);
+
+        let mut output = vec![None; num_rows];
+        if let (Some(first), Some(last)) = (first, last) {
+            let y = (first..=last)
+                .map(|idx| {
+                    if array.is_valid(idx) {
+                        array.value(idx)
+                    } else {
+                        f64::NAN
+                    }
+                })
+                .collect::<Vec<_>>();
+            let fitted_len = y.len();
+
+            if let Some(forecast) = HoltWinters::new(y, m).forecast(h) {
+                let skip = if self.include_fit { 0 } else { fitted_len };
+                for (idx, v) in forecast.into_iter().enumerate().skip(skip) {
+                    if let Some(o) = output.get_mut(first + idx) {
+                        *o = (!v.is_nan()).then_some(v);
+                    }
+                }
+            }
+        }
+
+        Ok(Arc::new(Float64Array::from(output)))
+    }
+
+    fn uses_window_frame(&self) -> bool {
+        false
+    }
+
+    fn include_rank(&self) -> bool {
+        false
+    }
+}
+
+/// A Holt-Winters model with a damped additive trend and multiplicative
+/// seasonality.
+///
+/// The parameters of the model are stored in a single vector, so they
+/// can be optimised together:
+///
+/// * `alpha`, `beta`, `gamma` and `phi`, which are the smoothing factors
+///   for the level, trend and seasonality, and the damping factor,
+/// * the initial level and trend,
+/// * the initial seasonal factors, when the model is seasonal.
+#[derive(Debug)]
+struct HoltWinters {
+    /// The observed values, where missing values are `NaN`.
+    y: Vec<f64>,
+    /// The length of the seasonal pattern. The model is not
+    /// seasonal when this is less than 2.
+    m: usize,
+}
+
+impl HoltWinters {
+    fn new(y: Vec<f64>, m: usize) -> Self {
+        Self { y, m }
+    }
+
+    fn seasonal(&self) -> bool {
+        self.m > 1
+    }
+
+    /// Fit the model to the observed values and return the fitted values
+    /// followed by `h` forecast values, or `None` if there are not
+    /// enough observed values to fit the model.
+    fn forecast(&self, h: usize) -> Option<Vec<f64>> {
+        let (y, m) = (&self.y, self.m);
+        if y.len() < 2 || (self.seasonal() && y.len() < m) {
+            return None;
+        }
+
+        // Initial guesses for the level, trend and seasonal factors. Any
+        // missing values are skipped, as these are only starting points
+        // for the optimisation.
+        let l0 = if self.seasonal() {
+            y[..m].iter().filter(|v| !v.is_nan()).sum::<f64>() / m as f64
+        } else {
+            y[0]
+        };
+        let b0 = if self.seasonal() {
+            (0..m)
+                .filter(|&i| m + i < y.len())
+                .map(|i| y[m + i] - y[i])
+                .filter(|v| !v.is_nan())
+                .sum::<f64>()
+                / (m * m) as f64
+        } else if y[1].is_nan() {
+            0.0
+        } else {
+            y[1] - y[0]
+        };
+
+        let mut params = vec![0.0, 0.0, 0.0, 0.0, l0, b0];
+        if self.seasonal() {
+            params.extend(y[..m].iter().map(|v| if v.is_nan() { 1.0 } else { v / l0 }));
+        }
+
+        let guesses = || {
+            let mut v = GUESS_LOWER;
+            std::iter::from_fn(move || {
+                let guess = (v < GUESS_UPPER).then_some(v);
+                v += GUESS_STEP;
+                guess
+            })
+        };
+
+        // Search for the parameters with the minimum error, starting from
+        // each point in a grid of guesses for the smoothing parameters.
+        let mut best: Option<(f64, Vec<f64>)> = None;
+        for alpha in guesses() {
+            for beta in guesses() {
+                for gamma in guesses() {
+                    for phi in guesses() {
+                        params[..4].copy_from_slice(&[alpha, beta, gamma, phi]);
+                        let (sse, p) =
+                            nelder_mead::optimize(|p| self.sse(p), &params, EPSILON, 1.0);
+                        match &best {
+                            Some((min, _)) if *min <= sse => {}
+                            _ => best = Some((sse, p)),
+                        }
+                    }
+                }
+            }
+        }
+
+        best.map(|(_, params)| self.predict(h, &params))
+    }
+
+    /// Return the one-step-ahead predictions of the model for the observed
+    /// values, followed by `h` forecast values.
+    fn predict(&self, h: usize, params: &[f64]) -> Vec<f64> {
+        // Constrain the smoothing and damping factors to [0, 1]
+        let [alpha, beta, gamma, phi] =
+            [params[0], params[1], params[2], params[3]].map(|v| v.clamp(0.0, 1.0));
+        let (mut level, mut trend) = (params[4], params[5]);
+        let mut seasonals = params[6..].to_vec();
+
+        let len = self.y.len() + h;
+        let mut predictions = Vec::with_capacity(len);
+        predictions.push(self.y[0]);
+        for t in 1..len {
+            let s = if self.seasonal() {
+                seasonals[t % self.m]
+            } else {
+                1.0
+            };
+
+            let base = level + phi * trend;
+            let prediction = base * s;
+            predictions.push(prediction);
+
+            // Missing and future values are replaced by the prediction.
+            let v = match self.y.get(t) {
+                Some(v) if !v.is_nan() => *v,
+                _ => prediction,
+            };
+
+            let next_level = alpha * (v / s) + (1.0 - alpha) * base;
+            trend = beta * (next_level - level) + (1.0 - beta) * phi * trend;
+            level = next_level;
+            if self.seasonal() {
+                seasonals[t % self.m] = gamma * (v / base) + (1.0 - gamma) * s;
+            }
+        }
+
+        predictions
+    }
+
+    /// The sum of squared errors of the model for the specified parameters.
+    fn sse(&self, params: &[f64]) -> f64 {
+        self.predict(0, params)
+            .iter()
+            .zip(&self.y)
+            .filter(|(_, y)| !y.is_nan())
+            .map(|(p, y)| {
+                if p.is_nan() {
+                    // Penalise parameters which produce NaN predictions
+                    f64::INFINITY
+                } else {
+                    (p - y).powi(2)
+                }
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_forecast_trend() {
+        let y = (0..10).map(|v| v as f64 * 2.0 + 1.0).collect::<Vec<_>>();
+        let forecast = HoltWinters::new(y, 0).forecast(3).unwrap();
+        assert_eq!(forecast.len(), 13);
+        for (i, v) in forecast[10..].iter().enumerate() {
+            let expected = (10 + i) as f64 * 2.0 + 1.0;
+            assert!((v - expected).abs() < 0.5, "{v} != {expected}");
+        }
+    }
+
+    #[test]
+    fn test_forecast_seasonal() {
+        let pattern = [10.0, 20.0, 15.0, 5.0];
+        let y = pattern.iter().cycle().take(16).copied().collect::<Vec<_>>();
+        let forecast = HoltWinters::new(y, 4).forecast(4).unwrap();
+        for (v, expected) in forecast[16..].iter().zip(pattern) {
+            assert!((v - expected).abs() < 1.0, "{v} != {expected}");
+        }
+    }
+
+    #[test]
+    fn test_forecast_insufficient_data() {
+        assert!(HoltWinters::new(vec![1.0], 0).forecast(3).is_none());
+        assert!(HoltWinters::new(vec![1.0, 2.0, 3.0], 4)
+            .forecast(3)
+            .is_none());
+    }
+}
@@ -0,0 +1,235 @@
+use crate::{error, NUMERICS};
+use arrow::array::{Array, ArrayRef, Float64Array, TimestampNanosecondArray};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, IntervalUnit, TimeUnit};
+use datafusion::common::{downcast_value, DataFusionError, Result, ScalarValue};
+use datafusion::logical_expr::{PartitionEvaluator, Signature, TypeSignature, Volatility};
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+/// The name of the integral window function.
+pub(super) const NAME: &str = "integral_window";
+
+/// Valid signatures for the integral window function.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| {
+    Signature::one_of(
+        NUMERICS
+            .iter()
+            .map(|dt| {
+                TypeSignature::Exact(vec![
+                    dt.clone(),
+                    DataType::Timestamp(TimeUnit::Nanosecond, None),
+                    DataType::Interval(IntervalUnit::MonthDayNano),
+                    DataType::Interval(IntervalUnit::MonthDayNano),
+                    DataType::Timestamp(TimeUnit::Nanosecond, None),
+                ])
+            })
+            .collect(),
+        Volatility::Immutable,
+    )
+});
+
+/// Calculate the return type given the function signature. The integral
+/// window function always returns a Float64.
+pub(super) fn return_type(_: &[DataType]) -> Result<Arc<DataType>> {
+    Ok(Arc::new(DataType::Float64))
+}
+
+/// Create a new partition_evaluator_factory.
+pub(super) fn partition_evaluator_factory() -> Result<Box<dyn PartitionEvaluator>> {
+    Ok(Box::new(IntegralPartitionEvaluator {}))
+}
+
+/// PartitionEvaluator which returns the area under the curve, in the
+/// provided units, that each row contributes to the `INTEGRAL` of the
+/// `GROUP BY time()` window containing the row. The rows must be sorted
+/// by time.
+///
+/// Summing the result for each window matches the InfluxQL OG integral
+/// reducer: the value at the end of a window is interpolated from the
+/// points either side of it, the area up to the end of the window is
+/// attributed to the last row of the window, and the remaining area is
+/// attributed to the first row of the following window containing a point.
+/// A window whose only points are at the start of the window produces no
+/// result.
+#[derive(Debug)]
+struct IntegralPartitionEvaluator {}
+
+impl PartitionEvaluator for IntegralPartitionEvaluator {
+    fn evaluate_all(&mut self, values: &[ArrayRef], num_rows: usize) -> Result<Arc<dyn Array>> {
+        assert_eq!(values.len(), 5);
+
+        let array = cast(&values[0], &DataType::Float64)?;
+        let array = downcast_value!(array, Float64Array);
+        let times = Arc::clone(&values[1]);
+        let times = downcast_value!(times, TimestampNanosecondArray);
+
+        // INVARIANT:
+        // The planner guarantees that the unit, stride and origin arguments
+        // are always literals.
+        let unit = crate::unit_nanos("integral", &ScalarValue::try_from_array(&values[2], 0)?)?;
+        let unit = unit as f64;
+        let stride = crate::unit_nanos(NAME, &ScalarValue::try_from_array(&values[3], 0)?)?;
+        let origin = match ScalarValue::try_from_array(&values[4], 0)? {
+            ScalarValue::TimestampNanosecond(Some(origin), _) => origin,
+            origin => {
+                return error::internal(format!("{NAME} attempted with invalid origin {origin}"))
+            }
+        };
+        let window_start = |t: i64| t - (t - origin).rem_euclid(stride);
+
+        let mut area: Vec<Option<f64>> = vec![None; num_rows];
+        // The row index, time and value of the previous point, and the end of
+        // the window containing it.
+        let mut prev: Option<(usize, i64, f64, i64)> = None;
+        // The index of the first row of the window containing the previous point.
+        let mut first_row = 0;
+        for idx in 0..num_rows {
+            if array.is_null(idx) || times.is_null(idx) {
+                continue;
+            }
+            let (t, v) = (times.value(idx), array.value(idx));
+
+            area[idx] = Some(match prev {
+                Some((_, prev_t, _, _)) if t == prev_t => 0.0,
+                Some((prev_idx, prev_t, prev_v, window_end)) if t >= window_end => {
+                    // Interpolate the value at the end of the window, and split
+                    // the area between the windows.
+                    let end_v = prev_v
+                        + (v - prev_v) * ((window_end - prev_t) as f64 / (t - prev_t) as f64);
+                    let prev_area = area[prev_idx].get_or_insert(0.0);
+                    *prev_area += 0.5 * (prev_v + end_v) * (window_end - prev_t) as f64 / unit;
+                    first_row = idx;
+                    0.5 * (end_v + v) * (t - window_end) as f64 / unit
+                }
+                Some((_, prev_t, prev_v, _)) => 0.5 * (prev_v + v) * (t - prev_t) as f64 / unit,
+                None => {
+                    first_row = idx;
+                    0.0
+                }
+            });
+
+            let window_end = match prev {
+                Some((_, _, _, window_end)) if t < window_end => window_end,
+                _ => window_start(t) + stride,
+            };
+            prev = Some((idx, t, v, window_end));
+        }
+
+        // If the last point is at the start of its window, the window has no
+        // area and produces no result.
+        if let Some((_, t, _, window_end)) = prev {
+            if t == window_end - stride {
+                area[first_row..].fill(None);
+            }
+        }
+
+        Ok(Arc::new(Float64Array::from(area)))
+    }
+
+    fn uses_window_frame(&self) -> bool {
+        false
+    }
+
+    fn include_rank(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SECOND: i64 = 1_000_000_000;
+
+    /// Evaluate the function for `points`, returning the sum of the areas of
+    /// the rows in each window, like the planner.
+    fn integral(points: &[(i64, Option<f64>)], unit: i64, stride: i64) -> Vec<(i64, f64)> {
+        let values: ArrayRef = Arc::new(Float64Array::from_iter(points.iter().map(|(_, v)| *v)));
+        let times: ArrayRef = Arc::new(TimestampNanosecondArray::from_iter_values(
+            points.iter().map(|(t, _)| *t),
+        ));
+        let n = points.len();
+        let args = vec![
+            values,
+            times,
+            ScalarValue::new_interval_mdn(0, 0, unit).to_array_of_size(n),
+            ScalarValue::new_interval_mdn(0, 0, stride).to_array_of_size(n),
+            ScalarValue::TimestampNanosecond(Some(0), None).to_array_of_size(n),
+        ];
+
+        let area = partition_evaluator_factory()
+            .unwrap()
+            .evaluate_all(&args, n)
+            .unwrap();
+        let area = area.as_any().downcast_ref::<Float64Array>().unwrap();
+
+        let mut windows: Vec<(i64, f64)> = vec![];
+        for (idx, (t, _)) in points.iter().enumerate() {
+            if area.is_null(idx) {
+                continue;
+            }
+            let start = t - t.rem_euclid(stride);
+            match windows.last_mut() {
+                Some((s, sum)) if *s == start => *sum += area.value(idx),
+                _ => windows.push((start, area.value(idx))),
+            }
+        }
+        windows
+    }
+
+    #[test]
+    fn test_integral_window() {
+        // The expected results are from InfluxQL OG, for
+        // SELECT integral(v) FROM m GROUP BY time(20s)
+        let points = [
+            (0, Some(20.0)),
+            (10 * SECOND, Some(10.0)),
+            (30 * SECOND, Some(30.0)),
+            (50 * SECOND, Some(10.0)),
+        ];
+        assert_eq!(
+            integral(&points, SECOND, 20 * SECOND),
+            vec![(0, 300.0), (20 * SECOND, 500.0), (40 * SECOND, 150.0)]
+        );
+
+        // NULL values are skipped
+        let points = [
+            (0, Some(20.0)),
+            (5 * SECOND, None),
+            (10 * SECOND, Some(10.0)),
+            (30 * SECOND, Some(30.0)),
+            (50 * SECOND, Some(10.0)),
+        ];
+        assert_eq!(
+            integral(&points, SECOND, 20 * SECOND),
+            vec![(0, 300.0), (20 * SECOND, 500.0), (40 * SECOND, 150.0)]
+        );
+
+        // The area across empty windows is attributed to the window of the
+        // following point.
+        let points = [(10 * SECOND, Some(10.0)), (70 * SECOND, Some(40.0))];
+        assert_eq!(
+            integral(&points, 10 * SECOND, 20 * SECOND),
+            vec![(0, 12.5), (60 * SECOND, 137.5)]
+        );
+
+        // A window whose last point is at the start of the window produces
+        // no result, and neither does a point at the same time as the
+        // previous point.
+        let points = [
+            (0, Some(10.0)),
+            (10 * SECOND, Some(20.0)),
+            (20 * SECOND, Some(30.0)),
+            (20 * SECOND, Some(30.0)),
+        ];
+        assert_eq!(integral(&points, SECOND, 20 * SECOND), vec![(0, 400.0)]);
+
+        // A single point that is not at the start of its window has no area
+        assert_eq!(
+            integral(&[(5 * SECOND, Some(10.0))], SECOND, 20 * SECOND),
+            vec![(0, 0.0)]
+        );
+        assert!(integral(&[(0, Some(10.0))], SECOND, 20 * SECOND).is_empty());
+    }
+}
@@ -0,0 +1,113 @@
+use super::kaufmans_efficiency_ratio::KaufmansEfficiencyRatio;
+use super::technical_analysis::{signature, Indicator, IndicatorPartitionEvaluator, WarmupType};
+use datafusion::common::Result;
+use datafusion::logical_expr::{PartitionEvaluator, Signature};
+use once_cell::sync::Lazy;
+
+/// The name of the kaufmans adaptive moving average window function.
+pub(super) const NAME: &str = "kaufmans_adaptive_moving_average";
+
+/// Valid signatures for the kaufmans adaptive moving average window function.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| signature(false));
+
+/// The smoothing factor of the fastest moving average, with a period of 2.
+const FAST_ALPHA: f64 = 2.0 / 3.0;
+
+/// The smoothing factor of the slowest moving average, with a period of 30.
+const SLOW_ALPHA: f64 = 2.0 / 31.0;
+
+/// Create a new partition_evaluator_factory.
+pub(super) fn partition_evaluator_factory() -> Result<Box<dyn PartitionEvaluator>> {
+    Ok(Box::new(IndicatorPartitionEvaluator::new(
+        NAME,
+        WarmupType::None,
+        |period, _| Box::new(KaufmansAdaptiveMovingAverage::new(period)),
+    )))
+}
+
+/// Indicator which returns Kaufman's adaptive moving average of the input,
+/// which is an exponential moving average whose smoothing factor is scaled
+/// by the efficiency ratio of the last `period` changes.
+///
+/// Until the efficiency ratio is of `period` changes, the average restarts
+/// from the previous value for each value added.
+#[derive(Debug)]
+struct KaufmansAdaptiveMovingAverage {
+    ker: KaufmansEfficiencyRatio,
+    last: f64,
+}
+
+impl KaufmansAdaptiveMovingAverage {
+    fn new(period: usize) -> Self {
+        Self {
+            ker: KaufmansEfficiencyRatio::new(period),
+            last: 0.0,
+        }
+    }
+}
+
+impl Indicator for KaufmansAdaptiveMovingAverage {
+    fn add(&mut self, v: f64) -> Option<f64> {
+        if !self.ker.warmed() {
+            self.last = self.ker.newest().unwrap_or_default();
+        }
+        let er = self.ker.update(v);
+        let alpha = (er * (FAST_ALPHA - SLOW_ALPHA) + SLOW_ALPHA).powi(2);
+        self.last += alpha * (v - self.last);
+        Some(self.last)
+    }
+
+    fn warm_count(&self) -> usize {
+        self.ker.warm_count()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::window::technical_analysis::assert_indicator;
+
+    #[test]
+    fn test_kaufmans_adaptive_moving_average() {
+        // The expected results follow InfluxQL OG.
+        assert_indicator(
+            partition_evaluator_factory,
+            3,
+            -1,
+            None,
+            &[
+                None,
+                None,
+                None,
+                Some(11.651327706478591),
+                Some(11.697459191195366),
+                None,
+                Some(12.414471301378923),
+                Some(13.627141166959994),
+                Some(14.359419969626662),
+                Some(14.415536190714146),
+                Some(14.572348281515294),
+            ],
+        );
+
+        assert_indicator(
+            partition_evaluator_factory,
+            3,
+            0,
+            None,
+            &[
+                Some(4.444444444444443),
+                Some(10.88888888888889),
+                Some(11.670491714676459),
+                Some(11.651327706478591),
+                Some(11.697459191195366),
+                None,
+                Some(12.414471301378923),
+                Some(13.627141166959994),
+                Some(14.359419969626662),
+                Some(14.415536190714146),
+                Some(14.572348281515294),
+            ],
+        );
+    }
+}
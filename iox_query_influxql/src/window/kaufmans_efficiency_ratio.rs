@@ -0,0 +1,143 @@
+use super::technical_analysis::{signature, Indicator, IndicatorPartitionEvaluator, WarmupType};
+use datafusion::common::Result;
+use datafusion::logical_expr::{PartitionEvaluator, Signature};
+use once_cell::sync::Lazy;
+use std::collections::VecDeque;
+
+/// The name of the kaufmans efficiency ratio window function.
+pub(super) const NAME: &str = "kaufmans_efficiency_ratio";
+
+/// Valid signatures for the kaufmans efficiency ratio window function.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| signature(false));
+
+/// Create a new partition_evaluator_factory.
+pub(super) fn partition_evaluator_factory() -> Result<Box<dyn PartitionEvaluator>> {
+    Ok(Box::new(IndicatorPartitionEvaluator::new(
+        NAME,
+        WarmupType::None,
+        |period, _| Box::new(KaufmansEfficiencyRatio::new(period)),
+    )))
+}
+
+/// Indicator which returns Kaufman's efficiency ratio of the input, which is
+/// the absolute change over the last `period` changes divided by the sum of
+/// the absolute values of those changes.
+///
+/// As in InfluxQL OG, the indicator starts with `period` values of zero, so
+/// the ratio is only meaningful once `period + 1` values have been added.
+#[derive(Debug)]
+pub(super) struct KaufmansEfficiencyRatio {
+    /// The last `period` values, and the absolute change from the value
+    /// before each of them, oldest first.
+    points: VecDeque<(f64, f64)>,
+    /// The sum of the absolute changes of `points`.
+    noise: f64,
+    /// The number of values added, up to `period + 1`.
+    count: usize,
+}
+
+impl KaufmansEfficiencyRatio {
+    pub(super) fn new(period: usize) -> Self {
+        Self {
+            points: VecDeque::from(vec![(0.0, 0.0); period]),
+            noise: 0.0,
+            count: 0,
+        }
+    }
+
+    /// Add the next value, returning the efficiency ratio.
+    pub(super) fn update(&mut self, v: f64) -> f64 {
+        let (oldest, oldest_change) = self.points.pop_front().unwrap_or_default();
+        let newest = self.newest().unwrap_or(oldest);
+
+        let signal = (v - oldest).abs();
+        let change = (v - newest).abs();
+        self.noise -= oldest_change;
+        self.noise += change;
+        self.points.push_back((v, change));
+
+        if !self.warmed() {
+            self.count += 1;
+        }
+
+        // When the values have not changed, there is no movement to be
+        // efficient with.
+        if signal == 0.0 || self.noise == 0.0 {
+            0.0
+        } else {
+            signal / self.noise
+        }
+    }
+
+    /// The last value added, if any.
+    pub(super) fn newest(&self) -> Option<f64> {
+        self.points.back().map(|(v, _)| *v)
+    }
+
+    /// Returns `true` once the ratio is of `period` changes.
+    pub(super) fn warmed(&self) -> bool {
+        self.count == self.points.len() + 1
+    }
+}
+
+impl Indicator for KaufmansEfficiencyRatio {
+    fn add(&mut self, v: f64) -> Option<f64> {
+        Some(self.update(v))
+    }
+
+    fn warm_count(&self) -> usize {
+        self.points.len()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::window::technical_analysis::assert_indicator;
+
+    #[test]
+    fn test_kaufmans_efficiency_ratio() {
+        // The expected results follow InfluxQL OG.
+        assert_indicator(
+            partition_evaluator_factory,
+            3,
+            -1,
+            None,
+            &[
+                None,
+                None,
+                None,
+                Some(0.6666666666666666),
+                Some(0.2),
+                None,
+                Some(0.6666666666666666),
+                Some(0.6666666666666666),
+                Some(0.6666666666666666),
+                Some(0.2),
+                Some(0.2),
+            ],
+        );
+
+        // Until `period + 1` values have been added, the ratio includes the
+        // initial values of zero.
+        assert_indicator(
+            partition_evaluator_factory,
+            3,
+            0,
+            None,
+            &[
+                Some(1.0),
+                Some(1.0),
+                Some(0.8461538461538461),
+                Some(0.6666666666666666),
+                Some(0.2),
+                None,
+                Some(0.6666666666666666),
+                Some(0.6666666666666666),
+                Some(0.6666666666666666),
+                Some(0.2),
+                Some(0.2),
+            ],
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//! An implementation of the [Nelder-Mead] downhill simplex method, used
+//! to find the parameters that minimise the error of the `holt_winters`
+//! model.
+//!
+//! [Nelder-Mead]: https://en.wikipedia.org/wiki/Nelder%E2%80%93Mead_method
+
+/// The maximum number of iterations performed by [`optimize`].
+const MAX_ITERATIONS: usize = 1000;
+
+/// Reflection coefficient.
+const ALPHA: f64 = 1.0;
+/// Expansion coefficient.
+const GAMMA: f64 = 2.0;
+/// Contraction coefficient.
+const RHO: f64 = 0.5;
+/// Shrink coefficient.
+const SIGMA: f64 = 0.5;
+
+/// Find the point that minimises `f`, starting from `start`.
+///
+/// The initial simplex is constructed by offsetting each dimension of
+/// `start` by `scale`. The search stops when the difference between the
+/// best and worst value of the simplex is less than `epsilon`, or after
+/// a fixed number of iterations.
+///
+/// Returns the minimum value of `f` found, and the point at which it
+/// was found.
+pub(super) fn optimize<F>(f: F, start: &[f64], epsilon: f64, scale: f64) -> (f64, Vec<f64>)
+where
+    F: Fn(&[f64]) -> f64,
+{
+    let n = start.len();
+
+    // The vertices of the simplex and the value of `f` at each vertex.
+    let mut points: Vec<(f64, Vec<f64>)> = Vec::with_capacity(n + 1);
+    points.push((f(start), start.to_vec()));
+    for i in 0..n {
+        let mut p = start.to_vec();
+        p[i] += scale;
+        points.push((f(&p), p));
+    }
+
+    for _ in 0..MAX_ITERATIONS {
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let (best, worst) = (points[0].0, points[n].0);
+        if (worst - best).abs() < epsilon {
+            break;
+        }
+
+        // The centroid of all vertices except the worst.
+        let mut centroid = vec![0.0; n];
+        for (_, p) in &points[..n] {
+            for (c, v) in centroid.iter_mut().zip(p) {
+                *c += v / n as f64;
+            }
+        }
+
+        let along = |coef: f64| -> Vec<f64> {
+            centroid
+                .iter()
+                .zip(&points[n].1)
+                .map(|(c, w)| c + coef * (w - c))
+                .collect()
+        };
+
+        // Reflection
+        let reflected = along(-ALPHA);
+        let reflected_value = f(&reflected);
+        if reflected_value < points[n - 1].0 && reflected_value >= best {
+            points[n] = (reflected_value, reflected);
+            continue;
+        }
+
+        // Expansion
+        if reflected_value < best {
+            let expanded = along(-GAMMA);
+            let expanded_value = f(&expanded);
+            points[n] = if expanded_value < reflected_value {
+                (expanded_value, expanded)
+            } else {
+                (reflected_value, reflected)
+            };
+            continue;
+        }
+
+        // Contraction
+        let contracted = along(RHO);
+        let contracted_value = f(&contracted);
+        if contracted_value < worst {
+            points[n] = (contracted_value, contracted);
+            continue;
+        }
+
+        // Shrink all vertices towards the best.
+        let best_point = points[0].1.clone();
+        for (value, p) in points.iter_mut().skip(1) {
+            for (v, b) in p.iter_mut().zip(&best_point) {
+                *v = b + SIGMA * (*v - b);
+            }
+            *value = f(p);
+        }
+    }
+
+    points.sort_by(|a, b| a.0.total_cmp(&b.0));
+    points.swap_remove(0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_optimize() {
+        // f(x, y) = (x - 1)^2 + (y + 2)^2 has a minimum of 0 at (1, -2)
+        let (min, p) = optimize(
+            |p| (p[0] - 1.0).powi(2) + (p[1] + 2.0).powi(2),
+            &[0.0, 0.0],
+            1e-10,
+            1.0,
+        );
+        assert!(min < 1e-6, "{min}");
+        assert!((p[0] - 1.0).abs() < 1e-3, "{p:?}");
+        assert!((p[1] + 2.0).abs() < 1e-3, "{p:?}");
+    }
+}
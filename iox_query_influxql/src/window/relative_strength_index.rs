@@ -0,0 +1,130 @@
+use super::technical_analysis::{
+    signature, Ema, Indicator, IndicatorPartitionEvaluator, WarmupType,
+};
+use datafusion::common::Result;
+use datafusion::logical_expr::{PartitionEvaluator, Signature};
+use once_cell::sync::Lazy;
+
+/// The name of the relative strength index window function.
+pub(super) const NAME: &str = "relative_strength_index";
+
+/// Valid signatures for the relative strength index window function.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| signature(true));
+
+/// Create a new partition_evaluator_factory.
+pub(super) fn partition_evaluator_factory() -> Result<Box<dyn PartitionEvaluator>> {
+    Ok(Box::new(IndicatorPartitionEvaluator::new(
+        NAME,
+        WarmupType::Exponential,
+        |period, warmup| Box::new(RelativeStrengthIndex::new(period, warmup)),
+    )))
+}
+
+/// Indicator which returns the relative strength index of the input, which
+/// compares the average gain to the average loss between consecutive values.
+///
+/// The averages use Wilder's smoothing, which is an exponential moving
+/// average with a smoothing factor of `1 / period`, warmed up over
+/// `period + 1` values. As in InfluxQL OG, the first value is treated as a
+/// change from zero.
+#[derive(Debug)]
+struct RelativeStrengthIndex {
+    gain: Ema,
+    loss: Ema,
+    last: f64,
+}
+
+impl RelativeStrengthIndex {
+    fn new(period: usize, warmup: WarmupType) -> Self {
+        let ema = Ema::with_alpha(period + 1, 1.0 / period as f64, warmup);
+        Self {
+            gain: ema.clone(),
+            loss: ema,
+            last: 0.0,
+        }
+    }
+}
+
+impl Indicator for RelativeStrengthIndex {
+    fn add(&mut self, v: f64) -> Option<f64> {
+        let gain = self.gain.add((v - self.last).max(0.0));
+        let loss = self.loss.add((self.last - v).max(0.0));
+        self.last = v;
+        Some(100.0 - 100.0 / (1.0 + gain / loss))
+    }
+
+    fn warm_count(&self) -> usize {
+        self.gain.warm_count()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::window::technical_analysis::assert_indicator;
+
+    #[test]
+    fn test_relative_strength_index() {
+        // The expected results follow InfluxQL OG.
+        assert_indicator(
+            partition_evaluator_factory,
+            3,
+            -1,
+            Some("exponential"),
+            &[
+                None,
+                None,
+                None,
+                Some(95.0413223140496),
+                Some(85.50185873605949),
+                None,
+                Some(88.85714285714286),
+                Some(92.6726162517614),
+                Some(79.12572688991378),
+                Some(64.89597894909959),
+                Some(80.59765935689126),
+            ],
+        );
+
+        assert_indicator(
+            partition_evaluator_factory,
+            3,
+            -1,
+            Some("simple"),
+            &[
+                None,
+                None,
+                None,
+                Some(93.75),
+                Some(83.33333333333334),
+                None,
+                Some(87.5),
+                Some(92.0),
+                Some(77.96610169491527),
+                Some(63.448275862068975),
+                Some(80.1125703564728),
+            ],
+        );
+
+        // The first value is a gain from zero.
+        assert_indicator(
+            partition_evaluator_factory,
+            3,
+            0,
+            Some("exponential"),
+            &[
+                Some(100.0),
+                Some(100.0),
+                Some(93.61702127659575),
+                Some(95.0413223140496),
+                Some(85.50185873605949),
+                None,
+                Some(88.85714285714286),
+                Some(92.6726162517614),
+                Some(79.12572688991378),
+                Some(64.89597894909959),
+                Some(80.59765935689126),
+            ],
+        );
+    }
+}
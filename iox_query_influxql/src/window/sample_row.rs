@@ -0,0 +1,147 @@
+use crate::error;
+use arrow::array::{Array, ArrayRef, BooleanArray, Int64Array};
+use arrow::datatypes::DataType;
+use datafusion::common::{downcast_value, DataFusionError, Result};
+use datafusion::logical_expr::{PartitionEvaluator, Signature, TypeSignature, Volatility};
+use once_cell::sync::Lazy;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::sync::Arc;
+
+/// The name of the sample_row window function.
+pub(super) const NAME: &str = "sample_row";
+
+/// Valid signatures for the sample_row window function.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| {
+    Signature::one_of(
+        vec![TypeSignature::Exact(vec![DataType::Int64])],
+        Volatility::Volatile,
+    )
+});
+
+/// Calculate the return type given the function signature. Sample_row
+/// always returns a Boolean.
+pub(super) fn return_type(_: &[DataType]) -> Result<Arc<DataType>> {
+    Ok(Arc::new(DataType::Boolean))
+}
+
+/// Create a new partition_evaluator_factory.
+///
+/// Each partition is sampled using a random number generator seeded from
+/// the operating system, so repeated queries may return different samples,
+/// as they do in the original influxdb implementation of sample.
+pub(super) fn partition_evaluator_factory() -> Result<Box<dyn PartitionEvaluator>> {
+    Ok(Box::new(SampleRowPartitionEvaluator {
+        rng: StdRng::from_entropy(),
+    }))
+}
+
+/// PartitionEvaluator which returns `true` for the rows of a random
+/// sample of N rows of the partition, where N is the argument.
+///
+/// This evaluator samples the entire partition, any data that should
+/// not be included must be filtered out before evaluating the window
+/// function.
+#[derive(Debug)]
+struct SampleRowPartitionEvaluator {
+    /// The random number generator used to sample the partition.
+    rng: StdRng,
+}
+
+impl PartitionEvaluator for SampleRowPartitionEvaluator {
+    fn evaluate_all(&mut self, values: &[ArrayRef], num_rows: usize) -> Result<Arc<dyn Array>> {
+        assert_eq!(values.len(), 1);
+
+        let array = Arc::clone(&values[0]);
+        let n = match array.data_type() {
+            DataType::Int64 => downcast_value!(array, Int64Array)
+                .iter()
+                .flatten()
+                .next()
+                .unwrap_or_default(),
+            dt => {
+                return error::internal(format!("invalid data type ({dt}) for SAMPLE n argument"))
+            }
+        };
+
+        let mut selected = vec![false; num_rows];
+        for idx in sample_indices(&mut self.rng, num_rows, n.max(0) as usize) {
+            selected[idx] = true;
+        }
+        Ok(Arc::new(BooleanArray::from(selected)))
+    }
+
+    fn supports_bounded_execution(&self) -> bool {
+        false
+    }
+
+    fn uses_window_frame(&self) -> bool {
+        false
+    }
+
+    fn include_rank(&self) -> bool {
+        false
+    }
+}
+
+/// Select `n` distinct row indices from a partition of `len` rows using
+/// reservoir sampling, which gives each row an equal chance of being
+/// selected, as does the original influxdb implementation of sample.
+fn sample_indices<R: Rng>(rng: &mut R, len: usize, n: usize) -> Vec<usize> {
+    let mut reservoir = Vec::with_capacity(n.min(len));
+    for idx in 0..len {
+        if reservoir.len() < n {
+            reservoir.push(idx);
+        } else {
+            let j = rng.gen_range(0..=idx);
+            if j < n {
+                reservoir[j] = idx;
+            }
+        }
+    }
+    reservoir
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A fixed seed, so that the tests are deterministic.
+    const SEED: u64 = 0x5A3F_1E22_C0FF_EE00;
+
+    #[test]
+    fn test_sample_indices() {
+        let mut rng = StdRng::seed_from_u64(SEED);
+
+        // All rows are selected when the partition is not larger than the sample
+        assert_eq!(sample_indices(&mut rng, 3, 5), vec![0, 1, 2]);
+        assert_eq!(sample_indices(&mut rng, 3, 3), vec![0, 1, 2]);
+        assert!(sample_indices(&mut rng, 3, 0).is_empty());
+
+        // Otherwise n distinct rows are selected
+        let mut got = sample_indices(&mut rng, 100, 5);
+        assert_eq!(got.len(), 5);
+        got.sort_unstable();
+        got.dedup();
+        assert_eq!(got.len(), 5);
+        assert!(got.iter().all(|&idx| idx < 100));
+
+        // and the sample is determined by the seed
+        assert_eq!(
+            sample_indices(&mut StdRng::seed_from_u64(SEED), 100, 5),
+            sample_indices(&mut StdRng::seed_from_u64(SEED), 100, 5)
+        );
+    }
+
+    #[test]
+    fn test_sample_row() {
+        let mut evaluator = SampleRowPartitionEvaluator {
+            rng: StdRng::seed_from_u64(SEED),
+        };
+        let n: ArrayRef = Arc::new(Int64Array::from(vec![2; 10]));
+        let selected = evaluator.evaluate_all(&[n], 10).unwrap();
+        let selected = selected.as_any().downcast_ref::<BooleanArray>().unwrap();
+        assert_eq!(selected.len(), 10);
+        assert_eq!(selected.true_count(), 2);
+    }
+}
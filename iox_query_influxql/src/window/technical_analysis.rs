@@ -0,0 +1,308 @@
+//! Shared implementation of the technical analysis window functions, such as
+//! `exponential_moving_average` and `relative_strength_index`.
+//!
+//! Each technical analysis function is implemented as an [`Indicator`], which
+//! is fed the non-null values of the partition in time order. The
+//! [`IndicatorPartitionEvaluator`] is responsible for parsing the common
+//! `period`, `hold_period` and `warmup_type` arguments, and for suppressing the
+//! output of the indicator until the hold period has elapsed.
+
+use crate::{error, NUMERICS};
+use arrow::array::{Array, ArrayRef, Float64Array};
+use arrow::compute::cast;
+use arrow::datatypes::DataType;
+use datafusion::common::{downcast_value, DataFusionError, Result, ScalarValue};
+use datafusion::logical_expr::{PartitionEvaluator, Signature, TypeSignature, Volatility};
+use std::sync::Arc;
+
+/// Returns the signature of a technical analysis window function, which
+/// accepts a numeric value, an integer period and an integer hold period,
+/// followed by the warmup type, when `with_warmup` is `true`.
+pub(super) fn signature(with_warmup: bool) -> Signature {
+    Signature::one_of(
+        NUMERICS
+            .iter()
+            .map(|dt| {
+                let mut args = vec![dt.clone(), DataType::Int64, DataType::Int64];
+                if with_warmup {
+                    args.push(DataType::Utf8);
+                }
+                TypeSignature::Exact(args)
+            })
+            .collect(),
+        Volatility::Immutable,
+    )
+}
+
+/// Calculate the return type given the function signature. Technical
+/// analysis functions always return a Float64.
+pub(super) fn return_type(_: &[DataType]) -> Result<Arc<DataType>> {
+    Ok(Arc::new(DataType::Float64))
+}
+
+/// The method used to calculate the initial values of an exponential
+/// moving average, before it has received `period` values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum WarmupType {
+    /// Use an exponential moving average from the first value.
+    Exponential,
+    /// Use a simple moving average of the first `period` values.
+    Simple,
+    /// Do not use a moving average.
+    None,
+}
+
+impl WarmupType {
+    fn try_from_str(name: &str, s: &str) -> Result<Self> {
+        match s {
+            "exponential" => Ok(Self::Exponential),
+            "simple" => Ok(Self::Simple),
+            "none" => Ok(Self::None),
+            _ => error::internal(format!("{name} attempted with invalid warmup type {s}")),
+        }
+    }
+}
+
+/// A technical analysis indicator, which is calculated from a sequence of
+/// values.
+pub(super) trait Indicator: std::fmt::Debug + Send {
+    /// Add the next value to the indicator, returning the current value of
+    /// the indicator, if one is available.
+    fn add(&mut self, v: f64) -> Option<f64>;
+
+    /// The number of values that must be added before the indicator
+    /// produces stable output, which is the default hold period.
+    fn warm_count(&self) -> usize;
+}
+
+/// A function to create a new [`Indicator`] for the specified period and
+/// warmup type.
+pub(super) type NewIndicator = fn(usize, WarmupType) -> Box<dyn Indicator>;
+
+/// PartitionEvaluator which calculates an [`Indicator`] over the non-null
+/// values of the partition.
+///
+/// The arguments of the window function are the input values, the period,
+/// the hold period and an optional warmup type. A hold period of `-1`
+/// selects the default hold period of the indicator. No values are
+/// returned for the first hold period values of the partition.
+#[derive(Debug)]
+pub(super) struct IndicatorPartitionEvaluator {
+    name: &'static str,
+    default_warmup: WarmupType,
+    new_indicator: NewIndicator,
+}
+
+impl IndicatorPartitionEvaluator {
+    pub(super) fn new(
+        name: &'static str,
+        default_warmup: WarmupType,
+        new_indicator: NewIndicator,
+    ) -> Self {
+        Self {
+            name,
+            default_warmup,
+            new_indicator,
+        }
+    }
+
+    fn integer_arg(&self, array: &ArrayRef, arg: &str) -> Result<i64> {
+        match ScalarValue::try_from_array(array, 0)? {
+            ScalarValue::Int64(Some(v)) => Ok(v),
+            v => error::internal(format!("{} attempted with invalid {arg} {v}", self.name)),
+        }
+    }
+}
+
+impl PartitionEvaluator for IndicatorPartitionEvaluator {
+    fn evaluate_all(&mut self, values: &[ArrayRef], _num_rows: usize) -> Result<Arc<dyn Array>> {
+        assert!(values.len() == 3 || values.len() == 4);
+
+        // INVARIANT:
+        // The planner guarantees that the period, hold period and warmup
+        // type arguments are always literals, and the rewriter has validated
+        // their values.
+        //
+        // See: FieldChecker::check_exponential_moving_average
+        let period = self.integer_arg(&values[1], "period")?;
+        if period < 1 {
+            return error::internal(format!(
+                "{} attempted with invalid period {period}",
+                self.name
+            ));
+        }
+        let hold_period = self.integer_arg(&values[2], "hold period")?;
+        let warmup = match values.get(3) {
+            Some(array) => match ScalarValue::try_from_array(array, 0)? {
+                ScalarValue::Utf8(Some(s)) => WarmupType::try_from_str(self.name, &s)?,
+                v => {
+                    return error::internal(format!(
+                        "{} attempted with invalid warmup type {v}",
+                        self.name
+                    ))
+                }
+            },
+            None => self.default_warmup,
+        };
+
+        let mut indicator = (self.new_indicator)(period as usize, warmup);
+        let hold_period = match hold_period {
+            -1 => indicator.warm_count(),
+            v => v.max(0) as usize,
+        };
+
+        let array = cast(&values[0], &DataType::Float64)?;
+        let array = downcast_value!(array, Float64Array);
+
+        let mut count = 0_usize;
+        let output: Float64Array = array
+            .iter()
+            .map(|v| {
+                let v = indicator.add(v?);
+                count += 1;
+                if count > hold_period {
+                    v
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        Ok(Arc::new(output))
+    }
+
+    fn uses_window_frame(&self) -> bool {
+        false
+    }
+
+    fn include_rank(&self) -> bool {
+        false
+    }
+}
+
+/// An exponential moving average, which is the building block of many of
+/// the technical analysis indicators.
+#[derive(Debug, Clone)]
+pub(super) struct Ema {
+    period: usize,
+    alpha: f64,
+    warmup: WarmupType,
+    count: usize,
+    last: f64,
+}
+
+impl Ema {
+    /// Create a new exponential moving average with the conventional
+    /// smoothing factor of `2 / (period + 1)`.
+    pub(super) fn new(period: usize, warmup: WarmupType) -> Self {
+        Self::with_alpha(period, 2.0 / (period as f64 + 1.0), warmup)
+    }
+
+    /// Create a new exponential moving average with the specified smoothing
+    /// factor.
+    pub(super) fn with_alpha(period: usize, alpha: f64, warmup: WarmupType) -> Self {
+        Self {
+            period,
+            alpha,
+            warmup,
+            count: 0,
+            last: 0.0,
+        }
+    }
+
+    /// Add the next value, returning the current value of the average.
+    pub(super) fn add(&mut self, v: f64) -> f64 {
+        self.last = if self.count == 0 {
+            v
+        } else if self.warmup == WarmupType::Simple && self.count < self.period {
+            (self.last * self.count as f64 + v) / (self.count + 1) as f64
+        } else {
+            self.alpha * v + (1.0 - self.alpha) * self.last
+        };
+        self.count = (self.count + 1).min(self.period);
+        self.last
+    }
+
+    /// Returns `true` if the value of the average should be added to an
+    /// average nested within it. When warming up with a simple moving
+    /// average, a nested average only receives values once the average has
+    /// received `period` values.
+    pub(super) fn feeds_nested(&self) -> bool {
+        self.warmup != WarmupType::Simple || self.count == self.period
+    }
+
+    /// The number of values that must be added before the average
+    /// produces stable output.
+    pub(super) fn warm_count(&self) -> usize {
+        self.period - 1
+    }
+}
+
+/// The input values of the technical analysis function tests.
+#[cfg(test)]
+pub(super) const TEST_VALUES: [Option<f64>; 11] = [
+    Some(10.0),
+    Some(12.0),
+    Some(11.0),
+    Some(14.0),
+    Some(13.0),
+    None,
+    Some(15.0),
+    Some(18.0),
+    Some(17.0),
+    Some(16.0),
+    Some(19.0),
+];
+
+/// Evaluate the window function created by `factory` over [`TEST_VALUES`],
+/// with the specified period, hold period and warmup type arguments, and
+/// assert the result matches `expected`, allowing for rounding errors.
+#[cfg(test)]
+pub(super) fn assert_indicator(
+    factory: fn() -> Result<Box<dyn PartitionEvaluator>>,
+    period: i64,
+    hold_period: i64,
+    warmup: Option<&str>,
+    expected: &[Option<f64>],
+) {
+    let n = TEST_VALUES.len();
+    let mut args: Vec<ArrayRef> = vec![
+        Arc::new(Float64Array::from(TEST_VALUES.to_vec())),
+        ScalarValue::Int64(Some(period)).to_array_of_size(n),
+        ScalarValue::Int64(Some(hold_period)).to_array_of_size(n),
+    ];
+    if let Some(warmup) = warmup {
+        args.push(ScalarValue::from(warmup).to_array_of_size(n));
+    }
+
+    let got = factory().unwrap().evaluate_all(&args, n).unwrap();
+    let got = got.as_any().downcast_ref::<Float64Array>().unwrap();
+    let got = got.iter().collect::<Vec<_>>();
+    assert_eq!(got.len(), expected.len(), "{got:?} != {expected:?}");
+    for (g, e) in got.iter().zip(expected) {
+        match (g, e) {
+            (Some(g), Some(e)) => assert!((g - e).abs() < 1e-9, "{got:?} != {expected:?}"),
+            (g, e) => assert_eq!(g, e, "{got:?} != {expected:?}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ema() {
+        let mut ema = Ema::new(3, WarmupType::Exponential);
+        assert_eq!(ema.add(1.0), 1.0);
+        assert_eq!(ema.add(3.0), 2.0);
+        assert_eq!(ema.add(5.0), 3.5);
+        assert_eq!(ema.warm_count(), 2);
+
+        let mut ema = Ema::new(3, WarmupType::Simple);
+        assert_eq!(ema.add(1.0), 1.0);
+        assert_eq!(ema.add(3.0), 2.0);
+        assert_eq!(ema.add(5.0), 3.0);
+        assert_eq!(ema.add(7.0), 5.0);
+    }
+}
@@ -0,0 +1,123 @@
+use super::technical_analysis::{
+    signature, Ema, Indicator, IndicatorPartitionEvaluator, WarmupType,
+};
+use datafusion::common::Result;
+use datafusion::logical_expr::{PartitionEvaluator, Signature};
+use once_cell::sync::Lazy;
+
+/// The name of the triple exponential derivative window function.
+pub(super) const NAME: &str = "triple_exponential_derivative";
+
+/// Valid signatures for the triple exponential derivative window function.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| signature(true));
+
+/// Create a new partition_evaluator_factory.
+pub(super) fn partition_evaluator_factory() -> Result<Box<dyn PartitionEvaluator>> {
+    Ok(Box::new(IndicatorPartitionEvaluator::new(
+        NAME,
+        WarmupType::Exponential,
+        |period, warmup| Box::new(TripleExponentialDerivative::new(period, warmup)),
+    )))
+}
+
+/// Indicator which returns the triple exponential derivative, or TRIX, of
+/// the input, which is the percentage change of the triple smoothed
+/// exponential moving average, `EMA(EMA(EMA))`.
+///
+/// When warming up with a simple moving average, the derivative is of the
+/// outermost average that has received `period` values.
+#[derive(Debug)]
+struct TripleExponentialDerivative {
+    ema1: Ema,
+    ema2: Ema,
+    ema3: Ema,
+    warmup: WarmupType,
+    last: Option<f64>,
+}
+
+impl TripleExponentialDerivative {
+    fn new(period: usize, warmup: WarmupType) -> Self {
+        Self {
+            ema1: Ema::new(period, warmup),
+            ema2: Ema::new(period, warmup),
+            ema3: Ema::new(period, warmup),
+            warmup,
+            last: None,
+        }
+    }
+}
+
+impl Indicator for TripleExponentialDerivative {
+    fn add(&mut self, v: f64) -> Option<f64> {
+        let mut ema = self.ema1.add(v);
+        if self.ema1.feeds_nested() {
+            ema = self.ema2.add(ema);
+            if self.ema2.feeds_nested() {
+                ema = self.ema3.add(ema);
+            }
+        }
+        self.last
+            .replace(ema)
+            .map(|last| (ema / last - 1.0) * 100.0)
+    }
+
+    fn warm_count(&self) -> usize {
+        // The derivative requires one more value than the smoothed average.
+        1 + match self.warmup {
+            WarmupType::Simple => {
+                self.ema1.warm_count() + self.ema2.warm_count() + self.ema3.warm_count()
+            }
+            _ => self.ema1.warm_count(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::window::technical_analysis::assert_indicator;
+
+    #[test]
+    fn test_triple_exponential_derivative() {
+        // The expected results follow InfluxQL OG.
+        assert_indicator(
+            partition_evaluator_factory,
+            3,
+            -1,
+            Some("exponential"),
+            &[
+                None,
+                None,
+                None,
+                Some(5.35714285714286),
+                Some(5.084745762711873),
+                None,
+                Some(6.048387096774199),
+                Some(8.745247148288971),
+                Some(7.721445221445222),
+                Some(4.8958615093319),
+                Some(5.24110366168129),
+            ],
+        );
+
+        assert_indicator(
+            partition_evaluator_factory,
+            2,
+            -1,
+            Some("simple"),
+            &[
+                None,
+                None,
+                None,
+                None,
+                Some(6.349206349206371),
+                None,
+                Some(7.562189054726365),
+                Some(12.210915818686408),
+                Some(6.9982595951268545),
+                Some(1.5181348628827251),
+                Some(5.18341531974702),
+            ],
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use super::technical_analysis::{
+    signature, Ema, Indicator, IndicatorPartitionEvaluator, WarmupType,
+};
+use datafusion::common::Result;
+use datafusion::logical_expr::{PartitionEvaluator, Signature};
+use once_cell::sync::Lazy;
+
+/// The name of the triple exponential moving average window function.
+pub(super) const NAME: &str = "triple_exponential_moving_average";
+
+/// Valid signatures for the triple exponential moving average window function.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| signature(true));
+
+/// Create a new partition_evaluator_factory.
+pub(super) fn partition_evaluator_factory() -> Result<Box<dyn PartitionEvaluator>> {
+    Ok(Box::new(IndicatorPartitionEvaluator::new(
+        NAME,
+        WarmupType::Exponential,
+        |period, warmup| Box::new(TripleExponentialMovingAverage::new(period, warmup)),
+    )))
+}
+
+/// Indicator which returns the triple exponential moving average of the
+/// input, which is `3 * EMA - 3 * EMA(EMA) + EMA(EMA(EMA))`.
+///
+/// When warming up with a simple moving average, each nested average tracks
+/// the average it is nested within until that average has received `period`
+/// values.
+#[derive(Debug)]
+struct TripleExponentialMovingAverage {
+    ema1: Ema,
+    ema2: Ema,
+    ema3: Ema,
+    warmup: WarmupType,
+}
+
+impl TripleExponentialMovingAverage {
+    fn new(period: usize, warmup: WarmupType) -> Self {
+        Self {
+            ema1: Ema::new(period, warmup),
+            ema2: Ema::new(period, warmup),
+            ema3: Ema::new(period, warmup),
+            warmup,
+        }
+    }
+}
+
+impl Indicator for TripleExponentialMovingAverage {
+    fn add(&mut self, v: f64) -> Option<f64> {
+        let e1 = self.ema1.add(v);
+        let e2 = if self.ema1.feeds_nested() {
+            self.ema2.add(e1)
+        } else {
+            e1
+        };
+        let e3 = if self.ema2.feeds_nested() {
+            self.ema3.add(e2)
+        } else {
+            e2
+        };
+        Some(3.0 * e1 - 3.0 * e2 + e3)
+    }
+
+    fn warm_count(&self) -> usize {
+        // When warming up with a simple moving average, each nested average
+        // is not stable until it has received the stable values of the
+        // average it is nested within.
+        match self.warmup {
+            WarmupType::Simple => {
+                self.ema1.warm_count() + self.ema2.warm_count() + self.ema3.warm_count()
+            }
+            _ => self.ema1.warm_count(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::window::technical_analysis::assert_indicator;
+
+    #[test]
+    fn test_triple_exponential_moving_average() {
+        // The expected results follow InfluxQL OG.
+        assert_indicator(
+            partition_evaluator_factory,
+            3,
+            -1,
+            Some("exponential"),
+            &[
+                None,
+                None,
+                Some(11.25),
+                Some(13.6875),
+                Some(13.3125),
+                None,
+                Some(14.859375),
+                Some(17.765625),
+                Some(17.41796875),
+                Some(16.28515625),
+                Some(18.5849609375),
+            ],
+        );
+
+        assert_indicator(
+            partition_evaluator_factory,
+            2,
+            -1,
+            Some("simple"),
+            &[
+                None,
+                None,
+                None,
+                Some(13.666666666666666),
+                Some(13.074074074074073),
+                None,
+                Some(14.90123456790123),
+                Some(17.93827160493828),
+                Some(17.15912208504801),
+                Some(16.027892089620487),
+                Some(18.8244170096022),
+            ],
+        );
+    }
+}
@@ -6,9 +6,9 @@ use arrow::{
 };
 use data_types::{
     partition_template::TablePartitionTemplateOverride, Column, ColumnSet, ColumnType,
-    ColumnsByName, CompactionLevel, MaxColumnsPerTable, MaxTables, Namespace, NamespaceName,
-    NamespaceSchema, ParquetFile, ParquetFileParams, Partition, PartitionId, SortedColumnSet,
-    Table, TableId, TableSchema, Timestamp, TransitionPartitionId,
+    ColumnsByName, CompactionLevel, DeletePredicate, MaxColumnsPerTable, MaxTables, Namespace,
+    NamespaceName, NamespaceSchema, ParquetFile, ParquetFileParams, Partition, PartitionId,
+    SortedColumnSet, Table, TableId, TableSchema, Timestamp, Tombstone, TransitionPartitionId,
 };
 use datafusion::physical_plan::metrics::Count;
 use datafusion_util::{unbounded_memory_pool, MemoryStream};
@@ -370,6 +370,28 @@ impl TestTable {
         })
     }
 
+    /// Record a delete of the data matching `predicate` in this table.
+    pub async fn create_tombstone(&self, predicate: &DeletePredicate) -> Tombstone {
+        let mut repos = self.catalog.catalog.repositories().await;
+
+        repos
+            .tombstones()
+            .create(self.table.id, predicate)
+            .await
+            .unwrap()
+    }
+
+    /// List the tombstones of this table.
+    pub async fn list_tombstones(&self) -> Vec<Tombstone> {
+        let mut repos = self.catalog.catalog.repositories().await;
+
+        repos
+            .tombstones()
+            .list_by_table_id(self.table.id)
+            .await
+            .unwrap()
+    }
+
     /// Get the TableSchema from the catalog.
     pub async fn catalog_schema(&self) -> TableSchema {
         TableSchema {
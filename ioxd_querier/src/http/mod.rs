@@ -0,0 +1,644 @@
+//! HTTP service implementations for the `querier`.
+
+pub mod prom;
+pub mod v1;
+
+use std::{convert::Infallible, sync::Arc};
+
+use authz::{
+    extract_token, http::AuthorizationHeaderExtension, Action, Authorizer, Permission, Resource,
+};
+use bytes::{Bytes, BytesMut};
+use futures::StreamExt;
+use generated_types::prometheus::read_request::ResponseType;
+use hyper::{
+    header::{ACCEPT, CONTENT_ENCODING, CONTENT_TYPE},
+    Body, Method, Request, Response, StatusCode,
+};
+use influxdb_influxql_parser::parse_statements;
+use iox_query::QueryNamespace;
+use iox_query_influxql::frontend::planner::InfluxQLQueryPlanner;
+use observability_deps::tracing::*;
+use service_common::{planner::Planner, QueryNamespaceProvider};
+use thiserror::Error;
+use trace::{ctx::SpanContext, span::SpanExt};
+use trace_http::ctx::{RequestLogContext, RequestLogContextExt};
+
+use self::prom::{PromReadError, PromReadParams};
+use self::v1::{
+    Epoch, QueryParamsV1, RawQueryParamsV1, ResponseFormat, StatementResult, V1QueryParseError,
+    V1ResponseError,
+};
+
+/// The maximum size of a form-encoded query or (decompressed) remote read
+/// request body.
+const MAX_REQUEST_BYTES: usize = 10 * 1024 * 1024;
+
+/// Errors returned by the `querier` HTTP request handler.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// The requested path has no registered handler.
+    #[error("not found")]
+    NoHandler,
+
+    /// The V1 query parameters are missing or invalid.
+    #[error(transparent)]
+    ParseV1Request(#[from] V1QueryParseError),
+
+    /// The query text could not be parsed as InfluxQL.
+    #[error("error parsing query: {0}")]
+    ParseQuery(String),
+
+    /// The client disconnected.
+    #[error("client disconnected")]
+    ClientHangup(hyper::Error),
+
+    /// The client sent a request body that exceeds the configured maximum.
+    #[error("max request size ({0} bytes) exceeded")]
+    RequestSizeExceeded(usize),
+
+    /// The requested namespace does not exist.
+    #[error("database not found: {0}")]
+    NamespaceNotFound(String),
+
+    /// The query results could not be encoded as a V1 response.
+    #[error(transparent)]
+    EncodeV1Response(#[from] V1ResponseError),
+
+    /// The request has no authentication, but authorization is configured.
+    #[error("authentication required")]
+    Unauthenticated,
+
+    /// The provided authorization is not sufficient to perform the request.
+    #[error("access denied")]
+    Forbidden,
+
+    /// An error occurred verifying the authorization token.
+    #[error("authz error: {0}")]
+    Authz(authz::Error),
+
+    /// An error serving a Prometheus remote read request.
+    #[error(transparent)]
+    PromRead(#[from] PromReadError),
+}
+
+impl Error {
+    /// Convert the error into an appropriate [`StatusCode`] to be returned to
+    /// the end user.
+    pub fn as_status_code(&self) -> StatusCode {
+        match self {
+            Self::NoHandler => StatusCode::NOT_FOUND,
+            Self::ParseV1Request(_) => StatusCode::BAD_REQUEST,
+            Self::ParseQuery(_) => StatusCode::BAD_REQUEST,
+            Self::ClientHangup(_) => StatusCode::BAD_REQUEST,
+            Self::RequestSizeExceeded(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::NamespaceNotFound(_) => StatusCode::NOT_FOUND,
+            Self::EncodeV1Response(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::Unauthenticated => StatusCode::UNAUTHORIZED,
+            Self::Forbidden => StatusCode::FORBIDDEN,
+            Self::Authz(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::PromRead(e) => e.into(),
+        }
+    }
+}
+
+impl From<authz::Error> for Error {
+    fn from(source: authz::Error) -> Self {
+        match source {
+            authz::Error::Forbidden | authz::Error::InvalidToken => Self::Forbidden,
+            authz::Error::NoToken => Self::Unauthenticated,
+            source => Self::Authz(source),
+        }
+    }
+}
+
+/// This type is responsible for servicing requests to the `querier` HTTP
+/// endpoint.
+///
+/// Requests to some paths may be handled externally by the caller - the IOx
+/// server runner framework takes care of implementing the heath endpoint,
+/// metrics, pprof, etc.
+#[derive(Debug)]
+pub struct HttpDelegate<S> {
+    server: Arc<S>,
+    authz: Option<Arc<dyn Authorizer>>,
+}
+
+impl<S> HttpDelegate<S>
+where
+    S: QueryNamespaceProvider,
+{
+    /// Initialise a new [`HttpDelegate`] executing queries against the
+    /// namespaces provided by `server`.
+    pub fn new(server: Arc<S>, authz: Option<Arc<dyn Authorizer>>) -> Self {
+        Self { server, authz }
+    }
+
+    /// Routes `req` to the appropriate handler, if any, returning the handler
+    /// response.
+    pub async fn route(&self, req: Request<Body>) -> Result<Response<Body>, Error> {
+        match (req.method(), req.uri().path()) {
+            (&Method::GET | &Method::POST, "/query") => {
+                // V1 clients expect errors in the V1 response shape, rather
+                // than the IOx error body.
+                Ok(self.v1_query(req).await.unwrap_or_else(|e| {
+                    debug!(error=%e, "error handling v1 query request");
+                    Response::builder()
+                        .status(e.as_status_code())
+                        .header(CONTENT_TYPE, ResponseFormat::Json.content_type())
+                        .body(Body::from(v1::encode_error(&e.to_string())))
+                        .unwrap()
+                }))
+            }
+            (&Method::POST, "/api/v1/prom/read") => self.prom_read(req).await,
+            _ => Err(Error::NoHandler),
+        }
+    }
+
+    /// Execute an InfluxQL query conforming to the [V1 Query API].
+    ///
+    /// Each statement of the query is executed in order and reported as a
+    /// separate statement result. Errors planning or executing a statement
+    /// are reported within its statement result, as in InfluxDB 1.x.
+    ///
+    /// [V1 Query API]:
+    ///     https://docs.influxdata.com/influxdb/v1.8/tools/api/#query-http-endpoint
+    async fn v1_query(&self, req: Request<Body>) -> Result<Response<Body>, Error> {
+        let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
+        let external_span_ctx: Option<RequestLogContext> = req.extensions().get().cloned();
+        let format =
+            ResponseFormat::from_accept(req.headers().get(ACCEPT).and_then(|v| v.to_str().ok()));
+        let header_token = extract_token(
+            req.extensions()
+                .get::<AuthorizationHeaderExtension>()
+                .and_then(|v| v.as_ref()),
+        );
+
+        let params = self.v1_query_params(req).await?;
+
+        let authz_token = header_token.or_else(|| params.password.clone().map(String::into_bytes));
+        let perms = v1_query_permissions(&params.namespace, &params.query);
+        self.authz.permissions(authz_token.clone(), &perms).await?;
+
+        // Reject queries that are not valid InfluxQL before acquiring any
+        // query resources.
+        let statements =
+            parse_statements(&params.query).map_err(|e| Error::ParseQuery(e.to_string()))?;
+
+        let db = self
+            .server
+            .db(
+                &params.namespace,
+                span_ctx.child_span("get namespace"),
+                false,
+            )
+            .await
+            .ok_or_else(|| Error::NamespaceNotFound(params.namespace.clone()))?;
+
+        let _permit = self
+            .server
+            .acquire_semaphore(span_ctx.child_span("query rate limit semaphore"))
+            .await;
+
+        info!(
+            namespace_name=%params.namespace,
+            query=%params.query,
+            trace=external_span_ctx.format_jaeger().as_str(),
+            "v1 query request",
+        );
+
+        // InfluxDB 1.x renders timestamps as nanosecond integers in CSV
+        // responses when no epoch is specified.
+        let epoch = match format {
+            ResponseFormat::Json => params.epoch,
+            ResponseFormat::Csv => params.epoch.or(Some(Epoch::Nanoseconds)),
+        };
+
+        let mut token = db.record_query(
+            external_span_ctx.as_ref().map(RequestLogContext::ctx),
+            "influxql",
+            Box::new(params.query.clone()),
+        );
+        let ctx = db.new_query_context(span_ctx).with_authz_token(authz_token);
+        let cancellation = token.cancellation_token().clone();
+
+        // Each statement is executed in order, and an error executing one
+        // statement does not prevent the remaining statements from running.
+        //
+        // Cancelling the query, such as with `KILL QUERY`, interrupts the
+        // running statement and skips the remaining statements.
+        let mut results = Vec::with_capacity(statements.len());
+        let mut success = true;
+        for (statement_id, statement) in statements.iter().enumerate() {
+            let result = tokio::select! {
+                biased;
+                _ = cancellation.cancelled() => None,
+                result = async {
+                    let plan = Planner::new(&ctx)
+                        .influxql(statement.to_string(), params.params.clone())
+                        .await?;
+                    ctx.collect(plan).await
+                } => Some(result),
+            };
+            let Some(result) = result else {
+                info!(
+                    namespace_name=%params.namespace,
+                    query=%params.query,
+                    statement_id,
+                    trace=external_span_ctx.format_jaeger().as_str(),
+                    "v1 query cancelled",
+                );
+                success = false;
+                results.push(StatementResult::error(statement_id, "query interrupted"));
+                break;
+            };
+
+            results.push(match result {
+                Ok(batches) => {
+                    StatementResult::ok(statement_id, v1::series_from_batches(&batches, epoch)?)
+                }
+                Err(e) => {
+                    info!(
+                        namespace_name=%params.namespace,
+                        query=%params.query,
+                        statement_id,
+                        trace=external_span_ctx.format_jaeger().as_str(),
+                        %e,
+                        "error executing v1 query",
+                    );
+                    success = false;
+                    StatementResult::error(statement_id, e)
+                }
+            });
+        }
+
+        if success {
+            token.set_success();
+        }
+
+        let body = v1::encode(format, results, params.chunk_size)?;
+        let body = if body.len() == 1 {
+            Body::from(body.into_iter().next().unwrap())
+        } else {
+            Body::wrap_stream(futures::stream::iter(
+                body.into_iter().map(Ok::<_, Infallible>),
+            ))
+        };
+
+        Ok(Response::builder()
+            .status(StatusCode::OK)
+            .header(CONTENT_TYPE, format.content_type())
+            .body(body)
+            .unwrap())
+    }
+
+    /// Extract the V1 query parameters from the URI query string and, for
+    /// `POST` requests, the form-encoded body.
+    async fn v1_query_params(&self, req: Request<Body>) -> Result<QueryParamsV1, Error> {
+        let uri_params = RawQueryParamsV1::try_from_urlencoded(
+            req.uri().query().unwrap_or_default().as_bytes(),
+        )?;
+
+        if req.method() != Method::POST {
+            return Ok(QueryParamsV1::try_from(uri_params)?);
+        }
+
+        let body = read_body(req).await?;
+        let body_params = RawQueryParamsV1::try_from_urlencoded(&body)?;
+        Ok(QueryParamsV1::try_from(body_params.or(uri_params))?)
+    }
+
+    /// Serve a [Prometheus remote read] request against the namespace named
+    /// by the `db` query parameter.
+    ///
+    /// [Prometheus remote read]:
+    ///     https://prometheus.io/docs/prometheus/latest/querying/remote_read_api/
+    async fn prom_read(&self, req: Request<Body>) -> Result<Response<Body>, Error> {
+        let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
+        let external_span_ctx: Option<RequestLogContext> = req.extensions().get().cloned();
+        let header_token = extract_token(
+            req.extensions()
+                .get::<AuthorizationHeaderExtension>()
+                .and_then(|v| v.as_ref()),
+        );
+
+        let params =
+            PromReadParams::try_from_urlencoded(req.uri().query().unwrap_or_default().as_bytes())?;
+
+        let token = header_token.or_else(|| params.password.clone().map(String::into_bytes));
+        let perms = [Permission::ResourceAction(
+            Resource::Database(params.db.clone()),
+            Action::Read,
+        )];
+        self.authz.permissions(token, &perms).await?;
+
+        // The body is snappy compressed, so the decompressed size is checked
+        // against the same limit when decoding.
+        let body = read_body(req).await?;
+        let request = prom::decode_read_request(&body, MAX_REQUEST_BYTES)?;
+        let response_type = prom::response_type(&request)?;
+
+        let db = self
+            .server
+            .db(&params.db, span_ctx.child_span("get namespace"), false)
+            .await
+            .ok_or_else(|| Error::NamespaceNotFound(params.db.clone()))?;
+
+        let _permit = self
+            .server
+            .acquire_semaphore(span_ctx.child_span("query rate limit semaphore"))
+            .await;
+
+        info!(
+            namespace_name=%params.db,
+            queries=request.queries.len(),
+            trace=external_span_ctx.format_jaeger().as_str(),
+            "prometheus remote read request",
+        );
+
+        let mut token = db.record_query(
+            external_span_ctx.as_ref().map(RequestLogContext::ctx),
+            "prom_read",
+            Box::new(format!("{:?}", request.queries)),
+        );
+        let ctx = db.new_query_context(span_ctx);
+        let mut results = Vec::with_capacity(request.queries.len());
+        for query in &request.queries {
+            results.push(prom::read(&ctx, query).await?);
+        }
+        token.set_success();
+
+        let response = match response_type {
+            ResponseType::Samples => Response::builder()
+                .header(CONTENT_TYPE, "application/x-protobuf")
+                .header(CONTENT_ENCODING, "snappy")
+                .body(Body::from(
+                    prom::encode_samples(results).map_err(PromReadError::from)?,
+                )),
+            ResponseType::StreamedXorChunks => Response::builder()
+                .header(CONTENT_TYPE, prom::CHUNKED_CONTENT_TYPE)
+                .body(Body::wrap_stream(futures::stream::iter(
+                    prom::encode_chunked(results)
+                        .into_iter()
+                        .map(Ok::<_, Infallible>),
+                ))),
+        };
+
+        Ok(response.unwrap())
+    }
+}
+
+/// Returns the permissions required to run the InfluxQL `query` against
+/// `namespace`.
+///
+/// Statements that write or delete data, or cancel queries, also require write
+/// access. Statements that read the measurements of other databases require
+/// read access to their namespaces, and `SELECT ... INTO` statements writing to
+/// other databases require write access to their namespaces.
+fn v1_query_permissions(namespace: &str, query: &str) -> Vec<Permission> {
+    let mut perms = vec![Permission::ResourceAction(
+        Resource::Database(namespace.to_string()),
+        Action::Read,
+    )];
+    if InfluxQLQueryPlanner::is_write_query(query) {
+        perms.push(Permission::ResourceAction(
+            Resource::Database(namespace.to_string()),
+            Action::Write,
+        ));
+    }
+    perms.extend(
+        InfluxQLQueryPlanner::referenced_namespaces(query)
+            .into_iter()
+            .filter(|name| name != namespace)
+            .map(|name| Permission::ResourceAction(Resource::Database(name), Action::Read)),
+    );
+    perms.extend(
+        InfluxQLQueryPlanner::into_namespaces(namespace, query)
+            .into_iter()
+            .filter(|name| name != namespace)
+            .map(|name| Permission::ResourceAction(Resource::Database(name), Action::Write)),
+    );
+    perms
+}
+
+/// Read the body of `req`, limited to [`MAX_REQUEST_BYTES`] in size.
+async fn read_body(req: Request<Body>) -> Result<Bytes, Error> {
+    let mut payload = req.into_body();
+    let mut body = BytesMut::new();
+    while let Some(chunk) = payload.next().await {
+        let chunk = chunk.map_err(Error::ClientHangup)?;
+        // limit max size of in-memory payload
+        if (body.len() + chunk.len()) > MAX_REQUEST_BYTES {
+            return Err(Error::RequestSizeExceeded(MAX_REQUEST_BYTES));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body.freeze())
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use hyper::header::HeaderValue;
+    use service_common::test_util::TestDatabaseStore;
+
+    use super::*;
+
+    fn delegate() -> HttpDelegate<TestDatabaseStore> {
+        HttpDelegate::new(Arc::new(TestDatabaseStore::default()), None)
+    }
+
+    async fn body_json(response: Response<Body>) -> serde_json::Value {
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_no_handler() {
+        let req = Request::builder()
+            .uri("https://bananas.example/api/v2/query")
+            .body(Body::empty())
+            .unwrap();
+
+        assert_matches!(delegate().route(req).await, Err(Error::NoHandler));
+    }
+
+    #[tokio::test]
+    async fn test_v1_query_missing_params() {
+        let req = Request::builder()
+            .uri("https://bananas.example/query?db=bananas")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = delegate().route(req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            response.headers().get(CONTENT_TYPE),
+            Some(&HeaderValue::from_static("application/json"))
+        );
+        assert_eq!(
+            body_json(response).await,
+            serde_json::json!({"error": "missing required parameter \"q\""})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_v1_query_invalid_influxql() {
+        let req = Request::builder()
+            .uri("https://bananas.example/query?db=bananas&q=SELEKT+1")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = delegate().route(req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = body_json(response).await;
+        assert!(body["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("error parsing query:"));
+    }
+
+    #[tokio::test]
+    async fn test_v1_query_namespace_not_found() {
+        let req = Request::builder()
+            .method("POST")
+            .uri("https://bananas.example/query?db=platanos")
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from("db=bananas&q=SHOW+MEASUREMENTS"))
+            .unwrap();
+
+        let response = delegate().route(req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            body_json(response).await,
+            serde_json::json!({"error": "database not found: bananas"})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_v1_query_multiple_statements() {
+        let server = Arc::new(TestDatabaseStore::default());
+        server.db_or_create("bananas").await;
+
+        let req = Request::builder()
+            .method("POST")
+            .uri("https://bananas.example/query?db=bananas")
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from(
+                "q=SHOW+SERIES+CARDINALITY+ON+foo%3BSHOW+MEASUREMENT+CARDINALITY+ON+bar",
+            ))
+            .unwrap();
+
+        // An error in the first statement does not prevent the second
+        // statement from being executed.
+        let response = HttpDelegate::new(server, None).route(req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = body_json(response).await;
+        let results = body["results"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        for (statement_id, result) in results.iter().enumerate() {
+            assert_eq!(result["statement_id"], statement_id);
+            assert!(result["error"].as_str().is_some());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_v1_query_cancelled() {
+        let server = Arc::new(TestDatabaseStore::default());
+        server.db_or_create("bananas").await.cancel_queries();
+
+        let req = Request::builder()
+            .uri("https://bananas.example/query?db=bananas&q=SHOW+MEASUREMENTS%3BSHOW+MEASUREMENTS")
+            .body(Body::empty())
+            .unwrap();
+
+        // The cancelled query stops executing, and the remaining statements
+        // are skipped.
+        let response = HttpDelegate::new(server, None).route(req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            body_json(response).await,
+            serde_json::json!({
+                "results": [{"statement_id": 0, "error": "query interrupted"}]
+            })
+        );
+    }
+
+    #[test]
+    fn test_v1_query_permissions() {
+        let read = Permission::ResourceAction(Resource::Database("bananas".into()), Action::Read);
+        let write = Permission::ResourceAction(Resource::Database("bananas".into()), Action::Write);
+
+        assert_eq!(
+            v1_query_permissions("bananas", "SELECT * FROM cpu"),
+            vec![read.clone()]
+        );
+        assert_eq!(
+            v1_query_permissions("bananas", "DELETE FROM cpu"),
+            vec![read.clone(), write.clone()]
+        );
+        assert_eq!(
+            v1_query_permissions("bananas", "SELECT * INTO rollup FROM cpu"),
+            vec![read.clone(), write.clone()]
+        );
+        assert_eq!(
+            v1_query_permissions(
+                "bananas",
+                "SELECT * INTO platanos..rollup FROM platanos.rp0.cpu"
+            ),
+            vec![
+                read,
+                write,
+                Permission::ResourceAction(Resource::Database("platanos/rp0".into()), Action::Read),
+                Permission::ResourceAction(Resource::Database("platanos".into()), Action::Write),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prom_read_missing_db() {
+        let req = Request::builder()
+            .method("POST")
+            .uri("https://bananas.example/api/v1/prom/read")
+            .body(Body::empty())
+            .unwrap();
+
+        let err = delegate().route(req).await.unwrap_err();
+        assert_matches!(err, Error::PromRead(PromReadError::DecodeParams(_)));
+        assert_eq!(err.as_status_code(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_prom_read_invalid_body() {
+        let req = Request::builder()
+            .method("POST")
+            .uri("https://bananas.example/api/v1/prom/read?db=bananas")
+            .body(Body::from("bananas"))
+            .unwrap();
+
+        let err = delegate().route(req).await.unwrap_err();
+        assert_matches!(err, Error::PromRead(PromReadError::Snappy(_)));
+        assert_eq!(err.as_status_code(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_prom_read_namespace_not_found() {
+        use generated_types::{prometheus::ReadRequest, prost::Message};
+
+        let body = snap::raw::Encoder::new()
+            .compress_vec(&ReadRequest::default().encode_to_vec())
+            .unwrap();
+        let req = Request::builder()
+            .method("POST")
+            .uri("https://bananas.example/api/v1/prom/read?db=bananas")
+            .body(Body::from(body))
+            .unwrap();
+
+        assert_matches!(
+            delegate().route(req).await,
+            Err(Error::NamespaceNotFound(ns)) => {
+                assert_eq!(ns, "bananas");
+            }
+        );
+    }
+}
@@ -0,0 +1,200 @@
+//! Support for the [Prometheus remote read] API.
+//!
+//! Series are read from tables containing a numeric `value` field, such as
+//! those written by the router's Prometheus remote write API: the table name
+//! is the metric name, and the tags are the remaining labels of each series.
+//!
+//! [Prometheus remote read]:
+//!     https://prometheus.io/docs/prometheus/latest/querying/remote_read_api/
+
+mod plan;
+mod response;
+mod xor;
+
+use datafusion::error::DataFusionError;
+use generated_types::prometheus::{read_request::ResponseType, Query, ReadRequest, TimeSeries};
+use generated_types::prost::Message;
+use hyper::StatusCode;
+use iox_query::exec::IOxSessionContext;
+use serde::Deserialize;
+use thiserror::Error;
+
+pub(crate) use response::{encode_chunked, encode_samples};
+
+/// The label containing the metric name of a series.
+const METRIC_NAME_LABEL: &str = "__name__";
+
+/// The name of the field containing the sample values.
+const VALUE_FIELD: &str = "value";
+
+/// The content type of a [`ResponseType::StreamedXorChunks`] response.
+pub(crate) const CHUNKED_CONTENT_TYPE: &str =
+    "application/x-streamed-protobuf; proto=prometheus.ChunkedReadResponse";
+
+/// Errors returned when serving a remote read request.
+#[derive(Debug, Error)]
+pub enum PromReadError {
+    /// The request contains invalid parameters.
+    #[error("failed to deserialize query parameters: {0}")]
+    DecodeParams(#[from] serde::de::value::Error),
+
+    /// The request body is not valid snappy compressed data.
+    #[error("invalid snappy compressed body: {0}")]
+    Snappy(#[from] snap::Error),
+
+    /// The decompressed request body exceeds the configured maximum.
+    #[error("max decompressed request size ({0} bytes) exceeded")]
+    RequestSizeExceeded(usize),
+
+    /// The request body is not a valid remote read protobuf message.
+    #[error("invalid remote read request: {0}")]
+    Decode(#[from] generated_types::DecodeError),
+
+    /// None of the response types accepted by the client are supported.
+    #[error("none of the accepted response types are supported")]
+    UnsupportedResponseType,
+
+    /// A label matcher has an unknown type.
+    #[error("invalid label matcher type: {0}")]
+    InvalidMatcherType(i32),
+
+    /// A regex label matcher has an invalid pattern.
+    #[error("invalid regex {pattern:?} in label matcher: {source}")]
+    InvalidRegex {
+        /// The invalid pattern.
+        pattern: String,
+        /// The regex compilation error.
+        source: regex::Error,
+    },
+
+    /// An error planning or executing a query.
+    #[error("error executing query: {0}")]
+    Query(#[from] DataFusionError),
+
+    /// An error converting the query results into series.
+    #[error("error reading query results: {0}")]
+    Arrow(#[from] arrow::error::ArrowError),
+}
+
+impl From<&PromReadError> for StatusCode {
+    fn from(e: &PromReadError) -> Self {
+        match e {
+            PromReadError::DecodeParams(_)
+            | PromReadError::Snappy(_)
+            | PromReadError::Decode(_)
+            | PromReadError::UnsupportedResponseType
+            | PromReadError::InvalidMatcherType(_)
+            | PromReadError::InvalidRegex { .. } => Self::BAD_REQUEST,
+            PromReadError::RequestSizeExceeded(_) => Self::PAYLOAD_TOO_LARGE,
+            PromReadError::Query(_) | PromReadError::Arrow(_) => Self::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+/// Query parameters of a remote read request.
+#[derive(Debug, Deserialize)]
+pub(crate) struct PromReadParams {
+    /// The name of the namespace to read from.
+    pub(crate) db: String,
+
+    /// An optional token, passed as the `p` parameter as in the V1 APIs.
+    #[serde(rename(deserialize = "p"))]
+    pub(crate) password: Option<String>,
+}
+
+impl PromReadParams {
+    /// Decode the parameters from a URI query string.
+    pub(crate) fn try_from_urlencoded(v: &[u8]) -> Result<Self, PromReadError> {
+        Ok(serde_urlencoded::from_bytes(v)?)
+    }
+}
+
+/// Decompress and decode the snappy compressed remote read request in
+/// `body`, limiting the decompressed size to `max_request_bytes`.
+pub(crate) fn decode_read_request(
+    body: &[u8],
+    max_request_bytes: usize,
+) -> Result<ReadRequest, PromReadError> {
+    if snap::raw::decompress_len(body)? > max_request_bytes {
+        return Err(PromReadError::RequestSizeExceeded(max_request_bytes));
+    }
+    let body = snap::raw::Decoder::new().decompress_vec(body)?;
+
+    Ok(ReadRequest::decode(body.as_slice())?)
+}
+
+/// Select the response type for `request`, preferring the client's order of
+/// preference.
+pub(crate) fn response_type(request: &ReadRequest) -> Result<ResponseType, PromReadError> {
+    if request.accepted_response_types.is_empty() {
+        return Ok(ResponseType::Samples);
+    }
+
+    request
+        .accepted_response_types
+        .iter()
+        .find_map(|t| ResponseType::from_i32(*t))
+        .ok_or(PromReadError::UnsupportedResponseType)
+}
+
+/// Execute `query` against the namespace in `ctx`, returning the matching
+/// series.
+pub(crate) async fn read(
+    ctx: &IOxSessionContext,
+    query: &Query,
+) -> Result<Vec<TimeSeries>, PromReadError> {
+    let mut out = vec![];
+    for (table_name, plan) in plan::plan_query(ctx, query).await? {
+        let physical_plan = ctx.create_physical_plan(&plan).await?;
+        let batches = ctx.collect(physical_plan).await?;
+        out.extend(response::series_from_batches(&table_name, &batches)?);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+
+    #[test]
+    fn test_response_type() {
+        let mut request = ReadRequest::default();
+        assert_matches!(response_type(&request), Ok(ResponseType::Samples));
+
+        request.accepted_response_types = vec![42, ResponseType::StreamedXorChunks as i32];
+        assert_matches!(response_type(&request), Ok(ResponseType::StreamedXorChunks));
+
+        request.accepted_response_types = vec![42];
+        assert_matches!(
+            response_type(&request),
+            Err(PromReadError::UnsupportedResponseType)
+        );
+    }
+
+    #[test]
+    fn test_decode_read_request() {
+        let request = ReadRequest {
+            queries: vec![Query {
+                start_timestamp_ms: 1,
+                end_timestamp_ms: 2,
+                matchers: vec![],
+            }],
+            accepted_response_types: vec![],
+        };
+        let body = snap::raw::Encoder::new()
+            .compress_vec(&request.encode_to_vec())
+            .unwrap();
+
+        assert_eq!(decode_read_request(&body, usize::MAX).unwrap(), request);
+        assert_matches!(
+            decode_read_request(&body, 1),
+            Err(PromReadError::RequestSizeExceeded(1))
+        );
+        assert_matches!(
+            decode_read_request(b"bananas", usize::MAX),
+            Err(PromReadError::Snappy(_))
+        );
+    }
+}
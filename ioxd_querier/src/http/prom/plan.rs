@@ -0,0 +1,283 @@
+//! Planning of Prometheus remote read queries against the tables of a
+//! namespace.
+
+use datafusion::{
+    common::Column,
+    datasource::provider_as_source,
+    error::DataFusionError,
+    logical_expr::{lit, lit_timestamp_nano, Expr, LogicalPlan, LogicalPlanBuilder},
+    optimizer::utils::conjunction,
+};
+use generated_types::prometheus::{label_matcher::Type as MatcherType, LabelMatcher, Query};
+use iox_query::exec::IOxSessionContext;
+use query_functions::{clean_non_meta_escapes, regex_match_expr, regex_not_match_expr};
+use regex::Regex;
+use schema::{InfluxColumnType, InfluxFieldType, Schema, TIME_COLUMN_NAME};
+
+use super::{PromReadError, METRIC_NAME_LABEL, VALUE_FIELD};
+
+/// The comparison applied by a [`Matcher`].
+#[derive(Debug)]
+enum MatchOp {
+    Eq(String),
+    Neq(String),
+    /// A fully anchored regex, and the pattern it was compiled from.
+    Re(Regex, String),
+    /// A fully anchored regex, and the pattern it was compiled from.
+    Nre(Regex, String),
+}
+
+/// A validated Prometheus label matcher.
+#[derive(Debug)]
+pub(crate) struct Matcher {
+    name: String,
+    op: MatchOp,
+}
+
+impl TryFrom<&LabelMatcher> for Matcher {
+    type Error = PromReadError;
+
+    fn try_from(m: &LabelMatcher) -> Result<Self, Self::Error> {
+        // Prometheus regex matchers are always fully anchored.
+        let regex = || {
+            let pattern = format!("^(?:{})$", m.value);
+            Regex::new(&clean_non_meta_escapes(&pattern))
+                .map(|r| (r, pattern))
+                .map_err(|source| PromReadError::InvalidRegex {
+                    pattern: m.value.clone(),
+                    source,
+                })
+        };
+
+        let op = match MatcherType::from_i32(m.r#type) {
+            Some(MatcherType::Eq) => MatchOp::Eq(m.value.clone()),
+            Some(MatcherType::Neq) => MatchOp::Neq(m.value.clone()),
+            Some(MatcherType::Re) => {
+                let (r, p) = regex()?;
+                MatchOp::Re(r, p)
+            }
+            Some(MatcherType::Nre) => {
+                let (r, p) = regex()?;
+                MatchOp::Nre(r, p)
+            }
+            None => return Err(PromReadError::InvalidMatcherType(m.r#type)),
+        };
+
+        Ok(Self {
+            name: m.name.clone(),
+            op,
+        })
+    }
+}
+
+impl Matcher {
+    /// Returns true if this matcher matches the label value `v`.
+    ///
+    /// A label that is not present has the value `""`.
+    pub(crate) fn matches(&self, v: &str) -> bool {
+        match &self.op {
+            MatchOp::Eq(want) => v == want,
+            MatchOp::Neq(want) => v != want,
+            MatchOp::Re(r, _) => r.is_match(v),
+            MatchOp::Nre(r, _) => !r.is_match(v),
+        }
+    }
+
+    /// Returns a filter expression applying this matcher to the tag column
+    /// of the same name.
+    fn to_expr(&self) -> Expr {
+        let col = Expr::Column(Column::from_name(&self.name));
+
+        let expr = match &self.op {
+            MatchOp::Eq(v) => col.clone().eq(lit(v.as_str())),
+            MatchOp::Neq(v) => col.clone().not_eq(lit(v.as_str())),
+            MatchOp::Re(_, p) => regex_match_expr(col.clone(), p.clone()),
+            MatchOp::Nre(_, p) => regex_not_match_expr(col.clone(), p.clone()),
+        };
+
+        // A NULL tag is an absent label, which has the value "".
+        if self.matches("") {
+            col.is_null().or(expr)
+        } else {
+            expr
+        }
+    }
+}
+
+/// Plan `query` against the tables of the namespace in `ctx`.
+///
+/// Returns the table name and plan for each table that may contain matching
+/// series. Each plan produces the tag columns of the table (sorted by name),
+/// the [`VALUE_FIELD`] and the time column, sorted by tag values and then
+/// time.
+pub(crate) async fn plan_query(
+    ctx: &IOxSessionContext,
+    query: &Query,
+) -> Result<Vec<(String, LogicalPlan)>, PromReadError> {
+    let matchers = query
+        .matchers
+        .iter()
+        .map(Matcher::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    let (name_matchers, label_matchers): (Vec<_>, Vec<_>) = matchers
+        .into_iter()
+        .partition(|m| m.name == METRIC_NAME_LABEL);
+
+    let session_cfg = ctx.inner().copied_config();
+    let cfg = session_cfg.options();
+    let schema = ctx
+        .inner()
+        .catalog(&cfg.catalog.default_catalog)
+        .and_then(|c| c.schema(&cfg.catalog.default_schema))
+        .ok_or_else(|| {
+            DataFusionError::Plan(format!(
+                "failed to resolve schema: {}.{}",
+                cfg.catalog.default_catalog, cfg.catalog.default_schema
+            ))
+        })?;
+
+    let mut table_names = schema.table_names();
+    table_names.sort_unstable();
+
+    let mut plans = vec![];
+    'tables: for table_name in table_names {
+        if !name_matchers.iter().all(|m| m.matches(&table_name)) {
+            continue;
+        }
+
+        let Some(table) = schema.table(&table_name).await else {
+            continue;
+        };
+        let table_schema = Schema::try_from(table.schema()).map_err(|e| {
+            DataFusionError::Internal(format!(
+                "unable to convert DataFusion schema for table {table_name} to IOx schema: {e}"
+            ))
+        })?;
+
+        // Only tables with a numeric value field contain Prometheus samples.
+        match table_schema.field_type_by_name(VALUE_FIELD) {
+            Some(InfluxColumnType::Field(
+                InfluxFieldType::Float | InfluxFieldType::Integer | InfluxFieldType::UInteger,
+            )) => {}
+            _ => continue,
+        }
+
+        let mut filters = vec![
+            time_col().gt_eq(lit_timestamp_nano(ms_to_ns(query.start_timestamp_ms))),
+            time_col().lt_eq(lit_timestamp_nano(ms_to_ns(query.end_timestamp_ms))),
+        ];
+        for m in &label_matchers {
+            match table_schema.field_type_by_name(&m.name) {
+                Some(InfluxColumnType::Tag) => filters.push(m.to_expr()),
+                // The label is absent from every series in this table.
+                _ if m.matches("") => {}
+                _ => continue 'tables,
+            }
+        }
+
+        let mut tags = table_schema
+            .tags_iter()
+            .map(|f| f.name().as_str())
+            .collect::<Vec<_>>();
+        tags.sort_unstable();
+
+        let projection = tags
+            .iter()
+            .chain([VALUE_FIELD, TIME_COLUMN_NAME].iter())
+            .map(|c| Expr::Column(Column::from_name(*c)))
+            .collect::<Vec<_>>();
+        let sort = tags
+            .iter()
+            .map(|c| Expr::Column(Column::from_name(*c)).sort(true, true))
+            .chain([time_col().sort(true, true)])
+            .collect::<Vec<_>>();
+
+        let plan = LogicalPlanBuilder::scan(table_name.as_str(), provider_as_source(table), None)?
+            .filter(conjunction(filters).expect("time filters are always present"))?
+            .project(projection)?
+            .sort(sort)?
+            .build()?;
+
+        plans.push((table_name, plan));
+    }
+
+    Ok(plans)
+}
+
+fn time_col() -> Expr {
+    Expr::Column(Column::from_name(TIME_COLUMN_NAME))
+}
+
+/// Convert a Prometheus millisecond timestamp to nanoseconds, saturating at
+/// the bounds of the IOx time range.
+fn ms_to_ns(ms: i64) -> i64 {
+    ms.saturating_mul(1_000_000)
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+
+    fn matcher(t: MatcherType, name: &str, value: &str) -> LabelMatcher {
+        LabelMatcher {
+            r#type: t as i32,
+            name: name.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_matcher_matches() {
+        let m = Matcher::try_from(&matcher(MatcherType::Eq, "job", "router")).unwrap();
+        assert!(m.matches("router"));
+        assert!(!m.matches(""));
+
+        let m = Matcher::try_from(&matcher(MatcherType::Neq, "job", "router")).unwrap();
+        assert!(!m.matches("router"));
+        assert!(m.matches(""));
+
+        // Regex matchers are fully anchored
+        let m = Matcher::try_from(&matcher(MatcherType::Re, "job", "rout.*|quer")).unwrap();
+        assert!(m.matches("router"));
+        assert!(m.matches("quer"));
+        assert!(!m.matches("querier"));
+        assert!(!m.matches(""));
+
+        let m = Matcher::try_from(&matcher(MatcherType::Nre, "job", "rout.*")).unwrap();
+        assert!(!m.matches("router"));
+        assert!(m.matches("querier"));
+        assert!(m.matches(""));
+    }
+
+    #[test]
+    fn test_matcher_errors() {
+        assert_matches!(
+            Matcher::try_from(&matcher(MatcherType::Re, "job", "(")),
+            Err(PromReadError::InvalidRegex { pattern, .. }) => {
+                assert_eq!(pattern, "(");
+            }
+        );
+
+        let mut m = matcher(MatcherType::Eq, "job", "router");
+        m.r#type = 42;
+        assert_matches!(
+            Matcher::try_from(&m),
+            Err(PromReadError::InvalidMatcherType(42))
+        );
+    }
+
+    #[test]
+    fn test_matcher_expr() {
+        let m = Matcher::try_from(&matcher(MatcherType::Eq, "job", "router")).unwrap();
+        assert_eq!(m.to_expr().to_string(), "job = Utf8(\"router\")");
+
+        // Matchers that match the empty string also match absent labels.
+        let m = Matcher::try_from(&matcher(MatcherType::Neq, "job", "router")).unwrap();
+        assert_eq!(
+            m.to_expr().to_string(),
+            "job IS NULL OR job != Utf8(\"router\")"
+        );
+    }
+}
@@ -0,0 +1,298 @@
+//! Encoding of query results into [Prometheus remote read] responses.
+//!
+//! [Prometheus remote read]:
+//!     https://prometheus.io/docs/prometheus/latest/querying/remote_read_api/
+
+use arrow::{
+    array::{as_primitive_array, as_string_array, Array},
+    compute::cast,
+    datatypes::{DataType, Float64Type, TimestampNanosecondType},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use bytes::{BufMut, Bytes, BytesMut};
+use generated_types::{
+    prometheus::{
+        chunk::Encoding, Chunk, ChunkedReadResponse, ChunkedSeries, Label, QueryResult,
+        ReadResponse, Sample, TimeSeries,
+    },
+    prost::{encoding::encode_varint, Message},
+};
+use schema::TIME_COLUMN_NAME;
+
+use super::{
+    xor::{encode_xor_chunk, MAX_SAMPLES_PER_CHUNK},
+    METRIC_NAME_LABEL, VALUE_FIELD,
+};
+
+/// The CRC32 (Castagnoli) checksum used by the streamed response framing.
+const CRC32C: crc::Crc<u32> = crc::Crc::<u32>::new(&crc::CRC_32_ISCSI);
+
+/// Convert the results of a query against `table`, sorted by tag values and
+/// then time, into Prometheus time series.
+///
+/// Each distinct set of tag values forms a series labelled with the tags and
+/// the table name as the metric name.
+pub(crate) fn series_from_batches(
+    table: &str,
+    batches: &[RecordBatch],
+) -> Result<Vec<TimeSeries>, ArrowError> {
+    let mut out: Vec<TimeSeries> = vec![];
+
+    for batch in batches {
+        let schema = batch.schema();
+        let mut tags = vec![];
+        let mut values = None;
+        let mut times = None;
+        for (field, column) in schema.fields().iter().zip(batch.columns()) {
+            match field.name().as_str() {
+                VALUE_FIELD => values = Some(cast(column, &DataType::Float64)?),
+                TIME_COLUMN_NAME => times = Some(column),
+                name => tags.push((name, cast(column, &DataType::Utf8)?)),
+            }
+        }
+        let (Some(values), Some(times)) = (values, times) else {
+            return Err(ArrowError::SchemaError(
+                "missing value or time column in query results".to_string(),
+            ));
+        };
+        let values = as_primitive_array::<Float64Type>(&values);
+        let times = as_primitive_array::<TimestampNanosecondType>(times);
+
+        for row in 0..batch.num_rows() {
+            if values.is_null(row) {
+                continue;
+            }
+
+            let mut labels = tags
+                .iter()
+                .filter_map(|(name, column)| {
+                    let column = as_string_array(column);
+                    column.is_valid(row).then(|| Label {
+                        name: name.to_string(),
+                        value: column.value(row).to_string(),
+                    })
+                })
+                .chain([Label {
+                    name: METRIC_NAME_LABEL.to_string(),
+                    value: table.to_string(),
+                }])
+                .collect::<Vec<_>>();
+            labels.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+
+            let sample = Sample {
+                value: values.value(row),
+                timestamp: times.value(row) / 1_000_000,
+            };
+
+            match out.last_mut() {
+                Some(series) if series.labels == labels => series.samples.push(sample),
+                _ => out.push(TimeSeries {
+                    labels,
+                    samples: vec![sample],
+                }),
+            }
+        }
+    }
+
+    Ok(out)
+}
+
+/// Encode the `results` of each query as a snappy compressed
+/// [`ReadResponse`].
+pub(crate) fn encode_samples(results: Vec<Vec<TimeSeries>>) -> Result<Bytes, snap::Error> {
+    let response = ReadResponse {
+        results: results
+            .into_iter()
+            .map(|timeseries| QueryResult { timeseries })
+            .collect(),
+    };
+
+    let body = snap::raw::Encoder::new().compress_vec(&response.encode_to_vec())?;
+    Ok(body.into())
+}
+
+/// Encode the `results` of each query as a sequence of [`ChunkedReadResponse`]
+/// frames, one per series.
+///
+/// Each frame is prefixed by the uvarint encoded size of the message and its
+/// big-endian CRC32 (Castagnoli) checksum.
+pub(crate) fn encode_chunked(results: Vec<Vec<TimeSeries>>) -> Vec<Bytes> {
+    results
+        .into_iter()
+        .enumerate()
+        .flat_map(|(query_index, timeseries)| {
+            timeseries.into_iter().map(move |series| {
+                let msg = ChunkedReadResponse {
+                    chunked_series: vec![chunk_series(series)],
+                    query_index: query_index as i64,
+                }
+                .encode_to_vec();
+
+                let mut frame = BytesMut::with_capacity(msg.len() + 14);
+                encode_varint(msg.len() as u64, &mut frame);
+                frame.put_u32(CRC32C.checksum(&msg));
+                frame.put_slice(&msg);
+                frame.freeze()
+            })
+        })
+        .collect()
+}
+
+/// Encode the samples of `series` as XOR chunks.
+fn chunk_series(series: TimeSeries) -> ChunkedSeries {
+    let chunks = series
+        .samples
+        .chunks(MAX_SAMPLES_PER_CHUNK)
+        .map(|samples| Chunk {
+            min_time_ms: samples.first().map(|s| s.timestamp).unwrap_or_default(),
+            max_time_ms: samples.last().map(|s| s.timestamp).unwrap_or_default(),
+            r#type: Encoding::Xor as i32,
+            data: encode_xor_chunk(samples),
+        })
+        .collect();
+
+    ChunkedSeries {
+        labels: series.labels,
+        chunks,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::{
+        array::{ArrayRef, DictionaryArray, Int64Array, TimestampNanosecondArray},
+        datatypes::Int32Type,
+    };
+
+    use super::*;
+
+    fn label(name: &str, value: &str) -> Label {
+        Label {
+            name: name.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    fn batch(tags: Vec<Option<&str>>, values: Vec<Option<i64>>, times: Vec<i64>) -> RecordBatch {
+        RecordBatch::try_from_iter([
+            (
+                "job",
+                Arc::new(tags.into_iter().collect::<DictionaryArray<Int32Type>>()) as ArrayRef,
+            ),
+            (VALUE_FIELD, Arc::new(Int64Array::from(values)) as ArrayRef),
+            (
+                TIME_COLUMN_NAME,
+                Arc::new(TimestampNanosecondArray::from(times)) as ArrayRef,
+            ),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_series_from_batches() {
+        let batches = [
+            batch(
+                vec![None, Some("querier"), Some("router")],
+                vec![Some(1), Some(2), Some(3)],
+                vec![1_000_000, 1_000_000, 1_000_000],
+            ),
+            // The "router" series continues across batches.
+            batch(
+                vec![Some("router"), Some("router")],
+                vec![Some(4), None],
+                vec![2_000_000, 3_000_000],
+            ),
+        ];
+
+        let got = series_from_batches("up", &batches).unwrap();
+        assert_eq!(
+            got,
+            [
+                TimeSeries {
+                    labels: vec![label(METRIC_NAME_LABEL, "up")],
+                    samples: vec![Sample {
+                        value: 1.0,
+                        timestamp: 1
+                    }],
+                },
+                TimeSeries {
+                    labels: vec![label(METRIC_NAME_LABEL, "up"), label("job", "querier")],
+                    samples: vec![Sample {
+                        value: 2.0,
+                        timestamp: 1
+                    }],
+                },
+                TimeSeries {
+                    labels: vec![label(METRIC_NAME_LABEL, "up"), label("job", "router")],
+                    samples: vec![
+                        Sample {
+                            value: 3.0,
+                            timestamp: 1
+                        },
+                        Sample {
+                            value: 4.0,
+                            timestamp: 2
+                        }
+                    ],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_encode_chunked() {
+        let series = TimeSeries {
+            labels: vec![label(METRIC_NAME_LABEL, "up")],
+            samples: (0..MAX_SAMPLES_PER_CHUNK as i64 + 1)
+                .map(|i| Sample {
+                    value: 1.0,
+                    timestamp: i * 1_000,
+                })
+                .collect(),
+        };
+
+        let frames = encode_chunked(vec![vec![], vec![series]]);
+        assert_eq!(frames.len(), 1);
+
+        // Decode the frame
+        let mut frame = frames[0].clone();
+        let len = generated_types::prost::encoding::decode_varint(&mut frame).unwrap();
+        assert_eq!(len as usize, frame.len() - 4);
+        let checksum = u32::from_be_bytes(frame[..4].try_into().unwrap());
+        assert_eq!(checksum, CRC32C.checksum(&frame[4..]));
+
+        let msg = ChunkedReadResponse::decode(&frame[4..]).unwrap();
+        assert_eq!(msg.query_index, 1);
+        assert_eq!(msg.chunked_series.len(), 1);
+
+        let chunks = &msg.chunked_series[0].chunks;
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].min_time_ms, 0);
+        assert_eq!(
+            chunks[0].max_time_ms,
+            (MAX_SAMPLES_PER_CHUNK as i64 - 1) * 1_000
+        );
+        assert_eq!(chunks[1].min_time_ms, MAX_SAMPLES_PER_CHUNK as i64 * 1_000);
+        assert_eq!(chunks[1].r#type, Encoding::Xor as i32);
+    }
+
+    #[test]
+    fn test_encode_samples() {
+        let results = vec![vec![TimeSeries {
+            labels: vec![label(METRIC_NAME_LABEL, "up")],
+            samples: vec![Sample {
+                value: 1.0,
+                timestamp: 1,
+            }],
+        }]];
+
+        let body = encode_samples(results.clone()).unwrap();
+        let body = snap::raw::Decoder::new().decompress_vec(&body).unwrap();
+        let got = ReadResponse::decode(body.as_slice()).unwrap();
+        assert_eq!(got.results.len(), 1);
+        assert_eq!(got.results[0].timeseries, results[0]);
+    }
+}
@@ -0,0 +1,308 @@
+//! Encoding of samples as Prometheus [XOR chunks], the "Gorilla" compressed
+//! chunk format used in streamed remote read responses.
+//!
+//! [XOR chunks]:
+//!     https://github.com/prometheus/prometheus/blob/main/tsdb/docs/format/chunks.md#xor-chunk-data
+
+use generated_types::{prometheus::Sample, prost::encoding::encode_varint};
+
+/// The maximum number of samples in a single chunk, matching the chunks cut
+/// by Prometheus.
+pub(crate) const MAX_SAMPLES_PER_CHUNK: usize = 120;
+
+/// A big-endian bit stream writer.
+#[derive(Debug, Default)]
+struct BitWriter {
+    buf: Vec<u8>,
+    /// The number of unwritten bits in the last byte of `buf`.
+    free: u8,
+}
+
+impl BitWriter {
+    fn write_bit(&mut self, bit: bool) {
+        if self.free == 0 {
+            self.buf.push(0);
+            self.free = 8;
+        }
+        if bit {
+            *self.buf.last_mut().unwrap() |= 1 << (self.free - 1);
+        }
+        self.free -= 1;
+    }
+
+    /// Write the `n` least significant bits of `value`.
+    fn write_bits(&mut self, value: u64, n: u8) {
+        for i in (0..n).rev() {
+            self.write_bit((value >> i) & 1 == 1);
+        }
+    }
+
+    fn write_uvarint(&mut self, value: u64) {
+        let mut buf = Vec::with_capacity(10);
+        encode_varint(value, &mut buf);
+        for b in buf {
+            self.write_bits(b as u64, 8);
+        }
+    }
+
+    /// Write `value` as a zig-zag encoded varint.
+    fn write_varint(&mut self, value: i64) {
+        self.write_uvarint(((value << 1) ^ (value >> 63)) as u64);
+    }
+}
+
+/// Returns true if `x` can be encoded in `nbits` bits by the delta-of-delta
+/// timestamp encoding.
+fn bit_range(x: i64, nbits: u8) -> bool {
+    -((1 << (nbits - 1)) - 1) <= x && x <= 1 << (nbits - 1)
+}
+
+/// The state of the XOR value encoding.
+#[derive(Debug)]
+struct XorState {
+    value: f64,
+    leading: u8,
+    trailing: u8,
+}
+
+impl XorState {
+    fn new(value: f64) -> Self {
+        Self {
+            value,
+            // No leading / trailing zero window has been written yet.
+            leading: 0xff,
+            trailing: 0,
+        }
+    }
+
+    fn write(&mut self, w: &mut BitWriter, value: f64) {
+        let delta = value.to_bits() ^ self.value.to_bits();
+        self.value = value;
+
+        if delta == 0 {
+            w.write_bit(false);
+            return;
+        }
+        w.write_bit(true);
+
+        // The number of leading zeros is written with 5 bits.
+        let leading = (delta.leading_zeros() as u8).min(31);
+        let trailing = delta.trailing_zeros() as u8;
+
+        // Reuse the previous window if the meaningful bits fall within it.
+        if self.leading != 0xff && leading >= self.leading && trailing >= self.trailing {
+            w.write_bit(false);
+            w.write_bits(delta >> self.trailing, 64 - self.leading - self.trailing);
+            return;
+        }
+
+        self.leading = leading;
+        self.trailing = trailing;
+
+        w.write_bit(true);
+        w.write_bits(leading as u64, 5);
+        // A length of 64 overflows to 0, which readers interpret as 64.
+        let significant = 64 - leading - trailing;
+        w.write_bits(significant as u64, 6);
+        w.write_bits(delta >> trailing, significant);
+    }
+}
+
+/// Encode `samples`, ordered by time, as the data of an XOR chunk.
+///
+/// # Panics
+///
+/// Panics if `samples` contains more than [`u16::MAX`] samples.
+pub(crate) fn encode_xor_chunk(samples: &[Sample]) -> Vec<u8> {
+    let num_samples = u16::try_from(samples.len()).expect("too many samples for one chunk");
+
+    let mut w = BitWriter::default();
+    w.write_bits(num_samples as u64, 16);
+
+    let (first, rest) = match samples.split_first() {
+        Some(v) => v,
+        None => return w.buf,
+    };
+    w.write_varint(first.timestamp);
+    w.write_bits(first.value.to_bits(), 64);
+
+    let mut xor = XorState::new(first.value);
+    let mut t = first.timestamp;
+    let mut t_delta = 0_i64;
+    for (i, s) in rest.iter().enumerate() {
+        let delta = s.timestamp.wrapping_sub(t);
+        if i == 0 {
+            w.write_uvarint(delta as u64);
+        } else {
+            match delta.wrapping_sub(t_delta) {
+                0 => w.write_bit(false),
+                dod if bit_range(dod, 14) => {
+                    w.write_bits(0b10, 2);
+                    w.write_bits(dod as u64, 14);
+                }
+                dod if bit_range(dod, 17) => {
+                    w.write_bits(0b110, 3);
+                    w.write_bits(dod as u64, 17);
+                }
+                dod if bit_range(dod, 20) => {
+                    w.write_bits(0b1110, 4);
+                    w.write_bits(dod as u64, 20);
+                }
+                dod => {
+                    w.write_bits(0b1111, 4);
+                    w.write_bits(dod as u64, 64);
+                }
+            }
+        }
+        xor.write(&mut w, s.value);
+
+        t = s.timestamp;
+        t_delta = delta;
+    }
+
+    w.buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A minimal XOR chunk decoder, following the Prometheus implementation.
+    struct BitReader<'a> {
+        buf: &'a [u8],
+        pos: usize,
+    }
+
+    impl<'a> BitReader<'a> {
+        fn read_bit(&mut self) -> bool {
+            let bit = self.buf[self.pos / 8] & (1 << (7 - self.pos % 8)) != 0;
+            self.pos += 1;
+            bit
+        }
+
+        fn read_bits(&mut self, n: u8) -> u64 {
+            (0..n).fold(0, |acc, _| (acc << 1) | self.read_bit() as u64)
+        }
+
+        fn read_uvarint(&mut self) -> u64 {
+            let mut v = 0;
+            for shift in (0..).step_by(7) {
+                let b = self.read_bits(8);
+                v |= (b & 0x7f) << shift;
+                if b & 0x80 == 0 {
+                    break;
+                }
+            }
+            v
+        }
+
+        /// Read an `n` bit two's complement signed value.
+        fn read_signed(&mut self, n: u8) -> i64 {
+            let v = self.read_bits(n) as i64;
+            if v > 1 << (n - 1) {
+                v - (1 << n)
+            } else {
+                v
+            }
+        }
+    }
+
+    fn decode(buf: &[u8]) -> Vec<(i64, f64)> {
+        let mut r = BitReader { buf, pos: 0 };
+        let n = r.read_bits(16);
+        let mut out = vec![];
+
+        let (mut t, mut t_delta, mut v) = (0_i64, 0_i64, 0_u64);
+        let (mut leading, mut trailing) = (0_u8, 0_u8);
+        for i in 0..n {
+            match i {
+                0 => {
+                    let zz = r.read_uvarint();
+                    t = ((zz >> 1) as i64) ^ -((zz & 1) as i64);
+                    v = r.read_bits(64);
+                    out.push((t, f64::from_bits(v)));
+                    continue;
+                }
+                1 => t_delta = r.read_uvarint() as i64,
+                _ => {
+                    let dod = if !r.read_bit() {
+                        0
+                    } else if !r.read_bit() {
+                        r.read_signed(14)
+                    } else if !r.read_bit() {
+                        r.read_signed(17)
+                    } else if !r.read_bit() {
+                        r.read_signed(20)
+                    } else {
+                        r.read_bits(64) as i64
+                    };
+                    t_delta += dod;
+                }
+            }
+            t += t_delta;
+
+            if r.read_bit() {
+                if r.read_bit() {
+                    leading = r.read_bits(5) as u8;
+                    let mut significant = r.read_bits(6) as u8;
+                    if significant == 0 {
+                        significant = 64;
+                    }
+                    trailing = 64 - leading - significant;
+                }
+                let significant = 64 - leading - trailing;
+                v ^= r.read_bits(significant) << trailing;
+            }
+            out.push((t, f64::from_bits(v)));
+        }
+        out
+    }
+
+    fn roundtrip(samples: &[(i64, f64)]) {
+        let encoded = encode_xor_chunk(
+            &samples
+                .iter()
+                .map(|&(timestamp, value)| Sample { value, timestamp })
+                .collect::<Vec<_>>(),
+        );
+        let got = decode(&encoded);
+
+        assert_eq!(got.len(), samples.len());
+        for (got, want) in got.iter().zip(samples) {
+            assert_eq!(got.0, want.0);
+            assert_eq!(got.1.to_bits(), want.1.to_bits());
+        }
+    }
+
+    #[test]
+    fn test_empty() {
+        assert_eq!(encode_xor_chunk(&[]), vec![0, 0]);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        roundtrip(&[(1_000, 1.0)]);
+        roundtrip(&[(-1_000, 1.0), (-500, 1.0)]);
+
+        // Regular intervals and repeated values.
+        roundtrip(
+            &(0..MAX_SAMPLES_PER_CHUNK as i64)
+                .map(|i| (1_600_000_000_000 + i * 15_000, (i / 10) as f64))
+                .collect::<Vec<_>>(),
+        );
+
+        // Every delta-of-delta encoding width, and varying values.
+        roundtrip(&[
+            (0, 0.0),
+            (10, 1.5),
+            (20, -1.5),
+            (8_200, 42.42),
+            (80_000, f64::MAX),
+            (600_000, f64::MIN_POSITIVE),
+            (1_000_000_000, 1e-300),
+            (1_000_000_001, f64::INFINITY),
+            (1_000_000_002, 3.0),
+            (2_000_000_000, 3.0),
+        ]);
+    }
+}
@@ -0,0 +1,14 @@
+//! Support for the InfluxDB [V1 Query API].
+//!
+//! [V1 Query API]:
+//!     https://docs.influxdata.com/influxdb/v1.8/tools/api/#query-http-endpoint
+
+mod params;
+mod response;
+
+pub use params::V1QueryParseError;
+pub(crate) use params::{Epoch, QueryParamsV1, RawQueryParamsV1};
+pub use response::V1ResponseError;
+pub(crate) use response::{
+    encode, encode_error, series_from_batches, ResponseFormat, StatementResult,
+};
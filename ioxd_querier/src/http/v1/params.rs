@@ -0,0 +1,292 @@
+//! Parsing of HTTP requests that conform to the [V1 Query API].
+//!
+//! [V1 Query API]:
+//!     https://docs.influxdata.com/influxdb/v1.8/tools/api/#query-http-endpoint
+
+use iox_query_influxql::{params::StatementParams, plan::namespace_name};
+use serde::Deserialize;
+use thiserror::Error;
+
+/// The number of rows per response chunk when `chunked=true` is specified
+/// without a valid `chunk_size`, matching InfluxDB 1.x.
+pub(crate) const DEFAULT_CHUNK_SIZE: usize = 10_000;
+
+/// Errors returned when decoding the query parameters of a V1 query request.
+#[derive(Debug, Error)]
+pub enum V1QueryParseError {
+    /// The request contains no `q` parameter.
+    #[error("missing required parameter \"q\"")]
+    NoQuery,
+
+    /// The request contains no `db` parameter.
+    #[error("database name required")]
+    NoDatabase,
+
+    /// The request contains invalid parameters.
+    #[error("failed to deserialize query parameters: {0}")]
+    DecodeFail(#[from] serde::de::value::Error),
+
+    /// The `params` parameter is not a JSON object of bind parameter values.
+    #[error("error parsing query parameters: {0}")]
+    InvalidParams(serde_json::Error),
+}
+
+/// The precision used to render timestamps in the response, as specified by
+/// the `epoch` parameter.
+///
+/// When no `epoch` is specified, timestamps are rendered as RFC3339 strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Epoch {
+    Nanoseconds,
+    Microseconds,
+    Milliseconds,
+    Seconds,
+    Minutes,
+    Hours,
+}
+
+impl Epoch {
+    /// Convert a nanosecond timestamp into this precision, truncating any
+    /// remainder.
+    pub(crate) fn convert(&self, nanos: i64) -> i64 {
+        let divisor = match self {
+            Self::Nanoseconds => 1,
+            Self::Microseconds => 1_000,
+            Self::Milliseconds => 1_000_000,
+            Self::Seconds => 1_000_000_000,
+            Self::Minutes => 60 * 1_000_000_000,
+            Self::Hours => 60 * 60 * 1_000_000_000,
+        };
+        nanos / divisor
+    }
+}
+
+impl From<&str> for Epoch {
+    /// Unrecognised values select nanosecond precision, as InfluxDB 1.x
+    /// does.
+    fn from(v: &str) -> Self {
+        match v {
+            "u" | "µ" | "us" => Self::Microseconds,
+            "ms" => Self::Milliseconds,
+            "s" => Self::Seconds,
+            "m" => Self::Minutes,
+            "h" => Self::Hours,
+            _ => Self::Nanoseconds,
+        }
+    }
+}
+
+/// The raw, untyped parameters of a V1 query request.
+///
+/// Parameters may be specified in the URI query string, or for `POST`
+/// requests, as an `application/x-www-form-urlencoded` body.
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct RawQueryParamsV1 {
+    db: Option<String>,
+    rp: Option<String>,
+    q: Option<String>,
+    epoch: Option<String>,
+    chunked: Option<String>,
+    chunk_size: Option<String>,
+    params: Option<String>,
+
+    // `u` (username) is an optional v1 query parameter, but is ignored -
+    // the `p` parameter is treated as a token.
+    #[serde(rename(deserialize = "p"))]
+    password: Option<String>,
+}
+
+impl RawQueryParamsV1 {
+    /// Decode the parameters from a `application/x-www-form-urlencoded`
+    /// string.
+    pub(crate) fn try_from_urlencoded(v: &[u8]) -> Result<Self, V1QueryParseError> {
+        Ok(serde_urlencoded::from_bytes(v)?)
+    }
+
+    /// Fill any parameters not present in `self` with those from `other`.
+    ///
+    /// Used to merge the form body parameters (`self`) of a `POST` request
+    /// with those of the URI query string (`other`) - as in InfluxDB 1.x, the
+    /// body takes precedence.
+    pub(crate) fn or(self, other: Self) -> Self {
+        Self {
+            db: self.db.or(other.db),
+            rp: self.rp.or(other.rp),
+            q: self.q.or(other.q),
+            epoch: self.epoch.or(other.epoch),
+            chunked: self.chunked.or(other.chunked),
+            chunk_size: self.chunk_size.or(other.chunk_size),
+            params: self.params.or(other.params),
+            password: self.password.or(other.password),
+        }
+    }
+}
+
+/// Validated parameters of a V1 query request.
+#[derive(Debug, PartialEq)]
+pub(crate) struct QueryParamsV1 {
+    /// The namespace derived from the `db` and `rp` parameters.
+    pub(crate) namespace: String,
+    /// The InfluxQL query text.
+    pub(crate) query: String,
+    /// The timestamp precision, if any.
+    pub(crate) epoch: Option<Epoch>,
+    /// The number of rows per chunk, if a chunked response was requested.
+    pub(crate) chunk_size: Option<usize>,
+    /// The values of any bind parameters in the query, from the JSON
+    /// encoded `params` parameter.
+    pub(crate) params: StatementParams,
+    /// An optional token, passed as the `p` parameter.
+    pub(crate) password: Option<String>,
+}
+
+impl TryFrom<RawQueryParamsV1> for QueryParamsV1 {
+    type Error = V1QueryParseError;
+
+    fn try_from(raw: RawQueryParamsV1) -> Result<Self, Self::Error> {
+        let query = raw
+            .q
+            .filter(|q| !q.trim().is_empty())
+            .ok_or(V1QueryParseError::NoQuery)?;
+        let db = raw
+            .db
+            .filter(|db| !db.is_empty())
+            .ok_or(V1QueryParseError::NoDatabase)?;
+
+        // Construct the namespace name from the db / rp pair, the same way
+        // as the router's V1 write API.
+        let namespace = namespace_name(&db, raw.rp.as_deref());
+
+        let chunk_size = match raw.chunked.as_deref() {
+            Some("true") => Some(
+                raw.chunk_size
+                    .and_then(|v| v.parse::<usize>().ok())
+                    .filter(|v| *v > 0)
+                    .unwrap_or(DEFAULT_CHUNK_SIZE),
+            ),
+            _ => None,
+        };
+
+        let params = match raw.params.as_deref() {
+            Some(v) if !v.trim().is_empty() => {
+                StatementParams::from_json(v).map_err(V1QueryParseError::InvalidParams)?
+            }
+            _ => StatementParams::default(),
+        };
+
+        Ok(Self {
+            namespace,
+            query,
+            epoch: raw.epoch.as_deref().map(Epoch::from),
+            chunk_size,
+            params,
+            password: raw.password,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+
+    fn parse(query_string: &str) -> Result<QueryParamsV1, V1QueryParseError> {
+        RawQueryParamsV1::try_from_urlencoded(query_string.as_bytes())
+            .and_then(QueryParamsV1::try_from)
+    }
+
+    #[test]
+    fn test_parse_minimal() {
+        let got = parse("db=bananas&q=SELECT+*+FROM+cpu").unwrap();
+        assert_eq!(
+            got,
+            QueryParamsV1 {
+                namespace: "bananas".to_string(),
+                query: "SELECT * FROM cpu".to_string(),
+                epoch: None,
+                chunk_size: None,
+                params: StatementParams::default(),
+                password: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_retention_policy() {
+        let got = parse("db=bananas&rp=Platanos&q=SHOW+MEASUREMENTS").unwrap();
+        assert_eq!(got.namespace, "bananas/platanos");
+
+        for rp in ["", "autogen", "default", "AUTOGEN", "''"] {
+            let got = parse(&format!("db=bananas&rp={rp}&q=SHOW+MEASUREMENTS")).unwrap();
+            assert_eq!(got.namespace, "bananas", "rp={rp}");
+        }
+    }
+
+    #[test]
+    fn test_parse_missing() {
+        assert_matches!(parse("db=bananas"), Err(V1QueryParseError::NoQuery));
+        assert_matches!(parse("db=bananas&q=+"), Err(V1QueryParseError::NoQuery));
+        assert_matches!(
+            parse("q=SHOW+MEASUREMENTS"),
+            Err(V1QueryParseError::NoDatabase)
+        );
+    }
+
+    #[test]
+    fn test_parse_epoch() {
+        let got = parse("db=bananas&q=x&epoch=ms").unwrap();
+        assert_eq!(got.epoch, Some(Epoch::Milliseconds));
+
+        let got = parse("db=bananas&q=x&epoch=bananas").unwrap();
+        assert_eq!(got.epoch, Some(Epoch::Nanoseconds));
+
+        assert_eq!(Epoch::Seconds.convert(1_500_000_000), 1);
+        assert_eq!(Epoch::Hours.convert(2 * 60 * 60 * 1_000_000_000), 2);
+    }
+
+    #[test]
+    fn test_parse_chunked() {
+        let got = parse("db=bananas&q=x&chunked=true").unwrap();
+        assert_eq!(got.chunk_size, Some(DEFAULT_CHUNK_SIZE));
+
+        let got = parse("db=bananas&q=x&chunked=true&chunk_size=42").unwrap();
+        assert_eq!(got.chunk_size, Some(42));
+
+        let got = parse("db=bananas&q=x&chunked=true&chunk_size=0").unwrap();
+        assert_eq!(got.chunk_size, Some(DEFAULT_CHUNK_SIZE));
+
+        let got = parse("db=bananas&q=x&chunk_size=42").unwrap();
+        assert_eq!(got.chunk_size, None);
+    }
+
+    #[test]
+    fn test_parse_params() {
+        let got = parse(
+            "db=bananas&q=SELECT+*+FROM+cpu+WHERE+host+%3D+%24host&params=%7B%22host%22%3A%22a%22%7D",
+        )
+        .unwrap();
+        assert_eq!(
+            got.params.get("host"),
+            Some(&iox_query_influxql::params::StatementParam::String(
+                "a".to_string()
+            ))
+        );
+
+        assert_matches!(
+            parse("db=bananas&q=x&params=bananas"),
+            Err(V1QueryParseError::InvalidParams(_))
+        );
+    }
+
+    #[test]
+    fn test_body_takes_precedence() {
+        let body = RawQueryParamsV1::try_from_urlencoded(b"q=SELECT+1&db=platanos").unwrap();
+        let uri = RawQueryParamsV1::try_from_urlencoded(b"db=bananas&epoch=s").unwrap();
+
+        let got = QueryParamsV1::try_from(body.or(uri)).unwrap();
+        assert_eq!(got.namespace, "platanos");
+        assert_eq!(got.query, "SELECT 1");
+        assert_eq!(got.epoch, Some(Epoch::Seconds));
+    }
+}
@@ -0,0 +1,663 @@
+//! Encoding of InfluxQL query results into the response shapes of the
+//! [V1 Query API].
+//!
+//! [V1 Query API]:
+//!     https://docs.influxdata.com/influxdb/v1.8/tools/api/#query-http-endpoint
+
+use std::collections::BTreeMap;
+
+use arrow::{
+    array::{as_boolean_array, as_primitive_array, as_string_array, Array, ArrayRef},
+    compute::cast,
+    datatypes::{DataType, Float64Type, Int64Type, TimeUnit, TimestampNanosecondType, UInt64Type},
+    error::ArrowError,
+    record_batch::RecordBatch,
+    util::display::{ArrayFormatter, FormatOptions},
+};
+use bytes::Bytes;
+use chrono::{TimeZone, Utc};
+use generated_types::influxdata::iox::querier::v1::InfluxQlMetadata;
+use serde::Serialize;
+use serde_json::Value;
+use thiserror::Error;
+
+use super::params::Epoch;
+
+/// Errors converting query results into a V1 response.
+#[derive(Debug, Error)]
+pub enum V1ResponseError {
+    /// An error reading the result record batches.
+    #[error("error reading query results: {0}")]
+    Arrow(#[from] ArrowError),
+
+    /// The InfluxQL metadata was not found in the result schema.
+    #[error("missing InfluxQL metadata in query results")]
+    MissingMetadata,
+
+    /// The InfluxQL metadata could not be deserialised.
+    #[error("invalid InfluxQL metadata in query results: {0}")]
+    InvalidMetadata(serde_json::Error),
+
+    /// The response could not be serialised.
+    #[error("error serialising response: {0}")]
+    Serialise(serde_json::Error),
+}
+
+/// The encoding of the response body, as negotiated by the `Accept` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ResponseFormat {
+    Json,
+    Csv,
+}
+
+impl ResponseFormat {
+    /// Select the response format for the given `Accept` header value,
+    /// defaulting to JSON.
+    pub(crate) fn from_accept(accept: Option<&str>) -> Self {
+        match accept {
+            Some(v)
+                if v.split(',')
+                    .map(|v| v.split(';').next().unwrap_or_default().trim())
+                    .any(|v| v == "application/csv" || v == "text/csv") =>
+            {
+                Self::Csv
+            }
+            _ => Self::Json,
+        }
+    }
+
+    /// The `Content-Type` of a response in this format.
+    pub(crate) fn content_type(&self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::Csv => "text/csv",
+        }
+    }
+}
+
+/// A single series (a measurement and group key) of a statement result.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct Series {
+    pub(crate) name: String,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) tags: BTreeMap<String, String>,
+    pub(crate) columns: Vec<String>,
+    pub(crate) values: Vec<Vec<Value>>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub(crate) partial: bool,
+}
+
+/// The result of executing a single statement.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct StatementResult {
+    pub(crate) statement_id: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) series: Vec<Series>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) error: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub(crate) partial: bool,
+}
+
+impl StatementResult {
+    /// A successful result containing `series`.
+    pub(crate) fn ok(statement_id: usize, series: Vec<Series>) -> Self {
+        Self {
+            statement_id,
+            series,
+            error: None,
+            partial: false,
+        }
+    }
+
+    /// A failed result, reporting `error` to the client.
+    pub(crate) fn error(statement_id: usize, error: impl ToString) -> Self {
+        Self {
+            statement_id,
+            series: vec![],
+            error: Some(error.to_string()),
+            partial: false,
+        }
+    }
+
+    /// Split this result into a sequence of results containing at most
+    /// `chunk_size` rows each, flagging all but the last as `partial`.
+    ///
+    /// As in InfluxDB 1.x, a chunk never spans more than one series, and a
+    /// series split across chunks is flagged `partial` in all but its last
+    /// chunk.
+    fn into_chunks(self, chunk_size: usize) -> Vec<Self> {
+        if self.error.is_some() || self.series.is_empty() {
+            return vec![self];
+        }
+
+        let statement_id = self.statement_id;
+        let mut chunks = vec![];
+        for series in self.series {
+            let mut values = series.values.into_iter().peekable();
+            loop {
+                let chunk = values.by_ref().take(chunk_size).collect::<Vec<_>>();
+                let partial = values.peek().is_some();
+                chunks.push(Self::ok(
+                    statement_id,
+                    vec![Series {
+                        name: series.name.clone(),
+                        tags: series.tags.clone(),
+                        columns: series.columns.clone(),
+                        values: chunk,
+                        partial,
+                    }],
+                ));
+                if !partial {
+                    break;
+                }
+            }
+        }
+
+        let last = chunks.len() - 1;
+        for chunk in &mut chunks[..last] {
+            chunk.partial = true;
+        }
+        chunks
+    }
+}
+
+/// The top-level JSON response object.
+#[derive(Debug, Serialize)]
+struct QueryResponse<'a> {
+    results: &'a [StatementResult],
+}
+
+/// The JSON body returned when the request fails as a whole.
+#[derive(Debug, Serialize)]
+struct ErrorResponse<'a> {
+    error: &'a str,
+}
+
+/// Serialise the request-level `error` as a V1 JSON error body.
+pub(crate) fn encode_error(error: &str) -> Bytes {
+    serde_json::to_vec(&ErrorResponse { error })
+        .expect("serialising a string cannot fail")
+        .into()
+}
+
+/// Convert the InfluxQL query result `batches` into a list of V1 [`Series`],
+/// one per contiguous run of rows that share the same measurement and group
+/// key.
+///
+/// Timestamps are rendered as integers in the `epoch` precision if
+/// specified, or RFC3339 strings otherwise.
+pub(crate) fn series_from_batches(
+    batches: &[RecordBatch],
+    epoch: Option<Epoch>,
+) -> Result<Vec<Series>, V1ResponseError> {
+    let Some(schema) = batches.first().map(|b| b.schema()) else {
+        return Ok(vec![]);
+    };
+    let md = schema
+        .metadata()
+        .get(schema::INFLUXQL_METADATA_KEY)
+        .ok_or(V1ResponseError::MissingMetadata)?;
+    let md: InfluxQlMetadata =
+        serde_json::from_str(md).map_err(V1ResponseError::InvalidMetadata)?;
+
+    // The columns of each series exclude the measurement name column and any
+    // tag key columns that only appear in the `GROUP BY` clause.
+    let measurement_idx = md.measurement_column_index as usize;
+    let col_indexes = (0..schema.fields().len())
+        .filter(|i| {
+            *i != measurement_idx
+                && !md
+                    .tag_key_columns
+                    .iter()
+                    .any(|tk| tk.column_index as usize == *i && !tk.is_projected)
+        })
+        .collect::<Vec<_>>();
+    let columns = col_indexes
+        .iter()
+        .map(|idx| schema.field(*idx).name().to_string())
+        .collect::<Vec<_>>();
+
+    let mut series: Vec<Series> = vec![];
+    for batch in batches {
+        let measurement = cast(batch.column(measurement_idx), &DataType::Utf8)?;
+        let measurement = as_string_array(&measurement);
+        let tag_values = md
+            .tag_key_columns
+            .iter()
+            .map(|tk| cast(batch.column(tk.column_index as usize), &DataType::Utf8))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for row in 0..batch.num_rows() {
+            let name = measurement.value(row);
+            let tags = md
+                .tag_key_columns
+                .iter()
+                .zip(&tag_values)
+                .map(|(tk, values)| {
+                    let values = as_string_array(values);
+                    let value = if values.is_null(row) {
+                        ""
+                    } else {
+                        values.value(row)
+                    };
+                    (tk.tag_key.clone(), value.to_string())
+                })
+                .collect::<BTreeMap<_, _>>();
+            let values = col_indexes
+                .iter()
+                .map(|idx| json_value(batch.column(*idx), row, epoch))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            match series.last_mut() {
+                Some(s) if s.name == name && s.tags == tags => s.values.push(values),
+                _ => series.push(Series {
+                    name: name.to_string(),
+                    tags,
+                    columns: columns.clone(),
+                    values: vec![values],
+                    partial: false,
+                }),
+            }
+        }
+    }
+
+    Ok(series)
+}
+
+/// Read the value at `row` of `array` as a JSON value.
+fn json_value(array: &ArrayRef, row: usize, epoch: Option<Epoch>) -> Result<Value, ArrowError> {
+    if array.is_null(row) {
+        return Ok(Value::Null);
+    }
+
+    Ok(match array.data_type() {
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+            let v = as_primitive_array::<TimestampNanosecondType>(array).value(row);
+            match epoch {
+                Some(epoch) => epoch.convert(v).into(),
+                None => format_rfc3339_nano(v).into(),
+            }
+        }
+        DataType::Int64 => as_primitive_array::<Int64Type>(array).value(row).into(),
+        DataType::UInt64 => as_primitive_array::<UInt64Type>(array).value(row).into(),
+        DataType::Float64 => {
+            // NaN and infinities have no JSON representation.
+            serde_json::Number::from_f64(as_primitive_array::<Float64Type>(array).value(row))
+                .map(Value::Number)
+                .unwrap_or(Value::Null)
+        }
+        DataType::Boolean => as_boolean_array(array).value(row).into(),
+        DataType::Utf8 => as_string_array(array).value(row).into(),
+        _ => {
+            let formatter = ArrayFormatter::try_new(array, &FormatOptions::default())?;
+            formatter.value(row).to_string().into()
+        }
+    })
+}
+
+/// Format `nanos` in the style of Go's `time.RFC3339Nano`, which InfluxDB
+/// 1.x uses and which omits trailing zeros of the fractional seconds.
+fn format_rfc3339_nano(nanos: i64) -> String {
+    let s = Utc
+        .timestamp_nanos(nanos)
+        .format("%Y-%m-%dT%H:%M:%S%.9f")
+        .to_string();
+    format!("{}Z", s.trim_end_matches('0').trim_end_matches('.'))
+}
+
+/// Encode `results` as a response body in the specified `format`.
+///
+/// If `chunk_size` is specified, JSON responses are encoded as a sequence of
+/// newline-delimited response objects, each containing at most `chunk_size`
+/// rows.
+pub(crate) fn encode(
+    format: ResponseFormat,
+    results: Vec<StatementResult>,
+    chunk_size: Option<usize>,
+) -> Result<Vec<Bytes>, V1ResponseError> {
+    match (format, chunk_size) {
+        (ResponseFormat::Json, None) => Ok(vec![serde_json::to_vec(&QueryResponse {
+            results: &results,
+        })
+        .map_err(V1ResponseError::Serialise)?
+        .into()]),
+        (ResponseFormat::Json, Some(chunk_size)) => results
+            .into_iter()
+            .flat_map(|r| r.into_chunks(chunk_size))
+            .map(|chunk| {
+                let mut buf = serde_json::to_vec(&QueryResponse {
+                    results: std::slice::from_ref(&chunk),
+                })
+                .map_err(V1ResponseError::Serialise)?;
+                buf.push(b'\n');
+                Ok(buf.into())
+            })
+            .collect(),
+        (ResponseFormat::Csv, _) => Ok(vec![encode_csv(&results).into()]),
+    }
+}
+
+/// Encode `results` in the InfluxDB 1.x CSV format, where each row is
+/// prefixed by the measurement name and the group key.
+fn encode_csv(results: &[StatementResult]) -> String {
+    let mut out = String::new();
+    let mut last_header: Option<Vec<String>> = None;
+
+    for result in results {
+        if let Some(error) = &result.error {
+            write_csv_row(&mut out, ["error"]);
+            write_csv_row(&mut out, [error.as_str()]);
+            last_header = None;
+            continue;
+        }
+
+        for series in &result.series {
+            let header = ["name", "tags"]
+                .into_iter()
+                .map(ToString::to_string)
+                .chain(series.columns.iter().cloned())
+                .collect::<Vec<_>>();
+            if last_header.as_ref() != Some(&header) {
+                // Separate each new header from any previous rows.
+                if last_header.is_some() {
+                    out.push('\n');
+                }
+                write_csv_row(&mut out, header.iter().map(String::as_str));
+                last_header = Some(header);
+            }
+
+            let tags = series
+                .tags
+                .iter()
+                .map(|(k, v)| format!("{k}={v}"))
+                .collect::<Vec<_>>()
+                .join(",");
+            for row in &series.values {
+                let row = row
+                    .iter()
+                    .map(|v| match v {
+                        Value::Null => String::new(),
+                        Value::String(s) => s.clone(),
+                        v => v.to_string(),
+                    })
+                    .collect::<Vec<_>>();
+                write_csv_row(
+                    &mut out,
+                    [series.name.as_str(), tags.as_str()]
+                        .into_iter()
+                        .chain(row.iter().map(String::as_str)),
+                );
+            }
+        }
+    }
+
+    out
+}
+
+/// Append a single CSV record to `out`, quoting fields as necessary.
+fn write_csv_row<'a>(out: &mut String, fields: impl IntoIterator<Item = &'a str>) {
+    for (i, field) in fields.into_iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        if field.contains([',', '"', '\n', '\r']) {
+            out.push('"');
+            out.push_str(&field.replace('"', "\"\""));
+            out.push('"');
+        } else {
+            out.push_str(field);
+        }
+    }
+    out.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Arc};
+
+    use arrow::{
+        array::{Float64Array, Int64Array, StringArray, TimestampNanosecondArray},
+        datatypes::{Field, Schema},
+    };
+    use generated_types::influxdata::iox::querier::v1::influx_ql_metadata::TagKeyColumn;
+    use serde_json::json;
+
+    use super::*;
+
+    fn batches(meta: InfluxQlMetadata) -> Vec<RecordBatch> {
+        let schema = Arc::new(Schema::new_with_metadata(
+            vec![
+                Field::new("iox::measurement", DataType::Utf8, false),
+                Field::new(
+                    "time",
+                    DataType::Timestamp(TimeUnit::Nanosecond, None),
+                    false,
+                ),
+                Field::new("cpu", DataType::Utf8, true),
+                Field::new("usage_idle", DataType::Float64, true),
+                Field::new("free", DataType::Int64, true),
+            ],
+            HashMap::from([(
+                schema::INFLUXQL_METADATA_KEY.to_owned(),
+                serde_json::to_string(&meta).unwrap(),
+            )]),
+        ));
+
+        vec![RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(StringArray::from(vec!["cpu", "cpu", "cpu", "disk"])),
+                Arc::new(TimestampNanosecondArray::from(vec![
+                    1157082300000000000,
+                    1157082310000000000,
+                    1157082300100000000,
+                    1157082300000000000,
+                ])),
+                Arc::new(StringArray::from(vec![
+                    Some("cpu0"),
+                    Some("cpu0"),
+                    Some("cpu1"),
+                    None,
+                ])),
+                Arc::new(Float64Array::from(vec![
+                    Some(99.1),
+                    Some(f64::NAN),
+                    Some(99.2),
+                    None,
+                ])),
+                Arc::new(Int64Array::from(vec![None, None, None, Some(2133)])),
+            ],
+        )
+        .unwrap()]
+    }
+
+    fn to_json(results: Vec<StatementResult>, chunk_size: Option<usize>) -> Vec<Value> {
+        encode(ResponseFormat::Json, results, chunk_size)
+            .unwrap()
+            .into_iter()
+            .map(|b| serde_json::from_slice(&b).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_series_no_group_key() {
+        let rb = batches(InfluxQlMetadata {
+            measurement_column_index: 0,
+            tag_key_columns: vec![],
+        });
+        let series = series_from_batches(&rb, None).unwrap();
+
+        assert_eq!(
+            to_json(vec![StatementResult::ok(0, series)], None),
+            [json!({"results": [{"statement_id": 0, "series": [
+                {
+                    "name": "cpu",
+                    "columns": ["time", "cpu", "usage_idle", "free"],
+                    "values": [
+                        ["2006-09-01T03:45:00Z", "cpu0", 99.1, null],
+                        ["2006-09-01T03:45:10Z", "cpu0", null, null],
+                        ["2006-09-01T03:45:00.1Z", "cpu1", 99.2, null],
+                    ],
+                },
+                {
+                    "name": "disk",
+                    "columns": ["time", "cpu", "usage_idle", "free"],
+                    "values": [["2006-09-01T03:45:00Z", null, null, 2133]],
+                },
+            ]}]})]
+        );
+    }
+
+    #[test]
+    fn test_series_group_key() {
+        let rb = batches(InfluxQlMetadata {
+            measurement_column_index: 0,
+            tag_key_columns: vec![TagKeyColumn {
+                tag_key: "cpu".to_string(),
+                column_index: 2,
+                is_projected: false,
+            }],
+        });
+        let series = series_from_batches(&rb, Some(Epoch::Seconds)).unwrap();
+
+        assert_eq!(
+            to_json(vec![StatementResult::ok(0, series)], None),
+            [json!({"results": [{"statement_id": 0, "series": [
+                {
+                    "name": "cpu",
+                    "tags": {"cpu": "cpu0"},
+                    "columns": ["time", "usage_idle", "free"],
+                    "values": [[1157082300, 99.1, null], [1157082310, null, null]],
+                },
+                {
+                    "name": "cpu",
+                    "tags": {"cpu": "cpu1"},
+                    "columns": ["time", "usage_idle", "free"],
+                    "values": [[1157082300, 99.2, null]],
+                },
+                {
+                    "name": "disk",
+                    "tags": {"cpu": ""},
+                    "columns": ["time", "usage_idle", "free"],
+                    "values": [[1157082300, null, 2133]],
+                },
+            ]}]})]
+        );
+    }
+
+    #[test]
+    fn test_missing_metadata() {
+        let rb = batches(InfluxQlMetadata::default());
+        let rb = vec![RecordBatch::try_new(
+            Arc::new(
+                rb[0]
+                    .schema()
+                    .as_ref()
+                    .clone()
+                    .with_metadata(HashMap::new()),
+            ),
+            rb[0].columns().to_vec(),
+        )
+        .unwrap()];
+
+        assert!(matches!(
+            series_from_batches(&rb, None),
+            Err(V1ResponseError::MissingMetadata)
+        ));
+        assert!(series_from_batches(&[], None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_statement_errors() {
+        let results = vec![
+            StatementResult::ok(0, vec![]),
+            StatementResult::error(1, "bananas"),
+        ];
+        assert_eq!(
+            to_json(results, None),
+            [json!({"results": [
+                {"statement_id": 0},
+                {"statement_id": 1, "error": "bananas"},
+            ]})]
+        );
+    }
+
+    #[test]
+    fn test_chunked() {
+        let rb = batches(InfluxQlMetadata {
+            measurement_column_index: 0,
+            tag_key_columns: vec![],
+        });
+        let series = series_from_batches(&rb, Some(Epoch::Seconds)).unwrap();
+
+        assert_eq!(
+            to_json(vec![StatementResult::ok(0, series)], Some(2)),
+            [
+                json!({"results": [{"statement_id": 0, "partial": true, "series": [{
+                    "name": "cpu",
+                    "columns": ["time", "cpu", "usage_idle", "free"],
+                    "values": [[1157082300, "cpu0", 99.1, null], [1157082310, "cpu0", null, null]],
+                    "partial": true,
+                }]}]}),
+                json!({"results": [{"statement_id": 0, "partial": true, "series": [{
+                    "name": "cpu",
+                    "columns": ["time", "cpu", "usage_idle", "free"],
+                    "values": [[1157082300, "cpu1", 99.2, null]],
+                }]}]}),
+                json!({"results": [{"statement_id": 0, "series": [{
+                    "name": "disk",
+                    "columns": ["time", "cpu", "usage_idle", "free"],
+                    "values": [[1157082300, null, null, 2133]],
+                }]}]}),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_csv() {
+        let rb = batches(InfluxQlMetadata {
+            measurement_column_index: 0,
+            tag_key_columns: vec![TagKeyColumn {
+                tag_key: "cpu".to_string(),
+                column_index: 2,
+                is_projected: false,
+            }],
+        });
+        let series = series_from_batches(&rb, Some(Epoch::Nanoseconds)).unwrap();
+        let results = vec![
+            StatementResult::ok(0, series),
+            StatementResult::error(1, "bad, things"),
+        ];
+
+        let got = encode(ResponseFormat::Csv, results, None).unwrap();
+        assert_eq!(got.len(), 1);
+        assert_eq!(
+            std::str::from_utf8(&got[0]).unwrap(),
+            "name,tags,time,usage_idle,free\n\
+             cpu,cpu=cpu0,1157082300000000000,99.1,\n\
+             cpu,cpu=cpu0,1157082310000000000,,\n\
+             cpu,cpu=cpu1,1157082300100000000,99.2,\n\
+             disk,cpu=,1157082300000000000,,2133\n\
+             error\n\
+             \"bad, things\"\n"
+        );
+    }
+
+    #[test]
+    fn test_response_format() {
+        assert_eq!(ResponseFormat::from_accept(None), ResponseFormat::Json);
+        assert_eq!(
+            ResponseFormat::from_accept(Some("application/json")),
+            ResponseFormat::Json
+        );
+        assert_eq!(
+            ResponseFormat::from_accept(Some("application/csv")),
+            ResponseFormat::Csv
+        );
+        assert_eq!(
+            ResponseFormat::from_accept(Some("text/html, text/csv;q=0.9")),
+            ResponseFormat::Csv
+        );
+    }
+}
@@ -32,7 +32,7 @@ use iox_query::exec::{Executor, ExecutorType};
 use iox_time::TimeProvider;
 use ioxd_common::{
     add_service,
-    http::error::{HttpApiError, HttpApiErrorCode, HttpApiErrorSource},
+    http::error::{HttpApiError, HttpApiErrorSource},
     rpc::RpcBuilderInput,
     serve_builder,
     server_type::{CommonServerState, RpcError, ServerType},
@@ -40,7 +40,10 @@ use ioxd_common::{
 };
 use metric::Registry;
 use object_store::{DynObjectStore, ObjectStore};
-use querier::{create_ingester_connections, QuerierCatalogCache, QuerierDatabase, QuerierServer};
+use querier::{
+    create_ingester_connections, QuerierCatalogCache, QuerierDatabase, QuerierServer,
+    RouterDeleter, RouterPointsWriter,
+};
 use std::{
     fmt::{Debug, Display},
     sync::Arc,
@@ -50,12 +53,14 @@ use tokio::runtime::Handle;
 use tokio_util::sync::CancellationToken;
 use trace::TraceCollector;
 
+mod http;
 mod rpc;
 
 pub struct QuerierServerType {
     catalog: Arc<dyn Catalog>,
     database: Arc<QuerierDatabase>,
     server: QuerierServer,
+    http: http::HttpDelegate<QuerierDatabase>,
     metric_registry: Arc<Registry>,
     object_store: Arc<dyn ObjectStore>,
     trace_collector: Option<Arc<dyn TraceCollector>>,
@@ -85,12 +90,18 @@ impl ServerType for QuerierServerType {
         self.trace_collector.as_ref().map(Arc::clone)
     }
 
-    /// Just return "not found".
+    /// Dispatches [`Request`] to the querier [`HttpDelegate`].
+    ///
+    /// [`HttpDelegate`]: http::HttpDelegate
     async fn route_http_request(
         &self,
-        _req: Request<Body>,
+        req: Request<Body>,
     ) -> Result<Response<Body>, Box<dyn HttpApiErrorSource>> {
-        Err(Box::new(IoxHttpError::NotFound))
+        self.http
+            .route(req)
+            .await
+            .map_err(IoxHttpErrorAdaptor)
+            .map_err(|e| Box::new(e) as _)
     }
 
     /// Configure the gRPC services.
@@ -142,31 +153,23 @@ impl ServerType for QuerierServerType {
     }
 }
 
-/// Simple error struct, we're not really providing an HTTP interface for the compactor.
+/// This adaptor converts the `querier` http error type into a type that
+/// satisfies the requirements of ioxd's runner framework, keeping the
+/// two decoupled.
 #[derive(Debug)]
-pub enum IoxHttpError {
-    NotFound,
-}
-
-impl IoxHttpError {
-    fn status_code(&self) -> HttpApiErrorCode {
-        match self {
-            Self::NotFound => HttpApiErrorCode::NotFound,
-        }
-    }
-}
+pub struct IoxHttpErrorAdaptor(http::Error);
 
-impl Display for IoxHttpError {
+impl Display for IoxHttpErrorAdaptor {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{self:?}")
+        Display::fmt(&self.0, f)
     }
 }
 
-impl std::error::Error for IoxHttpError {}
+impl std::error::Error for IoxHttpErrorAdaptor {}
 
-impl HttpApiErrorSource for IoxHttpError {
+impl HttpApiErrorSource for IoxHttpErrorAdaptor {
     fn to_http_api_error(&self) -> HttpApiError {
-        HttpApiError::new(self.status_code(), self.to_string())
+        HttpApiError::new(self.0.as_status_code(), self.to_string())
     }
 }
 
@@ -255,23 +258,33 @@ pub async fn create_querier_server_type(
         ))
     };
 
-    let database = Arc::new(
-        QuerierDatabase::new(
-            catalog_cache,
-            Arc::clone(&args.metric_registry),
-            args.exec,
-            ingester_connections,
-            args.querier_config.max_concurrent_queries,
-            Arc::new(args.querier_config.datafusion_config),
-        )
-        .await?,
-    );
+    let mut database = QuerierDatabase::new(
+        catalog_cache,
+        Arc::clone(&args.metric_registry),
+        args.exec,
+        ingester_connections,
+        args.querier_config.max_concurrent_queries,
+        Arc::new(args.querier_config.datafusion_config),
+    )
+    .await?;
+    if let Some(addr) = args.querier_config.router_address {
+        database = database.with_points_writer(Arc::new(RouterPointsWriter::new(
+            addr,
+            args.querier_config.single_tenant_deployment,
+        )));
+    }
+    if let Some(addr) = args.querier_config.router_grpc_address {
+        database = database.with_deleter(Arc::new(RouterDeleter::new(addr)));
+    }
+    let database = Arc::new(database);
 
     let server = QuerierServer::new(Arc::clone(&database));
+    let http = http::HttpDelegate::new(Arc::clone(&database), authz.as_ref().map(Arc::clone));
     Ok(Arc::new(QuerierServerType {
         catalog: args.catalog,
         database,
         server,
+        http,
         metric_registry: args.metric_registry,
         object_store: args.object_store,
         trace_collector: args.common_state.trace_collector(),
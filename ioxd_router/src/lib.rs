@@ -35,7 +35,7 @@ use ioxd_common::{
     http::error::{HttpApiError, HttpApiErrorSource},
     reexport::{
         generated_types::influxdata::iox::{
-            catalog::v1::catalog_service_server, gossip::Topic,
+            catalog::v1::catalog_service_server, delete::v1::delete_service_server, gossip::Topic,
             namespace::v1::namespace_service_server, object_store::v1::object_store_service_server,
             schema::v1::schema_service_server, table::v1::table_service_server,
         },
@@ -53,6 +53,7 @@ use router::{
     dml_handlers::{
         lazy_connector::LazyConnector, DmlHandler, DmlHandlerChainExt, FanOutAdaptor,
         InstrumentationDecorator, Partitioner, RetentionValidator, RpcWrite, SchemaValidator,
+        TombstoneRecorder,
     },
     gossip::{
         anti_entropy::mst::{
@@ -130,8 +131,8 @@ impl<D, N> std::fmt::Debug for RpcWriteRouterServerType<D, N> {
 #[async_trait]
 impl<D, N> ServerType for RpcWriteRouterServerType<D, N>
 where
-    D: DmlHandler<WriteInput = HashMap<String, MutableBatch>, WriteOutput = ()> + 'static,
-    N: NamespaceResolver + 'static,
+    D: DmlHandler<WriteInput = HashMap<String, MutableBatch>, WriteOutput = ()> + Clone + 'static,
+    N: NamespaceResolver + Clone + 'static,
 {
     fn name(&self) -> &str {
         "rpc_write_router"
@@ -191,6 +192,30 @@ where
             builder,
             table_service_server::TableServiceServer::new(self.server.grpc().table_service())
         );
+        add_service!(
+            builder,
+            delete_service_server::DeleteServiceServer::new(
+                self.server
+                    .grpc()
+                    .delete_service(self.server.http().dml_handler().clone())
+            )
+        );
+        add_service!(
+            builder,
+            self.server.grpc().flight_service(
+                self.server.http().namespace_resolver().clone(),
+                self.server.http().dml_handler().clone(),
+                self.server.http().max_request_bytes(),
+            )
+        );
+        add_service!(
+            builder,
+            self.server.grpc().otlp_metrics_service(
+                self.server.http().namespace_resolver().clone(),
+                self.server.http().dml_handler().clone(),
+                self.server.http().max_request_bytes(),
+            )
+        );
         serve_builder!(builder);
 
         Ok(())
@@ -393,7 +418,9 @@ pub async fn create_router_server_type(
     // Initialise the Namespace ID lookup + cache
     let namespace_resolver = NamespaceSchemaResolver::new(Arc::clone(&ns_cache));
 
-    let namespace_resolver = NamespaceAutocreation::new(
+    // The namespace resolver is shared between the HTTP and gRPC (Flight)
+    // write APIs.
+    let namespace_resolver = Arc::new(NamespaceAutocreation::new(
         namespace_resolver,
         Arc::clone(&ns_cache),
         Arc::clone(&catalog),
@@ -408,7 +435,7 @@ pub async fn create_router_server_type(
                 MissingNamespaceAction::Reject
             }
         },
-    );
+    ));
     //
     ////////////////////////////////////////////////////////////////////////////
 
@@ -431,13 +458,23 @@ pub async fn create_router_server_type(
             "parallel_write",
             &metrics,
             parallel_write,
-        ));
+        ))
+        // Deletes are recorded in the catalog only once they have been applied
+        // to the data buffered in the ingesters.
+        .and_then(TombstoneRecorder::new(Arc::clone(&catalog)));
 
     // Record the overall request handling latency
-    let handler_stack = InstrumentationDecorator::new("request", &metrics, handler_stack);
+    //
+    // The handler stack is shared between the HTTP and gRPC (delete) APIs.
+    let handler_stack = Arc::new(InstrumentationDecorator::new(
+        "request",
+        &metrics,
+        handler_stack,
+    ));
 
-    // Initialize the HTTP API delegate
-    let write_request_unifier: Result<Box<dyn WriteRequestUnifier>> = match (
+    // Connect to the authz service used to authorize writes in single tenant
+    // deployments.
+    let authz = match (
         router_config.single_tenant_deployment,
         &router_config.authz_address,
     ) {
@@ -452,7 +489,7 @@ pub async fn create_router_server_type(
                 })?;
             authz.probe().await.expect("Authz connection test failed.");
 
-            Ok(Box::new(SingleTenantRequestUnifier::new(authz)))
+            Some(authz)
         }
         (true, None) => {
             // Single tenancy was requested, but no auth was provided - the
@@ -461,26 +498,33 @@ pub async fn create_router_server_type(
             // never reach here.
             unreachable!("INFLUXDB_IOX_SINGLE_TENANCY is set, but could not create an authz service. Check the INFLUXDB_IOX_AUTHZ_ADDR")
         }
-        (false, None) => Ok(Box::<MultiTenantRequestUnifier>::default()),
+        (false, None) => None,
         (false, Some(_)) => {
             // As above, this combination should be prevented by the
             // router's clap flag parse configuration.
             unreachable!("INFLUXDB_IOX_AUTHZ_ADDR is set, but authz only exists for single_tenancy. Check the INFLUXDB_IOX_SINGLE_TENANCY")
         }
     };
+
+    // Initialize the HTTP API delegate
+    let write_request_unifier: Box<dyn WriteRequestUnifier> = match &authz {
+        Some(authz) => Box::new(SingleTenantRequestUnifier::new(Arc::clone(authz))),
+        None => Box::<MultiTenantRequestUnifier>::default(),
+    };
     let http = HttpDelegate::new(
         common_state.run_config().max_http_request_size,
         router_config.http_request_limit,
+        router_config.partial_writes_enabled,
         namespace_resolver,
         handler_stack,
         &metrics,
-        write_request_unifier?,
+        write_request_unifier,
     );
 
     // Initialize the gRPC API delegate that creates the services relevant to the RPC
     // write router path and use it to create the relevant `RpcWriteRouterServer` and
     // `RpcWriteRouterServerType`.
-    let grpc = RpcWriteGrpcDelegate::new(catalog, object_store);
+    let grpc = RpcWriteGrpcDelegate::new(catalog, object_store, authz);
 
     let router_server =
         RpcWriteRouterServer::new(http, grpc, metrics, common_state.trace_collector());
@@ -24,7 +24,7 @@ use hashbrown::{hash_map::Entry, HashMap, HashSet};
 use influxdb_line_protocol::{parse_lines, FieldValue, ParsedLine};
 use mutable_batch::writer::Writer;
 use mutable_batch::MutableBatch;
-use snafu::{ResultExt, Snafu};
+use snafu::{IntoError, ResultExt, Snafu};
 
 /// Error type for line protocol conversion
 #[derive(Debug, Snafu)]
@@ -46,6 +46,48 @@ pub enum Error {
     TimestampOverflow,
 }
 
+/// Error type for a single line rejected during line protocol conversion
+#[derive(Debug, Snafu)]
+#[allow(missing_docs)]
+pub enum LineError {
+    #[snafu(display("{}", source))]
+    Parse {
+        source: influxdb_line_protocol::Error,
+    },
+
+    #[snafu(display("{}", source))]
+    LineWrite { source: LineWriteError },
+
+    #[snafu(display("timestamp overflows i64"))]
+    TimestampOverflow,
+}
+
+impl LineError {
+    /// Convert this error into an [`Error`] for the 1-based `line` number.
+    fn into_error(self, line: usize) -> Error {
+        match self {
+            Self::Parse { source } => LineProtocolSnafu { line }.into_error(source),
+            Self::LineWrite { source } => WriteSnafu { line }.into_error(source),
+            Self::TimestampOverflow => Error::TimestampOverflow,
+        }
+    }
+}
+
+/// A line rejected by [`LinesConverter::write_lp_partial()`]
+#[derive(Debug)]
+pub struct RejectedLine {
+    /// The 1-based line number of the rejected line
+    pub line: usize,
+    /// The reason the line was rejected
+    pub error: LineError,
+}
+
+impl std::fmt::Display for RejectedLine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.error)
+    }
+}
+
 /// Result type for line protocol conversion
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
@@ -108,37 +150,73 @@ impl LinesConverter {
     ///
     pub fn write_lp(&mut self, lines: &str) -> Result<()> {
         for (line_idx, maybe_line) in parse_lines(lines).enumerate() {
-            let mut line = maybe_line.context(LineProtocolSnafu { line: line_idx + 1 })?;
+            self.convert_line(maybe_line)
+                .map_err(|e| e.into_error(line_idx + 1))?;
+        }
+        Ok(())
+    }
 
-            if let Some(t) = line.timestamp.as_mut() {
-                *t = t
-                    .checked_mul(self.timestamp_base)
-                    .ok_or(Error::TimestampOverflow)?;
-            }
+    /// Write some line protocol data, skipping any lines that cannot be
+    /// parsed or written.
+    ///
+    /// All valid lines are written following the same semantics as
+    /// [`Self::write_lp()`], and a [`RejectedLine`] is returned for each
+    /// invalid line, in the order they appear in `lines`.
+    pub fn write_lp_partial(&mut self, lines: &str) -> Vec<RejectedLine> {
+        parse_lines(lines)
+            .enumerate()
+            .filter_map(|(line_idx, maybe_line)| {
+                self.convert_line(maybe_line)
+                    .err()
+                    .map(|error| RejectedLine {
+                        line: line_idx + 1,
+                        error,
+                    })
+            })
+            .collect()
+    }
 
-            self.stats.num_lines += 1;
-            self.stats.num_fields += line.field_set.len();
+    /// Write a single (possibly unparsable) line to the batch for its
+    /// measurement.
+    ///
+    /// A line that fails to be written leaves the batches unchanged.
+    fn convert_line(
+        &mut self,
+        maybe_line: Result<ParsedLine<'_>, influxdb_line_protocol::Error>,
+    ) -> Result<(), LineError> {
+        let mut line = maybe_line.context(ParseSnafu)?;
+
+        if let Some(t) = line.timestamp.as_mut() {
+            *t = t
+                .checked_mul(self.timestamp_base)
+                .ok_or(LineError::TimestampOverflow)?;
+        }
 
-            let measurement = line.series.measurement.as_str();
+        let measurement = line.series.measurement.as_str();
 
-            let (_, batch) = self
-                .batches
-                .raw_entry_mut()
-                .from_key(measurement)
-                .or_insert_with(|| (measurement.to_string(), MutableBatch::new()));
+        let (_, batch) = self
+            .batches
+            .raw_entry_mut()
+            .from_key(measurement)
+            .or_insert_with(|| (measurement.to_string(), MutableBatch::new()));
+
+        // TODO: Reuse writer
+        let mut writer = Writer::new(batch, 1);
+        write_line(&mut writer, &line, self.default_time).context(LineWriteSnafu)?;
+        writer.commit();
+
+        self.stats.num_lines += 1;
+        self.stats.num_fields += line.field_set.len();
 
-            // TODO: Reuse writer
-            let mut writer = Writer::new(batch, 1);
-            write_line(&mut writer, &line, self.default_time)
-                .context(WriteSnafu { line: line_idx + 1 })?;
-            writer.commit();
-        }
         Ok(())
     }
 
     /// Consume this [`LinesConverter`] returning the [`MutableBatch`]
     /// and the [`PayloadStatistics`] for the written data
-    pub fn finish(self) -> Result<(HashMap<String, MutableBatch>, PayloadStatistics)> {
+    pub fn finish(mut self) -> Result<(HashMap<String, MutableBatch>, PayloadStatistics)> {
+        // Rejected lines may leave behind batches containing no rows.
+        self.batches.retain(|_, batch| batch.rows() > 0);
+
         match self.batches.is_empty() {
             false => Ok((self.batches, self.stats)),
             true => Err(Error::EmptyPayload),
@@ -452,6 +530,72 @@ m b=t 1639612800000000000
         assert!(!u.is_valid(2));
     }
 
+    #[test]
+    fn test_write_lp_partial() {
+        let lp = r#"cpu,tag1=v1 val=1i 1
+        cpu,tag1=v1 val=
+        mem,tag1=v2 ival=3i,ival=3.0 2
+        cpu,tag1=v2 val=2.0 3
+        cpu,tag1=v3 val=3i 4
+        "#;
+
+        let mut converter = LinesConverter::new(5);
+        let rejected = converter.write_lp_partial(lp);
+
+        assert_matches!(
+            rejected.as_slice(),
+            [
+                RejectedLine {
+                    line: 2,
+                    error: LineError::Parse { .. }
+                },
+                RejectedLine {
+                    line: 3,
+                    error: LineError::LineWrite {
+                        source: LineWriteError::ConflictedFieldTypes { .. }
+                    }
+                },
+                RejectedLine {
+                    line: 4,
+                    error: LineError::LineWrite {
+                        source: LineWriteError::MutableBatch { .. }
+                    }
+                },
+            ]
+        );
+        assert_eq!(
+            rejected[2].to_string(),
+            "line 4: Unable to insert iox::column_type::field::float type into column val \
+            with type iox::column_type::field::integer"
+        );
+
+        let (batches, stats) = converter.finish().unwrap();
+        assert_eq!(stats.num_lines, 2);
+        assert_eq!(stats.num_fields, 2);
+
+        // The rejected "mem" line must not leave an empty batch behind.
+        assert_eq!(batches.len(), 1);
+        assert_batches_eq!(
+            &[
+                "+------+--------------------------------+-----+",
+                "| tag1 | time                           | val |",
+                "+------+--------------------------------+-----+",
+                "| v1   | 1970-01-01T00:00:00.000000001Z | 1   |",
+                "| v3   | 1970-01-01T00:00:00.000000004Z | 3   |",
+                "+------+--------------------------------+-----+",
+            ],
+            &[batches["cpu"].to_arrow(Projection::All).unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_write_lp_partial_all_rejected() {
+        let mut converter = LinesConverter::new(5);
+        let rejected = converter.write_lp_partial("bananas\nplatanos");
+        assert_eq!(rejected.len(), 2);
+        assert_matches!(converter.finish(), Err(Error::EmptyPayload));
+    }
+
     // https://github.com/influxdata/influxdb_iox/issues/4326
     mod issue4326 {
         use super::*;
@@ -290,7 +290,8 @@ pub struct IoxMetadata {
     pub sort_key: Option<SortKey>,
 
     /// Max timestamp of creation timestamp of L0 files
-    /// If this metadata is for an L0 file, this value will be the same as the `creation_timestamp`
+    /// If this metadata is for an L0 file, this value will be the time the ingester snapshotted
+    ///  the data it contains, which is at or before the `creation_timestamp`
     /// If this metadata is for an L1/L2 file, this value will be the max of all L0 files
     ///  that are compacted into this file
     pub max_l0_created_at: Time,
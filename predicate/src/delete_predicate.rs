@@ -1,16 +1,19 @@
 use crate::delete_expr::{df_to_expr, expr_to_df};
 use chrono::DateTime;
-use data_types::{DeleteExpr, DeletePredicate, TimestampRange};
+use data_types::{DeleteExpr, DeletePredicate, TimestampRange, Tombstone};
 use datafusion::{
+    arrow::datatypes::Schema as ArrowSchema,
     logical_expr::Operator,
-    prelude::{binary_expr, lit, Column, Expr},
+    prelude::{binary_expr, col, lit, lit_timestamp_nano, Column, Expr},
 };
+use schema::TIME_COLUMN_NAME;
 use snafu::Snafu;
 use sqlparser::{
     ast::{BinaryOperator, Expr as SqlParserExpr, Ident, Statement, Value},
     dialect::GenericDialect,
     parser::Parser,
 };
+use std::sync::Arc;
 
 /// Parse Delete Predicates
 /// Parse Error
@@ -69,6 +72,54 @@ pub fn parse_delete_predicate(
     })
 }
 
+/// Reconstruct the [`DeletePredicate`] recorded by a catalog [`Tombstone`].
+pub fn parse_tombstone(tombstone: &Tombstone) -> Result<DeletePredicate> {
+    parse_delete_predicate(
+        &tombstone.min_time.get().to_string(),
+        &tombstone.max_time.get().to_string(),
+        &tombstone.serialized_predicate,
+    )
+}
+
+/// Build an expression that evaluates to true for the rows removed by
+/// `pred`.
+///
+/// The time range of a delete predicate is inclusive at both ends. Rows for
+/// which the expression evaluates to NULL (i.e. a column referenced by the
+/// predicate is not set) do not match, so callers that want to retain all
+/// other rows should filter on `NOT(expr IS TRUE)`.
+pub fn delete_predicate_expr(pred: &DeletePredicate) -> Expr {
+    let time_expr = col(TIME_COLUMN_NAME)
+        .gt_eq(lit_timestamp_nano(pred.range.start()))
+        .and(col(TIME_COLUMN_NAME).lt_eq(lit_timestamp_nano(pred.range.end())));
+
+    pred.exprs
+        .iter()
+        .cloned()
+        .map(expr_to_df)
+        .fold(time_expr, Expr::and)
+}
+
+/// Build an expression for each of `preds` selecting the rows of a table with
+/// the given `schema` that are not deleted by that predicate. A row is only
+/// kept if it is selected by all the returned expressions.
+///
+/// Predicates referring to a column not present in `schema` cannot match any
+/// row and are skipped.
+pub fn keep_rows_exprs(preds: &[Arc<DeletePredicate>], schema: &ArrowSchema) -> Vec<Expr> {
+    preds
+        .iter()
+        .filter(|pred| {
+            pred.exprs
+                .iter()
+                .all(|expr| schema.field_with_name(expr.column()).is_ok())
+        })
+        // A NULL column value never matches a delete expression, so those
+        // rows must be kept.
+        .map(|pred| delete_predicate_expr(pred).is_not_true())
+        .collect()
+}
+
 /// Parse the predicate and convert it into datafusion expression
 /// A delete predicate is a conjunctive expression of many
 /// binary expressions of 'colum = constant' or 'column != constant'
@@ -439,4 +490,68 @@ mod tests {
         let result = parse_delete_predicate(start, stop, pred);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_tombstone_round_trip() {
+        let pred = parse_delete_predicate(
+            "1970-01-01T00:00:00Z",
+            "200",
+            r#"city = "Boston" AND cost != 100"#,
+        )
+        .unwrap();
+
+        let tombstone = Tombstone {
+            id: data_types::TombstoneId::new(1),
+            table_id: data_types::TableId::new(1),
+            min_time: data_types::Timestamp::new(pred.range.start()),
+            max_time: data_types::Timestamp::new(pred.range.end()),
+            serialized_predicate: pred.expr_sql_string(),
+            created_at: data_types::Timestamp::new(42),
+        };
+
+        assert_eq!(parse_tombstone(&tombstone).unwrap(), pred);
+    }
+
+    #[test]
+    fn test_delete_predicate_expr() {
+        let pred = parse_delete_predicate("100", "200", r#"city = "Boston""#).unwrap();
+        let expr = delete_predicate_expr(&pred);
+
+        assert_eq!(
+            expr.to_string(),
+            "time >= TimestampNanosecond(100, None) AND time <= TimestampNanosecond(200, None) \
+            AND city = Utf8(\"Boston\")"
+        );
+    }
+
+    #[test]
+    fn test_keep_rows_exprs() {
+        use datafusion::arrow::datatypes::{DataType, Field, TimeUnit};
+
+        let schema = ArrowSchema::new(vec![
+            Field::new("city", DataType::Utf8, true),
+            Field::new(
+                TIME_COLUMN_NAME,
+                DataType::Timestamp(TimeUnit::Nanosecond, None),
+                false,
+            ),
+        ]);
+
+        let city = Arc::new(parse_delete_predicate("100", "200", r#"city = "Boston""#).unwrap());
+        let state = Arc::new(parse_delete_predicate("100", "200", r#"state = "MA""#).unwrap());
+
+        assert!(keep_rows_exprs(&[], &schema).is_empty());
+        assert!(keep_rows_exprs(&[Arc::clone(&state)], &schema).is_empty());
+
+        let want =
+            "time >= TimestampNanosecond(100, None) AND time <= TimestampNanosecond(200, None) \
+            AND city = Utf8(\"Boston\") IS NOT TRUE";
+        assert_eq!(
+            keep_rows_exprs(&[Arc::clone(&city), state, city], &schema)
+                .iter()
+                .map(|e| e.to_string())
+                .collect::<Vec<_>>(),
+            [want, want]
+        );
+    }
 }
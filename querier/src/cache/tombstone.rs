@@ -0,0 +1,248 @@
+//! Tombstone cache
+
+use backoff::{Backoff, BackoffConfig};
+use cache_system::{
+    backend::policy::{
+        lru::{LruPolicy, ResourcePool},
+        ttl::{ConstantValueTtlProvider, TtlPolicy},
+        PolicyBackend,
+    },
+    cache::{driver::CacheDriver, metrics::CacheWithMetrics, Cache},
+    loader::{metrics::MetricsLoader, FunctionLoader},
+    resource_consumption::FunctionEstimator,
+};
+use data_types::{DeletePredicate, TableId, Timestamp, Tombstone};
+use iox_catalog::interface::Catalog;
+use iox_time::TimeProvider;
+use observability_deps::tracing::error;
+use predicate::delete_predicate::parse_tombstone;
+use snafu::{ResultExt, Snafu};
+use std::{mem, sync::Arc, time::Duration};
+use trace::span::Span;
+
+use super::ram::RamSize;
+
+/// Duration to keep cached view.
+///
+/// Deletes of persisted data become visible to queries at most this long
+/// after they were accepted, so this is kept short.
+pub const TTL: Duration = Duration::from_secs(10);
+
+const CACHE_ID: &str = "tombstone";
+
+#[derive(Debug, Snafu)]
+#[allow(missing_copy_implementations, missing_docs)]
+pub enum Error {
+    #[snafu(display("CatalogError refreshing tombstone cache: {}", source))]
+    Catalog {
+        source: iox_catalog::interface::Error,
+    },
+}
+
+/// A delete predicate recorded in the catalog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedTombstone {
+    /// The catalog record of the delete.
+    pub tombstone: Tombstone,
+
+    /// The parsed delete predicate.
+    pub predicate: Arc<DeletePredicate>,
+}
+
+/// Holds the tombstones of a table.
+#[derive(Debug, Default)]
+pub struct CachedTombstones {
+    /// Tombstones, ordered by ID.
+    pub tombstones: Arc<[CachedTombstone]>,
+}
+
+impl CachedTombstones {
+    /// Return the delete predicates that apply to a parquet file with the
+    /// given `max_l0_created_at`, i.e. those recorded after all data in the
+    /// file was persisted.
+    pub fn predicates_for(&self, max_l0_created_at: Timestamp) -> Vec<Arc<DeletePredicate>> {
+        self.tombstones
+            .iter()
+            .filter(|t| t.tombstone.applies_to(max_l0_created_at))
+            .map(|t| Arc::clone(&t.predicate))
+            .collect()
+    }
+
+    /// Estimate the memory consumption of this object and its contents
+    fn size(&self) -> usize {
+        mem::size_of_val(self)
+            + mem::size_of_val(self.tombstones.as_ref())
+            + self
+                .tombstones
+                .iter()
+                .map(|t| t.tombstone.serialized_predicate.capacity() + t.predicate.size())
+                .sum::<usize>()
+    }
+}
+
+type CacheT = Box<
+    dyn Cache<
+        K = TableId,
+        V = Arc<CachedTombstones>,
+        GetExtra = ((), Option<Span>),
+        PeekExtra = ((), Option<Span>),
+    >,
+>;
+
+/// Cache for the tombstones (predicate deletes) of a table.
+#[derive(Debug)]
+pub struct TombstoneCache {
+    cache: CacheT,
+}
+
+impl TombstoneCache {
+    /// Create new empty cache.
+    pub fn new(
+        catalog: Arc<dyn Catalog>,
+        backoff_config: BackoffConfig,
+        time_provider: Arc<dyn TimeProvider>,
+        metric_registry: &metric::Registry,
+        ram_pool: Arc<ResourcePool<RamSize>>,
+        testing: bool,
+    ) -> Self {
+        let loader = FunctionLoader::new(move |table_id: TableId, _extra: ()| {
+            let catalog = Arc::clone(&catalog);
+            let backoff_config = backoff_config.clone();
+
+            async move {
+                let tombstones = Backoff::new(&backoff_config)
+                    .retry_all_errors("get tombstones", || async {
+                        catalog
+                            .repositories()
+                            .await
+                            .tombstones()
+                            .list_by_table_id(table_id)
+                            .await
+                            .context(CatalogSnafu)
+                    })
+                    .await
+                    .expect("retry forever");
+
+                let tombstones = tombstones
+                    .into_iter()
+                    .filter_map(|t| match parse_tombstone(&t) {
+                        Ok(predicate) => Some(CachedTombstone {
+                            tombstone: t,
+                            predicate: Arc::new(predicate),
+                        }),
+                        Err(e) => {
+                            error!(%e, tombstone_id=%t.id, %table_id, "invalid tombstone");
+                            None
+                        }
+                    })
+                    .collect();
+
+                Arc::new(CachedTombstones { tombstones })
+            }
+        });
+        let loader = Arc::new(MetricsLoader::new(
+            loader,
+            CACHE_ID,
+            Arc::clone(&time_provider),
+            metric_registry,
+            testing,
+        ));
+
+        let mut backend = PolicyBackend::hashmap_backed(Arc::clone(&time_provider));
+        backend.add_policy(LruPolicy::new(
+            Arc::clone(&ram_pool),
+            CACHE_ID,
+            Arc::new(FunctionEstimator::new(
+                |k: &TableId, v: &Arc<CachedTombstones>| {
+                    RamSize(mem::size_of_val(k) + mem::size_of_val(v) + v.size())
+                },
+            )),
+        ));
+        backend.add_policy(TtlPolicy::new(
+            Arc::new(ConstantValueTtlProvider::new(Some(TTL))),
+            CACHE_ID,
+            metric_registry,
+        ));
+
+        let cache = CacheDriver::new(loader, backend);
+        let cache = Box::new(CacheWithMetrics::new(
+            cache,
+            CACHE_ID,
+            time_provider,
+            metric_registry,
+        ));
+
+        Self { cache }
+    }
+
+    /// Get the tombstones of the given table.
+    pub async fn get(&self, table_id: TableId, span: Option<Span>) -> Arc<CachedTombstones> {
+        self.cache.get(table_id, ((), span)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use data_types::{DeleteExpr, Op, Scalar, TimestampRange};
+    use iox_tests::TestCatalog;
+
+    use crate::cache::{
+        ram::test_util::test_ram_pool, test_util::assert_catalog_access_metric_count,
+    };
+
+    const METRIC_NAME: &str = "tombstone_list_by_table_id";
+
+    #[tokio::test]
+    async fn test_tombstones() {
+        let catalog = TestCatalog::new();
+        let ns = catalog.create_namespace_1hr_retention("ns").await;
+        let table = ns.create_table("table1").await;
+
+        let cache = make_cache(&catalog);
+        assert!(cache.get(table.table.id, None).await.tombstones.is_empty());
+        assert_catalog_access_metric_count(&catalog.metric_registry, METRIC_NAME, 1);
+
+        let pred = DeletePredicate {
+            range: TimestampRange::new(1, 100),
+            exprs: vec![DeleteExpr::new(
+                "tag".to_string(),
+                Op::Eq,
+                Scalar::String("foo".to_string()),
+            )],
+        };
+        let tombstone = table.create_tombstone(&pred).await;
+
+        // still cached
+        assert!(cache.get(table.table.id, None).await.tombstones.is_empty());
+        assert_catalog_access_metric_count(&catalog.metric_registry, METRIC_NAME, 1);
+
+        // trigger TTL
+        catalog.mock_time_provider().inc(TTL);
+        let cached = cache.get(table.table.id, None).await;
+        assert_catalog_access_metric_count(&catalog.metric_registry, METRIC_NAME, 2);
+        assert_eq!(
+            cached.tombstones.as_ref(),
+            &[CachedTombstone {
+                tombstone: tombstone.clone(),
+                predicate: Arc::new(pred.clone()),
+            }]
+        );
+
+        // only applies to files persisted before the delete
+        let before = tombstone.created_at - 1;
+        assert_eq!(cached.predicates_for(before), vec![Arc::new(pred)]);
+        assert!(cached.predicates_for(tombstone.created_at).is_empty());
+    }
+
+    fn make_cache(catalog: &TestCatalog) -> TombstoneCache {
+        TombstoneCache::new(
+            catalog.catalog(),
+            BackoffConfig::default(),
+            catalog.time_provider(),
+            &catalog.metric_registry(),
+            test_ram_pool(),
+            true,
+        )
+    }
+}
@@ -2,9 +2,12 @@
 
 use crate::{
     cache::CatalogCache,
+    deleter::RouterDeleter,
     ingester::IngesterConnection,
     namespace::{QuerierNamespace, QuerierNamespaceArgs},
+    namespace_resolver::QuerierNamespaceResolver,
     parquet::ChunkAdapter,
+    points_writer::RouterPointsWriter,
     query_log::QueryLog,
     table::PruneMetrics,
     QueryLogEntry,
@@ -13,7 +16,7 @@ use async_trait::async_trait;
 use backoff::{Backoff, BackoffConfig};
 use data_types::Namespace;
 use iox_catalog::interface::SoftDeletedRows;
-use iox_query::exec::Executor;
+use iox_query::{exec::Executor, QueryNamespaceResolver};
 use service_common::QueryNamespaceProvider;
 use snafu::Snafu;
 use std::{
@@ -74,6 +77,16 @@ pub struct QuerierDatabase {
 
     /// DataFusion config.
     datafusion_config: Arc<HashMap<String, String>>,
+
+    /// Writer for the results of `SELECT ... INTO` queries.
+    points_writer: Option<Arc<RouterPointsWriter>>,
+
+    /// Deleter for the InfluxQL `DELETE`, `DROP MEASUREMENT` and
+    /// `DROP SERIES` statements.
+    deleter: Option<Arc<RouterDeleter>>,
+
+    /// Resolver for the other namespaces referenced by queries.
+    namespace_resolver: Arc<dyn QueryNamespaceResolver>,
 }
 
 #[async_trait]
@@ -137,6 +150,15 @@ impl QuerierDatabase {
 
         let prune_metrics = Arc::new(PruneMetrics::new(&metric_registry));
 
+        let namespace_resolver = Arc::new(QuerierNamespaceResolver::new(
+            Arc::clone(&chunk_adapter),
+            Arc::clone(&exec),
+            ingester_connection.clone(),
+            Arc::clone(&query_log),
+            Arc::clone(&prune_metrics),
+            Arc::clone(&datafusion_config),
+        ));
+
         Ok(Self {
             backoff_config,
             catalog_cache,
@@ -147,9 +169,29 @@ impl QuerierDatabase {
             query_execution_semaphore,
             prune_metrics,
             datafusion_config,
+            points_writer: None,
+            deleter: None,
+            namespace_resolver,
         })
     }
 
+    /// Write the results of `SELECT ... INTO` queries using `points_writer`.
+    pub fn with_points_writer(self, points_writer: Arc<RouterPointsWriter>) -> Self {
+        Self {
+            points_writer: Some(points_writer),
+            ..self
+        }
+    }
+
+    /// Apply the deletes of InfluxQL `DELETE`, `DROP MEASUREMENT` and
+    /// `DROP SERIES` statements using `deleter`.
+    pub fn with_deleter(self, deleter: Arc<RouterDeleter>) -> Self {
+        Self {
+            deleter: Some(deleter),
+            ..self
+        }
+    }
+
     /// Get namespace if it exists.
     ///
     /// This will await the internal namespace semaphore. Existence of namespaces is checked AFTER
@@ -182,6 +224,9 @@ impl QuerierDatabase {
             prune_metrics: Arc::clone(&self.prune_metrics),
             datafusion_config: Arc::clone(&self.datafusion_config),
             include_debug_info_tables,
+            points_writer: self.points_writer.clone(),
+            deleter: self.deleter.clone(),
+            namespace_resolver: Some(Arc::clone(&self.namespace_resolver)),
         })))
     }
 
@@ -0,0 +1,186 @@
+//! Applies the deletes of InfluxQL `DELETE`, `DROP MEASUREMENT` and
+//! `DROP SERIES` statements using the router.
+
+use async_trait::async_trait;
+use client_util::connection::{self, GrpcConnection};
+use data_types::{DeletePredicate, NamespaceId};
+use datafusion::error::DataFusionError;
+use influxdb_iox_client::delete::generated_types::{
+    delete_service_client::DeleteServiceClient, DeletePayload, DeleteRequest,
+};
+use iox_query::QueryDeleter;
+use observability_deps::tracing::debug;
+use snafu::{ResultExt, Snafu};
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+use tonic::metadata::{errors::InvalidMetadataValueBytes, AsciiMetadataValue};
+
+#[derive(Debug, Snafu)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[snafu(display("Failed to connect to router '{}': {}", router_address, source))]
+    Connecting {
+        router_address: String,
+        source: connection::Error,
+    },
+
+    #[snafu(display("Invalid authorization token: {}", source))]
+    InvalidAuthzToken { source: InvalidMetadataValueBytes },
+
+    #[snafu(display("Failed to delete from table '{}': {}", table_name, source))]
+    Deleting {
+        table_name: String,
+        source: influxdb_iox_client::error::Error,
+    },
+}
+
+/// Applies predicate deletes using the delete API of the router, which
+/// records them in the catalog.
+///
+/// The router authorizes the delete using the token of the caller that ran
+/// the query.
+///
+/// The connection to the router is established when the first delete is
+/// performed, so the router does not need to be available when the querier
+/// starts.
+#[derive(Debug)]
+pub struct RouterDeleter {
+    /// gRPC address of the router.
+    router_address: String,
+
+    /// Delete client, created on first use.
+    client: OnceCell<DeleteServiceClient<GrpcConnection>>,
+}
+
+impl RouterDeleter {
+    /// Create a new deleter for the router at `router_address`.
+    pub fn new(router_address: impl Into<String>) -> Self {
+        Self {
+            router_address: router_address.into(),
+            client: OnceCell::new(),
+        }
+    }
+
+    /// Return a [`QueryDeleter`] that deletes from the namespace with the
+    /// specified `id`.
+    pub(crate) fn for_namespace(self: &Arc<Self>, id: NamespaceId) -> Arc<dyn QueryDeleter> {
+        Arc::new(NamespaceDeleter {
+            deleter: Arc::clone(self),
+            namespace_id: id,
+        })
+    }
+
+    async fn client(&self) -> Result<DeleteServiceClient<GrpcConnection>, Error> {
+        let router_address = self.router_address.as_str();
+        let client = self
+            .client
+            .get_or_try_init(|| async {
+                debug!(%router_address, "Connecting to router");
+
+                let connection = connection::Builder::new()
+                    .build(router_address)
+                    .await
+                    .context(ConnectingSnafu { router_address })?;
+                Ok::<_, Error>(DeleteServiceClient::new(connection.into_grpc_connection()))
+            })
+            .await?;
+        Ok(client.clone())
+    }
+
+    async fn delete(
+        &self,
+        namespace_id: NamespaceId,
+        table_name: &str,
+        predicate: &DeletePredicate,
+        authz_token: Option<&[u8]>,
+    ) -> Result<(), Error> {
+        let request = delete_request(namespace_id, table_name, predicate, authz_token)?;
+        self.client()
+            .await?
+            .delete(request)
+            .await
+            .map_err(influxdb_iox_client::error::Error::from)
+            .context(DeletingSnafu { table_name })?;
+        Ok(())
+    }
+}
+
+/// Build the request deleting the rows matching `predicate` from the table
+/// `table_name`, passing `authz_token` to the router in the `authorization`
+/// header.
+fn delete_request(
+    namespace_id: NamespaceId,
+    table_name: &str,
+    predicate: &DeletePredicate,
+    authz_token: Option<&[u8]>,
+) -> Result<tonic::Request<DeleteRequest>, Error> {
+    let mut request = tonic::Request::new(DeleteRequest {
+        payload: Some(DeletePayload {
+            database_id: namespace_id.get(),
+            table_name: table_name.to_owned(),
+            predicate: Some(predicate.into()),
+            table_id: 0,
+        }),
+    });
+    if let Some(token) = authz_token {
+        let value = AsciiMetadataValue::try_from([b"Token ", token].concat().as_slice())
+            .context(InvalidAuthzTokenSnafu)?;
+        request.metadata_mut().insert("authorization", value);
+    }
+    Ok(request)
+}
+
+/// A [`QueryDeleter`] bound to the namespace a query runs against.
+#[derive(Debug)]
+struct NamespaceDeleter {
+    deleter: Arc<RouterDeleter>,
+    namespace_id: NamespaceId,
+}
+
+#[async_trait]
+impl QueryDeleter for NamespaceDeleter {
+    async fn delete(
+        &self,
+        table_name: &str,
+        predicate: &DeletePredicate,
+        authz_token: Option<&[u8]>,
+    ) -> Result<(), DataFusionError> {
+        debug!(
+            namespace_id=%self.namespace_id,
+            %table_name,
+            predicate=%predicate.expr_sql_string(),
+            "Deleting from table"
+        );
+        self.deleter
+            .delete(self.namespace_id, table_name, predicate, authz_token)
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use data_types::TimestampRange;
+
+    #[test]
+    fn test_delete_request_authz_token() {
+        let predicate = DeletePredicate {
+            range: TimestampRange::new(1, 2),
+            exprs: vec![],
+        };
+
+        let request = delete_request(NamespaceId::new(42), "cpu", &predicate, None).unwrap();
+        assert!(request.metadata().get("authorization").is_none());
+        let payload = request.into_inner().payload.unwrap();
+        assert_eq!(payload.database_id, 42);
+        assert_eq!(payload.table_name, "cpu");
+
+        let request =
+            delete_request(NamespaceId::new(42), "cpu", &predicate, Some(b"secret")).unwrap();
+        assert_eq!(
+            request.metadata().get("authorization").unwrap(),
+            "Token secret"
+        );
+    }
+}
@@ -18,9 +18,12 @@ use workspace_hack as _;
 
 mod cache;
 mod database;
+mod deleter;
 mod ingester;
 mod namespace;
+mod namespace_resolver;
 mod parquet;
+mod points_writer;
 mod query_log;
 mod server;
 mod system_tables;
@@ -33,7 +36,9 @@ const CONCURRENT_CHUNK_CREATION_JOBS: usize = 100;
 
 pub use cache::CatalogCache as QuerierCatalogCache;
 pub use database::{Error as QuerierDatabaseError, QuerierDatabase};
+pub use deleter::RouterDeleter;
 pub use ingester::{create_ingester_connection_for_testing, create_ingester_connections};
 pub use namespace::QuerierNamespace;
+pub use points_writer::RouterPointsWriter;
 pub use query_log::QueryLogEntry;
 pub use server::QuerierServer;
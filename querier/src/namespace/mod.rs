@@ -2,13 +2,15 @@
 
 use crate::{
     cache::{namespace::CachedNamespace, CatalogCache},
+    deleter::RouterDeleter,
     ingester::IngesterConnection,
     parquet::ChunkAdapter,
+    points_writer::RouterPointsWriter,
     query_log::QueryLog,
     table::{PruneMetrics, QuerierTable, QuerierTableArgs},
 };
 use data_types::NamespaceId;
-use iox_query::exec::Executor;
+use iox_query::{exec::Executor, QueryDeleter, QueryNamespaceResolver, QueryPointsWriter};
 use std::{collections::HashMap, sync::Arc, time::Duration};
 
 mod query_access;
@@ -28,6 +30,9 @@ pub struct QuerierNamespaceArgs {
     pub prune_metrics: Arc<PruneMetrics>,
     pub datafusion_config: Arc<HashMap<String, String>>,
     pub include_debug_info_tables: bool,
+    pub points_writer: Option<Arc<RouterPointsWriter>>,
+    pub deleter: Option<Arc<RouterDeleter>>,
+    pub namespace_resolver: Option<Arc<dyn QueryNamespaceResolver>>,
 }
 
 /// Maps a catalog namespace to all the in-memory resources and sync-state that the querier needs.
@@ -56,6 +61,9 @@ pub struct QuerierNamespace {
     /// Catalog cache.
     catalog_cache: Arc<CatalogCache>,
 
+    /// Cached catalog information of this namespace.
+    ns: Arc<CachedNamespace>,
+
     /// Query log.
     query_log: Arc<QueryLog>,
 
@@ -67,6 +75,16 @@ pub struct QuerierNamespace {
 
     /// Retention period.
     retention_period: Option<Duration>,
+
+    /// Writer for the results of `SELECT ... INTO` queries.
+    points_writer: Option<Arc<dyn QueryPointsWriter>>,
+
+    /// Deleter for the InfluxQL `DELETE`, `DROP MEASUREMENT` and
+    /// `DROP SERIES` statements.
+    deleter: Option<Arc<dyn QueryDeleter>>,
+
+    /// Resolver for the other namespaces referenced by queries.
+    namespace_resolver: Option<Arc<dyn QueryNamespaceResolver>>,
 }
 
 impl QuerierNamespace {
@@ -82,6 +100,9 @@ impl QuerierNamespace {
             prune_metrics,
             datafusion_config,
             include_debug_info_tables,
+            points_writer,
+            deleter,
+            namespace_resolver,
         } = args;
 
         let tables: HashMap<_, _> = ns
@@ -105,6 +126,8 @@ impl QuerierNamespace {
             .collect();
 
         let id = ns.id;
+        let points_writer = points_writer.map(|w| w.for_namespace(Arc::clone(&name)));
+        let deleter = deleter.map(|d| d.for_namespace(id));
 
         Self {
             id,
@@ -116,6 +139,10 @@ impl QuerierNamespace {
             datafusion_config,
             include_debug_info_tables,
             retention_period: ns.retention_period,
+            ns,
+            points_writer,
+            deleter,
+            namespace_resolver,
         }
     }
 
@@ -143,6 +170,9 @@ impl QuerierNamespace {
             prune_metrics,
             datafusion_config: Default::default(),
             include_debug_info_tables: true,
+            points_writer: None,
+            deleter: None,
+            namespace_resolver: None,
         })
     }
 
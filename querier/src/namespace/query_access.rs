@@ -1,8 +1,9 @@
 //! This module contains implementations of [`iox_query`] interfaces for [QuerierNamespace].
 
 use crate::{
+    cache::{namespace::CachedNamespace, CatalogCache},
     namespace::QuerierNamespace,
-    query_log::QueryLog,
+    query_log::{QueryLog, QueryLogCanceller},
     system_tables::{SystemSchemaProvider, SYSTEM_SCHEMA},
     table::QuerierTable,
 };
@@ -66,12 +67,24 @@ impl QueryNamespace for QuerierNamespace {
         query_type: &'static str,
         query_text: QueryText,
     ) -> QueryCompletedToken {
-        // When the query token is dropped the query entry's completion time
-        // will be set.
-        let query_log = Arc::clone(&self.query_log);
-        let trace_id = span_ctx.map(|ctx| ctx.trace_id);
-        let entry = query_log.push(self.id, query_type, query_text, trace_id);
-        QueryCompletedToken::new(move |success| query_log.set_completed(entry, success))
+        let query_id = self.query_log.reserve_id();
+        self.record_query_with_id(span_ctx, query_type, query_text, query_id)
+    }
+
+    fn record_query_with_handle(
+        &self,
+        span_ctx: Option<&SpanContext>,
+        query_type: &'static str,
+        query_text: QueryText,
+        query_handle: u64,
+    ) -> QueryCompletedToken {
+        // Handles are created by this querier, so unknown (or evicted)
+        // handles are recorded with a new ID rather than trusted.
+        let query_id = self
+            .query_log
+            .handle_id(self.id, query_handle)
+            .unwrap_or_else(|| self.query_log.reserve_id());
+        self.record_query_with_id(span_ctx, query_type, query_text, query_id)
     }
 
     fn new_query_context(&self, span_ctx: Option<SpanContext>) -> IOxSessionContext {
@@ -79,16 +92,59 @@ impl QueryNamespace for QuerierNamespace {
             .exec
             .new_execution_config(ExecutorType::Query)
             .with_default_catalog(Arc::new(QuerierCatalogProvider::from_namespace(self)) as _)
-            .with_span_context(span_ctx);
+            .with_span_context(span_ctx)
+            .with_query_canceller(Arc::new(QueryLogCanceller::new(
+                self.id,
+                Arc::clone(&self.query_log),
+            )));
 
         for (k, v) in self.datafusion_config.as_ref() {
             cfg = cfg.with_config_option(k, v);
         }
 
+        if let Some(points_writer) = &self.points_writer {
+            cfg = cfg.with_points_writer(Arc::clone(points_writer));
+        }
+
+        if let Some(deleter) = &self.deleter {
+            cfg = cfg.with_deleter(Arc::clone(deleter));
+        }
+
+        if let Some(namespace_resolver) = &self.namespace_resolver {
+            cfg = cfg.with_namespace_resolver(Arc::clone(namespace_resolver));
+        }
+
         cfg.build()
     }
 }
 
+impl QuerierNamespace {
+    /// Record the query in the query log with the ID `query_id`.
+    fn record_query_with_id(
+        &self,
+        span_ctx: Option<&SpanContext>,
+        query_type: &'static str,
+        query_text: QueryText,
+        query_id: u64,
+    ) -> QueryCompletedToken {
+        // When the query token is dropped the query entry's completion time
+        // will be set.
+        let query_log = Arc::clone(&self.query_log);
+        let trace_id = span_ctx.map(|ctx| ctx.trace_id);
+        let entry = query_log.push_with_id(query_id, self.id, query_type, query_text, trace_id);
+        let cancellation = entry.cancellation_token();
+        QueryCompletedToken::new(move |success| query_log.set_completed(entry, success))
+            .with_cancellation(cancellation)
+    }
+
+    /// Return a provider for the user-provided tables of this namespace.
+    pub(crate) fn user_schema_provider(&self) -> Arc<dyn SchemaProvider> {
+        Arc::new(UserSchemaProvider {
+            tables: Arc::clone(&self.tables),
+        })
+    }
+}
+
 pub struct QuerierCatalogProvider {
     /// Namespace ID.
     namespace_id: NamespaceId,
@@ -96,6 +152,12 @@ pub struct QuerierCatalogProvider {
     /// A snapshot of all tables.
     tables: Arc<HashMap<Arc<str>, Arc<QuerierTable>>>,
 
+    /// Catalog cache.
+    catalog_cache: Arc<CatalogCache>,
+
+    /// Cached catalog information of the namespace.
+    ns: Arc<CachedNamespace>,
+
     /// Query log.
     query_log: Arc<QueryLog>,
 
@@ -108,6 +170,8 @@ impl QuerierCatalogProvider {
         Self {
             namespace_id: namespace.id,
             tables: Arc::clone(&namespace.tables),
+            catalog_cache: Arc::clone(&namespace.catalog_cache),
+            ns: Arc::clone(&namespace.ns),
             query_log: Arc::clone(&namespace.query_log),
             include_debug_info_tables: namespace.include_debug_info_tables,
         }
@@ -129,6 +193,8 @@ impl CatalogProvider for QuerierCatalogProvider {
                 tables: Arc::clone(&self.tables),
             })),
             SYSTEM_SCHEMA => Some(Arc::new(SystemSchemaProvider::new(
+                Arc::clone(&self.catalog_cache),
+                Arc::clone(&self.ns),
                 Arc::clone(&self.query_log),
                 self.namespace_id,
                 self.include_debug_info_tables,
@@ -171,7 +237,7 @@ mod tests {
     use crate::namespace::test_util::{clear_parquet_cache, querier_namespace};
     use arrow::record_batch::RecordBatch;
     use arrow_util::test_util::{batches_to_sorted_lines, Normalizer};
-    use data_types::ColumnType;
+    use data_types::{ColumnType, CompactionLevel};
     use datafusion::common::DataFusionError;
     use iox_query::frontend::sql::SqlQueryPlanner;
     use iox_tests::{TestCatalog, TestParquetFileBuilder};
@@ -529,6 +595,160 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_record_query_with_handle() {
+        let catalog = TestCatalog::new();
+        let ns = catalog.create_namespace_with_retention("ns", None).await;
+        let querier_namespace = querier_namespace(&ns).await;
+
+        // queries run with a handle created by the namespace are recorded
+        // with the ID it identifies
+        let handle = querier_namespace
+            .new_query_context(None)
+            .query_canceller()
+            .unwrap()
+            .reserve_query_handle();
+        let query_id = querier_namespace
+            .query_log
+            .handle_id(querier_namespace.id, handle)
+            .unwrap();
+        let _token1 = querier_namespace.record_query_with_handle(
+            None,
+            "flightsql",
+            Box::new("SELECT 1"),
+            handle,
+        );
+
+        // handles chosen by clients are not trusted
+        let forged = handle.wrapping_add(1);
+        let _token2 = querier_namespace.record_query_with_handle(
+            None,
+            "flightsql",
+            Box::new("SELECT 2"),
+            forged,
+        );
+
+        let ids = querier_namespace
+            .query_log
+            .entries()
+            .iter()
+            .map(|entry| entry.id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids.len(), 2);
+        assert_eq!(ids[0], query_id);
+        assert_ne!(ids[1], query_id);
+        assert_ne!(ids[1], forged);
+    }
+
+    #[tokio::test]
+    async fn test_catalog_system_tables() {
+        test_helpers::maybe_start_logging();
+
+        let catalog = TestCatalog::new();
+
+        let ns = catalog.create_namespace_with_retention("ns", None).await;
+
+        let table_cpu = ns.create_table("cpu").await;
+        let table_mem = ns.create_table("mem").await;
+
+        table_cpu.create_column("host", ColumnType::Tag).await;
+        table_cpu.create_column("time", ColumnType::Time).await;
+        table_cpu.create_column("load", ColumnType::F64).await;
+        table_mem.create_column("host", ColumnType::Tag).await;
+        table_mem.create_column("time", ColumnType::Time).await;
+        table_mem.create_column("perc", ColumnType::I64).await;
+
+        let partition_cpu_a = table_cpu.create_partition("a").await;
+        let partition_cpu_b = table_cpu.create_partition("b").await;
+
+        let builder = TestParquetFileBuilder::default()
+            .with_line_protocol("cpu,host=a load=1 11")
+            .with_min_time(11)
+            .with_max_time(11);
+        partition_cpu_a.create_parquet_file(builder).await;
+
+        let builder = TestParquetFileBuilder::default()
+            .with_line_protocol("cpu,host=b load=2 22\ncpu,host=c load=3 33")
+            .with_min_time(22)
+            .with_max_time(33);
+        partition_cpu_a.create_parquet_file(builder).await;
+
+        let builder = TestParquetFileBuilder::default()
+            .with_line_protocol("cpu,host=b load=4 44")
+            .with_min_time(44)
+            .with_max_time(44)
+            .with_compaction_level(CompactionLevel::Final);
+        partition_cpu_b.create_parquet_file(builder).await;
+
+        let querier_namespace = Arc::new(querier_namespace(&ns).await);
+
+        insta::assert_yaml_snapshot!(
+            format_query(&querier_namespace, "SELECT * FROM system.tables").await,
+            @r###"
+        ---
+        - +----------+------------+
+        - "| table_id | table_name |"
+        - +----------+------------+
+        - "| 1        | cpu        |"
+        - "| 2        | mem        |"
+        - +----------+------------+
+        "###
+        );
+
+        insta::assert_yaml_snapshot!(
+            format_query(
+                &querier_namespace,
+                "SELECT table_name, column_name, column_type, data_type FROM system.columns"
+            ).await,
+            @r###"
+        ---
+        - +------------+-------------+-------------+-----------------------------+
+        - "| table_name | column_name | column_type | data_type                   |"
+        - +------------+-------------+-------------+-----------------------------+
+        - "| cpu        | host        | tag         | Dictionary(Int32, Utf8)     |"
+        - "| cpu        | load        | f64         | Float64                     |"
+        - "| cpu        | time        | time        | Timestamp(Nanosecond, None) |"
+        - "| mem        | host        | tag         | Dictionary(Int32, Utf8)     |"
+        - "| mem        | perc        | i64         | Int64                       |"
+        - "| mem        | time        | time        | Timestamp(Nanosecond, None) |"
+        - +------------+-------------+-------------+-----------------------------+
+        "###
+        );
+
+        insta::assert_yaml_snapshot!(
+            format_query(
+                &querier_namespace,
+                "SELECT table_name, sort_key, file_count FROM system.partitions"
+            ).await,
+            @r###"
+        ---
+        - +------------+-----------+------------+
+        - "| table_name | sort_key  | file_count |"
+        - +------------+-----------+------------+
+        - "| cpu        | host,time | 1          |"
+        - "| cpu        | host,time | 2          |"
+        - +------------+-----------+------------+
+        "###
+        );
+
+        insta::assert_yaml_snapshot!(
+            format_query(
+                &querier_namespace,
+                "SELECT table_name, compaction_level, row_count, min_time, max_time FROM system.parquet_files"
+            ).await,
+            @r###"
+        ---
+        - +------------+------------------+-----------+--------------------------------+--------------------------------+
+        - "| table_name | compaction_level | row_count | min_time                       | max_time                       |"
+        - +------------+------------------+-----------+--------------------------------+--------------------------------+
+        - "| cpu        | 1                | 1         | 1970-01-01T00:00:00.000000011Z | 1970-01-01T00:00:00.000000011Z |"
+        - "| cpu        | 1                | 2         | 1970-01-01T00:00:00.000000022Z | 1970-01-01T00:00:00.000000033Z |"
+        - "| cpu        | 2                | 1         | 1970-01-01T00:00:00.000000044Z | 1970-01-01T00:00:00.000000044Z |"
+        - +------------+------------------+-----------+--------------------------------+--------------------------------+
+        "###
+        );
+    }
+
     async fn format_query(querier_namespace: &Arc<QuerierNamespace>, sql: &str) -> Vec<String> {
         format_query_with_span_ctx(querier_namespace, sql, None).await
     }
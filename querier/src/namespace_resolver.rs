@@ -0,0 +1,104 @@
+//! Resolves the other namespaces referenced by a query, such as the database
+//! of an InfluxQL `SELECT ... FROM db.rp.measurement` statement.
+
+use crate::{
+    ingester::IngesterConnection,
+    namespace::{QuerierNamespace, QuerierNamespaceArgs},
+    parquet::ChunkAdapter,
+    query_log::QueryLog,
+    table::PruneMetrics,
+};
+use async_trait::async_trait;
+use datafusion::{catalog::schema::SchemaProvider, error::DataFusionError};
+use iox_query::{exec::Executor, QueryNamespaceResolver};
+use std::{collections::HashMap, sync::Arc};
+use trace::span::{Span, SpanRecorder};
+
+/// Resolves namespaces using the catalog cache, providing the tables of a
+/// namespace the same way as for a query against that namespace.
+///
+/// The namespaces are resolved for reading only: statements that write or
+/// delete data always apply to the namespace of the query.
+#[derive(Debug)]
+pub(crate) struct QuerierNamespaceResolver {
+    /// Adapter to create chunks.
+    chunk_adapter: Arc<ChunkAdapter>,
+
+    /// Executor for queries.
+    exec: Arc<Executor>,
+
+    /// Connection to ingester(s)
+    ingester_connection: Option<Arc<dyn IngesterConnection>>,
+
+    /// Query log.
+    query_log: Arc<QueryLog>,
+
+    /// Chunk prune metrics.
+    prune_metrics: Arc<PruneMetrics>,
+
+    /// DataFusion config.
+    datafusion_config: Arc<HashMap<String, String>>,
+}
+
+impl QuerierNamespaceResolver {
+    /// Create a new resolver.
+    pub(crate) fn new(
+        chunk_adapter: Arc<ChunkAdapter>,
+        exec: Arc<Executor>,
+        ingester_connection: Option<Arc<dyn IngesterConnection>>,
+        query_log: Arc<QueryLog>,
+        prune_metrics: Arc<PruneMetrics>,
+        datafusion_config: Arc<HashMap<String, String>>,
+    ) -> Self {
+        Self {
+            chunk_adapter,
+            exec,
+            ingester_connection,
+            query_log,
+            prune_metrics,
+            datafusion_config,
+        }
+    }
+}
+
+#[async_trait]
+impl QueryNamespaceResolver for QuerierNamespaceResolver {
+    async fn schema_provider(
+        &self,
+        namespace_name: &str,
+        span: Option<Span>,
+    ) -> Result<Option<Arc<dyn SchemaProvider>>, DataFusionError> {
+        let span_recorder = SpanRecorder::new(span);
+        let name: Arc<str> = Arc::from(namespace_name);
+        let Some(ns) = self
+            .chunk_adapter
+            .catalog_cache()
+            .namespace()
+            .get(
+                Arc::clone(&name),
+                &[],
+                span_recorder.child_span("cache GET namespace schema"),
+            )
+            .await
+        else {
+            return Ok(None);
+        };
+
+        let namespace = QuerierNamespace::new(QuerierNamespaceArgs {
+            chunk_adapter: Arc::clone(&self.chunk_adapter),
+            ns,
+            name,
+            exec: Arc::clone(&self.exec),
+            ingester_connection: self.ingester_connection.clone(),
+            query_log: Arc::clone(&self.query_log),
+            prune_metrics: Arc::clone(&self.prune_metrics),
+            datafusion_config: Arc::clone(&self.datafusion_config),
+            include_debug_info_tables: false,
+            points_writer: None,
+            deleter: None,
+            namespace_resolver: None,
+        });
+
+        Ok(Some(namespace.user_schema_provider()))
+    }
+}
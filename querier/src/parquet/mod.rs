@@ -1,6 +1,6 @@
 //! Querier Chunks
 
-use data_types::{ChunkId, ChunkOrder, TransitionPartitionId};
+use data_types::{ChunkId, ChunkOrder, DeletePredicate, Timestamp, TransitionPartitionId};
 use datafusion::physical_plan::Statistics;
 use iox_query::chunk_statistics::{create_chunk_statistics, ColumnRanges};
 use parquet_file::chunk::ParquetChunk;
@@ -55,6 +55,9 @@ pub struct QuerierParquetChunk {
 
     /// Stats
     stats: Arc<Statistics>,
+
+    /// Delete predicates recorded after this file was persisted.
+    delete_predicates: Vec<Arc<DeletePredicate>>,
 }
 
 impl QuerierParquetChunk {
@@ -75,9 +78,23 @@ impl QuerierParquetChunk {
             meta,
             parquet_chunk,
             stats,
+            delete_predicates: vec![],
         }
     }
 
+    /// Set the delete predicates that apply to this chunk.
+    pub fn with_delete_predicates(self, delete_predicates: Vec<Arc<DeletePredicate>>) -> Self {
+        Self {
+            delete_predicates,
+            ..self
+        }
+    }
+
+    /// The max creation time of all L0 files that were compacted into this file.
+    pub fn max_l0_created_at(&self) -> Timestamp {
+        self.parquet_chunk.parquet_file().max_l0_created_at
+    }
+
     /// Get metadata attached to the given chunk.
     pub fn meta(&self) -> &QuerierParquetChunkMeta {
         self.meta.as_ref()
@@ -0,0 +1,256 @@
+//! Writes the results of InfluxQL `SELECT ... INTO` queries to the router.
+
+use async_trait::async_trait;
+use client_util::connection::{self, HttpConnection};
+use datafusion::error::DataFusionError;
+use iox_query::QueryPointsWriter;
+use iox_query_influxql::plan::namespace_name;
+use observability_deps::tracing::debug;
+use reqwest::{header::AUTHORIZATION, StatusCode};
+use snafu::{ResultExt, Snafu};
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+
+#[derive(Debug, Snafu)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[snafu(display("Failed to connect to router '{}': {}", router_address, source))]
+    Connecting {
+        router_address: String,
+        source: connection::Error,
+    },
+
+    #[snafu(display(
+        "Cannot write to namespace '{}' of a multi-tenant router: \
+         the name is not of the form <org>_<bucket>",
+        namespace
+    ))]
+    NoOrgBucket { namespace: String },
+
+    #[snafu(display("Failed to write to database '{}': {}", database, source))]
+    Writing {
+        database: String,
+        source: reqwest::Error,
+    },
+
+    #[snafu(display(
+        "Failed to write to database '{}' (status {}): {}",
+        database,
+        status,
+        body
+    ))]
+    WriteRejected {
+        database: String,
+        status: StatusCode,
+        body: String,
+    },
+}
+
+/// Writes line protocol to the router.
+///
+/// Single tenant routers are written to using the InfluxDB 1.x compatible
+/// `/write` API, and resolve the namespace of the database and retention
+/// policy written to themselves. Multi-tenant routers only serve the
+/// InfluxDB 2.x `/api/v2/write` API, so the namespace is split into the org
+/// and bucket it was created from.
+///
+/// The router authorizes the write using the token of the caller that ran
+/// the query.
+///
+/// The connection to the router is established when the first write is
+/// performed, so the router does not need to be available when the querier
+/// starts.
+#[derive(Debug)]
+pub struct RouterPointsWriter {
+    /// HTTP address of the router.
+    router_address: String,
+
+    /// Whether the router runs in single tenancy mode.
+    single_tenancy: bool,
+
+    /// HTTP connection, created on first use.
+    connection: OnceCell<HttpConnection>,
+}
+
+impl RouterPointsWriter {
+    /// Create a new writer for the router at `router_address`, which runs in
+    /// single tenancy mode if `single_tenancy` is set.
+    pub fn new(router_address: impl Into<String>, single_tenancy: bool) -> Self {
+        Self {
+            router_address: router_address.into(),
+            single_tenancy,
+            connection: OnceCell::new(),
+        }
+    }
+
+    /// Return a [`QueryPointsWriter`] that writes to the namespace `name`
+    /// when a query does not specify a target database.
+    pub(crate) fn for_namespace(self: &Arc<Self>, name: Arc<str>) -> Arc<dyn QueryPointsWriter> {
+        Arc::new(NamespacePointsWriter {
+            writer: Arc::clone(self),
+            namespace: name,
+        })
+    }
+
+    async fn connection(&self) -> Result<HttpConnection, Error> {
+        let router_address = self.router_address.as_str();
+        let connection = self
+            .connection
+            .get_or_try_init(|| async {
+                debug!(%router_address, "Connecting to router");
+
+                let connection = connection::Builder::new()
+                    .build(router_address)
+                    .await
+                    .context(ConnectingSnafu { router_address })?;
+                Ok::<_, Error>(connection.into_http_connection())
+            })
+            .await?;
+        Ok(connection.clone())
+    }
+
+    async fn write_lp(
+        &self,
+        database: &str,
+        retention_policy: Option<&str>,
+        lp: String,
+        authz_token: Option<&[u8]>,
+    ) -> Result<(), Error> {
+        let (path, params) = self.write_path_and_params(database, retention_policy)?;
+        let connection = self.connection().await?;
+
+        let mut request = connection
+            .client()
+            .post(format!("{}{}", connection.uri(), path))
+            .query(&params)
+            .body(lp);
+        if let Some(token) = authz_token {
+            request = request.header(AUTHORIZATION, [b"Token ", token].concat());
+        }
+
+        let response = request.send().await.context(WritingSnafu { database })?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return WriteRejectedSnafu {
+                database,
+                status,
+                body,
+            }
+            .fail();
+        }
+        Ok(())
+    }
+
+    /// Return the path of the write API of the router, relative to its base
+    /// URI, and the query parameters identifying the namespace written to.
+    fn write_path_and_params(
+        &self,
+        database: &str,
+        retention_policy: Option<&str>,
+    ) -> Result<(&'static str, Vec<(&'static str, String)>), Error> {
+        if self.single_tenancy {
+            let mut params = vec![("db", database.to_owned())];
+            if let Some(rp) = retention_policy {
+                params.push(("rp", rp.to_owned()));
+            }
+            return Ok(("write", params));
+        }
+
+        // Multi-tenant namespaces are named "<org>_<bucket>" with a non-empty
+        // org and bucket. Any such split maps back to the same namespace, so
+        // use the first one.
+        let namespace = namespace_name(database, retention_policy);
+        let split = namespace
+            .char_indices()
+            .skip(1)
+            .find(|(_, c)| *c == '_')
+            .map(|(i, _)| (&namespace[..i], &namespace[i + 1..]));
+        match split {
+            Some((org, bucket)) if !bucket.is_empty() => Ok((
+                "api/v2/write",
+                vec![("org", org.to_owned()), ("bucket", bucket.to_owned())],
+            )),
+            _ => NoOrgBucketSnafu { namespace }.fail(),
+        }
+    }
+}
+
+/// A [`QueryPointsWriter`] bound to the namespace a query runs against.
+#[derive(Debug)]
+struct NamespacePointsWriter {
+    writer: Arc<RouterPointsWriter>,
+    namespace: Arc<str>,
+}
+
+#[async_trait]
+impl QueryPointsWriter for NamespacePointsWriter {
+    async fn write_lp(
+        &self,
+        database: Option<&str>,
+        retention_policy: Option<&str>,
+        lp: String,
+        authz_token: Option<&[u8]>,
+    ) -> Result<(), DataFusionError> {
+        let database = database.unwrap_or(&self.namespace);
+        self.writer
+            .write_lp(database, retention_policy, lp, authz_token)
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use data_types::NamespaceName;
+
+    #[test]
+    fn test_single_tenant_write_params() {
+        let writer = RouterPointsWriter::new("http://127.0.0.1:8080", true);
+
+        let (path, params) = writer.write_path_and_params("db", Some("rp")).unwrap();
+        assert_eq!(path, "write");
+        assert_eq!(
+            params,
+            vec![("db", "db".to_owned()), ("rp", "rp".to_owned())]
+        );
+
+        let (path, params) = writer.write_path_and_params("db", None).unwrap();
+        assert_eq!(path, "write");
+        assert_eq!(params, vec![("db", "db".to_owned())]);
+    }
+
+    #[test]
+    fn test_multi_tenant_write_params() {
+        let writer = RouterPointsWriter::new("http://127.0.0.1:8080", false);
+
+        for (database, retention_policy) in [
+            ("myorg_mybucket", None),
+            ("my_org_my_bucket", Some("autogen")),
+            ("_myorg_mybucket", None),
+            ("myorg_mybucket", Some("rp")),
+        ] {
+            let (path, params) = writer
+                .write_path_and_params(database, retention_policy)
+                .unwrap();
+            assert_eq!(path, "api/v2/write");
+            assert_matches!(params.as_slice(), [("org", org), ("bucket", bucket)] => {
+                // The router maps the org and bucket back to the namespace
+                // the query targets.
+                assert_eq!(
+                    NamespaceName::from_org_and_bucket(org, bucket).unwrap().as_str(),
+                    namespace_name(database, retention_policy),
+                );
+            });
+        }
+
+        for database in ["mybucket", "_mybucket", "myorg_", "_"] {
+            let err = writer.write_path_and_params(database, None).unwrap_err();
+            assert_matches!(err, Error::NoOrgBucket { namespace } => {
+                assert_eq!(namespace, database);
+            });
+        }
+    }
+}
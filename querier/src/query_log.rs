@@ -1,22 +1,34 @@
 //! Ring buffer of queries that have been run with some brief information
 
 use data_types::NamespaceId;
-use iox_query::QueryText;
+use iox_query::{QueryCanceller, QueryText};
 use iox_time::{Time, TimeProvider};
 use observability_deps::tracing::warn;
 use parking_lot::Mutex;
 use std::{
-    collections::VecDeque,
+    collections::{HashMap, VecDeque},
     sync::{atomic, Arc},
     time::Duration,
 };
+use tokio_util::sync::CancellationToken;
 use trace::ctx::TraceId;
 
 /// The query duration used for queries still running.
 const UNCOMPLETED_DURATION: i64 = -1;
 
+/// The maximum number of handles kept by a [`QueryLog`], see
+/// [`QueryLog::reserve_handle`].
+const MAX_QUERY_HANDLES: usize = 10_000;
+
 /// Information about a single query that was executed
 pub struct QueryLogEntry {
+    /// Query ID, unique within the [`QueryLog`].
+    ///
+    /// Queries recorded with an ID reserved by [`QueryLog::reserve_handle`],
+    /// such as the repeated executions of a Flight SQL ticket, share that
+    /// ID.
+    pub id: u64,
+
     /// Namespace ID.
     pub namespace_id: NamespaceId,
 
@@ -38,16 +50,21 @@ pub struct QueryLogEntry {
 
     /// If the query completed successfully
     pub success: atomic::AtomicBool,
+
+    /// Cancelled when the query is cancelled.
+    cancellation: CancellationToken,
 }
 
 impl std::fmt::Debug for QueryLogEntry {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("QueryLogEntry")
+            .field("id", &self.id)
             .field("query_type", &self.query_type)
             .field("query_text", &self.query_text.to_string())
             .field("issue_time", &self.issue_time)
             .field("query_completed_duration", &self.query_completed_duration)
             .field("success", &self.success)
+            .field("cancelled", &self.cancelled())
             .finish()
     }
 }
@@ -55,6 +72,7 @@ impl std::fmt::Debug for QueryLogEntry {
 impl QueryLogEntry {
     /// Creates a new QueryLogEntry -- use `QueryLog::push` to add new entries to the log
     fn new(
+        id: u64,
         namespace_id: NamespaceId,
         query_type: &'static str,
         query_text: QueryText,
@@ -62,6 +80,7 @@ impl QueryLogEntry {
         issue_time: Time,
     ) -> Self {
         Self {
+            id,
             namespace_id,
             query_type,
             query_text,
@@ -69,6 +88,7 @@ impl QueryLogEntry {
             issue_time,
             query_completed_duration: UNCOMPLETED_DURATION.into(),
             success: atomic::AtomicBool::new(false),
+            cancellation: CancellationToken::new(),
         }
     }
 
@@ -103,15 +123,43 @@ impl QueryLogEntry {
         }
         self.success.store(success, atomic::Ordering::SeqCst);
     }
+
+    /// Cancel this query.
+    ///
+    /// Returns `false` if the query already completed.
+    pub fn cancel(&self) -> bool {
+        if self.query_completed_duration().is_some() {
+            return false;
+        }
+        self.cancellation.cancel();
+        true
+    }
+
+    /// Returns true if `cancel` was called before the query completed
+    pub fn cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+
+    /// Token that is cancelled when this query is cancelled.
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.cancellation.clone()
+    }
 }
 
 /// Stores a fixed number `QueryExecutions` -- handles locking
 /// internally so can be shared across multiple
+///
+/// Queries that are still running are also tracked separately from the
+/// fixed size log, so they can be cancelled however many queries ran since.
 #[derive(Debug)]
 pub struct QueryLog {
     log: Mutex<VecDeque<Arc<QueryLogEntry>>>,
+    /// The entries of the queries that have not completed, keyed by ID.
+    running: Mutex<HashMap<u64, Vec<Arc<QueryLogEntry>>>>,
+    handles: Mutex<QueryHandles>,
     max_size: usize,
     time_provider: Arc<dyn TimeProvider>,
+    next_id: atomic::AtomicU64,
 }
 
 impl QueryLog {
@@ -120,8 +168,11 @@ impl QueryLog {
     pub fn new(max_size: usize, time_provider: Arc<dyn TimeProvider>) -> Self {
         Self {
             log: Mutex::new(VecDeque::with_capacity(max_size)),
+            running: Default::default(),
+            handles: Default::default(),
             max_size,
             time_provider,
+            next_id: atomic::AtomicU64::new(1),
         }
     }
 
@@ -131,8 +182,75 @@ impl QueryLog {
         query_type: &'static str,
         query_text: QueryText,
         trace_id: Option<TraceId>,
+    ) -> Arc<QueryLogEntry> {
+        self.push_with_id(
+            self.reserve_id(),
+            namespace_id,
+            query_type,
+            query_text,
+            trace_id,
+        )
+    }
+
+    /// Reserve a query ID, to record a query with
+    /// [`push_with_id`](Self::push_with_id) later.
+    pub fn reserve_id(&self) -> u64 {
+        self.next_id.fetch_add(1, atomic::Ordering::Relaxed)
+    }
+
+    /// Reserve a query ID for a query of the namespace `namespace_id` that
+    /// is run later, returning a random non-zero handle identifying it.
+    ///
+    /// Handles can be given to clients, which cannot use them to guess the
+    /// IDs of other queries. Only the [`MAX_QUERY_HANDLES`] most recent
+    /// handles are kept.
+    pub fn reserve_handle(&self, namespace_id: NamespaceId) -> u64 {
+        let id = self.reserve_id();
+
+        let mut handles = self.handles.lock();
+        let handle = loop {
+            let handle = rand::random::<u64>();
+            if handle != 0 && !handles.ids.contains_key(&handle) {
+                break handle;
+            }
+        };
+
+        // enforce limit
+        if handles.order.len() == MAX_QUERY_HANDLES {
+            if let Some(evicted) = handles.order.pop_front() {
+                handles.ids.remove(&evicted);
+            }
+        }
+
+        handles.order.push_back(handle);
+        handles.ids.insert(handle, (namespace_id, id));
+        handle
+    }
+
+    /// Return the query ID identified by the `handle` created by
+    /// [`reserve_handle`](Self::reserve_handle) for the namespace
+    /// `namespace_id`, if it is still known.
+    pub fn handle_id(&self, namespace_id: NamespaceId, handle: u64) -> Option<u64> {
+        self.handles
+            .lock()
+            .ids
+            .get(&handle)
+            .filter(|(handle_namespace_id, _)| *handle_namespace_id == namespace_id)
+            .map(|(_, id)| *id)
+    }
+
+    /// Record a query with the ID `id`, reserved with
+    /// [`reserve_id`](Self::reserve_id).
+    pub fn push_with_id(
+        &self,
+        id: u64,
+        namespace_id: NamespaceId,
+        query_type: &'static str,
+        query_text: QueryText,
+        trace_id: Option<TraceId>,
     ) -> Arc<QueryLogEntry> {
         let entry = Arc::new(QueryLogEntry::new(
+            id,
             namespace_id,
             query_type,
             query_text,
@@ -140,6 +258,12 @@ impl QueryLog {
             self.time_provider.now(),
         ));
 
+        self.running
+            .lock()
+            .entry(id)
+            .or_default()
+            .push(Arc::clone(&entry));
+
         if self.max_size == 0 {
             return entry;
         }
@@ -163,7 +287,70 @@ impl QueryLog {
     /// Marks the provided query entry as completed using the current time.
     /// `success` specifies the query ran successfully
     pub fn set_completed(&self, entry: Arc<QueryLogEntry>, success: bool) {
-        entry.set_completed(self.time_provider.now(), success)
+        entry.set_completed(self.time_provider.now(), success);
+
+        let mut running = self.running.lock();
+        if let Some(entries) = running.get_mut(&entry.id) {
+            entries.retain(|e| !Arc::ptr_eq(e, &entry));
+            if entries.is_empty() {
+                running.remove(&entry.id);
+            }
+        }
+    }
+
+    /// Cancel the running queries of namespace `namespace_id` with the ID
+    /// `query_id`.
+    ///
+    /// Returns `false` if there is no such query or if it already completed.
+    pub fn cancel(&self, namespace_id: NamespaceId, query_id: u64) -> bool {
+        let running = self.running.lock();
+        running
+            .get(&query_id)
+            .into_iter()
+            .flatten()
+            .filter(|entry| entry.namespace_id == namespace_id)
+            .fold(false, |cancelled, entry| entry.cancel() || cancelled)
+    }
+}
+
+/// Handles of the query IDs reserved by [`QueryLog::reserve_handle`].
+#[derive(Debug, Default)]
+struct QueryHandles {
+    /// The namespace and query ID of each handle.
+    ids: HashMap<u64, (NamespaceId, u64)>,
+    /// The handles in the order they were created, to evict the oldest.
+    order: VecDeque<u64>,
+}
+
+/// Cancels the queries of a single namespace in the [`QueryLog`].
+#[derive(Debug)]
+pub(crate) struct QueryLogCanceller {
+    namespace_id: NamespaceId,
+    query_log: Arc<QueryLog>,
+}
+
+impl QueryLogCanceller {
+    pub(crate) fn new(namespace_id: NamespaceId, query_log: Arc<QueryLog>) -> Self {
+        Self {
+            namespace_id,
+            query_log,
+        }
+    }
+}
+
+impl QueryCanceller for QueryLogCanceller {
+    fn cancel_query(&self, query_id: u64) -> bool {
+        self.query_log.cancel(self.namespace_id, query_id)
+    }
+
+    fn reserve_query_handle(&self) -> u64 {
+        self.query_log.reserve_handle(self.namespace_id)
+    }
+
+    fn cancel_query_handle(&self, query_handle: u64) -> bool {
+        self.query_log
+            .handle_id(self.namespace_id, query_handle)
+            .map_or(false, |query_id| self.cancel_query(query_id))
     }
 }
 
@@ -178,6 +365,7 @@ mod test_super {
         let time_provider = MockProvider::new(Time::from_timestamp_millis(100).unwrap());
 
         let entry = Arc::new(QueryLogEntry::new(
+            1,
             NamespaceId::new(1),
             "sql",
             Box::new("SELECT 1"),
@@ -205,4 +393,110 @@ mod test_super {
         );
         assert!(!entry.success());
     }
+
+    #[test]
+    fn test_query_log_cancel() {
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_millis(100).unwrap()));
+        let query_log = Arc::new(QueryLog::new(10, Arc::clone(&time_provider) as _));
+
+        let ns1 = NamespaceId::new(1);
+        let ns2 = NamespaceId::new(2);
+        let entry1 = query_log.push(ns1, "sql", Box::new("SELECT 1"), None);
+        let entry2 = query_log.push(ns1, "sql", Box::new("SELECT 2"), None);
+        let entry3 = query_log.push(ns2, "sql", Box::new("SELECT 1"), None);
+        assert_eq!(entry1.id, 1);
+        assert_eq!(entry2.id, 2);
+        assert_eq!(entry3.id, 3);
+
+        // queries of other namespaces cannot be cancelled
+        assert!(!query_log.cancel(ns1, entry3.id));
+        assert!(!entry3.cancelled());
+
+        // unknown query
+        assert!(!query_log.cancel(ns1, 42));
+
+        let token = entry1.cancellation_token();
+        assert!(query_log.cancel(ns1, entry1.id));
+        assert!(entry1.cancelled());
+        assert!(token.is_cancelled());
+        assert!(!entry2.cancelled());
+
+        // completed queries cannot be cancelled
+        query_log.set_completed(Arc::clone(&entry2), true);
+        assert!(!query_log.cancel(ns1, entry2.id));
+        assert!(!entry2.cancelled());
+
+        let canceller = QueryLogCanceller::new(ns2, Arc::clone(&query_log));
+        assert!(canceller.cancel_query(entry3.id));
+        assert!(entry3.cancelled());
+
+        // queries recorded with a reserved ID, such as repeated executions
+        // of a Flight SQL ticket, are cancelled together
+        let handle = canceller.reserve_query_handle();
+        assert_ne!(handle, 0);
+        let query_id = query_log.handle_id(ns2, handle).unwrap();
+        assert_eq!(query_id, 4);
+        let entry4 = query_log.push_with_id(query_id, ns2, "flightsql", Box::new("SELECT 1"), None);
+        let entry5 = query_log.push_with_id(query_id, ns2, "flightsql", Box::new("SELECT 1"), None);
+        let entry6 = query_log.push(ns2, "flightsql", Box::new("SELECT 1"), None);
+        assert_eq!(entry6.id, 5);
+        assert!(canceller.cancel_query_handle(handle));
+        assert!(entry4.cancelled());
+        assert!(entry5.cancelled());
+        assert!(!entry6.cancelled());
+
+        // handles are only valid in the namespace they were created for
+        assert_eq!(query_log.handle_id(ns1, handle), None);
+        assert!(!QueryLogCanceller::new(ns1, Arc::clone(&query_log)).cancel_query_handle(handle));
+
+        // unknown handles identify no query
+        assert!(!canceller.cancel_query_handle(handle.wrapping_add(1)));
+    }
+
+    #[test]
+    fn test_query_log_handles_evicted() {
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_millis(100).unwrap()));
+        let query_log = QueryLog::new(10, Arc::clone(&time_provider) as _);
+
+        let ns = NamespaceId::new(1);
+        let first = query_log.reserve_handle(ns);
+        let second = query_log.reserve_handle(ns);
+        for _ in 2..MAX_QUERY_HANDLES {
+            query_log.reserve_handle(ns);
+        }
+        assert_eq!(query_log.handle_id(ns, first), Some(1));
+
+        // the oldest handle is evicted once the limit is reached
+        query_log.reserve_handle(ns);
+        assert_eq!(query_log.handle_id(ns, first), None);
+        assert_eq!(query_log.handle_id(ns, second), Some(2));
+    }
+
+    #[test]
+    fn test_query_log_cancel_evicted() {
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_millis(100).unwrap()));
+        let query_log = QueryLog::new(2, Arc::clone(&time_provider) as _);
+
+        let ns = NamespaceId::new(1);
+        let running = query_log.push(ns, "sql", Box::new("SELECT 1"), None);
+        for _ in 0..3 {
+            let entry = query_log.push(ns, "sql", Box::new("SELECT 2"), None);
+            query_log.set_completed(entry, true);
+        }
+
+        // the running query was evicted from the log, but can still be
+        // cancelled
+        assert!(query_log
+            .entries()
+            .iter()
+            .all(|entry| !Arc::ptr_eq(entry, &running)));
+        assert!(query_log.cancel(ns, running.id));
+        assert!(running.cancelled());
+
+        // completed queries are no longer tracked
+        assert_eq!(query_log.running.lock().len(), 1);
+        query_log.set_completed(Arc::clone(&running), false);
+        assert!(query_log.running.lock().is_empty());
+        assert!(!query_log.cancel(ns, running.id));
+    }
 }
@@ -0,0 +1,110 @@
+use crate::{
+    cache::namespace::CachedNamespace,
+    system_tables::{sorted_tables, split_batch, BatchIterator, IoxSystemTable},
+};
+use arrow::{
+    array::{ArrayRef, Int64Array, StringArray},
+    datatypes::{DataType, Field, Schema, SchemaRef},
+    error::Result,
+    record_batch::RecordBatch,
+};
+use async_trait::async_trait;
+use data_types::ColumnType;
+use std::sync::Arc;
+
+/// Implementation of system.columns table
+#[derive(Debug)]
+pub(super) struct ColumnsTable {
+    schema: SchemaRef,
+    namespace: Arc<CachedNamespace>,
+}
+
+impl ColumnsTable {
+    pub(super) fn new(namespace: Arc<CachedNamespace>) -> Self {
+        Self {
+            schema: columns_schema(),
+            namespace,
+        }
+    }
+}
+
+/// A row of the system.columns table.
+struct ColumnRow<'a> {
+    table_name: &'a str,
+    column_id: i64,
+    column_name: &'a str,
+    column_type: ColumnType,
+    data_type: String,
+}
+
+#[async_trait]
+impl IoxSystemTable for ColumnsTable {
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    async fn scan(&self, batch_size: usize) -> Result<BatchIterator> {
+        let rows = sorted_tables(&self.namespace)
+            .into_iter()
+            .flat_map(|(table_name, table)| {
+                table
+                    .schema
+                    .iter()
+                    .filter_map(move |(influx_column_type, field)| {
+                        // the schema and the column ID map are built from the
+                        // same catalog columns
+                        let column_id = table.column_id_map_rev.get(field.name().as_str())?;
+
+                        Some(ColumnRow {
+                            table_name: table_name.as_ref(),
+                            column_id: column_id.get(),
+                            column_name: field.name(),
+                            column_type: influx_column_type.into(),
+                            data_type: field.data_type().to_string(),
+                        })
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(
+                rows.iter()
+                    .map(|row| Some(row.table_name))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|row| Some(row.column_id))
+                    .collect::<Int64Array>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|row| Some(row.column_name))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|row| Some(row.column_type.as_str()))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|row| Some(row.data_type.as_str()))
+                    .collect::<StringArray>(),
+            ),
+        ];
+
+        let batch = RecordBatch::try_new(self.schema(), columns)?;
+        Ok(split_batch(batch, batch_size))
+    }
+}
+
+fn columns_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("table_name", DataType::Utf8, false),
+        Field::new("column_id", DataType::Int64, false),
+        Field::new("column_name", DataType::Utf8, false),
+        Field::new("column_type", DataType::Utf8, false),
+        Field::new("data_type", DataType::Utf8, false),
+    ]))
+}
@@ -1,4 +1,10 @@
-use crate::query_log::QueryLog;
+use crate::{
+    cache::{
+        namespace::{CachedNamespace, CachedTable},
+        CatalogCache,
+    },
+    query_log::QueryLog,
+};
 use arrow::{datatypes::SchemaRef, error::Result as ArrowResult, record_batch::RecordBatch};
 use async_trait::async_trait;
 use data_types::NamespaceId;
@@ -16,6 +22,7 @@ use datafusion::{
     },
     prelude::Expr,
 };
+use futures::{stream::BoxStream, StreamExt, TryStreamExt};
 use std::collections::HashMap;
 use std::{
     any::Any,
@@ -24,11 +31,19 @@ use std::{
     task::{Context, Poll},
 };
 
+mod columns;
+mod parquet_files;
+mod partitions;
 mod queries;
+mod tables;
 
 pub const SYSTEM_SCHEMA: &str = "system";
 
+const COLUMNS_TABLE: &str = "columns";
+const PARQUET_FILES_TABLE: &str = "parquet_files";
+const PARTITIONS_TABLE: &str = "partitions";
 const QUERIES_TABLE: &str = "queries";
+const TABLES_TABLE: &str = "tables";
 
 pub struct SystemSchemaProvider {
     tables: HashMap<&'static str, Arc<dyn TableProvider>>,
@@ -36,6 +51,8 @@ pub struct SystemSchemaProvider {
 
 impl SystemSchemaProvider {
     pub fn new(
+        catalog_cache: Arc<CatalogCache>,
+        namespace: Arc<CachedNamespace>,
         query_log: Arc<QueryLog>,
         namespace_id: NamespaceId,
         include_debug_info: bool,
@@ -47,6 +64,32 @@ impl SystemSchemaProvider {
                 table: Arc::new(queries::QueriesTable::new(query_log, Some(namespace_id))),
             });
             tables.insert(QUERIES_TABLE, queries);
+
+            let tables_table = Arc::new(SystemTableProvider {
+                table: Arc::new(tables::TablesTable::new(Arc::clone(&namespace))),
+            });
+            tables.insert(TABLES_TABLE, tables_table);
+
+            let columns = Arc::new(SystemTableProvider {
+                table: Arc::new(columns::ColumnsTable::new(Arc::clone(&namespace))),
+            });
+            tables.insert(COLUMNS_TABLE, columns);
+
+            let partitions = Arc::new(SystemTableProvider {
+                table: Arc::new(partitions::PartitionsTable::new(
+                    Arc::clone(&catalog_cache),
+                    Arc::clone(&namespace),
+                )),
+            });
+            tables.insert(PARTITIONS_TABLE, partitions);
+
+            let parquet_files = Arc::new(SystemTableProvider {
+                table: Arc::new(parquet_files::ParquetFilesTable::new(
+                    catalog_cache,
+                    namespace,
+                )),
+            });
+            tables.insert(PARQUET_FILES_TABLE, parquet_files);
         }
 
         Self { tables }
@@ -81,12 +124,31 @@ impl SchemaProvider for SystemSchemaProvider {
 type BatchIterator = Box<dyn Iterator<Item = ArrowResult<RecordBatch>> + Send + Sync>;
 
 /// The minimal thing that a system table needs to implement
+#[async_trait]
 trait IoxSystemTable: Send + Sync {
     /// Produce the schema from this system table
     fn schema(&self) -> SchemaRef;
 
     /// Get the contents of the system table
-    fn scan(&self, batch_size: usize) -> ArrowResult<BatchIterator>;
+    async fn scan(&self, batch_size: usize) -> ArrowResult<BatchIterator>;
+}
+
+/// Split `batch` into batches of at most `batch_size` rows.
+fn split_batch(batch: RecordBatch, batch_size: usize) -> BatchIterator {
+    let num_rows = batch.num_rows();
+    let batch_size = batch_size.max(1);
+
+    Box::new((0..num_rows).step_by(batch_size).map(move |offset| {
+        let len = batch_size.min(num_rows - offset);
+        Ok(batch.slice(offset, len))
+    }))
+}
+
+/// The tables of `namespace`, ordered by name.
+fn sorted_tables(namespace: &CachedNamespace) -> Vec<(&Arc<str>, &Arc<CachedTable>)> {
+    let mut tables = namespace.tables.iter().collect::<Vec<_>>();
+    tables.sort_by(|(a, _), (b, _)| a.cmp(b));
+    tables
 }
 
 /// Adapter that makes any `IoxSystemTable` a DataFusion `TableProvider`
@@ -179,9 +241,18 @@ impl<T: IoxSystemTable + 'static> ExecutionPlan for SystemTableExecutionPlan<T>
     ) -> DataFusionResult<SendableRecordBatchStream> {
         let batch_size = context.session_config().batch_size();
 
+        // the contents of catalog-backed tables are fetched lazily when the
+        // stream is first polled
+        let table = Arc::clone(&self.table);
+        let batches = futures::stream::once(async move {
+            table.scan(batch_size).await.map(futures::stream::iter)
+        })
+        .try_flatten()
+        .boxed();
+
         Ok(Box::pin(SystemTableStream {
             projected_schema: Arc::clone(&self.projected_schema),
-            batches: self.table.scan(batch_size)?,
+            batches,
             projection: self.projection.clone(),
         }))
     }
@@ -205,7 +276,7 @@ impl<T> DisplayAs for SystemTableExecutionPlan<T> {
 struct SystemTableStream {
     projected_schema: SchemaRef,
     projection: Option<Vec<usize>>,
-    batches: BatchIterator,
+    batches: BoxStream<'static, ArrowResult<RecordBatch>>,
 }
 
 impl RecordBatchStream for SystemTableStream {
@@ -217,13 +288,15 @@ impl RecordBatchStream for SystemTableStream {
 impl futures::Stream for SystemTableStream {
     type Item = Result<RecordBatch, DataFusionError>;
 
-    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        Poll::Ready(self.batches.next().map(|maybe_batch| {
-            let batch = maybe_batch?;
-            match &self.projection {
-                Some(projection) => Ok(batch.project(projection)?),
-                None => Ok(batch),
-            }
-        }))
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.batches.poll_next_unpin(cx).map(|maybe_batch| {
+            maybe_batch.map(|maybe_batch| {
+                let batch = maybe_batch?;
+                match &self.projection {
+                    Some(projection) => Ok(batch.project(projection)?),
+                    None => Ok(batch),
+                }
+            })
+        })
     }
 }
@@ -0,0 +1,132 @@
+use crate::{
+    cache::{namespace::CachedNamespace, partition::PartitionRequest, CatalogCache},
+    system_tables::{sorted_tables, split_batch, BatchIterator, IoxSystemTable},
+};
+use arrow::{
+    array::{ArrayRef, Int64Array, StringArray},
+    datatypes::{DataType, Field, Schema, SchemaRef},
+    error::Result,
+    record_batch::RecordBatch,
+};
+use async_trait::async_trait;
+use data_types::TransitionPartitionId;
+use std::{collections::BTreeMap, sync::Arc};
+
+/// Implementation of system.partitions table
+///
+/// Only lists the partitions that have parquet files.
+#[derive(Debug)]
+pub(super) struct PartitionsTable {
+    schema: SchemaRef,
+    catalog_cache: Arc<CatalogCache>,
+    namespace: Arc<CachedNamespace>,
+}
+
+impl PartitionsTable {
+    pub(super) fn new(catalog_cache: Arc<CatalogCache>, namespace: Arc<CachedNamespace>) -> Self {
+        Self {
+            schema: partitions_schema(),
+            catalog_cache,
+            namespace,
+        }
+    }
+}
+
+/// A row of the system.partitions table.
+struct PartitionRow {
+    table_name: Arc<str>,
+    partition_id: TransitionPartitionId,
+    sort_key: Option<String>,
+    file_count: i64,
+}
+
+#[async_trait]
+impl IoxSystemTable for PartitionsTable {
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    async fn scan(&self, batch_size: usize) -> Result<BatchIterator> {
+        let mut rows = vec![];
+
+        for (table_name, table) in sorted_tables(&self.namespace) {
+            let files = self
+                .catalog_cache
+                .parquet_file()
+                .get(table.id, None, None)
+                .await;
+
+            let mut file_counts: BTreeMap<TransitionPartitionId, i64> = BTreeMap::new();
+            for file in files.files.iter() {
+                *file_counts.entry(file.partition_id.clone()).or_default() += 1;
+            }
+
+            let requests = file_counts
+                .keys()
+                .map(|partition_id| PartitionRequest {
+                    partition_id: partition_id.clone(),
+                    sort_key_should_cover: vec![],
+                })
+                .collect();
+            let mut sort_keys = self
+                .catalog_cache
+                .partition()
+                .get(Arc::clone(table), requests, None)
+                .await
+                .into_iter()
+                .map(|partition| {
+                    let sort_key = partition.sort_key.as_ref().map(|sort_key| {
+                        sort_key.sort_key.to_columns().collect::<Vec<_>>().join(",")
+                    });
+                    (partition.id.clone(), sort_key)
+                })
+                .collect::<BTreeMap<_, _>>();
+
+            rows.extend(
+                file_counts
+                    .into_iter()
+                    .map(|(partition_id, file_count)| PartitionRow {
+                        table_name: Arc::clone(table_name),
+                        sort_key: sort_keys.remove(&partition_id).flatten(),
+                        partition_id,
+                        file_count,
+                    }),
+            );
+        }
+
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(
+                rows.iter()
+                    .map(|row| Some(row.table_name.as_ref()))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|row| Some(row.partition_id.to_string()))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|row| row.sort_key.as_deref())
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|row| Some(row.file_count))
+                    .collect::<Int64Array>(),
+            ),
+        ];
+
+        let batch = RecordBatch::try_new(self.schema(), columns)?;
+        Ok(split_batch(batch, batch_size))
+    }
+}
+
+fn partitions_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("table_name", DataType::Utf8, false),
+        Field::new("partition_id", DataType::Utf8, false),
+        Field::new("sort_key", DataType::Utf8, true),
+        Field::new("file_count", DataType::Int64, false),
+    ]))
+}
@@ -0,0 +1,64 @@
+use crate::{
+    cache::namespace::CachedNamespace,
+    system_tables::{sorted_tables, split_batch, BatchIterator, IoxSystemTable},
+};
+use arrow::{
+    array::{ArrayRef, Int64Array, StringArray},
+    datatypes::{DataType, Field, Schema, SchemaRef},
+    error::Result,
+    record_batch::RecordBatch,
+};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Implementation of system.tables table
+#[derive(Debug)]
+pub(super) struct TablesTable {
+    schema: SchemaRef,
+    namespace: Arc<CachedNamespace>,
+}
+
+impl TablesTable {
+    pub(super) fn new(namespace: Arc<CachedNamespace>) -> Self {
+        Self {
+            schema: tables_schema(),
+            namespace,
+        }
+    }
+}
+
+#[async_trait]
+impl IoxSystemTable for TablesTable {
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    async fn scan(&self, batch_size: usize) -> Result<BatchIterator> {
+        let tables = sorted_tables(&self.namespace);
+
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(
+                tables
+                    .iter()
+                    .map(|(_, table)| Some(table.id.get()))
+                    .collect::<Int64Array>(),
+            ),
+            Arc::new(
+                tables
+                    .iter()
+                    .map(|(name, _)| Some(name.as_ref()))
+                    .collect::<StringArray>(),
+            ),
+        ];
+
+        let batch = RecordBatch::try_new(self.schema(), columns)?;
+        Ok(split_batch(batch, batch_size))
+    }
+}
+
+fn tables_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("table_id", DataType::Int64, false),
+        Field::new("table_name", DataType::Utf8, false),
+    ]))
+}
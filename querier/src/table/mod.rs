@@ -243,6 +243,11 @@ impl QuerierTable {
             )
             .await;
 
+        let tombstones = catalog_cache
+            .tombstone()
+            .get(self.id(), span_recorder.child_span("cache GET tombstone"))
+            .await;
+
         let columns: HashSet<ColumnId> = parquet_files
             .files
             .iter()
@@ -308,9 +313,15 @@ impl QuerierTable {
             .await;
 
         // Prune Parquet file chunks (assuming the ingester has already pruned ingester chunks)
+        //
+        // Deletes recorded after a file was persisted are applied at query time, the ingester
+        // applies them to the data it still holds.
         let parquet_file_chunks: Vec<_> = parquet_files
             .into_iter()
-            .map(|c| Arc::new(c) as Arc<dyn QueryChunk>)
+            .map(|c| {
+                let delete_predicates = tombstones.predicates_for(c.max_l0_created_at());
+                Arc::new(c.with_delete_predicates(delete_predicates)) as Arc<dyn QueryChunk>
+            })
             .collect();
         let num_initial_parquet_file_chunks = parquet_file_chunks.len();
         debug!(num_chunks=%num_initial_parquet_file_chunks, "Fetched Parquet file chunks");
@@ -0,0 +1,513 @@
+//! Aggregate functions to estimate the number of distinct values using
+//! [HyperLogLog] sketches.
+//!
+//! A sketch is produced with `SUM_HLL`, which may be stored or merged
+//! with other sketches, and the estimate is produced with `COUNT_HLL`:
+//!
+//! ```sql
+//! SELECT COUNT_HLL(sketch)
+//! FROM (
+//!   SELECT DATE_BIN(INTERVAL '1 day', time) AS day, SUM_HLL(host) AS sketch
+//!   FROM cpu
+//!   GROUP BY day
+//! )
+//! ```
+//!
+//! `APPROX_DISTINCT_HLL` is equivalent to `COUNT_HLL(SUM_HLL(value))`,
+//! computed in a single aggregation.
+//!
+//! Sketches are encoded as strings with the prefix `HLL_`, so that they
+//! may be written to and read from string fields. The hash of each value
+//! is stable, so sketches produced by different queries and processes
+//! may be merged.
+//!
+//! [HyperLogLog]: https://en.wikipedia.org/wiki/HyperLogLog
+use std::sync::Arc;
+
+use arrow::{
+    array::{Array, ArrayRef, AsArray, BinaryArray},
+    compute::cast,
+    datatypes::{
+        DataType, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type, UInt16Type, UInt32Type,
+        UInt64Type, UInt8Type,
+    },
+};
+use base64::{prelude::BASE64_STANDARD, Engine};
+use datafusion::{
+    error::{DataFusionError, Result},
+    logical_expr::{
+        Accumulator, AccumulatorFactoryFunction, AggregateUDF, ReturnTypeFunction, Signature,
+        StateTypeFunction, Volatility,
+    },
+    scalar::ScalarValue,
+};
+use once_cell::sync::Lazy;
+
+/// The name of the sum_hll UDAF given to DataFusion.
+pub const SUM_HLL_UDAF_NAME: &str = "sum_hll";
+
+/// The name of the count_hll UDAF given to DataFusion.
+pub const COUNT_HLL_UDAF_NAME: &str = "count_hll";
+
+/// The name of the approx_distinct_hll UDAF given to DataFusion.
+pub const APPROX_DISTINCT_HLL_UDAF_NAME: &str = "approx_distinct_hll";
+
+/// Implementation of sum_hll.
+/// This function takes a single argument of any type and produces
+/// a sketch of the distinct values of the argument.
+pub(crate) static SUM_HLL: Lazy<Arc<AggregateUDF>> = Lazy::new(|| {
+    make_uda(
+        SUM_HLL_UDAF_NAME,
+        Signature::any(1, Volatility::Immutable),
+        Input::Values,
+        Output::Sketch,
+    )
+});
+
+/// Implementation of count_hll.
+/// This function takes a single argument of sketches produced by
+/// `sum_hll`, and produces an estimate of the number of distinct values
+/// of the merged sketches.
+pub(crate) static COUNT_HLL: Lazy<Arc<AggregateUDF>> = Lazy::new(|| {
+    make_uda(
+        COUNT_HLL_UDAF_NAME,
+        Signature::exact(vec![DataType::Utf8], Volatility::Immutable),
+        Input::Sketches,
+        Output::Count,
+    )
+});
+
+/// Implementation of approx_distinct_hll.
+/// This function takes a single argument of any type and produces an
+/// estimate of the number of distinct values of the argument.
+pub(crate) static APPROX_DISTINCT_HLL: Lazy<Arc<AggregateUDF>> = Lazy::new(|| {
+    make_uda(
+        APPROX_DISTINCT_HLL_UDAF_NAME,
+        Signature::any(1, Volatility::Immutable),
+        Input::Values,
+        Output::Count,
+    )
+});
+
+/// The prefix of an encoded sketch.
+const SKETCH_PREFIX: &str = "HLL_";
+
+/// The number of bits of the hash used to select a register.
+const PRECISION: u8 = 14;
+
+/// The number of registers of a sketch.
+const NUM_REGISTERS: usize = 1 << PRECISION;
+
+/// The encoding of the registers in a serialized sketch.
+const DENSE_ENCODING: u8 = 0;
+const SPARSE_ENCODING: u8 = 1;
+
+/// The arguments of the aggregate.
+#[derive(Debug, Clone, Copy)]
+enum Input {
+    /// Values to add to the sketch.
+    Values,
+    /// Encoded sketches to merge.
+    Sketches,
+}
+
+/// The result of the aggregate.
+#[derive(Debug, Clone, Copy)]
+enum Output {
+    /// The encoded sketch.
+    Sketch,
+    /// The estimated number of distinct values.
+    Count,
+}
+
+/// Create a User Defined Aggregate Function (UDAF) for datafusion.
+fn make_uda(name: &str, signature: Signature, input: Input, output: Output) -> Arc<AggregateUDF> {
+    let return_type: ReturnTypeFunction = Arc::new(move |_| {
+        Ok(Arc::new(match output {
+            Output::Sketch => DataType::Utf8,
+            Output::Count => DataType::UInt64,
+        }))
+    });
+    let accumulator: AccumulatorFactoryFunction = Arc::new(move |_| {
+        Ok(Box::new(HllAccumulator {
+            input,
+            output,
+            sketch: HyperLogLog::new(),
+        }))
+    });
+    let state_type: StateTypeFunction = Arc::new(|_| Ok(Arc::new(vec![DataType::Binary])));
+
+    Arc::new(AggregateUDF::new(
+        name,
+        &signature,
+        &return_type,
+        &accumulator,
+        &state_type,
+    ))
+}
+
+/// Accumulator that adds values or merges sketches into a single
+/// [`HyperLogLog`] sketch. The intermediate state is the serialized
+/// sketch.
+#[derive(Debug)]
+struct HllAccumulator {
+    input: Input,
+    output: Output,
+    sketch: HyperLogLog,
+}
+
+impl Accumulator for HllAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        assert_eq!(values.len(), 1);
+
+        match self.input {
+            Input::Values => self.sketch.add_array(&values[0]),
+            Input::Sketches => {
+                let array = cast(&values[0], &DataType::Utf8)?;
+                for sketch in array.as_string::<i32>().iter().flatten() {
+                    self.sketch.merge(&HyperLogLog::decode(sketch)?);
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn evaluate(&self) -> Result<ScalarValue> {
+        Ok(match self.output {
+            Output::Sketch => ScalarValue::Utf8(Some(self.sketch.encode())),
+            Output::Count => ScalarValue::UInt64(Some(self.sketch.count())),
+        })
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self) + self.sketch.registers.capacity()
+    }
+
+    fn state(&self) -> Result<Vec<ScalarValue>> {
+        Ok(vec![ScalarValue::Binary(Some(self.sketch.serialize()))])
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        assert_eq!(states.len(), 1);
+
+        let array = states[0]
+            .as_any()
+            .downcast_ref::<BinaryArray>()
+            .ok_or_else(|| {
+                DataFusionError::Internal("expected binary state for HLL sketch".to_string())
+            })?;
+        for state in array.iter().flatten() {
+            self.sketch.merge(&HyperLogLog::deserialize(state)?);
+        }
+        Ok(())
+    }
+}
+
+/// A HyperLogLog sketch of the distinct values added to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct HyperLogLog {
+    /// For each register, the maximum position of the leftmost 1-bit
+    /// of the hashes selecting the register.
+    registers: Vec<u8>,
+}
+
+impl HyperLogLog {
+    fn new() -> Self {
+        Self {
+            registers: vec![0; NUM_REGISTERS],
+        }
+    }
+
+    fn add_hash(&mut self, hash: u64) {
+        let idx = (hash >> (64 - PRECISION)) as usize;
+        // Set a sentinel bit so the rank is bounded when the remaining
+        // bits of the hash are all zero.
+        let w = (hash << PRECISION) | (1 << (PRECISION - 1));
+        let rank = w.leading_zeros() as u8 + 1;
+        if rank > self.registers[idx] {
+            self.registers[idx] = rank;
+        }
+    }
+
+    fn add_bytes(&mut self, bytes: &[u8]) {
+        self.add_hash(hash(bytes))
+    }
+
+    /// Add the non-null values of the array to the sketch.
+    fn add_array(&mut self, array: &ArrayRef) -> Result<()> {
+        macro_rules! add_primitive {
+            ($T:ty) => {
+                for v in array.as_primitive::<$T>().iter().flatten() {
+                    self.add_bytes(&v.to_le_bytes())
+                }
+            };
+        }
+
+        match array.data_type() {
+            DataType::Int8 => add_primitive!(Int8Type),
+            DataType::Int16 => add_primitive!(Int16Type),
+            DataType::Int32 => add_primitive!(Int32Type),
+            DataType::Int64 => add_primitive!(Int64Type),
+            DataType::UInt8 => add_primitive!(UInt8Type),
+            DataType::UInt16 => add_primitive!(UInt16Type),
+            DataType::UInt32 => add_primitive!(UInt32Type),
+            DataType::UInt64 => add_primitive!(UInt64Type),
+            DataType::Float64 => add_primitive!(Float64Type),
+            DataType::Boolean => {
+                for v in array.as_boolean().iter().flatten() {
+                    self.add_bytes(&[v as u8])
+                }
+            }
+            DataType::Utf8 => {
+                for v in array.as_string::<i32>().iter().flatten() {
+                    self.add_bytes(v.as_bytes())
+                }
+            }
+            DataType::Dictionary(_, value_type) => {
+                let array = cast(array, value_type)?;
+                self.add_array(&array)?;
+            }
+            _ => {
+                // Any other type is hashed using its string representation.
+                let array = cast(array, &DataType::Utf8)?;
+                for v in array.as_string::<i32>().iter().flatten() {
+                    self.add_bytes(v.as_bytes())
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Merge the registers of `other` into this sketch.
+    fn merge(&mut self, other: &Self) {
+        for (r, o) in self.registers.iter_mut().zip(&other.registers) {
+            *r = (*r).max(*o);
+        }
+    }
+
+    /// Return the estimated number of distinct values added to the sketch.
+    fn count(&self) -> u64 {
+        let m = NUM_REGISTERS as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+
+        let (sum, zeros) = self
+            .registers
+            .iter()
+            .fold((0.0, 0usize), |(sum, zeros), &r| {
+                (sum + 2f64.powi(-(r as i32)), zeros + (r == 0) as usize)
+            });
+
+        let estimate = alpha * m * m / sum;
+        if estimate <= 2.5 * m && zeros > 0 {
+            // Use linear counting for small cardinalities.
+            (m * (m / zeros as f64).ln()).round() as u64
+        } else {
+            estimate.round() as u64
+        }
+    }
+
+    /// Serialize the sketch to bytes. Sketches with few non-zero
+    /// registers are stored as a list of `(index, value)` pairs.
+    fn serialize(&self) -> Vec<u8> {
+        let non_zero = self.registers.iter().filter(|&&r| r != 0).count();
+
+        let mut buf = vec![PRECISION];
+        if non_zero * 3 < NUM_REGISTERS {
+            buf.reserve(1 + non_zero * 3);
+            buf.push(SPARSE_ENCODING);
+            for (idx, &r) in self.registers.iter().enumerate() {
+                if r != 0 {
+                    buf.extend_from_slice(&(idx as u16).to_le_bytes());
+                    buf.push(r);
+                }
+            }
+        } else {
+            buf.reserve(1 + NUM_REGISTERS);
+            buf.push(DENSE_ENCODING);
+            buf.extend_from_slice(&self.registers);
+        }
+        buf
+    }
+
+    /// Deserialize a sketch previously serialized with [`Self::serialize`].
+    fn deserialize(buf: &[u8]) -> Result<Self> {
+        let invalid = |msg: &str| DataFusionError::Execution(format!("invalid HLL sketch: {msg}"));
+
+        let (precision, encoding, data) = match buf {
+            [precision, encoding, data @ ..] => (*precision, *encoding, data),
+            _ => return Err(invalid("too short")),
+        };
+        if precision != PRECISION {
+            return Err(invalid(&format!("unsupported precision {precision}")));
+        }
+
+        let mut sketch = Self::new();
+        match encoding {
+            DENSE_ENCODING if data.len() == NUM_REGISTERS => {
+                sketch.registers.copy_from_slice(data);
+            }
+            SPARSE_ENCODING if data.len() % 3 == 0 => {
+                for entry in data.chunks_exact(3) {
+                    let idx = u16::from_le_bytes([entry[0], entry[1]]) as usize;
+                    let r = sketch
+                        .registers
+                        .get_mut(idx)
+                        .ok_or_else(|| invalid("register out of range"))?;
+                    *r = entry[2];
+                }
+            }
+            _ => return Err(invalid("unexpected length")),
+        }
+        Ok(sketch)
+    }
+
+    /// Encode the sketch as a string.
+    fn encode(&self) -> String {
+        format!(
+            "{SKETCH_PREFIX}{}",
+            BASE64_STANDARD.encode(self.serialize())
+        )
+    }
+
+    /// Decode a sketch previously encoded with [`Self::encode`].
+    fn decode(s: &str) -> Result<Self> {
+        let data = s
+            .strip_prefix(SKETCH_PREFIX)
+            .ok_or_else(|| {
+                DataFusionError::Execution(format!(
+                    "invalid HLL sketch: expected prefix {SKETCH_PREFIX}"
+                ))
+            })
+            .and_then(|s| {
+                BASE64_STANDARD
+                    .decode(s)
+                    .map_err(|e| DataFusionError::Execution(format!("invalid HLL sketch: {e}")))
+            })?;
+        Self::deserialize(&data)
+    }
+}
+
+/// A stable 64-bit hash of `bytes`. The 64-bit FNV-1a hash is mixed
+/// with the MurmurHash3 finalizer, to distribute similar inputs over
+/// all the bits of the hash.
+fn hash(bytes: &[u8]) -> u64 {
+    let mut h: u64 = 0xcbf29ce484222325;
+    for b in bytes {
+        h ^= *b as u64;
+        h = h.wrapping_mul(0x100000001b3);
+    }
+
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xff51afd7ed558ccd);
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xc4ceb9fe1a85ec53);
+    h ^= h >> 33;
+    h
+}
+
+#[cfg(test)]
+mod test {
+    use arrow::{
+        array::{Int64Array, StringArray},
+        record_batch::RecordBatch,
+    };
+    use datafusion::assert_batches_eq;
+    use datafusion_util::context_with_table;
+
+    use super::*;
+
+    fn sketch_of(values: impl IntoIterator<Item = i64>) -> HyperLogLog {
+        let mut sketch = HyperLogLog::new();
+        let array: ArrayRef = Arc::new(Int64Array::from_iter_values(values));
+        sketch.add_array(&array).unwrap();
+        sketch
+    }
+
+    #[test]
+    fn test_count() {
+        assert_eq!(HyperLogLog::new().count(), 0);
+
+        for n in [1, 10, 1_000, 100_000] {
+            let sketch = sketch_of((0..n).chain(0..n));
+            let count = sketch.count() as f64;
+            let error = (count - n as f64).abs() / n as f64;
+            assert!(error < 0.02, "estimated {count} for {n} distinct values");
+        }
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut a = sketch_of(0..5_000);
+        let b = sketch_of(2_500..10_000);
+        a.merge(&b);
+        assert_eq!(a, sketch_of(0..10_000));
+    }
+
+    #[test]
+    fn test_encode_decode() {
+        for sketch in [HyperLogLog::new(), sketch_of(0..10), sketch_of(0..100_000)] {
+            let encoded = sketch.encode();
+            assert!(encoded.starts_with(SKETCH_PREFIX));
+            assert_eq!(HyperLogLog::decode(&encoded).unwrap(), sketch);
+        }
+
+        // The sparse encoding is used for small sketches.
+        assert_eq!(sketch_of(0..10).serialize().len(), 2 + 3 * 10);
+
+        let err = HyperLogLog::decode("foo").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Execution error: invalid HLL sketch: expected prefix HLL_"
+        );
+        let err = HyperLogLog::decode("HLL_AAAA").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Execution error: invalid HLL sketch: unsupported precision 0"
+        );
+    }
+
+    /// plumbing test to validate the functions may be called via SQL
+    #[tokio::test]
+    async fn test_sql() {
+        let batch = RecordBatch::try_from_iter(vec![
+            (
+                "tag",
+                Arc::new(StringArray::from(vec!["a", "a", "b", "b", "b"])) as ArrayRef,
+            ),
+            (
+                "host",
+                Arc::new(StringArray::from(vec![
+                    Some("h1"),
+                    Some("h2"),
+                    Some("h2"),
+                    None,
+                    Some("h3"),
+                ])) as ArrayRef,
+            ),
+        ])
+        .unwrap();
+
+        let ctx = context_with_table(batch);
+        crate::register_aggregate_functions(&ctx);
+
+        let result = ctx
+            .sql(
+                "SELECT count_hll(sketch) AS merged, sum(distinct_hosts) AS total \
+                 FROM (SELECT tag, sum_hll(host) AS sketch, approx_distinct_hll(host) AS distinct_hosts FROM t GROUP BY tag)",
+            )
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+
+        let expected = vec![
+            "+--------+-------+",
+            "| merged | total |",
+            "+--------+-------+",
+            "| 3      | 4     |",
+            "+--------+-------+",
+        ];
+        assert_batches_eq!(&expected, &result);
+    }
+}
@@ -39,6 +39,9 @@ mod window;
 
 pub mod gapfill;
 
+/// HyperLogLog sketches
+pub mod hll;
+
 /// Function registry
 mod registry;
 
@@ -110,6 +113,15 @@ pub fn register_scalar_functions(ctx: &SessionContext) {
     }
 }
 
+/// registers aggregate functions so they can be invoked via SQL
+pub fn register_aggregate_functions(ctx: &SessionContext) {
+    let registry = registry::instance();
+    for f in registry.udafs() {
+        let udaf = registry.udaf(&f).unwrap();
+        ctx.register_udaf(udaf.as_ref().clone())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use arrow::{
@@ -7,7 +7,7 @@ use datafusion::{
 };
 use once_cell::sync::Lazy;
 
-use crate::{gapfill, regex, window};
+use crate::{gapfill, hll, regex, window};
 
 static REGISTRY: Lazy<IOxFunctionRegistry> = Lazy::new(IOxFunctionRegistry::new);
 
@@ -19,6 +19,18 @@ impl IOxFunctionRegistry {
     fn new() -> Self {
         Self {}
     }
+
+    /// Set of all registered aggregate functions.
+    pub(crate) fn udafs(&self) -> HashSet<String> {
+        [
+            hll::APPROX_DISTINCT_HLL_UDAF_NAME,
+            hll::COUNT_HLL_UDAF_NAME,
+            hll::SUM_HLL_UDAF_NAME,
+        ]
+        .into_iter()
+        .map(|s| s.to_string())
+        .collect()
+    }
 }
 
 impl FunctionRegistry for IOxFunctionRegistry {
@@ -51,9 +63,14 @@ impl FunctionRegistry for IOxFunctionRegistry {
     }
 
     fn udaf(&self, name: &str) -> DataFusionResult<Arc<AggregateUDF>> {
-        Err(DataFusionError::Plan(format!(
-            "IOx FunctionRegistry does not contain user defined aggregate function '{name}'"
-        )))
+        match name {
+            hll::APPROX_DISTINCT_HLL_UDAF_NAME => Ok(hll::APPROX_DISTINCT_HLL.clone()),
+            hll::COUNT_HLL_UDAF_NAME => Ok(hll::COUNT_HLL.clone()),
+            hll::SUM_HLL_UDAF_NAME => Ok(hll::SUM_HLL.clone()),
+            _ => Err(DataFusionError::Plan(format!(
+                "IOx FunctionRegistry does not contain user defined aggregate function '{name}'"
+            ))),
+        }
     }
 
     fn udwf(&self, name: &str) -> DataFusionResult<Arc<WindowUDF>> {
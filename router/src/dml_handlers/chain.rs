@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use data_types::{NamespaceName, NamespaceSchema};
+use data_types::{DeletePredicate, NamespaceId, NamespaceName, NamespaceSchema, TableId};
 use std::sync::Arc;
 use trace::ctx::SpanContext;
 
@@ -54,6 +54,7 @@ where
     // All errors are converted into DML errors before returning to the caller
     // in order to present a consistent error type for chained handlers.
     type WriteError = DmlError;
+    type DeleteError = DmlError;
 
     /// Write `batches` to `namespace`.
     async fn write(
@@ -79,4 +80,31 @@ where
             .await
             .map_err(Into::into)
     }
+
+    /// Delete the data matching `predicate`, calling `second` only if `first`
+    /// succeeds.
+    async fn delete(
+        &self,
+        namespace: &NamespaceName<'static>,
+        namespace_id: NamespaceId,
+        table_id: TableId,
+        predicate: &DeletePredicate,
+        span_ctx: Option<SpanContext>,
+    ) -> Result<(), Self::DeleteError> {
+        self.first
+            .delete(
+                namespace,
+                namespace_id,
+                table_id,
+                predicate,
+                span_ctx.clone(),
+            )
+            .await
+            .map_err(Into::into)?;
+
+        self.second
+            .delete(namespace, namespace_id, table_id, predicate, span_ctx)
+            .await
+            .map_err(Into::into)
+    }
 }
@@ -1,7 +1,7 @@
 use std::{fmt::Debug, marker::PhantomData, sync::Arc};
 
 use async_trait::async_trait;
-use data_types::{NamespaceName, NamespaceSchema};
+use data_types::{DeletePredicate, NamespaceId, NamespaceName, NamespaceSchema, TableId};
 use futures::{stream::FuturesUnordered, TryStreamExt};
 use trace::ctx::SpanContext;
 
@@ -42,6 +42,7 @@ where
     type WriteInput = I;
     type WriteOutput = ();
     type WriteError = T::WriteError;
+    type DeleteError = T::DeleteError;
 
     /// Concurrently execute the write inputs in `input` against the inner
     /// handler, returning early and aborting in-flight writes if an error
@@ -70,4 +71,18 @@ where
             .await?;
         Ok(())
     }
+
+    /// Pass the delete through to the inner handler.
+    async fn delete(
+        &self,
+        namespace: &NamespaceName<'static>,
+        namespace_id: NamespaceId,
+        table_id: TableId,
+        predicate: &DeletePredicate,
+        span_ctx: Option<SpanContext>,
+    ) -> Result<(), Self::DeleteError> {
+        self.inner
+            .delete(namespace, namespace_id, table_id, predicate, span_ctx)
+            .await
+    }
 }
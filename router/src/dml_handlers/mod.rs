@@ -39,6 +39,10 @@
 //! to the catalog and populates the [`NamespaceCache`], converging it to match
 //! the set of [`NamespaceSchema`] in the global catalog.
 //!
+//! Deletes are passed through to the ingesters, which apply them to their
+//! buffered data, before the [`TombstoneRecorder`] records them in the catalog
+//! to be applied to persisted data.
+//!
 //! [`NamespaceCache`]: crate::namespace_cache::NamespaceCache
 //! [`NamespaceSchema`]: data_types::NamespaceSchema
 
@@ -68,5 +72,8 @@ pub use fan_out::*;
 mod rpc_write;
 pub use rpc_write::*;
 
+mod tombstone_recorder;
+pub use tombstone_recorder::*;
+
 #[cfg(test)]
 pub mod mock;
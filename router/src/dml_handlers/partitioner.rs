@@ -1,7 +1,7 @@
 use async_trait::async_trait;
 use data_types::{
-    partition_template::TablePartitionTemplateOverride, NamespaceName, NamespaceSchema,
-    PartitionKey, TableId,
+    partition_template::TablePartitionTemplateOverride, DeletePredicate, NamespaceId,
+    NamespaceName, NamespaceSchema, PartitionKey, TableId,
 };
 use hashbrown::HashMap;
 use mutable_batch::{MutableBatch, PartitionKeyError, PartitionWrite, WritePayload};
@@ -60,6 +60,7 @@ pub struct Partitioner {}
 #[async_trait]
 impl DmlHandler for Partitioner {
     type WriteError = PartitionError;
+    type DeleteError = PartitionError;
 
     type WriteInput = HashMap<TableId, (String, TablePartitionTemplateOverride, MutableBatch)>;
     type WriteOutput = Vec<Partitioned<HashMap<TableId, (String, MutableBatch)>>>;
@@ -99,6 +100,18 @@ impl DmlHandler for Partitioner {
             .map(|(key, batch)| Partitioned::new(key, batch))
             .collect::<Vec<_>>())
     }
+
+    /// Pass the delete request through unmodified to the next handler.
+    async fn delete(
+        &self,
+        _namespace: &NamespaceName<'static>,
+        _namespace_id: NamespaceId,
+        _table_id: TableId,
+        _predicate: &DeletePredicate,
+        _span_ctx: Option<SpanContext>,
+    ) -> Result<(), Self::DeleteError> {
+        Ok(())
+    }
 }
 
 #[cfg(test)]
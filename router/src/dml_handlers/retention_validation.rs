@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use data_types::{NamespaceName, NamespaceSchema};
+use data_types::{DeletePredicate, NamespaceId, NamespaceName, NamespaceSchema, TableId};
 use hashbrown::HashMap;
 use iox_time::{SystemProvider, TimeProvider};
 use mutable_batch::MutableBatch;
@@ -54,6 +54,7 @@ where
     P: TimeProvider,
 {
     type WriteError = RetentionError;
+    type DeleteError = RetentionError;
 
     type WriteInput = HashMap<String, MutableBatch>;
     type WriteOutput = Self::WriteInput;
@@ -85,6 +86,19 @@ where
 
         Ok(batch)
     }
+
+    /// Deletes are not subject to the retention period and are passed through
+    /// unmodified to the next handler.
+    async fn delete(
+        &self,
+        _namespace: &NamespaceName<'static>,
+        _namespace_id: NamespaceId,
+        _table_id: TableId,
+        _predicate: &DeletePredicate,
+        _span_ctx: Option<SpanContext>,
+    ) -> Result<(), Self::DeleteError> {
+        Ok(())
+    }
 }
 
 #[cfg(test)]
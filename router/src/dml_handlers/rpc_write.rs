@@ -11,10 +11,13 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
-use data_types::{NamespaceName, NamespaceSchema, TableId};
+use data_types::{DeletePredicate, NamespaceId, NamespaceName, NamespaceSchema, TableId};
 use dml::{DmlMeta, DmlWrite};
-use futures::{stream::FuturesUnordered, StreamExt};
-use generated_types::influxdata::iox::ingester::v1::WriteRequest;
+use futures::{stream::FuturesUnordered, StreamExt, TryStreamExt};
+use generated_types::influxdata::iox::{
+    delete::v1::DeletePayload,
+    ingester::v1::{DeleteRequest, WriteRequest},
+};
 use hashbrown::HashMap;
 use mutable_batch::MutableBatch;
 use mutable_batch_pb::encode::encode_write;
@@ -92,8 +95,10 @@ pub enum RpcWriteError {
 ///
 /// # Deletes
 ///
-/// This handler drops delete requests, logging the attempt and returning an
-/// error to the client.
+/// Deletes are sent to every configured upstream ingester, irrespective of
+/// their health state, as any of them may be buffering data matching the
+/// delete predicate. A delete is successful only if all upstreams acknowledge
+/// it.
 ///
 /// [gRPC write service]: client::WriteClient
 #[derive(Debug)]
@@ -176,6 +181,7 @@ where
     type WriteOutput = Vec<DmlMeta>;
 
     type WriteError = RpcWriteError;
+    type DeleteError = RpcWriteError;
 
     async fn write(
         &self,
@@ -299,6 +305,56 @@ where
 
         Ok(vec![op.meta().clone()])
     }
+
+    async fn delete(
+        &self,
+        namespace: &NamespaceName<'static>,
+        namespace_id: NamespaceId,
+        table_id: TableId,
+        predicate: &DeletePredicate,
+        span_ctx: Option<SpanContext>,
+    ) -> Result<(), RpcWriteError> {
+        let req = DeleteRequest {
+            payload: Some(DeletePayload {
+                database_id: namespace_id.get(),
+                table_name: String::new(),
+                table_id: table_id.get(),
+                predicate: Some(predicate.into()),
+            }),
+        };
+
+        // Each upstream is sent the delete exactly once, bounded by
+        // RPC_TIMEOUT - the client is expected to retry the (idempotent)
+        // delete if any upstream fails to apply it.
+        self.endpoints
+            .all()
+            .map(|client| {
+                let req = req.clone();
+                let span_ctx = span_ctx.clone();
+                async move {
+                    tokio::time::timeout(RPC_TIMEOUT, client.delete(req, span_ctx))
+                        .await
+                        .map_err(RpcWriteError::Timeout)?
+                        .map_err(|e| {
+                            warn!(error=%e, "failed ingester rpc delete");
+                            RpcWriteError::Client(e)
+                        })
+                }
+            })
+            .collect::<FuturesUnordered<_>>()
+            .try_collect::<()>()
+            .await?;
+
+        debug!(
+            %namespace,
+            %namespace_id,
+            %table_id,
+            ?predicate,
+            "dispatched delete to ingesters"
+        );
+
+        Ok(())
+    }
 }
 
 /// Perform an RPC write with `req` against one of the upstream ingesters in
@@ -509,6 +565,86 @@ mod tests {
         assert_eq!(got_tables, want_tables);
     }
 
+    /// Deletes are sent to all upstreams, irrespective of the replication
+    /// factor.
+    #[tokio::test]
+    async fn test_delete_all_upstreams() {
+        let client_1 = Arc::new(MockWriteClient::default());
+        let client_2 = Arc::new(MockWriteClient::default());
+
+        let handler = RpcWrite::new(
+            [
+                (Arc::clone(&client_1), "client_1"),
+                (Arc::clone(&client_2), "client_2"),
+            ],
+            1.try_into().unwrap(),
+            &metric::Registry::default(),
+            ARBITRARY_TEST_NUM_PROBES,
+        );
+
+        let predicate = DeletePredicate {
+            range: data_types::TimestampRange::new(1, 2),
+            exprs: vec![],
+        };
+
+        handler
+            .delete(
+                &NamespaceName::new(NAMESPACE_NAME).unwrap(),
+                NAMESPACE_ID,
+                TableId::new(42),
+                &predicate,
+                None,
+            )
+            .await
+            .expect("delete should succeed");
+
+        for client in [client_1, client_2] {
+            let call = {
+                let mut calls = client.delete_calls();
+                assert_eq!(calls.len(), 1);
+                calls.pop().unwrap()
+            };
+            let payload = assert_matches!(call.payload, Some(p) => p);
+            assert_eq!(payload.database_id, NAMESPACE_ID.get());
+            assert_eq!(payload.table_id, 42);
+            assert_eq!(
+                DeletePredicate::try_from(payload.predicate.unwrap()).unwrap(),
+                predicate
+            );
+        }
+    }
+
+    /// A delete fails if any upstream fails to apply it.
+    #[tokio::test]
+    async fn test_delete_upstream_error() {
+        let client_1 = Arc::new(MockWriteClient::default());
+        let client_2 = Arc::new(MockWriteClient::default().with_ret(iter::once(Err(
+            RpcWriteClientError::Upstream(tonic::Status::internal("bananas")),
+        ))));
+
+        let handler = RpcWrite::new(
+            [(client_1, "client_1"), (client_2, "client_2")],
+            1.try_into().unwrap(),
+            &metric::Registry::default(),
+            ARBITRARY_TEST_NUM_PROBES,
+        );
+
+        let got = handler
+            .delete(
+                &NamespaceName::new(NAMESPACE_NAME).unwrap(),
+                NAMESPACE_ID,
+                TableId::new(42),
+                &DeletePredicate {
+                    range: data_types::TimestampRange::new(1, 2),
+                    exprs: vec![],
+                },
+                None,
+            )
+            .await;
+
+        assert_matches!(got, Err(RpcWriteError::Client(_)));
+    }
+
     /// Ensure all candidates returned by the balancer are tried, aborting after
     /// the first successful request.
     #[tokio::test]
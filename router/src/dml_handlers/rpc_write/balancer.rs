@@ -66,6 +66,12 @@ where
         self.endpoints.len()
     }
 
+    /// Returns all configured upstream endpoints, irrespective of their
+    /// health state.
+    pub(super) fn all(&self) -> impl Iterator<Item = &Arc<CircuitBreakingClient<T, C>>> {
+        self.endpoints.iter()
+    }
+
     /// Return an (infinite) iterator of healthy [`CircuitBreakingClient`], and
     /// at most one client needing a health probe.
     ///
@@ -4,7 +4,7 @@ use std::{fmt::Debug, sync::Arc};
 
 use async_trait::async_trait;
 use generated_types::influxdata::iox::ingester::v1::{
-    write_service_client::WriteServiceClient, WriteRequest,
+    write_service_client::WriteServiceClient, DeleteRequest, WriteRequest,
 };
 use thiserror::Error;
 use trace::ctx::SpanContext;
@@ -40,6 +40,13 @@ pub(super) trait WriteClient: Send + Sync + std::fmt::Debug {
         op: WriteRequest,
         span_ctx: Option<SpanContext>,
     ) -> Result<(), RpcWriteClientError>;
+
+    /// Apply the delete `op` and wait for a response.
+    async fn delete(
+        &self,
+        op: DeleteRequest,
+        span_ctx: Option<SpanContext>,
+    ) -> Result<(), RpcWriteClientError>;
 }
 
 #[async_trait]
@@ -54,6 +61,14 @@ where
     ) -> Result<(), RpcWriteClientError> {
         (**self).write(op, span_ctx).await
     }
+
+    async fn delete(
+        &self,
+        op: DeleteRequest,
+        span_ctx: Option<SpanContext>,
+    ) -> Result<(), RpcWriteClientError> {
+        (**self).delete(op, span_ctx).await
+    }
 }
 
 #[derive(Debug)]
@@ -89,6 +104,20 @@ impl<'a> WriteClient for TracePropagatingWriteClient<'a> {
         WriteServiceClient::write(&mut self.inner.clone(), req).await?;
         Ok(())
     }
+
+    async fn delete(
+        &self,
+        op: DeleteRequest,
+        span_ctx: Option<SpanContext>,
+    ) -> Result<(), RpcWriteClientError> {
+        let req = decorate_request_with_span_context(
+            tonic::Request::new(op),
+            self.trace_context_header_name,
+            span_ctx,
+        )?;
+        WriteServiceClient::delete(&mut self.inner.clone(), req).await?;
+        Ok(())
+    }
 }
 
 fn decorate_request_with_span_context<T>(
@@ -156,6 +185,7 @@ pub mod mock {
 
     struct State {
         calls: Vec<WriteRequest>,
+        delete_calls: Vec<DeleteRequest>,
         ret: Box<dyn Iterator<Item = Result<(), RpcWriteClientError>> + Send + Sync>,
         returned_oks: usize,
     }
@@ -179,6 +209,7 @@ pub mod mock {
             Self {
                 state: Mutex::new(State {
                     calls: Default::default(),
+                    delete_calls: Default::default(),
                     ret: Box::new(iter::repeat_with(|| Ok(()))),
                     returned_oks: 0,
                 }),
@@ -192,6 +223,11 @@ pub mod mock {
             self.state.lock().calls.clone()
         }
 
+        /// Retrieve the delete requests that this mock received.
+        pub fn delete_calls(&self) -> Vec<DeleteRequest> {
+            self.state.lock().delete_calls.clone()
+        }
+
         /// Retrieve the number of times this mock returned [`Ok`] to a write
         /// request.
         pub fn success_count(&self) -> usize {
@@ -199,7 +235,7 @@ pub mod mock {
         }
 
         /// Read values off of the provided iterator and return them for calls
-        /// to [`Self::write()`].
+        /// to [`Self::write()`] and [`Self::delete()`].
         #[cfg(test)]
         pub(crate) fn with_ret<T, U>(self, ret: T) -> Self
         where
@@ -229,5 +265,22 @@ pub mod mock {
 
             ret
         }
+
+        async fn delete(
+            &self,
+            op: DeleteRequest,
+            _span_ctx: Option<SpanContext>,
+        ) -> Result<(), RpcWriteClientError> {
+            let mut guard = self.state.lock();
+            guard.delete_calls.push(op);
+
+            let ret = guard.ret.next().expect("no mock response");
+
+            if ret.is_ok() {
+                guard.returned_oks += 1;
+            }
+
+            ret
+        }
     }
 }
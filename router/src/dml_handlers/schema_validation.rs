@@ -2,7 +2,8 @@ use std::{ops::DerefMut, sync::Arc};
 
 use async_trait::async_trait;
 use data_types::{
-    partition_template::TablePartitionTemplateOverride, NamespaceName, NamespaceSchema, TableId,
+    partition_template::TablePartitionTemplateOverride, DeletePredicate, NamespaceId,
+    NamespaceName, NamespaceSchema, TableId,
 };
 use hashbrown::HashMap;
 use iox_catalog::{
@@ -143,6 +144,7 @@ where
     C: NamespaceCache<ReadError = iox_catalog::interface::Error>, // The handler expects the cache to read from the catalog if necessary.
 {
     type WriteError = SchemaError;
+    type DeleteError = SchemaError;
 
     // Accepts a map of TableName -> MutableBatch
     type WriteInput = HashMap<String, MutableBatch>;
@@ -302,6 +304,19 @@ where
 
         Ok(batches)
     }
+
+    /// This call is passed through to the inner handler - no schema validation
+    /// is performed for deletes.
+    async fn delete(
+        &self,
+        _namespace: &NamespaceName<'static>,
+        _namespace_id: NamespaceId,
+        _table_id: TableId,
+        _predicate: &DeletePredicate,
+        _span_ctx: Option<SpanContext>,
+    ) -> Result<(), Self::DeleteError> {
+        Ok(())
+    }
 }
 
 /// An error returned by schema limit evaluation against a cached
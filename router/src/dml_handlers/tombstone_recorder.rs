@@ -0,0 +1,157 @@
+//! A [`DmlHandler`] recording predicate deletes in the catalog.
+
+use std::{fmt::Debug, marker::PhantomData, sync::Arc};
+
+use async_trait::async_trait;
+use data_types::{DeletePredicate, NamespaceId, NamespaceName, NamespaceSchema, TableId};
+use iox_catalog::interface::Catalog;
+use observability_deps::tracing::*;
+use thiserror::Error;
+use trace::ctx::SpanContext;
+
+use super::DmlHandler;
+
+/// Errors emitted when recording a delete tombstone.
+#[derive(Debug, Error)]
+pub enum TombstoneError {
+    /// The catalog returned an error when creating the tombstone.
+    #[error("failed to record delete: {0}")]
+    Catalog(#[from] iox_catalog::interface::Error),
+}
+
+/// A [`TombstoneRecorder`] records deletes as tombstones in the catalog,
+/// causing them to be applied to persisted data by the querier and compactor.
+///
+/// Writes are passed through unmodified.
+///
+/// # Ordering
+///
+/// This handler is expected to be the last in the handler chain, after the
+/// delete has been applied to the data buffered in the ingesters. Recording
+/// the tombstone last ensures the tombstone covers all data persisted before
+/// it was created, including data that was buffered in an ingester when the
+/// delete was accepted.
+#[derive(Debug)]
+pub struct TombstoneRecorder<T> {
+    catalog: Arc<dyn Catalog>,
+    _input: PhantomData<T>,
+}
+
+impl<T> TombstoneRecorder<T> {
+    /// Initialise a new [`TombstoneRecorder`] recording tombstones in
+    /// `catalog`.
+    pub fn new(catalog: Arc<dyn Catalog>) -> Self {
+        Self {
+            catalog,
+            _input: PhantomData,
+        }
+    }
+}
+
+#[async_trait]
+impl<T> DmlHandler for TombstoneRecorder<T>
+where
+    T: Debug + Send + Sync,
+{
+    type WriteError = TombstoneError;
+    type DeleteError = TombstoneError;
+    type WriteInput = T;
+    type WriteOutput = T;
+
+    /// Pass the write through unmodified.
+    async fn write(
+        &self,
+        _namespace: &NamespaceName<'static>,
+        _namespace_schema: Arc<NamespaceSchema>,
+        input: Self::WriteInput,
+        _span_ctx: Option<SpanContext>,
+    ) -> Result<Self::WriteOutput, Self::WriteError> {
+        Ok(input)
+    }
+
+    /// Record a tombstone for `predicate` against `table_id`.
+    async fn delete(
+        &self,
+        namespace: &NamespaceName<'static>,
+        namespace_id: NamespaceId,
+        table_id: TableId,
+        predicate: &DeletePredicate,
+        _span_ctx: Option<SpanContext>,
+    ) -> Result<(), Self::DeleteError> {
+        let tombstone = self
+            .catalog
+            .repositories()
+            .await
+            .tombstones()
+            .create(table_id, predicate)
+            .await?;
+
+        debug!(
+            %namespace,
+            %namespace_id,
+            %table_id,
+            tombstone_id=%tombstone.id,
+            "recorded delete tombstone"
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use data_types::{DeleteExpr, Op, Scalar, TimestampRange};
+    use iox_catalog::mem::MemCatalog;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_delete_records_tombstone() {
+        let metrics = Arc::new(metric::Registry::default());
+        let catalog: Arc<dyn Catalog> = Arc::new(MemCatalog::new(metrics));
+        let ns = iox_catalog::test_helpers::arbitrary_namespace(
+            &mut *catalog.repositories().await,
+            "bananas",
+        )
+        .await;
+        let table = iox_catalog::test_helpers::arbitrary_table(
+            &mut *catalog.repositories().await,
+            "platanos",
+            &ns,
+        )
+        .await;
+
+        let predicate = DeletePredicate {
+            range: TimestampRange::new(1, 42),
+            exprs: vec![DeleteExpr::new(
+                "region".to_string(),
+                Op::Eq,
+                Scalar::String("west".to_string()),
+            )],
+        };
+
+        let handler = TombstoneRecorder::<()>::new(Arc::clone(&catalog));
+        handler
+            .delete(
+                &NamespaceName::new("bananas").unwrap(),
+                ns.id,
+                table.id,
+                &predicate,
+                None,
+            )
+            .await
+            .expect("delete should succeed");
+
+        let got = catalog
+            .repositories()
+            .await
+            .tombstones()
+            .list_by_table_id(table.id)
+            .await
+            .unwrap();
+        assert_eq!(got.len(), 1);
+        assert_eq!(got[0].table_id, table.id);
+        assert_eq!(got[0].min_time.get(), 1);
+        assert_eq!(got[0].max_time.get(), 42);
+    }
+}
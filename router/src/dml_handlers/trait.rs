@@ -1,8 +1,9 @@
 use super::{
     partitioner::PartitionError, retention_validation::RetentionError, RpcWriteError, SchemaError,
+    TombstoneError,
 };
 use async_trait::async_trait;
-use data_types::{NamespaceName, NamespaceSchema};
+use data_types::{DeletePredicate, NamespaceId, NamespaceName, NamespaceSchema, TableId};
 use std::{error::Error, fmt::Debug, sync::Arc};
 use thiserror::Error;
 use trace::ctx::SpanContext;
@@ -32,6 +33,10 @@ pub enum DmlError {
     #[error(transparent)]
     Retention(#[from] RetentionError),
 
+    /// An error recording a delete tombstone.
+    #[error(transparent)]
+    Tombstone(#[from] TombstoneError),
+
     /// An unknown error occured while processing the DML request.
     #[error("internal dml handler error: {0}")]
     Internal(Box<dyn Error + Send + Sync>),
@@ -57,6 +62,12 @@ pub trait DmlHandler: Debug + Send + Sync {
     /// All errors must be mappable into the concrete [`DmlError`] type.
     type WriteError: Error + Into<DmlError> + Send;
 
+    /// The type of error a [`DmlHandler`] implementation produces for delete
+    /// requests.
+    ///
+    /// All errors must be mappable into the concrete [`DmlError`] type.
+    type DeleteError: Error + Into<DmlError> + Send;
+
     /// Write `batches` to `namespace`.
     async fn write(
         &self,
@@ -65,6 +76,17 @@ pub trait DmlHandler: Debug + Send + Sync {
         input: Self::WriteInput,
         span_ctx: Option<SpanContext>,
     ) -> Result<Self::WriteOutput, Self::WriteError>;
+
+    /// Delete the data matching `predicate` from the table identified by
+    /// `table_id` in `namespace`.
+    async fn delete(
+        &self,
+        namespace: &NamespaceName<'static>,
+        namespace_id: NamespaceId,
+        table_id: TableId,
+        predicate: &DeletePredicate,
+        span_ctx: Option<SpanContext>,
+    ) -> Result<(), Self::DeleteError>;
 }
 
 #[async_trait]
@@ -75,6 +97,7 @@ where
     type WriteInput = T::WriteInput;
     type WriteOutput = T::WriteOutput;
     type WriteError = T::WriteError;
+    type DeleteError = T::DeleteError;
 
     async fn write(
         &self,
@@ -87,4 +110,17 @@ where
             .write(namespace, namespace_schema, input, span_ctx)
             .await
     }
+
+    async fn delete(
+        &self,
+        namespace: &NamespaceName<'static>,
+        namespace_id: NamespaceId,
+        table_id: TableId,
+        predicate: &DeletePredicate,
+        span_ctx: Option<SpanContext>,
+    ) -> Result<(), Self::DeleteError> {
+        (**self)
+            .delete(namespace, namespace_id, table_id, predicate, span_ctx)
+            .await
+    }
 }
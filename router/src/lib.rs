@@ -4,7 +4,7 @@
 //!
 //! * Creating IOx namespaces & synchronising them within the catalog.
 //! * Handling writes:
-//!     * Receiving IOx write/delete requests via HTTP
+//!     * Receiving IOx write/delete requests via HTTP and gRPC
 //!     * Creating or validating the write's namespace
 //!     * Validating write payloads are within the configured retention period
 //!     * Enforcing schema validation & synchronising it within the catalog
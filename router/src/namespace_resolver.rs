@@ -35,6 +35,19 @@ pub trait NamespaceResolver: std::fmt::Debug + Send + Sync {
     ) -> Result<Arc<NamespaceSchema>, Error>;
 }
 
+#[async_trait]
+impl<T> NamespaceResolver for Arc<T>
+where
+    T: NamespaceResolver,
+{
+    async fn get_namespace_schema(
+        &self,
+        namespace: &NamespaceName<'static>,
+    ) -> Result<Arc<NamespaceSchema>, Error> {
+        (**self).get_namespace_schema(namespace).await
+    }
+}
+
 /// An implementation of [`NamespaceResolver`] that resolves the [`NamespaceSchema`]
 /// for a given name through a [`NamespaceCache`].
 #[derive(Debug)]
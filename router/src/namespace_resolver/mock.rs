@@ -5,7 +5,10 @@
 use std::{collections::HashMap, sync::Arc};
 
 use async_trait::async_trait;
-use data_types::{NamespaceId, NamespaceName, NamespaceSchema};
+use data_types::{
+    partition_template::TablePartitionTemplateOverride, ColumnsByName, NamespaceId, NamespaceName,
+    NamespaceSchema, TableId, TableSchema,
+};
 use parking_lot::Mutex;
 
 use super::NamespaceResolver;
@@ -34,6 +37,25 @@ impl MockNamespaceResolver {
             .is_none());
         self
     }
+
+    /// Add an empty table named `table` with the given `id` to the existing
+    /// schema for `namespace`.
+    pub fn with_table(self, namespace: &str, table: impl Into<String>, id: TableId) -> Self {
+        let name = NamespaceName::try_from(namespace.to_string()).unwrap();
+        {
+            let mut map = self.map.lock();
+            let schema = Arc::make_mut(map.get_mut(&name).expect("namespace not mapped"));
+            schema.tables.insert(
+                table.into(),
+                TableSchema {
+                    id,
+                    partition_template: TablePartitionTemplateOverride::default(),
+                    columns: ColumnsByName::new([]),
+                },
+            );
+        }
+        self
+    }
 }
 
 #[async_trait]
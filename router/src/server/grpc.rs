@@ -1,9 +1,23 @@
 //! gRPC service implementations for `router`.
 
-use generated_types::influxdata::iox::{
-    catalog::v1::*, namespace::v1::*, object_store::v1::*, table::v1::*,
+mod delete;
+mod flight;
+mod otlp;
+
+use arrow_flight::flight_service_server::FlightServiceServer;
+use authz::{extract_token, Action, Authorizer, Permission, Resource};
+use data_types::NamespaceName;
+use generated_types::{
+    influxdata::iox::{
+        catalog::v1::*, delete::v1::*, namespace::v1::*, object_store::v1::*, table::v1::*,
+    },
+    opentelemetry::proto::collector::metrics::v1::metrics_service_server::{
+        MetricsService, MetricsServiceServer,
+    },
 };
+use hashbrown::HashMap;
 use iox_catalog::interface::Catalog;
+use mutable_batch::MutableBatch;
 use object_store::DynObjectStore;
 use service_grpc_catalog::CatalogService;
 use service_grpc_namespace::NamespaceService;
@@ -11,20 +25,36 @@ use service_grpc_object_store::ObjectStoreService;
 use service_grpc_schema::SchemaService;
 use service_grpc_table::TableService;
 use std::sync::Arc;
+use tonic::{metadata::MetadataMap, Status};
+
+use self::{delete::DeleteService, flight::FlightIngestService, otlp::OtlpMetricsService};
+use crate::{
+    dml_handlers::{DmlError, DmlHandler, RetentionError, RpcWriteError, SchemaError},
+    namespace_resolver::{self, NamespaceCreationError, NamespaceResolver},
+};
 
 /// This type manages all gRPC services exposed by a `router` using the RPC write path.
 #[derive(Debug)]
 pub struct RpcWriteGrpcDelegate {
     catalog: Arc<dyn Catalog>,
     object_store: Arc<DynObjectStore>,
+    authz: Option<Arc<dyn Authorizer>>,
 }
 
 impl RpcWriteGrpcDelegate {
-    /// Create a new gRPC handler
-    pub fn new(catalog: Arc<dyn Catalog>, object_store: Arc<DynObjectStore>) -> Self {
+    /// Create a new gRPC handler.
+    ///
+    /// If `authz` is provided, the services accepting writes and deletes
+    /// require the caller to be authorized to write to the target namespace.
+    pub fn new(
+        catalog: Arc<dyn Catalog>,
+        object_store: Arc<DynObjectStore>,
+        authz: Option<Arc<dyn Authorizer>>,
+    ) -> Self {
         Self {
             catalog,
             object_store,
+            authz,
         }
     }
 
@@ -62,4 +92,125 @@ impl RpcWriteGrpcDelegate {
     pub fn table_service(&self) -> impl table_service_server::TableService {
         TableService::new(Arc::clone(&self.catalog))
     }
+
+    /// Acquire a [`DeleteService`] gRPC service implementation, applying
+    /// deletes through `dml_handler`.
+    ///
+    /// If authorization is configured, deletes require the caller to be
+    /// authorized to write to the target namespace.
+    ///
+    /// [`DeleteService`]: generated_types::influxdata::iox::delete::v1::delete_service_server::DeleteService
+    pub fn delete_service<D>(&self, dml_handler: D) -> impl delete_service_server::DeleteService
+    where
+        D: DmlHandler + 'static,
+    {
+        DeleteService::new(Arc::clone(&self.catalog), dml_handler, self.authz.clone())
+    }
+
+    /// Acquire an Arrow Flight [`FlightService`] gRPC service implementation
+    /// accepting bulk writes of Arrow record batches via `DoPut`.
+    ///
+    /// Writes are resolved to a namespace with `namespace_resolver` and
+    /// applied through `dml_handler`. The data in a single `DoPut` request is
+    /// limited to `max_request_bytes`.
+    ///
+    /// [`FlightService`]: arrow_flight::flight_service_server::FlightService
+    pub fn flight_service<D, N>(
+        &self,
+        namespace_resolver: N,
+        dml_handler: D,
+        max_request_bytes: usize,
+    ) -> FlightServiceServer<impl arrow_flight::flight_service_server::FlightService>
+    where
+        D: DmlHandler<WriteInput = HashMap<String, MutableBatch>, WriteOutput = ()> + 'static,
+        N: NamespaceResolver + 'static,
+    {
+        FlightServiceServer::new(FlightIngestService::new(
+            namespace_resolver,
+            dml_handler,
+            max_request_bytes,
+            self.authz.clone(),
+        ))
+    }
+
+    /// Acquire an OpenTelemetry OTLP [`MetricsService`] gRPC service
+    /// implementation accepting exported metrics.
+    ///
+    /// Metrics are resolved to a namespace with `namespace_resolver` and
+    /// applied through `dml_handler`. The converted data in a single export
+    /// request is limited to `max_request_bytes`.
+    pub fn otlp_metrics_service<D, N>(
+        &self,
+        namespace_resolver: N,
+        dml_handler: D,
+        max_request_bytes: usize,
+    ) -> MetricsServiceServer<impl MetricsService>
+    where
+        D: DmlHandler<WriteInput = HashMap<String, MutableBatch>, WriteOutput = ()> + 'static,
+        N: NamespaceResolver + 'static,
+    {
+        MetricsServiceServer::new(OtlpMetricsService::new(
+            namespace_resolver,
+            dml_handler,
+            max_request_bytes,
+            self.authz.clone(),
+        ))
+    }
+}
+
+/// Authorize a write to `namespace`, using the token in the `authorization`
+/// request `metadata`.
+///
+/// Writes are always permitted when no `authz` service is configured.
+async fn authorize_write(
+    authz: Option<&Arc<dyn Authorizer>>,
+    metadata: &MetadataMap,
+    namespace: &NamespaceName<'_>,
+) -> Result<(), Status> {
+    let Some(authz) = authz else {
+        return Ok(());
+    };
+
+    let token = extract_token(metadata.get("authorization"));
+    let perms = [Permission::ResourceAction(
+        Resource::Database(namespace.to_string()),
+        Action::Write,
+    )];
+
+    authz
+        .permissions(token, &perms)
+        .await
+        .map(|_| ())
+        .map_err(|e| match &e {
+            authz::Error::NoToken => Status::unauthenticated("Unauthenticated"),
+            authz::Error::Forbidden | authz::Error::InvalidToken => {
+                Status::permission_denied("Permission denied")
+            }
+            authz::Error::Verification { .. } => Status::unavailable(e.to_string()),
+        })
+}
+
+/// Map a [`DmlError`] to the equivalent gRPC [`Status`].
+fn status_from_dml_error(e: DmlError) -> Status {
+    match e {
+        DmlError::NamespaceNotFound(_) => Status::not_found(e.to_string()),
+        DmlError::Schema(SchemaError::ServiceLimit(_) | SchemaError::Conflict(_))
+        | DmlError::Retention(RetentionError::OutsideRetention { .. }) => {
+            Status::invalid_argument(e.to_string())
+        }
+        DmlError::RpcWrite(RpcWriteError::Timeout(_)) => Status::deadline_exceeded(e.to_string()),
+        DmlError::RpcWrite(_) => Status::unavailable(e.to_string()),
+        _ => Status::internal(e.to_string()),
+    }
+}
+
+/// Map a [`namespace_resolver::Error`] to the equivalent gRPC [`Status`].
+fn status_from_namespace_error(e: namespace_resolver::Error) -> Status {
+    match e {
+        // Autocreation is disabled and the namespace does not exist.
+        namespace_resolver::Error::Create(NamespaceCreationError::Reject(_)) => {
+            Status::not_found(e.to_string())
+        }
+        _ => Status::internal(e.to_string()),
+    }
 }
@@ -0,0 +1,323 @@
+//! The [`DeleteService`] gRPC implementation, passing deletes through the
+//! router [`DmlHandler`] stack.
+//!
+//! When an authorization service is configured, the token in the
+//! `authorization` request header must grant write access to the namespace.
+//!
+//! [`DeleteService`]: delete_service_server::DeleteService
+
+use std::sync::Arc;
+
+use authz::Authorizer;
+use data_types::{DeletePredicate, NamespaceId, NamespaceName, TableId};
+use generated_types::influxdata::iox::delete::v1::*;
+use iox_catalog::interface::{Catalog, SoftDeletedRows};
+use observability_deps::tracing::*;
+use tonic::{Request, Response, Status};
+use trace::ctx::SpanContext;
+
+use super::{authorize_write, status_from_dml_error};
+use crate::dml_handlers::{DmlError, DmlHandler};
+
+/// Implementation of the delete gRPC service.
+#[derive(Debug)]
+pub(crate) struct DeleteService<D> {
+    catalog: Arc<dyn Catalog>,
+    dml_handler: D,
+    authz: Option<Arc<dyn Authorizer>>,
+}
+
+impl<D> DeleteService<D> {
+    /// Initialise a new [`DeleteService`] resolving table names using
+    /// `catalog`, and applying deletes with `dml_handler`.
+    ///
+    /// If `authz` is provided, deletes are only accepted from callers
+    /// authorized to write to the target namespace.
+    pub(crate) fn new(
+        catalog: Arc<dyn Catalog>,
+        dml_handler: D,
+        authz: Option<Arc<dyn Authorizer>>,
+    ) -> Self {
+        Self {
+            catalog,
+            dml_handler,
+            authz,
+        }
+    }
+}
+
+impl<D> DeleteService<D>
+where
+    D: DmlHandler,
+{
+    /// Resolve the name of the namespace targeted by a delete.
+    async fn namespace_name(
+        &self,
+        namespace_id: NamespaceId,
+    ) -> Result<NamespaceName<'static>, Status> {
+        let namespace = self
+            .catalog
+            .repositories()
+            .await
+            .namespaces()
+            .get_by_id(namespace_id, SoftDeletedRows::ExcludeDeleted)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .ok_or_else(|| {
+                Status::not_found(format!("Could not find a namespace with id {namespace_id}"))
+            })?;
+
+        NamespaceName::try_from(namespace.name).map_err(|e| Status::internal(e.to_string()))
+    }
+
+    /// Resolve the IDs of the tables targeted by a delete.
+    ///
+    /// If `table_name` is empty, all tables in the namespace are returned.
+    async fn table_ids(
+        &self,
+        namespace_id: NamespaceId,
+        namespace_name: &NamespaceName<'static>,
+        table_name: &str,
+    ) -> Result<Vec<TableId>, Status> {
+        let mut repos = self.catalog.repositories().await;
+
+        let table_ids = if table_name.is_empty() {
+            repos
+                .tables()
+                .list_by_namespace_id(namespace_id)
+                .await
+                .map_err(|e| Status::internal(e.to_string()))?
+                .into_iter()
+                .map(|t| t.id)
+                .collect()
+        } else {
+            let table = repos
+                .tables()
+                .get_by_namespace_and_name(namespace_id, table_name)
+                .await
+                .map_err(|e| Status::internal(e.to_string()))?
+                .ok_or_else(|| {
+                    Status::not_found(format!(
+                        "Could not find a table with name {table_name} in namespace {namespace_name}"
+                    ))
+                })?;
+            vec![table.id]
+        };
+
+        Ok(table_ids)
+    }
+}
+
+#[tonic::async_trait]
+impl<D> delete_service_server::DeleteService for DeleteService<D>
+where
+    D: DmlHandler + 'static,
+{
+    async fn delete(
+        &self,
+        request: Request<DeleteRequest>,
+    ) -> Result<Response<DeleteResponse>, Status> {
+        let span_ctx: Option<SpanContext> = request.extensions().get().cloned();
+        let metadata = request.metadata().clone();
+
+        let payload = request
+            .into_inner()
+            .payload
+            .ok_or_else(|| Status::invalid_argument("no delete payload provided"))?;
+
+        let predicate = payload
+            .predicate
+            .ok_or_else(|| Status::invalid_argument("no delete predicate provided"))?;
+        let predicate = DeletePredicate::try_from(predicate)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        let namespace_id = NamespaceId::new(payload.database_id);
+        let namespace = self.namespace_name(namespace_id).await?;
+
+        // Reject unauthorized deletes before the tables are resolved.
+        authorize_write(self.authz.as_ref(), &metadata, &namespace).await?;
+
+        let table_ids = self
+            .table_ids(namespace_id, &namespace, &payload.table_name)
+            .await?;
+
+        debug!(
+            %namespace,
+            %namespace_id,
+            table_name=%payload.table_name,
+            n_tables=table_ids.len(),
+            ?predicate,
+            "routing grpc delete",
+        );
+
+        for table_id in table_ids {
+            self.dml_handler
+                .delete(
+                    &namespace,
+                    namespace_id,
+                    table_id,
+                    &predicate,
+                    span_ctx.clone(),
+                )
+                .await
+                .map_err(|e| {
+                    let e: DmlError = e.into();
+                    warn!(error=%e, %namespace, %table_id, "failed to apply delete");
+                    status_from_dml_error(e)
+                })?;
+        }
+
+        Ok(Response::new(DeleteResponse {}))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use data_types::TimestampRange;
+    use delete_service_server::DeleteService as _;
+    use iox_catalog::{
+        mem::MemCatalog,
+        test_helpers::{arbitrary_namespace, arbitrary_table},
+    };
+
+    use super::*;
+    use crate::{
+        dml_handlers::mock::{MockDmlHandler, MockDmlHandlerCall},
+        server::http::write::single_tenant::auth::mock::{
+            MockAuthorizer, MOCK_AUTH_NO_PERMS_TOKEN, MOCK_AUTH_VALID_TOKEN,
+        },
+    };
+
+    fn predicate() -> DeletePredicate {
+        DeletePredicate {
+            range: TimestampRange::new(1, 2),
+            exprs: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delete() {
+        let catalog: Arc<dyn Catalog> =
+            Arc::new(MemCatalog::new(Arc::new(metric::Registry::default())));
+        let (ns, table) = {
+            let mut repos = catalog.repositories().await;
+            let ns = arbitrary_namespace(&mut *repos, "bananas").await;
+            let table = arbitrary_table(&mut *repos, "platanos", &ns).await;
+            (ns, table)
+        };
+
+        let handler = Arc::new(MockDmlHandler::<()>::default().with_delete_return([Ok(())]));
+        let service = DeleteService::new(Arc::clone(&catalog), Arc::clone(&handler), None);
+
+        service
+            .delete(Request::new(DeleteRequest {
+                payload: Some(DeletePayload {
+                    database_id: ns.id.get(),
+                    table_name: "platanos".to_string(),
+                    predicate: Some((&predicate()).into()),
+                    table_id: 0,
+                }),
+            }))
+            .await
+            .expect("delete should succeed");
+
+        assert_matches!(
+            handler.calls().as_slice(),
+            [MockDmlHandlerCall::Delete { namespace, namespace_id, table_id, predicate: got }] => {
+                assert_eq!(namespace, "bananas");
+                assert_eq!(*namespace_id, ns.id);
+                assert_eq!(*table_id, table.id);
+                assert_eq!(*got, predicate());
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_delete_table_not_found() {
+        let catalog: Arc<dyn Catalog> =
+            Arc::new(MemCatalog::new(Arc::new(metric::Registry::default())));
+        let ns = arbitrary_namespace(&mut *catalog.repositories().await, "bananas").await;
+
+        let handler = Arc::new(MockDmlHandler::<()>::default());
+        let service = DeleteService::new(Arc::clone(&catalog), Arc::clone(&handler), None);
+
+        let err = service
+            .delete(Request::new(DeleteRequest {
+                payload: Some(DeletePayload {
+                    database_id: ns.id.get(),
+                    table_name: "platanos".to_string(),
+                    predicate: Some((&predicate()).into()),
+                    table_id: 0,
+                }),
+            }))
+            .await
+            .expect_err("delete should fail");
+
+        assert_eq!(err.code(), tonic::Code::NotFound);
+        assert!(handler.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_delete_authz() {
+        let catalog: Arc<dyn Catalog> =
+            Arc::new(MemCatalog::new(Arc::new(metric::Registry::default())));
+        let ns = {
+            let mut repos = catalog.repositories().await;
+            let ns = arbitrary_namespace(&mut *repos, "bananas").await;
+            arbitrary_table(&mut *repos, "platanos", &ns).await;
+            ns
+        };
+
+        let handler = Arc::new(MockDmlHandler::<()>::default().with_delete_return([Ok(())]));
+        let service = DeleteService::new(
+            Arc::clone(&catalog),
+            Arc::clone(&handler),
+            Some(Arc::new(MockAuthorizer::default())),
+        );
+
+        let delete_request = |authorization: Option<&str>| {
+            let mut req = Request::new(DeleteRequest {
+                payload: Some(DeletePayload {
+                    database_id: ns.id.get(),
+                    table_name: "platanos".to_string(),
+                    predicate: Some((&predicate()).into()),
+                    table_id: 0,
+                }),
+            });
+            if let Some(authorization) = authorization {
+                req.metadata_mut()
+                    .insert("authorization", authorization.parse().unwrap());
+            }
+            req
+        };
+
+        // A delete without a token is rejected.
+        assert_matches!(service.delete(delete_request(None)).await, Err(e) => {
+            assert_eq!(e.code(), tonic::Code::Unauthenticated);
+        });
+
+        // A delete with a token lacking write permission is rejected.
+        let authorization = format!("Token {MOCK_AUTH_NO_PERMS_TOKEN}");
+        assert_matches!(
+            service.delete(delete_request(Some(&authorization))).await,
+            Err(e) => {
+                assert_eq!(e.code(), tonic::Code::PermissionDenied);
+            }
+        );
+        assert!(handler.calls().is_empty());
+
+        // A delete with a token granting write permission is applied.
+        let authorization = format!("Token {MOCK_AUTH_VALID_TOKEN}");
+        assert_matches!(
+            service.delete(delete_request(Some(&authorization))).await,
+            Ok(_)
+        );
+        assert_matches!(
+            handler.calls().as_slice(),
+            [MockDmlHandlerCall::Delete { namespace, .. }] => {
+                assert_eq!(namespace, "bananas");
+            }
+        );
+    }
+}
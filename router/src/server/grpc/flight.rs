@@ -0,0 +1,597 @@
+//! An Arrow Flight service accepting bulk writes of Arrow record batches via
+//! `DoPut`, passing them through the router [`DmlHandler`] stack.
+//!
+//! The target table is identified by the [`FlightDescriptor`] of the first
+//! message in the `DoPut` stream, either as a Flight SQL
+//! `CommandStatementIngest` command, or as a path containing only the table
+//! name. The target namespace is read from the `database` request header, as
+//! for Flight SQL queries against the querier.
+//!
+//! When an authorization service is configured, the token in the
+//! `authorization` request header must grant write access to the namespace.
+//!
+//! All other Flight RPCs are served by the querier.
+
+mod convert;
+
+use std::{pin::Pin, sync::Arc};
+
+use arrow_flight::{
+    decode::FlightRecordBatchStream,
+    error::FlightError,
+    flight_descriptor::DescriptorType,
+    flight_service_server::FlightService,
+    sql::{Any, DoPutUpdateResult},
+    Action, ActionType, Criteria, Empty, FlightData, FlightDescriptor, FlightInfo,
+    HandshakeRequest, HandshakeResponse, PutResult, SchemaResult, Ticket,
+};
+use authz::Authorizer;
+use data_types::NamespaceName;
+use futures::{Stream, StreamExt, TryStreamExt};
+use hashbrown::HashMap;
+use mutable_batch::MutableBatch;
+use observability_deps::tracing::*;
+use prost::Message;
+use tonic::{metadata::MetadataMap, Request, Response, Status, Streaming};
+use trace::ctx::SpanContext;
+
+use super::{authorize_write, status_from_dml_error, status_from_namespace_error};
+use crate::{
+    dml_handlers::{DmlError, DmlHandler},
+    namespace_resolver::NamespaceResolver,
+};
+
+/// The request headers that may contain the target namespace name, matching
+/// those accepted by the querier for Flight SQL requests.
+const DATABASE_HEADERS: [&str; 4] = ["database", "bucket", "bucket-name", "iox-namespace-name"];
+
+/// The `type_url` of a packed [`CommandStatementIngest`].
+const COMMAND_STATEMENT_INGEST_TYPE_URL: &str =
+    "type.googleapis.com/arrow.flight.protocol.sql.CommandStatementIngest";
+
+/// The Flight SQL `CommandStatementIngest` message, which is not yet provided
+/// by the `arrow-flight` crate.
+///
+/// Only the target table name is decoded - the table definition options are
+/// ignored, as tables are created on demand by the router.
+#[derive(Clone, PartialEq, Message)]
+struct CommandStatementIngest {
+    /// The name of the table to write to.
+    #[prost(string, tag = "2")]
+    table: String,
+}
+
+type TonicStream<T> = Pin<Box<dyn Stream<Item = Result<T, Status>> + Send + 'static>>;
+
+/// Implementation of the Arrow Flight `DoPut` RPC for the router.
+#[derive(Debug)]
+pub(crate) struct FlightIngestService<D, N> {
+    namespace_resolver: N,
+    dml_handler: D,
+    max_request_bytes: usize,
+    authz: Option<Arc<dyn Authorizer>>,
+}
+
+impl<D, N> FlightIngestService<D, N> {
+    /// Initialise a new [`FlightIngestService`] resolving namespaces with
+    /// `namespace_resolver`, and applying writes with `dml_handler`.
+    ///
+    /// If `authz` is provided, writes are only accepted from callers
+    /// authorized to write to the target namespace.
+    pub(crate) fn new(
+        namespace_resolver: N,
+        dml_handler: D,
+        max_request_bytes: usize,
+        authz: Option<Arc<dyn Authorizer>>,
+    ) -> Self {
+        Self {
+            namespace_resolver,
+            dml_handler,
+            max_request_bytes,
+            authz,
+        }
+    }
+}
+
+impl<D, N> FlightIngestService<D, N>
+where
+    D: DmlHandler<WriteInput = HashMap<String, MutableBatch>, WriteOutput = ()>,
+    N: NamespaceResolver,
+{
+    /// Authorize the `DoPut` `request` and write the record batches it
+    /// contains to the namespace named in its headers.
+    async fn put<S>(&self, request: Request<S>) -> Result<PutResult, Status>
+    where
+        S: Stream<Item = Result<FlightData, Status>> + Send + Unpin + 'static,
+    {
+        let span_ctx: Option<SpanContext> = request.extensions().get().cloned();
+        let namespace = namespace_from_metadata(request.metadata())?;
+
+        // Reject unauthorized writes before any data is buffered.
+        authorize_write(self.authz.as_ref(), request.metadata(), &namespace).await?;
+
+        self.write(namespace, request.into_inner(), span_ctx).await
+    }
+
+    /// Decode the record batches in the `DoPut` stream `data` and write them
+    /// to `namespace`, returning a [`PutResult`] describing the number of rows
+    /// written.
+    async fn write<S>(
+        &self,
+        namespace: NamespaceName<'static>,
+        mut data: S,
+        span_ctx: Option<SpanContext>,
+    ) -> Result<PutResult, Status>
+    where
+        S: Stream<Item = Result<FlightData, Status>> + Send + Unpin + 'static,
+    {
+        // The descriptor is only present in the first message of the stream.
+        let first = data
+            .try_next()
+            .await?
+            .ok_or_else(|| Status::invalid_argument("empty DoPut request"))?;
+        let table = table_from_descriptor(first.flight_descriptor.as_ref())?;
+
+        let mut batch = MutableBatch::new();
+        let mut decoder = FlightRecordBatchStream::new_from_flight_data(
+            futures::stream::once(async { Ok(first) }).chain(data.map_err(FlightError::from)),
+        );
+        while let Some(record_batch) = decoder
+            .try_next()
+            .await
+            .map_err(|e| Status::invalid_argument(e.to_string()))?
+        {
+            convert::write_record_batch(&mut batch, &record_batch)
+                .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+            if batch.size_data() > self.max_request_bytes {
+                return Err(Status::resource_exhausted(format!(
+                    "max request size ({} bytes) exceeded",
+                    self.max_request_bytes
+                )));
+            }
+        }
+
+        let record_count = batch.rows();
+        debug!(%namespace, %table, record_count, "routing flight write");
+
+        if record_count > 0 {
+            let namespace_schema = self
+                .namespace_resolver
+                .get_namespace_schema(&namespace)
+                .await
+                .map_err(status_from_namespace_error)?;
+
+            self.dml_handler
+                .write(
+                    &namespace,
+                    namespace_schema,
+                    HashMap::from([(table.clone(), batch)]),
+                    span_ctx,
+                )
+                .await
+                .map_err(|e| {
+                    let e: DmlError = e.into();
+                    warn!(error=%e, %namespace, %table, "failed to apply flight write");
+                    status_from_dml_error(e)
+                })?;
+        }
+
+        Ok(PutResult {
+            app_metadata: DoPutUpdateResult {
+                record_count: record_count as i64,
+            }
+            .encode_to_vec()
+            .into(),
+        })
+    }
+}
+
+#[tonic::async_trait]
+impl<D, N> FlightService for FlightIngestService<D, N>
+where
+    D: DmlHandler<WriteInput = HashMap<String, MutableBatch>, WriteOutput = ()> + 'static,
+    N: NamespaceResolver + 'static,
+{
+    type HandshakeStream = TonicStream<HandshakeResponse>;
+    type ListFlightsStream = TonicStream<FlightInfo>;
+    type DoGetStream = TonicStream<FlightData>;
+    type DoPutStream = TonicStream<PutResult>;
+    type DoActionStream = TonicStream<arrow_flight::Result>;
+    type ListActionsStream = TonicStream<ActionType>;
+    type DoExchangeStream = TonicStream<FlightData>;
+
+    async fn handshake(
+        &self,
+        _request: Request<Streaming<HandshakeRequest>>,
+    ) -> Result<Response<Self::HandshakeStream>, Status> {
+        Err(Status::unimplemented(
+            "handshake is not supported by the router",
+        ))
+    }
+
+    async fn list_flights(
+        &self,
+        _request: Request<Criteria>,
+    ) -> Result<Response<Self::ListFlightsStream>, Status> {
+        Err(Status::unimplemented(
+            "list_flights is not supported by the router",
+        ))
+    }
+
+    async fn get_flight_info(
+        &self,
+        _request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        Err(Status::unimplemented(
+            "get_flight_info is not supported by the router",
+        ))
+    }
+
+    async fn get_schema(
+        &self,
+        _request: Request<FlightDescriptor>,
+    ) -> Result<Response<SchemaResult>, Status> {
+        Err(Status::unimplemented(
+            "get_schema is not supported by the router",
+        ))
+    }
+
+    async fn do_get(
+        &self,
+        _request: Request<Ticket>,
+    ) -> Result<Response<Self::DoGetStream>, Status> {
+        Err(Status::unimplemented(
+            "do_get is not supported by the router",
+        ))
+    }
+
+    async fn do_put(
+        &self,
+        request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoPutStream>, Status> {
+        let result = self.put(request).await?;
+
+        Ok(Response::new(Box::pin(futures::stream::iter([Ok(result)]))))
+    }
+
+    async fn do_action(
+        &self,
+        _request: Request<Action>,
+    ) -> Result<Response<Self::DoActionStream>, Status> {
+        Err(Status::unimplemented(
+            "do_action is not supported by the router",
+        ))
+    }
+
+    async fn list_actions(
+        &self,
+        _request: Request<Empty>,
+    ) -> Result<Response<Self::ListActionsStream>, Status> {
+        Err(Status::unimplemented(
+            "list_actions is not supported by the router",
+        ))
+    }
+
+    async fn do_exchange(
+        &self,
+        _request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoExchangeStream>, Status> {
+        Err(Status::unimplemented(
+            "do_exchange is not supported by the router",
+        ))
+    }
+}
+
+/// Read the target namespace name from the first of [`DATABASE_HEADERS`]
+/// present in `metadata`.
+pub(super) fn namespace_from_metadata(
+    metadata: &MetadataMap,
+) -> Result<NamespaceName<'static>, Status> {
+    let name = DATABASE_HEADERS
+        .iter()
+        .find_map(|key| metadata.get(*key))
+        .ok_or_else(|| Status::invalid_argument("no 'database' header in request"))?
+        .to_str()
+        .map_err(|e| Status::invalid_argument(format!("invalid 'database' header: {e}")))?;
+
+    NamespaceName::try_from(name.to_string())
+        .map_err(|e| Status::invalid_argument(format!("invalid database name: {e}")))
+}
+
+/// Extract the name of the table to write to from the [`FlightDescriptor`]
+/// of a `DoPut` request.
+fn table_from_descriptor(descriptor: Option<&FlightDescriptor>) -> Result<String, Status> {
+    let descriptor = descriptor
+        .ok_or_else(|| Status::invalid_argument("DoPut request has no flight descriptor"))?;
+
+    let table = match descriptor.r#type() {
+        DescriptorType::Path => match descriptor.path.as_slice() {
+            [table] => table.clone(),
+            _ => {
+                return Err(Status::invalid_argument(
+                    "flight descriptor path must contain only the table name",
+                ))
+            }
+        },
+        DescriptorType::Cmd => {
+            let any = Any::decode(descriptor.cmd.clone())
+                .map_err(|e| Status::invalid_argument(format!("invalid command: {e}")))?;
+            if any.type_url != COMMAND_STATEMENT_INGEST_TYPE_URL {
+                return Err(Status::unimplemented(format!(
+                    "unsupported DoPut command: {}",
+                    any.type_url
+                )));
+            }
+            CommandStatementIngest::decode(any.value)
+                .map_err(|e| Status::invalid_argument(format!("invalid command: {e}")))?
+                .table
+        }
+        DescriptorType::Unknown => {
+            return Err(Status::invalid_argument(
+                "flight descriptor has an unknown type",
+            ))
+        }
+    };
+
+    if table.is_empty() {
+        return Err(Status::invalid_argument("no table name provided"));
+    }
+
+    Ok(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::{
+        array::{ArrayRef, Float64Array, TimestampNanosecondArray},
+        record_batch::RecordBatch,
+    };
+    use arrow_flight::encode::FlightDataEncoderBuilder;
+    use assert_matches::assert_matches;
+    use data_types::NamespaceId;
+
+    use super::*;
+    use crate::{
+        dml_handlers::mock::{MockDmlHandler, MockDmlHandlerCall},
+        namespace_resolver::mock::MockNamespaceResolver,
+        server::http::write::single_tenant::auth::mock::{
+            MockAuthorizer, MOCK_AUTH_NO_PERMS_TOKEN, MOCK_AUTH_VALID_TOKEN,
+        },
+    };
+
+    fn record_batch() -> RecordBatch {
+        RecordBatch::try_from_iter([
+            (
+                "value",
+                Arc::new(Float64Array::from(vec![1.0, 2.0])) as ArrayRef,
+            ),
+            (
+                "time",
+                Arc::new(TimestampNanosecondArray::from(vec![1, 2])) as ArrayRef,
+            ),
+        ])
+        .unwrap()
+    }
+
+    /// Encode `batches` as a sequence of [`FlightData`], with `descriptor`
+    /// set on the first message.
+    async fn encode(descriptor: FlightDescriptor, batches: Vec<RecordBatch>) -> Vec<FlightData> {
+        FlightDataEncoderBuilder::new()
+            .with_flight_descriptor(Some(descriptor))
+            .build(futures::stream::iter(batches.into_iter().map(Ok)))
+            .try_collect()
+            .await
+            .unwrap()
+    }
+
+    fn ingest_descriptor(table: &str) -> FlightDescriptor {
+        let cmd = CommandStatementIngest {
+            table: table.to_string(),
+        };
+        let any = Any {
+            type_url: COMMAND_STATEMENT_INGEST_TYPE_URL.to_string(),
+            value: cmd.encode_to_vec().into(),
+        };
+        FlightDescriptor::new_cmd(any.encode_to_vec())
+    }
+
+    #[test]
+    fn test_table_from_descriptor() {
+        assert_eq!(
+            table_from_descriptor(Some(&ingest_descriptor("platanos"))).unwrap(),
+            "platanos"
+        );
+        assert_eq!(
+            table_from_descriptor(Some(&FlightDescriptor::new_path(vec![
+                "platanos".to_string()
+            ])))
+            .unwrap(),
+            "platanos"
+        );
+
+        assert_matches!(table_from_descriptor(None), Err(e) => {
+            assert_eq!(e.code(), tonic::Code::InvalidArgument);
+        });
+        assert_matches!(
+            table_from_descriptor(Some(&FlightDescriptor::new_path(vec![
+                "a".to_string(),
+                "b".to_string()
+            ]))),
+            Err(e) => {
+                assert_eq!(e.code(), tonic::Code::InvalidArgument);
+            }
+        );
+        assert_matches!(table_from_descriptor(Some(&ingest_descriptor(""))), Err(e) => {
+            assert_eq!(e.code(), tonic::Code::InvalidArgument);
+        });
+
+        let any = Any {
+            type_url: "type.googleapis.com/arrow.flight.protocol.sql.CommandStatementUpdate"
+                .to_string(),
+            value: Default::default(),
+        };
+        assert_matches!(
+            table_from_descriptor(Some(&FlightDescriptor::new_cmd(any.encode_to_vec()))),
+            Err(e) => {
+                assert_eq!(e.code(), tonic::Code::Unimplemented);
+            }
+        );
+    }
+
+    #[test]
+    fn test_namespace_from_metadata() {
+        let mut metadata = MetadataMap::new();
+        assert_matches!(namespace_from_metadata(&metadata), Err(e) => {
+            assert_eq!(e.code(), tonic::Code::InvalidArgument);
+        });
+
+        metadata.insert("bucket", "bananas".parse().unwrap());
+        assert_eq!(
+            namespace_from_metadata(&metadata).unwrap().as_str(),
+            "bananas"
+        );
+
+        metadata.insert("database", "platanos".parse().unwrap());
+        assert_eq!(
+            namespace_from_metadata(&metadata).unwrap().as_str(),
+            "platanos"
+        );
+    }
+
+    /// Write `data` to the "bananas" namespace using `service`.
+    async fn write<D, N>(
+        service: &FlightIngestService<D, N>,
+        data: Vec<FlightData>,
+    ) -> Result<i64, Status>
+    where
+        D: DmlHandler<WriteInput = HashMap<String, MutableBatch>, WriteOutput = ()>,
+        N: NamespaceResolver,
+    {
+        let namespace = NamespaceName::try_from("bananas").unwrap();
+        let result = service
+            .write(
+                namespace,
+                futures::stream::iter(data.into_iter().map(Ok)),
+                None,
+            )
+            .await?;
+        Ok(DoPutUpdateResult::decode(result.app_metadata)
+            .unwrap()
+            .record_count)
+    }
+
+    #[tokio::test]
+    async fn test_write() {
+        let dml_handler = Arc::new(MockDmlHandler::default().with_write_return([Ok(())]));
+        let service = FlightIngestService::new(
+            MockNamespaceResolver::default().with_mapping("bananas", NamespaceId::new(42)),
+            Arc::clone(&dml_handler),
+            usize::MAX,
+            None,
+        );
+
+        let data = encode(
+            ingest_descriptor("platanos"),
+            vec![record_batch(), record_batch()],
+        )
+        .await;
+        assert_eq!(write(&service, data).await.unwrap(), 4);
+
+        assert_matches!(
+            dml_handler.calls().as_slice(),
+            [MockDmlHandlerCall::Write { namespace, write_input, .. }] => {
+                assert_eq!(namespace, "bananas");
+                assert_eq!(write_input.len(), 1);
+                assert_eq!(write_input["platanos"].rows(), 4);
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_size_limit() {
+        let dml_handler = Arc::new(MockDmlHandler::default());
+        let service = FlightIngestService::new(
+            MockNamespaceResolver::default().with_mapping("bananas", NamespaceId::new(42)),
+            Arc::clone(&dml_handler),
+            1,
+            None,
+        );
+
+        let data = encode(ingest_descriptor("platanos"), vec![record_batch()]).await;
+        assert_matches!(write(&service, data).await, Err(e) => {
+            assert_eq!(e.code(), tonic::Code::ResourceExhausted);
+        });
+        assert!(dml_handler.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_write_dml_error() {
+        let dml_handler = Arc::new(
+            MockDmlHandler::default()
+                .with_write_return([Err(DmlError::NamespaceNotFound("bananas".to_string()))]),
+        );
+        let service = FlightIngestService::new(
+            MockNamespaceResolver::default().with_mapping("bananas", NamespaceId::new(42)),
+            Arc::clone(&dml_handler),
+            usize::MAX,
+            None,
+        );
+
+        let data = encode(ingest_descriptor("platanos"), vec![record_batch()]).await;
+        assert_matches!(write(&service, data).await, Err(e) => {
+            assert_eq!(e.code(), tonic::Code::NotFound);
+        });
+    }
+
+    #[tokio::test]
+    async fn test_put_authz() {
+        let dml_handler = Arc::new(MockDmlHandler::default().with_write_return([Ok(())]));
+        let service = FlightIngestService::new(
+            MockNamespaceResolver::default().with_mapping("bananas", NamespaceId::new(42)),
+            Arc::clone(&dml_handler),
+            usize::MAX,
+            Some(Arc::new(MockAuthorizer::default())),
+        );
+
+        let data = encode(ingest_descriptor("platanos"), vec![record_batch()]).await;
+        let request = |authorization: Option<&str>| {
+            let mut request = Request::new(futures::stream::iter(
+                data.clone().into_iter().map(Ok::<_, Status>),
+            ));
+            request
+                .metadata_mut()
+                .insert("database", "bananas".parse().unwrap());
+            if let Some(authorization) = authorization {
+                request
+                    .metadata_mut()
+                    .insert("authorization", authorization.parse().unwrap());
+            }
+            request
+        };
+
+        // A put without a token is rejected.
+        assert_matches!(service.put(request(None)).await, Err(e) => {
+            assert_eq!(e.code(), tonic::Code::Unauthenticated);
+        });
+
+        // A put with a token lacking write permission is rejected.
+        let authorization = format!("Bearer {MOCK_AUTH_NO_PERMS_TOKEN}");
+        assert_matches!(service.put(request(Some(&authorization))).await, Err(e) => {
+            assert_eq!(e.code(), tonic::Code::PermissionDenied);
+        });
+        assert!(dml_handler.calls().is_empty());
+
+        // A put with a token granting write permission is applied.
+        let authorization = format!("Bearer {MOCK_AUTH_VALID_TOKEN}");
+        assert_matches!(service.put(request(Some(&authorization))).await, Ok(_));
+        assert_matches!(
+            dml_handler.calls().as_slice(),
+            [MockDmlHandlerCall::Write { namespace, .. }] => {
+                assert_eq!(namespace, "bananas");
+            }
+        );
+    }
+}
@@ -0,0 +1,328 @@
+//! Conversion of Arrow [`RecordBatch`] into [`MutableBatch`].
+//!
+//! Dictionary encoded string columns are written as tags, and all other
+//! columns (except the required `time` column) are written as fields.
+
+use arrow::{
+    array::{as_boolean_array, as_primitive_array, as_string_array, Array, ArrayRef},
+    compute::cast,
+    datatypes::{DataType, Float64Type, Int64Type, TimeUnit, TimestampNanosecondType, UInt64Type},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use mutable_batch::{writer::Writer, MutableBatch};
+use thiserror::Error;
+
+/// The name of the column containing the timestamp of each row.
+const TIME_COLUMN: &str = "time";
+
+/// Errors returned when writing a [`RecordBatch`] to a [`MutableBatch`].
+#[derive(Debug, Error)]
+pub enum ArrowWriteError {
+    /// The record batch has no time column.
+    #[error("missing required {TIME_COLUMN:?} column")]
+    MissingTime,
+
+    /// The time column contains null values.
+    #[error("{TIME_COLUMN:?} column contains null values")]
+    NullTime,
+
+    /// A column has a data type that cannot be stored by IOx.
+    #[error("unsupported data type {data_type} for column {column}")]
+    UnsupportedType {
+        /// The name of the column.
+        column: String,
+        /// The data type of the column.
+        data_type: DataType,
+    },
+
+    /// A column could not be cast to the equivalent IOx column type.
+    #[error("error converting column {column}: {source}")]
+    Cast {
+        /// The name of the column.
+        column: String,
+        /// The underlying cast error.
+        source: ArrowError,
+    },
+
+    /// The column values could not be written.
+    #[error("error writing column {column}: {source}")]
+    Write {
+        /// The name of the column.
+        column: String,
+        /// The underlying write error.
+        source: mutable_batch::writer::Error,
+    },
+}
+
+/// Append the rows of `batch` to `dst`.
+///
+/// If an error is returned, `dst` is left unchanged.
+pub(crate) fn write_record_batch(
+    dst: &mut MutableBatch,
+    batch: &RecordBatch,
+) -> Result<(), ArrowWriteError> {
+    let num_rows = batch.num_rows();
+    if num_rows == 0 {
+        return Ok(());
+    }
+
+    let time = batch
+        .column_by_name(TIME_COLUMN)
+        .ok_or(ArrowWriteError::MissingTime)?;
+    if time.null_count() > 0 {
+        return Err(ArrowWriteError::NullTime);
+    }
+
+    let mut writer = Writer::new(dst, num_rows);
+    let schema = batch.schema();
+    for (field, column) in schema.fields().iter().zip(batch.columns()) {
+        let name = field.name().as_str();
+        let write_err = |source| ArrowWriteError::Write {
+            column: name.to_string(),
+            source,
+        };
+        let mask = valid_mask(column);
+        let mask = mask.as_deref();
+
+        match field.data_type() {
+            DataType::Timestamp(_, _) | DataType::Int64 if name == TIME_COLUMN => {
+                let values = cast_column(
+                    name,
+                    column,
+                    &DataType::Timestamp(TimeUnit::Nanosecond, None),
+                )?;
+                writer
+                    .write_time(
+                        name,
+                        as_primitive_array::<TimestampNanosecondType>(&values)
+                            .values()
+                            .iter()
+                            .copied(),
+                    )
+                    .map_err(write_err)?;
+            }
+            data_type if name == TIME_COLUMN => {
+                return Err(ArrowWriteError::UnsupportedType {
+                    column: name.to_string(),
+                    data_type: data_type.clone(),
+                })
+            }
+            DataType::Dictionary(_, v)
+                if matches!(v.as_ref(), DataType::Utf8 | DataType::LargeUtf8) =>
+            {
+                let values = cast_column(name, column, &DataType::Utf8)?;
+                writer
+                    .write_tag(name, mask, as_string_array(&values).iter().flatten())
+                    .map_err(write_err)?;
+            }
+            DataType::Float16 | DataType::Float32 | DataType::Float64 => {
+                let values = cast_column(name, column, &DataType::Float64)?;
+                writer
+                    .write_f64(
+                        name,
+                        mask,
+                        as_primitive_array::<Float64Type>(&values).iter().flatten(),
+                    )
+                    .map_err(write_err)?;
+            }
+            DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::Int64 => {
+                let values = cast_column(name, column, &DataType::Int64)?;
+                writer
+                    .write_i64(
+                        name,
+                        mask,
+                        as_primitive_array::<Int64Type>(&values).iter().flatten(),
+                    )
+                    .map_err(write_err)?;
+            }
+            DataType::UInt8 | DataType::UInt16 | DataType::UInt32 | DataType::UInt64 => {
+                let values = cast_column(name, column, &DataType::UInt64)?;
+                writer
+                    .write_u64(
+                        name,
+                        mask,
+                        as_primitive_array::<UInt64Type>(&values).iter().flatten(),
+                    )
+                    .map_err(write_err)?;
+            }
+            DataType::Boolean => {
+                writer
+                    .write_bool(name, mask, as_boolean_array(column).iter().flatten())
+                    .map_err(write_err)?;
+            }
+            DataType::Utf8 | DataType::LargeUtf8 => {
+                let values = cast_column(name, column, &DataType::Utf8)?;
+                writer
+                    .write_string(name, mask, as_string_array(&values).iter().flatten())
+                    .map_err(write_err)?;
+            }
+            data_type => {
+                return Err(ArrowWriteError::UnsupportedType {
+                    column: name.to_string(),
+                    data_type: data_type.clone(),
+                })
+            }
+        }
+    }
+
+    writer.commit();
+    Ok(())
+}
+
+/// Cast `column` to `data_type`, if it is not already of that type.
+fn cast_column(
+    name: &str,
+    column: &ArrayRef,
+    data_type: &DataType,
+) -> Result<ArrayRef, ArrowWriteError> {
+    cast(column, data_type).map_err(|source| ArrowWriteError::Cast {
+        column: name.to_string(),
+        source,
+    })
+}
+
+/// Build the [`Writer`] validity bitmask for `column`, or [`None`] if all
+/// values are valid.
+fn valid_mask(column: &ArrayRef) -> Option<Vec<u8>> {
+    if column.null_count() == 0 {
+        return None;
+    }
+
+    let mut mask = vec![0_u8; (column.len() + 7) / 8];
+    for idx in (0..column.len()).filter(|idx| column.is_valid(*idx)) {
+        mask[idx / 8] |= 1 << (idx % 8);
+    }
+    Some(mask)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::{
+        array::{
+            BooleanArray, DictionaryArray, Float64Array, Int32Array, Int64Array, StringArray,
+            TimestampNanosecondArray, UInt64Array,
+        },
+        datatypes::Int32Type,
+    };
+    use arrow_util::assert_batches_eq;
+    use assert_matches::assert_matches;
+    use schema::Projection;
+
+    use super::*;
+
+    #[test]
+    fn test_write_record_batch() {
+        let batch = RecordBatch::try_from_iter([
+            (
+                "region",
+                Arc::new(
+                    [Some("west"), None, Some("east")]
+                        .into_iter()
+                        .collect::<DictionaryArray<Int32Type>>(),
+                ) as ArrayRef,
+            ),
+            (
+                "f",
+                Arc::new(Float64Array::from(vec![Some(1.5), Some(2.5), None])) as ArrayRef,
+            ),
+            (
+                "i",
+                Arc::new(Int32Array::from(vec![None, Some(-2), Some(3)])) as ArrayRef,
+            ),
+            ("u", Arc::new(UInt64Array::from(vec![1, 2, 3])) as ArrayRef),
+            (
+                "b",
+                Arc::new(BooleanArray::from(vec![Some(true), None, Some(false)])) as ArrayRef,
+            ),
+            (
+                "s",
+                Arc::new(StringArray::from(vec![Some("a"), Some("b"), None])) as ArrayRef,
+            ),
+            (
+                TIME_COLUMN,
+                Arc::new(TimestampNanosecondArray::from(vec![1, 2, 3])) as ArrayRef,
+            ),
+        ])
+        .unwrap();
+
+        let mut mb = MutableBatch::new();
+        write_record_batch(&mut mb, &batch).unwrap();
+        // Appending a second batch extends the existing columns.
+        write_record_batch(&mut mb, &batch.slice(0, 1)).unwrap();
+
+        assert_batches_eq!(
+            [
+                "+-------+-----+----+--------+---+--------------------------------+---+",
+                "| b     | f   | i  | region | s | time                           | u |",
+                "+-------+-----+----+--------+---+--------------------------------+---+",
+                "| true  | 1.5 |    | west   | a | 1970-01-01T00:00:00.000000001Z | 1 |",
+                "|       | 2.5 | -2 |        | b | 1970-01-01T00:00:00.000000002Z | 2 |",
+                "| false |     | 3  | east   |   | 1970-01-01T00:00:00.000000003Z | 3 |",
+                "| true  | 1.5 |    | west   | a | 1970-01-01T00:00:00.000000001Z | 1 |",
+                "+-------+-----+----+--------+---+--------------------------------+---+",
+            ],
+            &[mb.to_arrow(Projection::All).unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_write_record_batch_errors() {
+        let mut mb = MutableBatch::new();
+
+        let batch =
+            RecordBatch::try_from_iter([("f", Arc::new(Int64Array::from(vec![1])) as ArrayRef)])
+                .unwrap();
+        assert_matches!(
+            write_record_batch(&mut mb, &batch),
+            Err(ArrowWriteError::MissingTime)
+        );
+
+        let batch = RecordBatch::try_from_iter([(
+            TIME_COLUMN,
+            Arc::new(Int64Array::from(vec![Some(1), None])) as ArrayRef,
+        )])
+        .unwrap();
+        assert_matches!(
+            write_record_batch(&mut mb, &batch),
+            Err(ArrowWriteError::NullTime)
+        );
+
+        let batch = RecordBatch::try_from_iter([
+            (
+                "f",
+                Arc::new(arrow::array::Date32Array::from(vec![1])) as ArrayRef,
+            ),
+            (TIME_COLUMN, Arc::new(Int64Array::from(vec![1])) as ArrayRef),
+        ])
+        .unwrap();
+        assert_matches!(
+            write_record_batch(&mut mb, &batch),
+            Err(ArrowWriteError::UnsupportedType { column, .. }) => {
+                assert_eq!(column, "f");
+            }
+        );
+
+        // A column type conflicting with the existing batch.
+        let batch = RecordBatch::try_from_iter([
+            ("f", Arc::new(Int64Array::from(vec![1])) as ArrayRef),
+            (TIME_COLUMN, Arc::new(Int64Array::from(vec![1])) as ArrayRef),
+        ])
+        .unwrap();
+        write_record_batch(&mut mb, &batch).unwrap();
+        let batch = RecordBatch::try_from_iter([
+            ("f", Arc::new(StringArray::from(vec!["a"])) as ArrayRef),
+            (TIME_COLUMN, Arc::new(Int64Array::from(vec![2])) as ArrayRef),
+        ])
+        .unwrap();
+        assert_matches!(
+            write_record_batch(&mut mb, &batch),
+            Err(ArrowWriteError::Write { column, .. }) => {
+                assert_eq!(column, "f");
+            }
+        );
+        assert_eq!(mb.rows(), 1);
+    }
+}
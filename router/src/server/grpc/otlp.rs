@@ -0,0 +1,326 @@
+//! An OpenTelemetry [OTLP] `MetricsService` accepting exported metrics,
+//! passing them through the router [`DmlHandler`] stack.
+//!
+//! The target namespace is read from the `database` request header, as for
+//! Arrow Flight writes. See [`convert`] for the mapping of metrics to tables.
+//!
+//! When an authorization service is configured, the token in the
+//! `authorization` request header must grant write access to the namespace.
+//!
+//! [OTLP]: https://opentelemetry.io/docs/specs/otlp/
+
+mod convert;
+
+use std::sync::Arc;
+
+use authz::Authorizer;
+use data_types::NamespaceName;
+use generated_types::opentelemetry::proto::collector::metrics::v1::{
+    metrics_service_server::MetricsService, ExportMetricsServiceRequest,
+    ExportMetricsServiceResponse,
+};
+use hashbrown::HashMap;
+use mutable_batch::MutableBatch;
+use observability_deps::tracing::*;
+use tonic::{Request, Response, Status};
+use trace::ctx::SpanContext;
+
+use super::{
+    authorize_write, flight::namespace_from_metadata, status_from_dml_error,
+    status_from_namespace_error,
+};
+use crate::{
+    dml_handlers::{DmlError, DmlHandler},
+    namespace_resolver::NamespaceResolver,
+};
+
+/// Implementation of the OTLP metrics `Export` RPC for the router.
+#[derive(Debug)]
+pub(crate) struct OtlpMetricsService<D, N> {
+    namespace_resolver: N,
+    dml_handler: D,
+    max_request_bytes: usize,
+    authz: Option<Arc<dyn Authorizer>>,
+}
+
+impl<D, N> OtlpMetricsService<D, N> {
+    /// Initialise a new [`OtlpMetricsService`] resolving namespaces with
+    /// `namespace_resolver`, and applying writes with `dml_handler`.
+    ///
+    /// If `authz` is provided, metrics are only accepted from callers
+    /// authorized to write to the target namespace.
+    pub(crate) fn new(
+        namespace_resolver: N,
+        dml_handler: D,
+        max_request_bytes: usize,
+        authz: Option<Arc<dyn Authorizer>>,
+    ) -> Self {
+        Self {
+            namespace_resolver,
+            dml_handler,
+            max_request_bytes,
+            authz,
+        }
+    }
+}
+
+impl<D, N> OtlpMetricsService<D, N>
+where
+    D: DmlHandler<WriteInput = HashMap<String, MutableBatch>, WriteOutput = ()>,
+    N: NamespaceResolver,
+{
+    /// Authorize the export `request` and write the metrics it contains to
+    /// the namespace named in its headers.
+    async fn export_metrics(
+        &self,
+        request: Request<ExportMetricsServiceRequest>,
+    ) -> Result<ExportMetricsServiceResponse, Status> {
+        let span_ctx: Option<SpanContext> = request.extensions().get().cloned();
+        let namespace = namespace_from_metadata(request.metadata())?;
+
+        authorize_write(self.authz.as_ref(), request.metadata(), &namespace).await?;
+
+        self.write(namespace, request.into_inner(), span_ctx).await
+    }
+
+    /// Convert the metrics in `request` and write them to `namespace`.
+    ///
+    /// Invalid data points are rejected without failing the request, and
+    /// reported in the partial success of the response.
+    async fn write(
+        &self,
+        namespace: NamespaceName<'static>,
+        request: ExportMetricsServiceRequest,
+        span_ctx: Option<SpanContext>,
+    ) -> Result<ExportMetricsServiceResponse, Status> {
+        let converted = convert::convert_request(&request);
+
+        let size: usize = converted.batches.values().map(|b| b.size_data()).sum();
+        if size > self.max_request_bytes {
+            return Err(Status::resource_exhausted(format!(
+                "max request size ({} bytes) exceeded",
+                self.max_request_bytes
+            )));
+        }
+
+        let partial_success = converted.partial_success();
+        if let Some(p) = &partial_success {
+            debug!(
+                %namespace,
+                rejected_data_points = p.rejected_data_points,
+                error = %p.error_message,
+                "rejected otlp metrics"
+            );
+        }
+
+        debug!(
+            %namespace,
+            num_points = converted.num_points,
+            num_tables = converted.batches.len(),
+            "routing otlp metrics write"
+        );
+
+        if !converted.batches.is_empty() {
+            let namespace_schema = self
+                .namespace_resolver
+                .get_namespace_schema(&namespace)
+                .await
+                .map_err(status_from_namespace_error)?;
+
+            self.dml_handler
+                .write(&namespace, namespace_schema, converted.batches, span_ctx)
+                .await
+                .map_err(|e| {
+                    let e: DmlError = e.into();
+                    warn!(error=%e, %namespace, "failed to apply otlp metrics write");
+                    status_from_dml_error(e)
+                })?;
+        }
+
+        Ok(ExportMetricsServiceResponse { partial_success })
+    }
+}
+
+#[tonic::async_trait]
+impl<D, N> MetricsService for OtlpMetricsService<D, N>
+where
+    D: DmlHandler<WriteInput = HashMap<String, MutableBatch>, WriteOutput = ()> + 'static,
+    N: NamespaceResolver + 'static,
+{
+    async fn export(
+        &self,
+        request: Request<ExportMetricsServiceRequest>,
+    ) -> Result<Response<ExportMetricsServiceResponse>, Status> {
+        let response = self.export_metrics(request).await?;
+
+        Ok(Response::new(response))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use data_types::NamespaceId;
+    use generated_types::opentelemetry::proto::metrics::v1::{
+        metric::Data, number_data_point, Gauge, Metric, NumberDataPoint, ResourceMetrics,
+        ScopeMetrics,
+    };
+
+    use super::*;
+    use crate::{
+        dml_handlers::mock::{MockDmlHandler, MockDmlHandlerCall},
+        namespace_resolver::mock::MockNamespaceResolver,
+        server::http::write::single_tenant::auth::mock::{
+            MockAuthorizer, MOCK_AUTH_NO_PERMS_TOKEN, MOCK_AUTH_VALID_TOKEN,
+        },
+    };
+
+    fn request(points: Vec<NumberDataPoint>) -> ExportMetricsServiceRequest {
+        ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                scope_metrics: vec![ScopeMetrics {
+                    metrics: vec![Metric {
+                        name: "platanos".to_string(),
+                        data: Some(Data::Gauge(Gauge {
+                            data_points: points,
+                        })),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        }
+    }
+
+    fn point(time_unix_nano: u64) -> NumberDataPoint {
+        NumberDataPoint {
+            time_unix_nano,
+            value: Some(number_data_point::Value::AsDouble(1.0)),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_write() {
+        let dml_handler = Arc::new(MockDmlHandler::default().with_write_return([Ok(())]));
+        let service = OtlpMetricsService::new(
+            MockNamespaceResolver::default().with_mapping("bananas", NamespaceId::new(42)),
+            Arc::clone(&dml_handler),
+            usize::MAX,
+            None,
+        );
+
+        let namespace = NamespaceName::try_from("bananas").unwrap();
+        let response = service
+            .write(namespace, request(vec![point(1), point(2), point(0)]), None)
+            .await
+            .unwrap();
+
+        assert_matches!(response.partial_success, Some(p) => {
+            assert_eq!(p.rejected_data_points, 1);
+        });
+        assert_matches!(
+            dml_handler.calls().as_slice(),
+            [MockDmlHandlerCall::Write { namespace, write_input, .. }] => {
+                assert_eq!(namespace, "bananas");
+                assert_eq!(write_input.len(), 1);
+                assert_eq!(write_input["platanos"].rows(), 2);
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_all_rejected() {
+        let dml_handler = Arc::new(MockDmlHandler::default());
+        let service = OtlpMetricsService::new(
+            MockNamespaceResolver::default().with_mapping("bananas", NamespaceId::new(42)),
+            Arc::clone(&dml_handler),
+            usize::MAX,
+            None,
+        );
+
+        let namespace = NamespaceName::try_from("bananas").unwrap();
+        let response = service
+            .write(namespace, request(vec![point(0)]), None)
+            .await
+            .unwrap();
+
+        assert_matches!(response.partial_success, Some(p) => {
+            assert_eq!(p.rejected_data_points, 1);
+        });
+        assert!(dml_handler.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_write_size_limit() {
+        let dml_handler = Arc::new(MockDmlHandler::default());
+        let service = OtlpMetricsService::new(
+            MockNamespaceResolver::default().with_mapping("bananas", NamespaceId::new(42)),
+            Arc::clone(&dml_handler),
+            1,
+            None,
+        );
+
+        let namespace = NamespaceName::try_from("bananas").unwrap();
+        assert_matches!(
+            service.write(namespace, request(vec![point(1)]), None).await,
+            Err(e) => {
+                assert_eq!(e.code(), tonic::Code::ResourceExhausted);
+            }
+        );
+        assert!(dml_handler.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_export_authz() {
+        let dml_handler = Arc::new(MockDmlHandler::default().with_write_return([Ok(())]));
+        let service = OtlpMetricsService::new(
+            MockNamespaceResolver::default().with_mapping("bananas", NamespaceId::new(42)),
+            Arc::clone(&dml_handler),
+            usize::MAX,
+            Some(Arc::new(MockAuthorizer::default())),
+        );
+
+        let export_request = |authorization: Option<&str>| {
+            let mut req = Request::new(request(vec![point(1)]));
+            req.metadata_mut()
+                .insert("database", "bananas".parse().unwrap());
+            if let Some(authorization) = authorization {
+                req.metadata_mut()
+                    .insert("authorization", authorization.parse().unwrap());
+            }
+            req
+        };
+
+        // An export without a token is rejected.
+        assert_matches!(service.export_metrics(export_request(None)).await, Err(e) => {
+            assert_eq!(e.code(), tonic::Code::Unauthenticated);
+        });
+
+        // An export with a token lacking write permission is rejected.
+        let authorization = format!("Token {MOCK_AUTH_NO_PERMS_TOKEN}");
+        assert_matches!(
+            service.export_metrics(export_request(Some(&authorization))).await,
+            Err(e) => {
+                assert_eq!(e.code(), tonic::Code::PermissionDenied);
+            }
+        );
+        assert!(dml_handler.calls().is_empty());
+
+        // An export with a token granting write permission is applied.
+        let authorization = format!("Token {MOCK_AUTH_VALID_TOKEN}");
+        assert_matches!(
+            service
+                .export_metrics(export_request(Some(&authorization)))
+                .await,
+            Ok(_)
+        );
+        assert_matches!(
+            dml_handler.calls().as_slice(),
+            [MockDmlHandlerCall::Write { namespace, .. }] => {
+                assert_eq!(namespace, "bananas");
+            }
+        );
+    }
+}
@@ -0,0 +1,551 @@
+//! Conversion of OTLP metrics into [`MutableBatch`].
+//!
+//! Each metric is written to the table named by the metric name, with one
+//! row per data point. The attributes of the resource, instrumentation scope
+//! and data point are written as tags, with data point attributes taking
+//! precedence over scope attributes, and scope attributes over resource
+//! attributes of the same name.
+//!
+//! Gauge and sum values are written to the float field `value`. Histogram
+//! data points are written to the `count`, `sum`, `min` and `max` fields,
+//! and the count of each bucket to a field named by the upper bound of the
+//! bucket, such as `bucket_0.5` or `bucket_+Inf`. As in OTLP, bucket counts
+//! are not cumulative.
+
+use std::{collections::BTreeMap, iter};
+
+use generated_types::opentelemetry::proto::{
+    collector::metrics::v1::{ExportMetricsPartialSuccess, ExportMetricsServiceRequest},
+    common::v1::{any_value::Value, AnyValue, KeyValue},
+    metrics::v1::{metric::Data, number_data_point, HistogramDataPoint, Metric, NumberDataPoint},
+};
+use hashbrown::HashMap;
+use mutable_batch::{writer::Writer, MutableBatch};
+use thiserror::Error;
+
+/// The name of the field gauge and sum values are written to.
+pub(crate) const VALUE_FIELD: &str = "value";
+
+/// The prefix of the histogram bucket count field names.
+const BUCKET_FIELD_PREFIX: &str = "bucket_";
+
+/// Errors causing the data points of a metric to be rejected.
+#[derive(Debug, Error)]
+pub enum OtlpMetricError {
+    /// The metric has no name.
+    #[error("metric has no name")]
+    MissingName,
+
+    /// The metric is not a gauge, sum or histogram.
+    #[error("metric {0} has an unsupported type")]
+    UnsupportedType(String),
+
+    /// A number data point has no value.
+    #[error("data point of metric {0} has no value")]
+    MissingValue(String),
+
+    /// A data point has no timestamp.
+    #[error("data point of metric {0} has no timestamp")]
+    MissingTime(String),
+
+    /// A histogram data point has a bucket count for each bound, rather than
+    /// one more than the number of bounds.
+    #[error("histogram data point of metric {0} has mismatched bucket counts and bounds")]
+    InvalidBuckets(String),
+
+    /// The data point could not be written.
+    #[error("error writing data point of metric {metric}: {source}")]
+    Write {
+        /// The name of the metric.
+        metric: String,
+        /// The underlying write error.
+        source: mutable_batch::writer::Error,
+    },
+}
+
+/// The result of converting an OTLP metrics export request.
+#[derive(Debug, Default)]
+pub(crate) struct ConvertedMetrics {
+    /// The converted data points, keyed by table name.
+    pub(crate) batches: HashMap<String, MutableBatch>,
+    /// The number of data points written to `batches`.
+    pub(crate) num_points: usize,
+    /// The number of data points that were rejected.
+    pub(crate) num_rejected: usize,
+    /// The first error causing data to be rejected, if any.
+    pub(crate) first_error: Option<OtlpMetricError>,
+}
+
+impl ConvertedMetrics {
+    /// Returns the [`ExportMetricsPartialSuccess`] describing the rejected
+    /// data, or [`None`] if no data was rejected.
+    ///
+    /// Metrics of unsupported types are reported with a message but are not
+    /// included in the number of rejected data points, as their data points
+    /// are not decoded.
+    pub(crate) fn partial_success(&self) -> Option<ExportMetricsPartialSuccess> {
+        self.first_error
+            .as_ref()
+            .map(|e| ExportMetricsPartialSuccess {
+                rejected_data_points: self.num_rejected as i64,
+                error_message: e.to_string(),
+            })
+    }
+
+    fn reject(&mut self, n: usize, e: OtlpMetricError) {
+        self.num_rejected += n;
+        self.first_error.get_or_insert(e);
+    }
+}
+
+/// A field value of a data point.
+#[derive(Debug, Clone, Copy)]
+enum FieldValue {
+    F64(f64),
+    U64(u64),
+}
+
+/// Convert the metrics in `request` into [`MutableBatch`], rejecting any
+/// invalid data points.
+pub(crate) fn convert_request(request: &ExportMetricsServiceRequest) -> ConvertedMetrics {
+    let mut out = ConvertedMetrics::default();
+
+    for resource_metrics in &request.resource_metrics {
+        let mut tags = BTreeMap::new();
+        if let Some(resource) = &resource_metrics.resource {
+            extend_tags(&mut tags, &resource.attributes);
+        }
+
+        for scope_metrics in &resource_metrics.scope_metrics {
+            let mut tags = tags.clone();
+            if let Some(scope) = &scope_metrics.scope {
+                extend_tags(&mut tags, &scope.attributes);
+            }
+
+            for metric in &scope_metrics.metrics {
+                convert_metric(&mut out, &tags, metric);
+            }
+        }
+    }
+
+    // A batch may be empty if all the data points written to it failed.
+    out.batches.retain(|_, batch| batch.rows() > 0);
+
+    out
+}
+
+/// Write the data points of `metric` with the scope and resource `tags`.
+fn convert_metric(out: &mut ConvertedMetrics, tags: &BTreeMap<String, String>, metric: &Metric) {
+    let points: Vec<Result<_, _>> = match &metric.data {
+        Some(Data::Gauge(v)) => v
+            .data_points
+            .iter()
+            .map(|p| number_point(&metric.name, p))
+            .collect(),
+        Some(Data::Sum(v)) => v
+            .data_points
+            .iter()
+            .map(|p| number_point(&metric.name, p))
+            .collect(),
+        Some(Data::Histogram(v)) => v
+            .data_points
+            .iter()
+            .map(|p| histogram_point(&metric.name, p))
+            .collect(),
+        None => {
+            out.reject(0, OtlpMetricError::UnsupportedType(metric.name.clone()));
+            return;
+        }
+    };
+
+    if metric.name.is_empty() {
+        out.reject(points.len(), OtlpMetricError::MissingName);
+        return;
+    }
+
+    for point in points {
+        let written = point.and_then(|(attributes, fields, time)| {
+            let mut tags = tags.clone();
+            extend_tags(&mut tags, attributes);
+
+            let batch = out
+                .batches
+                .raw_entry_mut()
+                .from_key(metric.name.as_str())
+                .or_insert_with(|| (metric.name.clone(), MutableBatch::new()))
+                .1;
+            write_point(batch, &tags, &fields, time).map_err(|source| OtlpMetricError::Write {
+                metric: metric.name.clone(),
+                source,
+            })
+        });
+
+        match written {
+            Ok(()) => out.num_points += 1,
+            Err(e) => out.reject(1, e),
+        }
+    }
+}
+
+/// The attributes, fields and timestamp of a data point.
+type Point<'a> = (&'a [KeyValue], Vec<(String, FieldValue)>, i64);
+
+/// Extract the value of a gauge or sum data point.
+fn number_point<'a>(metric: &str, p: &'a NumberDataPoint) -> Result<Point<'a>, OtlpMetricError> {
+    let value = match &p.value {
+        Some(number_data_point::Value::AsDouble(v)) => *v,
+        Some(number_data_point::Value::AsInt(v)) => *v as f64,
+        None => return Err(OtlpMetricError::MissingValue(metric.to_string())),
+    };
+
+    Ok((
+        &p.attributes,
+        vec![(VALUE_FIELD.to_string(), FieldValue::F64(value))],
+        point_time(metric, p.time_unix_nano)?,
+    ))
+}
+
+/// Extract the count, sum, bounds and bucket counts of a histogram data
+/// point.
+fn histogram_point<'a>(
+    metric: &str,
+    p: &'a HistogramDataPoint,
+) -> Result<Point<'a>, OtlpMetricError> {
+    // A histogram with no buckets has no bounds.
+    if !p.bucket_counts.is_empty() && p.bucket_counts.len() != p.explicit_bounds.len() + 1 {
+        return Err(OtlpMetricError::InvalidBuckets(metric.to_string()));
+    }
+
+    let mut fields = vec![("count".to_string(), FieldValue::U64(p.count))];
+    for (name, value) in [("sum", p.sum), ("min", p.min), ("max", p.max)] {
+        if let Some(v) = value {
+            fields.push((name.to_string(), FieldValue::F64(v)));
+        }
+    }
+
+    let bounds = p
+        .explicit_bounds
+        .iter()
+        .map(|b| b.to_string())
+        .chain(iter::once("+Inf".to_string()));
+    fields.extend(bounds.zip(&p.bucket_counts).map(|(bound, count)| {
+        (
+            format!("{BUCKET_FIELD_PREFIX}{bound}"),
+            FieldValue::U64(*count),
+        )
+    }));
+
+    Ok((&p.attributes, fields, point_time(metric, p.time_unix_nano)?))
+}
+
+/// Validate the timestamp of a data point, which is required by OTLP.
+fn point_time(metric: &str, time_unix_nano: u64) -> Result<i64, OtlpMetricError> {
+    match time_unix_nano {
+        0 => Err(OtlpMetricError::MissingTime(metric.to_string())),
+        t => Ok(t.min(i64::MAX as u64) as i64),
+    }
+}
+
+/// Write a single row to `batch`.
+///
+/// If an error is returned, `batch` is left unchanged.
+fn write_point(
+    batch: &mut MutableBatch,
+    tags: &BTreeMap<String, String>,
+    fields: &[(String, FieldValue)],
+    time: i64,
+) -> Result<(), mutable_batch::writer::Error> {
+    let mut writer = Writer::new(batch, 1);
+
+    for (name, value) in tags {
+        writer.write_tag(name, None, iter::once(value.as_str()))?;
+    }
+    for (name, value) in fields {
+        match *value {
+            FieldValue::F64(v) => writer.write_f64(name, None, iter::once(v))?,
+            FieldValue::U64(v) => writer.write_u64(name, None, iter::once(v))?,
+        }
+    }
+    writer.write_time("time", iter::once(time))?;
+
+    writer.commit();
+    Ok(())
+}
+
+/// Add `attributes` to `tags`, replacing any existing tags of the same name.
+///
+/// Attributes with an empty value are treated as absent.
+fn extend_tags(tags: &mut BTreeMap<String, String>, attributes: &[KeyValue]) {
+    for kv in attributes {
+        match kv.value.as_ref().and_then(attribute_string) {
+            Some(v) if !v.is_empty() && !kv.key.is_empty() => {
+                tags.insert(kv.key.clone(), v);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Render an attribute value as a tag value.
+///
+/// Arrays and key/value lists are rendered in a JSON-like form, and bytes
+/// as lower-case hex.
+fn attribute_string(v: &AnyValue) -> Option<String> {
+    Some(match v.value.as_ref()? {
+        Value::StringValue(v) => v.clone(),
+        Value::BoolValue(v) => v.to_string(),
+        Value::IntValue(v) => v.to_string(),
+        Value::DoubleValue(v) => v.to_string(),
+        Value::BytesValue(v) => v.iter().map(|b| format!("{b:02x}")).collect(),
+        Value::ArrayValue(v) => format!(
+            "[{}]",
+            v.values
+                .iter()
+                .filter_map(attribute_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        ),
+        Value::KvlistValue(v) => format!(
+            "{{{}}}",
+            v.values
+                .iter()
+                .filter_map(|kv| Some(format!(
+                    "{}:{}",
+                    kv.key,
+                    attribute_string(kv.value.as_ref()?)?
+                )))
+                .collect::<Vec<_>>()
+                .join(",")
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_util::assert_batches_sorted_eq;
+    use assert_matches::assert_matches;
+    use generated_types::opentelemetry::proto::{
+        common::v1::{ArrayValue, InstrumentationScope, KeyValueList},
+        metrics::v1::{Gauge, Histogram, ResourceMetrics, ScopeMetrics, Sum},
+        resource::v1::Resource,
+    };
+    use schema::Projection;
+
+    use super::*;
+
+    fn attr(key: &str, value: &str) -> KeyValue {
+        KeyValue {
+            key: key.to_string(),
+            value: Some(AnyValue {
+                value: Some(Value::StringValue(value.to_string())),
+            }),
+        }
+    }
+
+    fn number(attributes: Vec<KeyValue>, value: f64, time_unix_nano: u64) -> NumberDataPoint {
+        NumberDataPoint {
+            attributes,
+            time_unix_nano,
+            value: Some(number_data_point::Value::AsDouble(value)),
+            ..Default::default()
+        }
+    }
+
+    fn request(metrics: Vec<Metric>) -> ExportMetricsServiceRequest {
+        ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                resource: Some(Resource {
+                    attributes: vec![attr("service.name", "router"), attr("host", "a")],
+                    dropped_attributes_count: 0,
+                }),
+                scope_metrics: vec![ScopeMetrics {
+                    scope: Some(InstrumentationScope {
+                        name: "scope".to_string(),
+                        attributes: vec![attr("host", "b")],
+                        ..Default::default()
+                    }),
+                    metrics,
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_convert_number_metrics() {
+        let got = convert_request(&request(vec![
+            Metric {
+                name: "cpu".to_string(),
+                data: Some(Data::Gauge(Gauge {
+                    data_points: vec![
+                        number(vec![attr("cpu", "0")], 1.5, 1),
+                        NumberDataPoint {
+                            attributes: vec![attr("cpu", "1"), attr("host", "c")],
+                            time_unix_nano: 2,
+                            value: Some(number_data_point::Value::AsInt(2)),
+                            ..Default::default()
+                        },
+                    ],
+                })),
+                ..Default::default()
+            },
+            Metric {
+                name: "requests".to_string(),
+                data: Some(Data::Sum(Sum {
+                    data_points: vec![number(vec![], 42.0, 3)],
+                    aggregation_temporality: 2,
+                    is_monotonic: true,
+                })),
+                ..Default::default()
+            },
+        ]));
+
+        assert_eq!(got.num_points, 3);
+        assert_eq!(got.num_rejected, 0);
+        assert!(got.partial_success().is_none());
+
+        assert_batches_sorted_eq!(
+            [
+                "+-----+------+--------------+--------------------------------+-------+",
+                "| cpu | host | service.name | time                           | value |",
+                "+-----+------+--------------+--------------------------------+-------+",
+                "| 0   | b    | router       | 1970-01-01T00:00:00.000000001Z | 1.5   |",
+                "| 1   | c    | router       | 1970-01-01T00:00:00.000000002Z | 2.0   |",
+                "+-----+------+--------------+--------------------------------+-------+",
+            ],
+            &[got.batches["cpu"].to_arrow(Projection::All).unwrap()]
+        );
+        assert_batches_sorted_eq!(
+            [
+                "+------+--------------+--------------------------------+-------+",
+                "| host | service.name | time                           | value |",
+                "+------+--------------+--------------------------------+-------+",
+                "| b    | router       | 1970-01-01T00:00:00.000000003Z | 42.0  |",
+                "+------+--------------+--------------------------------+-------+",
+            ],
+            &[got.batches["requests"].to_arrow(Projection::All).unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_convert_histogram() {
+        let got = convert_request(&request(vec![Metric {
+            name: "latency".to_string(),
+            data: Some(Data::Histogram(Histogram {
+                data_points: vec![HistogramDataPoint {
+                    time_unix_nano: 1,
+                    count: 6,
+                    sum: Some(7.5),
+                    bucket_counts: vec![1, 2, 3],
+                    explicit_bounds: vec![0.5, 1.0],
+                    ..Default::default()
+                }],
+                aggregation_temporality: 1,
+            })),
+            ..Default::default()
+        }]));
+
+        assert_eq!(got.num_points, 1);
+        assert_batches_sorted_eq!(
+            [
+                "+-------------+------------+----------+-------+------+--------------+-----+--------------------------------+",
+                "| bucket_+Inf | bucket_0.5 | bucket_1 | count | host | service.name | sum | time                           |",
+                "+-------------+------------+----------+-------+------+--------------+-----+--------------------------------+",
+                "| 3           | 1          | 2        | 6     | b    | router       | 7.5 | 1970-01-01T00:00:00.000000001Z |",
+                "+-------------+------------+----------+-------+------+--------------+-----+--------------------------------+",
+            ],
+            &[got.batches["latency"].to_arrow(Projection::All).unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_convert_rejected() {
+        let got = convert_request(&request(vec![
+            Metric {
+                name: "cpu".to_string(),
+                data: Some(Data::Gauge(Gauge {
+                    data_points: vec![
+                        number(vec![], 1.0, 1),
+                        // No timestamp
+                        number(vec![], 2.0, 0),
+                        // No value
+                        NumberDataPoint {
+                            time_unix_nano: 3,
+                            ..Default::default()
+                        },
+                        // An attribute conflicting with the value field
+                        number(vec![attr(VALUE_FIELD, "a")], 4.0, 4),
+                    ],
+                })),
+                ..Default::default()
+            },
+            Metric {
+                name: String::new(),
+                data: Some(Data::Gauge(Gauge {
+                    data_points: vec![number(vec![], 1.0, 1)],
+                })),
+                ..Default::default()
+            },
+            Metric {
+                name: "latency".to_string(),
+                data: Some(Data::Histogram(Histogram {
+                    data_points: vec![HistogramDataPoint {
+                        time_unix_nano: 1,
+                        bucket_counts: vec![1],
+                        explicit_bounds: vec![0.5],
+                        ..Default::default()
+                    }],
+                    aggregation_temporality: 1,
+                })),
+                ..Default::default()
+            },
+            // A metric of a type omitted from the proto definitions.
+            Metric {
+                name: "summary".to_string(),
+                ..Default::default()
+            },
+        ]));
+
+        assert_eq!(got.num_points, 1);
+        assert_eq!(got.num_rejected, 5);
+        assert_eq!(got.batches["cpu"].rows(), 1);
+        assert!(!got.batches.contains_key("latency"));
+        assert_matches!(got.first_error, Some(OtlpMetricError::MissingTime(m)) => {
+            assert_eq!(m, "cpu");
+        });
+        assert_eq!(got.partial_success().unwrap().rejected_data_points, 5);
+    }
+
+    #[test]
+    fn test_attribute_string() {
+        let v = AnyValue {
+            value: Some(Value::KvlistValue(KeyValueList {
+                values: vec![
+                    KeyValue {
+                        key: "a".to_string(),
+                        value: Some(AnyValue {
+                            value: Some(Value::ArrayValue(ArrayValue {
+                                values: vec![
+                                    AnyValue {
+                                        value: Some(Value::IntValue(1)),
+                                    },
+                                    AnyValue {
+                                        value: Some(Value::BoolValue(true)),
+                                    },
+                                ],
+                            })),
+                        }),
+                    },
+                    KeyValue {
+                        key: "b".to_string(),
+                        value: Some(AnyValue {
+                            value: Some(Value::BytesValue(vec![0xde, 0xad])),
+                        }),
+                    },
+                ],
+            })),
+        };
+        assert_eq!(attribute_string(&v).unwrap(), "{a:[1,true],b:dead}");
+    }
+}
@@ -1,30 +1,38 @@
 //! HTTP service implementations for `router`.
 
+pub mod delete;
+pub mod prom;
+pub mod records;
 pub mod write;
 
-use std::{str::Utf8Error, time::Instant};
+use std::{borrow::Cow, str::Utf8Error, time::Instant};
 
 use bytes::{Bytes, BytesMut};
 use futures::StreamExt;
 use hashbrown::HashMap;
 use hyper::{header::CONTENT_ENCODING, Body, Method, Request, Response, StatusCode};
 use iox_time::{SystemProvider, TimeProvider};
-use metric::{DurationHistogram, U64Counter};
+use metric::{DurationHistogram, Metric, U64Counter};
 use mutable_batch::MutableBatch;
-use mutable_batch_lp::LinesConverter;
+use mutable_batch_lp::{LinesConverter, RejectedLine};
 use observability_deps::tracing::*;
 use thiserror::Error;
 use tokio::sync::{Semaphore, TryAcquireError};
 use trace::ctx::SpanContext;
 
-use self::write::{
-    multi_tenant::MultiTenantExtractError, single_tenant::SingleTenantExtractError, WriteParams,
-    WriteRequestUnifier,
+use self::{
+    delete::{DeleteParseError, DeleteRequest},
+    prom::PromWriteError,
+    records::{RecordFormat, RecordParams, RecordWriteError},
+    write::{
+        multi_tenant::MultiTenantExtractError, single_tenant::SingleTenantExtractError,
+        WriteParams, WriteRequestUnifier,
+    },
 };
 use crate::{
     dml_handlers::{
         client::RpcWriteClientError, DmlError, DmlHandler, PartitionError, RetentionError,
-        RpcWriteError, SchemaError,
+        RpcWriteError, SchemaError, TombstoneError,
     },
     namespace_resolver::NamespaceResolver,
 };
@@ -36,9 +44,13 @@ pub enum Error {
     #[error("not found")]
     NoHandler,
 
-    /// A delete request was rejected (not supported).
-    #[error("deletes are not supported")]
-    DeletesUnsupported,
+    /// The delete request body is invalid.
+    #[error(transparent)]
+    ParseDelete(#[from] DeleteParseError),
+
+    /// The table named in a delete request does not exist.
+    #[error("table {0} does not exist")]
+    TableNotFound(String),
 
     /// An error parsing a single-tenant HTTP request.
     #[error(transparent)]
@@ -76,6 +88,26 @@ pub enum Error {
     #[error("failed to parse line protocol: {0}")]
     ParseLineProtocol(mutable_batch_lp::Error),
 
+    /// Some lines of the provided line protocol could not be decoded, and
+    /// were dropped while the remaining lines were written.
+    ///
+    /// Lines are only dropped when they fail to parse or conflict with other
+    /// lines of the request. Conflicts with the namespace schema are found
+    /// after the lines are decoded, and reject the whole request.
+    #[error(
+        "partial write has occurred, errors encountered on line(s): {}",
+        format_rejected_lines(.0)
+    )]
+    PartialWrite(Vec<RejectedLine>),
+
+    /// The Prometheus remote write request body is invalid.
+    #[error(transparent)]
+    ParsePromWrite(#[from] PromWriteError),
+
+    /// The CSV or NDJSON write request body is invalid.
+    #[error(transparent)]
+    ParseRecords(#[from] RecordWriteError),
+
     /// An error returned from the [`DmlHandler`].
     #[error("dml handler error: {0}")]
     DmlHandler(#[from] DmlError),
@@ -107,12 +139,16 @@ impl Error {
     pub fn as_status_code(&self) -> StatusCode {
         match self {
             Error::NoHandler => StatusCode::NOT_FOUND,
-            Error::DeletesUnsupported => StatusCode::NOT_IMPLEMENTED,
+            Error::ParseDelete(_) => StatusCode::BAD_REQUEST,
+            Error::TableNotFound(_) => StatusCode::NOT_FOUND,
             Error::ClientHangup(_) => StatusCode::BAD_REQUEST,
             Error::InvalidGzip(_) => StatusCode::BAD_REQUEST,
             Error::NonUtf8ContentHeader(_) => StatusCode::BAD_REQUEST,
             Error::NonUtf8Body(_) => StatusCode::BAD_REQUEST,
             Error::ParseLineProtocol(_) => StatusCode::BAD_REQUEST,
+            Error::PartialWrite(_) => StatusCode::BAD_REQUEST,
+            Error::ParsePromWrite(_) => StatusCode::BAD_REQUEST,
+            Error::ParseRecords(_) => StatusCode::BAD_REQUEST,
             Error::RequestSizeExceeded(_) => StatusCode::PAYLOAD_TOO_LARGE,
             Error::InvalidContentEncoding(_) => {
                 // https://www.rfc-editor.org/rfc/rfc7231#section-6.5.13
@@ -133,6 +169,30 @@ impl Error {
     }
 }
 
+/// The maximum number of rejected lines described in a
+/// [`Error::PartialWrite`] message.
+const MAX_REPORTED_REJECTED_LINES: usize = 100;
+
+/// Render the `rejected` lines of a partial write, truncating the list after
+/// [`MAX_REPORTED_REJECTED_LINES`] entries.
+fn format_rejected_lines(rejected: &[RejectedLine]) -> String {
+    let mut out = rejected
+        .iter()
+        .take(MAX_REPORTED_REJECTED_LINES)
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ");
+
+    if rejected.len() > MAX_REPORTED_REJECTED_LINES {
+        out.push_str(&format!(
+            " (and {} more)",
+            rejected.len() - MAX_REPORTED_REJECTED_LINES
+        ));
+    }
+
+    out
+}
+
 impl From<&DmlError> for StatusCode {
     fn from(e: &DmlError) -> Self {
         match e {
@@ -166,6 +226,7 @@ impl From<&DmlError> for StatusCode {
                 RpcWriteClientError::UpstreamNotConnected(_),
             )) => StatusCode::SERVICE_UNAVAILABLE,
             DmlError::RpcWrite(RpcWriteError::Timeout(_)) => StatusCode::GATEWAY_TIMEOUT,
+            DmlError::Tombstone(TombstoneError::Catalog(_)) => StatusCode::INTERNAL_SERVER_ERROR,
             DmlError::RpcWrite(
                 RpcWriteError::NoHealthyUpstreams
                 | RpcWriteError::NotEnoughReplicas
@@ -184,6 +245,7 @@ impl From<&DmlError> for StatusCode {
 #[derive(Debug)]
 pub struct HttpDelegate<D, N, T = SystemProvider> {
     max_request_bytes: usize,
+    partial_writes_enabled: bool,
     time_provider: T,
     namespace_resolver: N,
     dml_handler: D,
@@ -203,6 +265,10 @@ pub struct HttpDelegate<D, N, T = SystemProvider> {
     write_metric_fields: U64Counter,
     write_metric_tables: U64Counter,
     write_metric_body_size: U64Counter,
+    write_metric_rejected_lines: Metric<U64Counter>,
+    prom_write_metric_samples: U64Counter,
+    write_metric_records: U64Counter,
+    delete_metric_body_size: U64Counter,
     request_limit_rejected: U64Counter,
 }
 
@@ -212,9 +278,15 @@ impl<D, N> HttpDelegate<D, N, SystemProvider> {
     ///
     /// HTTP request bodies are limited to `max_request_bytes` in size,
     /// returning an error if exceeded.
+    ///
+    /// If `partial_writes_enabled` is true, the valid lines of a line protocol
+    /// write containing invalid lines are written, and the invalid lines are
+    /// reported in an [`Error::PartialWrite`]. Schema conflicts with the
+    /// namespace still reject the whole write.
     pub fn new(
         max_request_bytes: usize,
         max_requests: usize,
+        partial_writes_enabled: bool,
         namespace_resolver: N,
         dml_handler: D,
         metrics: &metric::Registry,
@@ -244,6 +316,28 @@ impl<D, N> HttpDelegate<D, N, SystemProvider> {
                 "cumulative byte size of successfully routed (decompressed) line protocol write requests",
             )
             .recorder(&[]);
+        let write_metric_rejected_lines = metrics.register_metric::<U64Counter>(
+            "http_write_rejected_lines",
+            "cumulative number of line protocol lines dropped from partial writes",
+        );
+        let prom_write_metric_samples = metrics
+            .register_metric::<U64Counter>(
+                "http_prom_write_samples",
+                "cumulative number of prometheus remote write samples successfully routed",
+            )
+            .recorder(&[]);
+        let write_metric_records = metrics
+            .register_metric::<U64Counter>(
+                "http_write_records",
+                "cumulative number of CSV and NDJSON records successfully routed",
+            )
+            .recorder(&[]);
+        let delete_metric_body_size = metrics
+            .register_metric::<U64Counter>(
+                "http_delete_body_bytes",
+                "cumulative byte size of successfully routed (decompressed) delete requests",
+            )
+            .recorder(&[]);
         let request_limit_rejected = metrics
             .register_metric::<U64Counter>(
                 "http_request_limit_rejected",
@@ -259,6 +353,7 @@ impl<D, N> HttpDelegate<D, N, SystemProvider> {
 
         Self {
             max_request_bytes,
+            partial_writes_enabled,
             time_provider: SystemProvider::default(),
             namespace_resolver,
             write_request_mode_handler,
@@ -269,11 +364,33 @@ impl<D, N> HttpDelegate<D, N, SystemProvider> {
             write_metric_fields,
             write_metric_tables,
             write_metric_body_size,
+            write_metric_rejected_lines,
+            prom_write_metric_samples,
+            write_metric_records,
+            delete_metric_body_size,
             request_limit_rejected,
         }
     }
 }
 
+impl<D, N, T> HttpDelegate<D, N, T> {
+    /// Return the [`DmlHandler`] requests are passed to.
+    pub fn dml_handler(&self) -> &D {
+        &self.dml_handler
+    }
+
+    /// Return the [`NamespaceResolver`] used to resolve the namespace of
+    /// write requests.
+    pub fn namespace_resolver(&self) -> &N {
+        &self.namespace_resolver
+    }
+
+    /// Return the maximum size of a (decompressed) request body, in bytes.
+    pub fn max_request_bytes(&self) -> usize {
+        self.max_request_bytes
+    }
+}
+
 impl<D, N, T> HttpDelegate<D, N, T>
 where
     D: DmlHandler<WriteInput = HashMap<String, MutableBatch>, WriteOutput = ()>,
@@ -304,13 +421,26 @@ where
         match (req.method(), req.uri().path()) {
             (&Method::POST, "/write") => {
                 let dml_info = self.write_request_mode_handler.parse_v1(&req).await?;
-                self.write_handler(req, dml_info).await
+                match RecordFormat::from_headers(req.headers()) {
+                    Some(format) => self.record_write_handler(req, dml_info, format).await,
+                    None => self.write_handler(req, dml_info).await,
+                }
             }
             (&Method::POST, "/api/v2/write") => {
                 let dml_info = self.write_request_mode_handler.parse_v2(&req).await?;
-                self.write_handler(req, dml_info).await
+                match RecordFormat::from_headers(req.headers()) {
+                    Some(format) => self.record_write_handler(req, dml_info, format).await,
+                    None => self.write_handler(req, dml_info).await,
+                }
+            }
+            (&Method::POST, "/api/v1/prom/write") => {
+                let dml_info = self.write_request_mode_handler.parse_v2(&req).await?;
+                self.prom_write_handler(req, dml_info).await
+            }
+            (&Method::POST, "/api/v2/delete") => {
+                let dml_info = self.write_request_mode_handler.parse_v2(&req).await?;
+                self.delete_handler(req, dml_info).await
             }
-            (&Method::POST, "/api/v2/delete") => return Err(Error::DeletesUnsupported),
             _ => return Err(Error::NoHandler),
         }
         .map(|_summary| {
@@ -344,8 +474,35 @@ where
 
         let mut converter = LinesConverter::new(default_time);
         converter.set_timestamp_base(write_info.precision.timestamp_base());
-        let (batches, stats) = match converter.write_lp(body).and_then(|_| converter.finish()) {
+
+        // In partial write mode, invalid lines are dropped and reported to the
+        // user once the remaining lines have been written.
+        let (converted, rejected) = if self.partial_writes_enabled {
+            let rejected = converter.write_lp_partial(body);
+            (converter.finish(), rejected)
+        } else {
+            (
+                converter.write_lp(body).and_then(|_| converter.finish()),
+                vec![],
+            )
+        };
+
+        if !rejected.is_empty() {
+            debug!(
+                num_rejected=rejected.len(),
+                namespace=%write_info.namespace,
+                "dropping invalid lines from partial write",
+            );
+            self.write_metric_rejected_lines
+                .recorder([("namespace", Cow::Owned(write_info.namespace.to_string()))])
+                .inc(rejected.len() as _);
+        }
+
+        let (batches, stats) = match converted {
             Ok(v) => v,
+            Err(mutable_batch_lp::Error::EmptyPayload) if !rejected.is_empty() => {
+                return Err(Error::PartialWrite(rejected));
+            }
             Err(mutable_batch_lp::Error::EmptyPayload) => {
                 debug!("nothing to write");
                 return Ok(());
@@ -383,6 +540,200 @@ where
         self.write_metric_tables.inc(num_tables as _);
         self.write_metric_body_size.inc(body.len() as _);
 
+        if !rejected.is_empty() {
+            return Err(Error::PartialWrite(rejected));
+        }
+
+        Ok(())
+    }
+
+    /// Write the samples of the [Prometheus remote write] request in `req` to
+    /// the namespace identified by `write_info`.
+    ///
+    /// [Prometheus remote write]:
+    ///     https://prometheus.io/docs/concepts/remote_write_spec/
+    async fn prom_write_handler(
+        &self,
+        mut req: Request<Body>,
+        write_info: WriteParams,
+    ) -> Result<(), Error> {
+        let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
+
+        trace!(
+            namespace=%write_info.namespace,
+            "processing prometheus remote write request"
+        );
+
+        // Remote write bodies are always snappy compressed, and the
+        // (mandatory) content encoding header describing this is decoded
+        // below, rather than when reading the body.
+        if req
+            .headers()
+            .get(&CONTENT_ENCODING)
+            .is_some_and(|v| v.as_bytes().eq_ignore_ascii_case(b"snappy"))
+        {
+            req.headers_mut().remove(&CONTENT_ENCODING);
+        }
+
+        let body = self.read_body(req).await?;
+        let (batches, stats) = prom::decode_write_request(&body, self.max_request_bytes)?;
+        if batches.is_empty() {
+            debug!("nothing to write");
+            return Ok(());
+        }
+
+        let num_tables = batches.len();
+        debug!(
+            num_series=stats.num_series,
+            num_samples=stats.num_samples,
+            num_tables,
+            body_size=body.len(),
+            namespace=%write_info.namespace,
+            "routing prometheus remote write",
+        );
+
+        let namespace_schema = self
+            .namespace_resolver
+            .get_namespace_schema(&write_info.namespace)
+            .await?;
+
+        self.dml_handler
+            .write(&write_info.namespace, namespace_schema, batches, span_ctx)
+            .await
+            .map_err(Into::into)?;
+
+        self.prom_write_metric_samples.inc(stats.num_samples as _);
+        self.write_metric_tables.inc(num_tables as _);
+
+        Ok(())
+    }
+
+    /// Write the CSV or NDJSON records in `req` to the namespace identified by
+    /// `write_info`.
+    ///
+    /// The columns of the records are described by the query parameters of
+    /// the request, or a CSV annotation row - see [`records`] for details.
+    async fn record_write_handler(
+        &self,
+        req: Request<Body>,
+        write_info: WriteParams,
+        format: RecordFormat,
+    ) -> Result<(), Error> {
+        let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
+
+        trace!(
+            namespace=%write_info.namespace,
+            ?format,
+            "processing record write request"
+        );
+
+        let params = RecordParams::try_from_query(req.uri().query())?;
+        let body = self.read_body(req).await?;
+
+        // The time, in nanoseconds since the epoch, to assign to any records
+        // that don't contain a timestamp
+        let default_time = self.time_provider.now().timestamp_nanos();
+
+        let (batches, stats) = records::decode_records(
+            format,
+            &body,
+            &params,
+            default_time,
+            write_info.precision.timestamp_base(),
+        )?;
+        if batches.is_empty() {
+            debug!("nothing to write");
+            return Ok(());
+        }
+
+        let num_tables = batches.len();
+        debug!(
+            num_records=stats.num_records,
+            num_fields=stats.num_fields,
+            num_tables,
+            ?format,
+            body_size=body.len(),
+            namespace=%write_info.namespace,
+            "routing record write",
+        );
+
+        let namespace_schema = self
+            .namespace_resolver
+            .get_namespace_schema(&write_info.namespace)
+            .await?;
+
+        self.dml_handler
+            .write(&write_info.namespace, namespace_schema, batches, span_ctx)
+            .await
+            .map_err(Into::into)?;
+
+        self.write_metric_records.inc(stats.num_records as _);
+        self.write_metric_fields.inc(stats.num_fields as _);
+        self.write_metric_tables.inc(num_tables as _);
+        self.write_metric_body_size.inc(body.len() as _);
+
+        Ok(())
+    }
+
+    /// Apply the [V2 delete request] in `req` to the namespace identified by
+    /// `params`.
+    ///
+    /// If the delete predicate does not restrict the delete to a single
+    /// measurement, the delete is applied to every table in the namespace
+    /// schema.
+    ///
+    /// [V2 delete request]:
+    ///     https://docs.influxdata.com/influxdb/v2.6/api/#operation/PostDelete
+    async fn delete_handler(&self, req: Request<Body>, params: WriteParams) -> Result<(), Error> {
+        let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
+
+        trace!(
+            namespace=%params.namespace,
+            "processing delete request"
+        );
+
+        let body = self.read_body(req).await?;
+        let delete = DeleteRequest::try_from_json(&body)?;
+
+        let namespace_schema = self
+            .namespace_resolver
+            .get_namespace_schema(&params.namespace)
+            .await?;
+
+        let table_ids = match &delete.table_name {
+            Some(name) => vec![
+                namespace_schema
+                    .tables
+                    .get(name)
+                    .ok_or_else(|| Error::TableNotFound(name.clone()))?
+                    .id,
+            ],
+            None => namespace_schema.tables.values().map(|t| t.id).collect(),
+        };
+
+        debug!(
+            namespace=%params.namespace,
+            table_name=?delete.table_name,
+            n_tables=table_ids.len(),
+            predicate=?delete.predicate,
+            "routing delete",
+        );
+
+        for table_id in table_ids {
+            self.dml_handler
+                .delete(
+                    &params.namespace,
+                    namespace_schema.id,
+                    table_id,
+                    &delete.predicate,
+                    span_ctx.clone(),
+                )
+                .await
+                .map_err(Into::into)?;
+        }
+
+        self.delete_metric_body_size.inc(body.len() as _);
+
         Ok(())
     }
 
@@ -453,7 +804,7 @@ mod tests {
         NamespaceId, NamespaceName, NamespaceNameError, OrgBucketMappingError, TableId,
     };
     use flate2::{write::GzEncoder, Compression};
-    use hyper::header::HeaderValue;
+    use hyper::header::{HeaderValue, CONTENT_TYPE};
     use metric::{Attributes, Metric};
     use mutable_batch::column::ColumnData;
     use mutable_batch_lp::LineWriteError;
@@ -478,6 +829,7 @@ mod tests {
 
     const MAX_BYTES: usize = 1024;
     const NAMESPACE_ID: NamespaceId = NamespaceId::new(42);
+    const TABLE_ID: TableId = TableId::new(24);
     static NAMESPACE_NAME: &str = "bananas_test";
 
     fn assert_metric_hit(metrics: &metric::Registry, name: &'static str, value: Option<u64>) {
@@ -582,14 +934,18 @@ mod tests {
                     test_http_handler!(encoding_header=$encoding, request);
 
                     let mock_namespace_resolver = MockNamespaceResolver::default()
-                        .with_mapping(NAMESPACE_NAME, NAMESPACE_ID);
+                        .with_mapping(NAMESPACE_NAME, NAMESPACE_ID)
+                        .with_table(NAMESPACE_NAME, "platanos", TABLE_ID)
+                        .with_table(NAMESPACE_NAME, "bananas", TableId::new(TABLE_ID.get() + 1));
                     let dml_handler = Arc::new(MockDmlHandler::default()
                         .with_write_return($dml_write_handler)
+                        .with_delete_return($dml_delete_handler)
                     );
                     let metrics = Arc::new(metric::Registry::default());
                     let delegate = HttpDelegate::new(
                         MAX_BYTES,
                         100,
+                        false,
                         mock_namespace_resolver,
                         Arc::clone(&dml_handler),
                         &metrics,
@@ -913,6 +1269,121 @@ mod tests {
         want_dml_calls = []
     );
 
+    // Wrapper over test_http_handler specifically for delete requests.
+    macro_rules! test_delete_handler {
+        (
+            $name:ident,
+            query_string = $query_string:expr,   // Request URI query string
+            body = $body:expr,                   // Request body content
+            dml_handler = $dml_handler:expr,     // DML delete handler response (if called)
+            want_result = $want_result:pat,
+            want_dml_calls = $($want_dml_calls:tt )+
+        ) => {
+            paste::paste! {
+                test_http_handler!(
+                    [<delete_ $name>],
+                    uri = format!("https://bananas.example/api/v2/delete{}", $query_string),
+                    body = $body,
+                    dml_write_handler = [],
+                    dml_delete_handler = $dml_handler,
+                    want_result = $want_result,
+                    want_dml_calls = $($want_dml_calls)+
+                );
+            }
+        };
+    }
+
+    test_delete_handler!(
+        ok,
+        query_string = "?org=bananas&bucket=test",
+        body = r#"{"start":"2021-04-01T14:00:00Z","stop":"2021-04-02T14:00:00Z", "predicate":"_measurement=platanos and tag1=A"}"#.as_bytes(),
+        dml_handler = [Ok(())],
+        want_result = Ok(_),
+        want_dml_calls = [
+            MockDmlHandlerCall::Delete { namespace, namespace_id, table_id, predicate }
+        ] => {
+            assert_eq!(namespace, NAMESPACE_NAME);
+            assert_eq!(*namespace_id, NAMESPACE_ID);
+            assert_eq!(*table_id, TABLE_ID);
+            assert_eq!(predicate.exprs.len(), 1);
+            assert_eq!(predicate.exprs[0].column, "tag1");
+        }
+    );
+
+    test_delete_handler!(
+        all_tables,
+        query_string = "?org=bananas&bucket=test",
+        body = r#"{"start":"2021-04-01T14:00:00Z","stop":"2021-04-02T14:00:00Z"}"#.as_bytes(),
+        dml_handler = [Ok(()), Ok(())],
+        want_result = Ok(_),
+        want_dml_calls = [
+            MockDmlHandlerCall::Delete { namespace: ns_a, table_id: table_a, .. },
+            MockDmlHandlerCall::Delete { namespace: ns_b, table_id: table_b, .. },
+        ] => {
+            assert_eq!(ns_a, NAMESPACE_NAME);
+            assert_eq!(ns_b, NAMESPACE_NAME);
+            assert_ne!(table_a, table_b);
+        }
+    );
+
+    test_delete_handler!(
+        table_not_found,
+        query_string = "?org=bananas&bucket=test",
+        body = r#"{"start":"2021-04-01T14:00:00Z","stop":"2021-04-02T14:00:00Z", "predicate":"_measurement=wat"}"#.as_bytes(),
+        dml_handler = [],
+        want_result = Err(Error::TableNotFound(_)),
+        want_dml_calls = [] // None
+    );
+
+    test_delete_handler!(
+        invalid_delete_body,
+        query_string = "?org=bananas&bucket=test",
+        body = r#"{wat}"#.as_bytes(),
+        dml_handler = [],
+        want_result = Err(Error::ParseDelete(DeleteParseError::InvalidJson(_))),
+        want_dml_calls = [] // None
+    );
+
+    test_delete_handler!(
+        invalid_time_range,
+        query_string = "?org=bananas&bucket=test",
+        body = r#"{"start":"2021-04-02T14:00:00Z","stop":"2021-04-01T14:00:00Z"}"#.as_bytes(),
+        dml_handler = [],
+        want_result = Err(Error::ParseDelete(DeleteParseError::InvalidPredicate(_))),
+        want_dml_calls = [] // None
+    );
+
+    test_delete_handler!(
+        no_query_params,
+        query_string = "",
+        body = r#"{"start":"2021-04-01T14:00:00Z","stop":"2021-04-02T14:00:00Z"}"#.as_bytes(),
+        dml_handler = [],
+        want_result = Err(Error::MultiTenantError(
+            MultiTenantExtractError::ParseV2Request(V2WriteParseError::NoQueryParams)
+        )),
+        want_dml_calls = [] // None
+    );
+
+    test_delete_handler!(
+        db_not_found,
+        query_string = "?org=bananas&bucket=wat",
+        body = r#"{"start":"2021-04-01T14:00:00Z","stop":"2021-04-02T14:00:00Z"}"#.as_bytes(),
+        dml_handler = [],
+        want_result = Err(Error::NamespaceResolver(_)),
+        want_dml_calls = [] // None
+    );
+
+    test_delete_handler!(
+        dml_handler_error,
+        query_string = "?org=bananas&bucket=test",
+        body = r#"{"start":"2021-04-01T14:00:00Z","stop":"2021-04-02T14:00:00Z", "predicate":"_measurement=platanos"}"#.as_bytes(),
+        dml_handler = [Err(DmlError::Internal("💣".into()))],
+        want_result = Err(Error::DmlHandler(DmlError::Internal(_))),
+        want_dml_calls = [MockDmlHandlerCall::Delete { namespace, .. }] => {
+            assert_eq!(namespace, NAMESPACE_NAME);
+        }
+    );
+
     test_http_handler!(
         not_found,
         uri = "https://bananas.example/wat",
@@ -1056,6 +1527,7 @@ mod tests {
         let delegate = Arc::new(HttpDelegate::new(
             MAX_BYTES,
             1,
+            false,
             mock_namespace_resolver,
             Arc::clone(&dml_handler),
             &metrics,
@@ -1188,6 +1660,7 @@ mod tests {
         let delegate = HttpDelegate::new(
             MAX_BYTES,
             1,
+            false,
             mock_namespace_resolver,
             Arc::clone(&dml_handler),
             &metrics,
@@ -1230,6 +1703,7 @@ mod tests {
         let delegate = HttpDelegate::new(
             MAX_BYTES,
             1,
+            false,
             mock_namespace_resolver,
             Arc::clone(&dml_handler),
             &metrics,
@@ -1276,6 +1750,300 @@ mod tests {
         );
     }
 
+    /// Build a multi-tenant [`HttpDelegate`] with partial writes enabled.
+    fn partial_write_delegate(
+        dml_handler: Arc<MockDmlHandler<HashMap<String, MutableBatch>>>,
+        metrics: &metric::Registry,
+    ) -> HttpDelegate<Arc<MockDmlHandler<HashMap<String, MutableBatch>>>, MockNamespaceResolver>
+    {
+        let mock_namespace_resolver =
+            MockNamespaceResolver::default().with_mapping(NAMESPACE_NAME, NAMESPACE_ID);
+
+        HttpDelegate::new(
+            MAX_BYTES,
+            1,
+            true,
+            mock_namespace_resolver,
+            dml_handler,
+            metrics,
+            Box::<MultiTenantRequestUnifier>::default(),
+        )
+    }
+
+    fn assert_rejected_lines_metric(metrics: &metric::Registry, want: u64) {
+        let got = metrics
+            .get_instrument::<Metric<U64Counter>>("http_write_rejected_lines")
+            .expect("failed to read metric")
+            .get_observer(&Attributes::from(&[("namespace", NAMESPACE_NAME)]))
+            .expect("failed to get observer")
+            .fetch();
+        assert_eq!(got, want);
+    }
+
+    /// Assert that, in partial write mode, the valid lines of a write are
+    /// passed to the DML handler and the invalid lines are reported.
+    #[tokio::test]
+    async fn test_partial_write() {
+        let dml_handler = Arc::new(MockDmlHandler::default().with_write_return([Ok(())]));
+        let metrics = Arc::new(metric::Registry::default());
+        let delegate = partial_write_delegate(Arc::clone(&dml_handler), &metrics);
+
+        let request = Request::builder()
+            .uri("https://bananas.example/api/v2/write?org=bananas&bucket=test")
+            .method("POST")
+            .body(Body::from(
+                "platanos,tag1=A val=42i 123456\n\
+                platanos,tag1=B val=\n\
+                platanos,tag1=C val=4.2 123456\n\
+                bananas,tag1=D val=24i 123456",
+            ))
+            .unwrap();
+
+        let got = delegate.route(request).await;
+        assert_matches!(got, Err(Error::PartialWrite(rejected)) => {
+            let lines = rejected.iter().map(|r| r.line).collect::<Vec<_>>();
+            assert_eq!(lines, [2, 3]);
+            assert_eq!(
+                Error::PartialWrite(rejected).as_status_code(),
+                StatusCode::BAD_REQUEST
+            );
+        });
+
+        assert_matches!(
+            dml_handler.calls().as_slice(),
+            [MockDmlHandlerCall::Write { namespace, write_input, .. }] => {
+                assert_eq!(namespace, NAMESPACE_NAME);
+                assert_eq!(write_input["platanos"].rows(), 1);
+                assert_eq!(write_input["bananas"].rows(), 1);
+            }
+        );
+
+        assert_metric_hit(&metrics, "http_write_lines", Some(2));
+        assert_rejected_lines_metric(&metrics, 2);
+    }
+
+    /// Assert that a partial write in which every line is invalid does not
+    /// invoke the DML handler.
+    #[tokio::test]
+    async fn test_partial_write_all_rejected() {
+        let dml_handler = Arc::new(MockDmlHandler::default());
+        let metrics = Arc::new(metric::Registry::default());
+        let delegate = partial_write_delegate(Arc::clone(&dml_handler), &metrics);
+
+        let request = Request::builder()
+            .uri("https://bananas.example/api/v2/write?org=bananas&bucket=test")
+            .method("POST")
+            .body(Body::from("platanos\nbananas"))
+            .unwrap();
+
+        let got = delegate.route(request).await;
+        assert_matches!(got, Err(Error::PartialWrite(rejected)) => {
+            assert_eq!(rejected.len(), 2);
+        });
+        assert!(dml_handler.calls().is_empty());
+        assert_rejected_lines_metric(&metrics, 2);
+    }
+
+    /// Assert that a fully valid write succeeds in partial write mode.
+    #[tokio::test]
+    async fn test_partial_write_all_valid() {
+        let dml_handler = Arc::new(MockDmlHandler::default().with_write_return([Ok(())]));
+        let metrics = Arc::new(metric::Registry::default());
+        let delegate = partial_write_delegate(Arc::clone(&dml_handler), &metrics);
+
+        let request = Request::builder()
+            .uri("https://bananas.example/api/v2/write?org=bananas&bucket=test")
+            .method("POST")
+            .body(Body::from("platanos,tag1=A val=42i 123456"))
+            .unwrap();
+
+        let got = delegate.route(request).await.expect("write should succeed");
+        assert_eq!(got.status(), StatusCode::NO_CONTENT);
+        assert_matches!(
+            dml_handler.calls().as_slice(),
+            [MockDmlHandlerCall::Write { .. }]
+        );
+    }
+
+    /// Assert Prometheus remote write requests are decoded and passed to the
+    /// DML handler.
+    #[tokio::test]
+    async fn test_prom_write() {
+        use generated_types::{
+            prometheus::{Label, Sample, TimeSeries, WriteRequest},
+            prost::Message,
+        };
+
+        let mock_namespace_resolver =
+            MockNamespaceResolver::default().with_mapping(NAMESPACE_NAME, NAMESPACE_ID);
+        let dml_handler = Arc::new(MockDmlHandler::default().with_write_return([Ok(())]));
+        let metrics = Arc::new(metric::Registry::default());
+        let delegate = HttpDelegate::new(
+            MAX_BYTES,
+            1,
+            false,
+            mock_namespace_resolver,
+            Arc::clone(&dml_handler),
+            &metrics,
+            Box::<MultiTenantRequestUnifier>::default(),
+        );
+
+        let body = WriteRequest {
+            timeseries: vec![TimeSeries {
+                labels: vec![
+                    Label {
+                        name: "__name__".to_string(),
+                        value: "platanos".to_string(),
+                    },
+                    Label {
+                        name: "job".to_string(),
+                        value: "router".to_string(),
+                    },
+                ],
+                samples: vec![
+                    Sample {
+                        value: 4.2,
+                        timestamp: 1,
+                    },
+                    Sample {
+                        value: 2.4,
+                        timestamp: 2,
+                    },
+                ],
+            }],
+        }
+        .encode_to_vec();
+        let body = snap::raw::Encoder::new().compress_vec(&body).unwrap();
+
+        let request = Request::builder()
+            .uri("https://bananas.example/api/v1/prom/write?org=bananas&bucket=test")
+            .method("POST")
+            .header(CONTENT_ENCODING, "snappy")
+            .header("X-Prometheus-Remote-Write-Version", "0.1.0")
+            .body(Body::from(body))
+            .unwrap();
+
+        let got = delegate.route(request).await.expect("write should succeed");
+        assert_eq!(got.status(), StatusCode::NO_CONTENT);
+
+        assert_matches!(
+            dml_handler.calls().as_slice(),
+            [MockDmlHandlerCall::Write { namespace, write_input, .. }] => {
+                assert_eq!(namespace, NAMESPACE_NAME);
+                let table = write_input.get("platanos").expect("table not found");
+                assert_eq!(table.rows(), 2);
+                assert!(table.column("job").is_ok());
+                assert!(table.column("value").is_ok());
+            }
+        );
+        assert_metric_hit(&metrics, "http_prom_write_samples", Some(2));
+
+        // A body that is not snappy compressed is rejected without invoking
+        // the DML handler.
+        let request = Request::builder()
+            .uri("https://bananas.example/api/v1/prom/write?org=bananas&bucket=test")
+            .method("POST")
+            .header(CONTENT_ENCODING, "snappy")
+            .body(Body::from("platanos,tag1=A val=42i 123456"))
+            .unwrap();
+
+        let got = delegate.route(request).await;
+        assert_matches!(got, Err(Error::ParsePromWrite(PromWriteError::Snappy(_))));
+        assert_eq!(dml_handler.calls().len(), 1);
+    }
+
+    /// Assert CSV and NDJSON write requests are routed by their content type,
+    /// decoded and passed to the DML handler.
+    #[tokio::test]
+    async fn test_record_write() {
+        let mock_namespace_resolver =
+            MockNamespaceResolver::default().with_mapping(NAMESPACE_NAME, NAMESPACE_ID);
+        let dml_handler = Arc::new(MockDmlHandler::default().with_write_return([Ok(()), Ok(())]));
+        let metrics = Arc::new(metric::Registry::default());
+        let delegate = HttpDelegate::new(
+            MAX_BYTES,
+            1,
+            false,
+            mock_namespace_resolver,
+            Arc::clone(&dml_handler),
+            &metrics,
+            Box::<MultiTenantRequestUnifier>::default(),
+        );
+
+        let request = Request::builder()
+            .uri("https://bananas.example/api/v2/write?org=bananas&bucket=test&precision=s")
+            .method("POST")
+            .header(CONTENT_TYPE, "text/csv")
+            .body(Body::from(
+                "#datatype measurement,tag,double,dateTime\nm,job,val,time\nplatanos,router,4.2,1\n",
+            ))
+            .unwrap();
+        let got = delegate.route(request).await.expect("write should succeed");
+        assert_eq!(got.status(), StatusCode::NO_CONTENT);
+
+        let request = Request::builder()
+            .uri("https://bananas.example/api/v2/write?org=bananas&bucket=test&measurement=platanos&tag_columns=job")
+            .method("POST")
+            .header(CONTENT_TYPE, "application/x-ndjson")
+            .body(Body::from(
+                "{\"job\": \"querier\", \"val\": 2.4, \"time\": 2}\n",
+            ))
+            .unwrap();
+        let got = delegate.route(request).await.expect("write should succeed");
+        assert_eq!(got.status(), StatusCode::NO_CONTENT);
+
+        assert_matches!(
+            dml_handler.calls().as_slice(),
+            [
+                MockDmlHandlerCall::Write { write_input: csv, .. },
+                MockDmlHandlerCall::Write { write_input: ndjson, .. },
+            ] => {
+                let table = csv.get("platanos").expect("table not found");
+                assert_eq!(table.rows(), 1);
+                assert_eq!(
+                    table.timestamp_summary().unwrap().stats.min,
+                    Some(1_000_000_000)
+                );
+                let table = ndjson.get("platanos").expect("table not found");
+                assert_eq!(table.rows(), 1);
+                assert!(table.column("job").is_ok());
+            }
+        );
+        assert_metric_hit(&metrics, "http_write_records", Some(2));
+
+        // An invalid record is rejected without invoking the DML handler.
+        let request = Request::builder()
+            .uri("https://bananas.example/api/v2/write?org=bananas&bucket=test")
+            .method("POST")
+            .header(CONTENT_TYPE, "text/csv")
+            .body(Body::from("val,time\n4.2,1\n"))
+            .unwrap();
+        let got = delegate.route(request).await;
+        assert_matches!(
+            got,
+            Err(Error::ParseRecords(RecordWriteError::NoMeasurement {
+                line: 2
+            }))
+        );
+        assert_eq!(dml_handler.calls().len(), 2);
+    }
+
+    #[test]
+    fn test_format_rejected_lines_truncated() {
+        let rejected = (1..=MAX_REPORTED_REJECTED_LINES + 3)
+            .map(|line| RejectedLine {
+                line,
+                error: mutable_batch_lp::LineError::TimestampOverflow,
+            })
+            .collect::<Vec<_>>();
+
+        let got = format_rejected_lines(&rejected);
+        assert!(got.starts_with("line 1: timestamp overflows i64; line 2:"));
+        assert!(got.ends_with(&format!(
+            "line {MAX_REPORTED_REJECTED_LINES}: timestamp overflows i64 (and 3 more)"
+        )));
+    }
+
     // The display text of Error gets passed through `ioxd_router::IoxHttpErrorAdaptor` then
     // `ioxd_common::http::error::HttpApiError` as the JSON "message" value in error response
     // bodies. These are fixture tests to document error messages that users might see when
@@ -1328,8 +2096,13 @@ mod tests {
         ),
 
         (
-            DeletesUnsupported,
-            "deletes are not supported",
+            ParseDelete(DeleteParseError::InvalidMeasurement),
+            "delete predicate must specify at most one _measurement=\"<name>\" expression",
+        ),
+
+        (
+            TableNotFound("[table name]".into()),
+            "table [table name] does not exist",
         ),
 
         (
@@ -1415,6 +2188,29 @@ mod tests {
             "failed to parse line protocol: timestamp overflows i64",
         ),
 
+        (
+            PartialWrite(vec![
+                mutable_batch_lp::RejectedLine {
+                    line: 2,
+                    error: mutable_batch_lp::LineError::Parse {
+                        source: influxdb_line_protocol::Error::FieldSetMissing,
+                    },
+                },
+                mutable_batch_lp::RejectedLine {
+                    line: 4,
+                    error: mutable_batch_lp::LineError::TimestampOverflow,
+                },
+            ]),
+            "partial write has occurred, errors encountered on line(s): \
+            line 2: No fields were provided; \
+            line 4: timestamp overflows i64",
+        ),
+
+        (
+            ParsePromWrite(PromWriteError::MissingMetricName),
+            "time series has no \"__name__\" label",
+        ),
+
         (
             DmlHandler(DmlError::NamespaceNotFound("[namespace name]".into())),
             "dml handler error: namespace [namespace name] does not exist",
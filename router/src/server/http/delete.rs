@@ -0,0 +1,146 @@
+//! Parsing of HTTP request bodies that conform to the [V2 Delete API].
+//!
+//! [V2 Delete API]:
+//!     https://docs.influxdata.com/influxdb/v2.6/api/#operation/PostDelete
+
+use data_types::{DeletePredicate, Op, Scalar};
+use predicate::delete_predicate::parse_delete_predicate;
+use serde::Deserialize;
+use thiserror::Error;
+
+/// The name of the pseudo-column used to restrict a delete to a single
+/// measurement (table).
+const MEASUREMENT_COLUMN: &str = "_measurement";
+
+/// Errors returned when decoding a delete request body.
+#[derive(Debug, Error)]
+pub enum DeleteParseError {
+    /// The request body is not a valid JSON delete request.
+    #[error("invalid delete request body: {0}")]
+    InvalidJson(#[from] serde_json::Error),
+
+    /// The time range or predicate of the delete is invalid.
+    #[error(transparent)]
+    InvalidPredicate(#[from] predicate::delete_predicate::Error),
+
+    /// The predicate restricts the measurement in an unsupported way.
+    #[error("delete predicate must specify at most one _measurement=\"<name>\" expression")]
+    InvalidMeasurement,
+}
+
+/// The JSON body of a v2 delete request.
+#[derive(Debug, Deserialize)]
+struct DeleteBody {
+    start: String,
+    stop: String,
+    #[serde(default)]
+    predicate: String,
+}
+
+/// A decoded delete request.
+#[derive(Debug, PartialEq)]
+pub(crate) struct DeleteRequest {
+    /// The measurement (table) the delete is restricted to, if any.
+    pub(crate) table_name: Option<String>,
+
+    /// The predicate identifying the rows to delete, excluding any
+    /// measurement restriction.
+    pub(crate) predicate: DeletePredicate,
+}
+
+impl DeleteRequest {
+    /// Decode a JSON delete request `body`.
+    pub(crate) fn try_from_json(body: &[u8]) -> Result<Self, DeleteParseError> {
+        let body: DeleteBody = serde_json::from_slice(body)?;
+        let mut predicate = parse_delete_predicate(&body.start, &body.stop, &body.predicate)?;
+
+        // Extract the measurement restriction from the predicate - there is no
+        // "_measurement" column in the stored data.
+        let (measurement, exprs) = predicate
+            .exprs
+            .into_iter()
+            .partition::<Vec<_>, _>(|e| e.column == MEASUREMENT_COLUMN);
+        predicate.exprs = exprs;
+
+        let table_name = match measurement.as_slice() {
+            [] => None,
+            [e] => match (e.op, &e.scalar) {
+                (Op::Eq, Scalar::String(name)) => Some(name.clone()),
+                _ => return Err(DeleteParseError::InvalidMeasurement),
+            },
+            _ => return Err(DeleteParseError::InvalidMeasurement),
+        };
+
+        Ok(Self {
+            table_name,
+            predicate,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use data_types::{DeleteExpr, TimestampRange};
+
+    use super::*;
+
+    #[test]
+    fn test_parse_measurement() {
+        let got = DeleteRequest::try_from_json(
+            br#"{"start":"1","stop":"2","predicate":"_measurement=\"cpu\" and host=\"a\""}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            got,
+            DeleteRequest {
+                table_name: Some("cpu".to_string()),
+                predicate: DeletePredicate {
+                    range: TimestampRange::new(1, 2),
+                    exprs: vec![DeleteExpr::new(
+                        "host".to_string(),
+                        Op::Eq,
+                        Scalar::String("a".to_string())
+                    )],
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_no_predicate() {
+        let got = DeleteRequest::try_from_json(
+            br#"{"start":"1970-01-01T00:00:00Z","stop":"1970-01-01T00:00:00.000000010Z"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(got.table_name, None);
+        assert_eq!(got.predicate.range, TimestampRange::new(0, 10));
+        assert!(got.predicate.exprs.is_empty());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_matches!(
+            DeleteRequest::try_from_json(b"bananas"),
+            Err(DeleteParseError::InvalidJson(_))
+        );
+        assert_matches!(
+            DeleteRequest::try_from_json(br#"{"start":"2","stop":"1"}"#),
+            Err(DeleteParseError::InvalidPredicate(_))
+        );
+        assert_matches!(
+            DeleteRequest::try_from_json(
+                br#"{"start":"1","stop":"2","predicate":"_measurement!=\"cpu\""}"#
+            ),
+            Err(DeleteParseError::InvalidMeasurement)
+        );
+        assert_matches!(
+            DeleteRequest::try_from_json(
+                br#"{"start":"1","stop":"2","predicate":"_measurement=\"a\" and _measurement=\"b\""}"#
+            ),
+            Err(DeleteParseError::InvalidMeasurement)
+        );
+    }
+}
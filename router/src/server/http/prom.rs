@@ -0,0 +1,332 @@
+//! Decoding of HTTP request bodies that conform to the [Prometheus remote
+//! write] protocol.
+//!
+//! Each time series is written to the table named by its metric name (the
+//! `__name__` label), with the remaining labels written as tags and the
+//! sample values written to the float field `value`.
+//!
+//! [Prometheus remote write]:
+//!     https://prometheus.io/docs/concepts/remote_write_spec/
+
+use std::iter;
+
+use generated_types::{
+    prometheus::{TimeSeries, WriteRequest},
+    prost::Message,
+};
+use hashbrown::{HashMap, HashSet};
+use mutable_batch::{writer::Writer, MutableBatch};
+use thiserror::Error;
+
+/// The label containing the metric name of a series.
+pub(crate) const METRIC_NAME_LABEL: &str = "__name__";
+
+/// The name of the field the sample values are written to.
+pub(crate) const VALUE_FIELD: &str = "value";
+
+/// The bit pattern of the NaN value Prometheus uses as a staleness marker,
+/// which is distinct from any NaN value a metric itself may have.
+const STALE_NAN_BITS: u64 = 0x7ff0000000000002;
+
+/// Errors returned when decoding a Prometheus remote write request body.
+#[derive(Debug, Error)]
+pub enum PromWriteError {
+    /// The request body is not valid snappy compressed data.
+    #[error("invalid snappy compressed body: {0}")]
+    Snappy(#[from] snap::Error),
+
+    /// The decompressed request body exceeds the configured maximum.
+    #[error("max decompressed request size ({0} bytes) exceeded")]
+    RequestSizeExceeded(usize),
+
+    /// The request body is not a valid remote write protobuf message.
+    #[error("invalid remote write request: {0}")]
+    Decode(#[from] generated_types::DecodeError),
+
+    /// A time series has no metric name.
+    #[error("time series has no {METRIC_NAME_LABEL:?} label")]
+    MissingMetricName,
+
+    /// A time series has more than one label with the same name.
+    #[error("time series for metric {metric} has duplicate label {label}")]
+    DuplicateLabel {
+        /// The metric name of the series.
+        metric: String,
+        /// The duplicated label name.
+        label: String,
+    },
+
+    /// The samples of a time series could not be written.
+    #[error("error writing time series for metric {metric}: {source}")]
+    Write {
+        /// The metric name of the series.
+        metric: String,
+        /// The underlying write error.
+        source: mutable_batch::writer::Error,
+    },
+}
+
+/// Statistics about a decoded remote write request.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct PromWriteStatistics {
+    /// The number of time series written.
+    pub(crate) num_series: usize,
+    /// The number of samples written.
+    pub(crate) num_samples: usize,
+}
+
+/// Decompress and decode the snappy compressed remote write request in
+/// `body`, converting it into a set of [`MutableBatch`] keyed by table name.
+///
+/// The decompressed body is limited to `max_request_bytes` in size.
+pub(crate) fn decode_write_request(
+    body: &[u8],
+    max_request_bytes: usize,
+) -> Result<(HashMap<String, MutableBatch>, PromWriteStatistics), PromWriteError> {
+    // Check the decompressed size before allocating the output buffer to
+    // prevent a decompression bomb based DoS.
+    if snap::raw::decompress_len(body)? > max_request_bytes {
+        return Err(PromWriteError::RequestSizeExceeded(max_request_bytes));
+    }
+    let body = snap::raw::Decoder::new().decompress_vec(body)?;
+
+    let request = WriteRequest::decode(body.as_slice())?;
+
+    let mut batches = HashMap::new();
+    let mut stats = PromWriteStatistics::default();
+    for series in &request.timeseries {
+        let n = write_series(&mut batches, series)?;
+        if n > 0 {
+            stats.num_series += 1;
+            stats.num_samples += n;
+        }
+    }
+
+    Ok((batches, stats))
+}
+
+/// Write the samples of `series` to the batch for its metric, returning the
+/// number of samples written.
+fn write_series(
+    batches: &mut HashMap<String, MutableBatch>,
+    series: &TimeSeries,
+) -> Result<usize, PromWriteError> {
+    let metric = series
+        .labels
+        .iter()
+        .find(|l| l.name == METRIC_NAME_LABEL)
+        .map(|l| l.value.as_str())
+        .filter(|v| !v.is_empty())
+        .ok_or(PromWriteError::MissingMetricName)?;
+
+    // Staleness markers carry no data, whereas other NaN values are
+    // written as is.
+    let samples = series
+        .samples
+        .iter()
+        .filter(|s| s.value.to_bits() != STALE_NAN_BITS)
+        .collect::<Vec<_>>();
+    if samples.is_empty() {
+        return Ok(0);
+    }
+
+    let (_, batch) = batches
+        .raw_entry_mut()
+        .from_key(metric)
+        .or_insert_with(|| (metric.to_string(), MutableBatch::new()));
+    let mut writer = Writer::new(batch, samples.len());
+
+    let write_err = |source| PromWriteError::Write {
+        metric: metric.to_string(),
+        source,
+    };
+
+    let mut seen = HashSet::with_capacity(series.labels.len());
+    for label in &series.labels {
+        if !seen.insert(label.name.as_str()) {
+            return Err(PromWriteError::DuplicateLabel {
+                metric: metric.to_string(),
+                label: label.name.clone(),
+            });
+        }
+
+        // An empty label value is equivalent to the label being absent.
+        if label.name == METRIC_NAME_LABEL || label.value.is_empty() {
+            continue;
+        }
+
+        writer
+            .write_tag(
+                &label.name,
+                None,
+                iter::repeat(label.value.as_str()).take(samples.len()),
+            )
+            .map_err(write_err)?;
+    }
+
+    writer
+        .write_f64(VALUE_FIELD, None, samples.iter().map(|s| s.value))
+        .map_err(write_err)?;
+
+    // Prometheus timestamps have millisecond precision.
+    writer
+        .write_time(
+            "time",
+            samples
+                .iter()
+                .map(|s| s.timestamp.saturating_mul(1_000_000)),
+        )
+        .map_err(write_err)?;
+
+    writer.commit();
+
+    Ok(samples.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_util::assert_batches_sorted_eq;
+    use assert_matches::assert_matches;
+    use generated_types::prometheus::{Label, Sample};
+    use schema::Projection;
+
+    use super::*;
+
+    fn label(name: &str, value: &str) -> Label {
+        Label {
+            name: name.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    fn sample(value: f64, timestamp: i64) -> Sample {
+        Sample { value, timestamp }
+    }
+
+    /// Encode and snappy compress `timeseries` as a remote write request body.
+    fn encode(timeseries: Vec<TimeSeries>) -> Vec<u8> {
+        let body = WriteRequest { timeseries }.encode_to_vec();
+        snap::raw::Encoder::new().compress_vec(&body).unwrap()
+    }
+
+    #[test]
+    fn test_decode() {
+        let body = encode(vec![
+            TimeSeries {
+                labels: vec![
+                    label(METRIC_NAME_LABEL, "http_requests_total"),
+                    label("job", "router"),
+                    label("status", "200"),
+                ],
+                samples: vec![sample(1.0, 1), sample(2.0, 2)],
+            },
+            TimeSeries {
+                labels: vec![
+                    label(METRIC_NAME_LABEL, "http_requests_total"),
+                    label("job", "querier"),
+                    label("status", ""),
+                ],
+                samples: vec![
+                    sample(3.0, 1),
+                    sample(f64::from_bits(STALE_NAN_BITS), 2),
+                    sample(f64::NAN, 3),
+                ],
+            },
+            TimeSeries {
+                labels: vec![label(METRIC_NAME_LABEL, "up")],
+                samples: vec![sample(1.0, 3)],
+            },
+            // Only staleness markers
+            TimeSeries {
+                labels: vec![label(METRIC_NAME_LABEL, "stale")],
+                samples: vec![sample(f64::from_bits(STALE_NAN_BITS), 3)],
+            },
+        ]);
+
+        let (batches, stats) = decode_write_request(&body, usize::MAX).unwrap();
+        assert_eq!(stats.num_series, 3);
+        assert_eq!(stats.num_samples, 5);
+        assert_eq!(batches.len(), 2);
+
+        assert_batches_sorted_eq!(
+            [
+                "+---------+--------+--------------------------+-------+",
+                "| job     | status | time                     | value |",
+                "+---------+--------+--------------------------+-------+",
+                "| querier |        | 1970-01-01T00:00:00.001Z | 3.0   |",
+                "| querier |        | 1970-01-01T00:00:00.003Z | NaN   |",
+                "| router  | 200    | 1970-01-01T00:00:00.001Z | 1.0   |",
+                "| router  | 200    | 1970-01-01T00:00:00.002Z | 2.0   |",
+                "+---------+--------+--------------------------+-------+",
+            ],
+            &[batches["http_requests_total"]
+                .to_arrow(Projection::All)
+                .unwrap()]
+        );
+        assert_batches_sorted_eq!(
+            [
+                "+--------------------------+-------+",
+                "| time                     | value |",
+                "+--------------------------+-------+",
+                "| 1970-01-01T00:00:00.003Z | 1.0   |",
+                "+--------------------------+-------+",
+            ],
+            &[batches["up"].to_arrow(Projection::All).unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert_matches!(
+            decode_write_request(b"bananas", usize::MAX),
+            Err(PromWriteError::Snappy(_))
+        );
+
+        let body = snap::raw::Encoder::new().compress_vec(b"bananas").unwrap();
+        assert_matches!(
+            decode_write_request(&body, usize::MAX),
+            Err(PromWriteError::Decode(_))
+        );
+        assert_matches!(
+            decode_write_request(&body, 1),
+            Err(PromWriteError::RequestSizeExceeded(1))
+        );
+
+        let body = encode(vec![TimeSeries {
+            labels: vec![label("job", "router")],
+            samples: vec![sample(1.0, 1)],
+        }]);
+        assert_matches!(
+            decode_write_request(&body, usize::MAX),
+            Err(PromWriteError::MissingMetricName)
+        );
+
+        let body = encode(vec![TimeSeries {
+            labels: vec![
+                label(METRIC_NAME_LABEL, "up"),
+                label("job", "a"),
+                label("job", "b"),
+            ],
+            samples: vec![sample(1.0, 1)],
+        }]);
+        assert_matches!(
+            decode_write_request(&body, usize::MAX),
+            Err(PromWriteError::DuplicateLabel { metric, label }) => {
+                assert_eq!(metric, "up");
+                assert_eq!(label, "job");
+            }
+        );
+
+        // A label conflicting with the value field.
+        let body = encode(vec![TimeSeries {
+            labels: vec![label(METRIC_NAME_LABEL, "up"), label(VALUE_FIELD, "a")],
+            samples: vec![sample(1.0, 1)],
+        }]);
+        assert_matches!(
+            decode_write_request(&body, usize::MAX),
+            Err(PromWriteError::Write { metric, .. }) => {
+                assert_eq!(metric, "up");
+            }
+        );
+    }
+}
@@ -0,0 +1,885 @@
+//! Decoding of HTTP write request bodies containing CSV or newline delimited
+//! JSON (NDJSON) records.
+//!
+//! Each CSV row or JSON object is written as a single row of the table named
+//! by its measurement. The role and type of each column may be given by an
+//! annotation row preceding the CSV header, in the form used by the `influx
+//! write` command:
+//!
+//! ```text
+//! #datatype measurement,tag,double,dateTime:RFC3339
+//! m,host,used_percent,time
+//! cpu,server01,2.5,2020-01-01T00:00:00Z
+//! ```
+//!
+//! or by the query parameters of the request:
+//!
+//! * `measurement`: the table to write records to, if they have no
+//!   measurement column.
+//! * `measurement_column`: the column containing the table name of each
+//!   record.
+//! * `tag_columns`: a comma separated list of the columns written as tags.
+//! * `time_column`: the column containing the timestamp of each record,
+//!   `time` by default.
+//! * `field_types`: a comma separated list of `column:type` pairs giving the
+//!   type of field columns, where the type is one of `double`, `long`,
+//!   `unsignedLong`, `boolean` or `string`.
+//!
+//! Any other column is written as a field. The type of a CSV field without a
+//! declared type is inferred from its first value: `true` or `false` are
+//! booleans, numbers are floats and anything else is a string. JSON fields
+//! without a declared type take the type of their JSON value, with numbers
+//! written as floats as for line protocol.
+//!
+//! Timestamps are either integers in the `precision` of the request, or
+//! RFC3339 strings. Records without a timestamp are assigned the current
+//! time, as for line protocol. Empty CSV values and JSON nulls are treated as
+//! absent.
+
+use std::{borrow::Cow, iter, str::FromStr};
+
+use hashbrown::{HashMap, HashSet};
+use hyper::{header::CONTENT_TYPE, HeaderMap};
+use mutable_batch::{writer::Writer, MutableBatch};
+use serde::Deserialize;
+use thiserror::Error;
+
+/// The default name of the column containing the timestamp of each record.
+const DEFAULT_TIME_COLUMN: &str = "time";
+
+/// The prefix of the CSV annotation row giving the type of each column.
+const DATATYPE_ANNOTATION: &str = "#datatype";
+
+/// Errors returned when decoding CSV or NDJSON records.
+#[derive(Debug, Error)]
+pub enum RecordWriteError {
+    /// The request contains invalid query parameters.
+    #[error("failed to deserialize query parameters: {0}")]
+    DecodeParams(#[from] serde::de::value::Error),
+
+    /// A column is declared with an unknown type.
+    #[error("unknown data type {0:?}")]
+    UnknownDataType(String),
+
+    /// An entry of the `field_types` parameter is not a `column:type` pair.
+    #[error("invalid field type {0:?}, expected column:type")]
+    InvalidFieldType(String),
+
+    /// The CSV body is malformed.
+    #[error("invalid CSV: {0}")]
+    Csv(#[from] csv::Error),
+
+    /// The CSV header row has no column names.
+    #[error("CSV header has no column names")]
+    NoHeader,
+
+    /// The CSV header contains the same column more than once.
+    #[error("duplicate column {0:?} in CSV header")]
+    DuplicateColumn(String),
+
+    /// A CSV row has a different number of values to the header.
+    #[error("line {line}: expected {want} values, found {got}")]
+    RowLength {
+        /// The line number of the row.
+        line: u64,
+        /// The number of columns in the header.
+        want: usize,
+        /// The number of values in the row.
+        got: usize,
+    },
+
+    /// A line of an NDJSON body is not a JSON object.
+    #[error("line {line}: invalid JSON object: {source}")]
+    Json {
+        /// The line number of the record.
+        line: u64,
+        /// The JSON decode error.
+        source: serde_json::Error,
+    },
+
+    /// A record has no measurement, and no `measurement` parameter is given.
+    #[error("line {line}: record has no measurement")]
+    NoMeasurement {
+        /// The line number of the record.
+        line: u64,
+    },
+
+    /// A record has no field values.
+    #[error("line {line}: record has no fields")]
+    NoFields {
+        /// The line number of the record.
+        line: u64,
+    },
+
+    /// A value cannot be converted to the type of its column.
+    #[error("line {line}: invalid {data_type} value {value} for column {column:?}")]
+    InvalidValue {
+        /// The line number of the record.
+        line: u64,
+        /// The name of the column.
+        column: String,
+        /// The type of the column.
+        data_type: &'static str,
+        /// The invalid value.
+        value: String,
+    },
+
+    /// The record could not be written.
+    #[error("line {line}: error writing record: {source}")]
+    Write {
+        /// The line number of the record.
+        line: u64,
+        /// The underlying write error.
+        source: mutable_batch::writer::Error,
+    },
+}
+
+/// The encoding of a record write request body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RecordFormat {
+    /// Comma separated values, with a header row.
+    Csv,
+    /// Newline delimited JSON objects.
+    Ndjson,
+}
+
+impl RecordFormat {
+    /// Returns the record format indicated by the `Content-Type` header in
+    /// `headers`, or [`None`] if the body is not CSV or NDJSON.
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let content_type = headers.get(CONTENT_TYPE)?.to_str().ok()?;
+        let mime = content_type.split(';').next()?.trim();
+
+        if mime.eq_ignore_ascii_case("text/csv") {
+            Some(Self::Csv)
+        } else if mime.eq_ignore_ascii_case("application/x-ndjson")
+            || mime.eq_ignore_ascii_case("application/jsonl")
+        {
+            Some(Self::Ndjson)
+        } else {
+            None
+        }
+    }
+}
+
+/// The query parameters describing the columns of a record write request.
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct RecordParams {
+    measurement: Option<String>,
+    measurement_column: Option<String>,
+    tag_columns: Option<String>,
+    time_column: Option<String>,
+    field_types: Option<String>,
+}
+
+impl RecordParams {
+    /// Decode the parameters from a URI query string, ignoring any unrelated
+    /// parameters.
+    pub(crate) fn try_from_query(query: Option<&str>) -> Result<Self, RecordWriteError> {
+        Ok(serde_urlencoded::from_str(query.unwrap_or_default())?)
+    }
+}
+
+/// Statistics about decoded records.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct RecordStatistics {
+    /// The number of records written.
+    pub(crate) num_records: usize,
+    /// The number of field values written.
+    pub(crate) num_fields: usize,
+}
+
+/// The type of a field column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldType {
+    Float,
+    Integer,
+    UInteger,
+    Boolean,
+    String,
+}
+
+impl FieldType {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Float => "double",
+            Self::Integer => "long",
+            Self::UInteger => "unsignedLong",
+            Self::Boolean => "boolean",
+            Self::String => "string",
+        }
+    }
+}
+
+/// The role of a column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnKind {
+    Measurement,
+    Tag,
+    Time,
+    /// A field, with its type if declared.
+    Field(Option<FieldType>),
+    Ignored,
+}
+
+impl FromStr for ColumnKind {
+    type Err = RecordWriteError;
+
+    /// Parse an `influx write` style data type annotation.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // The format of dateTime columns is inferred from their values.
+        let base = s.split(':').next().unwrap_or_default().trim();
+        Ok(match base {
+            "measurement" => Self::Measurement,
+            "tag" => Self::Tag,
+            "dateTime" => Self::Time,
+            "double" | "float" => Self::Field(Some(FieldType::Float)),
+            "long" | "integer" => Self::Field(Some(FieldType::Integer)),
+            "unsignedLong" | "unsigned" => Self::Field(Some(FieldType::UInteger)),
+            "boolean" => Self::Field(Some(FieldType::Boolean)),
+            "string" => Self::Field(Some(FieldType::String)),
+            "ignored" => Self::Ignored,
+            // An unannotated column.
+            "" => Self::Field(None),
+            _ => return Err(RecordWriteError::UnknownDataType(s.to_string())),
+        })
+    }
+}
+
+/// The roles and types of the columns of a record write request.
+#[derive(Debug)]
+struct ColumnSpec {
+    /// The table to write records without a measurement column to.
+    measurement: Option<String>,
+    /// The columns with an explicit role.
+    columns: HashMap<String, ColumnKind>,
+    /// The column containing the timestamp, if not explicitly declared.
+    time_column: String,
+}
+
+impl ColumnSpec {
+    fn try_from_params(params: &RecordParams) -> Result<Self, RecordWriteError> {
+        let mut columns = HashMap::new();
+
+        if let Some(c) = &params.measurement_column {
+            columns.insert(c.clone(), ColumnKind::Measurement);
+        }
+        for c in split_list(params.tag_columns.as_deref()) {
+            columns.insert(c.to_string(), ColumnKind::Tag);
+        }
+        for v in split_list(params.field_types.as_deref()) {
+            let (column, data_type) = v
+                .rsplit_once(':')
+                .ok_or_else(|| RecordWriteError::InvalidFieldType(v.to_string()))?;
+            match data_type.parse()? {
+                kind @ ColumnKind::Field(Some(_)) => columns.insert(column.to_string(), kind),
+                _ => return Err(RecordWriteError::InvalidFieldType(v.to_string())),
+            };
+        }
+
+        Ok(Self {
+            measurement: params.measurement.clone().filter(|v| !v.is_empty()),
+            columns,
+            time_column: params
+                .time_column
+                .clone()
+                .unwrap_or_else(|| DEFAULT_TIME_COLUMN.to_string()),
+        })
+    }
+
+    /// Returns the role of `column`.
+    fn kind(&self, column: &str) -> ColumnKind {
+        match self.columns.get(column) {
+            Some(kind) => *kind,
+            None if column == self.time_column => ColumnKind::Time,
+            None => ColumnKind::Field(None),
+        }
+    }
+}
+
+/// Split a comma separated parameter value, ignoring empty entries.
+fn split_list(v: Option<&str>) -> impl Iterator<Item = &str> {
+    v.unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+}
+
+/// A field value of a record.
+#[derive(Debug)]
+enum FieldValue<'a> {
+    Float(f64),
+    Integer(i64),
+    UInteger(u64),
+    Boolean(bool),
+    String(Cow<'a, str>),
+}
+
+/// A single decoded record.
+#[derive(Debug, Default)]
+struct Record<'a> {
+    measurement: Option<Cow<'a, str>>,
+    tags: Vec<(&'a str, Cow<'a, str>)>,
+    fields: Vec<(&'a str, FieldValue<'a>)>,
+    time: Option<i64>,
+}
+
+/// Accumulates records into [`MutableBatch`] keyed by table name.
+#[derive(Debug)]
+struct RecordWriter<'a> {
+    spec: &'a ColumnSpec,
+    default_time: i64,
+    timestamp_base: i64,
+    batches: HashMap<String, MutableBatch>,
+    stats: RecordStatistics,
+}
+
+impl<'a> RecordWriter<'a> {
+    fn new(spec: &'a ColumnSpec, default_time: i64, timestamp_base: i64) -> Self {
+        Self {
+            spec,
+            default_time,
+            timestamp_base,
+            batches: HashMap::new(),
+            stats: RecordStatistics::default(),
+        }
+    }
+
+    /// Parse a timestamp, either an integer in the request precision or an
+    /// RFC3339 string.
+    fn parse_time(&self, line: u64, column: &str, v: &str) -> Result<i64, RecordWriteError> {
+        let invalid = || RecordWriteError::InvalidValue {
+            line,
+            column: column.to_string(),
+            data_type: "dateTime",
+            value: format!("{v:?}"),
+        };
+
+        match v.parse::<i64>() {
+            Ok(t) => t.checked_mul(self.timestamp_base).ok_or_else(invalid),
+            Err(_) => chrono::DateTime::parse_from_rfc3339(v)
+                .ok()
+                .and_then(|t| t.timestamp_nanos_opt())
+                .ok_or_else(invalid),
+        }
+    }
+
+    /// Write `record` to the batch for its measurement.
+    fn write(&mut self, line: u64, record: Record<'_>) -> Result<(), RecordWriteError> {
+        let measurement = record
+            .measurement
+            .as_deref()
+            .or(self.spec.measurement.as_deref())
+            .ok_or(RecordWriteError::NoMeasurement { line })?;
+        if record.fields.is_empty() {
+            return Err(RecordWriteError::NoFields { line });
+        }
+
+        let (_, batch) = self
+            .batches
+            .raw_entry_mut()
+            .from_key(measurement)
+            .or_insert_with(|| (measurement.to_string(), MutableBatch::new()));
+        let mut writer = Writer::new(batch, 1);
+
+        let write_err = |source| RecordWriteError::Write { line, source };
+        for (name, value) in &record.tags {
+            writer
+                .write_tag(name, None, iter::once(value.as_ref()))
+                .map_err(write_err)?;
+        }
+        for (name, value) in &record.fields {
+            match value {
+                FieldValue::Float(v) => writer.write_f64(name, None, iter::once(*v)),
+                FieldValue::Integer(v) => writer.write_i64(name, None, iter::once(*v)),
+                FieldValue::UInteger(v) => writer.write_u64(name, None, iter::once(*v)),
+                FieldValue::Boolean(v) => writer.write_bool(name, None, iter::once(*v)),
+                FieldValue::String(v) => writer.write_string(name, None, iter::once(v.as_ref())),
+            }
+            .map_err(write_err)?;
+        }
+        writer
+            .write_time(
+                DEFAULT_TIME_COLUMN,
+                iter::once(record.time.unwrap_or(self.default_time)),
+            )
+            .map_err(write_err)?;
+        writer.commit();
+
+        self.stats.num_records += 1;
+        self.stats.num_fields += record.fields.len();
+        Ok(())
+    }
+
+    fn finish(self) -> (HashMap<String, MutableBatch>, RecordStatistics) {
+        (self.batches, self.stats)
+    }
+}
+
+/// Decode the records in `body`, converting them into a set of
+/// [`MutableBatch`] keyed by table name.
+///
+/// Records without a timestamp are assigned `default_time`, and integer
+/// timestamps are multiplied by `timestamp_base` to convert them to
+/// nanoseconds.
+pub(crate) fn decode_records(
+    format: RecordFormat,
+    body: &[u8],
+    params: &RecordParams,
+    default_time: i64,
+    timestamp_base: i64,
+) -> Result<(HashMap<String, MutableBatch>, RecordStatistics), RecordWriteError> {
+    let spec = ColumnSpec::try_from_params(params)?;
+    let mut writer = RecordWriter::new(&spec, default_time, timestamp_base);
+
+    match format {
+        RecordFormat::Csv => decode_csv(&mut writer, body)?,
+        RecordFormat::Ndjson => decode_ndjson(&mut writer, body)?,
+    }
+
+    Ok(writer.finish())
+}
+
+/// Decode the CSV rows in `body`.
+fn decode_csv(writer: &mut RecordWriter<'_>, body: &[u8]) -> Result<(), RecordWriteError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(body);
+    let mut rows = reader.records();
+
+    // Read the annotation rows preceding the header.
+    let mut annotation = None;
+    let header = loop {
+        let Some(row) = rows.next().transpose()? else {
+            // An empty body has nothing to write.
+            return Ok(());
+        };
+        match row.get(0) {
+            Some(v) if v.starts_with(DATATYPE_ANNOTATION) => {
+                let first = v[DATATYPE_ANNOTATION.len()..].trim();
+                annotation = Some(
+                    iter::once(first)
+                        .chain(row.iter().skip(1))
+                        .map(ColumnKind::from_str)
+                        .collect::<Result<Vec<_>, _>>()?,
+                );
+            }
+            // Other annotations and comments are ignored.
+            Some(v) if v.starts_with('#') => continue,
+            _ => break row,
+        }
+    };
+    if header.iter().all(str::is_empty) {
+        return Err(RecordWriteError::NoHeader);
+    }
+
+    let mut seen = HashSet::with_capacity(header.len());
+    if let Some(name) = header.iter().find(|name| !seen.insert(*name)) {
+        return Err(RecordWriteError::DuplicateColumn(name.to_string()));
+    }
+
+    // Resolve the role of each column, preferring annotated types over the
+    // query parameters.
+    let mut kinds = header
+        .iter()
+        .enumerate()
+        .map(
+            |(i, name)| match annotation.as_ref().and_then(|a| a.get(i)) {
+                Some(ColumnKind::Field(None)) | None => writer.spec.kind(name),
+                Some(kind) => *kind,
+            },
+        )
+        .collect::<Vec<_>>();
+
+    for row in rows {
+        let row = row?;
+        let line = row.position().map(|p| p.line()).unwrap_or_default();
+        if row.get(0).is_some_and(|v| v.starts_with('#')) {
+            continue;
+        }
+        if row.len() != header.len() {
+            return Err(RecordWriteError::RowLength {
+                line,
+                want: header.len(),
+                got: row.len(),
+            });
+        }
+
+        let mut record = Record::default();
+        for ((name, value), kind) in header.iter().zip(row.iter()).zip(kinds.iter_mut()) {
+            if value.is_empty() {
+                continue;
+            }
+
+            match kind {
+                ColumnKind::Measurement => record.measurement = Some(Cow::Borrowed(value)),
+                ColumnKind::Tag => record.tags.push((name, Cow::Borrowed(value))),
+                ColumnKind::Time => record.time = Some(writer.parse_time(line, name, value)?),
+                ColumnKind::Field(data_type) => {
+                    let data_type = *data_type.get_or_insert_with(|| infer_type(value));
+                    let value = parse_field(data_type, value).ok_or_else(|| {
+                        RecordWriteError::InvalidValue {
+                            line,
+                            column: name.to_string(),
+                            data_type: data_type.name(),
+                            value: format!("{value:?}"),
+                        }
+                    })?;
+                    record.fields.push((name, value));
+                }
+                ColumnKind::Ignored => {}
+            }
+        }
+
+        writer.write(line, record)?;
+    }
+
+    Ok(())
+}
+
+/// Infer the type of a CSV field from its first value.
+fn infer_type(v: &str) -> FieldType {
+    if v.eq_ignore_ascii_case("true") || v.eq_ignore_ascii_case("false") {
+        FieldType::Boolean
+    } else if v.parse::<f64>().is_ok() {
+        FieldType::Float
+    } else {
+        FieldType::String
+    }
+}
+
+/// Parse a CSV field value as `data_type`.
+fn parse_field(data_type: FieldType, v: &str) -> Option<FieldValue<'_>> {
+    Some(match data_type {
+        FieldType::Float => FieldValue::Float(v.parse().ok()?),
+        FieldType::Integer => FieldValue::Integer(v.parse().ok()?),
+        FieldType::UInteger => FieldValue::UInteger(v.parse().ok()?),
+        FieldType::Boolean if v.eq_ignore_ascii_case("true") => FieldValue::Boolean(true),
+        FieldType::Boolean if v.eq_ignore_ascii_case("false") => FieldValue::Boolean(false),
+        FieldType::Boolean => return None,
+        FieldType::String => FieldValue::String(Cow::Borrowed(v)),
+    })
+}
+
+/// Decode the newline delimited JSON objects in `body`.
+fn decode_ndjson(writer: &mut RecordWriter<'_>, body: &[u8]) -> Result<(), RecordWriteError> {
+    for (i, raw) in body.split(|b| *b == b'\n').enumerate() {
+        let line = i as u64 + 1;
+        if raw.iter().all(u8::is_ascii_whitespace) {
+            continue;
+        }
+
+        let object: serde_json::Map<String, serde_json::Value> = serde_json::from_slice(raw)
+            .map_err(|source| RecordWriteError::Json { line, source })?;
+
+        let mut record = Record::default();
+        for (name, value) in &object {
+            if value.is_null() {
+                continue;
+            }
+
+            let invalid = |data_type: &'static str| RecordWriteError::InvalidValue {
+                line,
+                column: name.to_string(),
+                data_type,
+                value: value.to_string(),
+            };
+
+            match writer.spec.kind(name) {
+                ColumnKind::Measurement => {
+                    let v = value.as_str().ok_or_else(|| invalid("measurement"))?;
+                    record.measurement = Some(Cow::Borrowed(v));
+                }
+                ColumnKind::Tag => {
+                    let v = match value {
+                        serde_json::Value::String(v) => Cow::Borrowed(v.as_str()),
+                        serde_json::Value::Number(_) | serde_json::Value::Bool(_) => {
+                            Cow::Owned(value.to_string())
+                        }
+                        _ => return Err(invalid("tag")),
+                    };
+                    record.tags.push((name, v));
+                }
+                ColumnKind::Time => {
+                    let time = match value {
+                        serde_json::Value::String(v) => writer.parse_time(line, name, v)?,
+                        serde_json::Value::Number(v) => v
+                            .as_i64()
+                            .and_then(|t| t.checked_mul(writer.timestamp_base))
+                            .ok_or_else(|| invalid("dateTime"))?,
+                        _ => return Err(invalid("dateTime")),
+                    };
+                    record.time = Some(time);
+                }
+                ColumnKind::Field(data_type) => {
+                    let v = json_field(data_type, value)
+                        .ok_or_else(|| invalid(data_type.map_or("field", |t| t.name())))?;
+                    record.fields.push((name, v));
+                }
+                ColumnKind::Ignored => {}
+            }
+        }
+
+        writer.write(line, record)?;
+    }
+
+    Ok(())
+}
+
+/// Convert a JSON field value to `data_type`, or the type of the JSON value
+/// if no type is declared.
+fn json_field(data_type: Option<FieldType>, v: &serde_json::Value) -> Option<FieldValue<'_>> {
+    use serde_json::Value;
+
+    Some(match (data_type, v) {
+        (None | Some(FieldType::Float), Value::Number(v)) => FieldValue::Float(v.as_f64()?),
+        (Some(FieldType::Integer), Value::Number(v)) => FieldValue::Integer(v.as_i64()?),
+        (Some(FieldType::UInteger), Value::Number(v)) => FieldValue::UInteger(v.as_u64()?),
+        (None | Some(FieldType::Boolean), Value::Bool(v)) => FieldValue::Boolean(*v),
+        (None | Some(FieldType::String), Value::String(v)) => {
+            FieldValue::String(Cow::Borrowed(v.as_str()))
+        }
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_util::assert_batches_sorted_eq;
+    use assert_matches::assert_matches;
+    use schema::Projection;
+
+    use super::*;
+
+    const DEFAULT_TIME: i64 = 42;
+
+    fn params(query: &str) -> RecordParams {
+        RecordParams::try_from_query(Some(query)).unwrap()
+    }
+
+    fn decode(
+        format: RecordFormat,
+        body: &str,
+        query: &str,
+    ) -> Result<(HashMap<String, MutableBatch>, RecordStatistics), RecordWriteError> {
+        decode_records(format, body.as_bytes(), &params(query), DEFAULT_TIME, 1)
+    }
+
+    #[test]
+    fn test_record_format() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(RecordFormat::from_headers(&headers), None);
+
+        headers.insert(CONTENT_TYPE, "text/plain".parse().unwrap());
+        assert_eq!(RecordFormat::from_headers(&headers), None);
+
+        headers.insert(CONTENT_TYPE, "text/csv; charset=utf-8".parse().unwrap());
+        assert_eq!(
+            RecordFormat::from_headers(&headers),
+            Some(RecordFormat::Csv)
+        );
+
+        headers.insert(CONTENT_TYPE, "application/x-ndjson".parse().unwrap());
+        assert_eq!(
+            RecordFormat::from_headers(&headers),
+            Some(RecordFormat::Ndjson)
+        );
+    }
+
+    #[test]
+    fn test_csv_annotated() {
+        let body = "\
+#datatype measurement,tag,double,long,ignored,dateTime:RFC3339
+m,host,usage,count,notes,time
+cpu,a,1.5,1,bananas,1970-01-01T00:00:00.000000001Z
+cpu,,2.5,,,1970-01-01T00:00:00.000000002Z
+mem,b,3,3,,
+";
+        let (batches, stats) = decode(RecordFormat::Csv, body, "").unwrap();
+        assert_eq!(stats.num_records, 3);
+        assert_eq!(stats.num_fields, 5);
+
+        assert_batches_sorted_eq!(
+            [
+                "+-------+------+--------------------------------+-------+",
+                "| count | host | time                           | usage |",
+                "+-------+------+--------------------------------+-------+",
+                "|       |      | 1970-01-01T00:00:00.000000002Z | 2.5   |",
+                "| 1     | a    | 1970-01-01T00:00:00.000000001Z | 1.5   |",
+                "+-------+------+--------------------------------+-------+",
+            ],
+            &[batches["cpu"].to_arrow(Projection::All).unwrap()]
+        );
+        assert_batches_sorted_eq!(
+            [
+                "+-------+------+--------------------------------+-------+",
+                "| count | host | time                           | usage |",
+                "+-------+------+--------------------------------+-------+",
+                "| 3     | b    | 1970-01-01T00:00:00.000000042Z | 3.0   |",
+                "+-------+------+--------------------------------+-------+",
+            ],
+            &[batches["mem"].to_arrow(Projection::All).unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_csv_params() {
+        let body = "\
+# a comment
+region,host,ok,status,time
+west,a,true,200,1
+east,b,false,500,2
+";
+        let (batches, stats) = decode(
+            RecordFormat::Csv,
+            body,
+            "bucket=bananas&measurement=http&tag_columns=region,host&field_types=status:unsignedLong",
+        )
+        .unwrap();
+        assert_eq!(stats.num_records, 2);
+
+        assert_batches_sorted_eq!(
+            [
+                "+------+-------+--------+--------+--------------------------------+",
+                "| host | ok    | region | status | time                           |",
+                "+------+-------+--------+--------+--------------------------------+",
+                "| a    | true  | west   | 200    | 1970-01-01T00:00:00.000000001Z |",
+                "| b    | false | east   | 500    | 1970-01-01T00:00:00.000000002Z |",
+                "+------+-------+--------+--------+--------------------------------+",
+            ],
+            &[batches["http"].to_arrow(Projection::All).unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_csv_errors() {
+        assert_matches!(
+            decode(RecordFormat::Csv, "v,time\n1,1\n", ""),
+            Err(RecordWriteError::NoMeasurement { line: 2 })
+        );
+        assert_matches!(
+            decode(RecordFormat::Csv, "v,v\n1,1\n", "measurement=m"),
+            Err(RecordWriteError::DuplicateColumn(c)) => {
+                assert_eq!(c, "v");
+            }
+        );
+        assert_matches!(
+            decode(RecordFormat::Csv, "v,time\n1\n", "measurement=m"),
+            Err(RecordWriteError::RowLength {
+                line: 2,
+                want: 2,
+                got: 1
+            })
+        );
+        assert_matches!(
+            decode(RecordFormat::Csv, "v,time\n1,1\nbananas,2\n", "measurement=m"),
+            Err(RecordWriteError::InvalidValue { line: 3, column, data_type: "double", .. }) => {
+                assert_eq!(column, "v");
+            }
+        );
+        assert_matches!(
+            decode(RecordFormat::Csv, "v,time\n1,yesterday\n", "measurement=m"),
+            Err(RecordWriteError::InvalidValue {
+                line: 2,
+                data_type: "dateTime",
+                ..
+            })
+        );
+        assert_matches!(
+            decode(RecordFormat::Csv, "#datatype tag,bananas\nt,v\n", ""),
+            Err(RecordWriteError::UnknownDataType(t)) => {
+                assert_eq!(t, "bananas");
+            }
+        );
+        assert_matches!(
+            decode(
+                RecordFormat::Csv,
+                "t,time\na,1\n",
+                "measurement=m&tag_columns=t"
+            ),
+            Err(RecordWriteError::NoFields { line: 2 })
+        );
+        assert_matches!(
+            decode(RecordFormat::Csv, "v\n1\n", "measurement=m&field_types=v"),
+            Err(RecordWriteError::InvalidFieldType(_))
+        );
+        assert_matches!(
+            decode(
+                RecordFormat::Csv,
+                "v\n1\n",
+                "measurement=m&field_types=v:tag"
+            ),
+            Err(RecordWriteError::InvalidFieldType(_))
+        );
+    }
+
+    #[test]
+    fn test_ndjson() {
+        let body = r#"
+{"m": "cpu", "host": "a", "usage": 1, "ok": true, "time": 1}
+{"m": "cpu", "host": 2, "usage": 2.5, "count": 3, "time": "1970-01-01T00:00:00.000000002Z"}
+
+{"m": "cpu", "host": null, "usage": 3, "note": "bananas"}
+"#;
+        let (batches, stats) = decode(
+            RecordFormat::Ndjson,
+            body,
+            "measurement_column=m&tag_columns=host&field_types=count:long",
+        )
+        .unwrap();
+        assert_eq!(stats.num_records, 3);
+        assert_eq!(stats.num_fields, 6);
+
+        assert_batches_sorted_eq!(
+            [
+                "+-------+------+---------+------+--------------------------------+-------+",
+                "| count | host | note    | ok   | time                           | usage |",
+                "+-------+------+---------+------+--------------------------------+-------+",
+                "|       |      | bananas |      | 1970-01-01T00:00:00.000000042Z | 3.0   |",
+                "|       | a    |         | true | 1970-01-01T00:00:00.000000001Z | 1.0   |",
+                "| 3     | 2    |         |      | 1970-01-01T00:00:00.000000002Z | 2.5   |",
+                "+-------+------+---------+------+--------------------------------+-------+",
+            ],
+            &[batches["cpu"].to_arrow(Projection::All).unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_ndjson_errors() {
+        assert_matches!(
+            decode(RecordFormat::Ndjson, "{\"v\": 1}\n[1]\n", "measurement=m"),
+            Err(RecordWriteError::Json { line: 2, .. })
+        );
+        assert_matches!(
+            decode(RecordFormat::Ndjson, r#"{"v": {"a": 1}}"#, "measurement=m"),
+            Err(RecordWriteError::InvalidValue { line: 1, column, .. }) => {
+                assert_eq!(column, "v");
+            }
+        );
+        assert_matches!(
+            decode(
+                RecordFormat::Ndjson,
+                r#"{"v": 1.5}"#,
+                "measurement=m&field_types=v:long"
+            ),
+            Err(RecordWriteError::InvalidValue {
+                data_type: "long",
+                ..
+            })
+        );
+        assert_matches!(
+            decode(RecordFormat::Ndjson, r#"{"v": 1}"#, ""),
+            Err(RecordWriteError::NoMeasurement { line: 1 })
+        );
+        // A field conflicting with the type of an earlier record.
+        assert_matches!(
+            decode(
+                RecordFormat::Ndjson,
+                "{\"v\": 1}\n{\"v\": \"a\"}\n",
+                "measurement=m"
+            ),
+            Err(RecordWriteError::Write { line: 2, .. })
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use std::{iter, string::String, sync::Arc, time::Duration};
 
 use data_types::TableId;
-use generated_types::influxdata::iox::ingester::v1::WriteRequest;
+use generated_types::influxdata::iox::ingester::v1::{DeleteRequest, WriteRequest};
 use hashbrown::HashMap;
 use hyper::{Body, Request, Response};
 use iox_catalog::{
@@ -14,7 +14,7 @@ use router::{
     dml_handlers::{
         client::mock::MockWriteClient, Chain, DmlHandlerChainExt, FanOutAdaptor,
         InstrumentationDecorator, Partitioned, Partitioner, RetentionValidator, RpcWrite,
-        SchemaValidator,
+        SchemaValidator, TombstoneRecorder,
     },
     namespace_cache::{MemoryNamespaceCache, ReadThroughCache, ShardedCache},
     namespace_resolver::{MissingNamespaceAction, NamespaceAutocreation, NamespaceSchemaResolver},
@@ -113,15 +113,20 @@ type HttpDelegateStack = HttpDelegate<
         Chain<
             Chain<
                 Chain<
-                    RetentionValidator,
-                    SchemaValidator<Arc<ReadThroughCache<Arc<ShardedCache<MemoryNamespaceCache>>>>>,
+                    Chain<
+                        RetentionValidator,
+                        SchemaValidator<
+                            Arc<ReadThroughCache<Arc<ShardedCache<MemoryNamespaceCache>>>>,
+                        >,
+                    >,
+                    Partitioner,
+                >,
+                FanOutAdaptor<
+                    RpcWrite<Arc<MockWriteClient>>,
+                    Vec<Partitioned<HashMap<TableId, (String, MutableBatch)>>>,
                 >,
-                Partitioner,
-            >,
-            FanOutAdaptor<
-                RpcWrite<Arc<MockWriteClient>>,
-                Vec<Partitioned<HashMap<TableId, (String, MutableBatch)>>>,
             >,
+            TombstoneRecorder<()>,
         >,
     >,
     NamespaceAutocreation<
@@ -175,7 +180,8 @@ impl TestContext {
         let handler_stack = retention_validator
             .and_then(schema_validator)
             .and_then(partitioner)
-            .and_then(parallel_write);
+            .and_then(parallel_write)
+            .and_then(TombstoneRecorder::new(Arc::clone(&catalog)));
 
         let handler_stack = InstrumentationDecorator::new("request", &metrics, handler_stack);
 
@@ -184,6 +190,7 @@ impl TestContext {
         let http_delegate = HttpDelegate::new(
             1024,
             100,
+            false,
             namespace_resolver,
             handler_stack,
             &metrics,
@@ -191,7 +198,7 @@ impl TestContext {
         );
 
         let grpc_delegate =
-            RpcWriteGrpcDelegate::new(Arc::clone(&catalog), Arc::new(InMemory::default()));
+            RpcWriteGrpcDelegate::new(Arc::clone(&catalog), Arc::new(InMemory::default()), None);
 
         Self {
             client,
@@ -242,6 +249,10 @@ impl TestContext {
         self.client.calls()
     }
 
+    pub fn delete_calls(&self) -> Vec<DeleteRequest> {
+        self.client.delete_calls()
+    }
+
     /// Get a reference to the test context's metrics.
     pub fn metrics(&self) -> &metric::Registry {
         self.metrics.as_ref()
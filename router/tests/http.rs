@@ -2,7 +2,10 @@ use crate::common::{TestContextBuilder, TEST_RETENTION_PERIOD};
 use assert_matches::assert_matches;
 use data_types::{ColumnType, MaxTables};
 use futures::{stream::FuturesUnordered, StreamExt};
-use generated_types::influxdata::{iox::ingester::v1::WriteRequest, pbdata::v1::DatabaseBatch};
+use generated_types::influxdata::{
+    iox::ingester::v1::{DeleteRequest, WriteRequest},
+    pbdata::v1::DatabaseBatch,
+};
 use hashbrown::HashMap;
 use hyper::{Body, Request, StatusCode};
 use iox_catalog::{interface::SoftDeletedRows, test_helpers::arbitrary_namespace};
@@ -335,13 +338,82 @@ async fn test_write_propagate_ids() {
 }
 
 #[tokio::test]
-async fn test_delete_unsupported() {
+async fn test_delete() {
     let ctx = TestContextBuilder::default()
         .with_autocreate_namespace(None)
         .build()
         .await;
 
-    // Create the namespace and a set of tables.
+    // Write some data to create the namespace and table.
+    let response = ctx
+        .write_lp("bananas", "test", "bananas,tag1=A val=42i 123456")
+        .await
+        .expect("write should succeed");
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let table_id = ctx.table_id("bananas_test", "bananas").await;
+
+    let body = r#"{
+        "predicate": "_measurement=bananas and tag1=A",
+        "start": "1970-01-01T00:00:00Z",
+        "stop": "2070-01-02T00:00:00Z"
+    }"#;
+    let request = Request::builder()
+        .uri("https://bananas.example/api/v2/delete?org=bananas&bucket=test")
+        .method("POST")
+        .body(Body::from(body))
+        .expect("failed to construct HTTP request");
+
+    let response = ctx
+        .http_delegate()
+        .route(request)
+        .await
+        .expect("delete should succeed");
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    // The ingester must have been sent the delete.
+    let deletes = ctx.delete_calls();
+    assert_matches!(
+        deletes.as_slice(),
+        [DeleteRequest { payload: Some(payload) }] => {
+            assert_eq!(payload.table_id, table_id.get());
+            let predicate = payload.predicate.as_ref().expect("no predicate");
+            assert_eq!(predicate.exprs.len(), 1);
+            assert_eq!(predicate.exprs[0].column, "tag1");
+        }
+    );
+
+    // And the delete must have been recorded in the catalog.
+    let tombstones = ctx
+        .catalog()
+        .repositories()
+        .await
+        .tombstones()
+        .list_by_table_id(table_id)
+        .await
+        .expect("query failed");
+    assert_eq!(tombstones.len(), 1);
+    assert_eq!(tombstones[0].serialized_predicate, r#""tag1"='A'"#);
+
+    assert_eq!(
+        ctx.metrics()
+            .get_instrument::<Metric<U64Counter>>("http_delete_body_bytes")
+            .expect("failed to read metric")
+            .get_observer(&Attributes::from(&[]))
+            .expect("failed to get observer")
+            .fetch(),
+        body.len() as u64,
+    );
+}
+
+#[tokio::test]
+async fn test_delete_unknown_table() {
+    let ctx = TestContextBuilder::default()
+        .with_autocreate_namespace(None)
+        .build()
+        .await;
+
+    // Create the namespace.
     ctx.catalog()
         .repositories()
         .await
@@ -353,7 +425,7 @@ async fn test_delete_unsupported() {
             None,
         )
         .await
-        .expect("failed to update table limit");
+        .expect("failed to create namespace");
 
     let request = Request::builder()
         .uri("https://bananas.example/api/v2/delete?org=bananas&bucket=test")
@@ -371,12 +443,10 @@ async fn test_delete_unsupported() {
 
     assert_matches!(
         &err,
-        e @ router::server::http::Error::DeletesUnsupported => {
-            assert_eq!(
-                e.to_string(),
-                "deletes are not supported"
-            );
+        e @ router::server::http::Error::TableNotFound(_) => {
+            assert_eq!(e.to_string(), "table bananas does not exist");
         }
     );
-    assert_eq!(err.as_status_code(), StatusCode::NOT_IMPLEMENTED);
+    assert_eq!(err.as_status_code(), StatusCode::NOT_FOUND);
+    assert!(ctx.delete_calls().is_empty());
 }
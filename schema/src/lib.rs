@@ -42,6 +42,16 @@ pub const TIME_COLUMN_NAME: &str = "time";
 pub const INFLUXQL_MEASUREMENT_COLUMN_NAME: &str = "iox::measurement";
 /// The key identifying the schema-level metadata.
 pub const INFLUXQL_METADATA_KEY: &str = "iox::influxql::group_key::metadata";
+/// The schema-level metadata key identifying the InfluxQL statement that
+/// produced a result, when a query contains multiple statements.
+pub const INFLUXQL_STATEMENT_ID_KEY: &str = "iox::influxql::statement_id";
+/// The schema-level metadata key containing the error message of an
+/// InfluxQL statement that failed.
+pub const INFLUXQL_STATEMENT_ERROR_KEY: &str = "iox::influxql::statement_error";
+/// The gRPC status metadata key containing the number of InfluxQL statements
+/// that failed, set on the error ending the results of a query with multiple
+/// statements.
+pub const INFLUXQL_STATEMENT_ERRORS_STATUS_KEY: &str = "iox-influxql-statement-errors";
 
 /// The Timezone to use for InfluxDB timezone (should be a constant)
 #[allow(non_snake_case)]
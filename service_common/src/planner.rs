@@ -15,7 +15,7 @@ use iox_query::{
 use iox_query_influxrpc::InfluxRpcPlanner;
 
 pub use datafusion::error::{DataFusionError as Error, Result};
-use iox_query_influxql::frontend::planner::InfluxQLQueryPlanner;
+use iox_query_influxql::{frontend::planner::InfluxQLQueryPlanner, params::StatementParams};
 use predicate::rpc_predicate::InfluxRpcPredicate;
 
 /// Query planner that plans queries on a separate threadpool.
@@ -50,17 +50,19 @@ impl Planner {
     }
 
     /// Plan an InfluxQL query against the data in `database`, and return a
-    /// DataFusion physical execution plan.
+    /// DataFusion physical execution plan. Bind parameters in the query are
+    /// replaced by the values in `params`.
     pub async fn influxql(
         &self,
         query: impl Into<String> + Send,
+        params: StatementParams,
     ) -> Result<Arc<dyn ExecutionPlan>> {
         let planner = InfluxQLQueryPlanner::new();
         let query = query.into();
         let ctx = self.ctx.child_ctx("planner influxql");
 
         self.ctx
-            .run(async move { planner.query(&query, &ctx).await })
+            .run(async move { planner.query(&query, &params, &ctx).await })
             .await
     }
 
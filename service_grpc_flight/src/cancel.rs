@@ -0,0 +1,61 @@
+//! Query cancellation using the Flight `DoAction` method.
+//!
+//! Two actions are supported:
+//!
+//! - `CancelQuery`: the native IOx action, cancelling a query by the ID
+//!   shown in the `system.queries` table. The body is a
+//!   [`CancelQueryRequest`](generated_types::influxdata::iox::querier::v1::CancelQueryRequest).
+//!
+//! - `CancelFlightInfo`: the [Arrow Flight] action, cancelling the queries
+//!   of the tickets of a [`FlightInfo`] returned by `GetFlightInfo`. The
+//!   body is a [`CancelFlightInfoRequest`].
+//!
+//! [Arrow Flight]: https://arrow.apache.org/docs/format/Flight.html
+
+use arrow_flight::FlightInfo;
+
+/// Action type of the native IOx query cancellation.
+pub(crate) const CANCEL_QUERY_ACTION: &str = "CancelQuery";
+
+/// Action type of the Arrow Flight query cancellation.
+pub(crate) const CANCEL_FLIGHT_INFO_ACTION: &str = "CancelFlightInfo";
+
+/// The request of the `CancelFlightInfo` action.
+///
+/// This mirrors the message of the Arrow Flight protocol, which the
+/// `arrow-flight` crate does not provide yet.
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct CancelFlightInfoRequest {
+    /// The result of the `GetFlightInfo` call to cancel.
+    #[prost(message, optional, tag = "1")]
+    pub info: Option<FlightInfo>,
+}
+
+/// The result of the `CancelFlightInfo` action.
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct CancelFlightInfoResult {
+    /// The [`CancelStatus`] of the queries.
+    #[prost(enumeration = "CancelStatus", tag = "1")]
+    pub status: i32,
+}
+
+/// The status of a `CancelFlightInfo` action.
+///
+/// Not all statuses are returned by IOx.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub(crate) enum CancelStatus {
+    /// The cancellation status is unknown.
+    Unspecified = 0,
+
+    /// The queries were cancelled.
+    Cancelled = 1,
+
+    /// The queries are being cancelled.
+    Cancelling = 2,
+
+    /// The queries cannot be cancelled, for example because they already
+    /// completed.
+    NotCancellable = 3,
+}
@@ -20,33 +20,48 @@ use keep_alive::KeepAliveStream;
 // Workaround for "unused crate" lint false positives.
 use workspace_hack as _;
 
+mod cancel;
 mod keep_alive;
 mod request;
 
-use arrow::error::ArrowError;
+use arrow::{datatypes::Fields, error::ArrowError, ipc::writer::IpcWriteOptions};
 use arrow_flight::{
-    encode::FlightDataEncoderBuilder,
+    decode::FlightRecordBatchStream,
+    encode::{FlightDataEncoder, FlightDataEncoderBuilder},
+    error::FlightError,
     flight_descriptor::DescriptorType,
     flight_service_server::{FlightService as Flight, FlightServiceServer as FlightServer},
     Action, ActionType, Criteria, Empty, FlightData, FlightDescriptor, FlightEndpoint, FlightInfo,
-    HandshakeRequest, HandshakeResponse, PutResult, SchemaResult, Ticket,
+    HandshakeRequest, HandshakeResponse, PutResult, SchemaAsIpc, SchemaResult, Ticket,
 };
 use authz::{extract_token, Authorizer};
+use bytes::Bytes;
+use cancel::{
+    CancelFlightInfoRequest, CancelFlightInfoResult, CancelStatus, CANCEL_FLIGHT_INFO_ACTION,
+    CANCEL_QUERY_ACTION,
+};
 use data_types::NamespaceNameError;
 use datafusion::{error::DataFusionError, physical_plan::ExecutionPlan};
-use flightsql::FlightSQLCommand;
-use futures::{ready, Stream, StreamExt, TryStreamExt};
+use flightsql::{FlightSQLCommand, FlightSQLPlanner};
+use futures::{future::BoxFuture, ready, FutureExt, Stream, StreamExt, TryStreamExt};
 use generated_types::influxdata::iox::querier::v1 as proto;
-use iox_query::{exec::IOxSessionContext, QueryCompletedToken, QueryNamespace};
+use iox_query::{
+    exec::IOxSessionContext, QueryCanceller, QueryCompletedToken, QueryNamespace, QueryText,
+};
+use iox_query_influxql::{frontend::planner::InfluxQLQueryPlanner, params::StatementParams};
 use observability_deps::tracing::{debug, info, warn};
 use prost::Message;
 use request::{IoxGetRequest, RunQuery};
+use schema::{
+    INFLUXQL_STATEMENT_ERRORS_STATUS_KEY, INFLUXQL_STATEMENT_ERROR_KEY, INFLUXQL_STATEMENT_ID_KEY,
+};
 use service_common::{datafusion_error_to_tonic_code, planner::Planner, QueryNamespaceProvider};
 use snafu::{OptionExt, ResultExt, Snafu};
 use std::{
+    collections::HashMap,
     fmt::Debug,
     pin::Pin,
-    sync::Arc,
+    sync::{Arc, Mutex},
     task::Poll,
     time::{Duration, Instant},
 };
@@ -85,6 +100,18 @@ pub enum Error {
     #[snafu(display("Invalid handshake. No payload provided"))]
     InvalidHandshake {},
 
+    #[snafu(display("Invalid DoPut request. No FlightDescriptor provided"))]
+    NoFlightDescriptor,
+
+    #[snafu(display("Invalid CancelFlightInfo request. No FlightInfo provided"))]
+    NoFlightInfo,
+
+    #[snafu(display(
+        "Query cancellation is not supported for database '{}'",
+        namespace_name
+    ))]
+    QueryCancellationNotSupported { namespace_name: String },
+
     #[snafu(display("Database '{}' not found", namespace_name))]
     DatabaseNotFound { namespace_name: String },
 
@@ -163,6 +190,9 @@ impl From<Error> for tonic::Status {
             Error::DatabaseNotFound { .. }
             | Error::InvalidTicket { .. }
             | Error::InvalidHandshake { .. }
+            | Error::NoFlightDescriptor
+            | Error::NoFlightInfo
+            | Error::QueryCancellationNotSupported { .. }
             | Error::Unauthenticated { .. }
             | Error::PermissionDenied { .. }
             | Error::InvalidDatabaseName { .. }
@@ -195,6 +225,8 @@ impl Error {
             Self::DatabaseNotFound { .. } => tonic::Code::NotFound,
             Self::InvalidTicket { .. }
             | Self::InvalidHandshake { .. }
+            | Self::NoFlightDescriptor
+            | Self::NoFlightInfo
             | Self::Deserialization { .. }
             | Self::TooManyFlightSQLDatabases { .. }
             | Self::NoFlightSQLDatabase
@@ -203,9 +235,12 @@ impl Error {
             Self::Planning { source, .. } | Self::Query { source, .. } => {
                 datafusion_error_to_tonic_code(&source)
             }
-            Self::UnsupportedMessageType { .. } => tonic::Code::Unimplemented,
+            Self::UnsupportedMessageType { .. } | Self::QueryCancellationNotSupported { .. } => {
+                tonic::Code::Unimplemented
+            }
             Self::FlightSQL { source } => match source {
                 flightsql::Error::InvalidHandle { .. }
+                | flightsql::Error::InvalidParameters { .. }
                 | flightsql::Error::Decode { .. }
                 | flightsql::Error::Protocol { .. }
                 | flightsql::Error::UnsupportedMessageType { .. } => tonic::Code::InvalidArgument,
@@ -234,6 +269,8 @@ impl Error {
             Error::InvalidTicket { .. }
             | Error::InternalCreatingTicket { .. }
             | Error::InvalidHandshake {}
+            | Error::NoFlightDescriptor
+            | Error::NoFlightInfo
             | Error::TooManyFlightSQLDatabases { .. }
             | Error::NoFlightSQLDatabase
             | Error::InvalidDatabaseHeader { .. }
@@ -247,6 +284,7 @@ impl Error {
             | Error::PermissionDenied
             | Error::Authz { .. } => "<unknown>",
             Error::DatabaseNotFound { namespace_name } => namespace_name,
+            Error::QueryCancellationNotSupported { namespace_name } => namespace_name,
             Error::Query { namespace_name, .. } => namespace_name,
             Error::Planning { namespace_name, .. } => namespace_name,
         }
@@ -258,6 +296,8 @@ impl Error {
             Error::InvalidTicket { .. }
             | Error::InternalCreatingTicket { .. }
             | Error::InvalidHandshake {}
+            | Error::NoFlightDescriptor
+            | Error::NoFlightInfo
             | Error::TooManyFlightSQLDatabases { .. }
             | Error::NoFlightSQLDatabase
             | Error::InvalidDatabaseHeader { .. }
@@ -270,7 +310,8 @@ impl Error {
             | Error::Unauthenticated
             | Error::PermissionDenied
             | Error::Authz { .. }
-            | Error::DatabaseNotFound { .. } => "NONE",
+            | Error::DatabaseNotFound { .. }
+            | Error::QueryCancellationNotSupported { .. } => "NONE",
             Error::Query { query, .. } => query,
             Error::Planning { query, .. } => query,
         }
@@ -356,6 +397,26 @@ type TonicStream<T> = Pin<Box<dyn Stream<Item = Result<T, tonic::Status>> + Send
 ///     3 ┃◀ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ┃
 /// ```
 ///
+/// ## Multiple InfluxQL statements
+///
+/// An InfluxQL query may contain multiple `;` separated statements,
+/// which are executed in order. The results of each statement are
+/// sent as a separate partition of the stream, starting with a schema
+/// message whose metadata contains the index of the statement under
+/// the `iox::influxql::statement_id` key.
+///
+/// If a statement fails, its partition ends with a schema message with
+/// no fields, whose metadata also contains the error message under the
+/// `iox::influxql::statement_error` key, and the remaining statements
+/// are still executed. Once all statements are executed, the stream ends
+/// with a gRPC error reporting the first statement that failed, whose
+/// metadata contains the number of failed statements under the
+/// `iox-influxql-statement-errors` key.
+///
+/// Clients that expect a single schema per stream, such as Arrow's
+/// `FlightRecordBatchStream`, reject the results of multiple statements.
+/// The IOx client decodes them with `Client::influxql_statements`.
+///
 /// # FlightSQL
 ///
 /// IOx also supports [Arrow FlightSQL]. In addition to `DoGet`,
@@ -411,7 +472,7 @@ type TonicStream<T> = Pin<Box<dyn Stream<Item = Result<T, tonic::Status>> + Send
 ///       ┃                                                  ┃
 /// ```
 ///
-/// ## FlightSQL Prepared Statement (no bind parameters like $1, etc)
+/// ## FlightSQL Prepared Statement
 ///
 /// To run a prepared query, via FlightSQL, the client undertakes a
 /// few more steps:
@@ -461,6 +522,43 @@ type TonicStream<T> = Pin<Box<dyn Stream<Item = Result<T, tonic::Status>> + Send
 ///     7 ┃◀ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ━ ┃
 /// ```
 ///
+/// ## FlightSQL Prepared Statement with bind parameters
+///
+/// Queries may contain placeholders (`$1`, `$2`, etc). The
+/// `ActionCreatePreparedStatementResponse` then contains a
+/// `parameter_schema` with one field per placeholder, in order.
+///
+/// Before step 4 above, the client binds values to the placeholders:
+///
+/// 1. Encode the handle in a `CommandPreparedStatementQuery` in a
+/// [`FlightDescriptor`] and call the `DoPut` method with a single
+/// row of parameter values matching the `parameter_schema`
+///
+/// 2. Receive a [`PutResult`] whose `app_metadata` contains a
+/// `DoPutPreparedStatementResult` with a new handle. As IOx is
+/// stateless, the new handle carries the bound values.
+///
+/// 3. Proceed from step 4 above with the new handle. The values are
+/// substituted into the plan, never into the SQL text.
+///
+/// # Query cancellation
+///
+/// Running queries are cancelled by calling the `DoAction` method with
+/// either
+///
+/// - a `CancelQuery` action, whose body is a `CancelQueryRequest` with
+/// the ID of the query in the `system.queries` table, or
+///
+/// - a FlightSQL `CancelFlightInfo` action, whose body contains the
+/// [`FlightInfo`] returned by `GetFlightInfo` for the query. Its tickets
+/// carry an opaque handle of the query log ID the querier reserved for the
+/// query, and only the queries run with these tickets are cancelled.
+///
+/// Both actions require write access to the namespace, as for the InfluxQL
+/// `KILL QUERY` statement.
+///
+/// The `DoGet` stream of a cancelled query ends with a `CANCELLED` error.
+///
 /// [Arrow Flight]: https://arrow.apache.org/docs/format/Flight.html
 /// [Arrow FlightSQL]: https://arrow.apache.org/docs/format/FlightSql.html
 #[derive(Debug)]
@@ -487,14 +585,17 @@ where
     S: QueryNamespaceProvider,
 {
     /// Implementation of the `DoGet` method
+    #[allow(clippy::too_many_arguments)]
     async fn run_do_get(
         &self,
         span_ctx: Option<SpanContext>,
         external_span_ctx: Option<RequestLogContext>,
+        authz_token: Option<Vec<u8>>,
         permit: InstrumentedAsyncOwnedSemaphorePermit,
         query: RunQuery,
         namespace_name: String,
         is_debug: bool,
+        query_handle: Option<u64>,
     ) -> Result<Response<TonicStream<FlightData>>, tonic::Status> {
         let db = self
             .server
@@ -508,14 +609,24 @@ where
                 namespace_name: &namespace_name,
             })?;
 
-        let ctx = db.new_query_context(span_ctx);
-        let (query_completed_token, physical_plan) = match &query {
+        let ctx = db.new_query_context(span_ctx).with_authz_token(authz_token);
+
+        // The queries of tickets returned by `GetFlightInfo` are recorded with
+        // the ID reserved for their handle, so they can be cancelled by
+        // `CancelFlightInfo`.
+        let record_query = |query_type: &'static str, query_text: QueryText| {
+            let span_ctx = external_span_ctx.as_ref().map(RequestLogContext::ctx);
+            match query_handle {
+                Some(query_handle) => {
+                    db.record_query_with_handle(span_ctx, query_type, query_text, query_handle)
+                }
+                None => db.record_query(span_ctx, query_type, query_text),
+            }
+        };
+
+        let (query_completed_token, output) = match &query {
             RunQuery::Sql(sql_query) => {
-                let token = db.record_query(
-                    external_span_ctx.as_ref().map(RequestLogContext::ctx),
-                    "sql",
-                    Box::new(sql_query.clone()),
-                );
+                let token = record_query("sql", Box::new(sql_query.clone()));
                 let plan = Planner::new(&ctx)
                     .sql(sql_query)
                     .await
@@ -523,29 +634,33 @@ where
                         namespace_name: &namespace_name,
                         query: query.to_string(),
                     })?;
-                (token, plan)
+                let output = execute_plan(&ctx, plan, &namespace_name, &query).await?;
+                (token, output.boxed())
             }
-            RunQuery::InfluxQL(sql_query) => {
-                let token = db.record_query(
-                    external_span_ctx.as_ref().map(RequestLogContext::ctx),
-                    "influxql",
-                    Box::new(sql_query.clone()),
-                );
-                let plan = Planner::new(&ctx)
-                    .influxql(sql_query)
-                    .await
-                    .context(PlanningSnafu {
+            RunQuery::InfluxQL(sql_query, params) => {
+                let token = record_query("influxql", Box::new(sql_query.clone()));
+                let statements =
+                    InfluxQLQueryPlanner::split_statements(sql_query).context(PlanningSnafu {
                         namespace_name: &namespace_name,
                         query: query.to_string(),
                     })?;
-                (token, plan)
+                if statements.len() > 1 {
+                    let output = influxql_statements_stream(ctx, statements, params.clone());
+                    (token, output.boxed())
+                } else {
+                    let plan = Planner::new(&ctx)
+                        .influxql(sql_query, params.clone())
+                        .await
+                        .context(PlanningSnafu {
+                            namespace_name: &namespace_name,
+                            query: query.to_string(),
+                        })?;
+                    let output = execute_plan(&ctx, plan, &namespace_name, &query).await?;
+                    (token, output.boxed())
+                }
             }
             RunQuery::FlightSQL(msg) => {
-                let token = db.record_query(
-                    external_span_ctx.as_ref().map(RequestLogContext::ctx),
-                    "flightsql",
-                    Box::new(msg.to_string()),
-                );
+                let token = record_query("flightsql", Box::new(msg.to_string()));
                 let plan = Planner::new(&ctx)
                     .flight_sql_do_get(&namespace_name, db, msg.clone())
                     .await
@@ -553,19 +668,12 @@ where
                         namespace_name: &namespace_name,
                         query: query.to_string(),
                     })?;
-                (token, plan)
+                let output = execute_plan(&ctx, plan, &namespace_name, &query).await?;
+                (token, output.boxed())
             }
         };
 
-        let output = GetStream::new(
-            ctx,
-            physical_plan,
-            namespace_name.to_string(),
-            &query,
-            query_completed_token,
-            permit,
-        )
-        .await?;
+        let output = GetStream::new(output, query_completed_token, permit);
 
         // Log any error that happens *during* execution (other error
         // handling in this file happen during planning)
@@ -584,6 +692,110 @@ where
 
         Ok(Response::new(Box::pin(output) as TonicStream<FlightData>))
     }
+
+    /// Implementation of the `CancelQuery` action
+    async fn run_cancel_query(
+        &self,
+        span_ctx: Option<SpanContext>,
+        authz_token: Option<Vec<u8>>,
+        body: Bytes,
+    ) -> Result<Bytes, tonic::Status> {
+        let proto::CancelQueryRequest { database, query_id } =
+            proto::CancelQueryRequest::decode(body).context(DeserializationSnafu)?;
+        info!(%database, %query_id, "CancelQuery request");
+
+        // cancelling a query by ID, which is not necessarily the caller's,
+        // requires the same permission as the InfluxQL `KILL QUERY` statement
+        let perms = [authz::Permission::ResourceAction(
+            authz::Resource::Database(database.clone()),
+            authz::Action::Write,
+        )];
+        self.authz
+            .permissions(authz_token, &perms)
+            .await
+            .map_err(Error::from)?;
+
+        let cancelled = self
+            .query_canceller(&database, span_ctx)
+            .await?
+            .cancel_query(query_id);
+
+        Ok(proto::CancelQueryResponse { cancelled }
+            .encode_to_vec()
+            .into())
+    }
+
+    /// Implementation of the FlightSQL `CancelFlightInfo` action
+    async fn run_cancel_flight_info(
+        &self,
+        span_ctx: Option<SpanContext>,
+        authz_token: Option<Vec<u8>>,
+        body: Bytes,
+    ) -> Result<Bytes, tonic::Status> {
+        let info = CancelFlightInfoRequest::decode(body)
+            .context(DeserializationSnafu)?
+            .info
+            .context(NoFlightInfoSnafu)?;
+
+        let mut cancelled = false;
+        for ticket in info.endpoint.into_iter().filter_map(|e| e.ticket) {
+            let request = IoxGetRequest::try_decode(ticket).context(InvalidTicketSnafu)?;
+            let namespace_name = request.database();
+            let query_handle = request.query_handle();
+            info!(%namespace_name, ?query_handle, "CancelFlightInfo request");
+
+            // as for `CancelQuery`, the query is identified by its ID, so
+            // cancelling it requires the permission of `KILL QUERY`
+            let perms = [authz::Permission::ResourceAction(
+                authz::Resource::Database(namespace_name.to_string()),
+                authz::Action::Write,
+            )];
+            self.authz
+                .permissions(authz_token.clone(), &perms)
+                .await
+                .map_err(Error::from)?;
+
+            // tickets not created by `GetFlightInfo` do not identify a query
+            let Some(query_handle) = query_handle else {
+                continue;
+            };
+
+            cancelled |= self
+                .query_canceller(namespace_name, span_ctx.clone())
+                .await?
+                .cancel_query_handle(query_handle);
+        }
+
+        let status = if cancelled {
+            CancelStatus::Cancelled
+        } else {
+            CancelStatus::NotCancellable
+        };
+
+        Ok(CancelFlightInfoResult {
+            status: status.into(),
+        }
+        .encode_to_vec()
+        .into())
+    }
+
+    /// Returns the canceller for the queries of the namespace `namespace_name`
+    async fn query_canceller(
+        &self,
+        namespace_name: &str,
+        span_ctx: Option<SpanContext>,
+    ) -> Result<Arc<dyn QueryCanceller>, Error> {
+        let db = self
+            .server
+            .db(namespace_name, span_ctx.child_span("get namespace"), false)
+            .await
+            .context(DatabaseNotFoundSnafu { namespace_name })?;
+
+        let ctx = db.new_query_context(span_ctx);
+        ctx.query_canceller()
+            .map(Arc::clone)
+            .context(QueryCancellationNotSupportedSnafu { namespace_name })
+    }
 }
 
 #[tonic::async_trait]
@@ -632,13 +844,14 @@ where
 
         let perms = match query {
             RunQuery::FlightSQL(cmd) => flightsql_permissions(namespace_name, cmd),
-            RunQuery::Sql(_) | RunQuery::InfluxQL(_) => vec![authz::Permission::ResourceAction(
+            RunQuery::Sql(_) => vec![authz::Permission::ResourceAction(
                 authz::Resource::Database(namespace_name.to_string()),
                 authz::Action::Read,
             )],
+            RunQuery::InfluxQL(sql_query, _) => influxql_permissions(namespace_name, sql_query),
         };
         self.authz
-            .permissions(authz_token, &perms)
+            .permissions(authz_token.clone(), &perms)
             .await
             .map_err(Error::from)?;
 
@@ -661,10 +874,12 @@ where
             .run_do_get(
                 span_ctx,
                 external_span_ctx.clone(),
+                authz_token,
                 permit,
                 query.clone(),
                 namespace_name.to_string(),
                 is_debug,
+                request.query_handle(),
             )
             .await;
 
@@ -794,8 +1009,14 @@ where
         };
         let schema = schema?;
 
+        // Reserve the ID the query is recorded with when it is run, so it can
+        // be cancelled by `CancelFlightInfo`. The ticket only contains an
+        // opaque handle of that ID, so clients cannot choose query IDs.
+        let query_handle = ctx.query_canceller().map(|c| c.reserve_query_handle());
+
         // Form the response ticket (that the client will pass back to DoGet)
         let ticket = IoxGetRequest::new(&namespace_name, RunQuery::FlightSQL(cmd), is_debug)
+            .with_query_handle(query_handle)
             .try_encode()
             .context(InternalCreatingTicketSnafu)?;
 
@@ -813,11 +1034,54 @@ where
 
     async fn do_put(
         &self,
-        _request: Request<Streaming<FlightData>>,
+        request: Request<Streaming<FlightData>>,
     ) -> Result<Response<Self::DoPutStream>, tonic::Status> {
-        info!("Handling flightsql do_put body");
+        let external_span_ctx: Option<RequestLogContext> = request.extensions().get().cloned();
+        let trace = external_span_ctx.format_jaeger();
+
+        let namespace_name = get_flightsql_namespace(request.metadata())?;
+        let authz_token = get_flight_authz(request.metadata());
+        let mut stream = request.into_inner();
 
-        Err(tonic::Status::unimplemented("Not yet implemented: do_put"))
+        // the descriptor is sent with the first message
+        let first = stream.message().await?.context(NoFlightDescriptorSnafu)?;
+        let flight_descriptor = first
+            .flight_descriptor
+            .clone()
+            .context(NoFlightDescriptorSnafu)?;
+
+        // extract the FlightSQL message
+        let cmd = cmd_from_descriptor(flight_descriptor)?;
+        info!(%namespace_name, %cmd, %trace, "DoPut request");
+
+        // Bulk ingest via DoPut is served by the router, which applies the
+        // write through its DML handler stack. Only binding parameters to
+        // prepared statements is supported here.
+        if !matches!(cmd, FlightSQLCommand::CommandPreparedStatementQuery(_)) {
+            return Err(Error::unsupported_message_type(format!(
+                "DoPut with {cmd}, send writes to the router"
+            ))
+            .into());
+        }
+
+        let perms = flightsql_permissions(&namespace_name, &cmd);
+        self.authz
+            .permissions(authz_token, &perms)
+            .await
+            .map_err(Error::from)?;
+
+        let batches: Vec<_> = FlightRecordBatchStream::new_from_flight_data(
+            futures::stream::once(async { Ok(first) }).chain(stream.map_err(FlightError::from)),
+        )
+        .try_collect()
+        .await
+        .map_err(|e| Error::from(flightsql::Error::from(e)))?;
+
+        let app_metadata =
+            FlightSQLPlanner::do_put(&namespace_name, cmd, batches).map_err(Error::from)?;
+
+        let stream = futures::stream::iter([Ok(PutResult { app_metadata })]);
+        Ok(Response::new(stream.boxed()))
     }
 
     async fn do_action(
@@ -829,13 +1093,29 @@ where
         let trace = external_span_ctx.format_jaeger();
         let is_debug = has_debug_header(request.metadata());
 
-        let namespace_name = get_flightsql_namespace(request.metadata())?;
+        let namespace_name = get_flightsql_namespace(request.metadata());
         let authz_token = get_flight_authz(request.metadata());
         let Action {
             r#type: action_type,
             body,
         } = request.into_inner();
 
+        // query cancellation does not use the 'database' header
+        match action_type.as_str() {
+            CANCEL_QUERY_ACTION => {
+                let body = self.run_cancel_query(span_ctx, authz_token, body).await?;
+                return Ok(do_action_response(body));
+            }
+            CANCEL_FLIGHT_INFO_ACTION => {
+                let body = self
+                    .run_cancel_flight_info(span_ctx, authz_token, body)
+                    .await?;
+                return Ok(do_action_response(body));
+            }
+            _ => {}
+        }
+        let namespace_name = namespace_name?;
+
         // extract the FlightSQL message
         let cmd = FlightSQLCommand::try_decode(body).context(FlightSQLSnafu)?;
 
@@ -868,10 +1148,7 @@ where
                 query: format!("{cmd:?}"),
             })?;
 
-        let result = arrow_flight::Result { body };
-        let stream = futures::stream::iter([Ok(result)]);
-
-        Ok(Response::new(stream.boxed()))
+        Ok(do_action_response(body))
     }
 
     async fn list_actions(
@@ -947,10 +1224,63 @@ fn get_flightsql_namespace(metadata: &MetadataMap) -> Result<String> {
 }
 
 /// Retrieve the authorization token associated with the request.
+/// Returns the `DoAction` response with the single result `body`.
+fn do_action_response(body: Bytes) -> Response<TonicStream<arrow_flight::Result>> {
+    let result = arrow_flight::Result { body };
+    let stream = futures::stream::iter([Ok(result)]);
+
+    Response::new(stream.boxed())
+}
+
 fn get_flight_authz(metadata: &MetadataMap) -> Option<Vec<u8>> {
     extract_token(metadata.get("authorization"))
 }
 
+/// Returns the permissions required to run the InfluxQL `query`.
+///
+/// Statements that write or delete data, such as `SELECT ... INTO` and
+/// `DROP MEASUREMENT`, or that cancel queries, such as `KILL QUERY`, also
+/// require write access to the namespace. Statements that read the
+/// measurements of other databases require read access to their namespaces,
+/// and `SELECT ... INTO` statements writing to other databases require write
+/// access to their namespaces.
+fn influxql_permissions(namespace_name: &str, query: &str) -> Vec<authz::Permission> {
+    let resource = authz::Resource::Database(namespace_name.to_string());
+    let mut perms = vec![authz::Permission::ResourceAction(
+        resource.clone(),
+        authz::Action::Read,
+    )];
+    if InfluxQLQueryPlanner::is_write_query(query) {
+        perms.push(authz::Permission::ResourceAction(
+            resource,
+            authz::Action::Write,
+        ));
+    }
+    perms.extend(
+        InfluxQLQueryPlanner::referenced_namespaces(query)
+            .into_iter()
+            .filter(|name| name != namespace_name)
+            .map(|name| {
+                authz::Permission::ResourceAction(
+                    authz::Resource::Database(name),
+                    authz::Action::Read,
+                )
+            }),
+    );
+    perms.extend(
+        InfluxQLQueryPlanner::into_namespaces(namespace_name, query)
+            .into_iter()
+            .filter(|name| name != namespace_name)
+            .map(|name| {
+                authz::Permission::ResourceAction(
+                    authz::Resource::Database(name),
+                    authz::Action::Write,
+                )
+            }),
+    );
+    perms
+}
+
 fn flightsql_permissions(namespace_name: &str, cmd: &FlightSQLCommand) -> Vec<authz::Permission> {
     let resource = authz::Resource::Database(namespace_name.to_string());
     let action = match cmd {
@@ -982,56 +1312,236 @@ fn has_debug_header(metadata: &MetadataMap) -> bool {
         .unwrap_or_default()
 }
 
-/// Wrapper over a FlightDataEncodeStream that adds IOx specfic
-/// metadata and records completion
+/// Execute `physical_plan` and encode the results as a stream of
+/// [`FlightData`], with IOx specific metadata.
+async fn execute_plan(
+    ctx: &IOxSessionContext,
+    physical_plan: Arc<dyn ExecutionPlan>,
+    namespace_name: &str,
+    query: &RunQuery,
+) -> Result<FlightDataEncoder, tonic::Status> {
+    let app_metadata = proto::AppMetadata {};
+
+    let schema = physical_plan.schema();
+
+    let query_results = ctx
+        .execute_stream(Arc::clone(&physical_plan))
+        .await
+        .context(QuerySnafu {
+            namespace_name,
+            query: query.to_string(),
+        })?
+        .map_err(|e| {
+            let code = datafusion_error_to_tonic_code(&e);
+            tonic::Status::new(code, e.to_string()).into()
+        });
+
+    Ok(FlightDataEncoderBuilder::new()
+        .with_schema(schema)
+        .with_metadata(app_metadata.encode_to_vec().into())
+        .build(query_results))
+}
+
+/// Plan and execute each of the InfluxQL `statements` in order, encoding the
+/// results of each statement as a separate partition of the [`FlightData`]
+/// stream.
+///
+/// Each partition starts with its own schema message, with the index of the
+/// statement in the [`INFLUXQL_STATEMENT_ID_KEY`] metadata. A statement that
+/// fails to plan or execute ends its partition with a schema message with no
+/// fields, carrying the error message in the [`INFLUXQL_STATEMENT_ERROR_KEY`]
+/// metadata. As in InfluxDB 1.x, the remaining statements are still executed,
+/// and the stream then ends with the error returned by
+/// [`StatementErrors::into_status`].
+fn influxql_statements_stream(
+    ctx: IOxSessionContext,
+    statements: Vec<String>,
+    params: StatementParams,
+) -> impl Stream<Item = Result<FlightData, FlightError>> + Send + 'static {
+    let n_statements = statements.len();
+    let errors = Arc::new(Mutex::new(StatementErrors::default()));
+
+    let results = {
+        let errors = Arc::clone(&errors);
+        futures::stream::iter(statements.into_iter().enumerate())
+            .then(move |(statement_id, statement)| {
+                let ctx = ctx.child_ctx("influxql statement");
+                let params = params.clone();
+                let errors = Arc::clone(&errors);
+                async move {
+                    let res = async {
+                        let plan = Planner::new(&ctx).influxql(statement, params).await?;
+                        let schema = plan.schema();
+                        let query_results = ctx.execute_stream(plan).await?;
+                        Ok::<_, DataFusionError>((schema, query_results))
+                    }
+                    .await;
+
+                    let (schema, query_results) = match res {
+                        Ok(v) => v,
+                        Err(e) => {
+                            let code = datafusion_error_to_tonic_code(&e);
+                            let data = errors.lock().expect("not poisoned").record(
+                                statement_id,
+                                code,
+                                e.to_string(),
+                            );
+                            return futures::stream::once(async { Ok::<_, FlightError>(data) })
+                                .boxed();
+                        }
+                    };
+
+                    let mut metadata = schema.metadata().clone();
+                    metadata.insert(
+                        INFLUXQL_STATEMENT_ID_KEY.to_string(),
+                        statement_id.to_string(),
+                    );
+                    let schema = Arc::new(arrow::datatypes::Schema::new_with_metadata(
+                        schema.fields().clone(),
+                        metadata,
+                    ));
+
+                    let app_metadata = proto::AppMetadata {};
+                    FlightDataEncoderBuilder::new()
+                        .with_schema(schema)
+                        .with_metadata(app_metadata.encode_to_vec().into())
+                        .build(query_results.map_err(|e| FlightError::ExternalError(Box::new(e))))
+                        // Report the first error executing the statement and
+                        // stop encoding its results.
+                        .scan(false, move |failed, res| {
+                            let res = match res {
+                                _ if *failed => None,
+                                Ok(data) => Some(Ok(data)),
+                                Err(e) => {
+                                    *failed = true;
+                                    let (code, msg) = match e {
+                                        FlightError::ExternalError(e) => (
+                                            e.downcast_ref::<DataFusionError>()
+                                                .map(datafusion_error_to_tonic_code)
+                                                .unwrap_or(tonic::Code::Internal),
+                                            e.to_string(),
+                                        ),
+                                        FlightError::Tonic(status) => {
+                                            (status.code(), status.message().to_string())
+                                        }
+                                        e => (tonic::Code::Internal, e.to_string()),
+                                    };
+                                    let data = errors.lock().expect("not poisoned").record(
+                                        statement_id,
+                                        code,
+                                        msg,
+                                    );
+                                    Some(Ok(data))
+                                }
+                            };
+                            futures::future::ready(res)
+                        })
+                        .boxed()
+                }
+            })
+            .flatten()
+    };
+
+    // Once all statements are executed, fail the request if any of them
+    // failed, so that clients unaware of the per-statement errors do not
+    // mistake the results for a success.
+    let status = futures::stream::once(async move {
+        let errors = std::mem::take(&mut *errors.lock().expect("not poisoned"));
+        errors.into_status(n_statements)
+    })
+    .filter_map(|status| futures::future::ready(status.map(|s| Err(FlightError::Tonic(s)))));
+
+    results.chain(status)
+}
+
+/// The InfluxQL statements of a query that failed.
+#[derive(Debug, Default)]
+struct StatementErrors {
+    /// The number of statements that failed.
+    count: usize,
+    /// The index, error code and error message of the first statement that
+    /// failed.
+    first: Option<(usize, tonic::Code, String)>,
+}
+
+impl StatementErrors {
+    /// Record that the statement `statement_id` failed with the error
+    /// message `error`, returning the schema message with no fields that
+    /// reports it.
+    fn record(&mut self, statement_id: usize, code: tonic::Code, error: String) -> FlightData {
+        info!(statement_id, %error, "Error executing InfluxQL statement via DoGet");
+
+        let metadata = HashMap::from([
+            (
+                INFLUXQL_STATEMENT_ID_KEY.to_string(),
+                statement_id.to_string(),
+            ),
+            (INFLUXQL_STATEMENT_ERROR_KEY.to_string(), error.clone()),
+        ]);
+        let schema = arrow::datatypes::Schema::new_with_metadata(Fields::empty(), metadata);
+
+        self.count += 1;
+        self.first.get_or_insert((statement_id, code, error));
+
+        SchemaAsIpc::new(&schema, &IpcWriteOptions::default()).into()
+    }
+
+    /// Return the error ending the results of a query with `n_statements`
+    /// statements, if any of them failed.
+    ///
+    /// The error has the code of the first statement that failed, and the
+    /// number of failed statements in the
+    /// [`INFLUXQL_STATEMENT_ERRORS_STATUS_KEY`] metadata.
+    fn into_status(self, n_statements: usize) -> Option<tonic::Status> {
+        let (statement_id, code, error) = self.first?;
+        let mut status = tonic::Status::new(
+            code,
+            format!(
+                "{} of {} InfluxQL statements failed, first failed statement {}: {}",
+                self.count, n_statements, statement_id, error
+            ),
+        );
+        status
+            .metadata_mut()
+            .insert(INFLUXQL_STATEMENT_ERRORS_STATUS_KEY, self.count.into());
+        Some(status)
+    }
+}
+
+/// Wrapper over a [`FlightData`] stream that adds keep alive messages and
+/// records completion
 struct GetStream {
     inner: KeepAliveStream,
     #[allow(dead_code)]
     permit: InstrumentedAsyncOwnedSemaphorePermit,
     query_completed_token: QueryCompletedToken,
+    /// Resolves when the query is cancelled.
+    cancelled: BoxFuture<'static, ()>,
     done: bool,
 }
 
 impl GetStream {
-    async fn new(
-        ctx: IOxSessionContext,
-        physical_plan: Arc<dyn ExecutionPlan>,
-        namespace_name: String,
-        query: &RunQuery,
+    fn new<S>(
+        inner: S,
         query_completed_token: QueryCompletedToken,
         permit: InstrumentedAsyncOwnedSemaphorePermit,
-    ) -> Result<Self, tonic::Status> {
-        let app_metadata = proto::AppMetadata {};
-
-        let schema = physical_plan.schema();
-
-        let query_results = ctx
-            .execute_stream(Arc::clone(&physical_plan))
-            .await
-            .context(QuerySnafu {
-                namespace_name: namespace_name.clone(),
-                query: query.to_string(),
-            })?
-            .map_err(|e| {
-                let code = datafusion_error_to_tonic_code(&e);
-                tonic::Status::new(code, e.to_string()).into()
-            });
-
-        // setup inner stream
-        let inner = FlightDataEncoderBuilder::new()
-            .with_schema(schema)
-            .with_metadata(app_metadata.encode_to_vec().into())
-            .build(query_results);
-
+    ) -> Self
+    where
+        S: Stream<Item = Result<FlightData, FlightError>> + Send + 'static,
+    {
         // add keep alive
         let inner = KeepAliveStream::new(inner, DO_GET_KEEP_ALIVE_INTERVAL);
 
-        Ok(Self {
+        let cancellation = query_completed_token.cancellation_token().clone();
+        let cancelled = async move { cancellation.cancelled().await }.boxed();
+
+        Self {
             inner,
             permit,
             query_completed_token,
+            cancelled,
             done: false,
-        })
+        }
     }
 }
 
@@ -1047,6 +1557,11 @@ impl Stream for GetStream {
                 return Poll::Ready(None);
             }
 
+            if self.cancelled.poll_unpin(cx).is_ready() {
+                self.done = true;
+                return Poll::Ready(Some(Err(tonic::Status::cancelled("Query cancelled"))));
+            }
+
             let res = ready!(self.inner.poll_next_unpin(cx));
             match res {
                 None => {
@@ -1214,6 +1729,56 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_influxql_multiple_statements() {
+        let test_storage = Arc::new(TestDatabaseStore::default());
+        test_storage.db_or_create("bananas").await;
+
+        let service = FlightService {
+            server: Arc::clone(&test_storage),
+            authz: Option::<Arc<dyn Authorizer>>::None,
+        };
+        let query = RunQuery::InfluxQL(
+            "SHOW DATABASES; SHOW DATABASES".to_string(),
+            Default::default(),
+        );
+        let ticket = IoxGetRequest::new("bananas".to_string(), query, false)
+            .try_encode()
+            .unwrap();
+        let mut data: Vec<Result<FlightData, tonic::Status>> = service
+            .do_get(tonic::Request::new(ticket))
+            .await
+            .unwrap()
+            .into_inner()
+            .collect()
+            .await;
+
+        // Once all statements are executed, the stream ends with an error
+        // reporting the number of failed statements.
+        let status = data.pop().unwrap().unwrap_err();
+        assert_eq!(
+            status.metadata().get(INFLUXQL_STATEMENT_ERRORS_STATUS_KEY),
+            Some(&AsciiMetadataValue::from_static("2"))
+        );
+        assert!(status
+            .message()
+            .starts_with("2 of 2 InfluxQL statements failed, first failed statement 0: "));
+
+        // An error in the first statement does not prevent the second
+        // statement from being executed, and each error is reported by a
+        // schema message with no fields.
+        assert_eq!(data.len(), 2);
+        for (statement_id, data) in data.into_iter().enumerate() {
+            let schema = arrow::datatypes::Schema::try_from(&data.unwrap()).unwrap();
+            assert!(schema.fields().is_empty());
+            assert_eq!(
+                schema.metadata()[INFLUXQL_STATEMENT_ID_KEY],
+                statement_id.to_string()
+            );
+            assert!(schema.metadata().contains_key(INFLUXQL_STATEMENT_ERROR_KEY));
+        }
+    }
+
     /// Assert that given future is pending.
     ///
     /// This will try to poll the future a bit to ensure that it is not stuck in tokios task preemption.
@@ -1250,6 +1815,16 @@ mod tests {
             match token {
                 Some(token) => match (&token as &dyn AsRef<[u8]>).as_ref() {
                     b"GOOD" => Ok(perms.to_vec()),
+                    b"READONLY" => {
+                        let write = perms.iter().any(|p| {
+                            matches!(p, Permission::ResourceAction(_, authz::Action::Write))
+                        });
+                        if write {
+                            Err(authz::Error::Forbidden)
+                        } else {
+                            Ok(perms.to_vec())
+                        }
+                    }
                     b"BAD" => Err(authz::Error::Forbidden),
                     b"INVALID" => Err(authz::Error::InvalidToken),
                     b"UGLY" => Err(authz::Error::verification("test", "test error")),
@@ -1306,7 +1881,7 @@ mod tests {
 
         fn influxql_request(authorization: &'static str) -> tonic::Request<arrow_flight::Ticket> {
             request(
-                RunQuery::InfluxQL("SHOW DATABASES".to_string()),
+                RunQuery::InfluxQL("SHOW DATABASES".to_string(), Default::default()),
                 authorization,
             )
         }
@@ -1368,6 +1943,67 @@ mod tests {
         .await;
     }
 
+    #[test]
+    fn test_influxql_permissions() {
+        let read = authz::Permission::ResourceAction(
+            authz::Resource::Database("bananas".to_string()),
+            authz::Action::Read,
+        );
+        let write = authz::Permission::ResourceAction(
+            authz::Resource::Database("bananas".to_string()),
+            authz::Action::Write,
+        );
+
+        assert_eq!(
+            influxql_permissions("bananas", "SELECT * FROM cpu"),
+            vec![read.clone()]
+        );
+        assert_eq!(
+            influxql_permissions("bananas", "DROP MEASUREMENT cpu"),
+            vec![read.clone(), write.clone()]
+        );
+        assert_eq!(
+            influxql_permissions("bananas", "SHOW DATABASES; DELETE FROM cpu"),
+            vec![read.clone(), write.clone()]
+        );
+        assert_eq!(
+            influxql_permissions("bananas", "KILL QUERY 36"),
+            vec![read.clone(), write.clone()]
+        );
+        assert_eq!(
+            influxql_permissions("bananas", "SELECT * INTO rollup FROM cpu"),
+            vec![read.clone(), write.clone()]
+        );
+        assert_eq!(
+            influxql_permissions("bananas", "SELECT * INTO platanos.rp0.rollup FROM cpu"),
+            vec![
+                read.clone(),
+                write,
+                authz::Permission::ResourceAction(
+                    authz::Resource::Database("platanos/rp0".to_string()),
+                    authz::Action::Write,
+                ),
+            ]
+        );
+        assert_eq!(
+            influxql_permissions(
+                "bananas",
+                "SELECT * FROM bananas..cpu, platanos.rp0.cpu; SHOW TAG KEYS ON platanos"
+            ),
+            vec![
+                read,
+                authz::Permission::ResourceAction(
+                    authz::Resource::Database("platanos".to_string()),
+                    authz::Action::Read,
+                ),
+                authz::Permission::ResourceAction(
+                    authz::Resource::Database("platanos/rp0".to_string()),
+                    authz::Action::Read,
+                ),
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn get_flight_info_authz() {
         let test_storage = Arc::new(TestDatabaseStore::default());
@@ -1412,4 +2048,140 @@ mod tests {
         assert_code(&svc, tonic::Code::PermissionDenied, request("Bearer BAD")).await;
         assert_code(&svc, tonic::Code::Internal, request("Bearer UGLY")).await;
     }
+    #[tokio::test]
+    async fn do_action_cancel_authz() {
+        let test_storage = Arc::new(TestDatabaseStore::default());
+        test_storage.db_or_create("bananas").await;
+
+        let svc = FlightService {
+            server: Arc::clone(&test_storage),
+            authz: Some(Arc::new(MockAuthorizer {})),
+        };
+
+        async fn assert_code(
+            svc: &FlightService<TestDatabaseStore>,
+            want: tonic::Code,
+            request: tonic::Request<Action>,
+        ) {
+            let got = match svc.do_action(request).await {
+                Ok(_) => tonic::Code::Ok,
+                Err(e) => e.code(),
+            };
+            assert_eq!(want, got);
+        }
+
+        fn request(
+            action_type: &str,
+            body: Vec<u8>,
+            authorization: &'static str,
+        ) -> tonic::Request<Action> {
+            let mut req = tonic::Request::new(Action {
+                r#type: action_type.to_string(),
+                body: body.into(),
+            });
+            if !authorization.is_empty() {
+                req.metadata_mut().insert(
+                    MetadataKey::from_static("authorization"),
+                    MetadataValue::from_static(authorization),
+                );
+            }
+            req
+        }
+
+        fn cancel_query_request(authorization: &'static str) -> tonic::Request<Action> {
+            let body = proto::CancelQueryRequest {
+                database: "bananas".to_string(),
+                query_id: 1,
+            };
+            request(CANCEL_QUERY_ACTION, body.encode_to_vec(), authorization)
+        }
+
+        fn cancel_flight_info_request(
+            authorization: &'static str,
+            query_handle: Option<u64>,
+        ) -> tonic::Request<Action> {
+            let ticket = IoxGetRequest::new(
+                "bananas".to_string(),
+                RunQuery::Sql("SELECT 1".to_string()),
+                false,
+            )
+            .with_query_handle(query_handle)
+            .try_encode()
+            .unwrap();
+            let body = CancelFlightInfoRequest {
+                info: Some(
+                    FlightInfo::new().with_endpoint(FlightEndpoint::new().with_ticket(ticket)),
+                ),
+            };
+            request(
+                CANCEL_FLIGHT_INFO_ACTION,
+                body.encode_to_vec(),
+                authorization,
+            )
+        }
+
+        // the test database does not support query cancellation
+        assert_code(&svc, tonic::Code::Unauthenticated, cancel_query_request("")).await;
+        assert_code(
+            &svc,
+            tonic::Code::Unimplemented,
+            cancel_query_request("Bearer GOOD"),
+        )
+        .await;
+        assert_code(
+            &svc,
+            tonic::Code::PermissionDenied,
+            cancel_query_request("Bearer BAD"),
+        )
+        .await;
+        assert_code(
+            &svc,
+            tonic::Code::InvalidArgument,
+            request(CANCEL_QUERY_ACTION, b"not protobuf".to_vec(), "Bearer GOOD"),
+        )
+        .await;
+
+        assert_code(
+            &svc,
+            tonic::Code::Unauthenticated,
+            cancel_flight_info_request("", Some(1)),
+        )
+        .await;
+        assert_code(
+            &svc,
+            tonic::Code::Unimplemented,
+            cancel_flight_info_request("Bearer GOOD", Some(1)),
+        )
+        .await;
+        assert_code(
+            &svc,
+            tonic::Code::PermissionDenied,
+            cancel_flight_info_request("Bearer BAD", Some(1)),
+        )
+        .await;
+        // cancelling a query by its ID requires write access
+        assert_code(
+            &svc,
+            tonic::Code::PermissionDenied,
+            cancel_flight_info_request("Bearer READONLY", Some(1)),
+        )
+        .await;
+        // tickets without a query handle do not identify a query to cancel
+        assert_code(
+            &svc,
+            tonic::Code::Ok,
+            cancel_flight_info_request("Bearer GOOD", None),
+        )
+        .await;
+        assert_code(
+            &svc,
+            tonic::Code::InvalidArgument,
+            request(
+                CANCEL_FLIGHT_INFO_ACTION,
+                CancelFlightInfoRequest { info: None }.encode_to_vec(),
+                "Bearer GOOD",
+            ),
+        )
+        .await;
+    }
 }
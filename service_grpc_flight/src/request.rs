@@ -5,11 +5,14 @@ use bytes::Bytes;
 use flightsql::FlightSQLCommand;
 use generated_types::google::protobuf::Any;
 use generated_types::influxdata::iox::querier::v1 as proto;
+use generated_types::influxdata::iox::querier::v1::query_param_value::Value as ParamValue;
 use generated_types::influxdata::iox::querier::v1::read_info::QueryType;
+use iox_query_influxql::params::{StatementParam, StatementParams};
 use observability_deps::tracing::trace;
 use prost::Message;
 use serde::Deserialize;
 use snafu::{ResultExt, Snafu};
+use std::collections::HashMap;
 use std::fmt::{Debug, Display, Formatter};
 
 #[derive(Debug, Snafu)]
@@ -90,19 +93,34 @@ enum AnyError {
 ///   "query_type": "influxql"
 /// }
 /// ```
+///
+/// InfluxQL queries may contain bind parameters, with the values passed
+/// in `params`
+///
+/// ```json
+/// {
+///   "database": "my_db",
+///   "sql_query": "SELECT * FROM cpu WHERE host = $host;"
+///   "query_type": "influxql",
+///   "params": {"host": "server01"}
+/// }
+/// ```
 #[derive(Debug, PartialEq, Clone)]
 pub struct IoxGetRequest {
     database: String,
     query: RunQuery,
     is_debug: bool,
+    /// Handle of the ID reserved for the query in the query log, see
+    /// [`with_query_handle`](Self::with_query_handle).
+    query_handle: Option<u64>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum RunQuery {
     /// Unparameterized SQL query
     Sql(String),
-    /// InfluxQL
-    InfluxQL(String),
+    /// InfluxQL, with the values of any bind parameters
+    InfluxQL(String, StatementParams),
     /// Execute a FlightSQL command. The payload is an encoded
     /// FlightSQL Command*. message that was received at the
     /// get_flight_info endpoint
@@ -113,7 +131,7 @@ impl RunQuery {
     pub fn variant(&self) -> &'static str {
         match self {
             Self::Sql(_) => "sql",
-            Self::InfluxQL(_) => "influxql",
+            Self::InfluxQL(_, _) => "influxql",
             Self::FlightSQL(_) => "flightsql",
         }
     }
@@ -123,7 +141,7 @@ impl Display for RunQuery {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Sql(s) => Display::fmt(s, f),
-            Self::InfluxQL(s) => Display::fmt(s, f),
+            Self::InfluxQL(s, _) => Display::fmt(s, f),
             Self::FlightSQL(s) => Display::fmt(s, f),
         }
     }
@@ -138,6 +156,17 @@ impl IoxGetRequest {
             database: database.into(),
             query,
             is_debug,
+            query_handle: None,
+        }
+    }
+
+    /// Record the query with the ID identified by `query_handle` in the
+    /// query log when it is executed, so that it can be cancelled using this
+    /// request.
+    pub fn with_query_handle(self, query_handle: Option<u64>) -> Self {
+        Self {
+            query_handle,
+            ..self
         }
     }
 
@@ -174,7 +203,9 @@ impl IoxGetRequest {
             database,
             query,
             is_debug,
+            query_handle,
         } = self;
+        let query_handle = query_handle.unwrap_or_default();
 
         let read_info = match query {
             RunQuery::Sql(sql_query) => proto::ReadInfo {
@@ -183,14 +214,21 @@ impl IoxGetRequest {
                 query_type: QueryType::Sql.into(),
                 flightsql_command: vec![],
                 is_debug,
+                params: HashMap::new(),
+                query_handle,
             },
-            RunQuery::InfluxQL(influxql) => proto::ReadInfo {
+            RunQuery::InfluxQL(influxql, params) => proto::ReadInfo {
                 database,
                 // field name is misleading
                 sql_query: influxql,
                 query_type: QueryType::InfluxQl.into(),
                 flightsql_command: vec![],
                 is_debug,
+                params: params
+                    .iter()
+                    .map(|(name, value)| (name.clone(), encode_param(value)))
+                    .collect(),
+                query_handle,
             },
             RunQuery::FlightSQL(flightsql_command) => proto::ReadInfo {
                 database,
@@ -201,6 +239,8 @@ impl IoxGetRequest {
                     .context(FlightSQLSnafu)?
                     .into(),
                 is_debug,
+                params: HashMap::new(),
+                query_handle,
             },
         };
 
@@ -229,6 +269,8 @@ impl IoxGetRequest {
             query_type: Option<String>,
             #[serde(default = "Default::default")]
             is_debug: bool,
+            #[serde(default = "Default::default")]
+            params: StatementParams,
         }
 
         let ReadInfoJson {
@@ -236,12 +278,17 @@ impl IoxGetRequest {
             sql_query,
             query_type,
             is_debug,
+            params,
         } = serde_json::from_str(&json_str).map_err(|e| format!("JSON parse error: {e}"))?;
 
+        if !params.is_empty() && query_type.as_deref() != Some("influxql") {
+            return Err("params are only supported for InfluxQL queries".to_string());
+        }
+
         let query = if let Some(query_type) = query_type {
             match query_type.as_str() {
                 "sql" => RunQuery::Sql(sql_query),
-                "influxql" => RunQuery::InfluxQL(sql_query),
+                "influxql" => RunQuery::InfluxQL(sql_query, params),
                 _ => {
                     return Err(format!(
                         "unknown query type. Expected 'sql' or 'influxql', got {query_type}'"
@@ -257,6 +304,7 @@ impl IoxGetRequest {
             database,
             query,
             is_debug,
+            query_handle: None,
         })
     }
 
@@ -284,8 +332,17 @@ impl IoxGetRequest {
             query_type: _,
             flightsql_command,
             is_debug,
+            params,
+            query_handle,
         } = read_info;
 
+        if !params.is_empty() && query_type != QueryType::InfluxQl {
+            return InvalidContentSnafu {
+                msg: "params are only supported for QueryType::InfluxQl",
+            }
+            .fail();
+        }
+
         Ok(Self {
             database,
             query: match query_type {
@@ -305,7 +362,14 @@ impl IoxGetRequest {
                         }
                         .fail();
                     }
-                    RunQuery::InfluxQL(sql_query)
+                    let params = params
+                        .into_iter()
+                        .map(|(name, value)| {
+                            let value = decode_param(&name, value)?;
+                            Ok((name, value))
+                        })
+                        .collect::<Result<_>>()?;
+                    RunQuery::InfluxQL(sql_query, params)
                 }
                 QueryType::FlightSqlMessage => {
                     if !sql_query.is_empty() {
@@ -320,6 +384,7 @@ impl IoxGetRequest {
                 }
             },
             is_debug,
+            query_handle: (query_handle != 0).then_some(query_handle),
         })
     }
 
@@ -334,6 +399,39 @@ impl IoxGetRequest {
     pub fn is_debug(&self) -> bool {
         self.is_debug
     }
+
+    pub fn query_handle(&self) -> Option<u64> {
+        self.query_handle
+    }
+}
+
+/// Encode a bind parameter value as protobuf
+fn encode_param(value: &StatementParam) -> proto::QueryParamValue {
+    let value = match value {
+        StatementParam::Boolean(v) => ParamValue::Boolean(*v),
+        StatementParam::Integer(v) => ParamValue::Integer(*v),
+        StatementParam::Unsigned(v) => ParamValue::Unsigned(*v),
+        StatementParam::Float(v) => ParamValue::Float(*v),
+        StatementParam::String(v) => ParamValue::String(v.clone()),
+    };
+    proto::QueryParamValue { value: Some(value) }
+}
+
+/// Decode the protobuf value of the bind parameter `name`
+fn decode_param(name: &str, value: proto::QueryParamValue) -> Result<StatementParam> {
+    Ok(match value.value {
+        Some(ParamValue::Boolean(v)) => StatementParam::Boolean(v),
+        Some(ParamValue::Integer(v)) => StatementParam::Integer(v),
+        Some(ParamValue::Unsigned(v)) => StatementParam::Unsigned(v),
+        Some(ParamValue::Float(v)) => StatementParam::Float(v),
+        Some(ParamValue::String(v)) => StatementParam::String(v),
+        None => {
+            return InvalidContentSnafu {
+                msg: format!("no value for parameter {name}"),
+            }
+            .fail()
+        }
+    })
 }
 
 #[cfg(test)]
@@ -374,6 +472,7 @@ mod tests {
                         database: String::from(expected_database),
                         query: RunQuery::Sql(String::from(query)),
                         is_debug: false,
+                        query_handle: None,
                     },
                 }
             }
@@ -383,8 +482,9 @@ mod tests {
                     json,
                     expected: IoxGetRequest {
                         database: String::from(expected_database),
-                        query: RunQuery::InfluxQL(String::from(query)),
+                        query: RunQuery::InfluxQL(String::from(query), StatementParams::default()),
                         is_debug: false,
+                        query_handle: None,
                     },
                 }
             }
@@ -556,6 +656,44 @@ mod tests {
         assert_matches!(e, Error::Invalid);
     }
 
+    #[test]
+    fn json_ticket_decoding_params() {
+        let ticket = make_json_ticket(
+            r#"{"database": "my_db", "sql_query": "SELECT * FROM cpu WHERE host = $host", "query_type": "influxql", "params": {"host": "server01", "n": 1}}"#,
+        );
+        let ri = IoxGetRequest::try_decode(ticket).unwrap();
+        assert_matches!(ri.query, RunQuery::InfluxQL(_, params) => {
+            assert_eq!(params.get("host"), Some(&StatementParam::String("server01".into())));
+            assert_eq!(params.get("n"), Some(&StatementParam::Integer(1)));
+        });
+
+        // params are not supported for SQL
+        let ticket = make_json_ticket(
+            r#"{"database": "my_db", "sql_query": "SELECT 1", "params": {"host": "server01"}}"#,
+        );
+        let e = IoxGetRequest::try_decode(ticket).unwrap_err();
+        assert_matches!(e, Error::Invalid);
+    }
+
+    #[test]
+    fn proto_ticket_decoding_sql_params() {
+        let ticket = make_any_wrapped_proto_ticket(&proto::ReadInfo {
+            database: "<foo>_<bar>".to_string(),
+            sql_query: "SELECT 1".to_string(),
+            query_type: QueryType::Sql.into(),
+            flightsql_command: vec![],
+            is_debug: false,
+            params: HashMap::from([(
+                "host".to_string(),
+                encode_param(&StatementParam::Boolean(true)),
+            )]),
+            query_handle: 0,
+        });
+
+        let e = IoxGetRequest::try_decode(ticket).unwrap_err();
+        assert_matches!(e, Error::InvalidContent { .. });
+    }
+
     #[test]
     fn proto_ticket_decoding_unspecified() {
         let ticket = make_proto_ticket(&proto::ReadInfo {
@@ -564,6 +702,8 @@ mod tests {
             query_type: QueryType::Unspecified.into(),
             flightsql_command: vec![],
             is_debug: false,
+            params: HashMap::new(),
+            query_handle: 0,
         });
 
         // Reverts to default (unspecified) for invalid query_type enumeration, and thus SQL
@@ -580,6 +720,8 @@ mod tests {
             query_type: QueryType::Sql.into(),
             flightsql_command: vec![],
             is_debug: false,
+            params: HashMap::new(),
+            query_handle: 0,
         });
 
         let ri = IoxGetRequest::try_decode(ticket).unwrap();
@@ -595,11 +737,13 @@ mod tests {
             query_type: QueryType::InfluxQl.into(),
             flightsql_command: vec![],
             is_debug: false,
+            params: HashMap::new(),
+            query_handle: 0,
         });
 
         let ri = IoxGetRequest::try_decode(ticket).unwrap();
         assert_eq!(ri.database, "<foo>_<bar>");
-        assert_matches!(ri.query, RunQuery::InfluxQL(query) => assert_eq!(query, "SELECT 1"));
+        assert_matches!(ri.query, RunQuery::InfluxQL(query, _) => assert_eq!(query, "SELECT 1"));
     }
 
     #[test]
@@ -610,6 +754,8 @@ mod tests {
             query_type: 42, // not a known query type
             flightsql_command: vec![],
             is_debug: false,
+            params: HashMap::new(),
+            query_handle: 0,
         });
 
         // Reverts to default (unspecified) for invalid query_type enumeration, and thus SQL
@@ -627,6 +773,8 @@ mod tests {
             // can't have both sql_query and flightsql
             flightsql_command: vec![1, 2, 3],
             is_debug: false,
+            params: HashMap::new(),
+            query_handle: 0,
         });
 
         let e = IoxGetRequest::try_decode(ticket).unwrap_err();
@@ -642,6 +790,8 @@ mod tests {
             // can't have both sql_query and flightsql
             flightsql_command: vec![1, 2, 3],
             is_debug: false,
+            params: HashMap::new(),
+            query_handle: 0,
         });
 
         let e = IoxGetRequest::try_decode(ticket).unwrap_err();
@@ -657,6 +807,8 @@ mod tests {
             // can't have both sql_query and flightsql
             flightsql_command: vec![1, 2, 3],
             is_debug: false,
+            params: HashMap::new(),
+            query_handle: 0,
         });
 
         let e = IoxGetRequest::try_decode(ticket).unwrap_err();
@@ -682,6 +834,8 @@ mod tests {
             query_type: QueryType::Unspecified.into(),
             flightsql_command: vec![],
             is_debug: false,
+            params: HashMap::new(),
+            query_handle: 0,
         });
 
         // Reverts to default (unspecified) for invalid query_type enumeration, and thus SQL
@@ -698,6 +852,8 @@ mod tests {
             query_type: QueryType::Sql.into(),
             flightsql_command: vec![],
             is_debug: false,
+            params: HashMap::new(),
+            query_handle: 0,
         });
 
         let ri = IoxGetRequest::try_decode(ticket).unwrap();
@@ -713,11 +869,13 @@ mod tests {
             query_type: QueryType::InfluxQl.into(),
             flightsql_command: vec![],
             is_debug: false,
+            params: HashMap::new(),
+            query_handle: 0,
         });
 
         let ri = IoxGetRequest::try_decode(ticket).unwrap();
         assert_eq!(ri.database, "<foo>_<bar>");
-        assert_matches!(ri.query, RunQuery::InfluxQL(query) => assert_eq!(query, "SELECT 1"));
+        assert_matches!(ri.query, RunQuery::InfluxQL(query, _) => assert_eq!(query, "SELECT 1"));
     }
 
     #[test]
@@ -728,6 +886,8 @@ mod tests {
             query_type: 42, // not a known query type
             flightsql_command: vec![],
             is_debug: false,
+            params: HashMap::new(),
+            query_handle: 0,
         });
 
         // Reverts to default (unspecified) for invalid query_type enumeration, and thus SQL
@@ -745,6 +905,8 @@ mod tests {
             // can't have both sql_query and flightsql
             flightsql_command: vec![1, 2, 3],
             is_debug: false,
+            params: HashMap::new(),
+            query_handle: 0,
         });
 
         let e = IoxGetRequest::try_decode(ticket).unwrap_err();
@@ -760,6 +922,8 @@ mod tests {
             // can't have both sql_query and flightsql
             flightsql_command: vec![1, 2, 3],
             is_debug: false,
+            params: HashMap::new(),
+            query_handle: 0,
         });
 
         let e = IoxGetRequest::try_decode(ticket).unwrap_err();
@@ -775,6 +939,8 @@ mod tests {
             // can't have both sql_query and flightsql
             flightsql_command: vec![1, 2, 3],
             is_debug: false,
+            params: HashMap::new(),
+            query_handle: 0,
         });
 
         let e = IoxGetRequest::try_decode(ticket).unwrap_err();
@@ -797,6 +963,7 @@ mod tests {
             database: "foo_blarg".into(),
             query: RunQuery::Sql("select * from bar".into()),
             is_debug: false,
+            query_handle: None,
         };
 
         let ticket = request.clone().try_encode().expect("encoding failed");
@@ -812,6 +979,7 @@ mod tests {
             database: "foo_blarg".into(),
             query: RunQuery::Sql("select * from bar".into()),
             is_debug: true,
+            query_handle: None,
         };
 
         let ticket = request.clone().try_encode().expect("encoding failed");
@@ -825,8 +993,15 @@ mod tests {
     fn round_trip_influxql() {
         let request = IoxGetRequest {
             database: "foo_blarg".into(),
-            query: RunQuery::InfluxQL("select * from bar".into()),
+            query: RunQuery::InfluxQL(
+                "select * from bar where host = $host".into(),
+                StatementParams::from_iter([
+                    ("host".to_string(), StatementParam::String("a".into())),
+                    ("value".to_string(), StatementParam::Float(1.5)),
+                ]),
+            ),
             is_debug: false,
+            query_handle: None,
         };
 
         let ticket = request.clone().try_encode().expect("encoding failed");
@@ -847,6 +1022,7 @@ mod tests {
             database: "foo_blarg".into(),
             query: RunQuery::FlightSQL(cmd),
             is_debug: false,
+            query_handle: Some(42),
         };
 
         let ticket = request.clone().try_encode().expect("encoding failed");
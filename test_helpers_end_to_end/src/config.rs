@@ -207,6 +207,19 @@ impl TestConfig {
         )
     }
 
+    /// Configure the router that the querier writes the results of
+    /// `SELECT ... INTO` queries to.
+    pub fn with_router_address(self, router_config: &Self) -> Self {
+        self.with_env(
+            "INFLUXDB_IOX_ROUTER_ADDRESS",
+            router_config
+                .addrs()
+                .router_http_api()
+                .client_base()
+                .to_string(),
+        )
+    }
+
     pub fn with_rpc_write_replicas(self, rpc_write_replicas: NonZeroUsize) -> Self {
         self.with_env(
             "INFLUXDB_IOX_RPC_WRITE_REPLICAS",
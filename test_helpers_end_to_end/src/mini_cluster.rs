@@ -252,8 +252,9 @@ impl MiniCluster {
         let ingester_config = TestConfig::new_ingester(&database_url);
         let router_config =
             TestConfig::new_router(&ingester_config).with_single_tenancy(authz_addr.clone());
-        let querier_config =
-            TestConfig::new_querier(&ingester_config).with_single_tenancy(authz_addr);
+        let querier_config = TestConfig::new_querier(&ingester_config)
+            .with_single_tenancy(authz_addr)
+            .with_router_address(&router_config);
         let compactor_config = TestConfig::new_compactor(&ingester_config);
 
         // Set up the cluster  ====================================
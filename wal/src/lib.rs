@@ -967,6 +967,7 @@ mod tests {
             database_id: TEST_NAMESPACE_ID.get(),
             predicate: None,
             table_name: "bananas".into(),
+            table_id: 44,
         }
     }
 